@@ -1,17 +1,25 @@
-use std::io::Error as IoError;
+use std::io::{Error as IoError, ErrorKind};
 use thiserror::Error;
 
+use fluvio_types::secret::SecretError;
+
 /// Possible errors from Auth
 #[derive(Error, Debug)]
 pub enum AuthError {
     #[error("IoError: {0}")]
     IoError(#[from] IoError),
+    #[error("Invalid token: {0}")]
+    InvalidToken(String),
+    #[error("Secret: {0}")]
+    Secret(#[from] SecretError),
 }
 
 impl From<AuthError> for IoError {
     fn from(e: AuthError) -> Self {
         match &e {
             AuthError::IoError(source) => IoError::new(source.kind(), e),
+            AuthError::InvalidToken(_) => IoError::new(ErrorKind::PermissionDenied, e),
+            AuthError::Secret(_) => IoError::new(ErrorKind::InvalidInput, e),
         }
     }
 }
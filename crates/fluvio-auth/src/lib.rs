@@ -2,6 +2,9 @@ mod policy;
 mod error;
 
 pub mod x509;
+pub mod token;
+pub mod tls;
+pub mod secret;
 
 pub use policy::*;
 pub use error::AuthError;
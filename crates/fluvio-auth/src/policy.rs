@@ -34,6 +34,11 @@ pub trait AuthContext: Debug {
         action: InstanceAction,
         key: &str,
     ) -> Result<bool, AuthError>;
+
+    /// authenticated principal, none if requests are not authenticated
+    fn principal(&self) -> Option<&str> {
+        None
+    }
 }
 
 #[async_trait]
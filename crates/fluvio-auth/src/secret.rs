@@ -0,0 +1,122 @@
+//! Secret providers for SC and SPU.
+//!
+//! Extends built-in `env`, `file` and `k8s` providers from [`fluvio_types::secret`]
+//! with HashiCorp Vault.
+
+use std::sync::Arc;
+
+use clap::Args;
+use serde_json::Value;
+use tracing::debug;
+
+use fluvio_types::secret::{SecretError, SecretProvider, SecretRef, SecretResolver, VAULT_PROVIDER};
+
+/// Secret options shared by SC and SPU
+#[derive(Debug, Default, Clone, PartialEq, Eq, Args)]
+pub struct SecretOpt {
+    /// Vault server address, enables `vault:<path>#<field>` secrets
+    #[arg(long, value_name = "url", env = "VAULT_ADDR")]
+    pub vault_addr: Option<String>,
+
+    /// secret with Vault token, e.g. `file:/var/run/secrets/vault/token`
+    #[arg(
+        long,
+        value_name = "secret",
+        env = "FLV_VAULT_TOKEN_SECRET",
+        default_value = "env:VAULT_TOKEN"
+    )]
+    pub vault_token: Option<SecretRef>,
+}
+
+impl SecretOpt {
+    /// resolver with built-in providers and Vault, if configured
+    pub fn resolver(&self) -> SecretResolver {
+        let resolver = SecretResolver::default();
+        match (&self.vault_addr, &self.vault_token) {
+            (Some(addr), Some(token)) => resolver.with_provider(
+                VAULT_PROVIDER,
+                Arc::new(VaultSecretProvider::new(addr, token.clone())),
+            ),
+            _ => resolver,
+        }
+    }
+}
+
+/// Reads field of Vault KV version 2 secret.
+/// Name is `<mount>/<path>#<field>`, e.g. `secret/fluvio/tls#key`
+#[derive(Debug)]
+pub struct VaultSecretProvider {
+    addr: String,
+    token: SecretRef,
+}
+
+impl VaultSecretProvider {
+    pub fn new(addr: impl Into<String>, token: SecretRef) -> Self {
+        Self {
+            addr: addr.into(),
+            token,
+        }
+    }
+
+    /// KV v2 api url for secret path
+    fn url(&self, path: &str) -> Option<String> {
+        let (mount, path) = path.split_once('/')?;
+        Some(format!(
+            "{}/v1/{mount}/data/{path}",
+            self.addr.trim_end_matches('/')
+        ))
+    }
+}
+
+impl SecretProvider for VaultSecretProvider {
+    fn get_secret(&self, name: &str) -> Result<String, SecretError> {
+        let invalid = || SecretError::InvalidRef(format!("{VAULT_PROVIDER}:{name}"));
+        let (path, field) = name.split_once('#').ok_or_else(invalid)?;
+        let url = self.url(path).ok_or_else(invalid)?;
+
+        // token is resolved on every read to pick up rotated tokens
+        let token = self.token.resolve()?;
+        debug!(%url, "reading vault secret");
+        let body = match ureq::get(&url).set("X-Vault-Token", &token).call() {
+            Ok(response) => response
+                .into_string()
+                .map_err(|err| SecretError::Provider(err.to_string()))?,
+            Err(ureq::Error::Status(404, _)) => return Err(SecretError::NotFound(name.to_owned())),
+            Err(err) => return Err(SecretError::Provider(err.to_string())),
+        };
+        kv_field(&body, field).ok_or_else(|| SecretError::NotFound(name.to_owned()))
+    }
+}
+
+/// extract field from KV v2 response: `{ "data": { "data": { <field>: <value> } } }`
+fn kv_field(body: &str, field: &str) -> Option<String> {
+    let response: Value = serde_json::from_str(body).ok()?;
+    match response.get("data")?.get("data")?.get(field)? {
+        Value::String(value) => Some(value.to_owned()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vault_url() {
+        let vault =
+            VaultSecretProvider::new("https://vault:8200/", SecretRef::new("env", "VAULT_TOKEN"));
+        assert_eq!(
+            vault.url("secret/fluvio/tls").as_deref(),
+            Some("https://vault:8200/v1/secret/data/fluvio/tls")
+        );
+        assert!(vault.url("secret").is_none());
+    }
+
+    #[test]
+    fn test_vault_kv_field() {
+        let body = r#"{ "data": { "data": { "key": "pem", "size": 1 }, "metadata": {} } }"#;
+        assert_eq!(kv_field(body, "key").as_deref(), Some("pem"));
+        assert!(kv_field(body, "size").is_none());
+        assert!(kv_field(body, "missing").is_none());
+    }
+}
@@ -0,0 +1,185 @@
+//! TLS termination with live certificate rotation.
+//!
+//! Certificate and key files are polled for changes and the acceptor is rebuilt when they
+//! are replaced, e.g. by cert-manager. New handshakes use the new certificate while
+//! established connections are not affected.
+
+mod proxy;
+
+pub use proxy::start_proxy;
+
+use std::fmt;
+use std::io::Error as IoError;
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, SystemTime};
+
+use tracing::{debug, info, warn};
+
+use fluvio_future::openssl::TlsAcceptor;
+use fluvio_future::task::spawn;
+use fluvio_future::timer::sleep;
+
+/// how often certificate files are checked for changes
+pub const CERT_RELOAD_INTERVAL: Duration = Duration::from_secs(10);
+
+pub type SharedTlsAcceptor = Arc<ReloadableTlsAcceptor>;
+
+type BuildAcceptor = Box<dyn Fn() -> Result<TlsAcceptor, IoError> + Send + Sync>;
+
+/// modification time and length of file, none if file is missing
+type FileStamp = Option<(Option<SystemTime>, u64)>;
+
+struct LoadedAcceptor {
+    acceptor: TlsAcceptor,
+    stamps: Vec<FileStamp>,
+}
+
+/// TLS acceptor which is rebuilt when any of watched files change
+pub struct ReloadableTlsAcceptor {
+    files: Vec<PathBuf>,
+    build: BuildAcceptor,
+    loaded: RwLock<LoadedAcceptor>,
+}
+
+impl fmt::Debug for ReloadableTlsAcceptor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReloadableTlsAcceptor")
+            .field("files", &self.files)
+            .finish()
+    }
+}
+
+impl ReloadableTlsAcceptor {
+    /// build acceptor, `build` is called again whenever one of `files` changes
+    pub fn shared<F>(files: Vec<PathBuf>, build: F) -> Result<SharedTlsAcceptor, IoError>
+    where
+        F: Fn() -> Result<TlsAcceptor, IoError> + Send + Sync + 'static,
+    {
+        // stamp before build so change in between is picked up by next check
+        let stamps = stamp_files(&files);
+        let acceptor = build()?;
+        Ok(Arc::new(Self {
+            files,
+            build: Box::new(build),
+            loaded: RwLock::new(LoadedAcceptor { acceptor, stamps }),
+        }))
+    }
+
+    /// acceptor for new connection
+    pub fn acceptor(&self) -> TlsAcceptor {
+        self.loaded
+            .read()
+            .expect("tls acceptor lock poisoned")
+            .acceptor
+            .clone()
+    }
+
+    /// rebuild acceptor if files have changed since last load.
+    /// On error, current acceptor is kept and reload is attempted again on next check.
+    pub fn reload_if_changed(&self) -> Result<bool, IoError> {
+        let stamps = stamp_files(&self.files);
+        if self
+            .loaded
+            .read()
+            .map(|loaded| loaded.stamps == stamps)
+            .unwrap_or(false)
+        {
+            return Ok(false);
+        }
+
+        let acceptor = (self.build)()?;
+        if let Ok(mut loaded) = self.loaded.write() {
+            *loaded = LoadedAcceptor { acceptor, stamps };
+        }
+        info!(files = ?self.files, "TLS certificates reloaded");
+        Ok(true)
+    }
+
+    /// check files for changes every `interval` until acceptor is dropped
+    pub fn watch(self: &Arc<Self>, interval: Duration) {
+        let acceptor = Arc::downgrade(self);
+        spawn(async move {
+            debug!(?interval, "watching TLS certificates");
+            loop {
+                sleep(interval).await;
+                let Some(acceptor) = acceptor.upgrade() else {
+                    break;
+                };
+                if let Err(err) = acceptor.reload_if_changed() {
+                    warn!(%err, "unable to reload TLS certificates, keeping current");
+                }
+            }
+        });
+    }
+}
+
+fn stamp_files(files: &[PathBuf]) -> Vec<FileStamp> {
+    files
+        .iter()
+        .map(|path| {
+            std::fs::metadata(path)
+                .ok()
+                .map(|meta| (meta.modified().ok(), meta.len()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+
+    const CERTS: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../fluvio-socket/certs/certs");
+
+    fn build_acceptor(cert: &Path, key: &Path) -> Result<TlsAcceptor, IoError> {
+        Ok(TlsAcceptor::builder()
+            .map_err(|err| err.into_io_error())?
+            .with_certifiate_and_key_from_pem_files(cert, key)
+            .map_err(|err| err.into_io_error())?
+            .build())
+    }
+
+    fn copy_pair(dir: &Path, name: &str) {
+        std::fs::copy(
+            Path::new(CERTS).join(format!("{name}.crt")),
+            dir.join("tls.crt"),
+        )
+        .expect("copy cert");
+        std::fs::copy(
+            Path::new(CERTS).join(format!("{name}.key")),
+            dir.join("tls.key"),
+        )
+        .expect("copy key");
+    }
+
+    #[test]
+    fn test_reload_on_change() {
+        let dir = std::env::temp_dir().join(format!("fluvio-tls-reload-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("dir");
+        copy_pair(&dir, "server");
+
+        let cert = dir.join("tls.crt");
+        let key = dir.join("tls.key");
+        let acceptor = {
+            let (cert, key) = (cert.clone(), key.clone());
+            ReloadableTlsAcceptor::shared(vec![cert.clone(), key.clone()], move || {
+                build_acceptor(&cert, &key)
+            })
+            .expect("initial acceptor")
+        };
+        assert!(!acceptor.reload_if_changed().expect("unchanged"));
+
+        copy_pair(&dir, "client");
+        assert!(acceptor.reload_if_changed().expect("reloaded"));
+        assert!(!acceptor.reload_if_changed().expect("unchanged"));
+
+        // broken certificate keeps previous acceptor and is retried
+        std::fs::write(&cert, "not a certificate").expect("write");
+        assert!(acceptor.reload_if_changed().is_err());
+        assert!(acceptor.reload_if_changed().is_err());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,89 @@
+use std::io::{Error as IoError, ErrorKind};
+use std::sync::Arc;
+
+use futures_util::future::{select, Either};
+use futures_util::io::{copy, AsyncReadExt};
+use futures_util::pin_mut;
+use futures_util::stream::StreamExt;
+use tracing::{debug, error, info, trace};
+
+use fluvio_future::net::{TcpListener, TcpStream};
+use fluvio_future::openssl::TlsAcceptor;
+use fluvio_future::task::spawn;
+use flv_tls_proxy::authenticator::Authenticator;
+
+use super::SharedTlsAcceptor;
+
+/// Terminate TLS on `addr` and forward traffic to plain `target`.
+/// Each handshake uses the acceptor current at the time connection is accepted.
+pub async fn start_proxy(
+    addr: &str,
+    acceptor: SharedTlsAcceptor,
+    target: String,
+    authenticator: Option<Arc<dyn Authenticator>>,
+) -> Result<(), IoError> {
+    let listener = TcpListener::bind(addr).await?;
+    info!(addr, %target, "TLS proxy started");
+
+    let mut incoming = listener.incoming();
+    while let Some(stream) = incoming.next().await {
+        match stream {
+            Ok(stream) => {
+                let tls_acceptor = acceptor.acceptor();
+                let target = target.clone();
+                let authenticator = authenticator.clone();
+                spawn(async move {
+                    if let Err(err) =
+                        process_stream(tls_acceptor, stream, target, authenticator).await
+                    {
+                        debug!(%err, "TLS proxy connection terminated");
+                    }
+                });
+            }
+            Err(err) => error!(%err, "error accepting TLS connection"),
+        }
+    }
+
+    Ok(())
+}
+
+async fn process_stream(
+    acceptor: TlsAcceptor,
+    stream: TcpStream,
+    target: String,
+    authenticator: Option<Arc<dyn Authenticator>>,
+) -> Result<(), IoError> {
+    let source = stream
+        .peer_addr()
+        .map(|addr| addr.to_string())
+        .unwrap_or_default();
+
+    let tls_stream = acceptor
+        .accept(stream)
+        .await
+        .map_err(|err| IoError::new(ErrorKind::ConnectionAborted, err.to_string()))?;
+    let target_stream = TcpStream::connect(&target).await?;
+
+    if let Some(authenticator) = authenticator {
+        if !authenticator
+            .authenticate(&tls_stream, &target_stream)
+            .await?
+        {
+            debug!(%source, "TLS proxy authentication failed");
+            return Ok(());
+        }
+    }
+    trace!(%source, %target, "TLS proxy connection established");
+
+    let (mut tls_read, mut tls_write) = tls_stream.split();
+    let mut target_read = target_stream.clone();
+    let mut target_write = target_stream;
+
+    let upstream = copy(&mut tls_read, &mut target_write);
+    let downstream = copy(&mut target_read, &mut tls_write);
+    pin_mut!(upstream, downstream);
+
+    match select(upstream, downstream).await {
+        Either::Left((result, _)) | Either::Right((result, _)) => result.map(|_| ()),
+    }
+}
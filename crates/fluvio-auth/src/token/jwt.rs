@@ -0,0 +1,260 @@
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use jsonwebtoken::jwk::JwkSet;
+use jsonwebtoken::{decode, decode_header, Algorithm, DecodingKey, Validation};
+use serde::Deserialize;
+use tracing::{debug, instrument};
+
+use fluvio_future::http_client::{self, ResponseExt};
+
+use crate::x509::X509Identity;
+use crate::AuthError;
+
+use super::TokenValidator;
+
+/// keys are refreshed at least this often to pick up rotation
+const JWKS_MAX_AGE: Duration = Duration::from_secs(300);
+/// unknown `kid` triggers refresh, but not more often than this
+const JWKS_MIN_REFRESH: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Deserialize)]
+struct Claims {
+    sub: String,
+    /// OAuth2 style space separated scopes
+    #[serde(default)]
+    scope: Option<String>,
+    #[serde(default)]
+    scopes: Vec<String>,
+}
+
+impl Claims {
+    fn into_identity(self) -> X509Identity {
+        let mut scopes = self.scopes;
+        if let Some(scope) = self.scope {
+            scopes.extend(scope.split_whitespace().map(|s| s.to_owned()));
+        }
+        X509Identity::new(self.sub, scopes)
+    }
+}
+
+#[derive(Debug)]
+struct CachedKeys {
+    fetched: Instant,
+    keys: JwkSet,
+}
+
+/// Validates JWT signed by key published at JWKS endpoint.
+/// Token must be issued for `audience` and signed with one of configured algorithms,
+/// algorithm in token header is not trusted.
+/// Principal is taken from `sub`, scopes from `scope` or `scopes` claim.
+#[derive(Debug)]
+pub struct JwtValidator {
+    jwks_url: String,
+    issuer: Option<String>,
+    audience: String,
+    algorithms: Vec<Algorithm>,
+    cache: RwLock<Option<CachedKeys>>,
+}
+
+impl JwtValidator {
+    pub fn new(
+        jwks_url: impl Into<String>,
+        audience: impl Into<String>,
+        algorithms: Vec<Algorithm>,
+    ) -> Self {
+        Self {
+            jwks_url: jwks_url.into(),
+            issuer: None,
+            audience: audience.into(),
+            algorithms,
+            cache: RwLock::new(None),
+        }
+    }
+
+    pub fn with_issuer(mut self, issuer: impl Into<String>) -> Self {
+        self.issuer = Some(issuer.into());
+        self
+    }
+
+    /// return cached keys if they are fresh and contain `kid`
+    fn cached_keys(&self, kid: Option<&str>) -> Option<JwkSet> {
+        let cache = self.cache.read().ok()?;
+        let cached = cache.as_ref()?;
+        let age = cached.fetched.elapsed();
+        let has_key = match kid {
+            Some(kid) => cached.keys.find(kid).is_some(),
+            None => true,
+        };
+        if age < JWKS_MAX_AGE && (has_key || age < JWKS_MIN_REFRESH) {
+            Some(cached.keys.clone())
+        } else {
+            None
+        }
+    }
+
+    #[instrument(skip(self), fields(url = %self.jwks_url))]
+    async fn fetch_keys(&self) -> Result<JwkSet, AuthError> {
+        let body = http_client::get(&self.jwks_url)
+            .await
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err.to_string()))?
+            .bytes()
+            .await
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err.to_string()))?;
+        let keys: JwkSet = serde_json::from_slice(&body).map_err(std::io::Error::from)?;
+        debug!(keys = keys.keys.len(), "fetched jwks");
+
+        if let Ok(mut cache) = self.cache.write() {
+            *cache = Some(CachedKeys {
+                fetched: Instant::now(),
+                keys: keys.clone(),
+            });
+        }
+        Ok(keys)
+    }
+
+    fn verify(&self, token: &str, keys: &JwkSet) -> Result<X509Identity, AuthError> {
+        let header = decode_header(token).map_err(invalid)?;
+        if !self.algorithms.contains(&header.alg) {
+            return Err(AuthError::InvalidToken(format!(
+                "algorithm {:?} is not allowed",
+                header.alg
+            )));
+        }
+
+        let jwk = match &header.kid {
+            Some(kid) => keys.find(kid),
+            None => keys.keys.first(),
+        }
+        .ok_or_else(|| AuthError::InvalidToken("signing key not found".to_owned()))?;
+        let key = DecodingKey::from_jwk(jwk).map_err(invalid)?;
+
+        let mut validation = Validation::new(header.alg);
+        validation.algorithms.clone_from(&self.algorithms);
+        validation.set_audience(&[&self.audience]);
+        validation.set_required_spec_claims(&["exp", "aud"]);
+        if let Some(issuer) = &self.issuer {
+            validation.set_issuer(&[issuer]);
+        }
+
+        let data = decode::<Claims>(token, &key, &validation).map_err(invalid)?;
+        Ok(data.claims.into_identity())
+    }
+}
+
+fn invalid(err: jsonwebtoken::errors::Error) -> AuthError {
+    AuthError::InvalidToken(err.to_string())
+}
+
+#[async_trait]
+impl TokenValidator for JwtValidator {
+    async fn validate(&self, token: &str) -> Result<X509Identity, AuthError> {
+        let header = decode_header(token).map_err(invalid)?;
+        let keys = match self.cached_keys(header.kid.as_deref()) {
+            Some(keys) => keys,
+            None => self.fetch_keys().await?,
+        };
+        self.verify(token, &keys)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jsonwebtoken::{encode, Algorithm, EncodingKey, Header};
+    use serde::Serialize;
+
+    use super::*;
+
+    const SECRET: &str = "fluvio-token-test-secret-of-48-bytes-for-hs256!!";
+
+    // base64url of SECRET
+    const JWKS: &str = r#"{
+        "keys": [
+            {
+                "kty": "oct",
+                "kid": "test-key",
+                "alg": "HS256",
+                "k": "Zmx1dmlvLXRva2VuLXRlc3Qtc2VjcmV0LW9mLTQ4LWJ5dGVzLWZvci1oczI1NiEh"
+            }
+        ]
+    }"#;
+
+    #[derive(Serialize)]
+    struct TestClaims<'a> {
+        sub: &'a str,
+        iss: &'a str,
+        aud: &'a str,
+        exp: u64,
+        scope: &'a str,
+    }
+
+    fn token(kid: &str, iss: &str) -> String {
+        let exp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            + 600;
+        let mut header = Header::new(Algorithm::HS256);
+        header.kid = Some(kid.to_owned());
+        let claims = TestClaims {
+            sub: "alice",
+            iss,
+            aud: "fluvio",
+            exp,
+            scope: "read write",
+        };
+        encode(
+            &header,
+            &claims,
+            &EncodingKey::from_secret(SECRET.as_bytes()),
+        )
+        .expect("encode")
+    }
+
+    #[test]
+    fn test_verify_jwt() {
+        let keys: JwkSet = serde_json::from_str(JWKS).expect("jwks");
+        let validator =
+            JwtValidator::new("http://localhost/jwks", "fluvio", vec![Algorithm::HS256])
+                .with_issuer("https://issuer.example");
+
+        let identity = validator
+            .verify(&token("test-key", "https://issuer.example"), &keys)
+            .expect("valid token");
+        assert_eq!(identity.principal, "alice");
+        assert_eq!(
+            identity.scopes(),
+            &vec!["read".to_owned(), "write".to_owned()]
+        );
+
+        assert!(matches!(
+            validator.verify(&token("test-key", "https://other.example"), &keys),
+            Err(AuthError::InvalidToken(_))
+        ));
+        assert!(matches!(
+            validator.verify(&token("rotated-key", "https://issuer.example"), &keys),
+            Err(AuthError::InvalidToken(_))
+        ));
+        assert!(matches!(
+            validator.verify("not-a-jwt", &keys),
+            Err(AuthError::InvalidToken(_))
+        ));
+
+        // algorithm is pinned by configuration, not taken from token
+        let rs256_only =
+            JwtValidator::new("http://localhost/jwks", "fluvio", vec![Algorithm::RS256])
+                .with_issuer("https://issuer.example");
+        assert!(matches!(
+            rs256_only.verify(&token("test-key", "https://issuer.example"), &keys),
+            Err(AuthError::InvalidToken(_))
+        ));
+
+        let other_audience =
+            JwtValidator::new("http://localhost/jwks", "other", vec![Algorithm::HS256]);
+        assert!(matches!(
+            other_audience.verify(&token("test-key", "https://issuer.example"), &keys),
+            Err(AuthError::InvalidToken(_))
+        ));
+    }
+}
@@ -0,0 +1,238 @@
+//! Bearer token authentication.
+//!
+//! When a token validator is configured, the first message on a public connection
+//! must be an [`AuthTokenRequest`]. The token is resolved into an [`X509Identity`]
+//! which is then used by the authorization policy the same way as a certificate identity.
+
+mod jwt;
+mod static_tokens;
+
+pub use jwt::JwtValidator;
+pub use static_tokens::StaticTokenValidator;
+
+use std::fmt::Debug;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use clap::Args;
+use futures_util::stream::StreamExt;
+use jsonwebtoken::Algorithm;
+use tracing::{debug, trace};
+
+use fluvio_protocol::api::{api_decode, ApiMessage, RequestHeader, RequestMessage, ResponseMessage};
+use fluvio_protocol::bytes::Buf;
+use fluvio_protocol::link::auth_token::{AuthTokenRequest, AuthTokenResponse, AUTH_TOKEN_API_KEY};
+use fluvio_socket::{FluvioSocket, SocketError};
+
+use fluvio_types::secret::SecretRef;
+
+use crate::secret::SecretOpt;
+use crate::x509::X509Identity;
+use crate::AuthError;
+
+pub type SharedTokenValidator = Arc<dyn TokenValidator>;
+
+/// Resolves bearer token into identity
+#[async_trait]
+pub trait TokenValidator: Debug + Send + Sync {
+    async fn validate(&self, token: &str) -> Result<X509Identity, AuthError>;
+}
+
+/// Token authentication options shared by SC and SPU
+#[derive(Debug, Default, Clone, PartialEq, Eq, Args)]
+pub struct TokenAuthOpt {
+    /// JSON file mapping static tokens to identities
+    #[arg(
+        long,
+        value_name = "path",
+        env = "FLV_AUTH_TOKENS",
+        conflicts_with_all = ["auth_jwks_url", "auth_tokens_secret"]
+    )]
+    pub auth_tokens: Option<PathBuf>,
+
+    /// secret with JSON mapping static tokens to identities, e.g. `vault:secret/fluvio#tokens`
+    #[arg(
+        long,
+        value_name = "secret",
+        env = "FLV_AUTH_TOKENS_SECRET",
+        conflicts_with = "auth_jwks_url"
+    )]
+    pub auth_tokens_secret: Option<SecretRef>,
+
+    /// JWKS endpoint used to verify JWT tokens
+    #[arg(
+        long,
+        value_name = "url",
+        env = "FLV_AUTH_JWKS_URL",
+        requires = "auth_jwt_audience"
+    )]
+    pub auth_jwks_url: Option<String>,
+
+    /// expected issuer (`iss`) of JWT tokens
+    #[arg(
+        long,
+        value_name = "issuer",
+        env = "FLV_AUTH_JWT_ISSUER",
+        requires = "auth_jwks_url"
+    )]
+    pub auth_jwt_issuer: Option<String>,
+
+    /// expected audience (`aud`) of JWT tokens, required with JWKS endpoint
+    #[arg(
+        long,
+        value_name = "audience",
+        env = "FLV_AUTH_JWT_AUDIENCE",
+        requires = "auth_jwks_url"
+    )]
+    pub auth_jwt_audience: Option<String>,
+
+    /// signing algorithms accepted for JWT tokens
+    #[arg(
+        long,
+        value_name = "algorithm",
+        env = "FLV_AUTH_JWT_ALGORITHMS",
+        value_delimiter = ',',
+        default_value = "RS256",
+        value_parser = parse_algorithm
+    )]
+    pub auth_jwt_algorithms: Vec<Algorithm>,
+
+    #[command(flatten)]
+    pub secrets: SecretOpt,
+}
+
+impl TokenAuthOpt {
+    /// build validator if token authentication is configured
+    pub fn validator(&self) -> Result<Option<SharedTokenValidator>, AuthError> {
+        if let Some(path) = &self.auth_tokens {
+            let validator = StaticTokenValidator::load(path)?;
+            return Ok(Some(Arc::new(validator)));
+        }
+
+        if let Some(secret) = &self.auth_tokens_secret {
+            let tokens = self.secrets.resolver().resolve(secret)?;
+            let validator = StaticTokenValidator::from_json(&tokens)?;
+            debug!(%secret, "static tokens loaded from secret");
+            return Ok(Some(Arc::new(validator)));
+        }
+
+        if let Some(url) = &self.auth_jwks_url {
+            let audience = self.auth_jwt_audience.as_ref().ok_or_else(|| {
+                std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    "JWT audience must be configured with JWKS endpoint",
+                )
+            })?;
+            let algorithms = if self.auth_jwt_algorithms.is_empty() {
+                vec![Algorithm::RS256]
+            } else {
+                self.auth_jwt_algorithms.clone()
+            };
+            let mut validator = JwtValidator::new(url, audience, algorithms);
+            if let Some(issuer) = &self.auth_jwt_issuer {
+                validator = validator.with_issuer(issuer);
+            }
+            return Ok(Some(Arc::new(validator)));
+        }
+
+        Ok(None)
+    }
+}
+
+#[derive(Debug)]
+pub enum AuthTokenApiRequest {
+    AuthTokenRequest(RequestMessage<AuthTokenRequest>),
+}
+
+// Added to satisfy Encoder/Decoder traits
+impl Default for AuthTokenApiRequest {
+    fn default() -> AuthTokenApiRequest {
+        AuthTokenApiRequest::AuthTokenRequest(RequestMessage::default())
+    }
+}
+
+impl ApiMessage for AuthTokenApiRequest {
+    type ApiKey = u16;
+
+    fn decode_with_header<T>(src: &mut T, header: RequestHeader) -> Result<Self, std::io::Error>
+    where
+        Self: Default + Sized,
+        Self::ApiKey: Sized,
+        T: Buf,
+    {
+        match header.api_key() {
+            AUTH_TOKEN_API_KEY => api_decode!(AuthTokenApiRequest, AuthTokenRequest, src, header),
+            _ => Err(std::io::Error::new(
+                std::io::ErrorKind::PermissionDenied,
+                format!("auth token request (key {AUTH_TOKEN_API_KEY}) is required"),
+            )),
+        }
+    }
+}
+
+/// Read token request from the connection and validate it.
+/// Response is sent back to client on both success and failure.
+pub async fn authenticate(
+    socket: &mut FluvioSocket,
+    validator: &dyn TokenValidator,
+) -> Result<X509Identity, AuthError> {
+    let req_msg = {
+        let mut api_stream = socket
+            .get_mut_stream()
+            .api_stream::<AuthTokenApiRequest, _>();
+
+        match api_stream.next().await {
+            Some(Ok(AuthTokenApiRequest::AuthTokenRequest(req_msg))) => req_msg,
+            Some(Err(err)) => {
+                debug!(%err, "invalid auth token request");
+                return Err(into_io_error(err).into());
+            }
+            None => {
+                trace!("client connect terminated");
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::Interrupted,
+                    "connection closed",
+                )
+                .into());
+            }
+        }
+    };
+
+    let result = validator.validate(&req_msg.request.token).await;
+    let response = match &result {
+        Ok(identity) => {
+            debug!(principal = %identity.principal, "token authenticated");
+            AuthTokenResponse::default()
+        }
+        Err(err) => {
+            debug!(%err, "token rejected");
+            AuthTokenResponse::denied(err.to_string())
+        }
+    };
+
+    let msg = ResponseMessage::from_header(&req_msg.header, response);
+    socket
+        .get_mut_sink()
+        .send_response(&msg, req_msg.header.api_version())
+        .await
+        .map_err(into_io_error)?;
+
+    result
+}
+
+fn parse_algorithm(value: &str) -> Result<Algorithm, String> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| format!("unknown JWT algorithm {value}"))
+}
+
+fn into_io_error(err: SocketError) -> std::io::Error {
+    match err {
+        SocketError::Io { source, .. } => source,
+        SocketError::SocketClosed | SocketError::SocketStale => {
+            std::io::Error::new(std::io::ErrorKind::Interrupted, "connection closed")
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+
+use async_trait::async_trait;
+use tracing::debug;
+
+use crate::x509::X509Identity;
+use crate::AuthError;
+
+use super::TokenValidator;
+
+/// Validates tokens against a fixed set loaded from file.
+///
+/// File format:
+/// ```json
+/// { "<token>": { "principal": "alice", "scopes": ["admin"] } }
+/// ```
+pub struct StaticTokenValidator {
+    tokens: HashMap<String, X509Identity>,
+}
+
+impl fmt::Debug for StaticTokenValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // never print tokens
+        f.debug_struct("StaticTokenValidator")
+            .field("tokens", &self.tokens.len())
+            .finish()
+    }
+}
+
+impl StaticTokenValidator {
+    pub fn load(path: &Path) -> Result<Self, AuthError> {
+        let file = std::fs::read_to_string(path)?;
+        let validator = Self::from_json(&file)?;
+        debug!(tokens = validator.tokens.len(), "static tokens loaded");
+        Ok(validator)
+    }
+
+    pub(crate) fn from_json(content: &str) -> Result<Self, AuthError> {
+        let tokens = serde_json::from_str(content).map_err(std::io::Error::from)?;
+        Ok(Self { tokens })
+    }
+
+    fn lookup(&self, token: &str) -> Result<X509Identity, AuthError> {
+        self.tokens
+            .get(token)
+            .cloned()
+            .ok_or_else(|| AuthError::InvalidToken("unknown token".to_owned()))
+    }
+}
+
+#[async_trait]
+impl TokenValidator for StaticTokenValidator {
+    async fn validate(&self, token: &str) -> Result<X509Identity, AuthError> {
+        self.lookup(token)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOKENS: &str = r#"{
+        "secret-1": { "principal": "alice", "scopes": ["admin"] },
+        "secret-2": { "principal": "bob", "scopes": [] }
+    }"#;
+
+    #[test]
+    fn test_lookup_static_token() {
+        let validator = StaticTokenValidator::from_json(TOKENS).expect("parse");
+
+        let alice = validator.lookup("secret-1").expect("alice");
+        assert_eq!(alice.principal, "alice");
+        assert_eq!(alice.scopes(), &vec!["admin".to_owned()]);
+
+        let bob = validator.lookup("secret-2").expect("bob");
+        assert_eq!(bob.principal, "bob");
+        assert!(bob.scopes().is_empty());
+
+        assert!(matches!(
+            validator.lookup("secret-3"),
+            Err(AuthError::InvalidToken(_))
+        ));
+    }
+
+    #[test]
+    fn test_debug_hides_tokens() {
+        let validator = StaticTokenValidator::from_json(TOKENS).expect("parse");
+        assert!(!format!("{validator:?}").contains("secret"));
+    }
+}
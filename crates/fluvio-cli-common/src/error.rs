@@ -1,4 +1,4 @@
-use semver::Version;
+use semver::{Version, VersionReq};
 use fluvio_index::{PackageId, Target};
 
 #[derive(thiserror::Error, Debug)]
@@ -9,6 +9,29 @@ pub struct PackageNotFound {
     pub target: Target,
 }
 
+#[derive(thiserror::Error, Debug)]
+#[error(
+    "Package {package} {version} requires Fluvio CLI {requirement}, but the CLI is {cli_version}{}",
+    compatible_hint(.compatible)
+)]
+pub struct IncompatiblePackage {
+    pub package: PackageId,
+    pub version: Version,
+    pub requirement: VersionReq,
+    pub cli_version: Version,
+    /// latest release which supports the CLI version
+    pub compatible: Option<Version>,
+}
+
+fn compatible_hint(compatible: &Option<Version>) -> String {
+    match compatible {
+        Some(version) => {
+            format!(", install compatible version {version} with `--version ={version}`")
+        }
+        None => ", no release supports it".to_string(),
+    }
+}
+
 #[derive(thiserror::Error, Debug)]
 pub enum HttpError {
     #[error("Invalid input: {0}")]
@@ -1,21 +1,66 @@
 use http::Request;
 use tracing::instrument;
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 
-use fluvio_future::http_client;
-use fluvio_future::http_client::ResponseExt;
+use fluvio_hub_util::htclient;
+use fluvio_index::{HttpAgent, RequestTimeout};
 
+/// get body of request, within the timeout set on the request by `HttpAgent`
 #[instrument]
 pub async fn get_bytes_req<T: std::fmt::Debug>(req: &Request<T>) -> Result<bytes::Bytes> {
     let uri = req.uri().to_string();
-    get_bytes(&uri).await
+    let resp = match req.extensions().get::<RequestTimeout>() {
+        Some(RequestTimeout(timeout)) => htclient::get_with_timeout(&uri, *timeout).await?,
+        None => htclient::get(&uri).await?,
+    };
+    body_bytes(&uri, resp)
 }
 
+/// get body of index metadata request, revalidating the response cached by `agent`.
+/// The cached body is used if the registry can't be reached.
+#[instrument(skip(agent))]
+pub async fn get_cached_req(agent: &HttpAgent, req: &Request<()>) -> Result<bytes::Bytes> {
+    let mut get = Request::get(req.uri()).body(Vec::<u8>::new())?;
+    *get.headers_mut() = req.headers().clone();
+    let resp = match req.extensions().get::<RequestTimeout>() {
+        Some(RequestTimeout(timeout)) => htclient::send_with_timeout(get, *timeout).await,
+        None => htclient::send(get).await,
+    };
+    let body = agent.cached_body_from_response(req, resp)?;
+    Ok(bytes::Bytes::from(body))
+}
+
+/// get body of uri, using the proxy and CA certificates configured in `htclient`
 #[instrument]
 pub async fn get_bytes(uri: &str) -> Result<bytes::Bytes> {
-    let resp = http_client::get(&uri).await?;
-    let body_bytes = resp.bytes().await?;
-    Ok(body_bytes)
+    let resp = htclient::get(uri).await?;
+    body_bytes(uri, resp)
+}
+
+fn body_bytes(uri: &str, resp: htclient::Response<Vec<u8>>) -> Result<bytes::Bytes> {
+    if !resp.status().is_success() {
+        return Err(anyhow!(
+            "request to {uri} failed with status {}",
+            resp.status()
+        ));
+    }
+    Ok(bytes::Bytes::from(resp.into_body()))
+}
+
+/// status of HEAD request with uri and headers of `req`
+#[instrument]
+pub async fn head_status_req<T: std::fmt::Debug>(req: &Request<T>) -> Result<htclient::StatusCode> {
+    let mut head = Request::head(req.uri()).body(Vec::<u8>::new())?;
+    *head.headers_mut() = req.headers().clone();
+    let resp = htclient::send(head).await?;
+    Ok(resp.status())
+}
+
+#[instrument]
+pub async fn get_simple_req<T: std::fmt::Debug>(req: &Request<T>) -> Result<String> {
+    let body_bytes = get_bytes_req(req).await?;
+    let body = std::str::from_utf8(&body_bytes)?;
+    Ok(body.to_string())
 }
 
 #[instrument]
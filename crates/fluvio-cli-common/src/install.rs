@@ -1,15 +1,19 @@
 use std::fs::File;
 use std::io::{ErrorKind, Error as IoError};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use tracing::{debug, instrument};
 
-use semver::Version;
+use semver::{Version, VersionReq};
 use anyhow::{anyhow, Result};
 
-use fluvio_index::{HttpAgent, PackageId, Target, WithVersion, Package, PackageVersion};
+use fluvio_index::{
+    Artifact, Explanation, HttpAgent, IndexLayout, InstallStrategy, MetaMember, PackageId, Target,
+    WithVersion, Package, PackageVersion,
+};
 
 use crate::FLUVIO_EXTENSIONS_DIR;
-use crate::error::PackageNotFound;
+use crate::error::{IncompatiblePackage, PackageNotFound};
 
 pub const FLUVIO_DIR: &str = "FLUVIO_DIR";
 
@@ -35,6 +39,28 @@ pub fn fluvio_bin_dir() -> Result<PathBuf> {
     Ok(fluvio_base_dir()?.join("bin"))
 }
 
+pub fn fluvio_lib_dir() -> Result<PathBuf> {
+    Ok(fluvio_base_dir()?.join("lib"))
+}
+
+/// Directory of cached package index responses
+pub fn fluvio_index_cache_dir() -> Result<PathBuf> {
+    Ok(fluvio_base_dir()?.join("cache").join("index"))
+}
+
+/// Caches index responses of the agent in [`fluvio_index_cache_dir`].
+///
+/// With `force_refresh`, cached responses are not used.
+pub fn with_index_cache(agent: HttpAgent, force_refresh: bool) -> HttpAgent {
+    match fluvio_index_cache_dir() {
+        Ok(dir) => agent.with_cache_dir(dir).with_force_refresh(force_refresh),
+        Err(err) => {
+            debug!(%err, "index cache directory not available");
+            agent
+        }
+    }
+}
+
 fn fluvio_base_dir_create(path: PathBuf) -> Result<PathBuf> {
     if !path.exists() {
         // Create the base dir if it doesn't exist yet (#718)
@@ -43,6 +69,39 @@ fn fluvio_base_dir_create(path: PathBuf) -> Result<PathBuf> {
     Ok(path)
 }
 
+/// Environment variable overriding the prefix of system-wide installs
+pub const FLUVIO_SYSTEM_PREFIX: &str = "FLUVIO_SYSTEM_PREFIX";
+
+#[cfg(windows)]
+const DEFAULT_SYSTEM_PREFIX: &str = "C:\\ProgramData\\Fluvio";
+#[cfg(not(windows))]
+const DEFAULT_SYSTEM_PREFIX: &str = "/usr/local";
+
+/// Prefix of system-wide installs, shared by all users of the machine
+pub fn fluvio_system_prefix() -> PathBuf {
+    std::env::var_os(FLUVIO_SYSTEM_PREFIX)
+        .filter(|it| !it.is_empty())
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_SYSTEM_PREFIX))
+}
+
+/// Directory of system-wide install state, such as install manifest
+pub fn fluvio_system_dir() -> PathBuf {
+    fluvio_system_prefix().join("lib").join("fluvio")
+}
+
+pub fn fluvio_system_bin_dir() -> PathBuf {
+    fluvio_system_prefix().join("bin")
+}
+
+pub fn fluvio_system_extensions_dir() -> PathBuf {
+    fluvio_system_dir().join("extensions")
+}
+
+pub fn fluvio_system_lib_dir() -> PathBuf {
+    fluvio_system_dir().join("lib")
+}
+
 pub fn fluvio_extensions_dir() -> Result<PathBuf> {
     // Check if FLUVIO_EXTENSIONS_DIR exists for extensions location
     if let Ok(dir_path) = std::env::var(FLUVIO_EXTENSIONS_DIR) {
@@ -61,21 +120,55 @@ pub fn fluvio_extensions_dir() -> Result<PathBuf> {
 // Think about adding check in bin dir for extensions.
 // Add to count by skipping anything that starts with `fluvio-`
 // I'm not sure if this is how things get added to the CLI though
+//
+// Plugins installed per user take precedence over system-wide ones with the same name
 pub fn get_extensions() -> Result<Vec<PathBuf>> {
     use std::fs;
-    let mut extensions = Vec::new();
-    let fluvio_dir = fluvio_extensions_dir()?;
-    if let Ok(entries) = fs::read_dir(fluvio_dir) {
-        for entry in entries.flatten() {
-            let is_plugin = entry.file_name().to_string_lossy().starts_with("fluvio-");
-            if is_plugin {
-                extensions.push(entry.path());
+    let mut extensions: Vec<PathBuf> = Vec::new();
+    for fluvio_dir in [fluvio_extensions_dir()?, fluvio_system_extensions_dir()] {
+        if let Ok(entries) = fs::read_dir(fluvio_dir) {
+            for entry in entries.flatten() {
+                let is_plugin = entry.file_name().to_string_lossy().starts_with("fluvio-");
+                let is_shadowed = extensions
+                    .iter()
+                    .any(|it| it.file_name() == Some(entry.file_name().as_os_str()));
+                if is_plugin && !is_shadowed {
+                    extensions.push(entry.path());
+                }
             }
         }
     }
     Ok(extensions)
 }
 
+/// Returns an agent for the newest index layout served by the registry.
+///
+/// The `v2` endpoint is probed first, and the agent falls back to `v1` if it can't
+/// be reached or its metadata doesn't list the `v2` layout.
+#[instrument(skip(agent), fields(prefix = agent.base_url()))]
+pub async fn discover_index_layout(agent: HttpAgent) -> HttpAgent {
+    let probe = async {
+        let v2 = agent.with_layout(IndexLayout::V2)?;
+        let body = crate::http::get_cached_req(&v2, &v2.request_index()?).await?;
+        let index = v2.index_from_response(&body).await?;
+        anyhow::Ok(index.metadata.serves_layout(IndexLayout::V2).then_some(v2))
+    };
+    match probe.await {
+        Ok(Some(v2)) => {
+            debug!(base_url = v2.base_url(), "using v2 index layout");
+            v2
+        }
+        Ok(None) => {
+            debug!("v2 index layout not served, using v1");
+            agent.with_layout(IndexLayout::V1).unwrap_or(agent)
+        }
+        Err(err) => {
+            debug!(%err, "v2 index layout not available, using v1");
+            agent.with_layout(IndexLayout::V1).unwrap_or(agent)
+        }
+    }
+}
+
 /// Fetches the latest version of the package with the given ID
 #[instrument(
     skip(agent, target, id),
@@ -87,16 +180,147 @@ pub async fn fetch_latest_version<T>(
     target: &Target,
     prerelease: bool,
 ) -> Result<Version> {
-    let request = agent.request_package(id)?;
-    let uri = request.uri().to_string();
-    let body = crate::http::get_simple(&uri).await?;
-    debug!(%uri, %body, "uri parsing version");
-    let package: Package = serde_json::from_str(&body)?;
+    let package = fetch_package(agent, id).await?;
     let rel = package.latest_release_for_target(target, false)?;
+    package.release_kind(rel)?;
     let ver = rel.version.clone();
     Ok(ver)
 }
 
+/// Fetches the latest version of the package matching the version requirement
+#[instrument(
+    skip(agent, target, id, req),
+    fields(%target, %req, id = %id.pretty())
+)]
+pub async fn fetch_matching_version<T>(
+    agent: &HttpAgent,
+    id: &PackageId<T>,
+    target: &Target,
+    req: &VersionReq,
+) -> Result<Version> {
+    let package = fetch_package(agent, id).await?;
+    let rel = package.latest_release_matching(target, req)?;
+    package.release_kind(rel)?;
+    Ok(rel.version.clone())
+}
+
+/// Fetches the version the channel of the package points to
+#[instrument(
+    skip(agent, target, id),
+    fields(%target, id = %id.pretty())
+)]
+pub async fn fetch_channel_version<T>(
+    agent: &HttpAgent,
+    id: &PackageId<T>,
+    target: &Target,
+    channel: &str,
+) -> Result<Version> {
+    let package = fetch_package(agent, id).await?;
+    let rel = package.release_for_channel(channel, target)?;
+    package.release_kind(rel)?;
+    Ok(rel.version.clone())
+}
+
+/// Explains which release of the package is resolved, latest matching `req` if given,
+/// and why newer releases were passed over
+#[instrument(
+    skip(agent, target, id, req),
+    fields(%target, id = %id.pretty())
+)]
+pub async fn explain_version_resolution<T>(
+    agent: &HttpAgent,
+    id: &PackageId<T>,
+    target: &Target,
+    req: Option<&VersionReq>,
+    prerelease: bool,
+) -> Result<Explanation> {
+    let package = fetch_package(agent, id).await?;
+    let (_, explanation) = match req {
+        Some(req) => package.explain_latest_release_matching(target, req),
+        None => package.explain_latest_release_for_target(target, prerelease),
+    };
+    Ok(explanation)
+}
+
+/// Fetches how the release of the package is installed, based on its kind
+#[instrument(
+    skip(agent, id),
+    fields(id = %id.pretty())
+)]
+pub async fn fetch_install_strategy<T>(
+    agent: &HttpAgent,
+    id: &PackageId<T>,
+    version: &Version,
+) -> Result<InstallStrategy> {
+    let package = fetch_package(agent, id).await?;
+    let release = package
+        .release(version)
+        .ok_or_else(|| fluvio_index::Error::MissingRelease(version.clone()))?;
+    Ok(package.install_strategy(release)?)
+}
+
+/// Checks the release of the package supports the CLI version
+///
+/// Returns [`IncompatiblePackage`] error naming the latest compatible release, if the release
+/// declares a range of CLI versions which doesn't include `cli_version`.
+#[instrument(
+    skip(agent, id, target),
+    fields(%target, id = %id.pretty())
+)]
+pub async fn check_cli_compatibility<T>(
+    agent: &HttpAgent,
+    id: &PackageId<T>,
+    version: &Version,
+    target: &Target,
+    prerelease: bool,
+    cli_version: &Version,
+) -> Result<()> {
+    let package = fetch_package(agent, id).await?;
+    let Some(requirement) = package
+        .release(version)
+        .filter(|release| !release.supports_cli(cli_version))
+        .and_then(|release| release.fluvio_cli.clone())
+    else {
+        return Ok(());
+    };
+    let compatible = package
+        .latest_compatible_release(target, prerelease, cli_version)
+        .map(|release| release.version.clone());
+    Err(IncompatiblePackage {
+        package: package.package_id(),
+        version: version.clone(),
+        requirement,
+        cli_version: cli_version.clone(),
+        compatible,
+    }
+    .into())
+}
+
+/// Fetches the packages of the latest release of a meta-package, with the version of the release
+#[instrument(
+    skip(agent, id),
+    fields(id = %id.pretty())
+)]
+pub async fn fetch_meta_members<T>(
+    agent: &HttpAgent,
+    id: &PackageId<T>,
+    prerelease: bool,
+) -> Result<(Version, Vec<MetaMember>)> {
+    let package = fetch_package(agent, id).await?;
+    let release = package.latest_meta_release(prerelease)?;
+    Ok((release.version.clone(), release.packages.clone()))
+}
+
+async fn fetch_package<T>(agent: &HttpAgent, id: &PackageId<T>) -> Result<Package> {
+    let request = agent.request_package(id)?;
+    let uri = request.uri().to_string();
+    let body = crate::http::get_cached_req(agent, &request).await?;
+    let body = std::str::from_utf8(&body)?;
+    debug!(%uri, %body, "uri parsing version");
+    let package: Package = serde_json::from_str(body)?;
+    Ok(package)
+}
+
 /// Downloads and verifies a package file via it's versioned ID and target
 #[instrument(
     skip(agent, id, target),
@@ -112,7 +336,7 @@ pub async fn fetch_package_file(
         PackageVersion::Semver(version) => version.clone(),
         PackageVersion::Tag(tag) => {
             let req = agent.request_tag(id, tag)?;
-            let tag_response = crate::http::get_bytes_req(&req).await?;
+            let tag_response = crate::http::get_cached_req(agent, &req).await?;
             agent.tag_version_from_response(tag, &tag_response).await?
         }
         _ => return Err(anyhow!("unknown PackageVersion type")),
@@ -132,12 +356,20 @@ pub async fn fetch_package_file(
             }
         })?;
 
+    // Prefer the checksum recorded in the index, older releases only publish a checksum file
+    let package = fetch_package(agent, id).await?;
+    if let Some(release) = package
+        .release(&version)
+        .filter(|release| release.digest(target).is_some())
+    {
+        let package_file = agent.download_verified(release, target, &package_file)?;
+        debug!("Verified checksum from index");
+        return Ok(package_file);
+    }
+
     // Download the package checksum from the package registry
-    let checksum_request = agent
-        .request_release_checksum(id, &version, target)?
-        .uri()
-        .to_string();
-    let package_checksum = crate::http::get_simple(&checksum_request).await?;
+    let checksum_request = agent.request_release_checksum(id, &version, target)?;
+    let package_checksum = crate::http::get_simple_req(&checksum_request).await?;
 
     if !verify_checksum(&package_file, &package_checksum) {
         return Err(fluvio_index::Error::ChecksumError.into());
@@ -146,6 +378,114 @@ pub async fn fetch_package_file(
     Ok(package_file.to_vec())
 }
 
+/// Checks the binary of the release is published, without downloading it
+#[instrument(
+    skip(agent, id, target),
+    fields(%target, id = %id.pretty())
+)]
+pub async fn release_exists<T>(
+    agent: &HttpAgent,
+    id: &PackageId<T>,
+    version: &Version,
+    target: &Target,
+) -> Result<bool> {
+    let request = agent.request_release_exists(id, version, target)?;
+    debug!(uri = ?request.uri(), "Checking package exists:");
+    let status = crate::http::head_status_req(&request).await?;
+    Ok(agent.release_exists_from_status(status)?)
+}
+
+/// Downloads the hex encoded signature of a package file, published by the package group
+#[instrument(
+    skip(agent, id, target),
+    fields(%target, id = %id.pretty())
+)]
+pub async fn fetch_package_signature<T>(
+    agent: &HttpAgent,
+    id: &PackageId<T>,
+    version: &Version,
+    target: &Target,
+) -> Result<String> {
+    let package = fetch_package(agent, id).await?;
+    if let Some(signature) = package
+        .release(version)
+        .and_then(|release| release.digest(target))
+        .and_then(|digest| digest.signature.as_ref())
+    {
+        return Ok(signature.trim().to_string());
+    }
+
+    let request = agent.request_release_signature(id, version, target)?;
+    debug!(uri = ?request.uri(), "Requesting package signature:");
+    let signature = crate::http::get_simple_req(&request).await?;
+    Ok(signature.trim().to_string())
+}
+
+/// Downloads and verifies the completion script published with a release for the given shell
+///
+/// Returns `None` if the release has no completion for the shell
+#[instrument(
+    skip(agent, id),
+    fields(id = %id.pretty())
+)]
+pub async fn fetch_completion<T>(
+    agent: &HttpAgent,
+    id: &PackageId<T>,
+    version: &Version,
+    shell: &str,
+) -> Result<Option<Vec<u8>>> {
+    let package = fetch_package(agent, id).await?;
+    let Some(artifact) = package
+        .release(version)
+        .and_then(|release| release.completion(shell))
+    else {
+        return Ok(None);
+    };
+    let file = fetch_artifact(agent, id, version, artifact).await?;
+    Ok(Some(file))
+}
+
+/// Downloads and verifies the release notes published with a release
+///
+/// Returns `None` if the release has no release notes
+#[instrument(
+    skip(agent, id),
+    fields(id = %id.pretty())
+)]
+pub async fn fetch_release_notes<T>(
+    agent: &HttpAgent,
+    id: &PackageId<T>,
+    version: &Version,
+) -> Result<Option<String>> {
+    let package = fetch_package(agent, id).await?;
+    let Some(artifact) = package
+        .release(version)
+        .and_then(|release| release.release_notes())
+    else {
+        return Ok(None);
+    };
+    let file = fetch_artifact(agent, id, version, artifact).await?;
+    Ok(Some(String::from_utf8_lossy(&file).into_owned()))
+}
+
+async fn fetch_artifact<T>(
+    agent: &HttpAgent,
+    id: &PackageId<T>,
+    version: &Version,
+    artifact: &Artifact,
+) -> Result<Vec<u8>> {
+    let download_request = agent.request_release_artifact(id, version, artifact)?;
+    debug!(uri = ?download_request.uri(), name = %artifact.name, "Requesting artifact download:");
+    let file = crate::http::get_bytes_req(&download_request).await?;
+
+    let checksum_request = agent.request_release_artifact_checksum(id, version, artifact)?;
+    let checksum = crate::http::get_simple_req(&checksum_request).await?;
+    if !verify_checksum(&file, &checksum) {
+        return Err(fluvio_index::Error::ChecksumError.into());
+    }
+    Ok(file.to_vec())
+}
+
 fn verify_checksum<B: AsRef<[u8]>>(buffer: B, checksum: &str) -> bool {
     let bytes = buffer.as_ref();
     let buffer_checksum = {
@@ -182,12 +522,76 @@ pub fn install_bin<P: AsRef<Path>, B: AsRef<[u8]>>(bin_path: P, bytes: B) -> Res
     // Mark the file as executable
     make_executable(&mut tmp_file)?;
 
+    // A running executable can't be overwritten on Windows, but it can be renamed.
+    // Move it aside, it is removed on next start by `remove_replaced_bins`
+    #[cfg(windows)]
+    let replaced = move_aside(bin_path)?;
+
     // Rename (atomic move on unix) temp file to destination
-    std::fs::rename(&tmp_path, bin_path)?;
+    if let Err(err) = std::fs::rename(&tmp_path, bin_path) {
+        #[cfg(windows)]
+        if let Some(replaced) = replaced {
+            let _ = std::fs::rename(replaced, bin_path);
+        }
+        return Err(err.into());
+    }
+
+    Ok(())
+}
+
+/// Suffix of binaries moved aside while being replaced
+const REPLACED_BIN_SUFFIX: &str = ".replaced";
+
+/// Rename existing binary next to itself, returns where it was moved
+#[cfg_attr(not(windows), allow(dead_code))]
+fn move_aside(bin_path: &Path) -> Result<Option<PathBuf>> {
+    if !bin_path.exists() {
+        return Ok(None);
+    }
+    let file_name = bin_path
+        .file_name()
+        .ok_or_else(|| IoError::new(ErrorKind::InvalidInput, "binary path has no file name"))?
+        .to_string_lossy();
+    // Binary replaced earlier may still be running, so each gets own name
+    let aside = bin_path.with_file_name(format!(
+        "{file_name}.{}{REPLACED_BIN_SUFFIX}",
+        chrono::Utc::now().timestamp_millis()
+    ));
+    std::fs::rename(bin_path, &aside)?;
+    Ok(Some(aside))
+}
 
+/// Removes binaries moved aside by earlier installs, from the directories binaries are installed to
+///
+/// Binaries which are still running can't be removed, they are left for next time.
+pub fn remove_replaced_bins() -> Result<()> {
+    let mut dirs = vec![fluvio_bin_dir()?, fluvio_extensions_dir()?];
+    if let Some(exe_dir) = std::env::current_exe()?.parent() {
+        dirs.push(exe_dir.to_path_buf());
+    }
+    for dir in dirs {
+        remove_replaced_in(&dir);
+    }
     Ok(())
 }
 
+fn remove_replaced_in(dir: &Path) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        if entry
+            .file_name()
+            .to_string_lossy()
+            .ends_with(REPLACED_BIN_SUFFIX)
+        {
+            if let Err(err) = std::fs::remove_file(entry.path()) {
+                debug!(path = ?entry.path(), %err, "replaced binary not removed");
+            }
+        }
+    }
+}
+
 #[cfg(unix)]
 fn make_executable(file: &mut File) -> std::result::Result<(), IoError> {
     use std::os::unix::fs::PermissionsExt;
@@ -207,10 +611,46 @@ fn make_executable(_file: &mut File) -> std::result::Result<(), IoError> {
     Ok(())
 }
 
+static PRINT_TO_STDERR: AtomicBool = AtomicBool::new(false);
+
+/// Print installer progress to stderr, so stdout only has machine-readable output
+pub fn install_print_to_stderr(enabled: bool) {
+    PRINT_TO_STDERR.store(enabled, Ordering::Relaxed);
+}
+
 pub fn install_println<S: AsRef<str>>(string: S) {
-    if std::env::var("FLUVIO_BOOTSTRAP").is_ok() {
-        println!("\x1B[1;34mfluvio:\x1B[0m {}", string.as_ref());
+    let line = if std::env::var("FLUVIO_BOOTSTRAP").is_ok() {
+        format!("\x1B[1;34mfluvio:\x1B[0m {}", string.as_ref())
+    } else {
+        string.as_ref().to_string()
+    };
+    if PRINT_TO_STDERR.load(Ordering::Relaxed) {
+        eprintln!("{line}");
     } else {
-        println!("{}", string.as_ref());
+        println!("{line}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_move_aside_and_remove_replaced() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let bin_path = dir.path().join("fluvio");
+        assert!(move_aside(&bin_path).expect("missing bin").is_none());
+
+        std::fs::write(&bin_path, b"old").expect("write");
+        let aside = move_aside(&bin_path).expect("move").expect("moved");
+        assert!(!bin_path.exists());
+        assert_eq!(std::fs::read(&aside).expect("read"), b"old");
+
+        install_bin(&bin_path, b"new").expect("install");
+        assert_eq!(std::fs::read(&bin_path).expect("read"), b"new");
+
+        remove_replaced_in(dir.path());
+        assert!(!aside.exists());
+        assert!(bin_path.exists());
     }
 }
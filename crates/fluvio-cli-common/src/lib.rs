@@ -16,3 +16,7 @@ pub const FLUVIO_RELEASE_CHANNEL: &str = "FLUVIO_RELEASE_CHANNEL";
 pub const FLUVIO_EXTENSIONS_DIR: &str = "FLUVIO_EXTENSIONS_DIR";
 pub const FLUVIO_IMAGE_TAG_STRATEGY: &str = "FLUVIO_IMAGE_TAG_STRATEGY";
 pub const FLUVIO_ALWAYS_CHECK_UPDATES: &str = "FLUVIO_ALWAYS_CHECK_UPDATES";
+/// Seconds between checks for CLI updates, 0 checks on every invocation
+pub const FLUVIO_UPDATE_CHECK_INTERVAL: &str = "FLUVIO_UPDATE_CHECK_INTERVAL";
+/// Run installer without prompts, as with `--non-interactive`
+pub const FLUVIO_NON_INTERACTIVE: &str = "FLUVIO_NON_INTERACTIVE";
@@ -10,6 +10,12 @@ use fluvio_future::task::run_block_on;
 fn main() -> Result<()> {
     fluvio_future::subscriber::init_tracer(None);
 
+    // Binaries replaced by update can only be removed once they are no longer running
+    #[cfg(windows)]
+    if let Err(err) = fluvio_cli_common::install::remove_replaced_bins() {
+        tracing::debug!(%err, "failed to remove replaced binaries");
+    }
+
     print_help_hack()?;
     let root: Root = Root::parse();
 
@@ -73,6 +79,15 @@ mod tests {
         assert!(parse("fluvio consume --start --end -5 -n 0 hello").is_err());
     }
 
+    #[test]
+    fn test_correct_command_parsing_uninstall() {
+        assert!(parse("fluvio uninstall fluvio/fluvio-cloud").is_ok());
+        assert!(parse("fluvio uninstall fluvio-cloud --scope system").is_ok());
+
+        assert!(parse("fluvio uninstall").is_err());
+        assert!(parse("fluvio uninstall fluvio-cloud --scope global").is_err());
+    }
+
     fn parse(command: &str) -> Result<Root, clap::error::Error> {
         Root::try_parse_from(command.split_whitespace())
     }
@@ -215,6 +215,10 @@ mod cmd {
         #[arg(long, conflicts_with_all = &["smartmodule_group", "transforms"], alias = "transform")]
         pub transforms_line: Vec<String>,
 
+        /// (Optional) Topic receiving records the SmartModule fails on, instead of stopping consumption
+        #[arg(long, value_name = "topic")]
+        pub dead_letter_topic: Option<String>,
+
         /// Truncate the output to one line
         #[arg(long, conflicts_with_all = &["output", "format"])]
         pub truncate: bool,
@@ -378,6 +382,10 @@ mod cmd {
                 builder.isolation(isolation);
             }
 
+            if let Some(ref dead_letter_topic) = self.dead_letter_topic {
+                builder.dead_letter_topic(dead_letter_topic.clone());
+            }
+
             let consume_config = builder.build()?;
             debug!("consume config: {:#?}", consume_config);
 
@@ -801,6 +809,7 @@ mod cmd {
                 beginning: Default::default(),
                 transforms: Default::default(),
                 transforms_line: Default::default(),
+                dead_letter_topic: Default::default(),
                 truncate: Default::default(),
                 consumer: Default::default(),
             }
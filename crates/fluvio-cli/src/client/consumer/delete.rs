@@ -1,5 +1,8 @@
+use std::time::Duration;
+
 use clap::Parser;
 use anyhow::Result;
+use humantime::parse_duration;
 
 use fluvio::Fluvio;
 use fluvio_types::PartitionId;
@@ -7,17 +10,24 @@ use fluvio_types::PartitionId;
 use crate::common::output::Terminal;
 use crate::common::OutputFormat;
 
+use super::list::is_inactive;
+
 /// Option for Deleting Consumers
 #[derive(Debug, Parser)]
 pub struct DeleteConsumerOpt {
     #[clap(flatten)]
     output: OutputFormat,
 
-    consumer: String,
+    /// Consumer to delete, all consumers matching other filters if not set
+    #[arg(required_unless_present = "inactive_for")]
+    consumer: Option<String>,
     #[arg(short, long, required = false)]
     topic: Option<String>,
     #[arg(short, long, required = false, requires = "topic")]
     partition: Option<PartitionId>,
+    /// Only delete consumers which haven't committed offset for this long, e.g. 30d
+    #[arg(long, value_parser = parse_duration)]
+    inactive_for: Option<Duration>,
 }
 
 impl DeleteConsumerOpt {
@@ -26,17 +36,28 @@ impl DeleteConsumerOpt {
     where
         O: Terminal,
     {
-        if let Some((topic, partition)) = self.topic.as_ref().zip(self.partition.as_ref()) {
-            delete(fluvio, self.consumer, topic.clone(), *partition).await?;
+        if let (Some(consumer), Some(topic), Some(partition), None) = (
+            self.consumer.as_ref(),
+            self.topic.as_ref(),
+            self.partition,
+            self.inactive_for,
+        ) {
+            delete(fluvio, consumer.clone(), topic.clone(), partition).await?;
         } else {
             let consumers: Vec<_> = fluvio
                 .consumer_offsets()
                 .await?
                 .into_iter()
-                .filter(|c| c.consumer_id.eq(&self.consumer))
+                .filter(|c| {
+                    self.consumer.is_none()
+                        || c.consumer_id
+                            .eq(self.consumer.as_deref().unwrap_or_default())
+                })
                 .filter(|c| {
                     self.topic.is_none() || c.topic.eq(self.topic.as_deref().unwrap_or_default())
                 })
+                .filter(|c| self.partition.is_none() || self.partition == Some(c.partition))
+                .filter(|c| is_inactive(c, self.inactive_for))
                 .collect();
             if consumers.is_empty() {
                 println!("no consumers found");
@@ -1,7 +1,11 @@
+use std::time::{Duration, SystemTime};
+
 use clap::Parser;
 use anyhow::Result;
+use humantime::parse_duration;
 
 use fluvio::Fluvio;
+use fluvio::consumer::ConsumerOffset;
 
 use crate::common::output::Terminal;
 use crate::common::OutputFormat;
@@ -11,6 +15,10 @@ use crate::common::OutputFormat;
 pub struct ListConsumerOpt {
     #[clap(flatten)]
     output: OutputFormat,
+
+    /// Only list consumers which haven't committed offset for this long, e.g. 7d
+    #[arg(long, value_parser = parse_duration)]
+    inactive_for: Option<Duration>,
 }
 
 impl ListConsumerOpt {
@@ -18,13 +26,30 @@ impl ListConsumerOpt {
     where
         O: Terminal,
     {
-        let consumers = fluvio.consumer_offsets().await?;
+        let consumers = fluvio
+            .consumer_offsets()
+            .await?
+            .into_iter()
+            .filter(|c| is_inactive(c, self.inactive_for))
+            .collect();
 
         display::format_response_output(out, consumers, self.output.format)?;
         Ok(())
     }
 }
 
+/// true if consumer hasn't committed offset for `inactive_for`, or if no duration is given
+pub(crate) fn is_inactive(consumer: &ConsumerOffset, inactive_for: Option<Duration>) -> bool {
+    let Some(inactive_for) = inactive_for else {
+        return true;
+    };
+    let now = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    now.saturating_sub(consumer.modified_time) >= inactive_for.as_secs()
+}
+
 mod display {
 
     use std::time::{Duration, SystemTime};
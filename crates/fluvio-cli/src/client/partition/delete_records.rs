@@ -0,0 +1,51 @@
+//!
+//! # Delete Records of Partition
+//!
+//! CLI to delete records before an offset, ahead of retention.
+//! Deleted records can no longer be read, their storage is reclaimed by retention
+//! unless whole segments are deleted
+//!
+
+use tracing::debug;
+use clap::Parser;
+use anyhow::Result;
+
+use fluvio::Fluvio;
+use fluvio_protocol::record::ReplicaKey;
+
+#[derive(Debug, Parser)]
+pub struct DeleteRecordsOpt {
+    /// The name of the Topic
+    #[arg(value_name = "topic")]
+    topic: String,
+
+    /// Partition to delete records from
+    #[arg(short = 'p', long, value_name = "integer", default_value_t = 0)]
+    partition: u32,
+
+    /// Records before this offset are deleted, capped at high watermark
+    #[arg(long, value_name = "integer")]
+    before: i64,
+}
+
+impl DeleteRecordsOpt {
+    pub async fn process(self, fluvio: &Fluvio) -> Result<()> {
+        debug!(
+            topic = self.topic,
+            partition = self.partition,
+            before = self.before,
+            "delete records"
+        );
+        let log_start_offset = fluvio
+            .delete_records(
+                ReplicaKey::new(self.topic.clone(), self.partition),
+                self.before,
+            )
+            .await?;
+        println!(
+            "records of partition \"{}-{}\" deleted, log start offset: {}",
+            self.topic, self.partition, log_start_offset
+        );
+        Ok(())
+    }
+}
@@ -1,4 +1,6 @@
 mod list;
+mod snapshot;
+mod delete_records;
 
 pub use cmd::PartitionCmd;
 
@@ -18,6 +20,8 @@ mod cmd {
     use crate::common::FluvioExtensionMetadata;
 
     use super::list::ListPartitionOpt;
+    use super::snapshot::{SnapshotPartitionOpt, RestorePartitionOpt};
+    use super::delete_records::DeleteRecordsOpt;
 
     #[derive(Debug, Parser)]
     #[command(name = "partition", about = "Partition operations")]
@@ -28,6 +32,33 @@ mod cmd {
             help_template = crate::common::COMMAND_TEMPLATE,
         )]
         List(ListPartitionOpt),
+
+        /// Write partition log to snapshot archive on its leader
+        ///
+        /// Records of encrypted partition are archived as ciphertext, so the archive can only
+        /// be restored to the same partition encrypted with the same key.
+        #[command(
+            name = "snapshot",
+            help_template = crate::common::COMMAND_TEMPLATE,
+        )]
+        Snapshot(SnapshotPartitionOpt),
+
+        /// Restore snapshot archive into empty partition
+        ///
+        /// Archive of encrypted partition can only be restored to the same partition, encrypted
+        /// with the same key and starting at the same offset as the archive.
+        #[command(
+            name = "restore",
+            help_template = crate::common::COMMAND_TEMPLATE,
+        )]
+        Restore(RestorePartitionOpt),
+
+        /// Delete records of partition before offset
+        #[command(
+            name = "delete-records",
+            help_template = crate::common::COMMAND_TEMPLATE,
+        )]
+        DeleteRecords(DeleteRecordsOpt),
     }
 
     #[async_trait]
@@ -41,6 +72,15 @@ mod cmd {
                 Self::List(list) => {
                     list.process(out, fluvio).await?;
                 }
+                Self::Snapshot(snapshot) => {
+                    snapshot.process(fluvio).await?;
+                }
+                Self::Restore(restore) => {
+                    restore.process(fluvio).await?;
+                }
+                Self::DeleteRecords(delete) => {
+                    delete.process(fluvio).await?;
+                }
             }
 
             Ok(())
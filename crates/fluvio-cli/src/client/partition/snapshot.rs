@@ -0,0 +1,97 @@
+//!
+//! # Snapshot and Restore Partitions
+//!
+//! CLI tree to write partition log to snapshot archive and restore it into empty partition
+//!
+
+use tracing::debug;
+use clap::Parser;
+use anyhow::Result;
+
+use fluvio::Fluvio;
+use fluvio_protocol::record::ReplicaKey;
+
+#[derive(Debug, Parser)]
+pub struct SnapshotPartitionOpt {
+    /// The name of the Topic
+    #[arg(value_name = "topic")]
+    topic: String,
+
+    /// Partition to snapshot
+    #[arg(short = 'p', long, value_name = "integer", default_value_t = 0)]
+    partition: u32,
+
+    /// Name of snapshot archive in snapshot directory of partition leader
+    #[arg(short = 'n', long, value_name = "name")]
+    name: String,
+
+    /// Only records before this offset are included, defaults to high watermark
+    #[arg(long, value_name = "integer")]
+    end_offset: Option<i64>,
+}
+
+impl SnapshotPartitionOpt {
+    pub async fn process(self, fluvio: &Fluvio) -> Result<()> {
+        debug!(
+            topic = self.topic,
+            partition = self.partition,
+            name = self.name,
+            "snapshot partition"
+        );
+        let response = fluvio
+            .snapshot_partition(
+                ReplicaKey::new(self.topic.clone(), self.partition),
+                &self.name,
+                self.end_offset,
+            )
+            .await?;
+        println!(
+            "partition \"{}-{}\" written to snapshot \"{}\": offsets {}..{}, {} batches, {} bytes",
+            self.topic,
+            self.partition,
+            self.name,
+            response.start_offset,
+            response.end_offset,
+            response.batches,
+            response.bytes
+        );
+        Ok(())
+    }
+}
+
+#[derive(Debug, Parser)]
+pub struct RestorePartitionOpt {
+    /// The name of the Topic
+    #[arg(value_name = "topic")]
+    topic: String,
+
+    /// Partition to restore into, must be empty
+    #[arg(short = 'p', long, value_name = "integer", default_value_t = 0)]
+    partition: u32,
+
+    /// Name of snapshot archive in snapshot directory of partition leader
+    #[arg(short = 'n', long, value_name = "name")]
+    name: String,
+}
+
+impl RestorePartitionOpt {
+    pub async fn process(self, fluvio: &Fluvio) -> Result<()> {
+        debug!(
+            topic = self.topic,
+            partition = self.partition,
+            name = self.name,
+            "restore partition"
+        );
+        let response = fluvio
+            .restore_partition(
+                ReplicaKey::new(self.topic.clone(), self.partition),
+                &self.name,
+            )
+            .await?;
+        println!(
+            "snapshot \"{}\" restored to partition \"{}-{}\": {} batches, end offset {}",
+            self.name, self.topic, self.partition, response.batches, response.leo
+        );
+        Ok(())
+    }
+}
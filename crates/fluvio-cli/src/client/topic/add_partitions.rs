@@ -0,0 +1,39 @@
+//!
+//! # Add Partitions
+//!
+//! CLI tree to increase partition count of a Topic
+//!
+
+use tracing::debug;
+use clap::Parser;
+use anyhow::Result;
+
+use fluvio::Fluvio;
+
+#[derive(Debug, Parser)]
+pub struct AddPartitionsOpt {
+    /// The name of the Topic
+    #[arg(value_name = "name")]
+    topic: String,
+
+    /// The new total number of partitions, must be greater than current count
+    #[arg(short = 'p', long = "partitions", value_name = "partitions")]
+    partitions: u32,
+}
+
+impl AddPartitionsOpt {
+    pub async fn process(self, fluvio: &Fluvio) -> Result<()> {
+        debug!(
+            topic = self.topic,
+            partitions = self.partitions,
+            "adding partitions"
+        );
+        let admin = fluvio.admin().await;
+        admin.add_partitions(&self.topic, self.partitions).await?;
+        println!(
+            "topic \"{}\" partition count increased to {}",
+            self.topic, self.partitions
+        );
+        Ok(())
+    }
+}
@@ -18,6 +18,10 @@ use fluvio::metadata::topic::ReplicaSpec;
 use fluvio::metadata::topic::SegmentBasedPolicy;
 use fluvio::metadata::topic::TopicStorageConfig;
 use fluvio::metadata::topic::CompressionAlgorithm;
+use fluvio::metadata::topic::TopicEncryption;
+use fluvio::metadata::topic::{TopicSchema, SchemaFormat, SchemaValidationAction};
+use fluvio::metadata::topic::LeaderAffinity;
+use fluvio::metadata::topic::Durability;
 
 use fluvio_controlplane_metadata::topic::config::TopicConfig;
 use fluvio_sc_schema::shared::validate_resource_name;
@@ -220,7 +224,14 @@ impl CreateTopicOpt {
             topic_spec.set_compression_type(compression_type);
         }
 
-        if self.setting.segment_size.is_some() || self.setting.max_partition_size.is_some() {
+        if self.setting.segment_size.is_some()
+            || self.setting.max_partition_size.is_some()
+            || self.setting.durability.is_some()
+            || self.setting.storage_backend.is_some()
+            || self.setting.min_in_sync_replicas.is_some()
+            || self.setting.index_interval.is_some()
+            || self.setting.index_max_size.is_some()
+        {
             let mut storage = TopicStorageConfig::default();
 
             if let Some(segment_size) = self.setting.segment_size {
@@ -231,9 +242,42 @@ impl CreateTopicOpt {
                 storage.max_partition_size = Some(max_partition_size.as_u64());
             }
 
+            storage.durability = self.setting.durability;
+            storage.backend = self.setting.storage_backend;
+            storage.min_in_sync_replicas = self.setting.min_in_sync_replicas;
+            storage.index_interval_bytes = self
+                .setting
+                .index_interval
+                .map(|interval| interval.as_u64() as u32);
+            storage.index_max_bytes = self
+                .setting
+                .index_max_size
+                .map(|max_size| max_size.as_u64() as u32);
+
             topic_spec.set_storage(storage);
         }
 
+        if let Some(key_id) = self.setting.encryption_key {
+            topic_spec.set_encryption(Some(TopicEncryption::new(key_id)));
+        }
+
+        if let Some((reference, format)) = self.setting.schema.zip(self.setting.schema_format) {
+            topic_spec.set_schema(Some(TopicSchema {
+                format,
+                reference,
+                version: self.setting.schema_version,
+                on_failure: self.setting.schema_on_failure.unwrap_or_default(),
+            }));
+        }
+
+        let affinity = LeaderAffinity {
+            preferred_leaders: self.setting.preferred_leader,
+            anti_affinity_topics: self.setting.leader_anti_affinity,
+        };
+        if !affinity.is_empty() {
+            topic_spec.set_leader_affinity(Some(affinity));
+        }
+
         Ok((topic_name, topic_spec))
     }
 }
@@ -268,6 +312,79 @@ pub struct TopicConfigOpt {
     /// Ex: `2048`, '2 Ki', '10 MiB', `1 GB`
     #[arg(long, value_name = "bytes")]
     max_partition_size: Option<bytesize::ByteSize>,
+
+    /// Id of the key used to encrypt records at rest, resolved by key provider of SPUs
+    #[arg(long, value_name = "key id")]
+    encryption_key: Option<String>,
+
+    /// When written records are synced to disk: 'os' (default), 'batch' for every batch,
+    /// or interval between syncs. Ex: 'batch', '100ms', '1s'
+    #[arg(long, value_name = "durability", value_parser=parse_durability)]
+    durability: Option<Durability>,
+
+    /// Storage backend of partitions, must be registered on SPUs. Default is 'file'
+    #[arg(long, value_name = "backend")]
+    storage_backend: Option<String>,
+
+    /// Replicas, including leader, which must be in sync to accept produce waiting for all
+    /// replicas. Produce is rejected while fewer replicas are in sync
+    #[arg(long, value_name = "replicas")]
+    min_in_sync_replicas: Option<u16>,
+
+    /// Bytes of records between segment index entries, smaller interval speeds up lookups
+    /// but uses more index space
+    /// Ex: `4096`, '4 Ki'
+    #[arg(long, value_name = "bytes")]
+    index_interval: Option<bytesize::ByteSize>,
+
+    /// Max size of segment index (by default measured in bytes)
+    /// Ex: `2048`, '2 Ki', '10 MiB'
+    #[arg(long, value_name = "bytes")]
+    index_max_size: Option<bytesize::ByteSize>,
+
+    /// Schema records are validated against by leader SPU, registry subject or inline definition
+    #[arg(long, value_name = "reference", requires = "schema_format")]
+    schema: Option<String>,
+
+    /// Format of topic schema: 'json', 'avro' or 'protobuf'
+    #[arg(long, value_name = "format", requires = "schema")]
+    schema_format: Option<SchemaFormat>,
+
+    /// Version of schema in registry, latest if not set
+    #[arg(long, value_name = "version", requires = "schema")]
+    schema_version: Option<u32>,
+
+    /// Action on records failing schema validation: 'reject' (default) rejects produce,
+    /// 'tag' writes records and counts them as invalid
+    #[arg(long, value_name = "action", requires = "schema")]
+    schema_on_failure: Option<SchemaValidationAction>,
+
+    /// SPU preferred to lead partitions when possible, can be repeated
+    #[arg(long, value_name = "spu id")]
+    preferred_leader: Vec<i32>,
+
+    /// Topic whose partition leaders should not lead partitions of this topic when possible,
+    /// can be repeated
+    #[arg(long, value_name = "topic")]
+    leader_anti_affinity: Vec<String>,
+}
+
+fn parse_durability(value: &str) -> Result<Durability> {
+    match value {
+        "os" => Ok(Durability::Os),
+        "batch" => Ok(Durability::Batch),
+        interval => {
+            let interval = parse_duration(interval)?;
+            let interval_ms = u32::try_from(interval.as_millis())?;
+            if interval_ms == 0 {
+                return Err(CliError::InvalidArg(
+                    "durability interval must be at least 1ms".to_string(),
+                )
+                .into());
+            }
+            Ok(Durability::IntervalMs(interval_ms))
+        }
+    }
 }
 
 /// module to load partitions maps from file
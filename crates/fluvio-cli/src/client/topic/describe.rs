@@ -4,6 +4,7 @@
 //! CLI to describe Topics and their corresponding Partitions
 //!
 
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use tracing::debug;
@@ -12,6 +13,7 @@ use anyhow::Result;
 
 use fluvio::Fluvio;
 use fluvio::metadata::topic::TopicSpec;
+use fluvio::metadata::partition::{PartitionSpec, ReplicaKey, StorageUsage};
 
 use crate::common::output::Terminal;
 use crate::common::OutputFormat;
@@ -38,15 +40,34 @@ impl DescribeTopicsOpt {
 
         let admin = fluvio.admin().await;
         let topics = admin.list::<TopicSpec, _>(vec![topic]).await?;
+        let usage = topic_usage(admin.all::<PartitionSpec>().await?);
 
-        display::describe_topics(topics, output_type, out).await?;
+        display::describe_topics(topics, usage, output_type, out).await?;
         Ok(())
     }
 }
 
+/// disk usage of leader replicas summed by topic
+fn topic_usage(
+    partitions: Vec<fluvio::metadata::objects::Metadata<PartitionSpec>>,
+) -> HashMap<String, StorageUsage> {
+    let mut usage: HashMap<String, StorageUsage> = HashMap::new();
+    for partition in partitions {
+        let Ok(replica) = ReplicaKey::try_from(partition.name) else {
+            continue;
+        };
+        *usage.entry(replica.topic).or_default() += partition.status.usage;
+    }
+    usage
+}
+
 mod display {
 
+    use std::collections::HashMap;
+
     use fluvio::metadata::topic::ReplicaSpec;
+    use fluvio::metadata::partition::StorageUsage;
+    use bytesize::ByteSize;
     use comfy_table::Row;
     use humantime::format_duration;
     use serde::Serialize;
@@ -63,18 +84,30 @@ mod display {
     // Connect to Kafka Controller and query server for topic
     pub async fn describe_topics<O>(
         topics: Vec<Metadata<TopicSpec>>,
+        mut usage: HashMap<String, StorageUsage>,
         output_type: OutputType,
         out: std::sync::Arc<O>,
     ) -> Result<(), OutputError>
     where
         O: Terminal,
     {
-        let topic_list: Vec<TopicMetadata> = topics.into_iter().map(|m| TopicMetadata(m)).collect();
+        let topic_list: Vec<TopicMetadata> = topics
+            .into_iter()
+            .map(|metadata| TopicMetadata {
+                usage: usage.remove(&metadata.name).unwrap_or_default(),
+                metadata,
+            })
+            .collect();
         out.describe_objects(&topic_list, output_type)
     }
 
     #[derive(Serialize, Clone)]
-    struct TopicMetadata(Metadata<TopicSpec>);
+    struct TopicMetadata {
+        #[serde(flatten)]
+        metadata: Metadata<TopicSpec>,
+        /// disk usage of all partitions
+        usage: StorageUsage,
+    }
 
     impl DescribeObjectHandler for TopicMetadata {
         fn label() -> &'static str {
@@ -116,10 +149,10 @@ mod display {
         /// key value hash map implementation
         fn key_values(&self) -> Vec<(String, Option<String>)> {
             let mut key_values = Vec::new();
-            let spec = &self.0.spec;
-            let status = &self.0.status;
+            let spec = &self.metadata.spec;
+            let status = &self.metadata.status;
 
-            key_values.push(("Name".to_owned(), Some(self.0.name.clone())));
+            key_values.push(("Name".to_owned(), Some(self.metadata.name.clone())));
             key_values.push(("Type".to_owned(), Some(spec.type_label().to_string())));
             match spec.replicas() {
                 ReplicaSpec::Computed(param) => {
@@ -171,6 +204,24 @@ mod display {
             ));
             key_values.push(("Reason".to_owned(), Some(status.reason.clone())));
 
+            let usage = &self.usage;
+            key_values.push((
+                "Segment Size".to_owned(),
+                Some(ByteSize::b(usage.segment_bytes).to_string()),
+            ));
+            key_values.push((
+                "Index Size".to_owned(),
+                Some(ByteSize::b(usage.index_bytes).to_string()),
+            ));
+            key_values.push((
+                "Tiered Size".to_owned(),
+                Some(ByteSize::b(usage.tiered_bytes).to_string()),
+            ));
+            key_values.push((
+                "Total Size".to_owned(),
+                Some(ByteSize::b(usage.total()).to_string()),
+            ));
+
             key_values.push(("-----------------".to_owned(), None));
 
             key_values
@@ -2,6 +2,8 @@ mod create;
 mod delete;
 mod describe;
 mod list;
+mod add_partitions;
+mod read_only;
 
 pub use cmd::TopicCmd;
 
@@ -25,6 +27,8 @@ mod cmd {
     use super::delete::DeleteTopicOpt;
     use super::describe::DescribeTopicsOpt;
     use super::list::ListTopicsOpt;
+    use super::add_partitions::AddPartitionsOpt;
+    use super::read_only::SetReadOnlyOpt;
 
     #[derive(Debug, Parser)]
     #[command(name = "topic", about = "Topic operations")]
@@ -34,7 +38,7 @@ mod cmd {
             name = "create",
             help_template = COMMAND_TEMPLATE,
         )]
-        Create(CreateTopicOpt),
+        Create(Box<CreateTopicOpt>),
 
         /// Delete one or more Topics with the given name(s)
         #[command(
@@ -56,6 +60,20 @@ mod cmd {
             help_template = COMMAND_TEMPLATE,
         )]
         List(ListTopicsOpt),
+
+        /// Increase number of partitions of a Topic
+        #[command(
+            name = "add-partitions",
+            help_template = COMMAND_TEMPLATE,
+        )]
+        AddPartitions(AddPartitionsOpt),
+
+        /// Mark a Topic or one of its partitions read-only, or writable again
+        #[command(
+            name = "set-read-only",
+            help_template = COMMAND_TEMPLATE,
+        )]
+        SetReadOnly(SetReadOnlyOpt),
     }
 
     #[async_trait]
@@ -78,6 +96,12 @@ mod cmd {
                 Self::List(list) => {
                     list.process(out, fluvio).await?;
                 }
+                Self::AddPartitions(add_partitions) => {
+                    add_partitions.process(fluvio).await?;
+                }
+                Self::SetReadOnly(read_only) => {
+                    read_only.process(fluvio).await?;
+                }
             }
 
             Ok(())
@@ -0,0 +1,49 @@
+//!
+//! # Set Read-Only
+//!
+//! CLI tree to mark a Topic or one of its partitions read-only
+//!
+
+use tracing::debug;
+use clap::Parser;
+use anyhow::Result;
+
+use fluvio::Fluvio;
+
+#[derive(Debug, Parser)]
+pub struct SetReadOnlyOpt {
+    /// The name of the Topic
+    #[arg(value_name = "name")]
+    topic: String,
+
+    /// Only change this partition, otherwise topic and all of its partitions are changed
+    #[arg(short = 'p', long = "partition", value_name = "partition")]
+    partition: Option<u32>,
+
+    /// Make topic or partition writable again
+    #[arg(long)]
+    writable: bool,
+}
+
+impl SetReadOnlyOpt {
+    pub async fn process(self, fluvio: &Fluvio) -> Result<()> {
+        let read_only = !self.writable;
+        debug!(
+            topic = self.topic,
+            partition = self.partition,
+            read_only,
+            "setting read-only"
+        );
+        let admin = fluvio.admin().await;
+        admin
+            .set_read_only(&self.topic, self.partition, read_only)
+            .await?;
+        let target = match self.partition {
+            Some(partition) => format!("partition \"{}/{partition}\"", self.topic),
+            None => format!("topic \"{}\"", self.topic),
+        };
+        let mode = if read_only { "read-only" } else { "writable" };
+        println!("{target} is {mode}");
+        Ok(())
+    }
+}
@@ -0,0 +1,132 @@
+//!
+//! # First-run Bootstrap
+//!
+//! New users get a working toolchain in one step with `fluvio setup`, which installs the curated
+//! set of default plugins. The set is a meta-package in the package index, so it can change
+//! without a CLI release. Each release of the meta-package lists the packages to install and,
+//! optionally, the versions to install them from.
+//!
+use anyhow::Result;
+use clap::Parser;
+
+use fluvio_cli_common::install::{fetch_meta_members, install_println};
+use fluvio_index::{HttpAgent, MaybeVersion, MetaMember, PackageId};
+
+use crate::error::CliError;
+use crate::install::non_interactive::{ExistingPolicy, NonInteractiveOpt, PackageReport, PackageStatus};
+use crate::install::opts::InstallOpt;
+use crate::install::scope::InstallScope;
+use crate::install::trust::VerifyPolicy;
+use crate::install::update::{check_update_required, prompt_required_update};
+
+/// Meta-package of the plugins installed by `fluvio setup`
+pub const DEFAULT_PLUGIN_SET: &str = "fluvio/fluvio-default-plugins";
+
+#[derive(Parser, Debug)]
+pub struct SetupOpt {
+    /// Meta-package listing the plugins to install
+    #[arg(long, value_name = "PACKAGE", default_value = DEFAULT_PLUGIN_SET)]
+    plugin_set: PackageId<MaybeVersion>,
+
+    /// Install the latest prerelease of the plugin set and plugins
+    #[arg(long)]
+    develop: bool,
+
+    /// Install for current user, or system-wide for all users of the machine
+    #[arg(long, value_enum, default_value_t = InstallScope::User)]
+    scope: InstallScope,
+
+    /// Signature verification policy, overrides the one set with `fluvio trust policy`
+    #[arg(long, value_enum, value_name = "POLICY")]
+    verify: Option<VerifyPolicy>,
+
+    /// Do not install shell completions published with the plugins
+    #[arg(long)]
+    no_completions: bool,
+
+    /// What to do with plugins which are already installed
+    #[arg(long, value_enum, value_name = "POLICY", default_value_t = ExistingPolicy::Skip)]
+    on_existing: ExistingPolicy,
+
+    #[command(flatten)]
+    interaction: NonInteractiveOpt,
+}
+
+impl SetupOpt {
+    pub async fn process(self) -> Result<()> {
+        self.interaction.begin();
+        let result = self.bootstrap(&HttpAgent::default()).await;
+        self.interaction.finish("setup", result)
+    }
+
+    /// install each plugin of the plugin set, a failed plugin doesn't stop the others
+    pub async fn bootstrap(&self, agent: &HttpAgent) -> Result<Vec<PackageReport>> {
+        if check_update_required(agent).await? {
+            prompt_required_update(agent).await?;
+            return match self.interaction.update_required() {
+                Some(err) => Err(err.into()),
+                None => Ok(vec![]),
+            };
+        }
+
+        install_println(format!("🎣 Resolving plugin set {}...", self.plugin_set));
+        let (version, members) = fetch_meta_members(agent, &self.plugin_set, self.develop).await?;
+        install_println(format!(
+            "📦 Installing {} plugin(s) of {} {version}",
+            members.len(),
+            self.plugin_set
+        ));
+
+        let mut reports = Vec::with_capacity(members.len());
+        for member in members {
+            let package = member.id.to_string();
+            let report = match self.member_opt(member).install_plugin(agent).await {
+                Ok(report) => report,
+                Err(err) => match err.downcast_ref::<CliError>() {
+                    Some(CliError::IndexError(fluvio_index::Error::MissingTarget(target))) => {
+                        install_println(format!(
+                            "❕ {package} is not available for target {target}, skipping"
+                        ));
+                        PackageReport::new(package, None, PackageStatus::Skipped)
+                            .with_message(format!("not available for target {target}"))
+                    }
+                    _ => {
+                        install_println(format!("❌ Failed to install {package}: {err}"));
+                        PackageReport::new(package, None, PackageStatus::Failed)
+                            .with_message(err.to_string())
+                    }
+                },
+            };
+            reports.push(report);
+        }
+        Ok(reports)
+    }
+
+    /// options to install a plugin of the set with
+    fn member_opt(&self, member: MetaMember) -> InstallOpt {
+        InstallOpt {
+            prefix: None,
+            develop: self.develop,
+            version: member.version,
+            path: None,
+            git: None,
+            list: false,
+            search: None,
+            group: member.id.group().clone(),
+            scope: self.scope,
+            verify: self.verify,
+            no_completions: self.no_completions,
+            ignore_compatibility: false,
+            on_existing: self.on_existing,
+            interaction: self.interaction.clone(),
+            hub: false,
+            use_hub_defaults: false,
+            channel: None,
+            target: None,
+            output_dir: None,
+            verbose: false,
+            force_refresh: false,
+            package: Some(member.id),
+        }
+    }
+}
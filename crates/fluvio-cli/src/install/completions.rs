@@ -0,0 +1,109 @@
+//!
+//! # Plugin Completions
+//!
+//! Plugin releases may publish shell completion scripts as release artifacts. On install, the
+//! script for the user's shell, detected from `SHELL`, is written where that shell loads
+//! completions from, replacing the one of previous install.
+//!
+use std::fs;
+use std::io::{Error as IoError, ErrorKind};
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+
+use fluvio_cli_common::install::fluvio_base_dir;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompletionShell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+impl CompletionShell {
+    pub const ALL: [Self; 3] = [Self::Bash, Self::Zsh, Self::Fish];
+
+    /// shell of user, none if it is not supported
+    pub fn detect() -> Option<Self> {
+        Self::from_shell_path(&std::env::var("SHELL").ok()?)
+    }
+
+    fn from_shell_path(path: &str) -> Option<Self> {
+        match Path::new(path).file_name()?.to_str()? {
+            "bash" => Some(Self::Bash),
+            "zsh" => Some(Self::Zsh),
+            "fish" => Some(Self::Fish),
+            _ => None,
+        }
+    }
+
+    /// name of shell in release artifacts
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Bash => "bash",
+            Self::Zsh => "zsh",
+            Self::Fish => "fish",
+        }
+    }
+
+    /// location the shell loads completions of `command` from
+    pub fn completion_path(&self, command: &str) -> Result<PathBuf> {
+        let path = match self {
+            Self::Bash => xdg_dir("XDG_DATA_HOME", ".local/share")?
+                .join("bash-completion")
+                .join("completions")
+                .join(command),
+            // zsh has no user completions dir by default, it must be added to `fpath`
+            Self::Zsh => fluvio_base_dir()?
+                .join("completions")
+                .join(format!("_{command}")),
+            Self::Fish => xdg_dir("XDG_CONFIG_HOME", ".config")?
+                .join("fish")
+                .join("completions")
+                .join(format!("{command}.fish")),
+        };
+        Ok(path)
+    }
+
+    /// install completion script of `command`, returns where it was written
+    pub fn install(&self, command: &str, script: impl AsRef<[u8]>) -> Result<PathBuf> {
+        let path = self.completion_path(command)?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, script)?;
+        Ok(path)
+    }
+}
+
+fn xdg_dir(var: &str, default: &str) -> Result<PathBuf> {
+    if let Some(dir) = std::env::var_os(var).filter(|it| !it.is_empty()) {
+        return Ok(PathBuf::from(dir));
+    }
+    let home =
+        home::home_dir().ok_or_else(|| IoError::new(ErrorKind::NotFound, "Homedir not found"))?;
+    Ok(home.join(default))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shell_from_path() {
+        assert_eq!(
+            CompletionShell::from_shell_path("/bin/bash"),
+            Some(CompletionShell::Bash)
+        );
+        assert_eq!(
+            CompletionShell::from_shell_path("/usr/local/bin/zsh"),
+            Some(CompletionShell::Zsh)
+        );
+        assert_eq!(
+            CompletionShell::from_shell_path("fish"),
+            Some(CompletionShell::Fish)
+        );
+        assert_eq!(CompletionShell::from_shell_path("/bin/tcsh"), None);
+        assert_eq!(CompletionShell::from_shell_path(""), None);
+    }
+}
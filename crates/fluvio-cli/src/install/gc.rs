@@ -0,0 +1,176 @@
+//!
+//! # Installer Garbage Collection
+//!
+//! Binaries replaced by installs are retained in the `previous` directory for rollback.
+//! Retained binaries no longer recorded in the install manifest are orphans and always
+//! removed. With a maximum size, the least recently retained binaries are removed too, and
+//! their packages can no longer be rolled back.
+//!
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use bytesize::ByteSize;
+use clap::Parser;
+
+use fluvio_cli_common::install::install_println;
+use fluvio_hub_util::cache::{lru_evictions, remove_entries, CacheEntry, GcReport};
+
+use crate::install::manifest::InstallManifest;
+use crate::install::scope::InstallScope;
+
+#[derive(Parser, Debug)]
+pub struct GcOpt {
+    /// Collect installs for current user, or system-wide ones
+    #[arg(long, value_enum, default_value_t = InstallScope::User)]
+    scope: InstallScope,
+    /// Remove least recently retained binaries until they fit this size, e.g. "200MB"
+    #[arg(long, value_name = "SIZE")]
+    max_size: Option<ByteSize>,
+    /// Print binaries which would be removed, without removing them
+    #[arg(long)]
+    dry_run: bool,
+}
+
+impl GcOpt {
+    pub async fn process(self) -> Result<()> {
+        let manifest_path = InstallManifest::default_path(self.scope)?;
+        let mut manifest = InstallManifest::load(&manifest_path)?;
+        let previous_dir = InstallManifest::previous_dir(self.scope)?;
+
+        let report = collect(
+            &mut manifest,
+            &previous_dir,
+            self.max_size.map(|it| it.as_u64()),
+            self.dry_run,
+        )?;
+        if !self.dry_run && !report.removed.is_empty() {
+            manifest.save(&manifest_path)?;
+        }
+
+        let verb = if self.dry_run {
+            "Would remove"
+        } else {
+            "Removed"
+        };
+        for path in &report.removed {
+            install_println(format!("🧹 {verb} {}", path.display()));
+        }
+        install_println(format!(
+            "✅ {verb} {} retained binaries, reclaiming {}",
+            report.removed.len(),
+            ByteSize(report.reclaimed)
+        ));
+        Ok(())
+    }
+}
+
+/// Remove orphaned and evicted binaries from `previous_dir`, evicted ones are no longer
+/// recorded as previous install in the manifest
+fn collect(
+    manifest: &mut InstallManifest,
+    previous_dir: &Path,
+    max_size: Option<u64>,
+    dry_run: bool,
+) -> Result<GcReport> {
+    let retained: BTreeMap<PathBuf, String> = manifest
+        .packages
+        .iter()
+        .filter_map(|(package, installed)| {
+            let path = installed.previous.as_ref()?.path.clone()?;
+            Some((path, package.clone()))
+        })
+        .collect();
+
+    let mut orphans = Vec::new();
+    let mut candidates = Vec::new();
+    for entry in fs::read_dir(previous_dir)? {
+        let entry = CacheEntry::open(entry?.path())?;
+        if retained.contains_key(&entry.path) {
+            candidates.push(entry);
+        } else {
+            orphans.push(entry);
+        }
+    }
+
+    let mut evicted = orphans;
+    if let Some(max_size) = max_size {
+        evicted.extend(lru_evictions(candidates, max_size));
+    }
+    let report = remove_entries(evicted, dry_run)?;
+
+    if !dry_run {
+        for path in &report.removed {
+            if let Some(installed) = retained
+                .get(path)
+                .and_then(|package| manifest.packages.get_mut(package))
+            {
+                installed.previous = None;
+            }
+        }
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use semver::Version;
+
+    use crate::install::manifest::{InstallSource, InstalledPackage};
+
+    use super::*;
+
+    fn installed(version: &str, path: Option<PathBuf>) -> InstalledPackage {
+        InstalledPackage {
+            version: Version::parse(version).unwrap(),
+            requirement: None,
+            target: "x86_64-unknown-linux-musl".to_owned(),
+            source: InstallSource::Registry,
+            path,
+            checksum: None,
+            previous: None,
+        }
+    }
+
+    #[test]
+    fn test_collect() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let retained = dir.path().join("fluvio-cloud");
+        let orphan = dir.path().join("fluvio-gone");
+        fs::write(&retained, b"retained").unwrap();
+        fs::write(&orphan, b"orphan").unwrap();
+
+        let mut manifest = InstallManifest::default();
+        manifest.insert(
+            "fluvio/fluvio-cloud",
+            installed(
+                "0.2.4",
+                Some("/home/user/.fluvio/extensions/fluvio-cloud".into()),
+            ),
+            None,
+        );
+        manifest.insert(
+            "fluvio/fluvio-cloud",
+            installed(
+                "0.2.5",
+                Some("/home/user/.fluvio/extensions/fluvio-cloud".into()),
+            ),
+            Some(retained.clone()),
+        );
+
+        let report = collect(&mut manifest, dir.path(), None, false).unwrap();
+        assert_eq!(report.removed, vec![orphan.clone()]);
+        assert_eq!(report.reclaimed, 6);
+        assert!(retained.exists() && !orphan.exists());
+
+        let report = collect(&mut manifest, dir.path(), Some(0), true).unwrap();
+        assert_eq!(report.removed, vec![retained.clone()]);
+        assert!(retained.exists());
+
+        collect(&mut manifest, dir.path(), Some(0), false).unwrap();
+        assert!(!retained.exists());
+        let cloud = manifest.get("fluvio/fluvio-cloud").expect("installed");
+        assert!(cloud.previous.is_none());
+    }
+}
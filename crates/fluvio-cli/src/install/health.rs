@@ -0,0 +1,129 @@
+//!
+//! # Post-install Health Check
+//!
+//! Installed binaries are run with `--version` to catch corrupt or wrong target downloads
+//! before they are recorded. The check runs without stdin, in a temporary directory and with
+//! a time limit. If the binary reports its version, it must match the installed release.
+//!
+use std::io::Read;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Result};
+use semver::Version;
+use tracing::debug;
+
+const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// run installed binary and verify it reports `expected` version, if it reports any
+pub fn check_installed(path: &Path, expected: &Version) -> Result<()> {
+    let output = match run(path, "--version")? {
+        Some(output) => output,
+        // binaries without version flag must at least show help
+        None => {
+            run(path, "--help")?.ok_or_else(|| anyhow!("{} exited with error", path.display()))?
+        }
+    };
+    match reported_version(&output) {
+        Some(version) if version != *expected => Err(anyhow!(
+            "{} reports version {version}, expected {expected}",
+            path.display()
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// stdout of successful run, none if it exited with error
+fn run(path: &Path, arg: &str) -> Result<Option<String>> {
+    let dir = tempfile::tempdir()?;
+    let mut child = Command::new(path)
+        .arg(arg)
+        .current_dir(dir.path())
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|err| anyhow!("{} can't be run: {err}", path.display()))?;
+
+    let started = Instant::now();
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if started.elapsed() > HEALTH_CHECK_TIMEOUT {
+            let _ = child.kill();
+            return Err(anyhow!(
+                "{} {arg} did not finish within {}s",
+                path.display(),
+                HEALTH_CHECK_TIMEOUT.as_secs()
+            ));
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    };
+    debug!(?path, arg, %status, "health check run");
+    if !status.success() {
+        return Ok(None);
+    }
+    let mut stdout = String::new();
+    if let Some(mut out) = child.stdout.take() {
+        out.read_to_string(&mut stdout)?;
+    }
+    Ok(Some(stdout))
+}
+
+/// first semver in output, e.g. `fluvio-cloud 0.2.5` or `v0.2.5`
+fn reported_version(output: &str) -> Option<Version> {
+    output
+        .split_whitespace()
+        .map(|it| it.trim_start_matches('v').trim_end_matches(','))
+        .find_map(|it| Version::parse(it).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reported_version() {
+        assert_eq!(
+            reported_version("fluvio-cloud 0.2.5\n"),
+            Some(Version::parse("0.2.5").unwrap())
+        );
+        assert_eq!(
+            reported_version("cdk v0.3.0-alpha.1"),
+            Some(Version::parse("0.3.0-alpha.1").unwrap())
+        );
+        assert_eq!(reported_version("Usage: smdk <COMMAND>"), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_check_installed() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let script = |name: &str, body: &str| {
+            let path = dir.path().join(name);
+            std::fs::write(&path, format!("#!/bin/sh\n{body}\n")).expect("write");
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).expect("chmod");
+            path
+        };
+        let expected = Version::parse("0.2.5").unwrap();
+
+        let plugin = script("fluvio-ok", "echo fluvio-ok 0.2.5");
+        assert!(check_installed(&plugin, &expected).is_ok());
+
+        let plugin = script("fluvio-old", "echo fluvio-old 0.2.4");
+        assert!(check_installed(&plugin, &expected).is_err());
+
+        let plugin = script("fluvio-broken", "exit 1");
+        assert!(check_installed(&plugin, &expected).is_err());
+
+        let corrupt = dir.path().join("fluvio-corrupt");
+        std::fs::write(&corrupt, [0u8, 1, 2, 3]).expect("write");
+        std::fs::set_permissions(&corrupt, std::fs::Permissions::from_mode(0o755)).expect("chmod");
+        assert!(check_installed(&corrupt, &expected).is_err());
+    }
+}
@@ -0,0 +1,347 @@
+//!
+//! # Install Manifest
+//!
+//! Records the packages installed by `fluvio install`, with the version requirement each was
+//! pinned to, so the resolved version of a package can be traced back to the request.
+//!
+//! When a package is reinstalled, the binary it replaces is retained in the `previous`
+//! directory and recorded as previous install, so it can be rolled back. Only one previous
+//! version is retained per package. User and system-wide installs have separate manifests.
+//!
+//! The sha256 checksum of each installed binary is recorded, so corrupted binaries can be
+//! detected and repaired.
+//!
+//! Installed plugins are also kept side-by-side by version, see [`crate::install::versions`].
+//!
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Result};
+use semver::{Version, VersionReq};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use fluvio_cli_common::install::install_bin;
+
+use crate::install::health::check_installed;
+use crate::install::scope::InstallScope;
+use crate::install::versions::PluginVersions;
+
+const INSTALL_MANIFEST_FILE: &str = "install-manifest.json";
+const PREVIOUS_DIR: &str = "previous";
+
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct InstallManifest {
+    #[serde(default)]
+    pub packages: BTreeMap<String, InstalledPackage>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InstalledPackage {
+    /// version which was installed
+    pub version: Version,
+    /// requirement given with `--version`, none if latest was installed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub requirement: Option<VersionReq>,
+    pub target: String,
+    /// where the package was installed from
+    #[serde(default, skip_serializing_if = "InstallSource::is_registry")]
+    pub source: InstallSource,
+    /// location of binary, for previous install this is the retained copy
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<PathBuf>,
+    /// hex encoded sha256 of binary, none for installs recorded before checksums
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checksum: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous: Option<Box<InstalledPackage>>,
+}
+
+/// Provenance of installed package
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum InstallSource {
+    /// package index
+    #[default]
+    Registry,
+    /// binary or cargo project on local filesystem
+    Path { path: PathBuf },
+    /// cargo project in git repository, `commit` is the revision which was built
+    Git {
+        url: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        rev: Option<String>,
+        commit: String,
+    },
+}
+
+impl InstallSource {
+    pub fn is_registry(&self) -> bool {
+        matches!(self, Self::Registry)
+    }
+}
+
+impl InstalledPackage {
+    /// swap with previous install, so current install becomes the previous one
+    pub fn rolled_back(mut self) -> Option<Self> {
+        let mut previous = self.previous.take()?;
+        let retained_path = std::mem::replace(&mut previous.path, self.path.take());
+        self.path = retained_path;
+        previous.previous = Some(Box::new(self));
+        Some(*previous)
+    }
+}
+
+impl InstallManifest {
+    pub fn default_path(scope: InstallScope) -> Result<PathBuf> {
+        Ok(scope.state_dir()?.join(INSTALL_MANIFEST_FILE))
+    }
+
+    /// load manifest, missing manifest is empty
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        match fs::read(path) {
+            Ok(bytes) => Ok(serde_json::from_slice(&bytes)?),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        fs::write(path, serde_json::to_vec_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// directory where replaced binaries are retained
+    pub fn previous_dir(scope: InstallScope) -> Result<PathBuf> {
+        let path = scope.state_dir()?.join(PREVIOUS_DIR);
+        fs::create_dir_all(&path)?;
+        Ok(path)
+    }
+
+    pub fn get(&self, package: &str) -> Option<&InstalledPackage> {
+        self.packages.get(package)
+    }
+
+    /// record binary at `path` was updated in place to `version`, returns false if not recorded
+    pub fn record_update(&mut self, path: &Path, version: &Version) -> Result<bool> {
+        let Some(installed) = self
+            .packages
+            .values_mut()
+            .find(|it| it.path.as_deref() == Some(path))
+        else {
+            return Ok(false);
+        };
+        installed.version = version.clone();
+        installed.checksum = Some(sha256_hex(fs::read(path)?));
+        Ok(true)
+    }
+
+    /// record install of package, `retained` is the copy of binary it replaced
+    pub fn insert(
+        &mut self,
+        package: impl Into<String>,
+        mut installed: InstalledPackage,
+        retained: Option<PathBuf>,
+    ) {
+        let package = package.into();
+        let replaced = self.packages.remove(&package);
+        installed.previous = replaced.zip(retained).map(|(mut replaced, retained)| {
+            replaced.path = Some(retained);
+            replaced.previous = None;
+            Box::new(replaced)
+        });
+        self.packages.insert(package, installed);
+    }
+}
+
+/// install binary to `path` and record it, retaining the binary it replaces
+///
+/// The installed binary is health checked, on failure the replaced binary is restored.
+pub fn install_recorded(
+    scope: InstallScope,
+    package: String,
+    path: PathBuf,
+    bytes: impl AsRef<[u8]>,
+    mut installed: InstalledPackage,
+) -> Result<()> {
+    let manifest_path = InstallManifest::default_path(scope)?;
+    let mut manifest = InstallManifest::load(&manifest_path)?;
+    installed.checksum = Some(sha256_hex(&bytes));
+
+    // binaries installed before manifest existed are not retained, their version is unknown
+    let retained = match path.file_name() {
+        Some(file_name) if manifest.get(&package).is_some() && path.exists() => {
+            let retained_path = InstallManifest::previous_dir(scope)?.join(file_name);
+            fs::copy(&path, &retained_path)?;
+            Some(retained_path)
+        }
+        _ => None,
+    };
+    install_bin(&path, &bytes)?;
+
+    // Restore replaced binary if the installed one is broken
+    if let Err(err) = check_installed(&path, &installed.version) {
+        match &retained {
+            Some(retained_path) => install_bin(&path, fs::read(retained_path)?)?,
+            None => fs::remove_file(&path)?,
+        }
+        return Err(anyhow!(
+            "Installed {package} failed health check and was rolled back: {err}"
+        ));
+    }
+
+    // keep the version side-by-side, so it can be pinned
+    if let Some(file_name) = path
+        .file_name()
+        .and_then(|it| it.to_str())
+        .filter(|it| it.starts_with("fluvio-"))
+    {
+        PluginVersions::open(scope)?.store(file_name, &installed.version, &bytes)?;
+    }
+
+    installed.path = Some(path);
+    manifest.insert(package, installed, retained);
+    manifest.save(&manifest_path)
+}
+
+/// hex encoded sha256 of bytes
+pub fn sha256_hex(bytes: impl AsRef<[u8]>) -> String {
+    hex::encode(Sha256::digest(bytes.as_ref()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_manifest_roundtrip() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join(INSTALL_MANIFEST_FILE);
+
+        let mut manifest = InstallManifest::load(&path).expect("load missing");
+        assert!(manifest.packages.is_empty());
+
+        manifest.insert(
+            "fluvio/fluvio-cloud",
+            installed("0.2.5", "/bin/fluvio-cloud"),
+            None,
+        );
+        manifest.insert(
+            "local/fluvio-foo",
+            InstalledPackage {
+                source: InstallSource::Git {
+                    url: "https://github.com/infinyon/fluvio-foo".to_owned(),
+                    rev: Some("v0.1.0".to_owned()),
+                    commit: "1f2e3d4c".to_owned(),
+                },
+                requirement: None,
+                ..installed("0.1.0", "/bin/fluvio-foo")
+            },
+            None,
+        );
+        manifest.save(&path).expect("save");
+
+        assert_eq!(InstallManifest::load(&path).expect("load"), manifest);
+    }
+
+    #[test]
+    fn test_manifest_retains_previous() {
+        let mut manifest = InstallManifest::default();
+        manifest.insert(
+            "fluvio/fluvio-cloud",
+            installed("0.2.4", "/bin/cloud"),
+            None,
+        );
+        manifest.insert(
+            "fluvio/fluvio-cloud",
+            installed("0.2.5", "/bin/cloud"),
+            Some("/previous/cloud".into()),
+        );
+        manifest.insert(
+            "fluvio/fluvio-cloud",
+            installed("0.2.6", "/bin/cloud"),
+            Some("/previous/cloud".into()),
+        );
+
+        let current = manifest.get("fluvio/fluvio-cloud").expect("installed");
+        assert_eq!(current.version, Version::parse("0.2.6").unwrap());
+        let previous = current.previous.as_ref().expect("previous");
+        assert_eq!(previous.version, Version::parse("0.2.5").unwrap());
+        assert_eq!(previous.path, Some("/previous/cloud".into()));
+        // only one previous version is retained
+        assert!(previous.previous.is_none());
+    }
+
+    #[test]
+    fn test_record_update() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("fluvio-cloud");
+        fs::write(&path, b"updated").expect("write");
+
+        let mut manifest = InstallManifest::default();
+        manifest.insert(
+            "fluvio/fluvio-cloud",
+            installed("0.2.4", &path.to_string_lossy()),
+            None,
+        );
+        let version = Version::parse("0.2.5").unwrap();
+        assert!(manifest.record_update(&path, &version).expect("record"));
+        assert!(!manifest
+            .record_update(&dir.path().join("fluvio-other"), &version)
+            .expect("record"));
+
+        let current = manifest.get("fluvio/fluvio-cloud").expect("installed");
+        assert_eq!(current.version, version);
+        assert_eq!(current.checksum, Some(sha256_hex(b"updated")));
+    }
+
+    #[test]
+    fn test_rolled_back() {
+        let mut manifest = InstallManifest::default();
+        manifest.insert(
+            "fluvio/fluvio-cloud",
+            installed("0.2.4", "/bin/cloud"),
+            None,
+        );
+        assert!(manifest
+            .get("fluvio/fluvio-cloud")
+            .cloned()
+            .expect("installed")
+            .rolled_back()
+            .is_none());
+
+        manifest.insert(
+            "fluvio/fluvio-cloud",
+            installed("0.2.5", "/bin/cloud"),
+            Some("/previous/cloud".into()),
+        );
+        let current = manifest
+            .get("fluvio/fluvio-cloud")
+            .cloned()
+            .expect("installed");
+
+        let rolled_back = current.clone().rolled_back().expect("previous");
+        assert_eq!(rolled_back.version, Version::parse("0.2.4").unwrap());
+        assert_eq!(rolled_back.path, Some("/bin/cloud".into()));
+        let previous = rolled_back.previous.as_ref().expect("previous");
+        assert_eq!(previous.version, Version::parse("0.2.5").unwrap());
+        assert_eq!(previous.path, Some("/previous/cloud".into()));
+
+        // rolling back again restores the newer install
+        assert_eq!(rolled_back.rolled_back(), Some(current));
+    }
+
+    fn installed(version: &str, path: &str) -> InstalledPackage {
+        InstalledPackage {
+            version: Version::parse(version).unwrap(),
+            requirement: Some(VersionReq::parse("^0.2").unwrap()),
+            target: "x86_64-unknown-linux-musl".to_owned(),
+            source: InstallSource::Registry,
+            path: Some(path.into()),
+            checksum: None,
+            previous: None,
+        }
+    }
+}
@@ -1,2 +1,18 @@
+pub mod bootstrap;
+pub mod completions;
+pub mod gc;
+pub mod health;
+pub mod manifest;
+pub mod network;
+pub mod non_interactive;
 pub mod opts;
+pub mod repair;
+pub mod rollback;
+pub mod scope;
+pub mod search;
+pub mod source;
+pub mod toolchain;
+pub mod trust;
+pub mod uninstall;
 pub mod update;
+pub mod versions;
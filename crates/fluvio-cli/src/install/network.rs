@@ -0,0 +1,41 @@
+//!
+//! # Installer Network Settings
+//!
+//! Proxy and extra CA certificates are configured once in the `[installer]` table of the Fluvio
+//! config file, e.g.
+//!
+//! ```toml
+//! [installer]
+//! proxy = "http://proxy.example.com:8080"
+//! ca_certs = ["/etc/ssl/corp-ca.pem"]
+//! ```
+//!
+//! They apply to every request of the installer, to the package index as well as the Hub.
+//!
+use anyhow::Result;
+use tracing::debug;
+
+use fluvio::config::{ConfigFile, InstallerConfig};
+use fluvio_hub_util::htclient::{self, HttpSettings};
+
+/// configure http client of installer from the Fluvio config, if it has installer settings
+pub fn configure_http() -> Result<()> {
+    let config_file = match ConfigFile::load(None) {
+        Ok(config_file) => config_file,
+        Err(err) => {
+            debug!(%err, "no config file, using default network settings");
+            return Ok(());
+        }
+    };
+    let Some(installer) = &config_file.config().installer else {
+        return Ok(());
+    };
+    htclient::configure(&http_settings(installer))
+}
+
+fn http_settings(installer: &InstallerConfig) -> HttpSettings {
+    HttpSettings {
+        proxy: installer.proxy.clone(),
+        ca_certs: installer.ca_certs.clone(),
+    }
+}
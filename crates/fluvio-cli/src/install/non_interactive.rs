@@ -0,0 +1,196 @@
+//!
+//! # Non-interactive Mode
+//!
+//! For CI and provisioning scripts, the installer and updater can run without waiting on the
+//! user. Decisions which would need the user, such as a required CLI update or overwriting an
+//! installed plugin, are made by policies given on the command line. Releases without a trusted
+//! signature follow the `--verify` policy.
+//!
+//! With `--output json`, progress is printed to stderr and a single JSON report to stdout.
+//!
+use anyhow::Result;
+use clap::{Args, ValueEnum};
+use semver::Version;
+use serde::Serialize;
+
+use fluvio_cli_common::install::install_print_to_stderr;
+use fluvio_cli_common::FLUVIO_NON_INTERACTIVE;
+
+use crate::error::CliError;
+
+#[derive(Debug, Clone, Args)]
+pub struct NonInteractiveOpt {
+    /// Never prompt, decisions are made by the policies of the command
+    #[arg(long, env = FLUVIO_NON_INTERACTIVE)]
+    pub non_interactive: bool,
+
+    /// What to do when the CLI must be updated before installing
+    #[arg(long, value_enum, value_name = "POLICY", default_value_t)]
+    pub on_update_required: UpdateRequiredPolicy,
+
+    /// Output format of the result
+    #[arg(long, value_enum, default_value_t)]
+    pub output: OutputFormat,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum UpdateRequiredPolicy {
+    /// Print how to update and stop, without error in interactive mode
+    #[default]
+    Notify,
+    /// Stop with error
+    Fail,
+}
+
+/// What to do with a plugin which is already installed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum ExistingPolicy {
+    #[default]
+    Overwrite,
+    Skip,
+    Fail,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+/// Machine-readable result of an install or update
+#[derive(Debug, Serialize)]
+pub struct Report {
+    pub command: &'static str,
+    pub success: bool,
+    pub packages: Vec<PackageReport>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PackageReport {
+    pub package: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<Version>,
+    pub status: PackageStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PackageStatus {
+    Installed,
+    Updated,
+    UpToDate,
+    Skipped,
+    DryRun,
+    /// downloaded to a directory, not installed
+    Downloaded,
+    Failed,
+}
+
+impl PackageReport {
+    pub fn new(
+        package: impl Into<String>,
+        version: Option<Version>,
+        status: PackageStatus,
+    ) -> Self {
+        Self {
+            package: package.into(),
+            version,
+            status,
+            message: None,
+        }
+    }
+
+    pub fn with_message(mut self, message: impl Into<String>) -> Self {
+        self.message = Some(message.into());
+        self
+    }
+}
+
+impl NonInteractiveOpt {
+    pub fn json(&self) -> bool {
+        self.output == OutputFormat::Json
+    }
+
+    /// route progress output away from stdout if it's reserved for the report
+    pub fn begin(&self) {
+        install_print_to_stderr(self.json());
+    }
+
+    /// error to stop with when the CLI must be updated, none if stopping is not an error
+    pub fn update_required(&self) -> Option<CliError> {
+        let fail = self.on_update_required == UpdateRequiredPolicy::Fail || self.non_interactive;
+        fail.then(|| {
+            CliError::Other("The Fluvio CLI must be updated before installing plugins".to_string())
+        })
+    }
+
+    /// print report of result with JSON output, fails if any package failed
+    pub fn finish(&self, command: &'static str, result: Result<Vec<PackageReport>>) -> Result<()> {
+        let (packages, error) = match result {
+            Ok(packages) => (packages, None),
+            Err(err) => (vec![], Some(err)),
+        };
+        let failed = packages
+            .iter()
+            .filter(|it| it.status == PackageStatus::Failed)
+            .count();
+        let error = error.or_else(|| {
+            let s = if failed != 1 { "s" } else { "" };
+            (failed > 0).then(|| CliError::Other(format!("{failed} {command}{s} failed")).into())
+        });
+
+        if self.json() {
+            let report = Report {
+                command,
+                success: error.is_none(),
+                packages,
+                error: error.as_ref().map(|it| it.to_string()),
+            };
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        }
+        match error {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_json() {
+        let report = Report {
+            command: "install",
+            success: true,
+            packages: vec![
+                PackageReport::new(
+                    "fluvio/fluvio-cloud",
+                    Some(Version::parse("0.2.5").unwrap()),
+                    PackageStatus::Installed,
+                ),
+                PackageReport::new("fluvio/fluvio-foo", None, PackageStatus::Skipped)
+                    .with_message("already installed"),
+            ],
+            error: None,
+        };
+        let json: serde_json::Value = serde_json::to_value(&report).expect("json");
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "command": "install",
+                "success": true,
+                "packages": [
+                    {"package": "fluvio/fluvio-cloud", "version": "0.2.5", "status": "installed"},
+                    {"package": "fluvio/fluvio-foo", "status": "skipped", "message": "already installed"},
+                ],
+            })
+        );
+    }
+}
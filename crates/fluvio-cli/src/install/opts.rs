@@ -1,23 +1,37 @@
-use std::str::FromStr;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 
 use anyhow::{anyhow, Result};
 use clap::Parser;
+use semver::{Version, VersionReq};
 use tracing::debug;
 use current_platform::CURRENT_PLATFORM;
 
-use fluvio_cli_common::error::{HttpError, PackageNotFound};
+use fluvio_cli_common::error::{HttpError, IncompatiblePackage, PackageNotFound};
 use fluvio_cli_common::install::{
-    fetch_latest_version, fetch_package_file, fluvio_extensions_dir, install_bin, install_println,
-    fluvio_bin_dir,
+    check_cli_compatibility, explain_version_resolution, fetch_channel_version, fetch_completion,
+    fetch_install_strategy, fetch_latest_version, fetch_matching_version, fetch_package_file,
+    install_bin, install_println, fluvio_bin_dir, discover_index_layout, with_index_cache,
 };
 
-use fluvio_index::{PackageId, HttpAgent, MaybeVersion};
+use fluvio_index::{
+    GroupName, PackageId, HttpAgent, InstallStrategy, MaybeVersion, PackageVersion, Target,
+    WithVersion,
+};
 use fluvio_channel::{LATEST_CHANNEL_NAME, FLUVIO_RELEASE_CHANNEL};
 use fluvio_hub_util as hubutil;
 use hubutil::{HubAccess, HUB_API_BPKG_AUTH, INFINYON_HUB_REMOTE, FLUVIO_HUB_PROFILE_ENV};
 use hubutil::htclient;
 
 use crate::error::CliError;
+use crate::install::completions::CompletionShell;
+use crate::install::manifest::{install_recorded, InstallManifest, InstallSource, InstalledPackage};
+use crate::install::non_interactive::{ExistingPolicy, NonInteractiveOpt, PackageReport, PackageStatus};
+use crate::install::scope::InstallScope;
+use crate::install::search::package_table;
+use crate::install::trust::{verify_package, Verification, VerifyPolicy};
+use crate::install::source::{resolve_local_binary, GitSource, LOCAL_PACKAGE_GROUP};
+use crate::metadata::{extension_metadata, subcommand_metadata};
 use crate::install::update::{
     check_update_required, prompt_required_update, check_update_available, prompt_available_update,
 };
@@ -27,16 +41,65 @@ use super::update::should_always_print_available_update;
 #[derive(Parser, Debug)]
 pub struct InstallOpt {
     /// The ID of a package to install, e.g. "fluvio/fluvio-cloud".
-    package: Option<PackageId<MaybeVersion>>,
+    pub(crate) package: Option<PackageId<MaybeVersion>>,
     /// Used for testing. Specifies alternate package location, e.g. "test/"
     #[arg(hide = true, long)]
-    prefix: Option<String>,
+    pub(crate) prefix: Option<String>,
     /// Install the latest prerelease rather than the latest release
     ///
     /// If the package ID contains a version (e.g. `fluvio/fluvio:0.6.0`), this is ignored
     #[arg(long)]
     pub develop: bool,
 
+    /// Install the latest release matching this version requirement, e.g. "^0.2"
+    ///
+    /// The resolved version is pinned in the install manifest
+    #[arg(long, value_name = "VERSION", conflicts_with = "develop")]
+    pub version: Option<VersionReq>,
+
+    /// Install plugin from a local binary or cargo project instead of the registry
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["package", "version", "hub", "git"])]
+    pub path: Option<PathBuf>,
+
+    /// Install plugin by building cargo project in git repository, e.g. "<url>#<rev>"
+    #[arg(long, value_name = "URL", conflicts_with_all = ["package", "version", "hub"])]
+    pub git: Option<GitSource>,
+
+    /// List plugins available to install from the package group
+    #[arg(long, conflicts_with_all = ["package", "version", "hub", "path", "git"])]
+    pub list: bool,
+
+    /// Search plugins available to install by name or description
+    #[arg(long, value_name = "QUERY", conflicts_with_all = ["package", "version", "hub", "path", "git"])]
+    pub search: Option<String>,
+
+    /// Package group to list or search
+    #[arg(long, value_name = "GROUP", default_value = "fluvio")]
+    pub group: GroupName,
+
+    /// Install for current user, or system-wide for all users of the machine
+    #[arg(long, value_enum, default_value_t = InstallScope::User)]
+    pub scope: InstallScope,
+
+    /// Signature verification policy, overrides the one set with `fluvio trust policy`
+    #[arg(long, value_enum, value_name = "POLICY")]
+    pub verify: Option<VerifyPolicy>,
+
+    /// Do not install shell completions published with the plugin
+    #[arg(long)]
+    pub no_completions: bool,
+
+    /// Install the plugin even if the release doesn't support this CLI version
+    #[arg(long)]
+    pub ignore_compatibility: bool,
+
+    /// What to do if the plugin is already installed
+    #[arg(long, value_enum, value_name = "POLICY", default_value_t)]
+    pub on_existing: ExistingPolicy,
+
+    #[command(flatten)]
+    pub interaction: NonInteractiveOpt,
+
     /// When this flag is provided, use the hub. Dev-only
     #[arg(long, hide_short_help = true)]
     pub hub: bool,
@@ -47,20 +110,73 @@ pub struct InstallOpt {
     #[arg(long, hide_short_help = true)]
     pub use_hub_defaults: bool,
 
-    /// When this flag is provided, use the hub. Dev-only
-    #[arg(long, hide_short_help = true)]
+    /// Release channel to install from, e.g. "stable" or "latest"
+    #[arg(long, hide_short_help = true, conflicts_with = "version")]
     pub channel: Option<String>,
 
-    /// override default target arch determination
-    #[arg(long, hide_short_help = true)]
-    pub target: Option<String>,
+    /// Download binary for another target, e.g. "aarch64-unknown-linux-musl".
+    /// Binaries for other targets can only be downloaded with `--output-dir`
+    #[arg(long, value_name = "TRIPLE")]
+    pub target: Option<Target>,
+
+    /// Download binary to this directory instead of installing it
+    #[arg(long, value_name = "DIR", conflicts_with_all = ["path", "git", "hub"])]
+    pub output_dir: Option<PathBuf>,
+
+    /// Print why newer releases were passed over when resolving the version to install
+    #[arg(long)]
+    pub verbose: bool,
+
+    /// Fetch the package index again instead of revalidating the cached copy
+    #[arg(long)]
+    pub force_refresh: bool,
+}
+
+/// Install completion script published with the release for the user's shell, if any
+async fn install_completions(
+    agent: &HttpAgent,
+    id: &PackageId<WithVersion>,
+    version: &Version,
+) -> Result<()> {
+    let Some(shell) = CompletionShell::detect() else {
+        debug!("shell not detected, skipping completions");
+        return Ok(());
+    };
+    let Some(script) = fetch_completion(agent, id, version, shell.name()).await? else {
+        debug!(shell = shell.name(), "no completions published");
+        return Ok(());
+    };
+    let path = shell.install(id.name().as_str(), script)?;
+    install_println(format!(
+        "✅ Installed {} completions to {}",
+        shell.name(),
+        path.display()
+    ));
+    if shell == CompletionShell::Zsh {
+        if let Some(dir) = path.parent() {
+            install_println(format!(
+                "💡 Add {} to fpath in ~/.zshrc to enable them",
+                dir.display()
+            ));
+        }
+    }
+    Ok(())
 }
 
 impl InstallOpt {
     pub async fn process(self) -> Result<()> {
-        println!("warning: `fluvio install` is deprecated, use `fvm install` instead.");
-        println!("Refer to https://www.fluvio.io/docs/get-started/linux/#install-fluvio-cli");
+        self.interaction.begin();
+        install_println("warning: `fluvio install` is deprecated, use `fvm install` instead.");
+        install_println(
+            "Refer to https://www.fluvio.io/docs/get-started/linux/#install-fluvio-cli",
+        );
+
+        let result = self.install().await;
+        self.interaction.finish("install", result)
+    }
 
+    /// install requested plugin, returns what was installed
+    async fn install(&self) -> Result<Vec<PackageReport>> {
         if self.hub {
             debug!("Using the hub to install");
 
@@ -95,10 +211,24 @@ impl InstallOpt {
 
             debug!(?bin_install_path, "Writing binary to fs");
             install_bin(bin_install_path, data)?;
+            Ok(vec![PackageReport::new(
+                package_name,
+                None,
+                PackageStatus::Installed,
+            )])
         } else {
+            if self.path.is_some() || self.git.is_some() {
+                return Ok(vec![self.install_local().await?]);
+            }
+
             let agent = match &self.prefix {
-                Some(prefix) => HttpAgent::with_prefix(prefix)?,
-                None => HttpAgent::default(),
+                Some(prefix) => {
+                    with_index_cache(HttpAgent::with_prefix(prefix)?, self.force_refresh)
+                }
+                None => {
+                    let agent = with_index_cache(HttpAgent::default(), self.force_refresh);
+                    discover_index_layout(agent).await
+                }
             };
 
             // Before any "install" type command, check if the CLI needs updating.
@@ -106,57 +236,63 @@ impl InstallOpt {
             let require_update = check_update_required(&agent).await?;
             if require_update {
                 prompt_required_update(&agent).await?;
-                return Ok(());
+                return match self.interaction.update_required() {
+                    Some(err) => Err(err.into()),
+                    None => Ok(vec![]),
+                };
+            }
+
+            if self.list || self.search.is_some() {
+                self.discover(&agent).await?;
+                return Ok(vec![]);
             }
 
             let result = self.install_plugin(&agent).await;
-            match result {
-                Ok(_) => (),
+            let report = match result {
+                Ok(report) => report,
                 Err(err) => match err.downcast_ref::<CliError>() {
                     Some(crate::CliError::IndexError(fluvio_index::Error::MissingTarget(
                         target,
                     ))) => {
+                        let package = self.package.as_ref().ok_or(crate::CliError::Other(
+                            "Package name not provided".to_string(),
+                        ))?;
                         install_println(format!(
                             "❕ Package '{}' is not available for target {}, skipping",
-                            self.package
-                                .ok_or(crate::CliError::Other(
-                                    "Package name not provided".to_string(),
-                                ))?
-                                .name(),
+                            package.name(),
                             target
                         ));
                         install_println("❕ Consider filing an issue to add support for this platform using the link below! 👇");
                         install_println(format!(
                     "❕   https://github.com/infinyon/fluvio/issues/new?title=Support+fluvio-cloud+on+target+{target}"
                 ));
-                        return Ok(());
+                        return Ok(vec![PackageReport::new(
+                            package.to_string(),
+                            None,
+                            PackageStatus::Skipped,
+                        )
+                        .with_message(format!("not available for target {target}"))]);
                     }
                     _ => return Err(err),
                 },
-            }
+            };
 
             // After any "install" command, check if the CLI has an available update,
             // i.e. one that is not required, but present.
             // Sometimes this is printed at the beginning, so we don't print it again here
-            if !should_always_print_available_update() {
+            if !should_always_print_available_update() && !self.interaction.json() {
                 let update_result = check_update_available(&agent, false).await;
                 if let Ok(Some(latest_version)) = update_result {
                     prompt_available_update(&latest_version);
                 }
             }
+            Ok(vec![report])
         }
-        Ok(())
     }
 
-    async fn install_plugin(&self, agent: &HttpAgent) -> Result<()> {
-        let target = if let Some(user_override) = &self.target {
-            fluvio_index::Target::from_str(&user_override.to_string())?
-        } else {
-            // need to analyze to if we can make CURRENT_PLATFORM
-            // the default instead of PACKAGE_TARGET, keep
-            // each use the same for now
-            fluvio_index::package_target()?
-        };
+    pub(crate) async fn install_plugin(&self, agent: &HttpAgent) -> Result<PackageReport> {
+        let target = self.package_target()?;
+        self.check_target(&target)?;
 
         // If a version is given in the package ID, use it. Otherwise, use latest
         let id = match self
@@ -167,6 +303,12 @@ impl InstallOpt {
             ))?
             .maybe_version()
         {
+            Some(_) if self.version.is_some() => {
+                return Err(crate::CliError::Other(
+                    "Package version and --version can't be used together".to_string(),
+                )
+                .into());
+            }
             Some(version) => {
                 install_println(format!(
                     "⏳ Downloading package with provided version: {}...",
@@ -182,11 +324,41 @@ impl InstallOpt {
                     ))?
                     .into_versioned(version)
             }
+            None if self.channel.is_some() => {
+                let id = &self.package.clone().ok_or(crate::CliError::Other(
+                    "Package name not provided".to_string(),
+                ))?;
+                let channel = self.channel.as_deref().expect("channel");
+                install_println(format!(
+                    "🎣 Fetching {channel} channel version for package: {id}..."
+                ));
+                let version = fetch_channel_version(agent, id, &target, channel).await?;
+                let id = id.clone().into_versioned(version.into());
+                install_println(format!(
+                    "⏳ Downloading package with {channel} channel version: {id}..."
+                ));
+                id
+            }
+            None if self.version.is_some() => {
+                let id = &self.package.clone().ok_or(crate::CliError::Other(
+                    "Package name not provided".to_string(),
+                ))?;
+                let req = self.version.as_ref().expect("version requirement");
+                install_println(format!("🎣 Resolving version {req} for package: {id}..."));
+                self.print_resolution(agent, id, &target, Some(req)).await;
+                let version = fetch_matching_version(agent, id, &target, req).await?;
+                let id = id.clone().into_versioned(version.into());
+                install_println(format!(
+                    "⏳ Downloading package with resolved version: {id}..."
+                ));
+                id
+            }
             None => {
                 let id = &self.package.clone().ok_or(crate::CliError::Other(
                     "Package name not provided".to_string(),
                 ))?;
                 install_println(format!("🎣 Fetching latest version for package: {id}..."));
+                self.print_resolution(agent, id, &target, None).await;
                 let version = fetch_latest_version(agent, id, &target, self.develop).await?;
                 let id = id.clone().into_versioned(version.into());
                 install_println(format!(
@@ -196,6 +368,52 @@ impl InstallOpt {
             }
         };
 
+        let strategy = match id.version() {
+            PackageVersion::Semver(version) => {
+                self.check_compatibility(agent, &id, version, &target)
+                    .await?;
+                fetch_install_strategy(agent, &id, version).await?
+            }
+            _ => InstallStrategy::Path,
+        };
+
+        // Install the package to the ~/.fluvio/bin/ dir, or system-wide bin dir
+        // If the plugin name doesn't start with `fluvio-`, then install it to the bin dir
+        // Checked before download, as system-wide install may not be permitted
+        let fluvio_dir = match (&self.output_dir, strategy) {
+            (Some(output_dir), _) => {
+                std::fs::create_dir_all(output_dir)?;
+                output_dir.clone()
+            }
+            (None, InstallStrategy::Path) => self.scope.install_dir(id.name().as_str())?,
+            (None, InstallStrategy::LibDir) => self.scope.lib_dir()?,
+            (None, InstallStrategy::WasmStore) => {
+                return Err(CliError::Other(format!(
+                    "{id} is a SmartModule, use `fluvio hub smartmodule download` to load it into a cluster, or --output-dir to download it"
+                ))
+                .into());
+            }
+            (None, InstallStrategy::Members) => {
+                return Err(CliError::Other(format!(
+                    "{id} is a meta-package and has no files to install"
+                ))
+                .into());
+            }
+        };
+        debug!("{fluvio_dir:#?}");
+        let package_filename =
+            if strategy == InstallStrategy::Path && target.to_string().contains("windows") {
+                format!("{}.exe", id.name().as_str())
+            } else {
+                id.name().to_string()
+            };
+        let package_path = fluvio_dir.join(package_filename);
+        let package_key = format!("{}/{}", id.group(), id.name());
+
+        if let Some(skipped) = self.check_existing(&package_key, &package_path)? {
+            return Ok(skipped);
+        }
+
         // Download the package file from the package registry
         let package_result = fetch_package_file(agent, &id, &target).await;
         let package_file = match package_result {
@@ -206,36 +424,287 @@ impl InstallOpt {
                     version,
                     target,
                 }) => {
-                    install_println(format!(
-                        "❕ Package {package} is not published at {version} for {target}, skipping"
-                    ));
-                    return Ok(());
+                    let message = format!("not published at {version} for {target}");
+                    install_println(format!("❕ Package {package} is {message}, skipping"));
+                    return Ok(PackageReport::new(
+                        package_key,
+                        Some(version.clone()),
+                        PackageStatus::Skipped,
+                    )
+                    .with_message(message));
                 }
                 None => return Err(err),
             },
         };
         install_println("🔑 Downloaded and verified package file");
 
-        // Install the package to the ~/.fluvio/bin/ dir
-        // If the plugin name doesn't start with `fluvio-`, then install it to the bin dir
-        let fluvio_dir = if id.name().to_string().starts_with("fluvio-") {
-            fluvio_extensions_dir()?
-        } else {
-            fluvio_bin_dir()?
+        let release_version = match id.version() {
+            PackageVersion::Semver(version) => Some(version),
+            _ => None,
         };
-        debug!("{fluvio_dir:#?}");
+        match verify_package(
+            agent,
+            &id,
+            release_version,
+            &target,
+            &package_file,
+            self.verify,
+        )
+        .await?
+        {
+            Verification::Verified => install_println("🔏 Verified release signature"),
+            Verification::Unverified(reason) => {
+                install_println(format!("⚠️ Release signature not verified: {reason}"))
+            }
+            Verification::Disabled => (),
+        }
 
-        let package_filename = if target.to_string().contains("windows") {
-            format!("{}.exe", id.name().as_str())
-        } else {
-            id.name().to_string()
+        if self.output_dir.is_some() {
+            install_bin(&package_path, package_file)?;
+            install_println(format!(
+                "✅ Downloaded {id} for {target} to {}",
+                package_path.display()
+            ));
+            return Ok(PackageReport::new(
+                package_key,
+                release_version.cloned(),
+                PackageStatus::Downloaded,
+            ));
+        }
+
+        let Some(version) = release_version else {
+            install_bin(package_path, package_file)?;
+            return Ok(PackageReport::new(
+                package_key,
+                None,
+                PackageStatus::Installed,
+            ));
         };
-        let package_path = fluvio_dir.join(package_filename);
-        install_bin(package_path, package_file)?;
+        install_recorded(
+            self.scope,
+            package_key.clone(),
+            package_path,
+            package_file,
+            InstalledPackage {
+                version: version.clone(),
+                requirement: self.version.clone(),
+                target: target.to_string(),
+                source: InstallSource::Registry,
+                path: None,
+                checksum: None,
+                previous: None,
+            },
+        )?;
 
+        if !self.no_completions && strategy == InstallStrategy::Path {
+            // Completions are optional, failing to install them does not fail the install
+            if let Err(err) = install_completions(agent, &id, version).await {
+                install_println(format!("❕ Failed to install shell completions: {err}"));
+            }
+        }
+        Ok(PackageReport::new(
+            package_key,
+            Some(version.clone()),
+            PackageStatus::Installed,
+        ))
+    }
+
+    /// Print the resolution trace of the package version, if verbose
+    async fn print_resolution(
+        &self,
+        agent: &HttpAgent,
+        id: &PackageId<MaybeVersion>,
+        target: &Target,
+        req: Option<&VersionReq>,
+    ) {
+        if !self.verbose {
+            return;
+        }
+        // latest version is resolved without prereleases, see `fetch_latest_version`
+        match explain_version_resolution(agent, id, target, req, false).await {
+            Ok(explanation) => {
+                for line in explanation.to_string().lines() {
+                    install_println(format!("   {line}"));
+                }
+            }
+            Err(err) => debug!(%err, "failed to explain version resolution"),
+        }
+    }
+
+    /// Print plugins of the package group, with the installed version of each
+    async fn discover(&self, agent: &HttpAgent) -> Result<()> {
+        let target = self.package_target()?;
+
+        let request = agent.request_group(&self.group)?;
+        let body = fluvio_cli_common::http::get_cached_req(agent, &request).await?;
+        let group = agent.group_from_response(&body).await?;
+        let packages = match &self.search {
+            Some(query) => group.search(query),
+            None => group.packages.iter().collect(),
+        };
+        if packages.is_empty() {
+            install_println(format!("❕ No plugins found in group {}", self.group));
+            return Ok(());
+        }
+
+        // Plugins are installed by `fluvio install`, or report their package in metadata
+        let mut installed: BTreeMap<String, Version> = subcommand_metadata()
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|it| {
+                let package = it.meta.package?;
+                Some((
+                    format!("{}/{}", package.group(), package.name()),
+                    it.meta.version,
+                ))
+            })
+            .collect();
+        for scope in [InstallScope::System, InstallScope::User] {
+            let manifest = InstallManifest::default_path(scope)
+                .and_then(InstallManifest::load)
+                .unwrap_or_default();
+            installed.extend(
+                manifest
+                    .packages
+                    .into_iter()
+                    .map(|(package, it)| (package, it.version)),
+            );
+        }
+
+        println!(
+            "{}",
+            package_table(&packages, &installed, &target, self.develop)
+        );
         Ok(())
     }
 
+    /// Install plugin from local path or git repository, bypassing the registry
+    async fn install_local(&self) -> Result<PackageReport> {
+        // checkout of git repository must live until the plugin is installed
+        let (checkout, binary_path, source) = match (&self.path, &self.git) {
+            (Some(path), _) => {
+                install_println(format!("🔧 Installing plugin from {}...", path.display()));
+                let binary_path = resolve_local_binary(path)?;
+                let source = InstallSource::Path {
+                    path: path.canonicalize()?,
+                };
+                (None, binary_path, source)
+            }
+            (None, Some(git)) => {
+                install_println(format!("🎣 Fetching plugin from {git}..."));
+                let (checkout, commit) = git.checkout()?;
+                install_println(format!("🔧 Building plugin at commit {commit}..."));
+                let binary_path = resolve_local_binary(checkout.path())?;
+                let source = InstallSource::Git {
+                    url: git.url.clone(),
+                    rev: git.rev.clone(),
+                    commit,
+                };
+                (Some(checkout), binary_path, source)
+            }
+            (None, None) => {
+                return Err(crate::CliError::Other("No plugin source provided".to_string()).into())
+            }
+        };
+
+        let meta = extension_metadata(&binary_path).ok_or_else(|| {
+            crate::CliError::Other(format!(
+                "{} does not report plugin metadata, it is not a Fluvio plugin",
+                binary_path.display()
+            ))
+        })?;
+        let package_filename = binary_path
+            .file_name()
+            .ok_or_else(|| crate::CliError::Other("Invalid plugin path".to_string()))?
+            .to_owned();
+        let package_key = match &meta.package {
+            Some(package) => format!("{}/{}", package.group(), package.name()),
+            None => format!(
+                "{LOCAL_PACKAGE_GROUP}/{}",
+                package_filename.to_string_lossy()
+            ),
+        };
+
+        let fluvio_dir = self
+            .scope
+            .install_dir(&package_filename.to_string_lossy())?;
+        let package_path = fluvio_dir.join(package_filename);
+        if let Some(skipped) = self.check_existing(&package_key, &package_path)? {
+            return Ok(skipped);
+        }
+        let package_file = std::fs::read(&binary_path)?;
+        drop(checkout);
+
+        install_recorded(
+            self.scope,
+            package_key.clone(),
+            package_path,
+            package_file,
+            InstalledPackage {
+                version: meta.version.clone(),
+                requirement: None,
+                target: fluvio_index::package_target()?.to_string(),
+                source,
+                path: None,
+                checksum: None,
+                previous: None,
+            },
+        )?;
+        install_println(format!(
+            "✅ Successfully installed {} {}",
+            meta.title, meta.version
+        ));
+        Ok(PackageReport::new(
+            package_key,
+            Some(meta.version),
+            PackageStatus::Installed,
+        ))
+    }
+
+    /// Apply policy for plugin which is already installed at `path`
+    ///
+    /// Returns report of skipped install, or none if the install should proceed.
+    fn check_existing(&self, package_key: &str, path: &Path) -> Result<Option<PackageReport>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        match self.on_existing {
+            ExistingPolicy::Overwrite => Ok(None),
+            ExistingPolicy::Skip => {
+                install_println(format!("❕ {package_key} is already installed, skipping"));
+                Ok(Some(
+                    PackageReport::new(package_key, None, PackageStatus::Skipped)
+                        .with_message("already installed"),
+                ))
+            }
+            ExistingPolicy::Fail => Err(CliError::Other(format!(
+                "{package_key} is already installed at {}",
+                path.display()
+            ))
+            .into()),
+        }
+    }
+
+    /// Refuse to install release which doesn't support this CLI, or warn if ignored
+    async fn check_compatibility(
+        &self,
+        agent: &HttpAgent,
+        id: &PackageId<WithVersion>,
+        version: &Version,
+        target: &fluvio_index::Target,
+    ) -> Result<()> {
+        let cli_version = Version::parse(crate::VERSION.trim())?;
+        let result =
+            check_cli_compatibility(agent, id, version, target, self.develop, &cli_version).await;
+        match result {
+            Err(err) if self.ignore_compatibility && err.is::<IncompatiblePackage>() => {
+                install_println(format!("⚠️ {err}"));
+                Ok(())
+            }
+            result => result,
+        }
+    }
+
     fn get_channel(&self) -> String {
         if let Some(user_override) = &self.channel {
             user_override.to_string()
@@ -246,6 +715,25 @@ impl InstallOpt {
         }
     }
 
+    /// target of binaries to install, this machine's unless overridden
+    fn package_target(&self) -> Result<Target> {
+        match &self.target {
+            Some(target) => Ok(target.clone()),
+            None => Ok(fluvio_index::package_target()?),
+        }
+    }
+
+    /// binaries for other targets can only be downloaded, not installed
+    fn check_target(&self, target: &Target) -> Result<()> {
+        if self.output_dir.is_none() && *target != fluvio_index::package_target()? {
+            return Err(CliError::Other(format!(
+                "Binaries for {target} can't be installed on this machine, use --output-dir to download them"
+            ))
+            .into());
+        }
+        Ok(())
+    }
+
     fn get_target(&self) -> String {
         if let Some(user_override) = &self.target {
             user_override.to_string()
@@ -271,10 +759,13 @@ impl InstallOpt {
             systuple = self.get_target(),
         );
         debug!("Downloading binary from hub: {binurl}");
-        let req = http::Request::get(binurl)
+        let mut req = http::Request::get(binurl)
             .header("Authorization", actiontoken)
             .body("")
             .map_err(|_| anyhow!("auth request error"))?;
+        access
+            .sign_request(&mut req)
+            .map_err(|e| anyhow!("request signing error {e}"))?;
 
         let resp = htclient::send(req)
             .await
@@ -294,3 +785,66 @@ impl InstallOpt {
         Ok(data)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(command: &str) -> Result<InstallOpt, clap::error::Error> {
+        InstallOpt::try_parse_from(command.split_whitespace())
+    }
+
+    #[test]
+    fn test_package_target_override() {
+        let opt = parse("install fluvio-cloud").expect("parse");
+        assert_eq!(
+            opt.package_target().expect("target"),
+            fluvio_index::package_target().expect("package target")
+        );
+
+        let opt = parse("install fluvio-cloud --target aarch64-unknown-linux-musl").expect("parse");
+        assert_eq!(
+            opt.package_target().expect("target").as_str(),
+            "aarch64-unknown-linux-musl"
+        );
+
+        // gnu binaries are published as musl ones
+        let opt = parse("install fluvio-cloud --target x86_64-unknown-linux-gnu").expect("parse");
+        assert_eq!(
+            opt.package_target().expect("target"),
+            Target::X86_64UnknownLinuxMusl
+        );
+    }
+
+    #[test]
+    fn test_other_target_requires_output_dir() {
+        let other_target = "wasm32-wasi";
+        let opt = parse(&format!("install fluvio-cloud --target {other_target}")).expect("parse");
+        let err = opt
+            .check_target(&opt.package_target().expect("target"))
+            .expect_err("other target");
+        assert!(err.to_string().contains("use --output-dir"));
+
+        let opt = parse(&format!(
+            "install fluvio-cloud --target {other_target} --output-dir /tmp/fluvio-wasm"
+        ))
+        .expect("parse");
+        assert!(opt
+            .check_target(&opt.package_target().expect("target"))
+            .is_ok());
+
+        let opt = parse("install fluvio-cloud").expect("parse");
+        assert!(opt
+            .check_target(&opt.package_target().expect("target"))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_output_dir_conflicts() {
+        assert!(parse("install --path ./fluvio-foo --output-dir /tmp/out").is_err());
+        assert!(parse(
+            "install --git https://github.com/infinyon/fluvio-foo --output-dir /tmp/out"
+        )
+        .is_err());
+    }
+}
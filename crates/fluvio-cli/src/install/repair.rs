@@ -0,0 +1,173 @@
+use std::fmt;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+use std::str::FromStr;
+
+use anyhow::Result;
+use clap::Parser;
+use tracing::debug;
+
+use fluvio_cli_common::install::{fetch_package_file, install_bin, install_println};
+use fluvio_index::{HttpAgent, MaybeVersion, PackageId, Target};
+
+use crate::error::CliError;
+use crate::install::health::check_installed;
+use crate::install::manifest::{sha256_hex, InstallManifest, InstalledPackage};
+use crate::install::scope::InstallScope;
+
+#[derive(Parser, Debug)]
+pub struct RepairOpt {
+    /// Repair installs for current user, or system-wide ones
+    #[arg(long, value_enum, default_value_t = InstallScope::User)]
+    scope: InstallScope,
+    /// Only verify installed binaries, don't re-download the broken ones
+    #[arg(long)]
+    check: bool,
+}
+
+/// State of installed binary compared to the recorded checksum
+#[derive(Debug, PartialEq, Eq)]
+enum BinaryState {
+    Intact,
+    Missing,
+    Corrupted,
+    /// installed before checksums were recorded
+    Unverifiable,
+}
+
+impl fmt::Display for BinaryState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Intact => write!(f, "intact"),
+            Self::Missing => write!(f, "missing"),
+            Self::Corrupted => write!(f, "checksum mismatch"),
+            Self::Unverifiable => write!(f, "no checksum recorded"),
+        }
+    }
+}
+
+impl RepairOpt {
+    pub async fn process(self) -> Result<()> {
+        let manifest = InstallManifest::load(InstallManifest::default_path(self.scope)?)?;
+        if manifest.packages.is_empty() {
+            install_println("👍 No recorded installs to verify");
+            return Ok(());
+        }
+
+        let agent = HttpAgent::default();
+        let mut broken = 0;
+        for (package, installed) in &manifest.packages {
+            let Some(path) = &installed.path else {
+                continue;
+            };
+            let state = binary_state(path, installed.checksum.as_deref())?;
+            debug!(package, ?path, %state, "verified installed binary");
+            match state {
+                BinaryState::Intact => install_println(format!("✅ {package} {state}")),
+                BinaryState::Unverifiable => install_println(format!("❕ {package} {state}")),
+                BinaryState::Missing | BinaryState::Corrupted if self.check => {
+                    broken += 1;
+                    install_println(format!("❌ {package} {state} at {}", path.display()));
+                }
+                BinaryState::Missing | BinaryState::Corrupted => {
+                    install_println(format!("🔧 {package} {state}, re-downloading..."));
+                    match repair(&agent, package, installed, path).await {
+                        Ok(()) => {
+                            install_println(format!("✅ Repaired {package} {}", installed.version))
+                        }
+                        Err(err) => {
+                            broken += 1;
+                            install_println(format!("❌ Unable to repair {package}: {err}"));
+                        }
+                    }
+                }
+            }
+        }
+
+        if broken > 0 {
+            let s = if broken != 1 { "s" } else { "" };
+            return Err(CliError::Other(format!("{broken} broken install{s}")).into());
+        }
+        Ok(())
+    }
+}
+
+fn binary_state(path: &Path, checksum: Option<&str>) -> Result<BinaryState> {
+    let bytes = match fs::read(path) {
+        Ok(bytes) => bytes,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(BinaryState::Missing),
+        Err(err) => return Err(err.into()),
+    };
+    let state = match checksum {
+        None => BinaryState::Unverifiable,
+        Some(checksum) if sha256_hex(bytes) == checksum => BinaryState::Intact,
+        Some(_) => BinaryState::Corrupted,
+    };
+    Ok(state)
+}
+
+/// download the recorded version of package again and install it over the broken binary
+async fn repair(
+    agent: &HttpAgent,
+    package: &str,
+    installed: &InstalledPackage,
+    path: &Path,
+) -> Result<()> {
+    if !installed.source.is_registry() {
+        return Err(CliError::Other(
+            "installed from local source, reinstall it with `fluvio install`".to_string(),
+        )
+        .into());
+    }
+    let id = PackageId::<MaybeVersion>::from_str(package)?
+        .into_versioned(installed.version.clone().into());
+    let target = Target::from_str(&installed.target)?;
+    let package_file = fetch_package_file(agent, &id, &target).await?;
+    if installed
+        .checksum
+        .as_ref()
+        .is_some_and(|checksum| *checksum != sha256_hex(&package_file))
+    {
+        return Err(CliError::Other(
+            "downloaded binary doesn't match the recorded checksum".to_string(),
+        )
+        .into());
+    }
+
+    install_bin(path, package_file)?;
+    check_installed(path, &installed.version)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_binary_state() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("fluvio-foo");
+        let checksum = sha256_hex(b"binary");
+
+        assert_eq!(
+            binary_state(&path, Some(&checksum)).unwrap(),
+            BinaryState::Missing
+        );
+
+        fs::write(&path, b"binary").unwrap();
+        assert_eq!(
+            binary_state(&path, Some(&checksum)).unwrap(),
+            BinaryState::Intact
+        );
+        assert_eq!(
+            binary_state(&path, None).unwrap(),
+            BinaryState::Unverifiable
+        );
+
+        fs::write(&path, b"binarz").unwrap();
+        assert_eq!(
+            binary_state(&path, Some(&checksum)).unwrap(),
+            BinaryState::Corrupted
+        );
+    }
+}
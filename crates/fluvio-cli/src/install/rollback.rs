@@ -0,0 +1,65 @@
+use std::fs;
+
+use anyhow::Result;
+use clap::Parser;
+use tracing::debug;
+
+use fluvio_cli_common::install::{install_bin, install_println};
+use fluvio_index::{PackageId, MaybeVersion};
+
+use crate::error::CliError;
+use crate::install::manifest::InstallManifest;
+use crate::install::scope::InstallScope;
+
+#[derive(Parser, Debug)]
+pub struct RollbackOpt {
+    /// The ID of an installed package to roll back, e.g. "fluvio/fluvio-cloud".
+    package: PackageId<MaybeVersion>,
+    /// Roll back install for current user, or system-wide one
+    #[arg(long, value_enum, default_value_t = InstallScope::User)]
+    scope: InstallScope,
+}
+
+impl RollbackOpt {
+    pub async fn process(self) -> Result<()> {
+        let package_key = format!("{}/{}", self.package.group(), self.package.name());
+        let manifest_path = InstallManifest::default_path(self.scope)?;
+        let mut manifest = InstallManifest::load(&manifest_path)?;
+
+        let installed = manifest
+            .get(&package_key)
+            .cloned()
+            .ok_or_else(|| CliError::Other(format!("Package {package_key} is not installed")))?;
+        let current_version = installed.version.clone();
+        let rolled_back = installed.rolled_back().ok_or_else(|| {
+            CliError::Other(format!(
+                "Package {package_key} has no previous version to roll back to"
+            ))
+        })?;
+        let (Some(path), Some(retained_path)) = (
+            rolled_back.path.clone(),
+            rolled_back.previous.as_ref().and_then(|it| it.path.clone()),
+        ) else {
+            return Err(CliError::Other(format!(
+                "Package {package_key} has no binary location recorded"
+            ))
+            .into());
+        };
+        debug!(?path, ?retained_path, "restoring previous binary");
+
+        // Swap binaries, each is replaced atomically, so current install is retained in turn
+        let retained = fs::read(&retained_path)?;
+        let current = fs::read(&path)?;
+        install_bin(&path, retained)?;
+        install_bin(&retained_path, current)?;
+
+        install_println(format!(
+            "✅ Rolled back {package_key} from {current_version} to {}",
+            rolled_back.version
+        ));
+        manifest.packages.insert(package_key, rolled_back);
+        manifest.save(&manifest_path)?;
+
+        Ok(())
+    }
+}
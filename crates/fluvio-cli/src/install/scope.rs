@@ -0,0 +1,117 @@
+//!
+//! # Install Scope
+//!
+//! Plugins are installed for the current user under `~/.fluvio`, or system-wide under
+//! `/usr/local` (or `FLUVIO_SYSTEM_PREFIX`) so they are shared by all users of the machine.
+//! Each scope has its own install manifest and retained previous versions.
+//!
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use clap::ValueEnum;
+
+use fluvio_cli_common::install::{
+    fluvio_base_dir, fluvio_bin_dir, fluvio_extensions_dir, fluvio_lib_dir, fluvio_system_bin_dir,
+    fluvio_system_dir, fluvio_system_extensions_dir, fluvio_system_lib_dir,
+};
+
+use crate::error::CliError;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum InstallScope {
+    /// Install for current user
+    #[default]
+    User,
+    /// Install for all users of the machine, requires write access to system prefix
+    System,
+}
+
+impl InstallScope {
+    /// directory of install state, such as install manifest
+    pub fn state_dir(&self) -> Result<PathBuf> {
+        match self {
+            Self::User => fluvio_base_dir(),
+            Self::System => Ok(fluvio_system_dir()),
+        }
+    }
+
+    /// directory of plugins with `fluvio-` prefix
+    pub fn extensions_dir(&self) -> Result<PathBuf> {
+        match self {
+            Self::User => fluvio_extensions_dir(),
+            Self::System => ensure_writable(fluvio_system_extensions_dir()),
+        }
+    }
+
+    /// directory of other binaries
+    pub fn bin_dir(&self) -> Result<PathBuf> {
+        match self {
+            Self::User => fluvio_bin_dir(),
+            Self::System => ensure_writable(fluvio_system_bin_dir()),
+        }
+    }
+
+    /// directory of libraries
+    pub fn lib_dir(&self) -> Result<PathBuf> {
+        match self {
+            Self::User => ensure_writable(fluvio_lib_dir()?),
+            Self::System => ensure_writable(fluvio_system_lib_dir()),
+        }
+    }
+
+    /// install directory of binary with `file_name`
+    pub fn install_dir(&self, file_name: &str) -> Result<PathBuf> {
+        if file_name.starts_with("fluvio-") {
+            self.extensions_dir()
+        } else {
+            self.bin_dir()
+        }
+    }
+}
+
+/// create directory if missing and check it can be written, so install fails before download
+fn ensure_writable(dir: PathBuf) -> Result<PathBuf> {
+    let permission_denied = |dir: &Path| {
+        CliError::Other(format!(
+            "System-wide install requires write access to {}, run as administrator or with sudo",
+            dir.display()
+        ))
+    };
+    match fs::create_dir_all(&dir).and_then(|_| tempfile::tempfile_in(&dir)) {
+        Ok(_) => Ok(dir),
+        Err(err) if err.kind() == ErrorKind::PermissionDenied => {
+            Err(permission_denied(&dir).into())
+        }
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ensure_writable() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let nested = dir.path().join("lib").join("fluvio");
+        assert_eq!(ensure_writable(nested.clone()).expect("writable"), nested);
+        assert!(nested.is_dir());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_ensure_writable_denied() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        fs::set_permissions(dir.path(), fs::Permissions::from_mode(0o500)).expect("chmod");
+        // permissions are not enforced for root
+        if tempfile::tempfile_in(dir.path()).is_ok() {
+            return;
+        }
+        let err = ensure_writable(dir.path().to_path_buf()).expect_err("denied");
+        assert!(err.to_string().contains("requires write access"));
+    }
+}
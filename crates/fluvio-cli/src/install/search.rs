@@ -0,0 +1,87 @@
+use std::collections::BTreeMap;
+
+use comfy_table::{Cell, Row, Table};
+use semver::Version;
+
+use fluvio_index::{Package, Target};
+
+/// Table of installable packages, with the installed version of each by `group/name`
+pub fn package_table(
+    packages: &[&Package],
+    installed: &BTreeMap<String, Version>,
+    target: &Target,
+    prerelease: bool,
+) -> Table {
+    let mut table = Table::new();
+    table.load_preset(comfy_table::presets::NOTHING);
+    table.set_header(Row::from(
+        ["PACKAGE", "LATEST", "INSTALLED", "DESCRIPTION"]
+            .into_iter()
+            .map(Cell::new)
+            .collect::<Vec<_>>(),
+    ));
+    for package in packages {
+        let id = format!("{}/{}", package.group, package.name);
+        let latest = package
+            .latest_release_for_target(target, prerelease)
+            .map(|it| it.version.to_string())
+            .unwrap_or_else(|_| "-".to_string());
+        let installed = installed
+            .get(&id)
+            .map(Version::to_string)
+            .unwrap_or_else(|| "-".to_string());
+        table.add_row(Row::from(vec![
+            Cell::new(id),
+            Cell::new(latest),
+            Cell::new(installed),
+            Cell::new(package.description.as_deref().unwrap_or_default()),
+        ]));
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_package_table() {
+        let mut cloud: Package = serde_json::from_str(
+            r#"{ "name": "fluvio-cloud", "group": "fluvio", "kind": "bin", "description": "Manage InfinyOn Cloud" }"#,
+        )
+        .unwrap();
+        cloud
+            .add_release(Version::parse("0.2.5").unwrap(), Target::X86_64AppleDarwin)
+            .unwrap();
+        let cdk: Package =
+            serde_json::from_str(r#"{ "name": "cdk", "group": "fluvio", "kind": "bin" }"#).unwrap();
+        let installed = BTreeMap::from([(
+            "fluvio/fluvio-cloud".to_string(),
+            Version::parse("0.2.4").unwrap(),
+        )]);
+
+        let table = package_table(
+            &[&cloud, &cdk],
+            &installed,
+            &Target::X86_64AppleDarwin,
+            false,
+        )
+        .to_string();
+        let lines: Vec<Vec<&str>> = table
+            .lines()
+            .map(|line| line.split_whitespace().collect())
+            .collect();
+        assert_eq!(
+            lines[1],
+            vec![
+                "fluvio/fluvio-cloud",
+                "0.2.5",
+                "0.2.4",
+                "Manage",
+                "InfinyOn",
+                "Cloud"
+            ]
+        );
+        assert_eq!(lines[2], vec!["fluvio/cdk", "-", "-"]);
+    }
+}
@@ -0,0 +1,168 @@
+//!
+//! # Local Plugin Sources
+//!
+//! Plugins can be installed from a local binary, a local cargo project or a cargo project in a
+//! git repository. Projects are built with `cargo build --release` and must build exactly one
+//! binary.
+//!
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::str::FromStr;
+
+use anyhow::{anyhow, Result};
+use tempfile::TempDir;
+use tracing::debug;
+
+use fluvio_command::CommandExt;
+
+/// group of package key in install manifest, for plugins which don't declare their package
+pub const LOCAL_PACKAGE_GROUP: &str = "local";
+
+/// Git repository with optional revision, parsed from `<url>#<rev>`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitSource {
+    pub url: String,
+    pub rev: Option<String>,
+}
+
+impl FromStr for GitSource {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (url, rev) = match s.rsplit_once('#') {
+            Some((url, rev)) if !rev.is_empty() => (url, Some(rev.to_owned())),
+            Some((url, _)) => (url, None),
+            None => (s, None),
+        };
+        if url.is_empty() {
+            return Err(anyhow!("git url is empty"));
+        }
+        Ok(Self {
+            url: url.to_owned(),
+            rev,
+        })
+    }
+}
+
+impl fmt::Display for GitSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.rev {
+            Some(rev) => write!(f, "{}#{rev}", self.url),
+            None => write!(f, "{}", self.url),
+        }
+    }
+}
+
+impl GitSource {
+    /// clone repository into temporary directory and check out revision, returns commit
+    pub fn checkout(&self) -> Result<(TempDir, String)> {
+        let dir = tempfile::Builder::new()
+            .prefix("fluvio-plugin-git")
+            .tempdir()?;
+        Command::new("git")
+            .arg("clone")
+            .arg("--quiet")
+            .arg(&self.url)
+            .arg(dir.path())
+            .result()?;
+        if let Some(rev) = &self.rev {
+            Command::new("git")
+                .arg("-C")
+                .arg(dir.path())
+                .args(["checkout", "--quiet", rev])
+                .result()?;
+        }
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(dir.path())
+            .args(["rev-parse", "HEAD"])
+            .result()?;
+        let commit = String::from_utf8(output.stdout)?.trim().to_owned();
+        Ok((dir, commit))
+    }
+}
+
+/// binary of plugin at `path`, cargo projects are built first
+pub fn resolve_local_binary(path: &Path) -> Result<PathBuf> {
+    if path.is_file() {
+        return Ok(path.to_owned());
+    }
+    if !path.join("Cargo.toml").is_file() {
+        return Err(anyhow!(
+            "{} is neither a plugin binary nor a cargo project",
+            path.display()
+        ));
+    }
+    let output = Command::new("cargo")
+        .args([
+            "build",
+            "--release",
+            "--message-format=json-render-diagnostics",
+        ])
+        .current_dir(path)
+        .result()?;
+    built_binary(&output.stdout)
+}
+
+/// executable built by cargo, from its json messages
+fn built_binary(messages: &[u8]) -> Result<PathBuf> {
+    let mut executables: Vec<PathBuf> = serde_json::Deserializer::from_slice(messages)
+        .into_iter::<serde_json::Value>()
+        .filter_map(|message| message.ok())
+        .filter(|message| message["reason"] == "compiler-artifact")
+        .filter_map(|message| message["executable"].as_str().map(PathBuf::from))
+        .collect();
+    debug!(?executables, "built executables");
+    match executables.len() {
+        1 => Ok(executables.remove(0)),
+        0 => Err(anyhow!("project does not build a binary")),
+        _ => Err(anyhow!(
+            "project builds more than one binary: {}",
+            executables
+                .iter()
+                .map(|it| it.display().to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_git_source() {
+        let source: GitSource = "https://github.com/infinyon/fluvio-foo#v0.1.0"
+            .parse()
+            .expect("parse");
+        assert_eq!(source.url, "https://github.com/infinyon/fluvio-foo");
+        assert_eq!(source.rev.as_deref(), Some("v0.1.0"));
+        assert_eq!(
+            source.to_string(),
+            "https://github.com/infinyon/fluvio-foo#v0.1.0"
+        );
+
+        let source: GitSource = "https://github.com/infinyon/fluvio-foo"
+            .parse()
+            .expect("parse");
+        assert_eq!(source.rev, None);
+
+        assert!("#main".parse::<GitSource>().is_err());
+    }
+
+    #[test]
+    fn test_built_binary() {
+        let messages = br#"{"reason":"compiler-artifact","target":{"name":"dep"},"executable":null}
+{"reason":"compiler-artifact","target":{"name":"fluvio-foo"},"executable":"/target/release/fluvio-foo"}
+{"reason":"build-finished","success":true}"#;
+        assert_eq!(
+            built_binary(messages).expect("binary"),
+            PathBuf::from("/target/release/fluvio-foo")
+        );
+
+        let messages = br#"{"reason":"build-finished","success":true}"#;
+        assert!(built_binary(messages).is_err());
+    }
+}
@@ -0,0 +1,443 @@
+//!
+//! # Toolchain Bundles
+//!
+//! A toolchain bundle captures the Fluvio CLI and the plugins recorded in the install manifest,
+//! pinned to their exact versions, in a single gzipped tar archive. Importing the bundle installs
+//! the same binaries without contacting the package index, so a workstation setup can be
+//! replicated on another machine, including air-gapped ones.
+//!
+//! The archive holds a `toolchain.json` lock and the binaries under `bin/`. The lock records the
+//! sha256 of each binary and the release signature published on the index when the bundle was
+//! exported. Imported binaries must match their digest, and their signature is checked with the
+//! trusted publisher keys according to the verification policy.
+//!
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::path::{Component, Path, PathBuf};
+
+use anyhow::{anyhow, Result};
+use clap::Parser;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use semver::{Version, VersionReq};
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use fluvio_cli_common::install::{fetch_package_signature, install_bin, install_println};
+use fluvio_index::{HttpAgent, PackageId, Target};
+
+use crate::error::CliError;
+use crate::install::manifest::{
+    install_recorded, sha256_hex, InstallManifest, InstallSource, InstalledPackage,
+};
+use crate::install::scope::InstallScope;
+use crate::install::trust::{verify_bundled_package, Verification, VerifyPolicy};
+use crate::install::update::{release_channel, FLUVIO_CLI_PACKAGE_ID};
+
+const TOOLCHAIN_LOCK_FILE: &str = "toolchain.json";
+const BUNDLE_BIN_DIR: &str = "bin";
+
+#[derive(Debug, Parser)]
+pub enum ToolchainCmd {
+    /// Export the CLI and installed plugins to a bundle
+    #[command(name = "export")]
+    Export(ExportOpt),
+    /// Install the CLI and plugins from a bundle, without contacting the package index
+    #[command(name = "import")]
+    Import(ImportOpt),
+}
+
+#[derive(Debug, Parser)]
+pub struct ExportOpt {
+    /// Path of bundle to write, e.g. "toolchain.tar.gz"
+    output: PathBuf,
+    /// Export install for current user, or system-wide one
+    #[arg(long, value_enum, default_value_t = InstallScope::User)]
+    scope: InstallScope,
+}
+
+#[derive(Debug, Parser)]
+pub struct ImportOpt {
+    /// Path of bundle to import
+    bundle: PathBuf,
+    /// Import for current user, or system-wide
+    #[arg(long, value_enum, default_value_t = InstallScope::User)]
+    scope: InstallScope,
+    /// Only install the plugins, keep the current CLI
+    #[arg(long)]
+    skip_cli: bool,
+    /// What to do if a release signature can't be verified, defaults to the stored policy
+    #[arg(long, value_enum, value_name = "POLICY")]
+    verify: Option<VerifyPolicy>,
+}
+
+/// Exact versions of binaries in bundle
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct ToolchainLock {
+    /// version of the Fluvio CLI
+    pub fluvio: Version,
+    /// hex encoded sha256 of the Fluvio CLI binary
+    pub fluvio_sha256: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fluvio_signature: Option<String>,
+    pub channel: String,
+    pub target: String,
+    #[serde(default)]
+    pub packages: BTreeMap<String, LockedPackage>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LockedPackage {
+    pub version: Version,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub requirement: Option<VersionReq>,
+    pub target: String,
+    #[serde(default, skip_serializing_if = "InstallSource::is_registry")]
+    pub source: InstallSource,
+    /// file name of binary in bundle
+    pub file: String,
+    /// hex encoded sha256 of binary
+    pub sha256: String,
+    /// release signature published on the index, none if package is not signed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+}
+
+impl ToolchainLock {
+    /// lock packages of manifest with a binary, returns the lock and the binaries by file name
+    pub fn from_manifest(
+        fluvio: Version,
+        fluvio_sha256: String,
+        channel: String,
+        target: String,
+        manifest: &InstallManifest,
+    ) -> Result<(Self, Vec<(String, PathBuf)>)> {
+        let mut binaries = Vec::new();
+        let mut packages = BTreeMap::new();
+        for (package, installed) in &manifest.packages {
+            let Some((path, file)) = installed.path.as_ref().and_then(|path| {
+                let file = path.file_name()?.to_string_lossy().to_string();
+                Some((path, file))
+            }) else {
+                debug!(package, "no binary recorded, skipping");
+                continue;
+            };
+            let sha256 = sha256_hex(
+                fs::read(path)
+                    .map_err(|err| anyhow!("unable to read {}: {err}", path.display()))?,
+            );
+            binaries.push((file.clone(), path.clone()));
+            packages.insert(
+                package.clone(),
+                LockedPackage {
+                    version: installed.version.clone(),
+                    requirement: installed.requirement.clone(),
+                    target: installed.target.clone(),
+                    source: installed.source.clone(),
+                    file,
+                    sha256,
+                    signature: None,
+                },
+            );
+        }
+        let lock = Self {
+            fluvio,
+            fluvio_sha256,
+            fluvio_signature: None,
+            channel,
+            target,
+            packages,
+        };
+        Ok((lock, binaries))
+    }
+
+    /// record signatures published on the index, packages which aren't signed are kept unsigned
+    async fn fetch_signatures(&mut self, agent: &HttpAgent) {
+        self.fluvio_signature =
+            fetch_signature(agent, FLUVIO_CLI_PACKAGE_ID, &self.fluvio, &self.target).await;
+        for (package, locked) in self
+            .packages
+            .iter_mut()
+            .filter(|(_, locked)| locked.source.is_registry())
+        {
+            locked.signature =
+                fetch_signature(agent, package, &locked.version, &locked.target).await;
+        }
+    }
+}
+
+async fn fetch_signature(
+    agent: &HttpAgent,
+    package: &str,
+    version: &Version,
+    target: &str,
+) -> Option<String> {
+    let id: PackageId = package.parse().ok()?;
+    let target: Target = target.parse().ok()?;
+    match fetch_package_signature(agent, &id, version, &target).await {
+        Ok(signature) => Some(signature),
+        Err(err) => {
+            debug!(package, %version, %err, "no release signature");
+            None
+        }
+    }
+}
+
+impl ToolchainCmd {
+    pub async fn process(self) -> Result<()> {
+        match self {
+            Self::Export(export) => export.process().await,
+            Self::Import(import) => import.process(),
+        }
+    }
+}
+
+impl ExportOpt {
+    async fn process(self) -> Result<()> {
+        let manifest = InstallManifest::load(InstallManifest::default_path(self.scope)?)?;
+        let cli = std::env::current_exe()?;
+        let (mut lock, mut binaries) = ToolchainLock::from_manifest(
+            Version::parse(crate::VERSION.trim())?,
+            sha256_hex(fs::read(&cli)?),
+            release_channel(),
+            fluvio_index::package_target()?.to_string(),
+            &manifest,
+        )?;
+        lock.fetch_signatures(&HttpAgent::default()).await;
+        let cli_file = cli_file_name();
+        binaries.push((cli_file.to_owned(), cli));
+
+        let mut bundle = tar::Builder::new(GzEncoder::new(
+            File::create(&self.output)?,
+            Compression::default(),
+        ));
+        let lock_json = serde_json::to_vec_pretty(&lock)?;
+        let mut header = tar::Header::new_gnu();
+        header.set_size(lock_json.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        bundle.append_data(&mut header, TOOLCHAIN_LOCK_FILE, lock_json.as_slice())?;
+        for (file, path) in &binaries {
+            bundle
+                .append_path_with_name(path, Path::new(BUNDLE_BIN_DIR).join(file))
+                .map_err(|err| anyhow!("unable to add {} to bundle: {err}", path.display()))?;
+        }
+        bundle.into_inner()?.finish()?;
+
+        install_println(format!(
+            "📦 Exported fluvio {} and {} plugin(s) to {}",
+            lock.fluvio,
+            lock.packages.len(),
+            self.output.display()
+        ));
+        Ok(())
+    }
+}
+
+impl ImportOpt {
+    fn process(self) -> Result<()> {
+        let unpacked = tempfile::tempdir()?;
+        tar::Archive::new(GzDecoder::new(File::open(&self.bundle)?))
+            .unpack(unpacked.path())
+            .map_err(|err| anyhow!("unable to read bundle {}: {err}", self.bundle.display()))?;
+        let lock: ToolchainLock = serde_json::from_slice(
+            &fs::read(unpacked.path().join(TOOLCHAIN_LOCK_FILE))
+                .map_err(|_| CliError::Other("Bundle has no toolchain lock".to_string()))?,
+        )?;
+        let target = fluvio_index::package_target()?.to_string();
+        if lock.target != target {
+            return Err(CliError::Other(format!(
+                "Bundle was exported for {}, this machine is {target}",
+                lock.target
+            ))
+            .into());
+        }
+        let bin_dir = unpacked.path().join(BUNDLE_BIN_DIR);
+
+        // every binary is checked before anything is installed
+        let cli = if self.skip_cli {
+            None
+        } else {
+            let bytes = fs::read(bin_dir.join(cli_file_name()))
+                .map_err(|_| anyhow!("Bundle is missing fluvio binary"))?;
+            self.verify_binary(
+                FLUVIO_CLI_PACKAGE_ID,
+                &lock.fluvio_sha256,
+                lock.fluvio_signature.as_deref(),
+                &bytes,
+            )?;
+            Some(bytes)
+        };
+        let mut plugins = Vec::with_capacity(lock.packages.len());
+        for (package, locked) in lock.packages {
+            validate_file_name(&locked.file)?;
+            let bytes = fs::read(bin_dir.join(&locked.file))
+                .map_err(|_| anyhow!("Bundle is missing binary of {package}"))?;
+            if locked.source.is_registry() {
+                self.verify_binary(
+                    &package,
+                    &locked.sha256,
+                    locked.signature.as_deref(),
+                    &bytes,
+                )?;
+            } else {
+                verify_digest(&package, &locked.sha256, &bytes)?;
+            }
+            plugins.push((package, locked, bytes));
+        }
+
+        if let Some(bytes) = cli {
+            let cli_path = self.scope.bin_dir()?.join(cli_file_name());
+            install_bin(&cli_path, bytes)?;
+            install_println(format!(
+                "✅ Installed fluvio {} to {}",
+                lock.fluvio,
+                cli_path.display()
+            ));
+        }
+
+        for (package, locked, bytes) in plugins {
+            let path = self.scope.install_dir(&locked.file)?.join(&locked.file);
+            install_recorded(
+                self.scope,
+                package.clone(),
+                path,
+                bytes,
+                InstalledPackage {
+                    version: locked.version.clone(),
+                    requirement: locked.requirement,
+                    target: locked.target,
+                    source: locked.source,
+                    path: None,
+                    checksum: None,
+                    previous: None,
+                },
+            )?;
+            install_println(format!("✅ Installed {package} {}", locked.version));
+        }
+        Ok(())
+    }
+
+    /// check digest of binary and its release signature with trusted publisher key
+    fn verify_binary(
+        &self,
+        package: &str,
+        sha256: &str,
+        signature: Option<&str>,
+        bytes: &[u8],
+    ) -> Result<()> {
+        verify_digest(package, sha256, bytes)?;
+        let id: PackageId = package.parse()?;
+        match verify_bundled_package(&id, signature, bytes, self.verify)? {
+            Verification::Verified => install_println(format!("🔏 Verified {package}")),
+            Verification::Unverified(reason) => install_println(format!(
+                "⚠️ Release signature of {package} not verified: {reason}"
+            )),
+            Verification::Disabled => {}
+        }
+        Ok(())
+    }
+}
+
+fn verify_digest(package: &str, sha256: &str, bytes: &[u8]) -> Result<()> {
+    if sha256_hex(bytes) != sha256 {
+        return Err(CliError::Other(format!(
+            "Binary of {package} in bundle doesn't match its sha256"
+        ))
+        .into());
+    }
+    Ok(())
+}
+
+/// binaries are installed by file name, which must not escape the install directory
+fn validate_file_name(file: &str) -> Result<()> {
+    let mut components = Path::new(file).components();
+    match (components.next(), components.next()) {
+        (Some(Component::Normal(name)), None) if name == file => Ok(()),
+        _ => Err(CliError::Other(format!("Invalid binary name in bundle: {file}")).into()),
+    }
+}
+
+fn cli_file_name() -> &'static str {
+    if cfg!(windows) {
+        "fluvio.exe"
+    } else {
+        "fluvio"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lock_from_manifest() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let cloud_path = dir.path().join("fluvio-cloud");
+        fs::write(&cloud_path, b"cloud").expect("write");
+
+        let mut manifest = InstallManifest::default();
+        manifest.insert(
+            "fluvio/fluvio-cloud",
+            InstalledPackage {
+                version: Version::parse("0.2.5").unwrap(),
+                requirement: Some(VersionReq::parse("^0.2").unwrap()),
+                target: "x86_64-unknown-linux-musl".to_owned(),
+                source: InstallSource::Registry,
+                path: Some(cloud_path.clone()),
+                checksum: None,
+                previous: None,
+            },
+            None,
+        );
+        manifest.insert(
+            "fluvio/fluvio-orphan",
+            InstalledPackage {
+                version: Version::parse("0.1.0").unwrap(),
+                requirement: None,
+                target: "x86_64-unknown-linux-musl".to_owned(),
+                source: InstallSource::Registry,
+                path: None,
+                checksum: None,
+                previous: None,
+            },
+            None,
+        );
+
+        let (lock, binaries) = ToolchainLock::from_manifest(
+            Version::parse("0.11.0").unwrap(),
+            sha256_hex(b"fluvio"),
+            "stable".to_owned(),
+            "x86_64-unknown-linux-musl".to_owned(),
+            &manifest,
+        )
+        .expect("lock");
+
+        assert_eq!(binaries, vec![("fluvio-cloud".to_owned(), cloud_path)]);
+        let locked = lock.packages.get("fluvio/fluvio-cloud").expect("locked");
+        assert_eq!(locked.version, Version::parse("0.2.5").unwrap());
+        assert_eq!(locked.file, "fluvio-cloud");
+        assert_eq!(locked.sha256, sha256_hex(b"cloud"));
+        assert!(verify_digest("fluvio/fluvio-cloud", &locked.sha256, b"cloud").is_ok());
+        assert!(verify_digest("fluvio/fluvio-cloud", &locked.sha256, b"tampered").is_err());
+        assert!(!lock.packages.contains_key("fluvio/fluvio-orphan"));
+
+        let json = serde_json::to_string(&lock).expect("serialize");
+        let parsed: ToolchainLock = serde_json::from_str(&json).expect("deserialize");
+        assert_eq!(parsed, lock);
+    }
+
+    #[test]
+    fn test_validate_file_name() {
+        assert!(validate_file_name("fluvio-cloud").is_ok());
+        assert!(validate_file_name("fluvio-cloud.exe").is_ok());
+        assert!(validate_file_name("").is_err());
+        assert!(validate_file_name(".").is_err());
+        assert!(validate_file_name("..").is_err());
+        assert!(validate_file_name("../fluvio-cloud").is_err());
+        assert!(validate_file_name("bin/fluvio-cloud").is_err());
+        assert!(validate_file_name("/usr/bin/fluvio-cloud").is_err());
+        assert!(validate_file_name("fluvio-cloud/").is_err());
+    }
+}
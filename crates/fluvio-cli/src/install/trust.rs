@@ -0,0 +1,300 @@
+//!
+//! # Release Signatures
+//!
+//! Package files on the index may be signed by their publisher with an ed25519 key, the hex
+//! encoded signature is published next to the file. Users trust a publisher by importing its
+//! public key for the package group. The installer policy decides what happens when a release
+//! can't be verified: `off` skips verification, `warn` installs with a warning and `enforce`
+//! refuses to install.
+//!
+//! Plugins installed from local path or git are not verified.
+//!
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Result};
+use clap::{Parser, ValueEnum};
+use semver::Version;
+use serde::{Deserialize, Serialize};
+
+use fluvio_cli_common::install::{fetch_package_signature, fluvio_base_dir, install_println};
+use fluvio_hub_util::keymgmt::{PublicKey, Signature};
+use fluvio_index::{HttpAgent, PackageId, Target};
+
+use crate::error::CliError;
+
+const TRUST_STORE_FILE: &str = "trusted-keys.json";
+
+/// What the installer does with releases which can't be verified
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VerifyPolicy {
+    #[default]
+    Off,
+    Warn,
+    Enforce,
+}
+
+impl fmt::Display for VerifyPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Off => write!(f, "off"),
+            Self::Warn => write!(f, "warn"),
+            Self::Enforce => write!(f, "enforce"),
+        }
+    }
+}
+
+/// Installer policy and trusted publisher keys, by package group
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct TrustStore {
+    #[serde(default)]
+    pub policy: VerifyPolicy,
+    /// hex encoded ed25519 public keys
+    #[serde(default)]
+    pub keys: BTreeMap<String, String>,
+}
+
+impl TrustStore {
+    pub fn default_path() -> Result<PathBuf> {
+        Ok(fluvio_base_dir()?.join(TRUST_STORE_FILE))
+    }
+
+    /// load store, missing store has no keys and verification off
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        match fs::read(path) {
+            Ok(bytes) => Ok(serde_json::from_slice(&bytes)?),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        fs::write(path, serde_json::to_vec_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// Outcome of verification allowed by policy
+#[derive(Debug, PartialEq, Eq)]
+pub enum Verification {
+    Disabled,
+    Verified,
+    /// release is not verified, with reason
+    Unverified(String),
+}
+
+/// Verify signature of package file, according to `policy` or the stored one
+///
+/// Returns error if the release can't be verified and policy is enforced.
+pub async fn verify_package<T>(
+    agent: &HttpAgent,
+    id: &PackageId<T>,
+    version: Option<&Version>,
+    target: &Target,
+    package_file: &[u8],
+    policy: Option<VerifyPolicy>,
+) -> Result<Verification> {
+    let store = TrustStore::load(TrustStore::default_path()?)?;
+    let policy = policy.unwrap_or(store.policy);
+    if policy == VerifyPolicy::Off {
+        return Ok(Verification::Disabled);
+    }
+
+    let group = id.group().to_string();
+    let result = match (store.keys.get(&group), version) {
+        (None, _) => Err(format!("no trusted key for publisher {group}, import it with `fluvio trust import {group} <KEY>`")),
+        (Some(_), None) => Err("release version is not resolved".to_string()),
+        (Some(key), Some(version)) => {
+            match fetch_package_signature(agent, id, version, target).await {
+                Ok(signature) => verify_signature(key, &signature, package_file)
+                    .map_err(|err| format!("invalid signature: {err}")),
+                Err(err) => Err(format!("signature not available: {err}")),
+            }
+        }
+    };
+
+    apply_policy(id, policy, result)
+}
+
+/// Verify package file with signature recorded when it was bundled, without contacting the index
+///
+/// Returns error if the release can't be verified and policy is enforced.
+pub fn verify_bundled_package<T>(
+    id: &PackageId<T>,
+    signature: Option<&str>,
+    package_file: &[u8],
+    policy: Option<VerifyPolicy>,
+) -> Result<Verification> {
+    let store = TrustStore::load(TrustStore::default_path()?)?;
+    let policy = policy.unwrap_or(store.policy);
+    if policy == VerifyPolicy::Off {
+        return Ok(Verification::Disabled);
+    }
+
+    let group = id.group().to_string();
+    let result = match (store.keys.get(&group), signature) {
+        (None, _) => Err(format!("no trusted key for publisher {group}, import it with `fluvio trust import {group} <KEY>`")),
+        (Some(_), None) => Err("bundle has no signature of release".to_string()),
+        (Some(key), Some(signature)) => verify_signature(key, signature, package_file)
+            .map_err(|err| format!("invalid signature: {err}")),
+    };
+
+    apply_policy(id, policy, result)
+}
+
+fn apply_policy<T>(
+    id: &PackageId<T>,
+    policy: VerifyPolicy,
+    result: Result<(), String>,
+) -> Result<Verification> {
+    match result {
+        Ok(()) => Ok(Verification::Verified),
+        Err(reason) if policy == VerifyPolicy::Enforce => {
+            Err(CliError::Other(format!("Refusing to install {}: {reason}", id.name())).into())
+        }
+        Err(reason) => Ok(Verification::Unverified(reason)),
+    }
+}
+
+/// verify hex encoded signature of `bytes` with hex encoded public key
+pub fn verify_signature(public_key: &str, signature: &str, bytes: &[u8]) -> Result<()> {
+    let public_key =
+        PublicKey::from_hex(public_key).map_err(|err| anyhow!("invalid public key: {err}"))?;
+    let signature = hex::decode(signature)
+        .ok()
+        .and_then(|it| Signature::from_slice(&it).ok())
+        .ok_or_else(|| anyhow!("malformed signature"))?;
+    public_key
+        .verify(bytes, &signature)
+        .map_err(|_| anyhow!("signature does not match package file"))
+}
+
+/// Manage publisher keys trusted by the installer and its verification policy
+#[derive(Debug, Parser)]
+pub enum TrustCmd {
+    /// Trust key of publisher for packages of its group
+    ///
+    /// The key can be hex encoded, an OpenSSH ed25519 public key, or a path to PEM file
+    #[command(name = "import")]
+    Import {
+        /// Package group of publisher, e.g. "fluvio"
+        group: String,
+        key: String,
+    },
+    /// List trusted publisher keys and the verification policy
+    #[command(name = "list")]
+    List,
+    /// Stop trusting key of publisher
+    #[command(name = "remove")]
+    Remove { group: String },
+    /// Set what the installer does with releases which can't be verified
+    #[command(name = "policy")]
+    Policy {
+        #[arg(value_enum)]
+        policy: VerifyPolicy,
+    },
+}
+
+impl TrustCmd {
+    pub fn process(self) -> Result<()> {
+        let path = TrustStore::default_path()?;
+        let mut store = TrustStore::load(&path)?;
+        match self {
+            Self::Import { group, key } => {
+                let public_key = parse_public_key(&key)?;
+                let hex_key = public_key.to_hex();
+                if let Some(previous) = store.keys.insert(group.clone(), hex_key.clone()) {
+                    if previous != hex_key {
+                        install_println(format!("❕ Replaced trusted key {previous} of {group}"));
+                    }
+                }
+                store.save(&path)?;
+                install_println(format!("🔏 Trusted key {hex_key} for packages of {group}"));
+                if store.policy == VerifyPolicy::Off {
+                    install_println(
+                        "💡 Verification is off, enable it with `fluvio trust policy warn` or `fluvio trust policy enforce`",
+                    );
+                }
+            }
+            Self::List => {
+                println!("policy: {}", store.policy);
+                if store.keys.is_empty() {
+                    println!("no trusted keys");
+                }
+                for (group, key) in &store.keys {
+                    println!("{group:<20} {key}");
+                }
+            }
+            Self::Remove { group } => {
+                if store.keys.remove(&group).is_none() {
+                    return Err(CliError::Other(format!("No trusted key for {group}")).into());
+                }
+                store.save(&path)?;
+                install_println(format!("🔓 Removed trusted key of {group}"));
+            }
+            Self::Policy { policy } => {
+                store.policy = policy;
+                store.save(&path)?;
+                install_println(format!("🔏 Signature verification policy set to {policy}"));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn parse_public_key(key: &str) -> Result<PublicKey> {
+    let result = if Path::new(key).is_file() {
+        PublicKey::read_from_file(key)
+    } else if key.starts_with("ssh-ed25519") {
+        PublicKey::from_ssh(key)
+    } else {
+        PublicKey::from_hex(key)
+    };
+    result.map_err(|err| anyhow!("invalid public key: {err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use fluvio_hub_util::keymgmt::Keypair;
+
+    use super::*;
+
+    #[test]
+    fn test_verify_signature() {
+        let keypair = Keypair::new().expect("keypair");
+        let public_key = keypair.public().to_hex();
+        let bytes = b"package file";
+        let signature = hex::encode(keypair.sign(bytes).expect("sign").to_bytes());
+
+        assert!(verify_signature(&public_key, &signature, bytes).is_ok());
+        assert!(verify_signature(&public_key, &signature, b"tampered file").is_err());
+        assert!(verify_signature(&public_key, "not hex", bytes).is_err());
+
+        let other_key = Keypair::new().expect("keypair").public().to_hex();
+        assert!(verify_signature(&other_key, &signature, bytes).is_err());
+    }
+
+    #[test]
+    fn test_trust_store_roundtrip() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join(TRUST_STORE_FILE);
+
+        let mut store = TrustStore::load(&path).expect("load missing");
+        assert_eq!(store.policy, VerifyPolicy::Off);
+
+        store.policy = VerifyPolicy::Enforce;
+        store.keys.insert(
+            "fluvio".to_owned(),
+            parse_public_key(&Keypair::new().unwrap().public().to_hex())
+                .unwrap()
+                .to_hex(),
+        );
+        store.save(&path).expect("save");
+
+        assert_eq!(TrustStore::load(&path).expect("load"), store);
+    }
+}
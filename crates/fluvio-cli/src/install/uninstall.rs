@@ -0,0 +1,216 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use clap::Parser;
+use tracing::debug;
+
+use fluvio_cli_common::install::install_println;
+use fluvio_index::{PackageId, MaybeVersion};
+
+use crate::error::CliError;
+use crate::install::completions::CompletionShell;
+use crate::install::manifest::InstallManifest;
+use crate::install::scope::InstallScope;
+
+#[derive(Parser, Debug)]
+pub struct UninstallOpt {
+    /// The ID of an installed package to uninstall, e.g. "fluvio/fluvio-cloud".
+    package: PackageId<MaybeVersion>,
+    /// Uninstall install for current user, or system-wide one
+    #[arg(long, value_enum, default_value_t = InstallScope::User)]
+    scope: InstallScope,
+}
+
+impl UninstallOpt {
+    pub async fn process(self) -> Result<()> {
+        let name = self.package.name().to_string();
+        let package_key = format!("{}/{}", self.package.group(), name);
+        let manifest_path = InstallManifest::default_path(self.scope)?;
+        let mut manifest = InstallManifest::load(&manifest_path)?;
+
+        // Binaries installed before the manifest existed are found in install dir
+        let recorded = manifest.get(&package_key).is_some();
+        let fallback = installed_binaries(self.scope, &name)?;
+        for binary in remove_binaries(&mut manifest, &package_key, fallback)? {
+            install_println(format!("🗑️ Removed {}", binary.display()));
+        }
+        for shell in CompletionShell::ALL {
+            let Ok(completion) = shell.completion_path(&name) else {
+                continue;
+            };
+            if fs::remove_file(&completion).is_ok() {
+                install_println(format!(
+                    "🗑️ Removed {} completions {}",
+                    shell.name(),
+                    completion.display()
+                ));
+            }
+        }
+        if recorded {
+            manifest.save(&manifest_path)?;
+        }
+
+        for config in orphaned_config(&self.scope.state_dir()?, &name) {
+            install_println(format!(
+                "❕ Configuration of {name} is left at {}, remove it if no longer needed",
+                config.display()
+            ));
+        }
+        install_println(format!("✅ Uninstalled {package_key}"));
+        Ok(())
+    }
+}
+
+/// remove binaries of package and its manifest entry, binaries at `fallback` are removed
+/// if package is not recorded in manifest. Returns binaries removed
+fn remove_binaries(
+    manifest: &mut InstallManifest,
+    package_key: &str,
+    fallback: Vec<PathBuf>,
+) -> Result<Vec<PathBuf>> {
+    let binaries: Vec<PathBuf> = match manifest.packages.remove(package_key) {
+        Some(installed) => installed
+            .path
+            .into_iter()
+            .chain(installed.previous.and_then(|it| it.path))
+            .collect(),
+        None => {
+            let binaries: Vec<PathBuf> = fallback.into_iter().filter(|it| it.exists()).collect();
+            if binaries.is_empty() {
+                return Err(
+                    CliError::Other(format!("Package {package_key} is not installed")).into(),
+                );
+            }
+            binaries
+        }
+    };
+
+    let mut removed = vec![];
+    for binary in binaries {
+        match fs::remove_file(&binary) {
+            Ok(()) => removed.push(binary),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                debug!(?binary, "binary already removed")
+            }
+            Err(err) => return Err(err.into()),
+        }
+    }
+    Ok(removed)
+}
+
+/// locations binary of plugin may be installed to
+fn installed_binaries(scope: InstallScope, name: &str) -> Result<Vec<PathBuf>> {
+    let dir = scope.install_dir(name)?;
+    Ok(vec![dir.join(name), dir.join(format!("{name}.exe"))])
+}
+
+/// files or directories in fluvio dir named after plugin, which uninstall doesn't remove
+fn orphaned_config(dir: &Path, name: &str) -> Vec<PathBuf> {
+    let short_name = name.strip_prefix("fluvio-").unwrap_or(name);
+    let mut candidates = vec![dir.join(name)];
+    if short_name != name {
+        candidates.push(dir.join(short_name));
+    }
+    candidates.into_iter().filter(|it| it.exists()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use semver::Version;
+
+    use crate::install::manifest::{InstalledPackage, InstallSource};
+
+    use super::*;
+
+    fn installed(path: PathBuf, previous: Option<PathBuf>) -> InstalledPackage {
+        let package = |path: Option<PathBuf>| InstalledPackage {
+            version: Version::parse("0.2.5").unwrap(),
+            requirement: None,
+            target: "x86_64-unknown-linux-musl".to_owned(),
+            source: InstallSource::Registry,
+            path,
+            checksum: None,
+            previous: None,
+        };
+        InstalledPackage {
+            previous: previous.map(|previous| Box::new(package(Some(previous)))),
+            ..package(Some(path))
+        }
+    }
+
+    #[test]
+    fn test_remove_recorded_binaries() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let binary = dir.path().join("fluvio-cloud");
+        let previous = dir.path().join("previous-fluvio-cloud");
+        fs::write(&binary, b"binary").expect("write");
+        fs::write(&previous, b"previous").expect("write");
+
+        let mut manifest = InstallManifest::default();
+        manifest.packages.insert(
+            "fluvio/fluvio-cloud".to_owned(),
+            installed(binary.clone(), Some(previous.clone())),
+        );
+        // fallback is ignored for recorded packages
+        let fallback = dir.path().join("other");
+        fs::write(&fallback, b"other").expect("write");
+
+        let removed = remove_binaries(&mut manifest, "fluvio/fluvio-cloud", vec![fallback.clone()])
+            .expect("removed");
+        assert_eq!(removed, vec![binary.clone(), previous.clone()]);
+        assert!(!binary.exists());
+        assert!(!previous.exists());
+        assert!(fallback.exists());
+        assert!(manifest.get("fluvio/fluvio-cloud").is_none());
+    }
+
+    #[test]
+    fn test_remove_recorded_binaries_already_removed() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut manifest = InstallManifest::default();
+        manifest.insert(
+            "fluvio/fluvio-cloud",
+            installed(dir.path().join("fluvio-cloud"), None),
+            None,
+        );
+
+        let removed =
+            remove_binaries(&mut manifest, "fluvio/fluvio-cloud", vec![]).expect("removed");
+        assert!(removed.is_empty());
+        assert!(manifest.packages.is_empty());
+    }
+
+    #[test]
+    fn test_remove_unrecorded_binaries() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let binary = dir.path().join("fluvio-cloud");
+        fs::write(&binary, b"binary").expect("write");
+        let mut manifest = InstallManifest::default();
+
+        let removed = remove_binaries(
+            &mut manifest,
+            "fluvio/fluvio-cloud",
+            vec![binary.clone(), dir.path().join("fluvio-cloud.exe")],
+        )
+        .expect("removed");
+        assert_eq!(removed, vec![binary.clone()]);
+        assert!(!binary.exists());
+
+        let err = remove_binaries(&mut manifest, "fluvio/fluvio-cloud", vec![binary])
+            .expect_err("not installed");
+        assert!(err.to_string().contains("is not installed"));
+    }
+
+    #[test]
+    fn test_orphaned_config() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        assert!(orphaned_config(dir.path(), "fluvio-cloud").is_empty());
+
+        fs::create_dir(dir.path().join("cloud")).expect("create");
+        assert_eq!(
+            orphaned_config(dir.path(), "fluvio-cloud"),
+            vec![dir.path().join("cloud")]
+        );
+    }
+}
@@ -1,22 +1,48 @@
+use std::fmt;
+use std::io::IsTerminal;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
+use bytesize::ByteSize;
 use clap::Parser;
+use comfy_table::{Cell, Row, Table};
+use futures::future::join_all;
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
 use tracing::{debug, instrument};
+use uuid::Uuid;
 use semver::Version;
+use serde::{Deserialize, Serialize};
 use anyhow::Result;
 
-use fluvio_channel::{LATEST_CHANNEL_NAME, FLUVIO_RELEASE_CHANNEL};
-use fluvio_cli_common::{FLUVIO_ALWAYS_CHECK_UPDATES, error::PackageNotFound};
-use fluvio_index::{PackageId, HttpAgent};
+use fluvio::{FluvioAdmin, FluvioConfig};
+use fluvio::config::ConfigFile;
+use fluvio_channel::{LATEST_CHANNEL_NAME, STABLE_CHANNEL_NAME, FLUVIO_RELEASE_CHANNEL};
+use fluvio_cli_common::{
+    FLUVIO_ALWAYS_CHECK_UPDATES, FLUVIO_UPDATE_CHECK_INTERVAL, error::PackageNotFound,
+};
+use fluvio_hub_util::htclient;
+use fluvio_index::{PackageId, HttpAgent, Target, WithVersion};
 use fluvio_cli_common::install::{
-    fetch_latest_version, fetch_package_file, install_bin, install_println, fluvio_extensions_dir,
+    check_cli_compatibility, fluvio_base_dir, fetch_latest_version, fetch_package_file,
+    fetch_release_notes, install_bin, install_println, fluvio_extensions_dir,
+    discover_index_layout, with_index_cache,
 };
 
-use crate::metadata::subcommand_metadata;
+use crate::error::CliError;
+use crate::install::health::check_installed;
+use crate::install::manifest::InstallManifest;
+use crate::install::non_interactive::{NonInteractiveOpt, PackageReport, PackageStatus};
+use crate::install::scope::InstallScope;
+use crate::install::trust::{verify_package, Verification};
+use crate::metadata::{subcommand_metadata, SubcommandMetadata};
 
-const FLUVIO_CLI_PACKAGE_ID: &str = "fluvio/fluvio";
+pub(crate) const FLUVIO_CLI_PACKAGE_ID: &str = "fluvio/fluvio";
 const FLUVIO_CHANNEL_PACKAGE_ID: &str = "fluvio/fluvio-channel";
+const INSTALL_ID_FILE: &str = "install-id";
+const UPDATE_CHECK_FILE: &str = "update-check.json";
+const DEFAULT_UPDATE_CHECK_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+const MAX_RELEASE_NOTES_LINES: usize = 40;
 
 #[derive(Parser, Debug)]
 pub struct UpdateOpt {
@@ -28,6 +54,14 @@ pub struct UpdateOpt {
     #[arg(long)]
     pub dry_run: bool,
 
+    /// Print current and available versions of the CLI and plugins, without downloading anything
+    #[arg(long, conflicts_with = "dry_run")]
+    pub check: bool,
+
+    /// Update the CLI without asking for confirmation
+    #[arg(short, long)]
+    pub yes: bool,
+
     // The fluvio-channel binary changes less frequently
     // pub skip_fluvio_channel: bool,
     // pub develop_fluvio_channel: bool,
@@ -37,15 +71,42 @@ pub struct UpdateOpt {
     /// override default target arch determination
     #[arg(long, hide_short_help = true)]
     pub target: Option<String>,
+
+    /// Fetch the package index again instead of revalidating the cached copy
+    #[arg(long)]
+    pub force_refresh: bool,
+
+    #[command(flatten)]
+    pub interaction: NonInteractiveOpt,
 }
 
 impl UpdateOpt {
     pub async fn process(self) -> Result<()> {
-        let agent = HttpAgent::default();
+        self.interaction.begin();
+        if self.check {
+            // report of check is the list of available updates
+            return self
+                .check_updates(
+                    &discover_index_layout(self.index_agent()).await,
+                    subcommand_metadata()?,
+                )
+                .await;
+        }
+        let result = self.update().await;
+        self.interaction.finish("update", result)
+    }
+
+    fn index_agent(&self) -> HttpAgent {
+        with_index_cache(HttpAgent::default(), self.force_refresh)
+    }
+
+    /// update CLI and plugins, returns what was updated
+    async fn update(&self) -> Result<Vec<PackageReport>> {
+        let agent = discover_index_layout(self.index_agent()).await;
         let plugin_meta = subcommand_metadata()?;
 
-        // A list of updates to perform. PackageId of the plugin and Path to install
-        let mut updates: Vec<(PackageId, PathBuf)> = Vec::new();
+        // A list of updates to perform. PackageId of the plugin, Path to install and installed version
+        let mut updates: Vec<(PackageId, PathBuf, Option<Version>)> = Vec::new();
 
         if self.plugins.is_empty() {
             // Collect updates from subcommand metadata
@@ -57,44 +118,202 @@ impl UpdateOpt {
             for plugin in plugin_metas {
                 let id = plugin.meta.package.unwrap();
                 let path = plugin.path;
-                updates.push((id, path));
+                updates.push((id, path, Some(plugin.meta.version)));
             }
         } else {
             // Collect updates from the given plugin IDs
             let ext_dir = fluvio_extensions_dir()?;
             for plugin in &self.plugins {
                 let path = ext_dir.join(plugin.name().as_str());
-                updates.push((plugin.clone(), path));
+                let current = plugin_meta
+                    .iter()
+                    .find(|it| it.path == path)
+                    .map(|it| it.meta.version.clone());
+                updates.push((plugin.clone(), path, current));
             }
         }
 
-        self.update_fluvio_cli(&agent).await?;
+        // Plugins must be compatible with the CLI they will run with
+        let cli = self.update_fluvio_cli(&agent).await?;
+        let cli_version = match &cli.version {
+            Some(version) => version.clone(),
+            None => Version::parse(crate::VERSION.trim())?,
+        };
+        let mut reports = vec![cli];
         self.update_fluvio_channel(&agent).await?;
 
         if updates.is_empty() {
-            println!("👍 No plugins to update, all done!");
-            return Ok(());
+            install_println("👍 No plugins to update, all done!");
+            return Ok(reports);
         }
 
         let s = if updates.len() != 1 { "s" } else { "" };
-        println!(
+        install_println(format!(
             "🔧 Preparing update for {} plugin{s}:",
             updates.len(),
             s = s
-        );
-        for (id, path) in &updates {
-            println!("   - {} ({})", id.name(), path.display());
+        ));
+        for (id, path, _) in &updates {
+            install_println(format!("   - {} ({})", id.name(), path.display()));
         }
 
-        for (id, path) in &updates {
-            self.update_plugin(&agent, id, path).await?;
+        // Plugins are updated concurrently, each with own line of progress
+        let progress = MultiProgress::new();
+        let (this, agent, cli_version) = (self, &agent, &cli_version);
+        let outcomes = join_all(updates.iter().map(|(id, path, current)| {
+            let pb = progress.add(ProgressBar::new_spinner());
+            pb.set_style(
+                ProgressStyle::with_template("{spinner} {prefix:.bold} {wide_msg}")
+                    .unwrap_or_else(|_| ProgressStyle::default_spinner()),
+            );
+            pb.set_prefix(id.name().to_string());
+            pb.enable_steady_tick(Duration::from_millis(100));
+            async move {
+                let outcome = this
+                    .update_plugin(agent, id, path, current.as_ref(), cli_version, &pb)
+                    .await;
+                match &outcome {
+                    Ok(outcome) => pb.finish_with_message(outcome.to_string()),
+                    Err(err) => pb.finish_with_message(format!("❌ {err}")),
+                }
+                outcome
+            }
+        }))
+        .await;
+
+        install_println("");
+        install_println("Summary:");
+        let manifest_path = InstallManifest::default_path(InstallScope::User)?;
+        let mut manifest = InstallManifest::load(&manifest_path)?;
+        let mut recorded = false;
+        for ((id, path, _), outcome) in updates.iter().zip(outcomes) {
+            if let Ok(PluginUpdate::Updated { to, .. }) = &outcome {
+                // keep version and checksum of recorded install in sync with the binary
+                recorded |= manifest.record_update(path, to)?;
+            }
+            let package = format!("{}/{}", id.group(), id.name());
+            match outcome {
+                Ok(outcome) => {
+                    install_println(format!("   {} {outcome}", id.name()));
+                    reports.push(outcome.report(package));
+                }
+                Err(err) => {
+                    install_println(format!(
+                        "   {} ❌ failed at {}: {err}",
+                        id.name(),
+                        path.display()
+                    ));
+                    reports.push(
+                        PackageReport::new(package, None, PackageStatus::Failed)
+                            .with_message(err.to_string()),
+                    );
+                }
+            }
+        }
+        if recorded {
+            manifest.save(&manifest_path)?;
+        }
+        Ok(reports)
+    }
+
+    /// resolve available versions and print them next to the installed ones
+    async fn check_updates(
+        &self,
+        agent: &HttpAgent,
+        plugin_meta: Vec<SubcommandMetadata>,
+    ) -> Result<()> {
+        let target = if let Some(user_override) = &self.target {
+            fluvio_index::Target::from_str(&user_override.to_string())?
+        } else {
+            fluvio_index::package_target()?
+        };
+
+        let current_cli =
+            Version::parse(crate::VERSION).expect("Fluvio CLI 'VERSION' should be a valid semver");
+        let mut installed: Vec<(PackageId, Option<Version>)> =
+            vec![(FLUVIO_CLI_PACKAGE_ID.parse()?, Some(current_cli))];
+        if self.plugins.is_empty() {
+            installed.extend(plugin_meta.into_iter().filter_map(|it| {
+                it.meta
+                    .package
+                    .map(|package| (package, Some(it.meta.version)))
+            }));
+        } else {
+            for plugin in &self.plugins {
+                let current = plugin_meta
+                    .iter()
+                    .find(|it| it.meta.package.as_ref().map(|it| it.name()) == Some(plugin.name()))
+                    .map(|it| it.meta.version.clone());
+                installed.push((plugin.clone(), current));
+            }
+        }
+
+        install_println("🎣 Checking for updates...");
+        let mut checks = Vec::with_capacity(installed.len());
+        for (id, current) in installed {
+            checks.push(self.check_update(agent, id, current, &target).await);
+        }
+        if self.interaction.json() {
+            println!("{}", serde_json::to_string_pretty(&checks)?);
+            return Ok(());
         }
+        println!("{}", update_check_table(&checks));
 
+        let available = checks.iter().filter(|it| it.has_update()).count();
+        if available == 0 {
+            println!("👍 Everything is up to date");
+        } else {
+            let s = if available != 1 { "s" } else { "" };
+            println!("💡 {available} update{s} available, run without --check to install");
+        }
         Ok(())
     }
 
+    async fn check_update(
+        &self,
+        agent: &HttpAgent,
+        id: PackageId,
+        current: Option<Version>,
+        target: &Target,
+    ) -> UpdateCheck {
+        let available = match fetch_latest_version(agent, &id, target, self.develop).await {
+            Ok(version) => Some(version),
+            Err(err) => {
+                debug!(%id, %err, "failed to resolve latest version");
+                None
+            }
+        };
+        let size = match &available {
+            Some(version) => fetch_package_size(agent, &id, version, target)
+                .await
+                .unwrap_or_else(|err| {
+                    debug!(%id, %err, "failed to fetch package size");
+                    None
+                }),
+            None => None,
+        };
+        UpdateCheck {
+            name: id.name().to_string(),
+            current,
+            available,
+            size,
+            channel: self.get_channel(),
+        }
+    }
+
+    fn get_channel(&self) -> String {
+        if let Ok(channel_name) = std::env::var(FLUVIO_RELEASE_CHANNEL) {
+            channel_name
+        } else if self.develop {
+            LATEST_CHANNEL_NAME.to_string()
+        } else {
+            STABLE_CHANNEL_NAME.to_string()
+        }
+    }
+
     #[instrument(skip(self, agent))]
-    async fn update_fluvio_cli(&self, agent: &HttpAgent) -> Result<()> {
+    /// Update the CLI, the reported version is the one installed after the update
+    async fn update_fluvio_cli(&self, agent: &HttpAgent) -> Result<PackageReport> {
         let target = if let Some(user_override) = &self.target {
             fluvio_index::Target::from_str(&user_override.to_string())?
         } else {
@@ -107,10 +326,23 @@ impl UpdateOpt {
         let id: PackageId = FLUVIO_CLI_PACKAGE_ID.parse()?;
         debug!(%target, %id, "Fluvio CLI updating self:");
 
-        // Find the latest version of this package
+        // Find the latest version of this package offered on our channel
         install_println("🎣 Fetching latest version for fluvio...");
-        let latest_version = fetch_latest_version(agent, &id, &target, self.develop).await?;
-        let id = id.into_versioned(latest_version.into());
+        let latest_version = latest_offered_version(agent, &id, &target, self.develop).await?;
+        let current_version = Version::parse(crate::VERSION.trim())?;
+        let id = id.into_versioned(latest_version.clone().into());
+
+        if latest_version != current_version
+            && !self.confirm_cli_update(agent, &id, &latest_version).await?
+        {
+            install_println("❎ Fluvio CLI update declined");
+            return Ok(PackageReport::new(
+                FLUVIO_CLI_PACKAGE_ID,
+                Some(current_version),
+                PackageStatus::Skipped,
+            )
+            .with_message(format!("update to {latest_version} declined")));
+        }
 
         // Download the package file from the package registry
         install_println(format!(
@@ -124,10 +356,14 @@ impl UpdateOpt {
                 Some(PackageNotFound {
                     version, target, ..
                 }) => {
-                    install_println(format!(
-                        "❕ Fluvio is not published at version {version} for {target}, skipping self-update"
-                    ));
-                    return Ok(());
+                    let message = format!("not published at version {version} for {target}");
+                    install_println(format!("❕ Fluvio is {message}, skipping self-update"));
+                    return Ok(PackageReport::new(
+                        FLUVIO_CLI_PACKAGE_ID,
+                        Some(current_version),
+                        PackageStatus::Skipped,
+                    )
+                    .with_message(message));
                 }
                 None => return Err(err),
             },
@@ -149,9 +385,50 @@ impl UpdateOpt {
                 "❎ (Dry run) Update installation skipped {}",
                 &fluvio_cli_path.display(),
             ));
+            // plugins are checked against the CLI which stays installed
+            return Ok(PackageReport::new(
+                FLUVIO_CLI_PACKAGE_ID,
+                Some(current_version),
+                PackageStatus::DryRun,
+            )
+            .with_message(format!("update to {latest_version} skipped")));
         }
 
-        Ok(())
+        Ok(PackageReport::new(
+            FLUVIO_CLI_PACKAGE_ID,
+            Some(latest_version),
+            PackageStatus::Updated,
+        ))
+    }
+
+    /// Show release notes of the new version, and ask whether to update when interactive
+    async fn confirm_cli_update(
+        &self,
+        agent: &HttpAgent,
+        id: &PackageId<WithVersion>,
+        version: &Version,
+    ) -> Result<bool> {
+        // Release notes are informative, failing to fetch them doesn't stop the update
+        match fetch_release_notes(agent, id, version).await {
+            Ok(Some(notes)) => {
+                install_println(format!("📝 Release notes of fluvio {version}:"));
+                install_println(render_release_notes(&notes, MAX_RELEASE_NOTES_LINES));
+            }
+            Ok(None) => debug!(%version, "No release notes published"),
+            Err(err) => debug!(%err, "Failed to fetch release notes"),
+        }
+
+        if self.yes
+            || self.dry_run
+            || self.interaction.non_interactive
+            || !std::io::stdin().is_terminal()
+        {
+            return Ok(true);
+        }
+        install_println(format!("❓ Update Fluvio CLI to {version}? (y/N)"));
+        let mut answer = String::new();
+        std::io::stdin().read_line(&mut answer)?;
+        Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
     }
 
     #[instrument(skip(self, agent))]
@@ -210,37 +487,183 @@ impl UpdateOpt {
         Ok(())
     }
 
-    #[instrument(skip(self, agent))]
-    async fn update_plugin(&self, agent: &HttpAgent, id: &PackageId, path: &Path) -> Result<()> {
+    #[instrument(skip(self, agent, pb))]
+    async fn update_plugin(
+        &self,
+        agent: &HttpAgent,
+        id: &PackageId,
+        path: &Path,
+        current: Option<&Version>,
+        cli_version: &Version,
+        pb: &ProgressBar,
+    ) -> Result<PluginUpdate> {
         let target = fluvio_index::package_target()?;
         debug!(%target, %id, "Fluvio CLI updating plugin:");
 
+        pb.set_message("🎣 Fetching latest version...");
         let version = fetch_latest_version(agent, id, &target, self.develop).await?;
+        if current.is_some_and(|current| *current >= version) {
+            return Ok(PluginUpdate::UpToDate(version));
+        }
+        check_cli_compatibility(agent, id, &version, &target, self.develop, cli_version).await?;
 
-        println!(
-            "⏳ Downloading plugin {} with version {}",
-            id.pretty(),
-            version
-        );
-        let id = id.clone().into_versioned(version.into());
-        let package_file = fetch_package_file(agent, &id, &target).await?;
-        println!("🔑 Downloaded and verified package file");
+        pb.set_message(format!("⏳ Downloading version {version}..."));
+        let versioned_id = id.clone().into_versioned(version.clone().into());
+        let package_file = fetch_package_file(agent, &versioned_id, &target).await?;
+        pb.set_message("🔑 Downloaded and verified package file");
+        let verification =
+            verify_package(agent, id, Some(&version), &target, &package_file, None).await?;
+        if let Verification::Unverified(reason) = &verification {
+            pb.println(format!(
+                "⚠️ {} release signature not verified: {reason}",
+                id.name()
+            ));
+        }
 
-        if !self.dry_run {
-            install_bin(path, package_file)?;
-            println!("✅ Successfully updated {} at ({})", id, path.display());
-        } else {
-            println!(
-                "❎ (Dry run) Update installation skipped {} at ({})",
-                id,
-                path.display()
-            );
+        if self.dry_run {
+            return Ok(PluginUpdate::DryRun(version));
+        }
+        let replaced = std::fs::read(path).ok();
+        install_bin(path, package_file)?;
+        if let Err(err) = check_installed(path, &version) {
+            if let Some(replaced) = replaced {
+                install_bin(path, replaced)?;
+            }
+            return Err(CliError::Other(format!(
+                "updated plugin failed health check and was rolled back: {err}"
+            ))
+            .into());
         }
+        Ok(PluginUpdate::Updated {
+            from: current.cloned(),
+            to: version,
+        })
+    }
+}
 
-        Ok(())
+/// Result of updating single plugin
+#[derive(Debug, PartialEq)]
+enum PluginUpdate {
+    Updated {
+        from: Option<Version>,
+        to: Version,
+    },
+    UpToDate(Version),
+    /// update downloaded but not installed
+    DryRun(Version),
+}
+
+impl PluginUpdate {
+    fn report(self, package: String) -> PackageReport {
+        match self {
+            Self::Updated { to, .. } => {
+                PackageReport::new(package, Some(to), PackageStatus::Updated)
+            }
+            Self::UpToDate(version) => {
+                PackageReport::new(package, Some(version), PackageStatus::UpToDate)
+            }
+            Self::DryRun(version) => {
+                PackageReport::new(package, Some(version), PackageStatus::DryRun)
+            }
+        }
+    }
+}
+
+impl fmt::Display for PluginUpdate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Updated {
+                from: Some(from),
+                to,
+            } => write!(f, "✅ updated {from} -> {to}"),
+            Self::Updated { from: None, to } => write!(f, "✅ updated to {to}"),
+            Self::UpToDate(version) => write!(f, "👍 up to date at {version}"),
+            Self::DryRun(version) => write!(f, "❎ (dry run) skipped install of {version}"),
+        }
+    }
+}
+
+/// Installed package compared to the latest one on the index
+#[derive(Debug, Serialize)]
+struct UpdateCheck {
+    name: String,
+    current: Option<Version>,
+    available: Option<Version>,
+    size: Option<u64>,
+    channel: String,
+}
+
+impl UpdateCheck {
+    fn has_update(&self) -> bool {
+        match (&self.current, &self.available) {
+            (Some(current), Some(available)) => current < available,
+            (None, Some(_)) => true,
+            _ => false,
+        }
     }
 }
 
+fn update_check_table(checks: &[UpdateCheck]) -> Table {
+    let mut table = Table::new();
+    table.load_preset(comfy_table::presets::NOTHING);
+    table.set_header(Row::from(
+        ["PACKAGE", "CURRENT", "AVAILABLE", "SIZE", "CHANNEL", ""]
+            .into_iter()
+            .map(Cell::new)
+            .collect::<Vec<_>>(),
+    ));
+    let unknown = || "-".to_string();
+    for check in checks {
+        table.add_row(Row::from(vec![
+            Cell::new(&check.name),
+            Cell::new(
+                check
+                    .current
+                    .as_ref()
+                    .map_or_else(unknown, Version::to_string),
+            ),
+            Cell::new(
+                check
+                    .available
+                    .as_ref()
+                    .map_or_else(unknown, Version::to_string),
+            ),
+            Cell::new(
+                check
+                    .size
+                    .map_or_else(unknown, |size| ByteSize::b(size).to_string()),
+            ),
+            Cell::new(&check.channel),
+            Cell::new(if check.has_update() { "update" } else { "" }),
+        ]));
+    }
+    table
+}
+
+/// Size of package file from the registry, without downloading it
+async fn fetch_package_size(
+    agent: &HttpAgent,
+    id: &PackageId,
+    version: &Version,
+    target: &Target,
+) -> Result<Option<u64>> {
+    let uri = agent
+        .request_release_download(id, version, target)?
+        .uri()
+        .to_string();
+    let request = htclient::http::Request::head(uri).body("")?;
+    let response = htclient::send(request).await?;
+    if !response.status().is_success() {
+        return Ok(None);
+    }
+    let size = response
+        .headers()
+        .get(htclient::http::header::CONTENT_LENGTH)
+        .and_then(|it| it.to_str().ok())
+        .and_then(|it| it.parse().ok());
+    Ok(size)
+}
+
 /// Check whether the index requires a more recent version of the client.
 ///
 /// If this is the case, we need to prompt the user to perform an update.
@@ -251,13 +674,20 @@ impl UpdateOpt {
 pub async fn check_update_required(agent: &HttpAgent) -> Result<bool> {
     debug!("Checking for a required CLI update");
     let request = agent.request_index()?;
-    let body = fluvio_cli_common::http::get_bytes_req(&request).await?;
+    let body = fluvio_cli_common::http::get_cached_req(agent, &request).await?;
     let index = agent.index_from_response(&body).await?;
     Ok(index.metadata.update_required())
 }
 
 // TODO: This needs to check on fluvio-channel updates as well. If on latest channel, only update fluvio-channel when flag passed
 /// Check whether there is any newer version of the Fluvio CLI available
+///
+/// On the stable channel, the version recommended by the cluster of the current profile is
+/// preferred, so operators of a cluster converge on the same release. Otherwise, or when the
+/// cluster can't be reached, the latest version offered by the index is used.
+///
+/// The check is done at most once per check interval, or when the cluster changes. In between
+/// and when nothing can be reached, the version found by the last successful check is used.
 #[instrument(
     skip(agent),
     fields(prefix = agent.base_url())
@@ -266,24 +696,202 @@ pub async fn check_update_available(
     agent: &HttpAgent,
     prerelease: bool,
 ) -> Result<Option<Version>> {
-    let target = fluvio_index::package_target()?;
-    let id: PackageId = FLUVIO_CLI_PACKAGE_ID.parse()?;
-    debug!(%target, %id, "Checking for an available (not required) CLI update:");
+    let cache_path = fluvio_base_dir()?.join(UPDATE_CHECK_FILE);
+    let mut cache = UpdateCheckCache::load(&cache_path);
+    let channel = release_channel();
+    let now = unix_now();
+    let cluster = (!prerelease && channel == STABLE_CHANNEL_NAME)
+        .then(current_cluster)
+        .flatten();
 
-    let request = agent.request_package(&id)?;
-    let body = fluvio_cli_common::http::get_bytes_req(&request).await?;
-    let package = agent.package_from_response(&body).await?;
+    if cache.is_due(now, &channel, update_check_interval())
+        || cache.cluster.as_ref() != cluster.as_ref().map(|it| &it.endpoint)
+    {
+        let target = fluvio_index::package_target()?;
+        let id: PackageId = FLUVIO_CLI_PACKAGE_ID.parse()?;
+        debug!(%target, %id, "Checking for an available (not required) CLI update:");
+
+        let recommended = match &cluster {
+            Some(cluster) => cluster_recommended_version(cluster, &target).await,
+            None => None,
+        };
+        match recommended {
+            Some(recommended) => cache.latest_version = Some(recommended),
+            None => match latest_offered_version(agent, &id, &target, prerelease).await {
+                Ok(latest_version) => cache.latest_version = Some(latest_version),
+                Err(err) => debug!(%err, "Update check failed, using last known version"),
+            },
+        }
+        // Failed checks are throttled as well, so being offline doesn't slow down every command
+        cache.checked_at = now;
+        cache.channel = channel;
+        cache.cluster = cluster.map(|it| it.endpoint);
+        if let Err(err) = cache.save(&cache_path) {
+            debug!(%err, "Failed to save update check");
+        }
+    }
 
-    let release = package.latest_release_for_target(&target, prerelease)?;
-    let latest_version = release.version.clone();
     let current_version =
         Version::parse(crate::VERSION).expect("Fluvio CLI 'VERSION' should be a valid semver");
+    Ok(cache
+        .latest_version
+        .filter(|latest| current_version < *latest))
+}
 
-    if current_version < latest_version {
-        Ok(Some(latest_version))
-    } else {
-        Ok(None)
+/// Result of last update check
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+struct UpdateCheckCache {
+    /// unix time of last check, in seconds
+    checked_at: u64,
+    channel: String,
+    /// latest version found by last successful check
+    latest_version: Option<Version>,
+    /// endpoint of cluster consulted by last check
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    cluster: Option<String>,
+}
+
+impl UpdateCheckCache {
+    /// load cache, unreadable cache is treated as never checked
+    fn load(path: &Path) -> Self {
+        std::fs::read(path)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self, path: &Path) -> Result<()> {
+        std::fs::write(path, serde_json::to_vec(self)?)?;
+        Ok(())
+    }
+
+    /// check is due once interval passed, or channel changed since last check
+    fn is_due(&self, now: u64, channel: &str, interval: Duration) -> bool {
+        self.channel != channel || now.saturating_sub(self.checked_at) >= interval.as_secs()
+    }
+}
+
+fn update_check_interval() -> Duration {
+    std::env::var(FLUVIO_UPDATE_CHECK_INTERVAL)
+        .ok()
+        .and_then(|it| it.parse().ok())
+        .map(Duration::from_secs)
+        .unwrap_or(DEFAULT_UPDATE_CHECK_INTERVAL)
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|it| it.as_secs())
+        .unwrap_or_default()
+}
+
+/// Cluster of current profile, if any
+fn current_cluster() -> Option<FluvioConfig> {
+    let config_file = ConfigFile::load(None).ok()?;
+    config_file.config().current_cluster().ok().cloned()
+}
+
+/// Version of the CLI recommended by the cluster, none if it can't be reached or doesn't
+/// recommend one
+async fn cluster_recommended_version(cluster: &FluvioConfig, target: &Target) -> Option<Version> {
+    let recommended = async {
+        let admin = FluvioAdmin::connect_with_config(cluster).await?;
+        let response = admin.recommended_cli(target.to_string()).await?;
+        debug!(
+            version = response.version,
+            mirrored = response.mirrored,
+            "Cluster recommends CLI"
+        );
+        Ok::<_, anyhow::Error>(Version::parse(&response.version)?)
+    };
+    recommended
+        .await
+        .map_err(|err| debug!(%err, endpoint = cluster.endpoint, "No CLI recommended by cluster"))
+        .ok()
+}
+
+/// Latest release of the CLI offered to this client
+///
+/// Prereleases are offered on the latest channel, and releases in a staged rollout
+/// only if this client falls within the rollout.
+async fn latest_offered_version(
+    agent: &HttpAgent,
+    id: &PackageId,
+    target: &Target,
+    prerelease: bool,
+) -> Result<Version> {
+    let channel = release_channel();
+    let prerelease = prerelease || channel == LATEST_CHANNEL_NAME;
+
+    let request = agent.request_index()?;
+    let body = fluvio_cli_common::http::get_cached_req(agent, &request).await?;
+    let index = agent.index_from_response(&body).await?;
+    let request = agent.request_package(id)?;
+    let body = fluvio_cli_common::http::get_cached_req(agent, &request).await?;
+    let package = agent.package_from_response(&body).await?;
+
+    let bucket = rollout_bucket()?;
+    debug!(%channel, bucket, "Resolving version offered to client");
+    let release = package
+        .releases_for_target(target)
+        .into_iter()
+        .rev()
+        .filter(|it| prerelease || (it.version.pre.is_empty() && it.version.build.is_empty()))
+        .find(|it| {
+            index
+                .metadata
+                .release_offered(FLUVIO_CLI_PACKAGE_ID, &it.version, &channel, bucket)
+        })
+        .ok_or_else(|| fluvio_index::Error::MissingTarget(target.clone()))?;
+    Ok(release.version.clone())
+}
+
+/// Release notes for the terminal, without markdown heading markers and truncated if long
+fn render_release_notes(notes: &str, max_lines: usize) -> String {
+    let mut lines: Vec<String> = notes
+        .lines()
+        .take(max_lines)
+        .map(|line| match line.strip_prefix('#') {
+            Some(heading) => format!("   {}", heading.trim_start_matches('#').trim()),
+            None if line.trim().is_empty() => String::new(),
+            None => format!("   {line}"),
+        })
+        .collect();
+    let more = notes.lines().count().saturating_sub(max_lines);
+    if more > 0 {
+        lines.push(format!("   ... {more} more lines"));
     }
+    lines.join("\n")
+}
+
+/// The release channel of this installation
+pub(crate) fn release_channel() -> String {
+    std::env::var(FLUVIO_RELEASE_CHANNEL).unwrap_or_else(|_| STABLE_CHANNEL_NAME.to_string())
+}
+
+/// Bucket of this installation in staged rollouts, from 0 to 99
+///
+/// Derived from an id generated on first use, so the installation stays in the same bucket
+/// and is offered a release in rollout once it reaches its share.
+fn rollout_bucket() -> Result<u8> {
+    let path = fluvio_base_dir()?.join(INSTALL_ID_FILE);
+    let id = match std::fs::read_to_string(&path)
+        .ok()
+        .and_then(|it| Uuid::parse_str(it.trim()).ok())
+    {
+        Some(id) => id,
+        None => {
+            let id = Uuid::new_v4();
+            std::fs::write(&path, id.to_string())?;
+            id
+        }
+    };
+    Ok(bucket_of(&id))
+}
+
+fn bucket_of(id: &Uuid) -> u8 {
+    (id.as_u128() % 100) as u8
 }
 
 /// Prompt the user about a new required version of the Fluvio CLI
@@ -297,11 +905,11 @@ pub async fn prompt_required_update(agent: &HttpAgent) -> Result<()> {
     debug!(%target, %id, "Fetching latest package version:");
     let latest_version = fetch_latest_version(agent, &id, &target, false).await?;
 
-    println!("⚠️ A major update to Fluvio has been detected!");
-    println!(
+    install_println("⚠️ A major update to Fluvio has been detected!");
+    install_println(format!(
         "⚠️     Run 'fvm update' to install v{} of Fluvio",
         &latest_version
-    );
+    ));
     Ok(())
 }
 
@@ -325,3 +933,142 @@ pub fn should_always_print_available_update() -> bool {
         false
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(current: Option<&str>, available: Option<&str>) -> UpdateCheck {
+        UpdateCheck {
+            name: "fluvio-cloud".to_owned(),
+            current: current.map(|it| Version::parse(it).unwrap()),
+            available: available.map(|it| Version::parse(it).unwrap()),
+            size: Some(2 * 1024 * 1024),
+            channel: STABLE_CHANNEL_NAME.to_owned(),
+        }
+    }
+
+    #[test]
+    fn test_update_check_has_update() {
+        assert!(check(Some("0.2.0"), Some("0.2.1")).has_update());
+        assert!(!check(Some("0.2.1"), Some("0.2.1")).has_update());
+        assert!(!check(Some("0.3.0"), Some("0.2.1")).has_update());
+        assert!(!check(Some("0.2.0"), None).has_update());
+        assert!(check(None, Some("0.2.1")).has_update());
+    }
+
+    #[test]
+    fn test_update_check_due() {
+        let interval = Duration::from_secs(60);
+        let never_checked = UpdateCheckCache::default();
+        assert!(never_checked.is_due(1000, STABLE_CHANNEL_NAME, interval));
+
+        let checked = UpdateCheckCache {
+            checked_at: 1000,
+            channel: STABLE_CHANNEL_NAME.to_owned(),
+            latest_version: Some(Version::parse("0.11.0").unwrap()),
+            cluster: None,
+        };
+        assert!(!checked.is_due(1059, STABLE_CHANNEL_NAME, interval));
+        assert!(checked.is_due(1060, STABLE_CHANNEL_NAME, interval));
+        assert!(checked.is_due(1001, LATEST_CHANNEL_NAME, interval));
+        assert!(checked.is_due(1000, STABLE_CHANNEL_NAME, Duration::ZERO));
+    }
+
+    #[test]
+    fn test_update_check_cache_roundtrip() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join(UPDATE_CHECK_FILE);
+        assert_eq!(UpdateCheckCache::load(&path), UpdateCheckCache::default());
+
+        let cache = UpdateCheckCache {
+            checked_at: 1000,
+            channel: STABLE_CHANNEL_NAME.to_owned(),
+            latest_version: Some(Version::parse("0.11.0").unwrap()),
+            cluster: Some("localhost:9003".to_owned()),
+        };
+        cache.save(&path).expect("save");
+        assert_eq!(UpdateCheckCache::load(&path), cache);
+
+        // corrupt cache is treated as never checked
+        std::fs::write(&path, b"{").expect("write");
+        assert_eq!(UpdateCheckCache::load(&path), UpdateCheckCache::default());
+    }
+
+    #[test]
+    fn test_render_release_notes() {
+        let notes = "# 0.11.1\n\n## Fixes\n- consumer reconnects\n- smaller binary\n";
+        assert_eq!(
+            render_release_notes(notes, 10),
+            "   0.11.1\n\n   Fixes\n   - consumer reconnects\n   - smaller binary"
+        );
+        assert_eq!(
+            render_release_notes(notes, 3),
+            "   0.11.1\n\n   Fixes\n   ... 2 more lines"
+        );
+    }
+
+    #[test]
+    fn test_bucket_of() {
+        assert_eq!(bucket_of(&Uuid::from_u128(0)), 0);
+        assert_eq!(bucket_of(&Uuid::from_u128(199)), 99);
+        for _ in 0..100 {
+            assert!(bucket_of(&Uuid::new_v4()) < 100);
+        }
+    }
+
+    #[test]
+    fn test_plugin_update_display() {
+        let version = || Version::parse("0.2.1").unwrap();
+        assert_eq!(
+            PluginUpdate::Updated {
+                from: Some(Version::parse("0.2.0").unwrap()),
+                to: version()
+            }
+            .to_string(),
+            "✅ updated 0.2.0 -> 0.2.1"
+        );
+        assert_eq!(
+            PluginUpdate::Updated {
+                from: None,
+                to: version()
+            }
+            .to_string(),
+            "✅ updated to 0.2.1"
+        );
+        assert_eq!(
+            PluginUpdate::UpToDate(version()).to_string(),
+            "👍 up to date at 0.2.1"
+        );
+    }
+
+    #[test]
+    fn test_update_check_table() {
+        let table = update_check_table(&[check(Some("0.2.0"), Some("0.2.1")), check(None, None)])
+            .to_string();
+        let lines: Vec<Vec<&str>> = table
+            .lines()
+            .map(|line| line.split_whitespace().collect())
+            .collect();
+        assert_eq!(
+            lines[0],
+            vec!["PACKAGE", "CURRENT", "AVAILABLE", "SIZE", "CHANNEL"]
+        );
+        assert_eq!(
+            lines[1],
+            vec![
+                "fluvio-cloud",
+                "0.2.0",
+                "0.2.1",
+                "2.1",
+                "MB",
+                "stable",
+                "update"
+            ]
+        );
+        assert_eq!(
+            lines[2],
+            vec!["fluvio-cloud", "-", "-", "2.1", "MB", "stable"]
+        );
+    }
+}
@@ -0,0 +1,297 @@
+//!
+//! # Plugin Versions
+//!
+//! Each installed version of a plugin is also kept side-by-side under `versions/<plugin>/<version>/`
+//! in the state directory of the install scope. When running a plugin, `fluvio` acts as a shim
+//! and dispatches to the pinned version, so projects can use different versions of a plugin.
+//!
+//! A plugin version is pinned by the `FLUVIO_<PLUGIN>_VERSION` environment variable, e.g.
+//! `FLUVIO_CLOUD_VERSION` for `fluvio-cloud`, or by the `fluvio-plugins.json` lockfile in the
+//! current directory or one of its ancestors. Unpinned plugins run the installed binary.
+//!
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use clap::Parser;
+use semver::Version;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use fluvio_cli_common::install::{install_bin, install_println};
+
+use crate::error::CliError;
+use crate::install::scope::InstallScope;
+
+const VERSIONS_DIR: &str = "versions";
+pub const PLUGIN_LOCK_FILE: &str = "fluvio-plugins.json";
+
+/// Versions of plugins kept side-by-side
+#[derive(Debug)]
+pub struct PluginVersions {
+    dir: PathBuf,
+}
+
+impl PluginVersions {
+    pub fn open(scope: InstallScope) -> Result<Self> {
+        Ok(Self {
+            dir: scope.state_dir()?.join(VERSIONS_DIR),
+        })
+    }
+
+    /// keep copy of plugin binary `file_name` for `version`
+    pub fn store(
+        &self,
+        file_name: &str,
+        version: &Version,
+        bytes: impl AsRef<[u8]>,
+    ) -> Result<PathBuf> {
+        let path = self.binary_path(file_name, version);
+        debug!(?path, "storing plugin version");
+        install_bin(&path, bytes)?;
+        Ok(path)
+    }
+
+    /// kept versions of plugin, oldest first
+    pub fn versions(&self, file_name: &str) -> Result<Vec<Version>> {
+        let entries = match fs::read_dir(self.dir.join(file_name)) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(vec![]),
+            Err(err) => return Err(err.into()),
+        };
+        let mut versions = Vec::new();
+        for entry in entries {
+            let entry = entry?;
+            let Some(version) = entry
+                .file_name()
+                .to_str()
+                .and_then(|it| Version::parse(it).ok())
+            else {
+                continue;
+            };
+            if self.get(file_name, &version).is_some() {
+                versions.push(version);
+            }
+        }
+        versions.sort();
+        Ok(versions)
+    }
+
+    /// path of kept binary for `version` of plugin, if any
+    pub fn get(&self, file_name: &str, version: &Version) -> Option<PathBuf> {
+        let path = self.binary_path(file_name, version);
+        path.is_file().then_some(path)
+    }
+
+    fn binary_path(&self, file_name: &str, version: &Version) -> PathBuf {
+        self.dir
+            .join(file_name)
+            .join(version.to_string())
+            .join(file_name)
+    }
+}
+
+/// Plugin versions pinned by a project
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct PluginLock {
+    #[serde(default)]
+    pub plugins: BTreeMap<String, Version>,
+}
+
+impl PluginLock {
+    /// nearest lockfile in `dir` or its ancestors
+    pub fn find(dir: &Path) -> Result<Option<(PathBuf, Self)>> {
+        for dir in dir.ancestors() {
+            let path = dir.join(PLUGIN_LOCK_FILE);
+            match fs::read(&path) {
+                Ok(bytes) => {
+                    let lock = serde_json::from_slice(&bytes).map_err(|err| {
+                        CliError::Other(format!("Invalid lockfile {}: {err}", path.display()))
+                    })?;
+                    return Ok(Some((path, lock)));
+                }
+                Err(err) if err.kind() == ErrorKind::NotFound => continue,
+                Err(err) => return Err(err.into()),
+            }
+        }
+        Ok(None)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        fs::write(path, serde_json::to_vec_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// environment variable pinning version of plugin, e.g. `FLUVIO_CLOUD_VERSION` for `fluvio-cloud`
+pub fn version_env(file_name: &str) -> String {
+    format!("{}_VERSION", file_name.to_uppercase().replace('-', "_"))
+}
+
+/// version of plugin pinned by environment or lockfile, with what pinned it
+pub fn pinned_version(file_name: &str, dir: &Path) -> Result<Option<(Version, String)>> {
+    let env = version_env(file_name);
+    if let Ok(version) = std::env::var(&env) {
+        let version = Version::parse(version.trim())
+            .map_err(|err| CliError::Other(format!("Invalid version in {env}: {err}")))?;
+        return Ok(Some((version, env)));
+    }
+    Ok(PluginLock::find(dir)?.and_then(|(path, mut lock)| {
+        let version = lock.plugins.remove(file_name)?;
+        Some((version, path.display().to_string()))
+    }))
+}
+
+/// binary of pinned version of plugin, none if the plugin is not pinned
+///
+/// Fails if the pinned version is not installed, rather than running another version.
+pub fn pinned_plugin(file_name: &str) -> Result<Option<PathBuf>> {
+    let Some((version, pinned_by)) = pinned_version(file_name, &std::env::current_dir()?)? else {
+        return Ok(None);
+    };
+    for scope in [InstallScope::User, InstallScope::System] {
+        if let Some(path) = PluginVersions::open(scope)?.get(file_name, &version) {
+            debug!(?path, %pinned_by, "dispatching to pinned plugin version");
+            return Ok(Some(path));
+        }
+    }
+    Err(CliError::Other(format!(
+        "{file_name} {version} is pinned by {pinned_by} but not installed, install it with `fluvio install {file_name}:{version}`"
+    ))
+    .into())
+}
+
+#[derive(Parser, Debug)]
+pub struct PinOpt {
+    /// Plugin to pin, e.g. "fluvio-cloud" or "cloud"
+    plugin: String,
+    /// Version to pin, lists installed versions if omitted
+    version: Option<Version>,
+    /// Remove the pin of plugin from the lockfile
+    #[arg(long, conflicts_with = "version")]
+    unpin: bool,
+}
+
+impl PinOpt {
+    pub async fn process(self) -> Result<()> {
+        let file_name = if self.plugin.starts_with("fluvio-") {
+            self.plugin.clone()
+        } else {
+            format!("fluvio-{}", self.plugin)
+        };
+        let current_dir = std::env::current_dir()?;
+        let (lock_path, mut lock) = PluginLock::find(&current_dir)?
+            .unwrap_or_else(|| (current_dir.join(PLUGIN_LOCK_FILE), PluginLock::default()));
+
+        if self.unpin {
+            if lock.plugins.remove(&file_name).is_some() {
+                lock.save(&lock_path)?;
+                install_println(format!(
+                    "📌 Unpinned {file_name} in {}",
+                    lock_path.display()
+                ));
+            } else {
+                install_println(format!("👍 {file_name} is not pinned"));
+            }
+            return Ok(());
+        }
+
+        let versions = PluginVersions::open(InstallScope::User)?;
+        let Some(version) = self.version else {
+            let pinned = pinned_version(&file_name, &current_dir)?;
+            let installed = versions.versions(&file_name)?;
+            if installed.is_empty() {
+                install_println(format!("No versions of {file_name} are installed"));
+            }
+            for version in installed {
+                match &pinned {
+                    Some((pinned, pinned_by)) if *pinned == version => {
+                        install_println(format!("{version} (pinned by {pinned_by})"))
+                    }
+                    _ => install_println(version.to_string()),
+                }
+            }
+            return Ok(());
+        };
+
+        if versions.get(&file_name, &version).is_none()
+            && PluginVersions::open(InstallScope::System)?
+                .get(&file_name, &version)
+                .is_none()
+        {
+            install_println(format!(
+                "⚠️ {file_name} {version} is not installed, install it with `fluvio install {file_name}:{version}`"
+            ));
+        }
+        lock.plugins.insert(file_name.clone(), version.clone());
+        lock.save(&lock_path)?;
+        install_println(format!(
+            "📌 Pinned {file_name} {version} in {}",
+            lock_path.display()
+        ));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_version_env() {
+        assert_eq!(version_env("fluvio-cloud"), "FLUVIO_CLOUD_VERSION");
+        assert_eq!(version_env("fluvio-cdk"), "FLUVIO_CDK_VERSION");
+    }
+
+    #[test]
+    fn test_plugin_versions() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let versions = PluginVersions {
+            dir: dir.path().to_owned(),
+        };
+        let v024 = Version::parse("0.2.4").unwrap();
+        let v0210 = Version::parse("0.2.10").unwrap();
+
+        assert!(versions.versions("fluvio-cloud").unwrap().is_empty());
+        versions.store("fluvio-cloud", &v0210, b"new").unwrap();
+        let path = versions.store("fluvio-cloud", &v024, b"old").unwrap();
+        fs::create_dir_all(dir.path().join("fluvio-cloud").join("not-a-version")).unwrap();
+
+        assert_eq!(
+            versions.versions("fluvio-cloud").unwrap(),
+            vec![v024.clone(), v0210]
+        );
+        assert_eq!(versions.get("fluvio-cloud", &v024), Some(path.clone()));
+        assert_eq!(fs::read(path).unwrap(), b"old");
+        assert!(versions
+            .get("fluvio-cloud", &Version::parse("0.1.0").unwrap())
+            .is_none());
+    }
+
+    #[test]
+    fn test_lock_find() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let project = dir.path().join("project").join("src");
+        fs::create_dir_all(&project).unwrap();
+        assert!(PluginLock::find(&project).unwrap().is_none());
+
+        let mut lock = PluginLock::default();
+        lock.plugins
+            .insert("fluvio-cloud".to_owned(), Version::parse("0.2.4").unwrap());
+        let lock_path = dir.path().join("project").join(PLUGIN_LOCK_FILE);
+        lock.save(&lock_path).unwrap();
+
+        let (found_path, found) = PluginLock::find(&project).unwrap().expect("lockfile");
+        assert_eq!(found_path, lock_path);
+        assert_eq!(found, lock);
+
+        let (version, pinned_by) = pinned_version("fluvio-cloud", &project)
+            .unwrap()
+            .expect("pinned");
+        assert_eq!(version, Version::parse("0.2.4").unwrap());
+        assert_eq!(pinned_by, lock_path.display().to_string());
+        assert!(pinned_version("fluvio-cdk", &project).unwrap().is_none());
+    }
+}
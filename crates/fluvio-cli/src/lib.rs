@@ -70,11 +70,20 @@ mod root {
 
     #[cfg(feature = "k8s")]
     use fluvio_cluster::cli::ClusterCmd;
-    use fluvio_cli_common::install::fluvio_extensions_dir;
+    use fluvio_cli_common::install::{fluvio_extensions_dir, fluvio_system_extensions_dir};
     use fluvio_channel::{FLUVIO_RELEASE_CHANNEL, LATEST_CHANNEL_NAME};
 
     use crate::profile::ProfileOpt;
+    use crate::install::network::configure_http;
     use crate::install::opts::InstallOpt;
+    use crate::install::repair::RepairOpt;
+    use crate::install::bootstrap::SetupOpt;
+    use crate::install::gc::GcOpt;
+    use crate::install::rollback::RollbackOpt;
+    use crate::install::toolchain::ToolchainCmd;
+    use crate::install::trust::TrustCmd;
+    use crate::install::uninstall::UninstallOpt;
+    use crate::install::versions::{pinned_plugin, PinOpt};
     use crate::client::FluvioCmd;
     use crate::metadata::{MetadataOpt, subcommand_metadata};
     use crate::version::VersionOpt;
@@ -93,6 +102,11 @@ mod root {
 
     impl Root {
         pub async fn process(self) -> Result<()> {
+            // invalid settings should not prevent using the CLI, only downloads are affected
+            if let Err(err) = configure_http() {
+                println!("⚠️ Ignoring installer network settings: {err}");
+            }
+
             if command_triggers_update_check(&self.command) {
                 tracing::info!("Triggered a Fluvio Update Check");
                 check_for_channel_update().await;
@@ -152,6 +166,59 @@ mod root {
         #[command(name = "install", hide = true)]
         Install(InstallOpt),
 
+        /// Roll back a plugin to the version installed before it
+        ///
+        /// The binary replaced by the last install of a plugin is retained, rolling back
+        /// restores it. Rolling back twice restores the newer version.
+        #[command(name = "rollback", hide = true)]
+        Rollback(RollbackOpt),
+
+        /// Verify installed plugins against their recorded checksums
+        ///
+        /// Plugins whose binary is missing or corrupted are downloaded again,
+        /// at the version they were installed at.
+        #[command(name = "repair", hide = true)]
+        Repair(RepairOpt),
+
+        /// Remove binaries retained for rollback which are no longer needed
+        ///
+        /// Binaries not recorded in the install manifest are removed, and with
+        /// `--max-size` the least recently retained ones too.
+        #[command(name = "gc", hide = true)]
+        Gc(GcOpt),
+
+        /// Pin the version of a plugin used in the current project
+        ///
+        /// The pin is written to the `fluvio-plugins.json` lockfile, and `fluvio <plugin>`
+        /// runs the pinned version in the project.
+        #[command(name = "pin", hide = true)]
+        Pin(PinOpt),
+
+        /// Install the default set of plugins, for a working toolchain in one step
+        #[command(name = "setup", hide = true)]
+        Setup(SetupOpt),
+
+        /// Uninstall a plugin
+        ///
+        /// Removes the plugin binary, its previous version retained for rollback,
+        /// and its shell completions.
+        #[command(name = "uninstall", hide = true)]
+        Uninstall(UninstallOpt),
+
+        /// Manage publisher keys trusted to sign plugin releases
+        ///
+        /// Releases are verified before install according to the policy, which is
+        /// off by default.
+        #[command(subcommand, name = "trust", hide = true)]
+        Trust(TrustCmd),
+
+        /// Export or import a bundle of the CLI and installed plugins
+        ///
+        /// The bundle pins the exact versions of the binaries it holds, importing it
+        /// doesn't require access to the package index.
+        #[command(subcommand, name = "toolchain", hide = true)]
+        Toolchain(ToolchainCmd),
+
         /// Print Fluvio version information
         #[command(name = "version")]
         Version(VersionOpt),
@@ -206,6 +273,30 @@ mod root {
 
                     install.process().await?;
                 }
+                Self::Rollback(rollback) => {
+                    rollback.process().await?;
+                }
+                Self::Repair(repair) => {
+                    repair.process().await?;
+                }
+                Self::Gc(gc) => {
+                    gc.process().await?;
+                }
+                Self::Pin(pin) => {
+                    pin.process().await?;
+                }
+                Self::Setup(setup) => {
+                    setup.process().await?;
+                }
+                Self::Uninstall(uninstall) => {
+                    uninstall.process().await?;
+                }
+                Self::Trust(trust) => {
+                    trust.process()?;
+                }
+                Self::Toolchain(toolchain) => {
+                    toolchain.process().await?;
+                }
                 Self::Version(version) => {
                     version.process(root.target).await?;
                 }
@@ -309,6 +400,7 @@ mod root {
         which::which_in(name, self_dir, ".")
             .or_else(|_| which::which(name))
             .or_else(|_| which::which_in(name, ext_dir, "."))
+            .or_else(|_| which::which_in(name, Some(fluvio_system_extensions_dir()), "."))
             .ok()
     }
 
@@ -318,7 +410,8 @@ mod root {
 
         // Check for a matching external command in the environment
         let subcommand = format!("fluvio-{cmd}");
-        let subcommand_path = match find_plugin(&subcommand) {
+        let subcommand_path = match pinned_plugin(&subcommand)?.or_else(|| find_plugin(&subcommand))
+        {
             Some(path) => path,
             None => {
                 match fluvio_extensions_dir() {
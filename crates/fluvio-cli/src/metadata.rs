@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
 use clap::Parser;
@@ -45,14 +45,7 @@ pub fn subcommand_metadata() -> Result<Vec<SubcommandMetadata>> {
 
     let extensions = fluvio_cli_common::install::get_extensions()?;
     for path in extensions {
-        let result = Command::new(&path).arg("metadata").result();
-        let output = match result {
-            Ok(out) => out.stdout,
-            _ => continue,
-        };
-
-        let json_result = serde_json::from_slice::<FluvioExtensionMetadata>(&output);
-        if let Ok(meta) = json_result {
+        if let Some(meta) = extension_metadata(&path) {
             let subcommand = SubcommandMetadata { path, meta };
             metadata.push(subcommand);
         }
@@ -60,3 +53,9 @@ pub fn subcommand_metadata() -> Result<Vec<SubcommandMetadata>> {
 
     Ok(metadata)
 }
+
+/// Runs the extension to get its metadata, none if it doesn't provide it
+pub fn extension_metadata(path: &Path) -> Option<FluvioExtensionMetadata> {
+    let output = Command::new(path).arg("metadata").result().ok()?.stdout;
+    serde_json::from_slice(&output).ok()
+}
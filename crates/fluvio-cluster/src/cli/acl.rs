@@ -0,0 +1,146 @@
+//!
+//! # Access Control Lists
+//!
+//! CLI to list, grant and revoke ACL entries of topics, consumer groups and transactional ids
+//!
+use std::sync::Arc;
+
+use anyhow::Result;
+use clap::{Args, Parser};
+
+use fluvio::Fluvio;
+use fluvio::metadata::acl::{AclEntry, AclPermission, AclResourceType};
+
+use crate::cli::common::output::Terminal;
+
+#[derive(Debug, Parser)]
+pub enum AclCmd {
+    /// List ACL entries
+    #[command(name = "list")]
+    List,
+
+    /// Grant permission to principal
+    #[command(name = "add")]
+    Add(AclEntryOpt),
+
+    /// Revoke permission of principal
+    #[command(name = "remove")]
+    Remove(AclEntryOpt),
+}
+
+#[derive(Debug, Args)]
+pub struct AclEntryOpt {
+    /// Principal, "*" for every principal including unauthenticated ones
+    principal: String,
+
+    /// Permission: produce, consume or admin
+    permission: AclPermission,
+
+    /// Topic name, "*" for all topics or trailing "*" for prefix
+    #[arg(
+        long,
+        value_name = "pattern",
+        required_unless_present_any = ["group", "transactional_id"],
+        conflicts_with_all = ["group", "transactional_id"]
+    )]
+    topic: Option<String>,
+
+    /// Consumer group name, "*" for all groups or trailing "*" for prefix
+    #[arg(long, value_name = "pattern", conflicts_with = "transactional_id")]
+    group: Option<String>,
+
+    /// Transactional id of producers, "*" for all ids or trailing "*" for prefix
+    #[arg(long, value_name = "pattern")]
+    transactional_id: Option<String>,
+}
+
+impl AclEntryOpt {
+    fn into_entry(self) -> AclEntry {
+        let (resource_type, resource) = match (self.topic, self.group, self.transactional_id) {
+            (Some(topic), _, _) => (AclResourceType::Topic, topic),
+            (None, Some(group), _) => (AclResourceType::ConsumerGroup, group),
+            (None, None, id) => (AclResourceType::TransactionalId, id.unwrap_or_default()),
+        };
+        AclEntry::new(self.principal, resource_type, resource, self.permission)
+    }
+}
+
+impl AclCmd {
+    pub async fn process<O: Terminal>(self, out: Arc<O>, fluvio: &Fluvio) -> Result<()> {
+        let admin = fluvio.admin().await;
+        match self {
+            Self::List => {
+                let response = admin.acl_list().await?;
+                if !response.enabled {
+                    out.println("ACLs are not enforced by the cluster");
+                }
+                if response.entries.is_empty() {
+                    out.println("no ACL entries");
+                }
+                for entry in response.entries {
+                    out.println(&entry.to_string());
+                }
+            }
+            Self::Add(opt) => {
+                let entry = opt.into_entry();
+                admin.acl_add(vec![entry.clone()]).await?;
+                out.println(&format!("granted: {entry}"));
+            }
+            Self::Remove(opt) => {
+                let entry = opt.into_entry();
+                admin.acl_remove(vec![entry.clone()]).await?;
+                out.println(&format!("revoked: {entry}"));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use fluvio::metadata::acl::{AclEntry, AclPermission, AclResourceType};
+
+    use super::AclCmd;
+
+    #[test]
+    fn test_parse_acl_entry() {
+        let AclCmd::Add(opt) =
+            AclCmd::parse_from(["acl", "add", "alice", "produce", "--topic", "orders-*"])
+        else {
+            panic!("expected add");
+        };
+        assert_eq!(
+            opt.into_entry(),
+            AclEntry::new(
+                "alice",
+                AclResourceType::Topic,
+                "orders-*",
+                AclPermission::Produce
+            )
+        );
+
+        let AclCmd::Remove(opt) = AclCmd::parse_from([
+            "acl",
+            "remove",
+            "alice",
+            "produce",
+            "--transactional-id",
+            "orders-writer",
+        ]) else {
+            panic!("expected remove");
+        };
+        assert_eq!(
+            opt.into_entry(),
+            AclEntry::new(
+                "alice",
+                AclResourceType::TransactionalId,
+                "orders-writer",
+                AclPermission::Produce
+            )
+        );
+
+        assert!(AclCmd::try_parse_from(["acl", "remove", "alice", "consume"]).is_err());
+    }
+}
@@ -0,0 +1,82 @@
+//!
+//! # Audit Log
+//!
+//! CLI to show most recent admin operations recorded by the cluster
+//!
+use std::sync::Arc;
+
+use anyhow::Result;
+use chrono::NaiveDateTime;
+use clap::Parser;
+
+use fluvio::Fluvio;
+use fluvio::metadata::audit::AuditEntry;
+
+use crate::cli::common::output::Terminal;
+
+#[derive(Debug, Parser)]
+pub struct AuditOpt {
+    /// Number of most recent operations to show, all kept by the cluster if 0
+    #[arg(short = 'n', long, default_value_t = 20)]
+    limit: u32,
+}
+
+impl AuditOpt {
+    pub async fn process<O: Terminal>(self, out: Arc<O>, fluvio: &Fluvio) -> Result<()> {
+        let admin = fluvio.admin().await;
+        let entries = admin.audit_log(self.limit).await?;
+        if entries.is_empty() {
+            out.println("no admin operations recorded");
+            return Ok(());
+        }
+        for entry in entries {
+            out.println(&format_entry(&entry));
+        }
+        Ok(())
+    }
+}
+
+fn format_entry(entry: &AuditEntry) -> String {
+    let time = NaiveDateTime::from_timestamp_millis(entry.timestamp_ms as i64)
+        .map(|time| time.format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string())
+        .unwrap_or_default();
+    let mut line = format!(
+        "{time} {} {} {} {}",
+        entry.principal.as_deref().unwrap_or("-"),
+        entry.operation,
+        entry.object,
+        entry.name
+    );
+    if !entry.summary.is_empty() {
+        line.push_str(&format!(" ({})", entry.summary));
+    }
+    if !entry.is_ok() {
+        line.push_str(&format!(" failed: {:?}", entry.error_code));
+    }
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use fluvio::metadata::audit::AuditEntry;
+    use fluvio_sc_schema::errors::ErrorCode;
+
+    use super::format_entry;
+
+    #[test]
+    fn test_format_entry() {
+        let entry = AuditEntry {
+            timestamp_ms: 1_700_000_000_000,
+            principal: Some("alice".to_owned()),
+            operation: "add-partitions".to_owned(),
+            object: "Topic".to_owned(),
+            name: "orders".to_owned(),
+            summary: "partitions=3".to_owned(),
+            error_code: ErrorCode::PermissionDenied,
+        };
+        assert_eq!(
+            format_entry(&entry),
+            "2023-11-14T22:13:20.000Z alice add-partitions Topic orders (partitions=3) failed: PermissionDenied"
+        );
+    }
+}
@@ -9,7 +9,9 @@ use sysinfo::{System, SystemExt, NetworkExt, ProcessExt, DiskExt, PidExt};
 use which::which;
 use anyhow::Result;
 
+use fluvio::Fluvio;
 use fluvio::metadata::{topic::TopicSpec, partition::PartitionSpec, spg::SpuGroupSpec, spu::SpuSpec};
+use fluvio::metadata::diagnostics::ClusterHealthReport;
 use fluvio_sc_schema::objects::Metadata;
 
 use crate::{InstallationType, cli::get_installation_type};
@@ -21,10 +23,26 @@ use crate::start::local::{DEFAULT_DATA_DIR as DEFAULT_LOCAL_DIR, DEFAULT_METADAT
 pub struct DiagnosticsOpt {
     #[arg(long)]
     quiet: bool,
+
+    /// Only show health of SPUs, partitions and mirrors reported by the cluster,
+    /// fails if cluster has critical issues
+    #[arg(long)]
+    health: bool,
 }
 
 impl DiagnosticsOpt {
     pub async fn process(self) -> Result<()> {
+        if self.health {
+            let report = cluster_health().await?;
+            print!("{}", format_report(&report));
+            if !report.is_healthy() {
+                return Err(
+                    ClusterCliError::Other("cluster has critical issues".to_owned()).into(),
+                );
+            }
+            return Ok(());
+        }
+
         let (installation_ty, config) = get_installation_type()?;
         let profile = config.config().current_profile_name().unwrap_or("none");
         println!("Installation type: {installation_ty:#?}\nProfile: {profile}");
@@ -41,6 +59,11 @@ impl DiagnosticsOpt {
             }
         };
 
+        // SPU disk, lag, mirror and version health as aggregated by SC
+        if let Err(err) = self.write_cluster_health(temp_path).await {
+            eprintln!("error getting cluster health: {err:#?}");
+        }
+
         // write internal fluvio cluster internal state
         match installation_ty {
             // Local cluster
@@ -212,8 +235,6 @@ impl DiagnosticsOpt {
     }
 
     async fn copy_fluvio_specs(&self, dest: &Path) -> Result<Vec<Metadata<SpuSpec>>> {
-        use fluvio::Fluvio;
-
         println!("start copying fluvio specs from...");
         let fluvio = Fluvio::connect().await?;
         let admin = fluvio.admin().await;
@@ -247,6 +268,18 @@ impl DiagnosticsOpt {
         Ok(spus)
     }
 
+    async fn write_cluster_health(&self, dest: &Path) -> Result<()> {
+        println!("getting cluster health");
+        let report = cluster_health().await?;
+        let path = dest.join("cluster-health.json");
+        self.dump(
+            "cluster health",
+            path,
+            Ok(serde_json::to_string_pretty(&report)?),
+        )?;
+        Ok(())
+    }
+
     /// write helm and other basic stuff
     fn write_helm(&self, dest_dir: &Path) -> Result<()> {
         let path = dest_dir.join("helm-list.txt");
@@ -463,3 +496,85 @@ impl ProcessInfo {
         processes
     }
 }
+
+async fn cluster_health() -> Result<ClusterHealthReport> {
+    let fluvio = Fluvio::connect().await?;
+    let admin = fluvio.admin().await;
+    admin.cluster_health().await
+}
+
+fn format_report(report: &ClusterHealthReport) -> String {
+    let mut out = format!("SC version: {}\n", report.sc_version);
+
+    for spu in &report.spus {
+        let status = if spu.online { "online" } else { "offline" };
+        let disk = spu
+            .disk_available_percent()
+            .map(|available| {
+                format!(
+                    "{available:.1}% of {} available",
+                    bytesize::ByteSize::b(spu.disk_total_bytes)
+                )
+            })
+            .unwrap_or_else(|| "-".to_owned());
+        out.push_str(&format!(
+            "SPU {} {status} version: {} disk: {disk} leaders: {}\n",
+            spu.id,
+            spu.version.as_deref().unwrap_or("-"),
+            spu.leaders
+        ));
+    }
+
+    for mirror in &report.mirrors {
+        out.push_str(&format!("Mirror {} {}\n", mirror.name, mirror.status));
+    }
+
+    if report.issues.is_empty() {
+        out.push_str("No issues found\n");
+    } else {
+        for issue in &report.issues {
+            out.push_str(&format!("{issue}\n"));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use fluvio::metadata::diagnostics::{ClusterHealthReport, HealthIssue, SpuHealth};
+
+    use super::format_report;
+
+    #[test]
+    fn test_format_report() {
+        let report = ClusterHealthReport {
+            sc_version: "0.11.9".to_owned(),
+            spus: vec![
+                SpuHealth {
+                    id: 5001,
+                    name: "spu-5001".to_owned(),
+                    online: true,
+                    version: Some("0.11.9".to_owned()),
+                    disk_total_bytes: 1000,
+                    disk_available_bytes: 250,
+                    leaders: 2,
+                    leader_bytes: 100,
+                },
+                SpuHealth {
+                    id: 5002,
+                    name: "spu-5002".to_owned(),
+                    ..Default::default()
+                },
+            ],
+            issues: vec![HealthIssue::critical("spu 5002", "SPU is offline")],
+            ..Default::default()
+        };
+        assert_eq!(
+            format_report(&report),
+            "SC version: 0.11.9\n\
+             SPU 5001 online version: 0.11.9 disk: 25.0% of 1.0 KB available leaders: 2\n\
+             SPU 5002 offline version: - disk: - leaders: 0\n\
+             [critical] spu 5002: SPU is offline\n"
+        );
+    }
+}
@@ -0,0 +1,379 @@
+//!
+//! # Metadata Backup
+//!
+//! CLI to export specs managed by SC to archive file and import them back, for disaster recovery
+//! or migration between metadata backends. Statuses are not archived, SC recomputes them.
+//! Objects and ACL entries which already exist are skipped on import, so import can be retried.
+//!
+use std::fmt::Debug;
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use anyhow::{anyhow, Context, Result};
+use clap::{Parser, Subcommand};
+use serde::{Deserialize, Serialize};
+use serde::de::DeserializeOwned;
+
+use fluvio::{Fluvio, FluvioAdmin};
+use fluvio::metadata::AdminSpec;
+use fluvio::metadata::acl::AclEntry;
+use fluvio::metadata::customspu::CustomSpuSpec;
+use fluvio::metadata::smartmodule::SmartModuleSpec;
+use fluvio::metadata::spg::SpuGroupSpec;
+use fluvio::metadata::topic::TopicSpec;
+use fluvio::dataplane::core::{Decoder, Encoder};
+use fluvio_sc_schema::CreatableAdminSpec;
+use fluvio_sc_schema::mirror::MirrorSpec;
+
+use crate::cli::common::output::Terminal;
+use crate::cli::VERSION;
+
+/// format version of archive, increased on incompatible changes
+const ARCHIVE_VERSION: u32 = 1;
+
+#[derive(Debug, Subcommand)]
+pub enum MetadataCmd {
+    /// Export topics, SPUs, SPU groups, SmartModules, mirrors and ACLs to archive file
+    #[command(name = "export")]
+    Export(ExportOpt),
+
+    /// Import objects from archive file, skipping ones which already exist
+    #[command(name = "import")]
+    Import(ImportOpt),
+}
+
+impl MetadataCmd {
+    pub async fn process<O: Terminal>(self, out: Arc<O>, fluvio: &Fluvio) -> Result<()> {
+        match self {
+            Self::Export(opt) => opt.process(out, fluvio).await,
+            Self::Import(opt) => opt.process(out, fluvio).await,
+        }
+    }
+}
+
+#[derive(Debug, Parser)]
+pub struct ExportOpt {
+    /// Archive file to write
+    #[arg(short, long, value_name = "path")]
+    file: PathBuf,
+}
+
+impl ExportOpt {
+    pub async fn process<O: Terminal>(self, out: Arc<O>, fluvio: &Fluvio) -> Result<()> {
+        let admin = fluvio.admin().await;
+        let archive = MetadataArchive::export(&admin).await?;
+
+        let file = File::create(&self.file)
+            .with_context(|| format!("unable to create {}", self.file.display()))?;
+        serde_json::to_writer_pretty(BufWriter::new(file), &archive)?;
+
+        out.println(&format!(
+            "exported {} to {}",
+            archive.summary(),
+            self.file.display()
+        ));
+        Ok(())
+    }
+}
+
+#[derive(Debug, Parser)]
+pub struct ImportOpt {
+    /// Archive file to read
+    #[arg(short, long, value_name = "path")]
+    file: PathBuf,
+
+    /// Validate objects without creating them
+    #[arg(long)]
+    dry_run: bool,
+}
+
+impl ImportOpt {
+    pub async fn process<O: Terminal>(self, out: Arc<O>, fluvio: &Fluvio) -> Result<()> {
+        let file = File::open(&self.file)
+            .with_context(|| format!("unable to open {}", self.file.display()))?;
+        let archive: MetadataArchive = serde_json::from_reader(BufReader::new(file))?;
+        archive.validate()?;
+
+        let admin = fluvio.admin().await;
+        // SPUs are created before topics whose replicas are assigned to them
+        let mut created = 0;
+        let mut skipped = 0;
+        for (kind, (kind_created, kind_skipped)) in [
+            (
+                "spu groups",
+                import(&admin, &archive.spu_groups, self.dry_run).await?,
+            ),
+            (
+                "custom spus",
+                import(&admin, &archive.custom_spus, self.dry_run).await?,
+            ),
+            (
+                "topics",
+                import(&admin, &archive.topics, self.dry_run).await?,
+            ),
+            (
+                "smartmodules",
+                import(&admin, &archive.smartmodules, self.dry_run).await?,
+            ),
+            (
+                "mirrors",
+                import(&admin, &archive.mirrors, self.dry_run).await?,
+            ),
+            (
+                "acls",
+                import_acls(&admin, &archive.acls, self.dry_run).await?,
+            ),
+        ] {
+            out.println(&format!(
+                "{kind}: {kind_created} imported, {kind_skipped} already exist"
+            ));
+            created += kind_created;
+            skipped += kind_skipped;
+        }
+
+        let verb = if self.dry_run {
+            "validated"
+        } else {
+            "imported"
+        };
+        out.println(&format!(
+            "{verb} {created} objects from {}, skipped {skipped}",
+            self.file.display()
+        ));
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MetadataArchive {
+    pub version: u32,
+    /// version of Fluvio which exported archive
+    pub fluvio_version: String,
+    pub topics: Vec<ArchivedObject<TopicSpec>>,
+    pub custom_spus: Vec<ArchivedObject<CustomSpuSpec>>,
+    pub spu_groups: Vec<ArchivedObject<SpuGroupSpec>>,
+    pub smartmodules: Vec<ArchivedObject<SmartModuleSpec>>,
+    pub mirrors: Vec<ArchivedObject<MirrorSpec>>,
+    /// missing in archives exported before ACLs were archived
+    #[serde(default)]
+    pub acls: Vec<AclEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(bound(deserialize = "S: DeserializeOwned"))]
+pub struct ArchivedObject<S> {
+    pub name: String,
+    pub spec: S,
+}
+
+impl MetadataArchive {
+    async fn export(admin: &FluvioAdmin) -> Result<Self> {
+        let topics = admin
+            .all::<TopicSpec>()
+            .await?
+            .into_iter()
+            // system topics are created by SC
+            .filter(|topic| !topic.spec.is_system())
+            .map(|topic| ArchivedObject {
+                name: topic.name,
+                spec: topic.spec,
+            })
+            .collect();
+
+        Ok(Self {
+            version: ARCHIVE_VERSION,
+            fluvio_version: VERSION.trim().to_owned(),
+            topics,
+            custom_spus: export(admin).await?,
+            spu_groups: export(admin).await?,
+            smartmodules: export(admin).await?,
+            mirrors: export(admin).await?,
+            acls: admin
+                .acl_list()
+                .await
+                .context("unable to export ACLs")?
+                .entries,
+        })
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.version > ARCHIVE_VERSION {
+            return Err(anyhow!(
+                "archive version {} exported by Fluvio {} is not supported, max supported version is {ARCHIVE_VERSION}",
+                self.version,
+                self.fluvio_version
+            ));
+        }
+        Ok(())
+    }
+
+    fn summary(&self) -> String {
+        format!(
+            "{} topics, {} custom spus, {} spu groups, {} smartmodules, {} mirrors, {} acls",
+            self.topics.len(),
+            self.custom_spus.len(),
+            self.spu_groups.len(),
+            self.smartmodules.len(),
+            self.mirrors.len(),
+            self.acls.len()
+        )
+    }
+}
+
+async fn export<S>(admin: &FluvioAdmin) -> Result<Vec<ArchivedObject<S>>>
+where
+    S: AdminSpec,
+    S::Status: Encoder + Decoder + Debug,
+{
+    Ok(admin
+        .all::<S>()
+        .await?
+        .into_iter()
+        .map(|object| ArchivedObject {
+            name: object.name,
+            spec: object.spec,
+        })
+        .collect())
+}
+
+/// create objects which don't exist yet, returns number of created and skipped objects
+async fn import<S>(
+    admin: &FluvioAdmin,
+    objects: &[ArchivedObject<S>],
+    dry_run: bool,
+) -> Result<(usize, usize)>
+where
+    S: CreatableAdminSpec + AdminSpec + Clone + Sync + Send,
+    S::Status: Encoder + Decoder + Debug,
+{
+    if objects.is_empty() {
+        return Ok((0, 0));
+    }
+    let existing: Vec<String> = admin
+        .all::<S>()
+        .await?
+        .into_iter()
+        .map(|object| object.name)
+        .collect();
+
+    let mut created = 0;
+    for object in objects {
+        if existing.contains(&object.name) {
+            continue;
+        }
+        admin
+            .create(object.name.clone(), dry_run, object.spec.clone())
+            .await
+            .with_context(|| format!("unable to import {} {}", S::LABEL, object.name))?;
+        created += 1;
+    }
+    Ok((created, objects.len() - created))
+}
+
+/// add ACL entries which don't exist yet, returns number of added and skipped entries
+async fn import_acls(
+    admin: &FluvioAdmin,
+    entries: &[AclEntry],
+    dry_run: bool,
+) -> Result<(usize, usize)> {
+    if entries.is_empty() {
+        return Ok((0, 0));
+    }
+    let existing = admin.acl_list().await?.entries;
+    let missing = missing_acls(&existing, entries);
+    let added = missing.len();
+    if !dry_run && !missing.is_empty() {
+        admin
+            .acl_add(missing)
+            .await
+            .context("unable to import ACLs")?;
+    }
+    Ok((added, entries.len() - added))
+}
+
+/// archived entries not in existing ones, without duplicates
+fn missing_acls(existing: &[AclEntry], archived: &[AclEntry]) -> Vec<AclEntry> {
+    let mut missing: Vec<AclEntry> = vec![];
+    for entry in archived {
+        if !existing.contains(entry) && !missing.contains(entry) {
+            missing.push(entry.clone());
+        }
+    }
+    missing
+}
+
+#[cfg(test)]
+mod tests {
+    use fluvio::metadata::acl::{AclPermission, AclResourceType};
+
+    use super::*;
+
+    fn archive(version: u32) -> MetadataArchive {
+        MetadataArchive {
+            version,
+            fluvio_version: "0.11.9".to_owned(),
+            topics: vec![ArchivedObject {
+                name: "orders".to_owned(),
+                spec: TopicSpec::new_computed(3, 2, None),
+            }],
+            custom_spus: vec![],
+            spu_groups: vec![],
+            smartmodules: vec![],
+            mirrors: vec![],
+            acls: vec![
+                AclEntry::new(
+                    "alice",
+                    AclResourceType::Topic,
+                    "orders",
+                    AclPermission::Produce,
+                ),
+                AclEntry::new(
+                    "bob",
+                    AclResourceType::ConsumerGroup,
+                    "*",
+                    AclPermission::Consume,
+                ),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_archive_roundtrip() {
+        let archive = archive(ARCHIVE_VERSION);
+        let json = serde_json::to_string(&archive).expect("serialize");
+        let restored: MetadataArchive = serde_json::from_str(&json).expect("deserialize");
+        assert_eq!(restored, archive);
+        assert!(restored.validate().is_ok());
+    }
+
+    #[test]
+    fn test_archive_without_acls() {
+        let mut json = serde_json::to_value(archive(ARCHIVE_VERSION)).expect("serialize");
+        json.as_object_mut().expect("object").remove("acls");
+        let restored: MetadataArchive = serde_json::from_value(json).expect("deserialize");
+        assert!(restored.acls.is_empty());
+        assert_eq!(restored.topics.len(), 1);
+    }
+
+    #[test]
+    fn test_missing_acls() {
+        let archive = archive(ARCHIVE_VERSION);
+        assert_eq!(missing_acls(&[], &archive.acls), archive.acls);
+        assert_eq!(missing_acls(&archive.acls, &archive.acls), vec![]);
+
+        let existing = vec![archive.acls[0].clone()];
+        let duplicated = vec![archive.acls[1].clone(), archive.acls[1].clone()];
+        assert_eq!(
+            missing_acls(&existing, &duplicated),
+            vec![archive.acls[1].clone()]
+        );
+    }
+
+    #[test]
+    fn test_archive_newer_version() {
+        assert!(archive(ARCHIVE_VERSION + 1).validate().is_err());
+    }
+}
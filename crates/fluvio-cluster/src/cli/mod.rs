@@ -19,6 +19,9 @@ mod diagnostics;
 mod status;
 mod shutdown;
 mod upgrade;
+mod audit;
+mod acl;
+mod metadata;
 
 use start::StartOpt;
 use resume::ResumeOpt;
@@ -30,6 +33,9 @@ use diagnostics::DiagnosticsOpt;
 use status::StatusOpt;
 use shutdown::ShutdownOpt;
 use upgrade::UpgradeOpt;
+use audit::AuditOpt;
+use acl::AclCmd;
+use metadata::MetadataCmd;
 
 pub use self::error::ClusterCliError;
 
@@ -94,6 +100,18 @@ pub enum ClusterCmd {
     /// Shutdown cluster processes without deleting data
     #[command(name = "shutdown")]
     Shutdown(ShutdownOpt),
+
+    /// Show most recent admin operations recorded by the cluster
+    #[command(name = "audit")]
+    Audit(AuditOpt),
+
+    /// Manage access control lists of topics and consumer groups
+    #[command(subcommand, name = "acl")]
+    Acl(AclCmd),
+
+    /// Back up and restore topics, SPUs, SmartModules and mirrors managed by SC
+    #[command(subcommand, name = "metadata")]
+    Metadata(MetadataCmd),
 }
 
 impl ClusterCmd {
@@ -169,6 +187,18 @@ impl ClusterCmd {
             Self::Shutdown(opt) => {
                 opt.process().await?;
             }
+            Self::Audit(opt) => {
+                let fluvio = target.connect().await?;
+                opt.process(out, &fluvio).await?;
+            }
+            Self::Acl(cmd) => {
+                let fluvio = target.connect().await?;
+                cmd.process(out, &fluvio).await?;
+            }
+            Self::Metadata(cmd) => {
+                let fluvio = target.connect().await?;
+                cmd.process(out, &fluvio).await?;
+            }
         }
 
         Ok(())
@@ -51,10 +51,15 @@ impl TableOutputHandler for ListSpus {
             .iter()
             .map(|metadata| {
                 let spu = &metadata.spec;
+                let status = if spu.draining {
+                    format!("{} (draining)", metadata.status)
+                } else {
+                    metadata.status.to_string()
+                };
                 Row::from([
                     Cell::new(spu.id),
                     Cell::new(metadata.name.to_string()),
-                    Cell::new(metadata.status.to_string()),
+                    Cell::new(status),
                     Cell::new(spu.spu_type.to_string()),
                     Cell::new(spu.rack.as_ref().unwrap_or(&"-".to_string())),
                     Cell::new(spu.public_endpoint.to_string()),
@@ -0,0 +1,68 @@
+//!
+//! # Drain SPUs
+//!
+//! CLI tree to drain SPU before maintenance
+//!
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use clap::Parser;
+
+use fluvio::Fluvio;
+use fluvio_future::timer::sleep;
+
+use crate::cli::common::output::Terminal;
+
+const DRAIN_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Parser)]
+pub struct DrainSpuOpt {
+    /// SPU id
+    #[arg(short = 'i', long = "id")]
+    id: i32,
+
+    /// Stop draining SPU
+    #[arg(long, conflicts_with = "wait")]
+    cancel: bool,
+
+    /// Wait until SPU doesn't lead any partition
+    #[arg(long)]
+    wait: bool,
+}
+
+impl DrainSpuOpt {
+    pub async fn process<O: Terminal>(self, out: Arc<O>, fluvio: &Fluvio) -> Result<()> {
+        let admin = fluvio.admin().await;
+        if self.cancel {
+            admin.cancel_spu_drain(self.id).await?;
+            out.println(&format!("drain of spu {} cancelled", self.id));
+            return Ok(());
+        }
+
+        loop {
+            let progress = admin.drain_spu(self.id).await?;
+            if progress.is_drained() {
+                out.println(&format!("spu {} is drained and safe to shut down", self.id));
+                return Ok(());
+            }
+
+            let mut msg = format!(
+                "spu {} is draining, still leading {} partitions",
+                self.id, progress.leaders
+            );
+            if progress.blocked > 0 {
+                msg.push_str(&format!(
+                    ", {} of them have no other replica to move leadership to",
+                    progress.blocked
+                ));
+            }
+            out.println(&msg);
+
+            if !self.wait {
+                return Ok(());
+            }
+            sleep(DRAIN_POLL_INTERVAL).await;
+        }
+    }
+}
@@ -3,12 +3,14 @@ use clap::Parser;
 
 mod list;
 mod display;
+mod drain;
 mod register;
 mod unregister;
 
 use anyhow::Result;
 
 use fluvio::Fluvio;
+use drain::DrainSpuOpt;
 use list::ListSpusOpt;
 use register::RegisterCustomSpuOpt;
 use unregister::UnregisterCustomSpuOpt;
@@ -38,6 +40,13 @@ pub enum SpuCmd {
         help_template = COMMAND_TEMPLATE,
     )]
     List(ListSpusOpt),
+
+    /// Move leadership off SPU and stop assigning replicas to it, before shutting it down
+    #[command(
+        name = "drain",
+        help_template = COMMAND_TEMPLATE,
+    )]
+    Drain(DrainSpuOpt),
 }
 
 impl SpuCmd {
@@ -52,6 +61,9 @@ impl SpuCmd {
             Self::List(list) => {
                 list.process(out, fluvio).await?;
             }
+            Self::Drain(drain) => {
+                drain.process(out, fluvio).await?;
+            }
         }
         Ok(())
     }
@@ -52,6 +52,10 @@ pub async fn process_k8(opt: StartOpt, platform_version: Version, upgrade: bool)
         builder.rust_log(rust_log);
     }
 
+    if let Some(log_format) = opt.log_format {
+        builder.log_format(log_format);
+    }
+
     if let Some(map) = opt.authorization_config_map {
         builder.authorization_config_map(map);
     }
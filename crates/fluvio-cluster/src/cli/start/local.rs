@@ -26,6 +26,10 @@ pub async fn process_local(opt: StartOpt, platform_version: Version) -> Result<(
         builder.rust_log(rust_log);
     }
 
+    if let Some(log_format) = opt.log_format {
+        builder.log_format(log_format);
+    }
+
     if opt.tls.tls {
         let (client, server): (TlsPolicy, TlsPolicy) = opt.tls.try_into()?;
         builder.tls(client, server);
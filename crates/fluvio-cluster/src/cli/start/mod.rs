@@ -149,6 +149,10 @@ pub struct StartOpt {
     #[arg(long)]
     pub rust_log: Option<String>,
 
+    /// log format of SC and SPU, json writes a JSON object per line
+    #[arg(long, value_name = "FORMAT", value_parser = ["text", "json"])]
+    pub log_format: Option<String>,
+
     /// log dir
     #[arg(long, default_value_t)]
     pub log_dir: DefaultLogDirectory,
@@ -258,6 +258,7 @@ impl ClusterUninstaller {
         let _ = self.remove_custom_objects("managedconnectors", ns, None, false, &pb);
         let _ = self.remove_custom_objects("derivedstreams", ns, None, false, &pb);
         let _ = self.remove_custom_objects("smartmodules", ns, None, false, &pb);
+        let _ = self.remove_custom_objects("acls", ns, None, false, &pb);
 
         // delete secrets
         let _ = self.remove_secrets("fluvio-ca");
@@ -59,7 +59,7 @@ mod common {
     use std::{path::PathBuf, borrow::Cow};
 
     use anyhow::Result;
-    use fluvio::config::{TlsPaths, TlsConfig};
+    use fluvio::config::{TlsCerts, TlsPaths, TlsConfig};
     use serde::{Serialize, Deserialize};
 
     /// The result of a successful startup of a Fluvio cluster
@@ -105,37 +105,42 @@ mod common {
         use std::iter;
         use rand::Rng;
 
+        let write_certs = |certs: &TlsCerts| -> Result<TlsPaths> {
+            const NUM_RAND_DIR_CHARS: usize = 12;
+
+            let mut rng = rand::thread_rng();
+            let rand_dir_name: String = iter::repeat(())
+                .map(|()| rng.sample(Alphanumeric))
+                .map(char::from)
+                .take(NUM_RAND_DIR_CHARS)
+                .collect();
+
+            let tmp_dir = std::env::temp_dir().join(rand_dir_name);
+
+            std::fs::create_dir(&tmp_dir)?;
+
+            let tls_key = tmp_dir.join("tls.key");
+            let tls_cert = tmp_dir.join("tls.crt");
+            let ca_cert = tmp_dir.join("ca.crt");
+
+            write(&tls_key, certs.key.as_bytes())?;
+            write(&tls_cert, certs.cert.as_bytes())?;
+            write(&ca_cert, certs.ca_cert.as_bytes())?;
+
+            Ok(TlsPaths {
+                domain: certs.domain.clone(),
+                key: tls_key,
+                cert: tls_cert,
+                ca_cert,
+            })
+        };
+
         let cert_paths: Cow<TlsPaths> = match config {
             TlsConfig::Files(paths) => Cow::Borrowed(paths),
-            TlsConfig::Inline(certs) => Cow::Owned({
-                const NUM_RAND_DIR_CHARS: usize = 12;
-
-                let mut rng = rand::thread_rng();
-                let rand_dir_name: String = iter::repeat(())
-                    .map(|()| rng.sample(Alphanumeric))
-                    .map(char::from)
-                    .take(NUM_RAND_DIR_CHARS)
-                    .collect();
-
-                let tmp_dir = std::env::temp_dir().join(rand_dir_name);
-
-                std::fs::create_dir(&tmp_dir)?;
-
-                let tls_key = tmp_dir.join("tls.key");
-                let tls_cert = tmp_dir.join("tls.crt");
-                let ca_cert = tmp_dir.join("ca.crt");
-
-                write(&tls_key, certs.key.as_bytes())?;
-                write(&tls_cert, certs.cert.as_bytes())?;
-                write(&ca_cert, certs.ca_cert.as_bytes())?;
-
-                TlsPaths {
-                    domain: certs.domain.clone(),
-                    key: tls_key,
-                    cert: tls_cert,
-                    ca_cert,
-                }
-            }),
+            TlsConfig::Inline(certs) => Cow::Owned(write_certs(certs)?),
+            TlsConfig::Secrets(secrets) => {
+                Cow::Owned(write_certs(&TlsCerts::try_from(secrets.clone())?)?)
+            }
         };
         Ok(cert_paths)
     }
@@ -7,6 +7,7 @@ use std::{
 use anyhow::Result;
 use fluvio::config::TlsPolicy;
 use fluvio_command::CommandExt;
+use fluvio_types::defaults::FLV_LOG_FORMAT;
 use tracing::info;
 
 use super::{FluvioLocalProcess, LocalRuntimeError};
@@ -17,6 +18,7 @@ pub struct ScProcess {
     pub launcher: Option<PathBuf>,
     pub tls_policy: TlsPolicy,
     pub rust_log: String,
+    pub log_format: Option<String>,
     pub mode: ScMode,
 }
 
@@ -58,6 +60,9 @@ impl ScProcess {
             self.set_server_tls(&mut binary, tls, 9005)?;
         }
         binary.env("RUST_LOG", &self.rust_log);
+        if let Some(log_format) = &self.log_format {
+            binary.env(FLV_LOG_FORMAT, log_format);
+        }
 
         info!(cmd = %binary.display(),"Invoking command");
         binary
@@ -9,6 +9,7 @@ use fluvio_controlplane_metadata::spu::{Endpoint, IngressAddr, IngressPort, SpuS
 use fluvio_command::CommandExt;
 use fluvio::config::TlsPolicy;
 use fluvio_types::SpuId;
+use fluvio_types::defaults::FLV_LOG_FORMAT;
 
 use crate::runtime::spu::{SpuClusterManager, SpuTarget};
 
@@ -22,6 +23,7 @@ pub struct LocalSpuProcess {
     pub spec: SpuSpec,
     pub launcher: Option<PathBuf>,
     pub rust_log: String,
+    pub log_format: Option<String>,
     pub data_dir: PathBuf,
     pub tls_policy: TlsPolicy,
 }
@@ -46,6 +48,9 @@ impl SpuTarget for LocalSpuProcess {
             self.set_server_tls(&mut binary, tls, self.spec.private_endpoint.port + 1)?;
         }
         binary.env("RUST_LOG", &self.rust_log);
+        if let Some(log_format) = &self.log_format {
+            binary.env(FLV_LOG_FORMAT, log_format);
+        }
         let cmd = binary
             .arg("-i")
             .arg(format!("{}", self.id))
@@ -87,6 +92,7 @@ pub struct LocalSpuProcessClusterManager {
     pub log_dir: PathBuf,
     pub launcher: Option<PathBuf>,
     pub rust_log: String,
+    pub log_format: Option<String>,
     pub data_dir: PathBuf,
     pub tls_policy: TlsPolicy,
 }
@@ -126,6 +132,7 @@ impl SpuClusterManager for LocalSpuProcessClusterManager {
             spec: spu_spec,
             log_dir: spu_log_dir,
             rust_log: self.rust_log.clone(),
+            log_format: self.log_format.clone(),
             launcher: self.launcher.clone(),
             tls_policy: self.tls_policy.clone(),
             data_dir: self.data_dir.clone(),
@@ -213,6 +213,21 @@ pub struct ClusterConfig {
     /// [`RUST_LOG`]: https://docs.rs/tracing-subscriber/0.2.11/tracing_subscriber/filter/struct.EnvFilter.html
     #[builder(setter(into, strip_option), default)]
     rust_log: Option<String>,
+    /// Sets the log format of SC and SPU, `text` or `json`. Defaults to text.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use fluvio_cluster::{ClusterConfig, ClusterConfigBuilder, ClusterError};
+    /// # fn example(builder: &mut ClusterConfigBuilder) -> anyhow::Result<()> {
+    /// let config = builder
+    ///     .log_format("json")
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[builder(setter(into, strip_option), default)]
+    log_format: Option<String>,
     /// The TLS policy for the SC and SPU servers
     #[builder(default = "TlsPolicy::Disabled")]
     server_tls_policy: TlsPolicy,
@@ -834,6 +849,10 @@ impl ClusterInstaller {
             install_settings.push(("scLog", Cow::Borrowed(log)));
         }
 
+        if let Some(log_format) = &self.config.log_format {
+            install_settings.push(("scLogFormat", Cow::Borrowed(log_format)));
+        }
+
         if let Some(authorization_config_map) = &self.config.authorization_config_map {
             install_settings.push((
                 "authorizationConfigMap",
@@ -108,6 +108,21 @@ pub struct LocalConfig {
     /// [`RUST_LOG`]: https://docs.rs/tracing-subscriber/0.2.11/tracing_subscriber/filter/struct.EnvFilter.html
     #[builder(setter(into), default = "DEFAULT_RUST_LOG.to_string()")]
     rust_log: String,
+    /// Sets the log format of SC and SPU, `text` or `json`. Defaults to text.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use fluvio_cluster::{ClusterError, LocalConfigBuilder};
+    /// # fn example(builder: &mut LocalConfigBuilder) -> anyhow::Result<()> {
+    /// let config = builder
+    ///     .log_format("json")
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[builder(setter(into, strip_option), default)]
+    log_format: Option<String>,
     /// Sets the number of SPU replicas that should be provisioned. Defaults to 1.
     ///
     /// # Example
@@ -207,6 +222,7 @@ impl LocalConfig {
         LocalSpuProcessClusterManager {
             log_dir: self.log_dir.to_owned(),
             rust_log: self.rust_log.clone(),
+            log_format: self.log_format.clone(),
             launcher: self.launcher.clone(),
             tls_policy: self.server_tls_policy.clone(),
             data_dir: self.data_dir.clone(),
@@ -512,6 +528,7 @@ impl LocalInstaller {
             launcher: self.config.launcher.clone(),
             tls_policy: self.config.server_tls_policy.clone(),
             rust_log: self.config.rust_log.clone(),
+            log_format: self.config.log_format.clone(),
             mode,
         };
 
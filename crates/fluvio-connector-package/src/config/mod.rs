@@ -770,6 +770,14 @@ mod tests {
                             },
                         },
                     }),
+                    deduplication_window: None,
+                    encryption: None,
+                    durability: None,
+                    storage_backend: None,
+                    min_in_sync_replicas: None,
+                    index_interval: None,
+                    index_max_size: None,
+                    schema: None,
                 },
                 version: "0.1.0".to_string(),
                 producer: Some(ProducerParameters {
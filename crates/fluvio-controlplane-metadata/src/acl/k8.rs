@@ -0,0 +1,52 @@
+use fluvio_stream_model::k8_types::{Crd, GROUP, V1, CrdNames, Spec, Status, DefaultHeader};
+
+use super::AclSpec;
+use super::AclStatus;
+
+const ACL_API: Crd = Crd {
+    group: GROUP,
+    version: V1,
+    names: CrdNames {
+        kind: "Acl",
+        plural: "acls",
+        singular: "acl",
+    },
+};
+
+impl Spec for AclSpec {
+    type Header = DefaultHeader;
+    type Status = AclStatus;
+    fn metadata() -> &'static Crd {
+        &ACL_API
+    }
+}
+
+impl Status for AclStatus {}
+
+#[cfg(test)]
+mod test_v1_spec {
+    use std::{io::BufReader, fs::File};
+    use fluvio_stream_model::k8_types::K8Obj;
+
+    use super::AclSpec;
+    use super::super::{AclPermission, AclResourceType};
+
+    type K8AclSpec = K8Obj<AclSpec>;
+
+    #[test]
+    fn read_k8_acl_json() {
+        let reader: BufReader<File> =
+            BufReader::new(File::open("tests/k8_acl_v1.json").expect("spec"));
+        let acl: K8AclSpec = serde_json::from_reader(reader).expect("failed to parse acl");
+        assert_eq!(acl.metadata.name, "acl-orders-producer");
+        assert_eq!(
+            acl.spec,
+            AclSpec::new(
+                "orders-service",
+                AclResourceType::Topic,
+                "orders-*",
+                AclPermission::Produce
+            )
+        );
+    }
+}
@@ -0,0 +1,66 @@
+//!
+//! # Access Control Lists
+//!
+//! ACL entries grant principal a permission on topics, consumer groups or transactional ids.
+//! Principal and resource are patterns: `*` matches anything, trailing `*` matches prefix.
+//!
+mod spec;
+mod status;
+
+pub use self::spec::*;
+pub use self::status::*;
+
+#[cfg(feature = "k8")]
+mod k8;
+
+mod metadata {
+
+    use crate::core::{Spec, Status};
+
+    use super::*;
+
+    impl Spec for AclSpec {
+        const LABEL: &'static str = "Acl";
+        type IndexKey = String;
+        type Status = AclStatus;
+        type Owner = Self;
+    }
+
+    impl Status for AclStatus {}
+
+    #[cfg(feature = "k8")]
+    mod extended {
+
+        use fluvio_stream_model::{
+            store::{
+                k8::{K8ExtendedSpec, K8MetaItem, K8ConvertError, default_convert_from_k8},
+                MetadataStoreObject,
+            },
+            k8_types::K8Obj,
+        };
+
+        use super::AclSpec;
+
+        impl K8ExtendedSpec for AclSpec {
+            type K8Spec = Self;
+
+            fn convert_from_k8(
+                k8_obj: K8Obj<Self::K8Spec>,
+                multi_namespace_context: bool,
+            ) -> Result<MetadataStoreObject<Self, K8MetaItem>, K8ConvertError<Self::K8Spec>>
+            {
+                default_convert_from_k8(k8_obj, multi_namespace_context)
+            }
+
+            fn convert_status_from_k8(
+                status: Self::Status,
+            ) -> <Self::K8Spec as fluvio_stream_model::k8_types::Spec>::Status {
+                status
+            }
+
+            fn into_k8(self) -> Self::K8Spec {
+                self
+            }
+        }
+    }
+}
@@ -0,0 +1,275 @@
+use std::fmt;
+
+use fluvio_protocol::{Encoder, Decoder};
+
+/// principal of connections which are not authenticated
+pub const ANONYMOUS_PRINCIPAL: &str = "anonymous";
+
+#[derive(Decoder, Default, Encoder, Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
+pub enum AclResourceType {
+    #[default]
+    #[fluvio(tag = 0)]
+    Topic,
+    #[fluvio(tag = 1)]
+    ConsumerGroup,
+    #[fluvio(tag = 2)]
+    TransactionalId,
+}
+
+impl fmt::Display for AclResourceType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Topic => write!(f, "topic"),
+            Self::ConsumerGroup => write!(f, "consumer-group"),
+            Self::TransactionalId => write!(f, "transactional-id"),
+        }
+    }
+}
+
+impl std::str::FromStr for AclResourceType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "topic" => Ok(Self::Topic),
+            "consumer-group" | "group" => Ok(Self::ConsumerGroup),
+            "transactional-id" => Ok(Self::TransactionalId),
+            _ => Err(format!(
+                "invalid resource type {s}, expected topic, consumer-group or transactional-id"
+            )),
+        }
+    }
+}
+
+/// `Admin` implies `Produce` and `Consume`
+#[derive(Decoder, Default, Encoder, Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
+pub enum AclPermission {
+    #[fluvio(tag = 0)]
+    Produce,
+    #[default]
+    #[fluvio(tag = 1)]
+    Consume,
+    #[fluvio(tag = 2)]
+    Admin,
+}
+
+impl AclPermission {
+    /// check if this permission grants `requested`
+    pub fn grants(&self, requested: AclPermission) -> bool {
+        *self == AclPermission::Admin || *self == requested
+    }
+}
+
+impl fmt::Display for AclPermission {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Produce => write!(f, "produce"),
+            Self::Consume => write!(f, "consume"),
+            Self::Admin => write!(f, "admin"),
+        }
+    }
+}
+
+impl std::str::FromStr for AclPermission {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "produce" => Ok(Self::Produce),
+            "consume" => Ok(Self::Consume),
+            "admin" => Ok(Self::Admin),
+            _ => Err(format!(
+                "invalid permission {s}, expected produce, consume or admin"
+            )),
+        }
+    }
+}
+
+/// ACL entries are stored as metadata objects, each entry is one object
+pub type AclSpec = AclEntry;
+
+#[derive(Decoder, Default, Encoder, Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct AclEntry {
+    pub principal: String,
+    pub resource_type: AclResourceType,
+    pub resource: String,
+    pub permission: AclPermission,
+}
+
+impl AclEntry {
+    pub fn new(
+        principal: impl Into<String>,
+        resource_type: AclResourceType,
+        resource: impl Into<String>,
+        permission: AclPermission,
+    ) -> Self {
+        Self {
+            principal: principal.into(),
+            resource_type,
+            resource: resource.into(),
+            permission,
+        }
+    }
+
+    /// check if this entry grants `permission` on resource to principal
+    pub fn allows(
+        &self,
+        principal: &str,
+        resource_type: AclResourceType,
+        resource: &str,
+        permission: AclPermission,
+    ) -> bool {
+        self.resource_type == resource_type
+            && self.permission.grants(permission)
+            && pattern_matches(&self.principal, principal)
+            && pattern_matches(&self.resource, resource)
+    }
+}
+
+impl fmt::Display for AclEntry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {}:{}",
+            self.principal, self.permission, self.resource_type, self.resource
+        )
+    }
+}
+
+/// check if any of entries grants `permission` on resource to principal.
+/// Unauthenticated principal is checked as [`ANONYMOUS_PRINCIPAL`]
+pub fn is_allowed(
+    entries: &[AclEntry],
+    principal: Option<&str>,
+    resource_type: AclResourceType,
+    resource: &str,
+    permission: AclPermission,
+) -> bool {
+    let principal = principal.unwrap_or(ANONYMOUS_PRINCIPAL);
+    entries
+        .iter()
+        .any(|entry| entry.allows(principal, resource_type, resource, permission))
+}
+
+fn pattern_matches(pattern: &str, value: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => value.starts_with(prefix),
+        None => pattern == value,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_acl_patterns() {
+        let entries = vec![
+            AclEntry::new(
+                "alice",
+                AclResourceType::Topic,
+                "orders",
+                AclPermission::Produce,
+            ),
+            AclEntry::new(
+                "bob",
+                AclResourceType::Topic,
+                "logs-*",
+                AclPermission::Consume,
+            ),
+            AclEntry::new(
+                "*",
+                AclResourceType::ConsumerGroup,
+                "*",
+                AclPermission::Admin,
+            ),
+        ];
+
+        let topic = AclResourceType::Topic;
+        assert!(is_allowed(
+            &entries,
+            Some("alice"),
+            topic,
+            "orders",
+            AclPermission::Produce
+        ));
+        assert!(!is_allowed(
+            &entries,
+            Some("alice"),
+            topic,
+            "orders",
+            AclPermission::Consume
+        ));
+        assert!(!is_allowed(
+            &entries,
+            Some("alice"),
+            topic,
+            "orders-2",
+            AclPermission::Produce
+        ));
+
+        assert!(is_allowed(
+            &entries,
+            Some("bob"),
+            topic,
+            "logs-app",
+            AclPermission::Consume
+        ));
+        assert!(!is_allowed(
+            &entries,
+            Some("bob"),
+            topic,
+            "metrics",
+            AclPermission::Consume
+        ));
+        assert!(!is_allowed(
+            &entries,
+            None,
+            topic,
+            "logs-app",
+            AclPermission::Consume
+        ));
+
+        // admin implies consume, wildcard principal matches anonymous
+        let group = AclResourceType::ConsumerGroup;
+        assert!(is_allowed(
+            &entries,
+            None,
+            group,
+            "readers",
+            AclPermission::Consume
+        ));
+        assert!(!is_allowed(
+            &entries,
+            Some("bob"),
+            topic,
+            "logs-app",
+            AclPermission::Admin
+        ));
+    }
+
+    #[test]
+    fn test_acl_parse() {
+        assert_eq!(
+            "consumer-group".parse::<AclResourceType>(),
+            Ok(AclResourceType::ConsumerGroup)
+        );
+        assert_eq!("Admin".parse::<AclPermission>(), Ok(AclPermission::Admin));
+        assert!("write".parse::<AclPermission>().is_err());
+    }
+}
@@ -0,0 +1,12 @@
+use std::fmt;
+
+/// ACL entries have no state beyond their spec
+#[derive(Default, Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "use_serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AclStatus {}
+
+impl fmt::Display for AclStatus {
+    fn fmt(&self, _f: &mut fmt::Formatter) -> fmt::Result {
+        Ok(())
+    }
+}
@@ -7,9 +7,14 @@ pub mod tableformat;
 pub mod message;
 pub mod mirror;
 pub mod mirroring;
+pub mod acl;
 
 pub use fluvio_stream_model::core;
 
+/// version which encodes every field of metadata objects,
+/// raise it when field with higher `min_version` is added
+pub const COMMON_VERSION: i16 = 25;
+
 pub mod store {
     pub use fluvio_stream_model::store::*;
 }
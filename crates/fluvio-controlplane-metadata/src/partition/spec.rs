@@ -7,7 +7,10 @@
 use fluvio_types::SpuId;
 use fluvio_protocol::{Encoder, Decoder};
 
-use crate::topic::{CleanupPolicy, CompressionAlgorithm, Deduplication, TopicSpec, TopicStorageConfig};
+use crate::topic::{
+    CleanupPolicy, CompressionAlgorithm, Deduplication, DeduplicationWindow, TopicEncryption,
+    TopicSchema, TopicSpec, TopicStorageConfig, LeaderAffinity,
+};
 
 /// Spec for Partition
 /// Each partition has replicas spread among SPU
@@ -37,6 +40,29 @@ pub struct PartitionSpec {
     #[cfg_attr(feature = "use_serde", serde(default))]
     #[fluvio(min_version = 14)]
     pub mirror: Option<PartitionMirrorConfig>,
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    #[fluvio(min_version = 15)]
+    pub deduplication_window: Option<DeduplicationWindow>,
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    #[fluvio(min_version = 15)]
+    pub reassignment: Option<PartitionReassignment>,
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    #[fluvio(min_version = 16)]
+    pub encryption: Option<TopicEncryption>,
+    /// produces are rejected, records can still be consumed
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    #[fluvio(min_version = 18)]
+    pub read_only: bool,
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    #[fluvio(min_version = 21)]
+    pub schema: Option<TopicSchema>,
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    #[fluvio(min_version = 24)]
+    pub leader_affinity: Option<LeaderAffinity>,
+    /// incremented whenever leader changes, requests carrying older epoch are fenced
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    #[fluvio(min_version = 25)]
+    pub leader_epoch: i32,
 }
 
 impl PartitionSpec {
@@ -65,6 +91,21 @@ impl PartitionSpec {
             compression_type: topic.get_compression_type().clone(),
             deduplication: topic.get_deduplication().cloned(),
             system: topic.is_system(),
+            deduplication_window: topic.get_deduplication_window().cloned(),
+            reassignment: None,
+            encryption: topic.get_encryption().cloned(),
+            read_only: topic.is_read_only(),
+            schema: topic.get_schema().cloned(),
+            leader_affinity: topic.get_leader_affinity().cloned(),
+            leader_epoch: 0,
+        }
+    }
+
+    /// change leader, starting new leader epoch if leader is different
+    pub fn set_leader(&mut self, leader: SpuId) {
+        if self.leader != leader {
+            self.leader = leader;
+            self.leader_epoch += 1;
         }
     }
 
@@ -80,6 +121,10 @@ impl PartitionSpec {
             .collect()
     }
 
+    pub fn is_reassigning(&self) -> bool {
+        self.reassignment.is_some()
+    }
+
     pub fn mirror_string(&self) -> String {
         if let Some(mirror) = &self.mirror {
             mirror.external_cluster()
@@ -99,6 +144,30 @@ impl From<Vec<SpuId>> for PartitionSpec {
     }
 }
 
+/// Replica set which partition is moving to.
+/// While reassignment is in progress, `replicas` contains both current and target SPUs so new
+/// replicas can catch up with the leader. First SPU of the target becomes leader.
+#[derive(Decoder, Encoder, Default, Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct PartitionReassignment {
+    pub target: Vec<SpuId>,
+}
+
+impl PartitionReassignment {
+    pub fn new(target: Vec<SpuId>) -> Self {
+        Self { target }
+    }
+
+    /// leader after reassignment is completed
+    pub fn target_leader(&self) -> Option<SpuId> {
+        self.target.first().copied()
+    }
+}
+
 /// Setting applied to a replica
 #[derive(Decoder, Encoder, Debug, Eq, PartialEq, Clone, Default)]
 pub struct PartitionConfig {
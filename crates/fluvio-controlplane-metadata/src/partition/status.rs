@@ -38,6 +38,22 @@ pub struct PartitionStatus {
     #[fluvio(min_version = 5)]
     pub size: i64,
     pub is_being_deleted: bool,
+    /// ranges of leader storage which failed checksum validation
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    #[fluvio(min_version = 15)]
+    pub corrupt_ranges: Vec<CorruptRange>,
+    /// digests of closed segments reported by SPUs hosting replicas
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    #[fluvio(min_version = 22)]
+    pub digests: Vec<ReplicaDigests>,
+    /// followers whose segments differ from same segments of leader
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    #[fluvio(min_version = 22)]
+    pub divergent_replicas: Vec<SpuId>,
+    /// disk usage of leader replica
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    #[fluvio(min_version = 23)]
+    pub usage: StorageUsage,
 }
 
 impl Default for PartitionStatus {
@@ -49,6 +65,10 @@ impl Default for PartitionStatus {
             lsr: Default::default(),
             replicas: Default::default(),
             is_being_deleted: Default::default(),
+            corrupt_ranges: Default::default(),
+            digests: Default::default(),
+            divergent_replicas: Default::default(),
+            usage: Default::default(),
         }
     }
 }
@@ -137,6 +157,39 @@ impl PartitionStatus {
         !self.replicas.is_empty()
     }
 
+    pub fn is_corrupt(&self) -> bool {
+        !self.corrupt_ranges.is_empty()
+    }
+
+    pub fn is_divergent(&self) -> bool {
+        !self.divergent_replicas.is_empty()
+    }
+
+    /// replace digests reported by SPU and compare followers with leader again.
+    /// Digests of SPUs no longer in `replicas` are dropped
+    pub fn update_digests(&mut self, digests: ReplicaDigests, replicas: &[SpuId]) {
+        self.digests
+            .retain(|current| current.spu != digests.spu && replicas.contains(&current.spu));
+        if replicas.contains(&digests.spu) {
+            self.digests.push(digests);
+        }
+        self.digests.sort_by_key(|current| current.spu);
+
+        self.divergent_replicas = match self
+            .digests
+            .iter()
+            .find(|current| current.spu == self.leader.spu)
+        {
+            Some(leader) => self
+                .digests
+                .iter()
+                .filter(|follower| follower.spu != leader.spu && follower.diverges_from(leader))
+                .map(|follower| follower.spu)
+                .collect(),
+            None => vec![],
+        };
+    }
+
     /// set to being deleted
     pub fn set_to_delete(mut self) -> Self {
         self.is_being_deleted = true;
@@ -207,3 +260,101 @@ impl From<(SpuId, Offset, Offset)> for ReplicaStatus {
         Self::new(id, high_watermark, end_offset)
     }
 }
+
+/// Offsets `[start, end)` of the replica which failed checksum validation
+#[derive(Decoder, Encoder, Default, Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct CorruptRange {
+    pub start: Offset,
+    pub end: Offset,
+}
+
+/// Bytes used by replica storage
+#[derive(Decoder, Encoder, Default, Debug, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct StorageUsage {
+    /// bytes of segment logs
+    pub segment_bytes: u64,
+    /// bytes of segment indexes
+    pub index_bytes: u64,
+    /// bytes of segments moved to remote tier
+    pub tiered_bytes: u64,
+}
+
+impl StorageUsage {
+    /// bytes on local disk
+    pub fn local_bytes(&self) -> u64 {
+        self.segment_bytes + self.index_bytes
+    }
+
+    pub fn total(&self) -> u64 {
+        self.local_bytes() + self.tiered_bytes
+    }
+}
+
+impl std::ops::AddAssign for StorageUsage {
+    fn add_assign(&mut self, other: Self) {
+        self.segment_bytes += other.segment_bytes;
+        self.index_bytes += other.index_bytes;
+        self.tiered_bytes += other.tiered_bytes;
+    }
+}
+
+/// Checksum digest of closed segment `[base_offset, end_offset)` of a replica.
+/// Digest covers offsets and checksums of all batches, so it's same on replicas
+/// which stored same batches
+#[derive(Decoder, Encoder, Default, Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct SegmentDigest {
+    pub base_offset: Offset,
+    pub end_offset: Offset,
+    pub digest: u32,
+}
+
+/// Segment digests of replica hosted by SPU
+#[derive(Decoder, Encoder, Default, Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct ReplicaDigests {
+    pub spu: SpuId,
+    pub segments: Vec<SegmentDigest>,
+}
+
+impl ReplicaDigests {
+    pub fn new(spu: SpuId, segments: Vec<SegmentDigest>) -> Self {
+        Self { spu, segments }
+    }
+
+    /// true if segment covering same offsets as segment of `other` has different digest.
+    /// Segments which don't have a counterpart are not compared
+    pub fn diverges_from(&self, other: &Self) -> bool {
+        self.segments.iter().any(|segment| {
+            other.segments.iter().any(|other| {
+                other.base_offset == segment.base_offset
+                    && other.end_offset == segment.end_offset
+                    && other.digest != segment.digest
+            })
+        })
+    }
+}
+
+impl fmt::Display for CorruptRange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}..{}", self.start, self.end)
+    }
+}
@@ -38,6 +38,11 @@ pub struct SpuSpec {
     #[fluvio(min_version = 1)]
     #[cfg_attr(feature = "use_serde", serde(skip_serializing_if = "Option::is_none"))]
     pub public_endpoint_local: Option<Endpoint>,
+
+    /// leadership is moved off spu and no new replicas are assigned to it
+    #[fluvio(min_version = 16)]
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    pub draining: bool,
 }
 
 impl fmt::Display for SpuSpec {
@@ -152,6 +157,7 @@ impl From<CustomSpuSpec> for SpuSpec {
             rack: spec.rack,
             spu_type: SpuType::Custom,
             public_endpoint_local: Default::default(),
+            draining: false,
         }
     }
 }
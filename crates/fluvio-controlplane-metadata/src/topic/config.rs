@@ -5,9 +5,13 @@ use fluvio_types::{ReplicationFactor, TopicName, PartitionCount, IgnoreRackAssig
 
 use crate::topic::{
     ReplicaSpec, TopicReplicaParam, SegmentBasedPolicy, CleanupPolicy, TopicStorageConfig,
+    TopicEncryption, Durability, TopicSchema,
 };
 
-use super::{TopicSpec, PartitionMap, CompressionAlgorithm, deduplication::Deduplication};
+use super::{
+    TopicSpec, PartitionMap, CompressionAlgorithm,
+    deduplication::{Deduplication, DeduplicationWindow},
+};
 
 const DEFAULT_PARTITION_COUNT: PartitionCount = 1;
 const DEFAULT_REPLICATION_FACTOR: ReplicationFactor = 1;
@@ -45,6 +49,63 @@ pub struct TopicConfig {
         serde(default, skip_serializing_if = "Option::is_none")
     )]
     pub deduplication: Option<Deduplication>,
+
+    #[builder(default)]
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub deduplication_window: Option<DeduplicationWindow>,
+
+    #[builder(default)]
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub encryption: Option<TopicEncryption>,
+
+    #[builder(default)]
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub durability: Option<Durability>,
+
+    #[builder(default)]
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub storage_backend: Option<String>,
+
+    #[builder(default)]
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub min_in_sync_replicas: Option<u16>,
+
+    /// bytes of records between segment index entries
+    #[builder(default)]
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub index_interval: Option<bytesize::ByteSize>,
+
+    #[builder(default)]
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub index_max_size: Option<bytesize::ByteSize>,
+
+    #[builder(default)]
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub schema: Option<TopicSchema>,
 }
 
 #[derive(Debug, Default, Builder, Clone, PartialEq, Eq)]
@@ -140,6 +201,8 @@ impl From<TopicConfig> for TopicSpec {
     fn from(config: TopicConfig) -> Self {
         let segment_size = config.retention.segment_size.map(|s| s.as_u64() as u32);
         let max_partition_size = config.partition.max_size.map(|s| s.as_u64());
+        let index_interval_bytes = config.index_interval.map(|s| s.as_u64() as u32);
+        let index_max_bytes = config.index_max_size.map(|s| s.as_u64() as u32);
 
         let replica_spec = match config.partition.maps {
             Some(maps) => ReplicaSpec::Assigned(maps.into()),
@@ -164,11 +227,26 @@ impl From<TopicConfig> for TopicSpec {
 
         topic_spec.set_compression_type(config.compression.type_);
         topic_spec.set_deduplication(config.deduplication);
-
-        if segment_size.is_some() || max_partition_size.is_some() {
+        topic_spec.set_deduplication_window(config.deduplication_window);
+        topic_spec.set_encryption(config.encryption);
+        topic_spec.set_schema(config.schema);
+
+        if segment_size.is_some()
+            || max_partition_size.is_some()
+            || config.durability.is_some()
+            || config.storage_backend.is_some()
+            || config.min_in_sync_replicas.is_some()
+            || index_interval_bytes.is_some()
+            || index_max_bytes.is_some()
+        {
             topic_spec.set_storage(TopicStorageConfig {
                 segment_size,
                 max_partition_size,
+                durability: config.durability,
+                backend: config.storage_backend,
+                min_in_sync_replicas: config.min_in_sync_replicas,
+                index_interval_bytes,
+                index_max_bytes,
             });
         }
 
@@ -309,6 +387,11 @@ compression:
         test_spec.set_storage(TopicStorageConfig {
             segment_size: Some(2000),
             max_partition_size: Some(1000),
+            durability: None,
+            backend: None,
+            min_in_sync_replicas: None,
+            index_interval_bytes: None,
+            index_max_bytes: None,
         });
         test_spec.set_deduplication(Some(test_deduplication()));
 
@@ -340,6 +423,14 @@ compression:
                 type_: CompressionAlgorithm::Lz4,
             },
             deduplication: Some(test_deduplication()),
+            deduplication_window: None,
+            encryption: None,
+            durability: None,
+            storage_backend: None,
+            min_in_sync_replicas: None,
+            index_interval: None,
+            index_max_size: None,
+            schema: None,
         }
     }
 
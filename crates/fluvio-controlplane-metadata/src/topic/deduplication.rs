@@ -59,6 +59,38 @@ pub struct Transform {
     pub with: BTreeMap<String, String>,
 }
 
+/// Deduplication done natively by the leader without a SmartModule.
+/// Records are compared within the window defined by `bounds`.
+#[derive(Debug, Default, Builder, Clone, PartialEq, Eq, Encoder, Decoder)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
+pub struct DeduplicationWindow {
+    pub bounds: Bounds,
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    #[builder(default)]
+    pub key: DeduplicationKey,
+}
+
+/// Part of the record used to detect duplicates
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Encoder, Decoder)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
+pub enum DeduplicationKey {
+    /// records with same key are duplicates, records without key are never duplicates
+    #[default]
+    #[fluvio(tag = 0)]
+    RecordKey,
+    /// records with same value hash are duplicates
+    #[fluvio(tag = 1)]
+    ValueHash,
+}
+
 #[cfg(feature = "use_serde")]
 fn is_zero(val: &u64) -> bool {
     *val == 0
@@ -5,7 +5,7 @@ use anyhow::{anyhow, Result};
 use fluvio_protocol::record::ReplicaKey;
 use fluvio_types::defaults::{
     STORAGE_RETENTION_SECONDS, SPU_LOG_LOG_SEGMENT_MAX_BYTE_MIN, STORAGE_RETENTION_SECONDS_MIN,
-    SPU_PARTITION_MAX_BYTES_MIN, SPU_LOG_SEGMENT_MAX_BYTES,
+    SPU_PARTITION_MAX_BYTES_MIN, SPU_LOG_SEGMENT_MAX_BYTES, SPU_LOG_INDEX_MAX_BYTES_MIN,
 };
 use fluvio_types::SpuId;
 use fluvio_types::{PartitionId, PartitionCount, ReplicationFactor, IgnoreRackAssignment};
@@ -13,7 +13,7 @@ use fluvio_protocol::{Encoder, Decoder};
 
 use crate::partition::{PartitionMirrorConfig, RemotePartitionConfig, HomePartitionConfig};
 
-use super::deduplication::Deduplication;
+use super::deduplication::{Deduplication, DeduplicationWindow};
 
 #[derive(Debug, Clone, PartialEq, Default, Encoder, Decoder)]
 #[cfg_attr(
@@ -39,6 +39,26 @@ pub struct TopicSpec {
     #[cfg_attr(feature = "use_serde", serde(default))]
     #[fluvio(min_version = 13)]
     system: bool,
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    #[cfg_attr(feature = "use_serde", serde(skip_serializing_if = "Option::is_none"))]
+    #[fluvio(min_version = 15)]
+    deduplication_window: Option<DeduplicationWindow>,
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    #[cfg_attr(feature = "use_serde", serde(skip_serializing_if = "Option::is_none"))]
+    #[fluvio(min_version = 16)]
+    encryption: Option<TopicEncryption>,
+    /// produces are rejected, records can still be consumed
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    #[fluvio(min_version = 18)]
+    read_only: bool,
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    #[cfg_attr(feature = "use_serde", serde(skip_serializing_if = "Option::is_none"))]
+    #[fluvio(min_version = 21)]
+    schema: Option<TopicSchema>,
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    #[cfg_attr(feature = "use_serde", serde(skip_serializing_if = "Option::is_none"))]
+    #[fluvio(min_version = 24)]
+    leader_affinity: Option<LeaderAffinity>,
 }
 
 impl From<ReplicaSpec> for TopicSpec {
@@ -139,10 +159,50 @@ impl TopicSpec {
         self.deduplication = deduplication;
     }
 
+    pub fn get_deduplication_window(&self) -> Option<&DeduplicationWindow> {
+        self.deduplication_window.as_ref()
+    }
+
+    pub fn set_deduplication_window(&mut self, window: Option<DeduplicationWindow>) {
+        self.deduplication_window = window;
+    }
+
+    pub fn get_encryption(&self) -> Option<&TopicEncryption> {
+        self.encryption.as_ref()
+    }
+
+    pub fn set_encryption(&mut self, encryption: Option<TopicEncryption>) {
+        self.encryption = encryption;
+    }
+
+    pub fn get_schema(&self) -> Option<&TopicSchema> {
+        self.schema.as_ref()
+    }
+
+    pub fn set_schema(&mut self, schema: Option<TopicSchema>) {
+        self.schema = schema;
+    }
+
+    pub fn get_leader_affinity(&self) -> Option<&LeaderAffinity> {
+        self.leader_affinity.as_ref()
+    }
+
+    pub fn set_leader_affinity(&mut self, leader_affinity: Option<LeaderAffinity>) {
+        self.leader_affinity = leader_affinity;
+    }
+
     pub fn is_system(&self) -> bool {
         self.system
     }
 
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    pub fn set_read_only(&mut self, read_only: bool) {
+        self.read_only = read_only;
+    }
+
     pub fn set_system(&mut self, system: bool) {
         self.system = system;
     }
@@ -166,7 +226,63 @@ impl TopicSpec {
             }
         }
 
+        if let Some(window) = self.get_deduplication_window() {
+            if window.bounds.count == 0 {
+                return Some("deduplication window requires count bound".to_string());
+            }
+        }
+
+        if let Some(encryption) = self.get_encryption() {
+            if encryption.key_id.is_empty() {
+                return Some("encryption requires key id".to_string());
+            }
+        }
+
+        if let Some(schema) = self.get_schema() {
+            if schema.reference.is_empty() {
+                return Some("schema requires reference".to_string());
+            }
+        }
+
+        if let Some(affinity) = self.get_leader_affinity() {
+            if affinity.is_empty() {
+                return Some(
+                    "leader affinity requires preferred leaders or anti-affinity topics"
+                        .to_string(),
+                );
+            }
+        }
+
         if let Some(storage) = self.get_storage() {
+            if storage.durability == Some(Durability::IntervalMs(0)) {
+                return Some("durability interval must be greater than 0".to_string());
+            }
+            if storage
+                .backend
+                .as_ref()
+                .is_some_and(|backend| backend.is_empty())
+            {
+                return Some("storage backend name must not be empty".to_string());
+            }
+            if let Some(min_in_sync_replicas) = storage.min_in_sync_replicas {
+                if min_in_sync_replicas == 0 {
+                    return Some("min_in_sync_replicas must be at least 1".to_string());
+                }
+                if let Some(replication) = self.replication_factor() {
+                    if min_in_sync_replicas as ReplicationFactor > replication {
+                        return Some(format!(
+                            "min_in_sync_replicas {min_in_sync_replicas} is greater than replication factor {replication}"
+                        ));
+                    }
+                }
+            }
+            if let Some(index_max_bytes) = storage.index_max_bytes {
+                if index_max_bytes < SPU_LOG_INDEX_MAX_BYTES_MIN {
+                    return Some(format!(
+                        "index_max_bytes {index_max_bytes} is less than minimum {SPU_LOG_INDEX_MAX_BYTES_MIN}"
+                    ));
+                }
+            }
             if let Some(segment_size) = storage.segment_size {
                 if segment_size < SPU_LOG_LOG_SEGMENT_MAX_BYTE_MIN {
                     return Some(format!(
@@ -827,6 +943,228 @@ impl SegmentBasedPolicy {
 pub struct TopicStorageConfig {
     pub segment_size: Option<u32>,       // segment size
     pub max_partition_size: Option<u64>, // max partition size
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    #[fluvio(min_version = 16)]
+    pub durability: Option<Durability>,
+    /// storage backend registered on SPUs, file backend if not set
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    #[fluvio(min_version = 17)]
+    pub backend: Option<String>,
+    /// replicas which must be in sync, including leader, to accept produce waiting for all replicas
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    #[fluvio(min_version = 19)]
+    pub min_in_sync_replicas: Option<u16>,
+    /// bytes of records between index entries of segment
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    #[fluvio(min_version = 20)]
+    pub index_interval_bytes: Option<u32>,
+    /// max size of segment index
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    #[fluvio(min_version = 20)]
+    pub index_max_bytes: Option<u32>,
+}
+
+/// When records written to partition are synced to disk.
+///
+/// High watermark is advanced after records are written, so with `Batch` it never covers
+/// records which are not on disk. With `Interval` and `Os` records under high watermark
+/// may be lost if SPU host crashes before they are synced, unless they were replicated.
+#[derive(Decoder, Encoder, Default, Debug, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
+pub enum Durability {
+    /// syncing is left to OS
+    #[default]
+    #[fluvio(tag = 0)]
+    Os,
+    /// sync after every batch is written, before it's acknowledged
+    #[fluvio(tag = 1)]
+    Batch,
+    /// sync at most this many milliseconds after batch is written
+    #[fluvio(tag = 2)]
+    IntervalMs(u32),
+}
+
+/// Encryption at rest of partition records with AES-256-GCM
+#[derive(Decoder, Encoder, Default, Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct TopicEncryption {
+    /// id of the key, resolved to key by key provider of SPU
+    pub key_id: String,
+}
+
+impl TopicEncryption {
+    pub fn new(key_id: impl Into<String>) -> Self {
+        Self {
+            key_id: key_id.into(),
+        }
+    }
+}
+
+/// Schema which records of topic are expected to conform to.
+///
+/// Schema is referenced by a registry subject or an inline definition. Leader SPU validates
+/// values of produced records with its schema validator before they are written.
+#[derive(Decoder, Encoder, Default, Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct TopicSchema {
+    pub format: SchemaFormat,
+    /// registry subject or inline schema definition
+    pub reference: String,
+    /// version of schema in registry, latest if not set
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub version: Option<u32>,
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    pub on_failure: SchemaValidationAction,
+}
+
+impl TopicSchema {
+    pub fn new(format: SchemaFormat, reference: impl Into<String>) -> Self {
+        Self {
+            format,
+            reference: reference.into(),
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Decoder, Encoder, Default, Debug, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
+pub enum SchemaFormat {
+    #[default]
+    #[fluvio(tag = 0)]
+    Json,
+    #[fluvio(tag = 1)]
+    Avro,
+    #[fluvio(tag = 2)]
+    Protobuf,
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("Invalid schema format, expected one of: json, avro, protobuf")]
+pub struct InvalidSchemaFormat;
+
+impl std::str::FromStr for SchemaFormat {
+    type Err = InvalidSchemaFormat;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "json" => Ok(SchemaFormat::Json),
+            "avro" => Ok(SchemaFormat::Avro),
+            "protobuf" => Ok(SchemaFormat::Protobuf),
+            _ => Err(InvalidSchemaFormat),
+        }
+    }
+}
+
+impl std::fmt::Display for SchemaFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match self {
+            Self::Json => write!(f, "json"),
+            Self::Avro => write!(f, "avro"),
+            Self::Protobuf => write!(f, "protobuf"),
+        }
+    }
+}
+
+/// What leader does with records failing schema validation
+#[derive(Decoder, Encoder, Default, Debug, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
+pub enum SchemaValidationAction {
+    /// whole produce to partition is rejected
+    #[default]
+    #[fluvio(tag = 0)]
+    Reject,
+    /// records are written and counted as invalid in SPU metrics
+    #[fluvio(tag = 1)]
+    Tag,
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("Invalid schema validation action, expected one of: reject, tag")]
+pub struct InvalidSchemaValidationAction;
+
+impl std::str::FromStr for SchemaValidationAction {
+    type Err = InvalidSchemaValidationAction;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "reject" => Ok(SchemaValidationAction::Reject),
+            "tag" => Ok(SchemaValidationAction::Tag),
+            _ => Err(InvalidSchemaValidationAction),
+        }
+    }
+}
+
+/// Hints where leaders of topic partitions are placed.
+///
+/// SC places leaders on preferred SPUs and away from SPUs leading partitions of anti-affinity
+/// topics, both when partitions are assigned and when leader is re-elected. Hints are honored
+/// only when possible, partition gets a leader even if no SPU satisfies them.
+#[derive(Decoder, Encoder, Default, Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct LeaderAffinity {
+    /// SPUs which should lead partitions
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    pub preferred_leaders: Vec<SpuId>,
+    /// topics whose partition leaders should not lead partitions of this topic
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    pub anti_affinity_topics: Vec<String>,
+}
+
+impl LeaderAffinity {
+    pub fn is_empty(&self) -> bool {
+        self.preferred_leaders.is_empty() && self.anti_affinity_topics.is_empty()
+    }
+
+    /// rank of spu as leader, lower is better. `avoided` are spus leading anti-affinity topics
+    pub fn rank(&self, spu: SpuId, avoided: &[SpuId]) -> u16 {
+        let avoid = avoided.contains(&spu) as u16;
+        let not_preferred =
+            (!self.preferred_leaders.is_empty() && !self.preferred_leaders.contains(&spu)) as u16;
+        avoid * 2 + not_preferred
+    }
 }
 
 #[derive(Decoder, Default, Encoder, Debug, Clone, Eq, PartialEq)]
@@ -884,7 +1222,7 @@ mod test {
 
     use std::io::Cursor;
 
-    use crate::topic::{Bounds, Filter, Transform};
+    use crate::topic::{Bounds, Filter, Transform, DeduplicationKey};
 
     use super::*;
 
@@ -1099,6 +1437,384 @@ mod test {
         assert!(topic_spec_decoded.deduplication.is_none());
     }
 
+    #[test]
+    fn test_topic_with_dedup_window_prev_version_compatibility() {
+        //given
+        let prev_version = 14;
+        let mut topic_spec: TopicSpec = ReplicaSpec::Computed((2, 3, true).into()).into();
+        topic_spec.set_deduplication_window(Some(DeduplicationWindow {
+            bounds: Bounds {
+                count: 100,
+                age: None,
+            },
+            key: DeduplicationKey::ValueHash,
+        }));
+
+        //when
+        let mut dest = vec![];
+        topic_spec.encode(&mut dest, prev_version).expect("encoded");
+        let mut topic_spec_decoded = TopicSpec::default();
+        topic_spec_decoded
+            .decode(&mut Cursor::new(&dest), prev_version)
+            .expect("decoded");
+
+        //then
+        assert!(topic_spec_decoded.deduplication_window.is_none());
+        assert!(topic_spec.validate_config().is_none());
+    }
+
+    #[test]
+    fn test_topic_with_encryption_prev_version_compatibility() {
+        //given
+        let prev_version = 15;
+        let mut topic_spec: TopicSpec = ReplicaSpec::Computed((2, 3, true).into()).into();
+        topic_spec.set_encryption(Some(TopicEncryption::new("orders-key")));
+
+        //when
+        let mut dest = vec![];
+        topic_spec.encode(&mut dest, prev_version).expect("encoded");
+        let mut topic_spec_decoded = TopicSpec::default();
+        topic_spec_decoded
+            .decode(&mut Cursor::new(&dest), prev_version)
+            .expect("decoded");
+
+        //then
+        assert!(topic_spec_decoded.encryption.is_none());
+
+        let mut dest = vec![];
+        topic_spec.encode(&mut dest, 16).expect("encoded");
+        let mut topic_spec_decoded = TopicSpec::default();
+        topic_spec_decoded
+            .decode(&mut Cursor::new(&dest), 16)
+            .expect("decoded");
+        assert_eq!(
+            topic_spec_decoded.get_encryption(),
+            Some(&TopicEncryption::new("orders-key"))
+        );
+    }
+
+    #[test]
+    fn test_topic_with_durability_prev_version_compatibility() {
+        //given
+        let prev_version = 15;
+        let mut topic_spec: TopicSpec = ReplicaSpec::Computed((2, 3, true).into()).into();
+        topic_spec.set_storage(TopicStorageConfig {
+            durability: Some(Durability::IntervalMs(100)),
+            ..Default::default()
+        });
+
+        //when
+        let mut dest = vec![];
+        topic_spec.encode(&mut dest, prev_version).expect("encoded");
+        let mut topic_spec_decoded = TopicSpec::default();
+        topic_spec_decoded
+            .decode(&mut Cursor::new(&dest), prev_version)
+            .expect("decoded");
+
+        //then
+        assert_eq!(
+            topic_spec_decoded.get_storage().and_then(|s| s.durability),
+            None
+        );
+
+        let mut dest = vec![];
+        topic_spec.encode(&mut dest, 16).expect("encoded");
+        let mut topic_spec_decoded = TopicSpec::default();
+        topic_spec_decoded
+            .decode(&mut Cursor::new(&dest), 16)
+            .expect("decoded");
+        assert_eq!(
+            topic_spec_decoded.get_storage().and_then(|s| s.durability),
+            Some(Durability::IntervalMs(100))
+        );
+    }
+
+    #[test]
+    fn test_topic_with_storage_backend_prev_version_compatibility() {
+        //given
+        let prev_version = 16;
+        let mut topic_spec: TopicSpec = ReplicaSpec::Computed((2, 3, true).into()).into();
+        topic_spec.set_storage(TopicStorageConfig {
+            backend: Some("memory".to_string()),
+            ..Default::default()
+        });
+
+        //when
+        let mut dest = vec![];
+        topic_spec.encode(&mut dest, prev_version).expect("encoded");
+        let mut topic_spec_decoded = TopicSpec::default();
+        topic_spec_decoded
+            .decode(&mut Cursor::new(&dest), prev_version)
+            .expect("decoded");
+
+        //then
+        assert_eq!(
+            topic_spec_decoded
+                .get_storage()
+                .and_then(|s| s.backend.clone()),
+            None
+        );
+
+        let mut dest = vec![];
+        topic_spec.encode(&mut dest, 17).expect("encoded");
+        let mut topic_spec_decoded = TopicSpec::default();
+        topic_spec_decoded
+            .decode(&mut Cursor::new(&dest), 17)
+            .expect("decoded");
+        assert_eq!(
+            topic_spec_decoded
+                .get_storage()
+                .and_then(|s| s.backend.clone()),
+            Some("memory".to_string())
+        );
+    }
+
+    #[test]
+    fn test_topic_read_only_prev_version_compatibility() {
+        //given
+        let prev_version = 17;
+        let mut topic_spec: TopicSpec = ReplicaSpec::Computed((2, 3, true).into()).into();
+        topic_spec.set_read_only(true);
+
+        //when
+        let mut dest = vec![];
+        topic_spec.encode(&mut dest, prev_version).expect("encoded");
+        let mut topic_spec_decoded = TopicSpec::default();
+        topic_spec_decoded
+            .decode(&mut Cursor::new(&dest), prev_version)
+            .expect("decoded");
+
+        //then
+        assert!(!topic_spec_decoded.is_read_only());
+
+        let mut dest = vec![];
+        topic_spec.encode(&mut dest, 18).expect("encoded");
+        let mut topic_spec_decoded = TopicSpec::default();
+        topic_spec_decoded
+            .decode(&mut Cursor::new(&dest), 18)
+            .expect("decoded");
+        assert!(topic_spec_decoded.is_read_only());
+    }
+
+    #[test]
+    fn test_durability_interval_must_be_positive() {
+        let mut topic_spec: TopicSpec = ReplicaSpec::Computed((1, 1, false).into()).into();
+        topic_spec.set_storage(TopicStorageConfig {
+            durability: Some(Durability::IntervalMs(0)),
+            ..Default::default()
+        });
+        assert_eq!(
+            topic_spec.validate_config(),
+            Some("durability interval must be greater than 0".to_string())
+        );
+    }
+
+    #[test]
+    fn test_min_in_sync_replicas_within_replication() {
+        let mut topic_spec: TopicSpec = ReplicaSpec::Computed((1, 2, false).into()).into();
+        topic_spec.set_storage(TopicStorageConfig {
+            min_in_sync_replicas: Some(3),
+            ..Default::default()
+        });
+        assert_eq!(
+            topic_spec.validate_config(),
+            Some("min_in_sync_replicas 3 is greater than replication factor 2".to_string())
+        );
+
+        topic_spec.get_storage_mut().unwrap().min_in_sync_replicas = Some(0);
+        assert_eq!(
+            topic_spec.validate_config(),
+            Some("min_in_sync_replicas must be at least 1".to_string())
+        );
+
+        topic_spec.get_storage_mut().unwrap().min_in_sync_replicas = Some(2);
+        assert!(topic_spec.validate_config().is_none());
+    }
+
+    #[test]
+    fn test_topic_with_min_in_sync_replicas_prev_version_compatibility() {
+        //given
+        let prev_version = 18;
+        let mut topic_spec: TopicSpec = ReplicaSpec::Computed((2, 3, true).into()).into();
+        topic_spec.set_storage(TopicStorageConfig {
+            min_in_sync_replicas: Some(2),
+            ..Default::default()
+        });
+
+        //when
+        let mut dest = vec![];
+        topic_spec.encode(&mut dest, prev_version).expect("encoded");
+        let mut topic_spec_decoded = TopicSpec::default();
+        topic_spec_decoded
+            .decode(&mut Cursor::new(&dest), prev_version)
+            .expect("decoded");
+
+        //then
+        assert_eq!(
+            topic_spec_decoded
+                .get_storage()
+                .and_then(|s| s.min_in_sync_replicas),
+            None
+        );
+
+        let mut dest = vec![];
+        topic_spec.encode(&mut dest, 19).expect("encoded");
+        let mut topic_spec_decoded = TopicSpec::default();
+        topic_spec_decoded
+            .decode(&mut Cursor::new(&dest), 19)
+            .expect("decoded");
+        assert_eq!(
+            topic_spec_decoded
+                .get_storage()
+                .and_then(|s| s.min_in_sync_replicas),
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn test_topic_with_index_settings_prev_version_compatibility() {
+        //given
+        let prev_version = 19;
+        let mut topic_spec: TopicSpec = ReplicaSpec::Computed((2, 3, true).into()).into();
+        topic_spec.set_storage(TopicStorageConfig {
+            index_interval_bytes: Some(1024),
+            index_max_bytes: Some(4096),
+            ..Default::default()
+        });
+
+        //when
+        let mut dest = vec![];
+        topic_spec.encode(&mut dest, prev_version).expect("encoded");
+        let mut topic_spec_decoded = TopicSpec::default();
+        topic_spec_decoded
+            .decode(&mut Cursor::new(&dest), prev_version)
+            .expect("decoded");
+
+        //then
+        let storage = topic_spec_decoded.get_storage().expect("storage");
+        assert_eq!(storage.index_interval_bytes, None);
+        assert_eq!(storage.index_max_bytes, None);
+
+        let mut dest = vec![];
+        topic_spec.encode(&mut dest, 20).expect("encoded");
+        let mut topic_spec_decoded = TopicSpec::default();
+        topic_spec_decoded
+            .decode(&mut Cursor::new(&dest), 20)
+            .expect("decoded");
+        let storage = topic_spec_decoded.get_storage().expect("storage");
+        assert_eq!(storage.index_interval_bytes, Some(1024));
+        assert_eq!(storage.index_max_bytes, Some(4096));
+        assert!(topic_spec_decoded.validate_config().is_none());
+
+        topic_spec.get_storage_mut().unwrap().index_max_bytes = Some(8);
+        assert_eq!(
+            topic_spec.validate_config(),
+            Some("index_max_bytes 8 is less than minimum 1024".to_string())
+        );
+    }
+
+    #[test]
+    fn test_topic_with_schema_prev_version_compatibility() {
+        //given
+        let prev_version = 20;
+        let mut topic_spec: TopicSpec = ReplicaSpec::Computed((2, 3, true).into()).into();
+        let schema = TopicSchema {
+            version: Some(3),
+            on_failure: SchemaValidationAction::Tag,
+            ..TopicSchema::new(SchemaFormat::Avro, "orders-value")
+        };
+        topic_spec.set_schema(Some(schema.clone()));
+
+        //when
+        let mut dest = vec![];
+        topic_spec.encode(&mut dest, prev_version).expect("encoded");
+        let mut topic_spec_decoded = TopicSpec::default();
+        topic_spec_decoded
+            .decode(&mut Cursor::new(&dest), prev_version)
+            .expect("decoded");
+
+        //then
+        assert!(topic_spec_decoded.get_schema().is_none());
+
+        let mut dest = vec![];
+        topic_spec.encode(&mut dest, 21).expect("encoded");
+        let mut topic_spec_decoded = TopicSpec::default();
+        topic_spec_decoded
+            .decode(&mut Cursor::new(&dest), 21)
+            .expect("decoded");
+        assert_eq!(topic_spec_decoded.get_schema(), Some(&schema));
+    }
+
+    #[test]
+    fn test_topic_with_leader_affinity_prev_version_compatibility() {
+        let mut topic_spec: TopicSpec = ReplicaSpec::Computed((2, 3, true).into()).into();
+        let affinity = LeaderAffinity {
+            preferred_leaders: vec![5001, 5002],
+            anti_affinity_topics: vec!["noisy".to_string()],
+        };
+        topic_spec.set_leader_affinity(Some(affinity.clone()));
+
+        let mut dest = vec![];
+        topic_spec.encode(&mut dest, 23).expect("encoded");
+        let mut topic_spec_decoded = TopicSpec::default();
+        topic_spec_decoded
+            .decode(&mut Cursor::new(&dest), 23)
+            .expect("decoded");
+        assert!(topic_spec_decoded.get_leader_affinity().is_none());
+
+        let mut dest = vec![];
+        topic_spec.encode(&mut dest, 24).expect("encoded");
+        let mut topic_spec_decoded = TopicSpec::default();
+        topic_spec_decoded
+            .decode(&mut Cursor::new(&dest), 24)
+            .expect("decoded");
+        assert_eq!(topic_spec_decoded.get_leader_affinity(), Some(&affinity));
+    }
+
+    #[test]
+    fn test_leader_affinity_rank() {
+        let affinity = LeaderAffinity {
+            preferred_leaders: vec![1, 2],
+            anti_affinity_topics: vec!["noisy".to_string()],
+        };
+        let avoided = [2, 3];
+        assert_eq!(affinity.rank(1, &avoided), 0);
+        assert_eq!(affinity.rank(2, &avoided), 2);
+        assert_eq!(affinity.rank(4, &avoided), 1);
+        assert_eq!(affinity.rank(3, &avoided), 3);
+        assert_eq!(LeaderAffinity::default().rank(3, &[]), 0);
+    }
+
+    #[test]
+    fn test_schema_requires_reference() {
+        let mut topic_spec: TopicSpec = ReplicaSpec::Computed((1, 1, false).into()).into();
+        topic_spec.set_schema(Some(TopicSchema::default()));
+        assert_eq!(
+            topic_spec.validate_config(),
+            Some("schema requires reference".to_string())
+        );
+    }
+
+    #[test]
+    fn test_encryption_requires_key_id() {
+        let mut topic_spec: TopicSpec = ReplicaSpec::Computed((1, 1, false).into()).into();
+        topic_spec.set_encryption(Some(TopicEncryption::default()));
+        assert_eq!(
+            topic_spec.validate_config(),
+            Some("encryption requires key id".to_string())
+        );
+    }
+
+    #[test]
+    fn test_dedup_window_requires_count() {
+        let mut topic_spec: TopicSpec = ReplicaSpec::Computed((1, 1, false).into()).into();
+        topic_spec.set_deduplication_window(Some(DeduplicationWindow::default()));
+        assert_eq!(
+            topic_spec.validate_config(),
+            Some("deduplication window requires count bound".to_string())
+        );
+    }
+
     #[test]
     fn test_partition_map_str() {
         // Test multiple
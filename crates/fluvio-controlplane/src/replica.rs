@@ -3,7 +3,10 @@
 use std::fmt;
 
 use fluvio_controlplane_metadata::{
-    topic::{CleanupPolicy, TopicStorageConfig, CompressionAlgorithm, Deduplication},
+    topic::{
+        CleanupPolicy, TopicStorageConfig, CompressionAlgorithm, Deduplication,
+        DeduplicationWindow, TopicEncryption, TopicSchema,
+    },
     core::MetadataItem,
     store::MetadataStoreObject,
     partition::{PartitionSpec, PartitionMirrorConfig},
@@ -25,6 +28,11 @@ pub struct Replica {
     pub storage: Option<TopicStorageConfig>,
     pub compression_type: CompressionAlgorithm,
     pub deduplication: Option<Deduplication>,
+    pub deduplication_window: Option<DeduplicationWindow>,
+    pub encryption: Option<TopicEncryption>,
+    pub read_only: bool,
+    pub schema: Option<TopicSchema>,
+    pub leader_epoch: i32,
 }
 
 impl Replica {
@@ -69,6 +77,11 @@ where
             storage: spec.storage,
             compression_type: spec.compression_type,
             deduplication: spec.deduplication,
+            deduplication_window: spec.deduplication_window,
+            encryption: spec.encryption,
+            read_only: spec.read_only,
+            schema: spec.schema,
+            leader_epoch: spec.leader_epoch,
         }
     }
 }
@@ -9,6 +9,8 @@ pub struct ControlPlaneRequest<S> {
     pub epoch: i64,
     pub changes: Vec<Message<S>>,
     pub all: Vec<S>,
+    /// W3C trace context of the sender, to continue its trace
+    pub traceparent: Option<String>,
 }
 
 impl<S> ControlPlaneRequest<S>
@@ -20,6 +22,7 @@ where
             epoch,
             changes,
             all: vec![],
+            traceparent: None,
         }
     }
 
@@ -28,6 +31,16 @@ where
             epoch,
             changes: vec![],
             all,
+            traceparent: None,
         }
     }
+
+    pub fn with_traceparent(mut self, traceparent: Option<String>) -> Self {
+        self.traceparent = traceparent;
+        self
+    }
+
+    pub fn traceparent(&self) -> Option<&str> {
+        self.traceparent.as_deref()
+    }
 }
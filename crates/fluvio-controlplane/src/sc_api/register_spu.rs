@@ -8,6 +8,8 @@
 //!     * FlvErrorCode::None - for success
 //!     * FLVErrorCode::SpuNotAuthorized - for error
 //!
+//! Since version 1, SPU reports its build version so SC can detect version skew.
+//!
 //! In subsequent releases, Register SPU will carry additional credentials for mTLS
 //!
 use fluvio_protocol::api::Request;
@@ -25,10 +27,13 @@ use super::api::InternalScKey;
 #[derive(Decoder, Encoder, Debug, Default)]
 pub struct RegisterSpuRequest {
     spu: SpuId,
+    #[fluvio(min_version = 1)]
+    version: String,
 }
 
 impl Request for RegisterSpuRequest {
     const API_KEY: u16 = InternalScKey::RegisterSpu as u16;
+    const DEFAULT_API_VERSION: i16 = 1;
     type Response = RegisterSpuResponse;
 }
 
@@ -44,12 +49,29 @@ pub struct RegisterSpuResponse {
 
 impl RegisterSpuRequest {
     pub fn new(spu: SpuId) -> Self {
-        Self { spu }
+        Self {
+            spu,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_version(mut self, version: impl Into<String>) -> Self {
+        self.version = version.into();
+        self
     }
 
     pub fn spu(&self) -> SpuId {
         self.spu
     }
+
+    /// version of SPU, none if SPU is older than version reporting
+    pub fn version(&self) -> Option<&str> {
+        if self.version.is_empty() {
+            None
+        } else {
+            Some(&self.version)
+        }
+    }
 }
 
 // -----------------------------------
@@ -76,6 +98,13 @@ impl RegisterSpuResponse {
         }
     }
 
+    pub fn rejected(message: impl Into<String>) -> Self {
+        RegisterSpuResponse {
+            error_code: ErrorCode::SpuRegisterationFailed,
+            error_message: Some(message.into()),
+        }
+    }
+
     pub fn is_error(&self) -> bool {
         self.error_code.is_error()
     }
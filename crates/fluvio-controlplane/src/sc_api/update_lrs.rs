@@ -6,6 +6,9 @@ use fluvio_protocol::Decoder;
 use fluvio_protocol::Encoder;
 use fluvio_controlplane_metadata::partition::ReplicaKey;
 use fluvio_controlplane_metadata::partition::ReplicaStatus;
+use fluvio_controlplane_metadata::partition::CorruptRange;
+use fluvio_controlplane_metadata::partition::ReplicaDigests;
+use fluvio_controlplane_metadata::partition::StorageUsage;
 
 use super::api::InternalScKey;
 
@@ -15,6 +18,10 @@ use super::api::InternalScKey;
 pub struct UpdateLrsRequest {
     replicas: Vec<LrsRequest>,
     stat: SpuStat,
+    /// segment digests of leader and follower replicas hosted by SPU
+    digests: Vec<ReplicaDigestsRequest>,
+    /// W3C trace context of the SPU, to continue its trace
+    traceparent: Option<String>,
 }
 
 impl UpdateLrsRequest {
@@ -22,17 +29,47 @@ impl UpdateLrsRequest {
         Self {
             replicas,
             stat: SpuStat::default(),
+            digests: vec![],
+            traceparent: None,
         }
     }
 
+    pub fn with_digests(mut self, digests: Vec<ReplicaDigestsRequest>) -> Self {
+        self.digests = digests;
+        self
+    }
+
+    pub fn with_stat(mut self, stat: SpuStat) -> Self {
+        self.stat = stat;
+        self
+    }
+
+    pub fn with_traceparent(mut self, traceparent: Option<String>) -> Self {
+        self.traceparent = traceparent;
+        self
+    }
+
+    pub fn traceparent(&self) -> Option<&str> {
+        self.traceparent.as_deref()
+    }
+
     /// make into vec of requests
     pub fn into_requests(self) -> Vec<LrsRequest> {
         self.replicas
     }
 
+    /// make into lrs requests and digests requests
+    pub fn into_parts(self) -> (Vec<LrsRequest>, Vec<ReplicaDigestsRequest>) {
+        (self.replicas, self.digests)
+    }
+
     pub fn stat(self) -> SpuStat {
         self.stat
     }
+
+    pub fn stat_ref(&self) -> &SpuStat {
+        &self.stat
+    }
 }
 
 impl fmt::Display for UpdateLrsRequest {
@@ -46,10 +83,35 @@ impl Request for UpdateLrsRequest {
     type Response = UpdateLrsResponse;
 }
 
-#[derive(Decoder, Encoder, Debug, Default, Clone)]
+#[derive(Decoder, Encoder, Debug, Default, Clone, PartialEq, Eq)]
 pub struct SpuStat {
     smartmodules_count: u32,
     smart_streams_count: u32,
+    /// version of SPU, empty if stat is not reported
+    pub version: String,
+    /// size of volume hosting replicas
+    pub disk_total_bytes: u64,
+    pub disk_available_bytes: u64,
+}
+
+impl SpuStat {
+    pub fn new(
+        version: impl Into<String>,
+        disk_total_bytes: u64,
+        disk_available_bytes: u64,
+    ) -> Self {
+        Self {
+            version: version.into(),
+            disk_total_bytes,
+            disk_available_bytes,
+            ..Default::default()
+        }
+    }
+
+    /// SPU only reports stat periodically, other updates carry empty stat
+    pub fn is_reported(&self) -> bool {
+        !self.version.is_empty()
+    }
 }
 
 #[derive(Decoder, Encoder, Default, Debug)]
@@ -62,6 +124,21 @@ pub struct LrsRequest {
     pub leader: ReplicaStatus,
     pub replicas: Vec<ReplicaStatus>,
     pub size: i64,
+    pub corrupt_ranges: Vec<CorruptRange>,
+    pub usage: StorageUsage,
+}
+
+/// Segment digests of replica hosted by SPU, used by SC to detect divergent followers
+#[derive(Decoder, Encoder, Debug, Default, Clone, PartialEq, Eq)]
+pub struct ReplicaDigestsRequest {
+    pub id: ReplicaKey,
+    pub digests: ReplicaDigests,
+}
+
+impl ReplicaDigestsRequest {
+    pub fn new(id: ReplicaKey, digests: ReplicaDigests) -> Self {
+        Self { id, digests }
+    }
 }
 
 impl PartialEq for LrsRequest {
@@ -97,6 +174,8 @@ impl LrsRequest {
             leader,
             replicas,
             size,
+            corrupt_ranges: vec![],
+            usage: StorageUsage::default(),
         }
     }
 }
@@ -0,0 +1,26 @@
+use fluvio_controlplane_metadata::acl::AclEntry;
+use fluvio_protocol::{Encoder, Decoder, api::Request};
+
+use super::api::InternalSpuApi;
+
+/// Full set of ACL entries, SPU replaces its entries with these
+#[derive(Decoder, Encoder, Debug, Default, Clone)]
+pub struct UpdateAclRequest {
+    /// ACLs are enforced only if enabled
+    pub enabled: bool,
+    pub entries: Vec<AclEntry>,
+}
+
+impl UpdateAclRequest {
+    pub fn new(enabled: bool, entries: Vec<AclEntry>) -> Self {
+        Self { enabled, entries }
+    }
+}
+
+impl Request for UpdateAclRequest {
+    const API_KEY: u16 = InternalSpuApi::UpdateAcl as u16;
+    type Response = UpdateAclResponse;
+}
+
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct UpdateAclResponse {}
@@ -12,7 +12,42 @@ pub type UpdateReplicaRequest = ControlPlaneRequest<Replica>;
 impl Request for UpdateReplicaRequest {
     const API_KEY: u16 = InternalSpuApi::UpdateReplica as u16;
     type Response = UpdateReplicaResponse;
+    // replica carries topic configs, so all of their fields must be encoded
+    const DEFAULT_API_VERSION: i16 = fluvio_controlplane_metadata::COMMON_VERSION;
 }
 
 #[derive(Decoder, Encoder, Default, Debug)]
 pub struct UpdateReplicaResponse {}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use fluvio_controlplane_metadata::topic::TopicStorageConfig;
+
+    use super::*;
+
+    #[test]
+    fn test_replica_topic_config_round_trip() {
+        let mut replica = Replica::new(("orders", 0), 5001, vec![5001, 5002]);
+        replica.storage = Some(TopicStorageConfig {
+            min_in_sync_replicas: Some(2),
+            index_interval_bytes: Some(1024),
+            index_max_bytes: Some(4096),
+            ..Default::default()
+        });
+        let request = UpdateReplicaRequest::with_all(1, vec![replica.clone()]);
+
+        let mut bytes = vec![];
+        request
+            .encode(&mut bytes, UpdateReplicaRequest::DEFAULT_API_VERSION)
+            .expect("encode");
+        let decoded = UpdateReplicaRequest::decode_from(
+            &mut Cursor::new(bytes),
+            UpdateReplicaRequest::DEFAULT_API_VERSION,
+        )
+        .expect("decode");
+
+        assert_eq!(decoded.all, vec![replica]);
+    }
+}
@@ -59,6 +59,9 @@ pub enum HubError {
     #[error("Package already published: {0}")]
     PackageAlreadyPublished(String),
 
+    #[error("Group {0} is not owned by the logged in account, check group in package-meta")]
+    GroupNotOwned(String),
+
     #[error("Unable to package: {0}")]
     UnableToAssemblePackage(String),
 
@@ -0,0 +1,162 @@
+//! Garbage collection of download caches
+//!
+//! The installer and FVM keep downloaded binaries in cache directories. Each entry of a cache
+//! directory is a file or a directory which is used as a whole. Entries no longer referenced
+//! are orphans and always collected, the others are evicted least recently used first until
+//! the cache fits its maximum size.
+
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use anyhow::Result;
+use serde::Serialize;
+use tracing::debug;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheEntry {
+    pub path: PathBuf,
+    /// size in bytes, of all files for a directory
+    pub size: u64,
+    /// modification time, of most recently modified file for a directory
+    pub last_used: SystemTime,
+}
+
+/// Entries removed by collection
+#[derive(Debug, Default, PartialEq, Eq, Serialize)]
+pub struct GcReport {
+    pub removed: Vec<PathBuf>,
+    /// bytes freed
+    pub reclaimed: u64,
+}
+
+impl CacheEntry {
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let (size, last_used) = usage(&path)?;
+        Ok(Self {
+            path,
+            size,
+            last_used,
+        })
+    }
+}
+
+fn usage(path: &Path) -> Result<(u64, SystemTime)> {
+    let metadata = fs::metadata(path)?;
+    if !metadata.is_dir() {
+        return Ok((metadata.len(), metadata.modified()?));
+    }
+    let mut size = 0;
+    let mut last_used = metadata.modified()?;
+    for entry in fs::read_dir(path)? {
+        let (entry_size, entry_used) = usage(&entry?.path())?;
+        size += entry_size;
+        last_used = last_used.max(entry_used);
+    }
+    Ok((size, last_used))
+}
+
+/// Mark file as used now, so it's evicted last
+pub fn touch(path: impl AsRef<Path>) -> Result<()> {
+    File::options()
+        .append(true)
+        .open(path)?
+        .set_modified(SystemTime::now())?;
+    Ok(())
+}
+
+/// Entries to evict so the rest fits in `max_size` bytes, least recently used first
+pub fn lru_evictions(mut entries: Vec<CacheEntry>, max_size: u64) -> Vec<CacheEntry> {
+    entries.sort_by_key(|entry| entry.last_used);
+    let mut size: u64 = entries.iter().map(|entry| entry.size).sum();
+    entries
+        .into_iter()
+        .take_while(|entry| {
+            let evict = size > max_size;
+            size = size.saturating_sub(entry.size);
+            evict
+        })
+        .collect()
+}
+
+/// Remove entries from the cache, with `dry_run` only report what would be removed
+pub fn remove_entries(entries: Vec<CacheEntry>, dry_run: bool) -> Result<GcReport> {
+    let mut report = GcReport::default();
+    for entry in entries {
+        if !dry_run {
+            debug!(path = ?entry.path, size = entry.size, "removing cache entry");
+            if entry.path.is_dir() {
+                fs::remove_dir_all(&entry.path)?;
+            } else {
+                fs::remove_file(&entry.path)?;
+            }
+        }
+        report.reclaimed += entry.size;
+        report.removed.push(entry.path);
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn entry(name: &str, size: u64, age: u64) -> CacheEntry {
+        CacheEntry {
+            path: PathBuf::from(name),
+            size,
+            last_used: SystemTime::UNIX_EPOCH + Duration::from_secs(1000 - age),
+        }
+    }
+
+    #[test]
+    fn test_lru_evictions() {
+        let entries = vec![
+            entry("recent", 40, 1),
+            entry("oldest", 30, 300),
+            entry("old", 20, 200),
+        ];
+        let evicted = |max_size| -> Vec<PathBuf> {
+            lru_evictions(entries.clone(), max_size)
+                .into_iter()
+                .map(|it| it.path)
+                .collect()
+        };
+
+        assert!(evicted(90).is_empty());
+        assert_eq!(evicted(60), vec![PathBuf::from("oldest")]);
+        assert_eq!(
+            evicted(40),
+            vec![PathBuf::from("oldest"), PathBuf::from("old")]
+        );
+        assert_eq!(evicted(0).len(), 3);
+    }
+
+    #[test]
+    fn test_remove_entries() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let file = dir.path().join("fluvio-cloud");
+        fs::write(&file, b"binary").unwrap();
+        let version = dir.path().join("0.11.0");
+        fs::create_dir(&version).unwrap();
+        fs::write(version.join("fluvio"), b"fluvio").unwrap();
+        fs::write(version.join("manifest.json"), b"{}").unwrap();
+
+        let entries = vec![
+            CacheEntry::open(&file).unwrap(),
+            CacheEntry::open(&version).unwrap(),
+        ];
+        assert_eq!(entries[1].size, 8);
+
+        let report = remove_entries(entries.clone(), true).unwrap();
+        assert_eq!(report.reclaimed, 14);
+        assert!(file.exists() && version.exists());
+
+        let report = remove_entries(entries, false).unwrap();
+        assert_eq!(report.removed, vec![file.clone(), version.clone()]);
+        assert!(!file.exists() && !version.exists());
+    }
+}
@@ -27,10 +27,11 @@ pub async fn get_pkg_list(endpoint: &str, remote: &Option<String>) -> Result<Pac
         .map_err(|_| anyhow!("rejected access credentials, try 'fluvio cloud login'"))?;
     let url = format!("{}/{endpoint}", &access.remote);
 
-    let req = http::Request::get(&url)
+    let mut req = http::Request::get(&url)
         .header("Authorization", action_token)
         .body("")
         .map_err(|e| anyhow!("request format error {e}"))?;
+    access.sign_request(&mut req)?;
 
     let resp = htclient::send(req)
         .await
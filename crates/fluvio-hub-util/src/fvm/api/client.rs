@@ -1,10 +1,23 @@
 //! Hub FVM API Client
 
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
 use anyhow::{Error, Result};
 use serde::{Deserialize, Serialize};
 use url::Url;
 
-use crate::fvm::{Channel, PackageSet, PackageSetRecord};
+use crate::fvm::{Artifact, Channel, PackageSet, PackageSetRecord};
+
+use super::download::Download;
+use super::metrics::{DownloadMetrics, MetricsSink};
+
+/// Attempts made after a failed artifact download
+pub const DEFAULT_DOWNLOAD_RETRIES: u32 = 2;
+
+/// Wait before the first retry, grows linearly with each attempt
+const RETRY_BACKOFF: Duration = Duration::from_millis(500);
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct ApiError {
@@ -15,6 +28,8 @@ pub struct ApiError {
 /// HTTP Client for interacting with the Hub FVM API
 pub struct Client {
     api_url: Url,
+    retries: u32,
+    metrics: Option<Arc<dyn MetricsSink>>,
 }
 
 impl Client {
@@ -22,7 +37,75 @@ impl Client {
     pub fn new(url: &str) -> Result<Self> {
         let api_url = url.parse::<Url>()?;
 
-        Ok(Self { api_url })
+        Ok(Self {
+            api_url,
+            retries: DEFAULT_DOWNLOAD_RETRIES,
+            metrics: None,
+        })
+    }
+
+    /// Sets the number of attempts made after a failed artifact download
+    pub fn with_retries(mut self, retries: u32) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Reports [`DownloadMetrics`] of every artifact retrieved to `sink`
+    pub fn with_metrics(mut self, sink: Arc<dyn MetricsSink>) -> Self {
+        self.metrics = Some(sink);
+        self
+    }
+
+    /// Downloads the artifact to `target_dir`, retrying failed attempts.
+    ///
+    /// Returns the path to the downloaded artifact
+    pub async fn download(&self, artifact: &Artifact, target_dir: &Path) -> Result<PathBuf> {
+        let start = Instant::now();
+        let mut retries = 0;
+
+        let path = loop {
+            match artifact.download(target_dir.to_path_buf()).await {
+                Ok(path) => break path,
+                Err(err) if retries < self.retries => {
+                    retries += 1;
+                    tracing::warn!(
+                        name = artifact.name,
+                        retries,
+                        %err,
+                        "Artifact download failed, retrying"
+                    );
+                    fluvio_future::timer::sleep(RETRY_BACKOFF * retries).await;
+                }
+                Err(err) => return Err(err),
+            }
+        };
+
+        self.record(DownloadMetrics {
+            name: artifact.name.to_owned(),
+            bytes: std::fs::metadata(&path)?.len(),
+            duration: start.elapsed(),
+            retries,
+            cache_hit: false,
+        });
+
+        Ok(path)
+    }
+
+    /// Reports an artifact reused from a previous install instead of downloaded
+    pub fn record_cache_hit(&self, artifact: &Artifact, bytes: u64, duration: Duration) {
+        self.record(DownloadMetrics {
+            name: artifact.name.to_owned(),
+            bytes,
+            duration,
+            retries: 0,
+            cache_hit: true,
+        });
+    }
+
+    fn record(&self, metrics: DownloadMetrics) {
+        if let Some(sink) = &self.metrics {
+            sink.record(metrics);
+        }
     }
 
     /// Fetches a [`PackageSet`] from the Hub with the specific [`Channel`]
@@ -0,0 +1,106 @@
+//! Download metrics reported by the FVM [`Client`](super::Client)
+
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Measurements for a single artifact download
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DownloadMetrics {
+    /// Name of the artifact
+    pub name: String,
+    /// Size of the artifact in bytes
+    pub bytes: u64,
+    /// Time spent retrieving the artifact, including retries
+    pub duration: Duration,
+    /// Attempts made after the first one failed
+    pub retries: u32,
+    /// Whether the artifact was reused from a previous install instead of downloaded
+    pub cache_hit: bool,
+}
+
+/// Receives metrics of each download made by the [`Client`](super::Client)
+pub trait MetricsSink: Send + Sync {
+    fn record(&self, metrics: DownloadMetrics);
+}
+
+/// Sink keeping every recorded download, used to summarize an install
+#[derive(Debug, Default)]
+pub struct MetricsSummary {
+    downloads: Mutex<Vec<DownloadMetrics>>,
+}
+
+impl MetricsSummary {
+    /// Recorded downloads, in the order they finished
+    pub fn downloads(&self) -> Vec<DownloadMetrics> {
+        self.downloads
+            .lock()
+            .map(|downloads| downloads.clone())
+            .unwrap_or_default()
+    }
+
+    /// Bytes transferred over the network, cache hits are not counted
+    pub fn total_bytes(&self) -> u64 {
+        self.downloads()
+            .iter()
+            .filter(|metrics| !metrics.cache_hit)
+            .map(|metrics| metrics.bytes)
+            .sum()
+    }
+
+    pub fn total_duration(&self) -> Duration {
+        self.downloads()
+            .iter()
+            .map(|metrics| metrics.duration)
+            .sum()
+    }
+
+    pub fn total_retries(&self) -> u32 {
+        self.downloads().iter().map(|metrics| metrics.retries).sum()
+    }
+
+    pub fn cache_hits(&self) -> usize {
+        self.downloads()
+            .iter()
+            .filter(|metrics| metrics.cache_hit)
+            .count()
+    }
+}
+
+impl MetricsSink for MetricsSummary {
+    fn record(&self, metrics: DownloadMetrics) {
+        if let Ok(mut downloads) = self.downloads.lock() {
+            downloads.push(metrics);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summarizes_recorded_downloads() {
+        let summary = MetricsSummary::default();
+
+        summary.record(DownloadMetrics {
+            name: "fluvio".to_string(),
+            bytes: 1024,
+            duration: Duration::from_millis(300),
+            retries: 2,
+            cache_hit: false,
+        });
+        summary.record(DownloadMetrics {
+            name: "fluvio-run".to_string(),
+            bytes: 2048,
+            duration: Duration::from_millis(10),
+            retries: 0,
+            cache_hit: true,
+        });
+
+        assert_eq!(summary.downloads().len(), 2);
+        assert_eq!(summary.total_bytes(), 1024);
+        assert_eq!(summary.total_duration(), Duration::from_millis(310));
+        assert_eq!(summary.total_retries(), 2);
+        assert_eq!(summary.cache_hits(), 1);
+    }
+}
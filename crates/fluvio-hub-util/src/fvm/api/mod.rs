@@ -1,5 +1,7 @@
 mod client;
 mod download;
+mod metrics;
 
 pub use client::Client;
 pub use download::Download;
+pub use metrics::{DownloadMetrics, MetricsSink, MetricsSummary};
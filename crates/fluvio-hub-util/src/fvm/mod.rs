@@ -11,7 +11,7 @@ use thiserror::Error;
 use serde::{Deserialize, Serialize};
 use semver::Version;
 
-pub use api::{Client, Download};
+pub use api::{Client, Download, DownloadMetrics, MetricsSink, MetricsSummary};
 
 pub const STABLE_VERSION_CHANNEL: &str = "stable";
 pub const LATEST_VERSION_CHANNEL: &str = "latest";
@@ -12,8 +12,10 @@ use fluvio_hub_protocol::infinyon_tok::read_infinyon_token;
 use fluvio_hub_protocol::infinyon_tok::read_infinyon_token_rem;
 use fluvio_hub_protocol::constants::{HUB_API_ACT, HUB_API_HUBID, HUB_REMOTE, CLI_CONFIG_HUB};
 use fluvio_types::defaults::CLI_CONFIG_PATH;
+use fluvio_types::secret::SecretRef;
 
 use crate::keymgmt::Keypair;
+use crate::signing::RequestSigningKey;
 use crate::htclient;
 use crate::htclient::ResponseExt;
 
@@ -22,6 +24,9 @@ const ACCESS_FILE_PTR: &str = "hcurrent";
 const ACCESS_FILE_DEF: &str = "default"; // default profile name
 const DEFAULT_CLOUD_REMOTE: &str = "https://infinyon.cloud";
 
+/// secret with cloud token, e.g. `k8s:hub-auth/token`, overrides logged in credentials
+pub const HUB_TOKEN_SECRET_ENV: &str = "FLUVIO_HUB_TOKEN_SECRET";
+
 pub const ACTION_LIST: &str = "list";
 pub const ACTION_LIST_WITH_META: &str = "lwm";
 pub const ACTION_CREATE_HUBID: &str = "chid";
@@ -38,6 +43,9 @@ pub struct HubAccess {
     pub hubid: String,  // hubid associated with the signing key
     pub pkgkey: String, // package signing key (private)
     pub pubkey: String, // package signing key (public)
+    /// hub requests are signed with the key if set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_signing: Option<RequestSigningKey>,
 }
 
 impl HubAccess {
@@ -47,6 +55,7 @@ impl HubAccess {
             hubid: String::new(),
             pkgkey: String::new(),
             pubkey: String::new(),
+            request_signing: None,
         }
     }
 
@@ -67,11 +76,12 @@ impl HubAccess {
         let api_url = format!("{host}/{HUB_API_HUBID}");
         debug!("Sending Action token {action_token}");
 
-        let req = http::Request::put(&api_url)
+        let mut req = http::Request::put(&api_url)
             .header("Authorization", &action_token)
             .header(http::header::CONTENT_TYPE, mime::JSON.as_str())
             .body(msg_json)
             .map_err(|e| HubError::HubAccess(format!("request formatting error {e}")))?;
+        self.sign_request(&mut req)?;
 
         let res = crate::htclient::send(req)
             .await
@@ -130,7 +140,17 @@ impl HubAccess {
     }
 
     async fn get_action_auth(&self, action: &str) -> Result<String> {
-        let cloud_token = read_infinyon_token().unwrap_or_default();
+        let cloud_token = match std::env::var(HUB_TOKEN_SECRET_ENV) {
+            Ok(secret) => {
+                let secret: SecretRef = secret
+                    .parse()
+                    .map_err(|e| HubError::General(format!("{HUB_TOKEN_SECRET_ENV}: {e}")))?;
+                secret
+                    .resolve()
+                    .map_err(|e| HubError::General(format!("{HUB_TOKEN_SECRET_ENV}: {e}")))?
+            }
+            Err(_) => read_infinyon_token().unwrap_or_default(),
+        };
         self.make_action_token(action, cloud_token).await
     }
 
@@ -147,10 +167,11 @@ impl HubAccess {
         if !authn_token.is_empty() {
             builder = builder.header("Authorization", &authn_token);
         }
-        let req = builder
+        let mut req = builder
             .header(http::header::CONTENT_TYPE, mime::JSON.as_str())
             .body(msg_action_token)
             .map_err(|e| HubError::HubAccess(format!("request formatting error {e}")))?;
+        self.sign_request(&mut req)?;
 
         let resp = crate::htclient::send(req)
             .await
@@ -174,6 +195,15 @@ impl HubAccess {
         }
     }
 
+    /// Adds signature headers to a request for the hub, if request signing is
+    /// configured for the profile
+    pub fn sign_request<T: AsRef<[u8]>>(&self, req: &mut http::Request<T>) -> Result<()> {
+        match &self.request_signing {
+            Some(key) => key.sign(req),
+            None => Ok(()),
+        }
+    }
+
     pub fn have_pkgkey(&self) -> bool {
         !self.pkgkey.is_empty()
     }
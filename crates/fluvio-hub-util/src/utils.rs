@@ -12,7 +12,7 @@ use fluvio_hub_protocol::constants::HUB_PACKAGE_EXT;
 
 use crate::htclient;
 use crate::HubAccess;
-use crate::{HUB_API_SM, HUB_API_CONN_PKG};
+use crate::{HUB_API_SM, HUB_API_CONN_PKG, HUB_API_GROUP};
 use crate::{package_get_meta, packagename_validate};
 use crate::htclient::ResponseExt;
 
@@ -85,15 +85,23 @@ pub fn cli_pkgname_to_filename(pkgname: &str) -> Result<String> {
 /// returns recommended name and data
 pub async fn get_package(pkgurl: &str, access: &HubAccess) -> Result<Vec<u8>> {
     let actiontoken = access.get_download_token().await?;
-    get_package_with_token(pkgurl, &actiontoken).await
+    let mut req = package_request(pkgurl, &actiontoken)?;
+    access.sign_request(&mut req)?;
+    send_package_request(req).await
 }
 
 pub async fn get_package_with_token(pkgurl: &str, actiontoken: &str) -> Result<Vec<u8>> {
-    let req = http::Request::get(pkgurl)
+    send_package_request(package_request(pkgurl, actiontoken)?).await
+}
+
+fn package_request(pkgurl: &str, actiontoken: &str) -> Result<http::Request<&'static str>> {
+    http::Request::get(pkgurl)
         .header("Authorization", actiontoken)
         .body("")
-        .map_err(|_| HubError::PackageDownload("authorization error".into()))?;
+        .map_err(|_| HubError::PackageDownload("authorization error".into()))
+}
 
+async fn send_package_request(req: http::Request<&'static str>) -> Result<Vec<u8>> {
     let resp = htclient::send(req)
         .await
         .map_err(|e| HubError::HubAccess(format!("Failed to connect {e}")))?;
@@ -185,13 +193,17 @@ async fn push_package_api(put_url: &str, pkgpath: &str, access: &HubAccess) -> R
         )));
     }
 
-    let pkg_bytes = std::fs::read(pkgpath)?;
     let actiontoken = access.get_publish_token().await?;
-    let req = http::Request::put(put_url)
+    // fail before uploading if the package would be rejected for its group
+    check_group_owner(access, &pm.group, &actiontoken).await?;
+
+    let pkg_bytes = std::fs::read(pkgpath)?;
+    let mut req = http::Request::put(put_url)
         .header("Authorization", &actiontoken)
         .header(http::header::CONTENT_TYPE, mime::OCTET_STREAM.as_str())
         .body(pkg_bytes)
         .map_err(|e| HubError::HubAccess(format!("request formatting error {e}")))?;
+    access.sign_request(&mut req)?;
     debug!("auth accepted");
     let res = crate::htclient::send(req)
         .await
@@ -219,6 +231,39 @@ async fn push_package_api(put_url: &str, pkgpath: &str, access: &HubAccess) -> R
     }
 }
 
+/// Checks with the hub that the account of the publish token owns the group
+pub async fn check_group_owner(access: &HubAccess, group: &str, actiontoken: &str) -> Result<()> {
+    let url = group_owner_url(&access.remote, group);
+    let mut req = http::Request::get(&url)
+        .header("Authorization", actiontoken)
+        .body("")
+        .map_err(|e| HubError::HubAccess(format!("request formatting error {e}")))?;
+    access.sign_request(&mut req)?;
+    let res = crate::htclient::send(req)
+        .await
+        .map_err(|e| HubError::HubAccess(format!("Failed to connect {e}")))?;
+    match res.status() {
+        StatusCode::OK => {
+            debug!(group, "group ownership verified");
+            Ok(())
+        }
+        StatusCode::FORBIDDEN => Err(HubError::GroupNotOwned(group.to_string())),
+        StatusCode::UNAUTHORIZED => Err(HubError::HubAccess("Unauthorized, please log in".into())),
+        // hub without ownership endpoint, the upload itself is checked
+        StatusCode::NOT_FOUND => {
+            debug!(group, "group ownership check not supported by hub");
+            Ok(())
+        }
+        status => Err(HubError::HubAccess(format!(
+            "group ownership check error status code({status})"
+        ))),
+    }
+}
+
+fn group_owner_url(host: &str, group: &str) -> String {
+    format!("{host}/{HUB_API_GROUP}/{group}/owner")
+}
+
 /// Generates Sha256 checksum for a given file
 pub fn sha256_digest(path: &PathBuf) -> Result<String> {
     let mut hasher = Sha256::new();
@@ -241,6 +286,7 @@ mod util_tests {
     use super::cli_pkgname_to_url;
     use super::cli_pkgname_to_filename;
     use super::cli_conn_pkgname_to_url;
+    use super::group_owner_url;
 
     #[test]
     fn cli_pkgname_split_t() {
@@ -280,6 +326,15 @@ mod util_tests {
         }
     }
 
+    #[test]
+    fn group_owner_url_t() {
+        let url = group_owner_url("https://hub.infinyon.cloud", "infinyon");
+        assert_eq!(
+            url,
+            "https://hub.infinyon.cloud/hub/v0/group/infinyon/owner"
+        );
+    }
+
     #[test]
     fn cli_pkgname_to_filename_t() {
         let recs_good = vec![
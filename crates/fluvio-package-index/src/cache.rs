@@ -0,0 +1,101 @@
+//! Disk cache of index responses.
+//!
+//! Responses are stored by URL together with their `ETag` and `Last-Modified`
+//! headers, so following requests for the same file can be made conditional and
+//! the cached body reused when the server answers `304 Not Modified` or can't be reached.
+
+use std::fs;
+use std::io::Result as IoResult;
+use std::path::{Path, PathBuf};
+
+use http::header::{ETAG, LAST_MODIFIED};
+use http::HeaderMap;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use crate::sha256_hex;
+
+/// Headers used to revalidate a cached response
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CacheValidators {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub etag: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_modified: Option<String>,
+}
+
+impl CacheValidators {
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        let header = |name| {
+            headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_owned)
+        };
+        Self {
+            etag: header(ETAG),
+            last_modified: header(LAST_MODIFIED),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheEntry {
+    url: String,
+    #[serde(flatten)]
+    validators: CacheValidators,
+}
+
+/// Cached responses in a directory, one body and one entry file per URL
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResponseCache {
+    dir: PathBuf,
+}
+
+impl ResponseCache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Validators of the cached response for `url`, if any
+    pub fn validators(&self, url: &str) -> Option<CacheValidators> {
+        let (entry_path, body_path) = self.paths(url);
+        if !body_path.exists() {
+            return None;
+        }
+        let entry: CacheEntry = serde_json::from_slice(&fs::read(entry_path).ok()?).ok()?;
+        // entries are named by hash of the url
+        (entry.url == url).then_some(entry.validators)
+    }
+
+    /// Body of the cached response for `url`, if any
+    pub fn body(&self, url: &str) -> Option<Vec<u8>> {
+        self.validators(url)?;
+        fs::read(self.paths(url).1).ok()
+    }
+
+    pub fn store(&self, url: &str, validators: CacheValidators, body: &[u8]) -> IoResult<()> {
+        let (entry_path, body_path) = self.paths(url);
+        let entry = CacheEntry {
+            url: url.to_owned(),
+            validators,
+        };
+        fs::create_dir_all(&self.dir)?;
+        fs::write(body_path, body)?;
+        fs::write(entry_path, serde_json::to_vec(&entry)?)?;
+        debug!(url, dir = %self.dir.display(), "cached response");
+        Ok(())
+    }
+
+    fn paths(&self, url: &str) -> (PathBuf, PathBuf) {
+        let key = sha256_hex(url.as_bytes());
+        (
+            self.dir.join(format!("{key}.json")),
+            self.dir.join(format!("{key}.body")),
+        )
+    }
+}
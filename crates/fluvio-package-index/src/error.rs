@@ -13,8 +13,18 @@ pub enum Error {
     MissingRelease(semver::Version),
     #[error("Failed to lookup package: target {0} does not exist")]
     MissingTarget(Target),
+    #[error("Failed to lookup package: channel {0} does not exist")]
+    MissingChannel(String),
+    #[error("Failed to lookup package: no release matching {0} for target {1}")]
+    NoMatchingRelease(semver::VersionReq, Target),
+    #[error("Failed to lookup package: no release matching {0}")]
+    NoReleaseInRange(semver::VersionReq),
     #[error("Package {0} has no releases")]
     NoReleases(String),
+    #[error("Package {0} is not a meta-package")]
+    NotMetaPackage(String),
+    #[error("Package kind {0} is not supported by this client, try updating it")]
+    UnsupportedKind(String),
     #[error("Failed to create new package {0}: it already exists")]
     PackageAlreadyExists(String),
     #[error("Failed to add release: release version {0} for {0} already exists")]
@@ -29,6 +39,14 @@ pub enum Error {
     HttpError(#[from] HttpError),
     #[error("DANGER: Downloaded package checksum did not match")]
     ChecksumError,
+    #[error("Index has no checksum for target {0}")]
+    MissingChecksum(Target),
+    #[error("Registry transport error: {0}")]
+    TransportError(String),
+    #[error("Deadline of registry requests exceeded")]
+    DeadlineExceeded,
+    #[error("Unexpected response status {0}")]
+    UnexpectedStatus(u16),
 
     // Package ID specific errors
     #[error("PackageIds must have at least one `/` separator: <group>/<name>:<version>")]
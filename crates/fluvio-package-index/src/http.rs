@@ -1,16 +1,77 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
 use url::Url;
-use http::Request;
+use http::header::{IF_MODIFIED_SINCE, IF_NONE_MATCH};
+use http::{Method, Request, Response, StatusCode};
+use tracing::{debug, warn};
+use crate::cache::{CacheValidators, ResponseCache};
 use crate::package_id::WithVersion;
-use crate::{Result, FluvioIndex, Package, PackageId, Target, TagName};
+use crate::{
+    Error, Result, Artifact, FluvioIndex, Group, GroupName, IndexLayout, Package, PackageId,
+    Release, Target, TagName,
+};
+
+/// Kind of file a request fetches, which decides its timeout
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RequestClass {
+    /// Index, package metadata, checksums and signatures
+    Metadata,
+    /// Release binaries and artifacts
+    Download,
+}
+
+/// Timeouts of requests by class
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timeouts {
+    pub metadata: Duration,
+    pub download: Duration,
+}
+
+impl Default for Timeouts {
+    fn default() -> Self {
+        Self {
+            metadata: Duration::from_secs(10),
+            download: Duration::from_secs(300),
+        }
+    }
+}
+
+impl Timeouts {
+    pub fn of(&self, class: RequestClass) -> Duration {
+        match class {
+            RequestClass::Metadata => self.metadata,
+            RequestClass::Download => self.download,
+        }
+    }
+}
+
+/// Timeout of a request, set as extension of every request built by `HttpAgent`.
+/// Clients sending the request should apply it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RequestTimeout(pub Duration);
 
 pub struct HttpAgent {
     base_url: url::Url,
+    layout: IndexLayout,
+    timeouts: Timeouts,
+    /// requests built after the deadline fail, others time out by the deadline at the latest
+    deadline: Option<Instant>,
+    /// metadata responses are cached and revalidated, if set
+    cache: Option<ResponseCache>,
+    force_refresh: bool,
 }
 
 impl Default for HttpAgent {
     fn default() -> Self {
         Self {
             base_url: url::Url::parse(crate::INDEX_LOCATION).unwrap(),
+            layout: IndexLayout::V1,
+            timeouts: Timeouts::default(),
+            deadline: None,
+            cache: None,
+            force_refresh: false,
         }
     }
 }
@@ -19,16 +80,191 @@ impl HttpAgent {
     pub fn with_prefix(prefix: &str) -> Result<Self> {
         Ok(Self {
             base_url: Url::parse(crate::INDEX_HOST).unwrap().join(prefix)?,
+            ..Default::default()
+        })
+    }
+
+    /// agent for index hosted at `base_url`, e.g. a mirror of packages.fluvio.io
+    pub fn with_base_url(base_url: &str) -> Result<Self> {
+        let mut base_url = Url::parse(base_url)?;
+        if !base_url.path().ends_with('/') {
+            base_url.set_path(&format!("{}/", base_url.path()));
+        }
+        Ok(Self {
+            base_url,
+            ..Default::default()
+        })
+    }
+
+    /// agent reading the index in `layout`.
+    ///
+    /// If the base URL ends with a versioned endpoint, e.g. `/v1/`, it is replaced
+    /// with the endpoint of `layout`. Otherwise only the file paths change.
+    pub fn with_layout(&self, layout: IndexLayout) -> Result<Self> {
+        let current = self
+            .base_url
+            .path_segments()
+            .and_then(|mut segments| segments.rfind(|it| !it.is_empty()));
+        let is_versioned = [IndexLayout::V1, IndexLayout::V2]
+            .iter()
+            .any(|it| it.endpoint() == current);
+        let base_url = match layout.endpoint() {
+            Some(endpoint) if is_versioned => self.base_url.join(&format!("../{endpoint}/"))?,
+            _ => self.base_url.clone(),
+        };
+        Ok(Self {
+            base_url,
+            layout,
+            timeouts: self.timeouts,
+            deadline: self.deadline,
+            cache: self.cache.clone(),
+            force_refresh: self.force_refresh,
         })
     }
 
+    pub fn with_timeouts(mut self, timeouts: Timeouts) -> Self {
+        self.timeouts = timeouts;
+        self
+    }
+
+    /// All requests must complete by the deadline
+    pub fn with_deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Timeout of the next request of the class, limited by the deadline
+    pub fn timeout(&self, class: RequestClass) -> Result<Duration> {
+        let timeout = self.timeouts.of(class);
+        match self.deadline {
+            Some(deadline) => {
+                let left = deadline.saturating_duration_since(Instant::now());
+                if left.is_zero() {
+                    return Err(Error::DeadlineExceeded);
+                }
+                Ok(timeout.min(left))
+            }
+            None => Ok(timeout),
+        }
+    }
+
+    /// Cache index, package and other metadata responses in `dir`.
+    ///
+    /// Requests for cached files are made conditional, see [`HttpAgent::cached_body_from_response`].
+    pub fn with_cache_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.cache = Some(ResponseCache::new(dir));
+        self
+    }
+
+    /// Ignore cached responses, the fresh responses are still cached
+    pub fn with_force_refresh(mut self, force_refresh: bool) -> Self {
+        self.force_refresh = force_refresh;
+        self
+    }
+
+    pub fn cache_dir(&self) -> Option<&Path> {
+        self.cache.as_ref().map(ResponseCache::dir)
+    }
+
+    fn request_get(&self, url: &Url, class: RequestClass) -> Result<Request<()>> {
+        let mut builder = Request::get(url.as_str());
+        if let Some(validators) = self.revalidate(url, class) {
+            if let Some(etag) = validators.etag {
+                builder = builder.header(IF_NONE_MATCH, etag);
+            }
+            if let Some(last_modified) = validators.last_modified {
+                builder = builder.header(IF_MODIFIED_SINCE, last_modified);
+            }
+        }
+        let mut request = builder.body(())?;
+        request
+            .extensions_mut()
+            .insert(RequestTimeout(self.timeout(class)?));
+        Ok(request)
+    }
+
+    /// Validators of cached response to send with a request for `url`
+    fn revalidate(&self, url: &Url, class: RequestClass) -> Option<CacheValidators> {
+        if class != RequestClass::Metadata || self.force_refresh {
+            return None;
+        }
+        self.cache.as_ref()?.validators(url.as_str())
+    }
+
+    /// Returns the body of the response to a request built by this agent.
+    ///
+    /// Successful responses are cached if a cache directory is set. The cached body is
+    /// returned when the server answers `304 Not Modified`, or when the request failed
+    /// or the server errored and refresh is not forced.
+    pub fn cached_body_from_response<E: fmt::Display>(
+        &self,
+        request: &Request<()>,
+        response: std::result::Result<Response<Vec<u8>>, E>,
+    ) -> Result<Vec<u8>> {
+        let url = request.uri().to_string();
+        let cached = || match (&self.cache, self.force_refresh) {
+            (Some(cache), false) => cache.body(&url),
+            _ => None,
+        };
+
+        let response = match response {
+            Ok(response) => response,
+            Err(err) => {
+                return match cached() {
+                    Some(body) => {
+                        warn!(%url, %err, "request failed, using cached response");
+                        Ok(body)
+                    }
+                    None => Err(Error::TransportError(err.to_string())),
+                };
+            }
+        };
+
+        let status = response.status();
+        if status == StatusCode::NOT_MODIFIED {
+            debug!(%url, "not modified, using cached response");
+            return cached().ok_or(Error::UnexpectedStatus(status.as_u16()));
+        }
+        if status.is_server_error() {
+            if let Some(body) = cached() {
+                warn!(%url, %status, "server error, using cached response");
+                return Ok(body);
+            }
+        }
+        if !status.is_success() {
+            return Err(Error::UnexpectedStatus(status.as_u16()));
+        }
+
+        let validators = CacheValidators::from_headers(response.headers());
+        let body = response.into_body();
+        if let Some(cache) = &self.cache {
+            if let Err(err) = cache.store(&url, validators, &body) {
+                debug!(%url, %err, "failed to cache response");
+            }
+        }
+        Ok(body)
+    }
+
     pub fn base_url(&self) -> &str {
         self.base_url.as_str()
     }
 
+    pub fn layout(&self) -> IndexLayout {
+        self.layout
+    }
+
+    /// Turns a request for a file into a request uploading it to the same path
+    pub fn request_put<B>(&self, request: &Request<()>, body: B) -> Result<Request<B>> {
+        let mut put = Request::put(request.uri()).body(body)?;
+        if let Some(timeout) = request.extensions().get::<RequestTimeout>() {
+            put.extensions_mut().insert(*timeout);
+        }
+        Ok(put)
+    }
+
     pub fn request_index(&self) -> Result<Request<()>> {
         let url = self.base_url.join("index.json")?;
-        Ok(Request::get(url.as_str()).body(())?)
+        self.request_get(&url, RequestClass::Metadata)
     }
 
     pub async fn index_from_response(&self, response: &[u8]) -> Result<FluvioIndex> {
@@ -37,10 +273,12 @@ impl HttpAgent {
     }
 
     pub fn request_package<T>(&self, id: &PackageId<T>) -> Result<Request<()>> {
-        let url =
-            self.base_url
-                .join(&format!("packages/{}/{}/meta.json", id.group(), id.name()))?;
-        Ok(Request::get(url.as_str()).body(())?)
+        let path = match self.layout {
+            IndexLayout::V2 => format!("index/{}/{}.json", id.group(), id.name()),
+            _ => format!("packages/{}/{}/meta.json", id.group(), id.name()),
+        };
+        let url = self.base_url.join(&path)?;
+        self.request_get(&url, RequestClass::Metadata)
     }
 
     pub async fn package_from_response(&self, response: &[u8]) -> Result<Package> {
@@ -48,6 +286,20 @@ impl HttpAgent {
         Ok(package)
     }
 
+    pub fn request_group(&self, group: &GroupName) -> Result<Request<()>> {
+        let path = match self.layout {
+            IndexLayout::V2 => format!("index/{group}.json"),
+            _ => format!("packages/{group}/meta.json"),
+        };
+        let url = self.base_url.join(&path)?;
+        self.request_get(&url, RequestClass::Metadata)
+    }
+
+    pub async fn group_from_response(&self, response: &[u8]) -> Result<Group> {
+        let group: Group = serde_json::from_slice(response)?;
+        Ok(group)
+    }
+
     pub fn request_tag(&self, id: &PackageId<WithVersion>, tag: &TagName) -> Result<Request<()>> {
         let url = self.base_url.join(&format!(
             "packages/{group}/{name}/tags/{tag}",
@@ -56,7 +308,7 @@ impl HttpAgent {
             tag = tag,
         ))?;
 
-        Ok(Request::get(url.as_str()).body(())?)
+        self.request_get(&url, RequestClass::Metadata)
     }
 
     pub fn request_release_download<T>(
@@ -80,7 +332,34 @@ impl HttpAgent {
             target = target.as_str(),
         ))?;
 
-        Ok(Request::get(url.as_str()).body(())?)
+        self.request_get(&url, RequestClass::Download)
+    }
+
+    /// HEAD request for the binary of the release, to check it is published without
+    /// downloading it
+    pub fn request_release_exists<T>(
+        &self,
+        id: &PackageId<T>,
+        version: &semver::Version,
+        target: &Target,
+    ) -> Result<Request<()>> {
+        let mut request = self.request_release_download(id, version, target)?;
+        *request.method_mut() = Method::HEAD;
+        // only headers are transferred
+        request
+            .extensions_mut()
+            .insert(RequestTimeout(self.timeout(RequestClass::Metadata)?));
+        Ok(request)
+    }
+
+    /// Whether the binary is published, from status of `request_release_exists` response
+    pub fn release_exists_from_status(&self, status: StatusCode) -> Result<bool> {
+        match status {
+            status if status.is_success() => Ok(true),
+            // object stores deny access to missing files without list permission
+            StatusCode::NOT_FOUND | StatusCode::FORBIDDEN => Ok(false),
+            status => Err(Error::UnexpectedStatus(status.as_u16())),
+        }
     }
 
     pub fn request_release_checksum<T>(
@@ -103,7 +382,76 @@ impl HttpAgent {
             target = target.as_str(),
         ))?;
 
-        Ok(Request::get(url.as_str()).body(())?)
+        self.request_get(&url, RequestClass::Metadata)
+    }
+
+    pub fn request_release_signature<T>(
+        &self,
+        id: &PackageId<T>,
+        version: &semver::Version,
+        target: &Target,
+    ) -> Result<Request<()>> {
+        let file_name = if target.to_string().contains("windows") {
+            format!("{}.exe", id.name())
+        } else {
+            id.name().to_string()
+        };
+        let url = self.base_url.join(&format!(
+            "packages/{group}/{name}/{version}/{target}/{file_name}.sig",
+            group = &id.group(),
+            name = &id.name(),
+            file_name = file_name,
+            version = version,
+            target = target.as_str(),
+        ))?;
+
+        self.request_get(&url, RequestClass::Metadata)
+    }
+
+    /// Checks the binary downloaded with `request_release_download` against the
+    /// checksum recorded in the index, returning the verified bytes
+    pub fn download_verified(
+        &self,
+        release: &Release,
+        target: &Target,
+        response: &[u8],
+    ) -> Result<Vec<u8>> {
+        release.verify(target, response)?;
+        Ok(response.to_vec())
+    }
+
+    pub fn request_release_artifact<T>(
+        &self,
+        id: &PackageId<T>,
+        version: &semver::Version,
+        artifact: &Artifact,
+    ) -> Result<Request<()>> {
+        let url = self.base_url.join(&format!(
+            "packages/{group}/{name}/{version}/artifacts/{file_name}",
+            group = &id.group(),
+            name = &id.name(),
+            file_name = artifact.name,
+            version = version,
+        ))?;
+
+        self.request_get(&url, RequestClass::Download)
+    }
+
+    pub fn request_release_artifact_checksum<T>(
+        &self,
+        id: &PackageId<T>,
+        version: &semver::Version,
+        artifact: &Artifact,
+    ) -> Result<Request<()>> {
+        let url = self.base_url.join(&format!(
+            "packages/{group}/{name}/{version}/artifacts/{file_name}.sha256",
+            group = &id.group(),
+            name = &id.name(),
+            file_name = artifact.name,
+            version = version,
+        ))?;
+
+        self.request_get(&url, RequestClass::Metadata)
     }
 
     pub async fn tag_version_from_response(
@@ -119,3 +467,172 @@ impl HttpAgent {
         Ok(version)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_timeouts() {
+        let id: PackageId = "fluvio/fluvio-cloud:0.1.0".parse().unwrap();
+        let version = semver::Version::parse("0.1.0").unwrap();
+        let target = Target::X86_64UnknownLinuxMusl;
+        let timeout = |request: Request<()>| request.extensions().get::<RequestTimeout>().copied();
+
+        let agent = HttpAgent::default().with_timeouts(Timeouts {
+            metadata: Duration::from_secs(2),
+            download: Duration::from_secs(60),
+        });
+        assert_eq!(
+            timeout(agent.request_package(&id).unwrap()),
+            Some(RequestTimeout(Duration::from_secs(2)))
+        );
+        assert_eq!(
+            timeout(
+                agent
+                    .request_release_download(&id, &version, &target)
+                    .unwrap()
+            ),
+            Some(RequestTimeout(Duration::from_secs(60)))
+        );
+
+        // deadline limits timeouts
+        let agent = agent.with_deadline(Instant::now() + Duration::from_secs(30));
+        let download = agent.timeout(RequestClass::Download).unwrap();
+        assert!(download <= Duration::from_secs(30));
+        assert_eq!(
+            agent.timeout(RequestClass::Metadata).unwrap(),
+            Duration::from_secs(2)
+        );
+
+        let agent = agent.with_deadline(Instant::now());
+        assert!(matches!(
+            agent.request_index(),
+            Err(Error::DeadlineExceeded)
+        ));
+    }
+
+    #[test]
+    fn test_release_exists() {
+        let id: PackageId = "fluvio/fluvio-cloud:0.1.0".parse().unwrap();
+        let version = semver::Version::parse("0.1.0").unwrap();
+        let agent = HttpAgent::default();
+
+        let request = agent
+            .request_release_exists(&id, &version, &Target::X86_64UnknownLinuxMusl)
+            .unwrap();
+        assert_eq!(request.method(), Method::HEAD);
+        assert_eq!(
+            request.uri(),
+            "https://packages.fluvio.io/v1/packages/fluvio/fluvio-cloud/0.1.0/x86_64-unknown-linux-musl/fluvio-cloud"
+        );
+
+        assert!(agent.release_exists_from_status(StatusCode::OK).unwrap());
+        assert!(!agent
+            .release_exists_from_status(StatusCode::NOT_FOUND)
+            .unwrap());
+        assert!(matches!(
+            agent.release_exists_from_status(StatusCode::BAD_GATEWAY),
+            Err(Error::UnexpectedStatus(502))
+        ));
+    }
+
+    #[test]
+    fn test_cached_responses() {
+        let dir = std::env::temp_dir().join(format!("fluvio-index-cache-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let agent = HttpAgent::default().with_cache_dir(&dir);
+        let ok = |body: &str| -> std::result::Result<Response<Vec<u8>>, String> {
+            Ok(Response::builder()
+                .header(http::header::ETAG, "\"v1\"")
+                .body(body.as_bytes().to_vec())
+                .unwrap())
+        };
+        let status = |status: StatusCode| -> std::result::Result<Response<Vec<u8>>, String> {
+            Ok(Response::builder().status(status).body(vec![]).unwrap())
+        };
+
+        // nothing cached yet
+        let request = agent.request_index().unwrap();
+        assert!(request.headers().get(IF_NONE_MATCH).is_none());
+        assert!(matches!(
+            agent.cached_body_from_response(&request, Err("offline")),
+            Err(Error::TransportError(_))
+        ));
+        assert_eq!(
+            agent
+                .cached_body_from_response(&request, ok("index"))
+                .unwrap(),
+            b"index"
+        );
+
+        let request = agent.request_index().unwrap();
+        assert_eq!(request.headers()[IF_NONE_MATCH], "\"v1\"");
+        assert_eq!(
+            agent
+                .cached_body_from_response(&request, status(StatusCode::NOT_MODIFIED))
+                .unwrap(),
+            b"index"
+        );
+        assert_eq!(
+            agent
+                .cached_body_from_response(&request, Err("offline"))
+                .unwrap(),
+            b"index"
+        );
+        assert_eq!(
+            agent
+                .cached_body_from_response(&request, status(StatusCode::BAD_GATEWAY))
+                .unwrap(),
+            b"index"
+        );
+        assert!(matches!(
+            agent.cached_body_from_response(&request, status(StatusCode::NOT_FOUND)),
+            Err(Error::UnexpectedStatus(404))
+        ));
+
+        // downloads are not revalidated
+        let id: PackageId = "fluvio/fluvio-cloud:0.1.0".parse().unwrap();
+        let version = semver::Version::parse("0.1.0").unwrap();
+        let download = agent
+            .request_release_download(&id, &version, &Target::X86_64UnknownLinuxMusl)
+            .unwrap();
+        assert!(download.headers().is_empty());
+
+        let agent = agent.with_force_refresh(true);
+        let request = agent.request_index().unwrap();
+        assert!(request.headers().get(IF_NONE_MATCH).is_none());
+        assert!(agent
+            .cached_body_from_response(&request, Err("offline"))
+            .is_err());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_agent_with_layout() {
+        let id: PackageId = "fluvio/fluvio-cloud:0.1.0".parse().unwrap();
+
+        let agent = HttpAgent::default().with_layout(IndexLayout::V2).unwrap();
+        assert_eq!(agent.base_url(), "https://packages.fluvio.io/v2/");
+        assert_eq!(
+            agent.request_package(&id).unwrap().uri(),
+            "https://packages.fluvio.io/v2/index/fluvio/fluvio-cloud.json"
+        );
+        let agent = agent.with_layout(IndexLayout::V1).unwrap();
+        assert_eq!(
+            agent.request_package(&id).unwrap().uri(),
+            "https://packages.fluvio.io/v1/packages/fluvio/fluvio-cloud/meta.json"
+        );
+
+        // mirrors without versioned endpoint keep base url
+        let agent = HttpAgent::with_base_url("https://mirror.example.com/fluvio")
+            .unwrap()
+            .with_layout(IndexLayout::V2)
+            .unwrap();
+        assert_eq!(
+            agent.request_index().unwrap().uri(),
+            "https://mirror.example.com/fluvio/index.json"
+        );
+    }
+}
@@ -12,21 +12,34 @@ use serde::{Serialize, Deserialize};
 
 mod tags;
 #[cfg(feature = "http_agent")]
+mod cache;
+#[cfg(feature = "http_agent")]
 mod http;
+#[cfg(feature = "http_agent")]
+mod mirror;
 mod error;
 mod target;
 mod version;
 mod package;
 mod package_id;
+mod resolve;
 
 #[cfg(feature = "http_agent")]
-pub use crate::http::HttpAgent;
+pub use crate::cache::{CacheValidators, ResponseCache};
+#[cfg(feature = "http_agent")]
+pub use crate::http::{HttpAgent, RequestClass, RequestTimeout, Timeouts};
+#[cfg(feature = "http_agent")]
+pub use crate::mirror::{RegistryTransport, mirror_release};
 
 pub use tags::TagName;
 pub use error::{Error, Result};
 pub use target::{Target, package_target};
 pub use version::PackageVersion;
-pub use package::{Package, PackageKind, Release};
+pub use package::{
+    Package, PackageKind, InstallStrategy, KindChange, Release, TargetDigest, Artifact,
+    ArtifactKind, Group, MetaMember, sha256_hex,
+};
+pub use resolve::{Explanation, RejectReason, Rejection, ReleaseSelection};
 pub use package_id::{PackageId, GroupName, PackageName, Registry, WithVersion, MaybeVersion};
 use semver::Version;
 
@@ -44,6 +57,61 @@ pub struct IndexMetadata {
     /// This version number corresponds to the crate version of the
     /// `fluvio-package-index` crate.
     pub minimum_client_version: Version,
+    /// Releases being rolled out in stages. A release with a rollout is offered
+    /// only to the given percentage of clients, and to none once halted.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rollouts: Vec<Rollout>,
+    /// Layouts the registry serves the index in, only `v1` if not given
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub layouts: Vec<IndexLayout>,
+}
+
+/// Layout of the package index, served under a versioned endpoint
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IndexLayout {
+    /// One `meta.json` per group and package under `packages/`
+    #[default]
+    V1,
+    /// Sparse layout, one file per group and package under `index/`
+    V2,
+    #[serde(other)]
+    Unknown,
+}
+
+impl IndexLayout {
+    /// The endpoint segment the layout is served under
+    pub fn endpoint(&self) -> Option<&'static str> {
+        match self {
+            Self::V1 => Some("v1"),
+            Self::V2 => Some("v2"),
+            Self::Unknown => None,
+        }
+    }
+}
+
+/// A staged rollout of a package release
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Rollout {
+    /// The package being rolled out, e.g. `fluvio/fluvio`
+    pub package: String,
+    pub version: Version,
+    /// The release channel the rollout applies to, all channels if not given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel: Option<String>,
+    /// The share of clients, from 0 to 100, the release is offered to
+    pub percentage: u8,
+    /// A halted release is not offered to any more clients
+    #[serde(default)]
+    pub halted: bool,
+}
+
+impl Rollout {
+    fn applies_to(&self, package: &str, version: &Version, channel: &str) -> bool {
+        self.package == package
+            && self.version == *version
+            && self.channel.as_deref().map_or(true, |it| it == channel)
+    }
 }
 
 impl IndexMetadata {
@@ -56,6 +124,35 @@ impl IndexMetadata {
         let required_version = &self.minimum_client_version;
         *required_version > client_version
     }
+
+    /// This checks whether the registry serves the index in the given layout.
+    pub fn serves_layout(&self, layout: IndexLayout) -> bool {
+        if self.layouts.is_empty() {
+            return layout == IndexLayout::V1;
+        }
+        self.layouts.contains(&layout)
+    }
+
+    /// This checks whether a release is offered to a client on the given channel.
+    ///
+    /// Clients are assigned a stable `bucket` from 0 to 99, a release in rollout
+    /// is offered to clients whose bucket is below the rollout percentage.
+    pub fn release_offered(
+        &self,
+        package: &str,
+        version: &Version,
+        channel: &str,
+        bucket: u8,
+    ) -> bool {
+        match self
+            .rollouts
+            .iter()
+            .find(|it| it.applies_to(package, version, channel))
+        {
+            Some(rollout) => !rollout.halted && bucket < rollout.percentage,
+            None => true,
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -64,3 +161,52 @@ pub struct FluvioIndex {
     #[serde(alias = "index")]
     pub metadata: IndexMetadata,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metadata() -> IndexMetadata {
+        serde_json::from_str(
+            r#"{
+              "minimum_client_version": "0.1.0",
+              "rollouts": [
+                { "package": "fluvio/fluvio", "version": "0.11.1", "percentage": 20 },
+                { "package": "fluvio/fluvio", "version": "0.11.2", "channel": "stable", "percentage": 100, "halted": true }
+              ]
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_release_offered_by_rollout() {
+        let metadata = metadata();
+        let version = |v| Version::parse(v).unwrap();
+
+        // releases without rollout are offered to everyone
+        assert!(metadata.release_offered("fluvio/fluvio", &version("0.11.0"), "stable", 99));
+        assert!(metadata.release_offered("fluvio/fluvio-cloud", &version("0.11.1"), "stable", 99));
+
+        assert!(metadata.release_offered("fluvio/fluvio", &version("0.11.1"), "stable", 19));
+        assert!(!metadata.release_offered("fluvio/fluvio", &version("0.11.1"), "stable", 20));
+
+        // halted only on stable channel
+        assert!(!metadata.release_offered("fluvio/fluvio", &version("0.11.2"), "stable", 0));
+        assert!(metadata.release_offered("fluvio/fluvio", &version("0.11.2"), "latest", 0));
+    }
+
+    #[test]
+    fn test_serves_layout() {
+        let metadata = metadata();
+        assert!(metadata.serves_layout(IndexLayout::V1));
+        assert!(!metadata.serves_layout(IndexLayout::V2));
+
+        let metadata: IndexMetadata = serde_json::from_str(
+            r#"{ "minimum_client_version": "0.1.0", "layouts": ["v1", "v2", "v3"] }"#,
+        )
+        .unwrap();
+        assert!(metadata.serves_layout(IndexLayout::V2));
+        assert!(metadata.serves_layout(IndexLayout::Unknown));
+    }
+}
@@ -0,0 +1,261 @@
+//! Mirroring of releases into self-hosted registries.
+//!
+//! A release is copied with all of its binaries and artifacts, each file is
+//! verified against its checksum before it is published to the destination.
+//! The destination must accept `PUT` of files at the same paths they are served from.
+
+use std::future::Future;
+
+use http::Request;
+use semver::Version;
+use tracing::debug;
+
+use crate::{Error, HttpAgent, PackageId, Registry, Release, Result, TargetDigest, sha256_hex};
+
+/// Moves files between registries, the index does not depend on a http client
+pub trait RegistryTransport {
+    /// Returns the body of a successful response, `Error::TransportError` otherwise
+    fn get(&self, request: Request<()>) -> impl Future<Output = Result<Vec<u8>>> + Send;
+
+    fn put(&self, request: Request<Vec<u8>>) -> impl Future<Output = Result<()>> + Send;
+}
+
+/// Copies a release of a package from `src_registry` to `dst_registry`.
+///
+/// The release is added to the destination package, which is created from the
+/// source package if it does not exist. Returns the mirrored release.
+pub async fn mirror_release<C: RegistryTransport, T>(
+    transport: &C,
+    src_registry: &Registry,
+    dst_registry: &Registry,
+    id: &PackageId<T>,
+    version: &Version,
+) -> Result<Release> {
+    let src = HttpAgent::with_base_url(src_registry.as_ref().as_str())?;
+    let dst = HttpAgent::with_base_url(dst_registry.as_ref().as_str())?;
+
+    let src_package = src
+        .package_from_response(&transport.get(src.request_package(id)?).await?)
+        .await?;
+    let mut release = src_package
+        .release(version)
+        .cloned()
+        .ok_or_else(|| Error::MissingRelease(version.clone()))?;
+
+    // digests are set on the release while iterating its targets
+    let targets = release.targets().to_vec();
+    for target in targets {
+        let binary = transport
+            .get(src.request_release_download(id, version, &target)?)
+            .await?;
+        let digest = match release.digest(&target) {
+            Some(digest) => {
+                release.verify(&target, &binary)?;
+                digest.clone()
+            }
+            None => {
+                let checksum = transport
+                    .get(src.request_release_checksum(id, version, &target)?)
+                    .await?;
+                verify_checksum(&binary, &checksum)?;
+                TargetDigest::new(target.clone(), &binary)
+            }
+        };
+        debug!(%target, sha256 = %digest.sha256, "verified release binary");
+
+        transport
+            .put(dst.request_put(&dst.request_release_download(id, version, &target)?, binary)?)
+            .await?;
+        // clients predating index checksums verify with checksum file
+        let checksum = digest.sha256.clone().into_bytes();
+        transport
+            .put(dst.request_put(
+                &dst.request_release_checksum(id, version, &target)?,
+                checksum,
+            )?)
+            .await?;
+        if let Some(signature) = &digest.signature {
+            let signature = signature.clone().into_bytes();
+            transport
+                .put(dst.request_put(
+                    &dst.request_release_signature(id, version, &target)?,
+                    signature,
+                )?)
+                .await?;
+        }
+        release.set_digest(digest);
+    }
+
+    for artifact in &release.artifacts {
+        let file = transport
+            .get(src.request_release_artifact(id, version, artifact)?)
+            .await?;
+        let checksum = transport
+            .get(src.request_release_artifact_checksum(id, version, artifact)?)
+            .await?;
+        verify_checksum(&file, &checksum)?;
+        debug!(artifact = %artifact.name, "verified release artifact");
+
+        transport
+            .put(dst.request_put(&dst.request_release_artifact(id, version, artifact)?, file)?)
+            .await?;
+        transport
+            .put(dst.request_put(
+                &dst.request_release_artifact_checksum(id, version, artifact)?,
+                checksum,
+            )?)
+            .await?;
+    }
+
+    let mut dst_package = match transport.get(dst.request_package(id)?).await {
+        Ok(response) => dst.package_from_response(&response).await?,
+        Err(err) => {
+            debug!(%err, "destination package not found, creating it");
+            let mut package = src_package.clone();
+            package.clear_releases();
+            package
+        }
+    };
+    dst_package.put_release(release.clone());
+    let body = serde_json::to_vec(&dst_package)?;
+    transport
+        .put(dst.request_put(&dst.request_package(id)?, body)?)
+        .await?;
+
+    Ok(release)
+}
+
+fn verify_checksum(bytes: &[u8], checksum: &[u8]) -> Result<()> {
+    let checksum = String::from_utf8_lossy(checksum);
+    if !checksum.trim().eq_ignore_ascii_case(&sha256_hex(bytes)) {
+        return Err(Error::ChecksumError);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    use crate::{Artifact, ArtifactKind, Package, Target};
+
+    use super::*;
+
+    #[derive(Default)]
+    struct MemoryTransport {
+        files: Mutex<HashMap<String, Vec<u8>>>,
+    }
+
+    impl MemoryTransport {
+        fn insert(&self, request: Result<Request<()>>, body: &[u8]) {
+            let uri = request.unwrap().uri().to_string();
+            self.files.lock().unwrap().insert(uri, body.to_vec());
+        }
+
+        fn file(&self, request: Result<Request<()>>) -> Option<Vec<u8>> {
+            let uri = request.unwrap().uri().to_string();
+            self.files.lock().unwrap().get(&uri).cloned()
+        }
+    }
+
+    impl RegistryTransport for MemoryTransport {
+        async fn get(&self, request: Request<()>) -> Result<Vec<u8>> {
+            let uri = request.uri().to_string();
+            self.files
+                .lock()
+                .unwrap()
+                .get(&uri)
+                .cloned()
+                .ok_or(Error::TransportError(format!("{uri} not found")))
+        }
+
+        async fn put(&self, request: Request<Vec<u8>>) -> Result<()> {
+            let uri = request.uri().to_string();
+            self.files.lock().unwrap().insert(uri, request.into_body());
+            Ok(())
+        }
+    }
+
+    #[fluvio_future::test]
+    async fn test_mirror_release() {
+        let src_registry: Registry = "https://packages.fluvio.io/v1/".parse().unwrap();
+        let dst_registry: Registry = "https://registry.example.com/fluvio/".parse().unwrap();
+        let src = HttpAgent::with_base_url(src_registry.as_ref().as_str()).unwrap();
+        let dst = HttpAgent::with_base_url(dst_registry.as_ref().as_str()).unwrap();
+
+        let id: PackageId = "fluvio/fluvio-cloud".parse().unwrap();
+        let version = Version::parse("0.2.0").unwrap();
+        let linux = Target::X86_64UnknownLinuxMusl;
+        let darwin = Target::X86_64AppleDarwin;
+
+        let mut package = Package::new_binary(&id, "Fluvio", "Cloud plugin", "");
+        package.add_release(version.clone(), linux.clone()).unwrap();
+        package
+            .add_release(version.clone(), darwin.clone())
+            .unwrap();
+        let mut release = package.release(&version).cloned().unwrap();
+        release.set_digest(TargetDigest::new(linux.clone(), b"linux binary"));
+        release.artifacts.push(Artifact {
+            name: "notes.md".to_string(),
+            kind: ArtifactKind::ReleaseNotes,
+            shell: None,
+        });
+        package.put_release(release.clone());
+
+        let transport = MemoryTransport::default();
+        transport.insert(
+            src.request_package(&id),
+            &serde_json::to_vec(&package).unwrap(),
+        );
+        transport.insert(
+            src.request_release_download(&id, &version, &linux),
+            b"linux binary",
+        );
+        transport.insert(
+            src.request_release_download(&id, &version, &darwin),
+            b"darwin binary",
+        );
+        transport.insert(
+            src.request_release_checksum(&id, &version, &darwin),
+            sha256_hex(b"darwin binary").as_bytes(),
+        );
+        let notes = &release.artifacts[0];
+        transport.insert(src.request_release_artifact(&id, &version, notes), b"notes");
+        transport.insert(
+            src.request_release_artifact_checksum(&id, &version, notes),
+            sha256_hex(b"notes").as_bytes(),
+        );
+
+        let mirrored = mirror_release(&transport, &src_registry, &dst_registry, &id, &version)
+            .await
+            .expect("mirror");
+        assert!(mirrored.digest(&darwin).is_some());
+        assert_eq!(
+            transport.file(dst.request_release_download(&id, &version, &darwin)),
+            Some(b"darwin binary".to_vec())
+        );
+        assert_eq!(
+            transport.file(dst.request_release_artifact(&id, &version, notes)),
+            Some(b"notes".to_vec())
+        );
+
+        let dst_package = dst
+            .package_from_response(&transport.file(dst.request_package(&id)).unwrap())
+            .await
+            .unwrap();
+        let dst_release = dst_package.release(&version).unwrap();
+        dst_release.verify(&linux, b"linux binary").unwrap();
+        dst_release.verify(&darwin, b"darwin binary").unwrap();
+
+        // tampered binary is not published
+        transport.insert(
+            src.request_release_download(&id, &version, &linux),
+            b"tampered",
+        );
+        let err = mirror_release(&transport, &src_registry, &dst_registry, &id, &version)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::ChecksumError));
+    }
+}
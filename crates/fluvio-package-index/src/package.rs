@@ -1,7 +1,10 @@
+use std::collections::BTreeMap;
+
 use tracing::debug;
 use serde::{Serialize, Deserialize};
-use semver::Version;
+use semver::{Version, VersionReq};
 use crate::{PackageName, GroupName, PackageId, Error, Result, Target, MaybeVersion};
+use crate::resolve::{Explanation, RejectReason, Rejection, ReleaseSelection};
 
 /// A `Package` represents a single published item in Fluvio's registry.
 ///
@@ -10,14 +13,20 @@ use crate::{PackageName, GroupName, PackageId, Error, Result, Target, MaybeVersi
 ///
 /// A package has a specified type, and all releases of that package must
 /// be the same type.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Package {
     /// The unique name of this package
     pub name: PackageName,
     /// The ID of the group that published the package
     pub group: GroupName,
     /// The type of package this is
+    ///
+    /// When the kind is migrated, this stays the original kind so that older
+    /// clients keep installing the releases published before the migration.
     pub kind: PackageKind,
+    /// Kind migrations of this package, ordered by version
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub kind_changes: Vec<KindChange>,
     /// The author of this package
     pub author: Option<String>,
     /// The human-readable description of this package
@@ -27,6 +36,9 @@ pub struct Package {
     /// The instances of this package that have been published
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     releases: Vec<Release>,
+    /// Release streams of this package, e.g. "stable" or "latest", by the version they point to
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub channels: BTreeMap<String, Version>,
 }
 
 impl Package {
@@ -43,41 +55,131 @@ impl Package {
             name: id.name().clone(),
             group: id.group().clone(),
             kind: PackageKind::Binary,
+            kind_changes: vec![],
             author: Some(author),
             description: Some(description),
             repository: Some(repository),
             releases: vec![],
+            channels: BTreeMap::new(),
         }
     }
 
-    /// Returns a reference to the latest release for this package
+    /// Returns a reference to the latest release for this package, which is not yanked
+    ///
+    /// Prereleases are not returned, use [`Package::select_release`] to include them.
     pub fn latest_release(&self) -> Result<&Release> {
         debug!(releases = ?&self.releases, "Finding latest release");
-        // Since releases are sorted upon insert, we just need to grab the last one
-        self.releases
-            .last()
-            .ok_or_else(|| Error::NoReleases(self.package_id().to_string()))
+        self.select_release(&ReleaseSelection::default())
     }
 
-    /// Returns a reference to the latest release with this target
+    /// Returns a reference to the latest release with this target, which is not yanked
     ///
     /// If `prerelease` is false, this will return only the latest release
     /// whose version does not include a prerelease tag.
     pub fn latest_release_for_target(&self, target: &Target, prerelease: bool) -> Result<&Release> {
-        self.releases
-            .iter()
-            .rev()
-            .find(|it| {
-                // If not in prerelease mode, do not keep prerelease or build meta
-                if !prerelease && (!it.version.pre.is_empty() || !it.version.build.is_empty()) {
-                    return false;
+        self.explain_latest_release_for_target(target, prerelease).0
+    }
+
+    /// Same as [`Package::latest_release_for_target`], also returning why newer releases were
+    /// not picked
+    pub fn explain_latest_release_for_target(
+        &self,
+        target: &Target,
+        prerelease: bool,
+    ) -> (Result<&Release>, Explanation) {
+        let selection = ReleaseSelection::default()
+            .for_target(target.clone())
+            .include_prerelease(prerelease);
+        self.explain_select_release(&selection)
+    }
+
+    /// Returns a reference to the latest release with this target whose version matches `req`,
+    /// which is not yanked
+    ///
+    /// Prerelease versions are matched only if `req` names a prerelease of the same version,
+    /// following semver rules.
+    ///
+    /// Fails with [`Error::NoReleases`] if no release is available, [`Error::NoReleaseInRange`]
+    /// if no release matches `req`, and [`Error::NoMatchingRelease`] if releases matching `req`
+    /// are not published for the target.
+    pub fn latest_release_matching(&self, target: &Target, req: &VersionReq) -> Result<&Release> {
+        self.explain_latest_release_matching(target, req).0
+    }
+
+    /// Same as [`Package::latest_release_matching`], also returning why newer releases were
+    /// not picked
+    pub fn explain_latest_release_matching(
+        &self,
+        target: &Target,
+        req: &VersionReq,
+    ) -> (Result<&Release>, Explanation) {
+        let selection = ReleaseSelection::default()
+            .for_target(target.clone())
+            .matching(req.clone());
+        self.explain_select_release(&selection)
+    }
+
+    /// Returns a reference to the latest release which is not yanked and passes the selection
+    pub fn select_release(&self, selection: &ReleaseSelection) -> Result<&Release> {
+        self.explain_select_release(selection).0
+    }
+
+    /// Same as [`Package::select_release`], also returning why newer releases were not picked
+    pub fn explain_select_release(
+        &self,
+        selection: &ReleaseSelection,
+    ) -> (Result<&Release>, Explanation) {
+        let mut explanation = Explanation::default();
+        for release in self.releases.iter().rev() {
+            match selection.reject(release) {
+                Some(reason) => explanation.rejections.push(Rejection {
+                    version: release.version.clone(),
+                    reason,
+                }),
+                None => {
+                    explanation.selected = Some(release.version.clone());
+                    return (Ok(release), explanation);
                 }
-                it.targets.contains(target)
-            })
-            .ok_or_else(|| Error::MissingTarget(target.clone()))
+            }
+        }
+
+        let missing_target = explanation
+            .rejections
+            .iter()
+            .any(|it| matches!(it.reason, RejectReason::MissingTarget(_)));
+        let err = match (selection.req(), selection.target()) {
+            _ if self.releases.iter().all(|it| it.yanked) => {
+                Error::NoReleases(self.package_id().to_string())
+            }
+            // target is checked last, the release matched otherwise
+            (Some(req), Some(target)) if missing_target => {
+                Error::NoMatchingRelease(req.clone(), target.clone())
+            }
+            (Some(req), _) => Error::NoReleaseInRange(req.clone()),
+            (None, Some(target)) => Error::MissingTarget(target.clone()),
+            (None, None) => Error::NoReleases(self.package_id().to_string()),
+        };
+        (Err(err), explanation)
+    }
+
+    /// Returns a reference to the latest release with this target which supports the CLI version
+    ///
+    /// If `prerelease` is false, releases whose version includes a prerelease tag are skipped.
+    pub fn latest_compatible_release(
+        &self,
+        target: &Target,
+        prerelease: bool,
+        cli_version: &Version,
+    ) -> Option<&Release> {
+        self.releases.iter().rev().find(|it| {
+            (prerelease || (it.version.pre.is_empty() && it.version.build.is_empty()))
+                && it.targets.contains(target)
+                && it.supports_cli(cli_version)
+        })
     }
 
-    fn package_id(&self) -> PackageId<MaybeVersion> {
+    /// Returns the unversioned ID of this package
+    pub fn package_id(&self) -> PackageId<MaybeVersion> {
         PackageId::new_unversioned(self.name.clone(), self.group.clone())
     }
 
@@ -103,6 +205,99 @@ impl Package {
         Ok(())
     }
 
+    /// Inserts a release, replacing the release with the same version
+    pub fn put_release(&mut self, release: Release) {
+        self.releases
+            .retain(|it| !version_exactly_eq(&it.version, &release.version));
+        self.releases.push(release);
+        self.releases.sort_by(|a, b| a.version.cmp(&b.version));
+    }
+
+    #[cfg(feature = "http_agent")]
+    pub(crate) fn clear_releases(&mut self) {
+        self.releases.clear();
+    }
+
+    /// Returns a reference to the release with exactly this version
+    pub fn release(&self, version: &Version) -> Option<&Release> {
+        self.releases
+            .iter()
+            .find(|it| version_exactly_eq(&it.version, version))
+    }
+
+    /// Returns the latest release of a meta-package, which is not yanked
+    ///
+    /// Meta-packages have no binaries, so releases are not filtered by target.
+    /// If `prerelease` is false, releases with a prerelease tag are skipped.
+    pub fn latest_meta_release(&self, prerelease: bool) -> Result<&Release> {
+        if self.kind != PackageKind::Meta {
+            return Err(Error::NotMetaPackage(self.package_id().to_string()));
+        }
+        self.releases
+            .iter()
+            .rev()
+            .find(|it| !it.yanked && (prerelease || it.version.pre.is_empty()))
+            .ok_or_else(|| Error::NoReleases(self.package_id().to_string()))
+    }
+
+    /// Points the channel to the version
+    pub fn set_channel(&mut self, channel: impl Into<String>, version: Version) {
+        self.channels.insert(channel.into(), version);
+    }
+
+    /// Returns the version the channel points to
+    pub fn channel_version(&self, channel: &str) -> Option<&Version> {
+        self.channels.get(channel)
+    }
+
+    /// Returns the release the channel points to, if it was published for the target
+    pub fn release_for_channel(&self, channel: &str, target: &Target) -> Result<&Release> {
+        let version = self
+            .channel_version(channel)
+            .ok_or_else(|| Error::MissingChannel(channel.to_string()))?;
+        let release = self
+            .release(version)
+            .ok_or_else(|| Error::MissingRelease(version.clone()))?;
+        if !release.target_exists(target) {
+            return Err(Error::MissingTarget(target.clone()));
+        }
+        Ok(release)
+    }
+
+    /// Returns the kind of the package at the given version, taking migrations into account
+    pub fn kind_of(&self, version: &Version) -> &PackageKind {
+        self.kind_changes
+            .iter()
+            .rev()
+            .find(|it| &it.since <= version)
+            .map(|it| &it.kind)
+            .unwrap_or(&self.kind)
+    }
+
+    /// Returns the kind of the release, or [`Error::UnsupportedKind`] if this client
+    /// doesn't know how to install it
+    pub fn release_kind(&self, release: &Release) -> Result<&PackageKind> {
+        let kind = self.kind_of(&release.version);
+        match kind {
+            PackageKind::Unknown(other) => Err(Error::UnsupportedKind(other.clone())),
+            _ => Ok(kind),
+        }
+    }
+
+    /// Returns how the release is installed, based on its kind
+    pub fn install_strategy(&self, release: &Release) -> Result<InstallStrategy> {
+        let kind = self.release_kind(release)?;
+        kind.install_strategy()
+            .ok_or_else(|| Error::UnsupportedKind(format!("{kind:?}")))
+    }
+
+    /// Migrates the package to a new kind, starting with the release with version `since`.
+    /// Releases before `since` keep their previous kind.
+    pub fn migrate_kind(&mut self, kind: PackageKind, since: Version) {
+        self.kind_changes.retain(|it| it.since < since);
+        self.kind_changes.push(KindChange { since, kind });
+    }
+
     pub fn releases_for_target(&self, target: &Target) -> Vec<&Release> {
         self.releases
             .iter()
@@ -115,6 +310,13 @@ fn version_exactly_eq(a: &Version, b: &Version) -> bool {
     a.eq(b) && a.build.eq(&b.build)
 }
 
+/// Records that releases of a package starting with version `since` are of `kind`
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct KindChange {
+    pub since: Version,
+    pub kind: PackageKind,
+}
+
 /// Packages have a `PackageKind`, which describes the contents being distributed.
 ///
 /// This is used by installers and updaters to determine what the installation
@@ -125,11 +327,46 @@ fn version_exactly_eq(a: &Version, b: &Version) -> bool {
 pub enum PackageKind {
     /// An executable binary package, "bin".
     Binary,
+    /// A SmartModule published to the registry, "smartmodule".
+    SmartModule,
+    /// A connector executable, "connector".
+    Connector,
+    /// A shared library, "lib".
+    Library,
+    /// A curated set of other packages, "meta". Its releases list the packages
+    /// to install rather than publish binaries.
+    Meta,
     /// Anything we don't recognize. This is here to prevent breaking changes
     /// if the registry adds new package kinds and not all clients are updated.
     Unknown(String),
 }
 
+impl PackageKind {
+    /// Returns how releases of this kind are installed, `None` if the kind is unknown
+    pub fn install_strategy(&self) -> Option<InstallStrategy> {
+        match self {
+            Self::Binary | Self::Connector => Some(InstallStrategy::Path),
+            Self::SmartModule => Some(InstallStrategy::WasmStore),
+            Self::Library => Some(InstallStrategy::LibDir),
+            Self::Meta => Some(InstallStrategy::Members),
+            Self::Unknown(_) => None,
+        }
+    }
+}
+
+/// Where the installer places the contents of a package
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InstallStrategy {
+    /// Executable placed in a directory on the PATH
+    Path,
+    /// Wasm module loaded into the SmartModule store of a cluster
+    WasmStore,
+    /// Library placed in the library directory
+    LibDir,
+    /// Nothing is placed, the member packages are installed instead
+    Members,
+}
+
 impl Serialize for PackageKind {
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
     where
@@ -137,6 +374,10 @@ impl Serialize for PackageKind {
     {
         let value = match self {
             Self::Binary => "bin".serialize(serializer)?,
+            Self::Meta => "meta".serialize(serializer)?,
+            Self::SmartModule => "smartmodule".serialize(serializer)?,
+            Self::Connector => "connector".serialize(serializer)?,
+            Self::Library => "lib".serialize(serializer)?,
             Self::Unknown(other) => other.serialize(serializer)?,
         };
         Ok(value)
@@ -151,12 +392,43 @@ impl<'de> Deserialize<'de> for PackageKind {
         let string = String::deserialize(deserializer)?;
         let kind = match &*string {
             "bin" => Self::Binary,
+            "meta" => Self::Meta,
+            "smartmodule" => Self::SmartModule,
+            "connector" => Self::Connector,
+            "lib" => Self::Library,
             _ => Self::Unknown(string),
         };
         Ok(kind)
     }
 }
 
+/// A `Group` lists the packages published by one publisher in Fluvio's registry.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Group {
+    /// The name of the group
+    pub name: GroupName,
+    /// The packages published in this group
+    #[serde(default)]
+    pub packages: Vec<Package>,
+}
+
+impl Group {
+    /// Returns the packages whose name or description contains `query`, ignoring case
+    pub fn search(&self, query: &str) -> Vec<&Package> {
+        let query = query.to_lowercase();
+        self.packages
+            .iter()
+            .filter(|package| {
+                package.name.as_str().to_lowercase().contains(&query)
+                    || package
+                        .description
+                        .as_ref()
+                        .is_some_and(|it| it.to_lowercase().contains(&query))
+            })
+            .collect()
+    }
+}
+
 /// A `Release` is a specific version of a published item in Fluvio's registry.
 #[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub struct Release {
@@ -167,6 +439,18 @@ pub struct Release {
     pub yanked: bool,
     /// The targets that have published releases with this version
     targets: Vec<Target>,
+    /// Checksums and signatures of the binaries published for each target
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub digests: Vec<TargetDigest>,
+    /// Additional files published with this version, shared by all targets
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub artifacts: Vec<Artifact>,
+    /// The Fluvio CLI versions this release works with, any version if not given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fluvio_cli: Option<VersionReq>,
+    /// The packages installed by this release of a meta-package
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub packages: Vec<MetaMember>,
 }
 
 impl Release {
@@ -175,6 +459,10 @@ impl Release {
             version,
             yanked: false,
             targets: vec![target],
+            digests: vec![],
+            artifacts: vec![],
+            fluvio_cli: None,
+            packages: vec![],
         }
     }
 
@@ -189,6 +477,128 @@ impl Release {
     pub fn target_exists(&self, target: &Target) -> bool {
         self.targets.iter().any(|it| it == target)
     }
+
+    /// Returns the targets that have published binaries with this release
+    pub fn targets(&self) -> &[Target] {
+        &self.targets
+    }
+
+    /// Removes a target from this release, e.g. to drop a broken artifact.
+    /// Returns false if the target did not exist
+    pub fn remove_target(&mut self, target: Target) -> bool {
+        let count = self.targets.len();
+        self.targets.retain(|it| it != &target);
+        self.digests.retain(|it| it.target != target);
+        self.targets.len() != count
+    }
+
+    /// Returns the checksum and signature of the binary published for the target
+    pub fn digest(&self, target: &Target) -> Option<&TargetDigest> {
+        self.digests.iter().find(|it| &it.target == target)
+    }
+
+    /// Records the checksum and signature of the binary published for a target,
+    /// replacing the previous one
+    pub fn set_digest(&mut self, digest: TargetDigest) {
+        self.digests.retain(|it| it.target != digest.target);
+        self.digests.push(digest);
+    }
+
+    /// Verifies the binary downloaded for the target against its checksum in the index
+    pub fn verify(&self, target: &Target, bytes: &[u8]) -> Result<()> {
+        let digest = self
+            .digest(target)
+            .ok_or_else(|| Error::MissingChecksum(target.clone()))?;
+        if !digest.sha256.eq_ignore_ascii_case(&sha256_hex(bytes)) {
+            return Err(Error::ChecksumError);
+        }
+        Ok(())
+    }
+
+    /// Returns true if this release works with the given Fluvio CLI version
+    ///
+    /// Prerelease and build tags of the CLI version are ignored, so development
+    /// builds are compatible with the releases their version is.
+    pub fn supports_cli(&self, cli_version: &Version) -> bool {
+        let Some(req) = &self.fluvio_cli else {
+            return true;
+        };
+        let release = Version::new(cli_version.major, cli_version.minor, cli_version.patch);
+        req.matches(&release)
+    }
+
+    /// Returns the completion script artifact for the given shell, if published
+    pub fn completion(&self, shell: &str) -> Option<&Artifact> {
+        self.artifacts
+            .iter()
+            .find(|it| it.kind == ArtifactKind::Completion && it.shell.as_deref() == Some(shell))
+    }
+
+    /// Returns the release notes artifact, if published
+    pub fn release_notes(&self) -> Option<&Artifact> {
+        self.artifacts
+            .iter()
+            .find(|it| it.kind == ArtifactKind::ReleaseNotes)
+    }
+}
+
+/// The checksum and signature of a binary published for one target
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TargetDigest {
+    pub target: Target,
+    /// Hex encoded SHA-256 of the binary
+    pub sha256: String,
+    /// Hex encoded detached signature of the binary by the package group
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+}
+
+impl TargetDigest {
+    /// Digest of the binary, without signature
+    pub fn new(target: Target, bytes: &[u8]) -> Self {
+        Self {
+            target,
+            sha256: sha256_hex(bytes),
+            signature: None,
+        }
+    }
+}
+
+/// Returns the hex encoded SHA-256 of the bytes
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    use sha2::Digest as _;
+    hex::encode(sha2::Sha256::digest(bytes))
+}
+
+/// A package installed by a meta-package release
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct MetaMember {
+    /// The ID of the package, e.g. `fluvio/fluvio-cloud`
+    pub id: PackageId<MaybeVersion>,
+    /// The versions of the package to install from, latest release if not given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<VersionReq>,
+}
+
+/// An `Artifact` is a file published with a release in addition to its binaries.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Artifact {
+    /// The file name of the artifact within the release
+    pub name: String,
+    pub kind: ArtifactKind,
+    /// The shell a completion script is for, e.g. `bash`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shell: Option<String>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArtifactKind {
+    Completion,
+    /// Markdown notes describing the changes of the release
+    ReleaseNotes,
+    #[serde(other)]
+    Unknown,
 }
 
 #[cfg(test)]
@@ -200,6 +610,7 @@ mod tests {
             name: "my-package".parse().unwrap(),
             group: "my-group".parse().unwrap(),
             kind: PackageKind::Binary,
+            kind_changes: vec![],
             author: None,
             description: None,
             repository: None,
@@ -208,23 +619,40 @@ mod tests {
                     version: Version::parse("0.1.0-alpha.1").unwrap(),
                     yanked: false,
                     targets: vec![Target::X86_64AppleDarwin],
+                    digests: vec![],
+                    artifacts: vec![],
+                    fluvio_cli: None,
+                    packages: vec![],
                 },
                 Release {
                     version: Version::parse("0.1.0").unwrap(),
                     yanked: false,
                     targets: vec![Target::X86_64AppleDarwin],
+                    digests: vec![],
+                    artifacts: vec![],
+                    fluvio_cli: None,
+                    packages: vec![],
                 },
                 Release {
                     version: Version::parse("0.2.0-alpha.1").unwrap(),
                     yanked: false,
                     targets: vec![Target::X86_64AppleDarwin],
+                    digests: vec![],
+                    artifacts: vec![],
+                    fluvio_cli: None,
+                    packages: vec![],
                 },
                 Release {
                     version: Version::parse("0.2.0-alpha.2").unwrap(),
                     yanked: false,
                     targets: vec![Target::X86_64AppleDarwin],
+                    digests: vec![],
+                    artifacts: vec![],
+                    fluvio_cli: None,
+                    packages: vec![],
                 },
             ],
+            channels: BTreeMap::new(),
         }
     }
 
@@ -279,6 +707,135 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_deserialize_release_artifacts() {
+        let json = r#"{
+          "version": "0.2.0",
+          "yanked": false,
+          "targets": ["x86_64-unknown-linux-musl"],
+          "artifacts": [
+            { "name": "fluvio-cloud.bash", "kind": "completion", "shell": "bash" },
+            { "name": "fluvio-cloud.1", "kind": "manpage" },
+            { "name": "CHANGELOG.md", "kind": "release_notes" }
+          ]
+        }"#;
+        let release: Release = serde_json::from_str(json).unwrap();
+        assert_eq!(release.artifacts.len(), 3);
+        assert_eq!(release.artifacts[1].kind, ArtifactKind::Unknown);
+        assert_eq!(
+            release.completion("bash").map(|it| it.name.as_str()),
+            Some("fluvio-cloud.bash")
+        );
+        assert!(release.completion("zsh").is_none());
+        assert_eq!(
+            release.release_notes().map(|it| it.name.as_str()),
+            Some("CHANGELOG.md")
+        );
+
+        // releases published before artifacts have none
+        let release: Release =
+            serde_json::from_str(r#"{"version": "0.1.0", "yanked": false, "targets": []}"#)
+                .unwrap();
+        assert!(release.artifacts.is_empty());
+    }
+
+    #[test]
+    fn test_release_remove_target() {
+        let mut release = Release::new(Version::parse("0.1.0").unwrap(), Target::X86_64AppleDarwin);
+        release.add_target(Target::X86_64UnknownLinuxMusl);
+        assert_eq!(
+            release.targets(),
+            &[Target::X86_64AppleDarwin, Target::X86_64UnknownLinuxMusl]
+        );
+
+        assert!(release.remove_target(Target::X86_64AppleDarwin));
+        assert!(!release.remove_target(Target::X86_64AppleDarwin));
+        assert_eq!(release.targets(), &[Target::X86_64UnknownLinuxMusl]);
+        assert!(!release.target_exists(&Target::X86_64AppleDarwin));
+    }
+
+    #[test]
+    fn test_release_verify_digest() {
+        let target = Target::X86_64UnknownLinuxMusl;
+        let mut release = Release::new(Version::parse("0.1.0").unwrap(), target.clone());
+        assert!(matches!(
+            release.verify(&target, b"binary"),
+            Err(Error::MissingChecksum(_))
+        ));
+
+        release.set_digest(TargetDigest::new(target.clone(), b"binary"));
+        release.verify(&target, b"binary").unwrap();
+        assert!(matches!(
+            release.verify(&target, b"binar"),
+            Err(Error::ChecksumError)
+        ));
+
+        let json = serde_json::to_string(&release).unwrap();
+        let release: Release = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            release.digest(&target).map(|it| it.sha256.as_str()),
+            Some("9a3a45d01531a20e89ac6ae10b0b0beb0492acd7216a368aa062d1a5fecaf9cd")
+        );
+    }
+
+    #[test]
+    fn test_release_for_channel() {
+        let mut package = test_package();
+        let target = Target::X86_64AppleDarwin;
+        package.set_channel("stable", Version::parse("0.1.0").unwrap());
+        package.set_channel("latest", Version::parse("0.2.0-alpha.2").unwrap());
+
+        let json = serde_json::to_string(&package).unwrap();
+        let package: Package = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            package
+                .release_for_channel("stable", &target)
+                .unwrap()
+                .version,
+            Version::parse("0.1.0").unwrap()
+        );
+        assert_eq!(
+            package
+                .release_for_channel("latest", &target)
+                .unwrap()
+                .version,
+            Version::parse("0.2.0-alpha.2").unwrap()
+        );
+        assert!(matches!(
+            package.release_for_channel("dev", &target),
+            Err(Error::MissingChannel(_))
+        ));
+        assert!(matches!(
+            package.release_for_channel("stable", &Target::X86_64UnknownLinuxMusl),
+            Err(Error::MissingTarget(_))
+        ));
+    }
+
+    #[test]
+    fn test_search_group() {
+        let json = r#"{
+          "name": "fluvio",
+          "packages": [
+            { "name": "fluvio-cloud", "group": "fluvio", "kind": "bin", "description": "Manage InfinyOn Cloud" },
+            { "name": "cdk", "group": "fluvio", "kind": "bin", "description": "Connector Development Kit" },
+            { "name": "smdk", "group": "fluvio", "kind": "bin" }
+          ]
+        }"#;
+        let group: Group = serde_json::from_str(json).unwrap();
+        assert_eq!(group.packages.len(), 3);
+
+        let names = |packages: Vec<&Package>| {
+            packages
+                .into_iter()
+                .map(|it| it.name.to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names(group.search("cloud")), vec!["fluvio-cloud"]);
+        assert_eq!(names(group.search("CONNECTOR")), vec!["cdk"]);
+        assert_eq!(names(group.search("")).len(), 3);
+        assert!(group.search("missing").is_empty());
+    }
+
     #[test]
     fn test_deserialize_package_unknown_kind() {
         let json = r#"{
@@ -312,6 +869,251 @@ mod tests {
         assert_eq!(release.version, Version::parse("0.1.0").unwrap());
     }
 
+    #[test]
+    fn test_get_latest_release_matching() {
+        let package = test_package();
+        let release = package
+            .latest_release_matching(
+                &Target::X86_64AppleDarwin,
+                &VersionReq::parse("^0.1").unwrap(),
+            )
+            .unwrap();
+        assert_eq!(release.version, Version::parse("0.1.0").unwrap());
+
+        let release = package
+            .latest_release_matching(
+                &Target::X86_64AppleDarwin,
+                &VersionReq::parse(">=0.2.0-alpha.1").unwrap(),
+            )
+            .unwrap();
+        assert_eq!(release.version, Version::parse("0.2.0-alpha.2").unwrap());
+
+        assert!(matches!(
+            package.latest_release_matching(
+                &Target::X86_64AppleDarwin,
+                &VersionReq::parse("^0.3").unwrap()
+            ),
+            Err(Error::NoReleaseInRange(_))
+        ));
+        assert!(matches!(
+            package.latest_release_matching(
+                &Target::X86_64UnknownLinuxMusl,
+                &VersionReq::parse("^0.1").unwrap()
+            ),
+            Err(Error::NoMatchingRelease(_, _))
+        ));
+
+        // yanked releases are not available
+        let mut package = package;
+        package.releases[1].yanked = true;
+        assert!(matches!(
+            package.latest_release_matching(
+                &Target::X86_64AppleDarwin,
+                &VersionReq::parse("=0.1.0").unwrap()
+            ),
+            Err(Error::NoReleaseInRange(_))
+        ));
+        package.releases.iter_mut().for_each(|it| it.yanked = true);
+        assert!(matches!(
+            package.latest_release_matching(
+                &Target::X86_64AppleDarwin,
+                &VersionReq::parse("^0.1").unwrap()
+            ),
+            Err(Error::NoReleases(_))
+        ));
+    }
+
+    #[test]
+    fn test_select_release() {
+        let package = test_package();
+        let release = package.latest_release().unwrap();
+        assert_eq!(release.version, Version::parse("0.1.0").unwrap());
+
+        let selection = ReleaseSelection::default().include_prerelease(true);
+        let release = package.select_release(&selection).unwrap();
+        assert_eq!(release.version, Version::parse("0.2.0-alpha.2").unwrap());
+
+        let req = VersionReq::parse("^0.2").unwrap();
+        let selection = ReleaseSelection::default().matching(req.clone());
+        assert!(matches!(
+            package.select_release(&selection),
+            Err(Error::NoReleaseInRange(_))
+        ));
+        let release = package
+            .select_release(&selection.include_prerelease(true))
+            .unwrap();
+        assert_eq!(release.version, Version::parse("0.2.0-alpha.2").unwrap());
+
+        let selection = ReleaseSelection::default()
+            .for_target(Target::X86_64UnknownLinuxMusl)
+            .include_prerelease(true);
+        assert!(matches!(
+            package.select_release(&selection),
+            Err(Error::MissingTarget(_))
+        ));
+    }
+
+    #[test]
+    fn test_explain_latest_release() {
+        let mut package = test_package();
+        package.releases[1].yanked = true;
+        package.releases[2].targets = vec![Target::X86_64UnknownLinuxMusl];
+
+        let (release, explanation) =
+            package.explain_latest_release_for_target(&Target::X86_64AppleDarwin, false);
+        assert!(matches!(release, Err(Error::MissingTarget(_))));
+        assert_eq!(
+            explanation.rejections[0],
+            Rejection {
+                version: Version::parse("0.2.0-alpha.2").unwrap(),
+                reason: RejectReason::Prerelease,
+            }
+        );
+        assert_eq!(
+            explanation.rejections[2].reason,
+            RejectReason::Yanked,
+            "{explanation}"
+        );
+        assert_eq!(explanation.selected, None);
+
+        let (release, explanation) = package.explain_latest_release_matching(
+            &Target::X86_64AppleDarwin,
+            &VersionReq::parse(">=0.1.0-alpha.1, <0.2.0-alpha.2").unwrap(),
+        );
+        assert_eq!(
+            release.unwrap().version,
+            Version::parse("0.1.0-alpha.1").unwrap()
+        );
+        let reasons: Vec<_> = explanation
+            .rejections
+            .iter()
+            .map(|it| it.reason.to_string())
+            .collect();
+        assert_eq!(
+            reasons,
+            vec![
+                "does not match >=0.1.0-alpha.1, <0.2.0-alpha.2",
+                "not published for x86_64-apple-darwin",
+                "yanked",
+            ]
+        );
+        assert_eq!(
+            explanation.selected,
+            Some(Version::parse("0.1.0-alpha.1").unwrap())
+        );
+    }
+
+    #[test]
+    fn test_latest_compatible_release() {
+        let mut package = test_package();
+        package.releases[1].fluvio_cli = Some(VersionReq::parse(">=0.11, <0.12").unwrap());
+        package.releases[3].fluvio_cli = Some(VersionReq::parse(">=0.12").unwrap());
+
+        let cli = Version::parse("0.11.5").unwrap();
+        assert!(package.releases[1].supports_cli(&cli));
+        assert!(!package.releases[3].supports_cli(&cli));
+        // development builds are compatible with releases of their version
+        assert!(package.releases[3].supports_cli(&Version::parse("0.12.0-dev.1").unwrap()));
+
+        let release = package
+            .latest_compatible_release(&Target::X86_64AppleDarwin, true, &cli)
+            .unwrap();
+        assert_eq!(release.version, Version::parse("0.2.0-alpha.1").unwrap());
+        let release = package
+            .latest_compatible_release(&Target::X86_64AppleDarwin, false, &cli)
+            .unwrap();
+        assert_eq!(release.version, Version::parse("0.1.0").unwrap());
+        assert!(package
+            .latest_compatible_release(
+                &Target::X86_64AppleDarwin,
+                false,
+                &Version::parse("0.10.0").unwrap()
+            )
+            .is_none());
+    }
+
+    #[test]
+    fn test_latest_meta_release() {
+        let json = r#"{
+          "name": "fluvio-default-plugins",
+          "group": "fluvio",
+          "kind": "meta",
+          "releases": [
+            {
+              "version": "0.1.0",
+              "yanked": false,
+              "targets": [],
+              "packages": [{ "id": "fluvio/fluvio-cloud" }]
+            },
+            {
+              "version": "0.2.0",
+              "yanked": false,
+              "targets": [],
+              "packages": [
+                { "id": "fluvio/fluvio-cloud", "version": "^0.2" },
+                { "id": "fluvio/cdk" }
+              ]
+            },
+            { "version": "0.3.0-alpha.1", "yanked": false, "targets": [] }
+          ]
+        }"#;
+        let package: Package = serde_json::from_str(json).unwrap();
+        assert_eq!(package.kind, PackageKind::Meta);
+
+        let release = package.latest_meta_release(false).unwrap();
+        assert_eq!(release.version, Version::parse("0.2.0").unwrap());
+        assert_eq!(release.packages.len(), 2);
+        assert_eq!(
+            release.packages[0].id,
+            "fluvio/fluvio-cloud".parse().unwrap()
+        );
+        assert_eq!(
+            release.packages[0].version,
+            Some(VersionReq::parse("^0.2").unwrap())
+        );
+        assert!(release.packages[1].version.is_none());
+        assert_eq!(
+            package.latest_meta_release(true).unwrap().version,
+            Version::parse("0.3.0-alpha.1").unwrap()
+        );
+
+        assert!(matches!(
+            test_package().latest_meta_release(false),
+            Err(Error::NotMetaPackage(_))
+        ));
+    }
+
+    #[test]
+    fn test_migrate_package_kind() {
+        let mut package = test_package();
+        let before = Version::parse("0.1.0").unwrap();
+        let after = Version::parse("0.2.0-alpha.1").unwrap();
+        package.migrate_kind(PackageKind::SmartModule, after.clone());
+
+        assert_eq!(package.kind, PackageKind::Binary);
+        assert_eq!(package.kind_of(&before), &PackageKind::Binary);
+        assert_eq!(package.kind_of(&after), &PackageKind::SmartModule);
+        assert_eq!(
+            package.release_kind(&package.releases[3]).unwrap(),
+            &PackageKind::SmartModule
+        );
+
+        // older clients see original kind and ignore migrations
+        let json = serde_json::to_string(&package).unwrap();
+        assert!(json.contains(r#""kind":"bin""#));
+        let package: Package = serde_json::from_str(&json).unwrap();
+        assert_eq!(package.kind_of(&after), &PackageKind::SmartModule);
+
+        // kinds unknown to this client surface as typed error
+        let mut package = package;
+        package.migrate_kind(PackageKind::Unknown("wasm-component".to_owned()), after);
+        assert!(matches!(
+            package.release_kind(&package.releases[3]),
+            Err(Error::UnsupportedKind(kind)) if kind == "wasm-component"
+        ));
+        assert!(package.release_kind(&package.releases[1]).is_ok());
+    }
+
     #[test]
     fn test_deserialize_package_kind_bin() {
         let name = "\"bin\"";
@@ -331,5 +1133,29 @@ mod tests {
         let name = "\"wasm\"";
         let kind: PackageKind = serde_json::from_str(name).unwrap();
         assert_eq!(kind, PackageKind::Unknown("wasm".to_string()));
+        assert_eq!(kind.install_strategy(), None);
+    }
+
+    #[test]
+    fn test_package_kind_install_strategy() {
+        let kinds = [
+            (
+                "\"connector\"",
+                PackageKind::Connector,
+                InstallStrategy::Path,
+            ),
+            ("\"lib\"", PackageKind::Library, InstallStrategy::LibDir),
+            (
+                "\"smartmodule\"",
+                PackageKind::SmartModule,
+                InstallStrategy::WasmStore,
+            ),
+        ];
+        for (name, expected, strategy) in kinds {
+            let kind: PackageKind = serde_json::from_str(name).unwrap();
+            assert_eq!(kind, expected);
+            assert_eq!(serde_json::to_string(&kind).unwrap(), name);
+            assert_eq!(kind.install_strategy(), Some(strategy));
+        }
     }
 }
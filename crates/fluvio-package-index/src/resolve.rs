@@ -0,0 +1,128 @@
+//! Release selection and explanations of version resolution.
+//!
+//! Resolution walks releases from newest to oldest and picks the first one that
+//! passes every check. The explain variants of the resolution APIs also return
+//! why each newer release was passed over.
+
+use std::fmt;
+
+use semver::{Version, VersionReq};
+
+use crate::{Release, Target};
+
+/// Criteria a release must meet to be selected, besides not being yanked
+///
+/// Prereleases are excluded unless included with [`ReleaseSelection::include_prerelease`].
+/// A version requirement matches prereleases it names, following semver rules, or any
+/// prerelease of a matching version if prereleases are included.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReleaseSelection {
+    prerelease: bool,
+    target: Option<Target>,
+    req: Option<VersionReq>,
+}
+
+impl ReleaseSelection {
+    pub fn include_prerelease(mut self, prerelease: bool) -> Self {
+        self.prerelease = prerelease;
+        self
+    }
+
+    /// Select only releases published for the target
+    pub fn for_target(mut self, target: Target) -> Self {
+        self.target = Some(target);
+        self
+    }
+
+    /// Select only releases whose version matches the requirement
+    pub fn matching(mut self, req: VersionReq) -> Self {
+        self.req = Some(req);
+        self
+    }
+
+    pub fn target(&self) -> Option<&Target> {
+        self.target.as_ref()
+    }
+
+    pub fn req(&self) -> Option<&VersionReq> {
+        self.req.as_ref()
+    }
+
+    /// Returns why the release is not selected, `None` if it is
+    pub(crate) fn reject(&self, release: &Release) -> Option<RejectReason> {
+        if release.yanked {
+            return Some(RejectReason::Yanked);
+        }
+
+        let version = &release.version;
+        let is_prerelease = !version.pre.is_empty() || !version.build.is_empty();
+        match &self.req {
+            Some(req) => {
+                let base = Version::new(version.major, version.minor, version.patch);
+                let included = self.prerelease && is_prerelease && req.matches(&base);
+                if !req.matches(version) && !included {
+                    return Some(RejectReason::NotMatching(req.clone()));
+                }
+            }
+            None if is_prerelease && !self.prerelease => return Some(RejectReason::Prerelease),
+            None => {}
+        }
+
+        match &self.target {
+            Some(target) if !release.target_exists(target) => {
+                Some(RejectReason::MissingTarget(target.clone()))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Why a release was not picked
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RejectReason {
+    Yanked,
+    /// Release has a prerelease tag or build metadata, and prereleases were not requested
+    Prerelease,
+    /// Release version does not satisfy the requirement
+    NotMatching(VersionReq),
+    /// Release was not published for the target
+    MissingTarget(Target),
+}
+
+impl fmt::Display for RejectReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Yanked => write!(f, "yanked"),
+            Self::Prerelease => write!(f, "prerelease"),
+            Self::NotMatching(req) => write!(f, "does not match {req}"),
+            Self::MissingTarget(target) => write!(f, "not published for {target}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rejection {
+    pub version: Version,
+    pub reason: RejectReason,
+}
+
+/// Trace of a resolution, newest release first
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Explanation {
+    /// Releases newer than the selected one, and why they were passed over
+    pub rejections: Vec<Rejection>,
+    /// Version picked by resolution, if any
+    pub selected: Option<Version>,
+}
+
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for rejection in &self.rejections {
+            writeln!(f, "{}: {}", rejection.version, rejection.reason)?;
+        }
+        match &self.selected {
+            Some(version) => write!(f, "{version}: selected"),
+            None => write!(f, "no release selected"),
+        }
+    }
+}
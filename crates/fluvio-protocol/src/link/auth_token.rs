@@ -0,0 +1,49 @@
+use crate::{Encoder, Decoder};
+use crate::api::Request;
+
+use super::ErrorCode;
+
+/// Key of token request, it's the first request of connection when server requires tokens
+pub const AUTH_TOKEN_API_KEY: u16 = 9;
+
+// -----------------------------------
+// AuthTokenRequest
+// -----------------------------------
+
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct AuthTokenRequest {
+    /// bearer token, e.g. JWT issued by identity provider
+    pub token: String,
+}
+
+impl AuthTokenRequest {
+    pub fn new(token: impl Into<String>) -> Self {
+        Self {
+            token: token.into(),
+        }
+    }
+}
+
+impl Request for AuthTokenRequest {
+    const API_KEY: u16 = AUTH_TOKEN_API_KEY;
+    type Response = AuthTokenResponse;
+}
+
+// -----------------------------------
+// AuthTokenResponse
+// -----------------------------------
+
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct AuthTokenResponse {
+    pub error_code: ErrorCode,
+    pub error_message: Option<String>,
+}
+
+impl AuthTokenResponse {
+    pub fn denied(msg: impl Into<String>) -> Self {
+        Self {
+            error_code: ErrorCode::PermissionDenied,
+            error_message: Some(msg.into()),
+        }
+    }
+}
@@ -0,0 +1,154 @@
+//! Capabilities exchanged by peers on connection setup.
+//!
+//! Each side advertises its capabilities in `ApiVersions` request and response,
+//! feature is used only when both sides have it.
+
+use std::fmt;
+use std::io::Error as IoError;
+
+use crate::bytes::{Buf, BufMut};
+use crate::{Decoder, Encoder, Version};
+
+/// Registry of negotiated features.
+/// Discriminant is bit position in wire format, never reuse or reorder them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum Capability {
+    /// stream fetch applies chain of SmartModules
+    SmartModuleChain = 0,
+    /// consumer offsets are stored by SPU
+    OffsetManagement = 1,
+    /// stream fetch can be served by follower
+    ReadFromFollower = 2,
+    /// records failing SmartModule are sent to dead letter topic
+    DeadLetterTopic = 3,
+    /// records carry broker append timestamps
+    BrokerTimestamps = 4,
+}
+
+impl Capability {
+    /// all capabilities known to this build
+    pub const ALL: &'static [Capability] = &[
+        Capability::SmartModuleChain,
+        Capability::OffsetManagement,
+        Capability::ReadFromFollower,
+        Capability::DeadLetterTopic,
+        Capability::BrokerTimestamps,
+    ];
+
+    fn bit(self) -> u64 {
+        1 << self as u8
+    }
+}
+
+/// Bitmap of capabilities
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Capabilities(u64);
+
+impl Capabilities {
+    pub const NONE: Self = Self(0);
+
+    /// capabilities supported by this build
+    pub fn supported() -> Self {
+        Capability::ALL
+            .iter()
+            .fold(Self::NONE, |caps, capability| caps.with(*capability))
+    }
+
+    pub fn with(self, capability: Capability) -> Self {
+        Self(self.0 | capability.bit())
+    }
+
+    pub fn contains(&self, capability: Capability) -> bool {
+        self.0 & capability.bit() != 0
+    }
+
+    /// capabilities present on both sides
+    pub fn intersect(self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Capability> + '_ {
+        Capability::ALL
+            .iter()
+            .copied()
+            .filter(|capability| self.contains(*capability))
+    }
+}
+
+impl fmt::Debug for Capabilities {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl Encoder for Capabilities {
+    fn write_size(&self, version: Version) -> usize {
+        self.0.write_size(version)
+    }
+
+    fn encode<T>(&self, dest: &mut T, version: Version) -> Result<(), IoError>
+    where
+        T: BufMut,
+    {
+        self.0.encode(dest, version)
+    }
+}
+
+impl Decoder for Capabilities {
+    fn decode<T>(&mut self, src: &mut T, version: Version) -> Result<(), IoError>
+    where
+        T: Buf,
+    {
+        // peers which predate capabilities reply with older response layout
+        if src.remaining() < self.0.write_size(version) {
+            self.0 = 0;
+            return Ok(());
+        }
+        self.0.decode(src, version)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capabilities_negotiation() {
+        let local = Capabilities::supported();
+        let remote = Capabilities::NONE
+            .with(Capability::SmartModuleChain)
+            .with(Capability::BrokerTimestamps);
+
+        let negotiated = local.intersect(remote);
+        assert!(negotiated.contains(Capability::SmartModuleChain));
+        assert!(negotiated.contains(Capability::BrokerTimestamps));
+        assert!(!negotiated.contains(Capability::ReadFromFollower));
+        assert_eq!(negotiated.iter().count(), 2);
+    }
+
+    #[test]
+    fn test_capabilities_encoding() {
+        let caps = Capabilities::NONE.with(Capability::DeadLetterTopic);
+        let mut dest = vec![];
+        caps.encode(&mut dest, 0).expect("encode");
+        assert_eq!(dest.len(), 8);
+
+        let mut decoded = Capabilities::default();
+        decoded
+            .decode(&mut std::io::Cursor::new(&dest), 0)
+            .expect("decode");
+        assert_eq!(decoded, caps);
+
+        // missing field decodes as no capabilities
+        let mut legacy = Capabilities::supported();
+        legacy
+            .decode(&mut std::io::Cursor::new(Vec::<u8>::new()), 0)
+            .expect("decode");
+        assert!(legacy.is_empty());
+    }
+}
@@ -119,6 +119,90 @@ pub enum ErrorCode {
     #[fluvio(tag = 3004)]
     #[error("the offset management is disabled for the stream")]
     OffsetManagementDisabled,
+    #[fluvio(tag = 3005)]
+    #[error("producer {producer_id} with epoch {epoch} is fenced by epoch {current_epoch}")]
+    ProducerFenced {
+        producer_id: i64,
+        epoch: i16,
+        current_epoch: i16,
+    },
+    #[fluvio(tag = 3006)]
+    #[error("producer {producer_id} sent sequence {received}, expected {expected}")]
+    OutOfOrderSequence {
+        producer_id: i64,
+        expected: i32,
+        received: i32,
+    },
+
+    // Consumer group errors
+    #[fluvio(tag = 3007)]
+    #[error("member {member_id} is not part of consumer group {group_id}")]
+    ConsumerGroupUnknownMember { group_id: String, member_id: String },
+    #[fluvio(tag = 3008)]
+    #[error("consumer group {group_id} already consumes topic {topic}")]
+    ConsumerGroupTopicMismatch { group_id: String, topic: String },
+
+    // Partition reassignment errors
+    #[fluvio(tag = 3009)]
+    #[error("the partition reassignment is in progress")]
+    PartitionReassignmentInProgress,
+
+    // Transaction errors
+    #[fluvio(tag = 3010)]
+    #[error("transaction {transactional_id} is not in progress")]
+    TransactionNotInProgress { transactional_id: String },
+    #[fluvio(tag = 3011)]
+    #[error("transaction {transactional_id} is already being ended")]
+    TransactionEnding { transactional_id: String },
+
+    // Fetch session errors
+    #[fluvio(tag = 3012)]
+    #[error("fetch session {session_id} expected epoch {expected}, received {received}")]
+    FetchSessionEpochMismatch {
+        session_id: i32,
+        expected: i32,
+        received: i32,
+    },
+
+    // SPU memory errors
+    #[fluvio(tag = 3013)]
+    #[error("SPU memory budget exceeded, requested {requested} bytes")]
+    SpuMemoryBudgetExceeded { requested: u64 },
+
+    // Read-only partition errors
+    #[fluvio(tag = 3014)]
+    #[error("the partition is read-only")]
+    PartitionReadOnly,
+
+    // Min in-sync replicas errors
+    #[fluvio(tag = 3015)]
+    #[error("{in_sync} replicas are in sync, {required} required to accept produce")]
+    NotEnoughReplicas { in_sync: u16, required: u16 },
+
+    // Schema validation errors
+    #[fluvio(tag = 3016)]
+    #[error("record failed schema validation: {0}")]
+    SchemaValidation(String),
+
+    // Overload errors
+    #[fluvio(tag = 3017)]
+    #[error("SPU is busy, retry later")]
+    ServerBusy,
+
+    // Leader epoch errors
+    #[fluvio(tag = 3018)]
+    #[error("leader epoch {received} of request does not match partition leader epoch {current}, refresh metadata and retry")]
+    StaleLeaderEpoch { received: i32, current: i32 },
+
+    // Rate limiting errors
+    #[fluvio(tag = 3019)]
+    #[error("request rate limit exceeded, retry after {retry_after_ms} ms")]
+    RequestThrottled { retry_after_ms: u64 },
+
+    // Consumer group errors
+    #[fluvio(tag = 3020)]
+    #[error("generation {generation} of consumer group {group_id} is not current, heartbeat to get new assignment")]
+    ConsumerGroupStaleGeneration { group_id: String, generation: i32 },
 
     // Legacy SmartModule errors
     #[cfg(feature = "smartmodule")]
@@ -237,6 +321,18 @@ impl ErrorCode {
     pub fn is_error(&self) -> bool {
         !self.is_ok()
     }
+
+    /// error is transient, same request can succeed after backoff
+    pub fn is_retriable(&self) -> bool {
+        matches!(
+            self,
+            ErrorCode::ServerBusy
+                | ErrorCode::StaleLeaderEpoch { .. }
+                | ErrorCode::NotEnoughReplicas { .. }
+                | ErrorCode::NotLeaderForPartition
+                | ErrorCode::RequestThrottled { .. }
+        )
+    }
 }
 
 // -----------------------------------
@@ -314,6 +410,117 @@ mod test {
 
         // Stream Fetch error
         assert_tag!(ErrorCode::FetchSessionNotFoud, 3002, 0);
+        assert_tag!(
+            ErrorCode::ProducerFenced {
+                producer_id: 1,
+                epoch: 0,
+                current_epoch: 1
+            },
+            3005,
+            0
+        );
+        assert_tag!(
+            ErrorCode::OutOfOrderSequence {
+                producer_id: 1,
+                expected: 1,
+                received: 2
+            },
+            3006,
+            0
+        );
+        assert_tag!(
+            ErrorCode::FetchSessionEpochMismatch {
+                session_id: 1,
+                expected: 2,
+                received: 3
+            },
+            3012,
+            0
+        );
+
+        // Consumer group errors
+        assert_tag!(
+            ErrorCode::ConsumerGroupUnknownMember {
+                group_id: "g".to_string(),
+                member_id: "m".to_string()
+            },
+            3007,
+            0
+        );
+        assert_tag!(
+            ErrorCode::ConsumerGroupTopicMismatch {
+                group_id: "g".to_string(),
+                topic: "t".to_string()
+            },
+            3008,
+            0
+        );
+
+        // Partition reassignment errors
+        assert_tag!(ErrorCode::PartitionReassignmentInProgress, 3009, 0);
+        assert_tag!(
+            ErrorCode::TransactionNotInProgress {
+                transactional_id: "txn".to_owned()
+            },
+            3010,
+            0
+        );
+        assert_tag!(
+            ErrorCode::TransactionEnding {
+                transactional_id: "txn".to_owned()
+            },
+            3011,
+            0
+        );
+        assert_tag!(ErrorCode::SpuMemoryBudgetExceeded { requested: 1 }, 3013, 0);
+
+        // Read-only partition errors
+        assert_tag!(ErrorCode::PartitionReadOnly, 3014, 0);
+
+        // Min in-sync replicas errors
+        assert_tag!(
+            ErrorCode::NotEnoughReplicas {
+                in_sync: 1,
+                required: 2
+            },
+            3015,
+            0
+        );
+
+        // Schema validation errors
+        assert_tag!(ErrorCode::SchemaValidation("".to_owned()), 3016, 0);
+
+        // Overload errors
+        assert_tag!(ErrorCode::ServerBusy, 3017, 0);
+
+        // Leader epoch errors
+        assert_tag!(
+            ErrorCode::StaleLeaderEpoch {
+                received: 1,
+                current: 2
+            },
+            3018,
+            0
+        );
+
+        // Rate limiting errors
+        assert_tag!(
+            ErrorCode::RequestThrottled {
+                retry_after_ms: 100
+            },
+            3019,
+            0
+        );
+
+        // Consumer group errors
+        assert_tag!(
+            ErrorCode::ConsumerGroupStaleGeneration {
+                group_id: "g".to_string(),
+                generation: 1
+            },
+            3020,
+            0
+        );
     }
 
     #[test]
@@ -1,5 +1,8 @@
 mod error_code;
+pub mod auth_token;
+pub mod capabilities;
 pub mod smartmodule;
 pub mod versions;
+pub mod throttle;
 
 pub use error_code::*;
@@ -64,6 +64,36 @@ impl fmt::Display for SmartModuleTransformRuntimeError {
     }
 }
 
+/// Record which failed in SmartModule, as written to dead-letter topic.
+///
+/// Records don't carry headers, so the dead-letter record keeps key of the failed record
+/// and its value is this struct encoded, with the original value inside the error.
+#[derive(Debug, Default, Clone, Eq, PartialEq, Encoder, Decoder)]
+pub struct DeadLetter {
+    /// Topic the failed record was read from
+    pub topic: String,
+    /// Partition the failed record was read from
+    pub partition: u32,
+    pub error: SmartModuleTransformRuntimeError,
+}
+
+impl DeadLetter {
+    pub fn into_record(self) -> Result<Record, std::io::Error> {
+        let key = self.error.record_key.clone();
+        let value = self.as_bytes(0)?;
+        Ok(Record {
+            key,
+            value: RecordData::from(value.to_vec()),
+            ..Default::default()
+        })
+    }
+
+    /// decode dead letter from value of record read from dead-letter topic
+    pub fn from_record(record: &Record) -> Result<Self, std::io::Error> {
+        Self::decode_from(&mut record.value.as_ref(), 0)
+    }
+}
+
 fn display_record_data(record: &RecordData) -> String {
     match std::str::from_utf8(record.as_ref()) {
         Ok(s) => s.to_string(),
@@ -193,3 +223,29 @@ impl fmt::Display for SmartModuleLookbackRuntimeError {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dead_letter_record() {
+        let failed = Record::new_key_value("key", "value");
+        let dead_letter = DeadLetter {
+            topic: "topic".to_owned(),
+            partition: 1,
+            error: SmartModuleTransformRuntimeError::new(
+                &failed,
+                10,
+                SmartModuleKind::Map,
+                eyre::eyre!("failed"),
+            ),
+        };
+
+        let record = dead_letter.clone().into_record().expect("encode");
+        assert_eq!(record.key, failed.key);
+        let decoded = DeadLetter::from_record(&record).expect("decode");
+        assert_eq!(decoded, dead_letter);
+        assert_eq!(decoded.error.record_value.as_ref(), b"value");
+    }
+}
@@ -0,0 +1,12 @@
+//! Typed response of requests rejected by rate limiting.
+
+use super::ErrorCode;
+
+/// Response which can report that its request was rejected as over rate limit
+pub trait ThrottledResponse: Sized {
+    /// response rejecting request with `error`.
+    /// None if response can't carry error, such request is delayed until it's within limit
+    fn throttled(_error: ErrorCode) -> Option<Self> {
+        None
+    }
+}
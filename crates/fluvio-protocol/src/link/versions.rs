@@ -5,9 +5,12 @@ use crate::{Encoder, Version, Decoder};
 use crate::api::Request;
 
 use super::ErrorCode;
+use super::capabilities::Capabilities;
 
 pub const VERSIONS_API_KEY: u16 = 18;
 pub const V10_PLATFORM: i16 = 2;
+/// versions exchange capabilities
+pub const CAPABILITIES_API: i16 = 3;
 
 // -----------------------------------
 // ApiVersionsRequest
@@ -21,11 +24,13 @@ pub struct ApiVersionsRequest {
     pub client_os: String,
     #[fluvio(min_version = 1)]
     pub client_arch: String,
+    #[fluvio(min_version = 3)]
+    pub capabilities: Capabilities,
 }
 
 impl Request for ApiVersionsRequest {
     const API_KEY: u16 = VERSIONS_API_KEY;
-    const DEFAULT_API_VERSION: i16 = V10_PLATFORM;
+    const DEFAULT_API_VERSION: i16 = CAPABILITIES_API;
     type Response = ApiVersionsResponse;
 }
 
@@ -40,6 +45,8 @@ pub struct ApiVersionsResponse {
     pub error_code: ErrorCode,
     pub api_keys: ApiVersions,
     pub platform_version: PlatformVersion,
+    #[fluvio(min_version = 3)]
+    pub capabilities: Capabilities,
 }
 
 #[derive(Decoder, Encoder, Default, Clone, Debug, Eq, PartialEq)]
@@ -157,6 +164,7 @@ mod tests {
                 error_code: ErrorCode::None,
                 api_keys: vec![],
                 platform_version,
+                ..Default::default()
             }
         }
 
@@ -20,6 +20,13 @@ use super::Offset;
 use super::Size;
 
 const ATTR_SCHEMA_PRESENT: i16 = 0x10;
+const ATTR_RECORD_TTL_PRESENT: i16 = 0x20;
+const ATTR_SCHEDULED_PRESENT: i16 = 0x40;
+const ATTR_TRANSACTIONAL: i16 = 0x80;
+const ATTR_CONTROL: i16 = 0x100;
+/// set on control batch which commits transaction, otherwise transaction is aborted
+const ATTR_CONTROL_COMMIT: i16 = 0x200;
+const ATTR_ENCRYPTED: i16 = 0x400;
 const ATTR_COMPRESSION_CODEC_MASK: i16 = 0x07;
 pub const NO_TIMESTAMP: i64 = -1;
 
@@ -272,6 +279,18 @@ impl<R: BatchRecords> Batch<R> {
 }
 
 impl Batch {
+    /// control batch which ends transaction of the producer.
+    /// Batch has single empty record, since empty batches are not stored
+    pub fn txn_marker(producer_id: i64, producer_epoch: i16, marker: TxnMarker) -> Self {
+        let mut batch = Self::default();
+        batch.add_record(Record::default());
+        let header = batch.get_mut_header();
+        header.producer_id = producer_id;
+        header.producer_epoch = producer_epoch;
+        header.set_txn_marker(marker);
+        batch
+    }
+
     /// add new record, this will update the offset to correct
     pub fn add_record(&mut self, record: Record) {
         self.add_records(&mut vec![record]);
@@ -279,6 +298,9 @@ impl Batch {
     }
 
     pub fn add_records(&mut self, records: &mut Vec<Record>) {
+        if records.iter().any(|record| record.ttl().is_some()) {
+            self.header.set_record_ttl();
+        }
         self.records.append(records);
         self.batch_len = self.calc_batch_len();
         self.update_offset_deltas();
@@ -478,6 +500,83 @@ impl BatchHeader {
     pub fn set_schema_id(&mut self) {
         self.attributes |= ATTR_SCHEMA_PRESENT;
     }
+
+    /// true if at least one record of the batch has TTL
+    pub fn has_record_ttl(&self) -> bool {
+        self.attributes & ATTR_RECORD_TTL_PRESENT != 0
+    }
+
+    /// set record TTL attr flag
+    pub fn set_record_ttl(&mut self) {
+        self.attributes |= ATTR_RECORD_TTL_PRESENT;
+    }
+
+    /// true if at least one record of the batch is scheduled.
+    /// Batch is visible to consumers after its max timestamp
+    pub fn has_scheduled_records(&self) -> bool {
+        self.attributes & ATTR_SCHEDULED_PRESENT != 0
+    }
+
+    /// set scheduled records attr flag
+    pub fn set_scheduled_records(&mut self) {
+        self.attributes |= ATTR_SCHEDULED_PRESENT;
+    }
+
+    /// true if batch is part of producer transaction
+    pub fn is_transactional(&self) -> bool {
+        self.attributes & ATTR_TRANSACTIONAL != 0
+    }
+
+    /// set transactional attr flag
+    pub fn set_transactional(&mut self) {
+        self.attributes |= ATTR_TRANSACTIONAL;
+    }
+
+    /// true if batch is control batch which carries transaction marker instead of user records
+    pub fn is_control(&self) -> bool {
+        self.attributes & ATTR_CONTROL != 0
+    }
+
+    /// transaction marker of control batch
+    pub fn txn_marker(&self) -> Option<TxnMarker> {
+        if !self.is_control() {
+            None
+        } else if self.attributes & ATTR_CONTROL_COMMIT != 0 {
+            Some(TxnMarker::Commit)
+        } else {
+            Some(TxnMarker::Abort)
+        }
+    }
+
+    /// true if records of the batch are encrypted by the SPU storing them
+    pub fn is_encrypted(&self) -> bool {
+        self.attributes & ATTR_ENCRYPTED != 0
+    }
+
+    /// set or clear encrypted attr flag
+    pub fn set_encrypted(&mut self, encrypted: bool) {
+        if encrypted {
+            self.attributes |= ATTR_ENCRYPTED;
+        } else {
+            self.attributes &= !ATTR_ENCRYPTED;
+        }
+    }
+
+    /// mark as control batch with transaction marker
+    pub fn set_txn_marker(&mut self, marker: TxnMarker) {
+        self.attributes |= ATTR_TRANSACTIONAL | ATTR_CONTROL;
+        match marker {
+            TxnMarker::Commit => self.attributes |= ATTR_CONTROL_COMMIT,
+            TxnMarker::Abort => self.attributes &= !ATTR_CONTROL_COMMIT,
+        }
+    }
+}
+
+/// Outcome of transaction, written to each partition of transaction as control batch
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum TxnMarker {
+    Abort,
+    Commit,
 }
 impl Default for BatchHeader {
     fn default() -> Self {
@@ -841,6 +940,23 @@ mod test {
         });
     }
 
+    #[test]
+    fn test_txn_marker_batch() {
+        let batch = Batch::txn_marker(7, 2, TxnMarker::Commit);
+        assert_eq!(batch.records_len(), 1);
+        assert!(batch.header.is_transactional());
+        assert!(batch.header.is_control());
+        assert_eq!(batch.header.txn_marker(), Some(TxnMarker::Commit));
+        assert_eq!(batch.header.producer_id, 7);
+        assert_eq!(batch.header.producer_epoch, 2);
+
+        let mut header = BatchHeader::default();
+        assert_eq!(header.txn_marker(), None);
+        header.set_txn_marker(TxnMarker::Commit);
+        header.set_txn_marker(TxnMarker::Abort);
+        assert_eq!(header.txn_marker(), Some(TxnMarker::Abort));
+    }
+
     #[test]
     fn test_batch_len() {
         let mem_records = vec![Record::default(), Record::default(), Record::default()];
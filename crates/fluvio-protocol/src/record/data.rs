@@ -6,6 +6,7 @@ use std::io::Error;
 use std::io::ErrorKind;
 use std::ops::Deref;
 use std::str::Utf8Error;
+use std::time::Duration;
 
 use bytes::Bytes;
 use bytes::BytesMut;
@@ -35,6 +36,13 @@ use super::Offset;
 #[cfg(feature = "compress")]
 use super::batch::RawRecords;
 
+/// record attribute flag, set when record has TTL.
+/// TTL in milliseconds is encoded as varint after headers of such record
+const RECORD_ATTR_TTL: i8 = 0x01;
+
+/// record attribute flag, set when record is hidden from consumers until its timestamp
+const RECORD_ATTR_SCHEDULED: i8 = 0x02;
+
 /// maximum text to display
 static MAX_STRING_DISPLAY: Lazy<usize> = Lazy::new(|| {
     let var_value = std::env::var("FLV_MAX_STRING_DISPLAY").unwrap_or_default();
@@ -411,6 +419,14 @@ impl RecordHeader {
     pub fn get_timestamp_delta(&self) -> Timestamp {
         self.timestamp_delta
     }
+
+    pub fn has_ttl(&self) -> bool {
+        self.attributes & RECORD_ATTR_TTL != 0
+    }
+
+    pub fn is_scheduled(&self) -> bool {
+        self.attributes & RECORD_ATTR_SCHEDULED != 0
+    }
 }
 
 #[derive(Default, Clone)]
@@ -419,6 +435,8 @@ pub struct Record<B = RecordData> {
     pub key: Option<B>,
     pub value: B,
     pub headers: i64,
+    /// time to live, encoded only if TTL attribute is set
+    pub ttl: Option<Duration>,
 }
 
 impl<B: Default> Record<B> {
@@ -456,6 +474,67 @@ impl<B: Default> Record<B> {
     pub fn into_key(self) -> Option<B> {
         self.key
     }
+
+    /// Time to live of this record, counted from the record timestamp
+    pub fn ttl(&self) -> Option<Duration> {
+        self.ttl.filter(|_| self.preamble.has_ttl())
+    }
+
+    /// Set time to live. Expired records are not returned to consumers
+    /// and are eventually removed from storage
+    pub fn set_ttl(&mut self, ttl: Duration) {
+        self.preamble.attributes |= RECORD_ATTR_TTL;
+        self.ttl = Some(ttl);
+    }
+
+    /// TTL to encode, none unless TTL attribute is set
+    fn ttl_millis(&self) -> Option<i64> {
+        self.ttl()
+            .map(|ttl| ttl.as_millis().min(i64::MAX as u128) as i64)
+    }
+
+    /// Builder style version of [`set_ttl`](Self::set_ttl)
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.set_ttl(ttl);
+        self
+    }
+
+    /// Deliver this record only after `deliver_at` (in milliseconds).
+    ///
+    /// Scheduled record takes deliver time as its timestamp. Until record is pushed into
+    /// producer batch, timestamp delta holds absolute deliver time; batch rebases it
+    /// on its own timestamp.
+    pub fn set_deliver_at(&mut self, deliver_at: Timestamp) {
+        self.preamble.attributes |= RECORD_ATTR_SCHEDULED;
+        self.preamble.timestamp_delta = deliver_at;
+    }
+
+    /// Builder style version of [`set_deliver_at`](Self::set_deliver_at)
+    pub fn with_deliver_at(mut self, deliver_at: Timestamp) -> Self {
+        self.set_deliver_at(deliver_at);
+        self
+    }
+
+    /// Timestamp when this record becomes visible to consumers, given base timestamp of its batch.
+    /// None if record is not scheduled or batch has no timestamp
+    pub fn deliver_at(&self, timestamp_base: Timestamp) -> Option<Timestamp> {
+        if timestamp_base <= 0 || !self.preamble.is_scheduled() {
+            return None;
+        }
+        Some(timestamp_base + self.preamble.timestamp_delta)
+    }
+
+    /// Timestamp when this record expires, given base timestamp of its batch.
+    /// None if record has no TTL or batch has no timestamp
+    pub fn expires_at(&self, timestamp_base: Timestamp) -> Option<Timestamp> {
+        if timestamp_base <= 0 {
+            return None;
+        }
+        self.ttl().map(|ttl| {
+            (timestamp_base + self.preamble.timestamp_delta)
+                .saturating_add(ttl.as_millis() as Timestamp)
+        })
+    }
 }
 
 impl Record {
@@ -501,6 +580,7 @@ impl<B: Debug> Debug for Record<B> {
             .field("key", &self.key)
             .field("value", &self.value)
             .field("headers", &self.headers)
+            .field("ttl", &self.ttl)
             .finish()
     }
 }
@@ -513,7 +593,8 @@ where
         let inner_size = self.preamble.write_size(version)
             + self.key.write_size(version)
             + self.value.write_size(version)
-            + self.headers.var_write_size();
+            + self.headers.var_write_size()
+            + self.ttl_millis().map_or(0, |ttl| ttl.var_write_size());
         let len: i64 = inner_size as i64;
         len.var_write_size() + inner_size
     }
@@ -527,6 +608,9 @@ where
         self.key.encode(&mut out, version)?;
         self.value.encode(&mut out, version)?;
         self.headers.encode_varint(&mut out)?;
+        if let Some(ttl) = self.ttl_millis() {
+            ttl.encode_varint(&mut out)?;
+        }
         let len: i64 = out.len() as i64;
         trace!("record encode as {} bytes", len);
         len.encode_varint(dest)?;
@@ -555,11 +639,23 @@ where
                 "not enough for record",
             ));
         }
+        let start = src.remaining();
         self.preamble.decode(src, version)?;
         trace!("offset delta: {}", self.preamble.offset_delta);
         self.key.decode(src, version)?;
         self.value.decode(src, version)?;
         self.headers.decode_varint(src)?;
+        if self.preamble.has_ttl() {
+            let mut ttl: i64 = 0;
+            ttl.decode_varint(src)?;
+            self.ttl = Some(Duration::from_millis(ttl.max(0) as u64));
+        }
+
+        // skip fields added by newer versions
+        let read = (start - src.remaining()) as i64;
+        if read < len {
+            src.advance((len - read) as usize);
+        }
 
         Ok(())
     }
@@ -627,6 +723,13 @@ impl ConsumerRecord {
             self.timestamp_base + self.record.timestamp_delta()
         }
     }
+
+    /// Returns true if record has TTL and it expired before `now` (in milliseconds)
+    pub fn is_expired(&self, now: Timestamp) -> bool {
+        self.record
+            .expires_at(self.timestamp_base)
+            .is_some_and(|expires_at| expires_at <= now)
+    }
 }
 
 impl AsRef<[u8]> for ConsumerRecord {
@@ -670,6 +773,82 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_record_ttl() -> Result<(), IoError> {
+        let record = Record::new("value").with_ttl(Duration::from_secs(5));
+        assert_eq!(record.ttl(), Some(Duration::from_secs(5)));
+
+        let bytes = record.as_bytes(0)?;
+        let decoded = Record::<RecordData>::decode_from(&mut Cursor::new(&bytes), 0)?;
+        assert_eq!(decoded.ttl(), Some(Duration::from_secs(5)));
+        assert_eq!(decoded.headers, 0);
+        assert_eq!(decoded.write_size(0), bytes.len());
+        assert_eq!(decoded.expires_at(1000), Some(6000));
+        assert_eq!(decoded.expires_at(NO_TIMESTAMP), None);
+
+        let consumer_record = ConsumerRecord {
+            offset: 0,
+            partition: 0,
+            record: decoded,
+            timestamp_base: 1000,
+        };
+        assert!(!consumer_record.is_expired(5999));
+        assert!(consumer_record.is_expired(6000));
+
+        assert_eq!(Record::new("value").ttl(), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_record_ttl_in_batch() -> Result<(), IoError> {
+        let mut batch = Batch::<MemoryRecords>::default();
+        batch.add_records(&mut vec![
+            Record::new("first").with_ttl(Duration::from_millis(300)),
+            Record::new("second"),
+        ]);
+        assert!(batch.header.has_record_ttl());
+
+        let bytes = batch.as_bytes(0)?;
+        let decoded = Batch::<MemoryRecords>::decode_from(&mut Cursor::new(bytes), 0)?;
+        let records = decoded.records();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].ttl(), Some(Duration::from_millis(300)));
+        assert_eq!(records[0].value.as_ref(), b"first");
+        assert_eq!(records[1].ttl(), None);
+        assert_eq!(records[1].value.as_ref(), b"second");
+        Ok(())
+    }
+
+    #[test]
+    fn test_record_skips_unknown_fields() -> Result<(), IoError> {
+        // record of existing test with one extra byte, followed by next record
+        let data = [
+            0x14, 0x0, 0x0, 0x2, 0x0, 0x6, 0x64, 0x6f, 0x67, 0x0, 0x7, 0x12,
+        ];
+        let mut src = Cursor::new(&data);
+        let record = Record::<RecordData>::decode_from(&mut src, 0)?;
+        assert_eq!(record.value.as_ref(), b"dog");
+        assert_eq!(record.ttl(), None);
+        assert_eq!(src.position(), 11);
+        Ok(())
+    }
+
+    #[test]
+    fn test_record_deliver_at() -> Result<(), IoError> {
+        let mut record = Record::new("value").with_deliver_at(5000);
+        assert!(record.get_header().is_scheduled());
+
+        // rebased on batch timestamp
+        record.get_mut_header().set_timestamp_delta(4000);
+        let bytes = record.as_bytes(0)?;
+        let decoded = Record::<RecordData>::decode_from(&mut Cursor::new(&bytes), 0)?;
+        assert_eq!(decoded.deliver_at(1000), Some(5000));
+        assert_eq!(decoded.deliver_at(NO_TIMESTAMP), None);
+
+        assert_eq!(Record::new("value").deliver_at(1000), None);
+        Ok(())
+    }
+
     /// test decoding of records when one of the batch was truncated
     #[test]
     fn test_decode_batch_truncation() {
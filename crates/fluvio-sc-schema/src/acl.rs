@@ -0,0 +1,127 @@
+//!
+//! # Access Control Lists
+//!
+//! ACL entries are managed by the SC and enforced by the SC and SPUs once ACLs are enabled.
+//!
+use fluvio_protocol::{Encoder, Decoder};
+use fluvio_protocol::api::Request;
+use fluvio_protocol::link::throttle::ThrottledResponse;
+
+pub use fluvio_controlplane_metadata::acl::*;
+
+use crate::{AdminPublicApiKey, ApiError};
+use crate::errors::ErrorCode;
+use crate::objects::COMMON_VERSION;
+
+#[derive(Encoder, Decoder, Default, Debug)]
+pub struct AclListRequest {}
+
+impl Request for AclListRequest {
+    const API_KEY: u16 = AdminPublicApiKey::AclList as u16;
+    const MIN_API_VERSION: i16 = 25;
+    const DEFAULT_API_VERSION: i16 = COMMON_VERSION;
+    type Response = AclListResponse;
+}
+
+#[derive(Encoder, Decoder, Default, Debug)]
+pub struct AclListResponse {
+    pub error_code: ErrorCode,
+    pub error_message: Option<String>,
+    /// whether ACLs are enforced
+    pub enabled: bool,
+    pub entries: Vec<AclEntry>,
+}
+
+impl ThrottledResponse for AclListResponse {
+    fn throttled(error: ErrorCode) -> Option<Self> {
+        let msg = error.to_string();
+        Some(Self::error(error, msg))
+    }
+}
+
+impl AclListResponse {
+    pub fn new(enabled: bool, entries: Vec<AclEntry>) -> Self {
+        Self {
+            error_code: ErrorCode::None,
+            error_message: None,
+            enabled,
+            entries,
+        }
+    }
+
+    pub fn error(error_code: ErrorCode, msg: impl Into<String>) -> Self {
+        Self {
+            error_code,
+            error_message: Some(msg.into()),
+            ..Default::default()
+        }
+    }
+
+    pub fn as_result(self) -> Result<Self, ApiError> {
+        if self.error_code.is_ok() {
+            Ok(self)
+        } else {
+            Err(ApiError::Code(self.error_code, self.error_message))
+        }
+    }
+}
+
+/// Add and remove ACL entries. Entries are removed first
+#[derive(Encoder, Decoder, Default, Debug)]
+pub struct AclUpdateRequest {
+    pub add: Vec<AclEntry>,
+    pub remove: Vec<AclEntry>,
+}
+
+impl AclUpdateRequest {
+    pub fn add(entries: Vec<AclEntry>) -> Self {
+        Self {
+            add: entries,
+            remove: vec![],
+        }
+    }
+
+    pub fn remove(entries: Vec<AclEntry>) -> Self {
+        Self {
+            add: vec![],
+            remove: entries,
+        }
+    }
+}
+
+impl Request for AclUpdateRequest {
+    const API_KEY: u16 = AdminPublicApiKey::AclUpdate as u16;
+    const MIN_API_VERSION: i16 = 25;
+    const DEFAULT_API_VERSION: i16 = COMMON_VERSION;
+    type Response = AclUpdateResponse;
+}
+
+#[derive(Encoder, Decoder, Default, Debug)]
+pub struct AclUpdateResponse {
+    pub error_code: ErrorCode,
+    pub error_message: Option<String>,
+}
+
+impl ThrottledResponse for AclUpdateResponse {
+    fn throttled(error: ErrorCode) -> Option<Self> {
+        let msg = error.to_string();
+        Some(Self::error(error, msg))
+    }
+}
+
+impl AclUpdateResponse {
+    pub fn error(error_code: ErrorCode, msg: impl Into<String>) -> Self {
+        Self {
+            error_code,
+            error_message: Some(msg.into()),
+        }
+    }
+
+    pub fn as_result(self) -> Result<(), ApiError> {
+        if self.error_code.is_ok() {
+            Ok(())
+        } else {
+            Err(ApiError::Code(self.error_code, self.error_message))
+        }
+    }
+}
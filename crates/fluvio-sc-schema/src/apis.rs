@@ -23,6 +23,16 @@ pub enum AdminPublicApiKey {
     List = 1003,
     Watch = 1004,
     Mirroring = 1005,
+    ReassignPartition = 1006,
+    AddPartitions = 1007,
+    DrainSpu = 1008,
+    SetReadOnly = 1009,
+    RecommendedCli = 1010,
+    AuditLog = 1011,
+    AclList = 1012,
+    AclUpdate = 1013,
+    ClusterDiagnostics = 1014,
+    ClusterEvents = 1015,
 }
 
 impl Default for AdminPublicApiKey {
@@ -0,0 +1,97 @@
+//!
+//! # Audit Log
+//!
+//! Admin mutations are recorded by the SC with the principal who requested them.
+//! Clients query the most recent entries.
+//!
+use fluvio_protocol::{Encoder, Decoder};
+use fluvio_protocol::api::Request;
+use fluvio_protocol::link::throttle::ThrottledResponse;
+
+use crate::{AdminPublicApiKey, ApiError};
+use crate::errors::ErrorCode;
+use crate::objects::COMMON_VERSION;
+
+#[derive(Encoder, Decoder, Default, Debug)]
+pub struct AuditLogRequest {
+    /// max number of most recent entries, all entries kept by SC if 0
+    pub limit: u32,
+}
+
+impl AuditLogRequest {
+    pub fn new(limit: u32) -> Self {
+        Self { limit }
+    }
+}
+
+impl Request for AuditLogRequest {
+    const API_KEY: u16 = AdminPublicApiKey::AuditLog as u16;
+    const MIN_API_VERSION: i16 = 25;
+    const DEFAULT_API_VERSION: i16 = COMMON_VERSION;
+    type Response = AuditLogResponse;
+}
+
+#[derive(Encoder, Decoder, Default, Debug)]
+pub struct AuditLogResponse {
+    pub error_code: ErrorCode,
+    pub error_message: Option<String>,
+    /// entries, oldest first
+    pub entries: Vec<AuditEntry>,
+}
+
+impl ThrottledResponse for AuditLogResponse {
+    fn throttled(error: ErrorCode) -> Option<Self> {
+        let msg = error.to_string();
+        Some(Self::error(error, msg))
+    }
+}
+
+impl AuditLogResponse {
+    pub fn new(entries: Vec<AuditEntry>) -> Self {
+        Self {
+            error_code: ErrorCode::None,
+            error_message: None,
+            entries,
+        }
+    }
+
+    pub fn error(error_code: ErrorCode, msg: impl Into<String>) -> Self {
+        Self {
+            error_code,
+            error_message: Some(msg.into()),
+            ..Default::default()
+        }
+    }
+
+    pub fn as_result(self) -> Result<Self, ApiError> {
+        if self.error_code.is_ok() {
+            Ok(self)
+        } else {
+            Err(ApiError::Code(self.error_code, self.error_message))
+        }
+    }
+}
+
+/// Admin mutation recorded by SC
+#[derive(Encoder, Decoder, Default, Debug, Clone)]
+pub struct AuditEntry {
+    /// unix time of mutation, in milliseconds
+    pub timestamp_ms: u64,
+    /// principal who requested mutation, none if requests are not authenticated
+    pub principal: Option<String>,
+    /// e.g. "create", "delete", "add-partitions"
+    pub operation: String,
+    /// kind of object, e.g. "Topic"
+    pub object: String,
+    pub name: String,
+    /// arguments of request other than name
+    pub summary: String,
+    /// outcome of mutation
+    pub error_code: ErrorCode,
+}
+
+impl AuditEntry {
+    pub fn is_ok(&self) -> bool {
+        self.error_code.is_ok()
+    }
+}
@@ -0,0 +1,79 @@
+//!
+//! # Recommended CLI Release
+//!
+//! Clients ask the cluster which Fluvio CLI release they should run, so a fleet of
+//! operators converges on the release matching the cluster instead of the latest one.
+//!
+use fluvio_protocol::{Encoder, Decoder};
+use fluvio_protocol::api::Request;
+use fluvio_protocol::link::throttle::ThrottledResponse;
+
+use crate::{AdminPublicApiKey, ApiError};
+use crate::errors::ErrorCode;
+use crate::objects::COMMON_VERSION;
+
+#[derive(Encoder, Decoder, Default, Debug)]
+pub struct RecommendedCliRequest {
+    /// package target of client, e.g. "x86_64-unknown-linux-musl"
+    pub target: String,
+}
+
+impl RecommendedCliRequest {
+    pub fn new(target: impl Into<String>) -> Self {
+        Self {
+            target: target.into(),
+        }
+    }
+}
+
+impl Request for RecommendedCliRequest {
+    const API_KEY: u16 = AdminPublicApiKey::RecommendedCli as u16;
+    const MIN_API_VERSION: i16 = 25;
+    const DEFAULT_API_VERSION: i16 = COMMON_VERSION;
+    type Response = RecommendedCliResponse;
+}
+
+#[derive(Encoder, Decoder, Default, Debug)]
+pub struct RecommendedCliResponse {
+    pub error_code: ErrorCode,
+    pub error_message: Option<String>,
+    /// recommended version of the CLI
+    pub version: String,
+    /// version was resolved from the package index mirrored by the cluster,
+    /// otherwise it's the platform version of the cluster
+    pub mirrored: bool,
+}
+
+impl ThrottledResponse for RecommendedCliResponse {
+    fn throttled(error: ErrorCode) -> Option<Self> {
+        let msg = error.to_string();
+        Some(Self::error(error, msg))
+    }
+}
+
+impl RecommendedCliResponse {
+    pub fn new(version: impl Into<String>, mirrored: bool) -> Self {
+        Self {
+            error_code: ErrorCode::None,
+            error_message: None,
+            version: version.into(),
+            mirrored,
+        }
+    }
+
+    pub fn error(error_code: ErrorCode, msg: impl Into<String>) -> Self {
+        Self {
+            error_code,
+            error_message: Some(msg.into()),
+            ..Default::default()
+        }
+    }
+
+    pub fn as_result(self) -> Result<Self, ApiError> {
+        if self.error_code.is_ok() {
+            Ok(self)
+        } else {
+            Err(ApiError::Code(self.error_code, self.error_message))
+        }
+    }
+}
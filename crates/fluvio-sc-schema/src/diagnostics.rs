@@ -0,0 +1,216 @@
+//!
+//! # Cluster Diagnostics
+//!
+//! SC aggregates health of SPUs, partitions and mirrors into single report.
+//! Problems found are listed as issues, critical issues make cluster unhealthy.
+//!
+use std::fmt;
+
+use fluvio_protocol::{Encoder, Decoder};
+use fluvio_protocol::api::Request;
+use fluvio_protocol::link::throttle::ThrottledResponse;
+use fluvio_types::SpuId;
+
+use crate::{AdminPublicApiKey, ApiError};
+use crate::errors::ErrorCode;
+use crate::objects::COMMON_VERSION;
+
+#[derive(Encoder, Decoder, Default, Debug)]
+pub struct ClusterDiagnosticsRequest {}
+
+impl Request for ClusterDiagnosticsRequest {
+    const API_KEY: u16 = AdminPublicApiKey::ClusterDiagnostics as u16;
+    const MIN_API_VERSION: i16 = 25;
+    const DEFAULT_API_VERSION: i16 = COMMON_VERSION;
+    type Response = ClusterDiagnosticsResponse;
+}
+
+#[derive(Encoder, Decoder, Default, Debug)]
+pub struct ClusterDiagnosticsResponse {
+    pub error_code: ErrorCode,
+    pub error_message: Option<String>,
+    pub report: ClusterHealthReport,
+}
+
+impl ThrottledResponse for ClusterDiagnosticsResponse {
+    fn throttled(error: ErrorCode) -> Option<Self> {
+        let msg = error.to_string();
+        Some(Self::error(error, msg))
+    }
+}
+
+impl ClusterDiagnosticsResponse {
+    pub fn new(report: ClusterHealthReport) -> Self {
+        Self {
+            error_code: ErrorCode::None,
+            error_message: None,
+            report,
+        }
+    }
+
+    pub fn error(error_code: ErrorCode, msg: impl Into<String>) -> Self {
+        Self {
+            error_code,
+            error_message: Some(msg.into()),
+            ..Default::default()
+        }
+    }
+
+    pub fn as_result(self) -> Result<Self, ApiError> {
+        if self.error_code.is_ok() {
+            Ok(self)
+        } else {
+            Err(ApiError::Code(self.error_code, self.error_message))
+        }
+    }
+}
+
+#[derive(Encoder, Decoder, Default, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct ClusterHealthReport {
+    pub sc_version: String,
+    pub spus: Vec<SpuHealth>,
+    /// partitions which are offline, lagging, corrupt or divergent
+    pub partitions: Vec<PartitionHealth>,
+    pub mirrors: Vec<MirrorHealth>,
+    pub issues: Vec<HealthIssue>,
+}
+
+impl ClusterHealthReport {
+    /// true if there are no critical issues
+    pub fn is_healthy(&self) -> bool {
+        self.issues
+            .iter()
+            .all(|issue| issue.severity != IssueSeverity::Critical)
+    }
+}
+
+#[derive(Encoder, Decoder, Default, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct SpuHealth {
+    pub id: SpuId,
+    pub name: String,
+    pub online: bool,
+    /// version reported by SPU, none if not reported yet
+    pub version: Option<String>,
+    /// size of volume hosting replicas, 0 if not reported yet
+    pub disk_total_bytes: u64,
+    pub disk_available_bytes: u64,
+    /// number of partitions SPU is leader of
+    pub leaders: u32,
+    /// local disk usage of replicas SPU is leader of
+    pub leader_bytes: u64,
+}
+
+impl SpuHealth {
+    /// percentage of disk available, none if not reported
+    pub fn disk_available_percent(&self) -> Option<f64> {
+        if self.disk_total_bytes == 0 {
+            None
+        } else {
+            Some(self.disk_available_bytes as f64 * 100.0 / self.disk_total_bytes as f64)
+        }
+    }
+}
+
+#[derive(Encoder, Decoder, Default, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct PartitionHealth {
+    /// `<topic>-<partition>`
+    pub name: String,
+    pub leader: SpuId,
+    pub online: bool,
+    /// max number of records followers are behind leader
+    pub max_lag: i64,
+    pub offline_replicas: Vec<SpuId>,
+    pub divergent_replicas: Vec<SpuId>,
+    pub corrupt: bool,
+}
+
+#[derive(Encoder, Decoder, Default, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct MirrorHealth {
+    pub name: String,
+    /// e.g. "Online", "Offline", "Failed"
+    pub status: String,
+}
+
+#[derive(Encoder, Decoder, Default, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct HealthIssue {
+    pub severity: IssueSeverity,
+    /// e.g. "spu 5001", "partition orders-0", "mirror edge-1"
+    pub component: String,
+    pub message: String,
+}
+
+impl HealthIssue {
+    pub fn warning(component: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            severity: IssueSeverity::Warning,
+            component: component.into(),
+            message: message.into(),
+        }
+    }
+
+    pub fn critical(component: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            severity: IssueSeverity::Critical,
+            component: component.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for HealthIssue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "[{}] {}: {}",
+            self.severity, self.component, self.message
+        )
+    }
+}
+
+#[derive(Encoder, Decoder, Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum IssueSeverity {
+    #[default]
+    #[fluvio(tag = 0)]
+    Warning,
+    #[fluvio(tag = 1)]
+    Critical,
+}
+
+impl fmt::Display for IssueSeverity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Warning => write!(f, "warning"),
+            Self::Critical => write!(f, "critical"),
+        }
+    }
+}
@@ -0,0 +1,183 @@
+//!
+//! # Cluster Events
+//!
+//! SC streams typed change events to subscribers, so automation can react to cluster
+//! changes without polling list endpoints. Only changes after subscription are sent.
+//!
+use std::fmt;
+
+use fluvio_protocol::{Encoder, Decoder};
+use fluvio_protocol::api::Request;
+use fluvio_protocol::link::throttle::ThrottledResponse;
+use fluvio_types::{PartitionId, SpuId};
+
+use crate::{AdminPublicApiKey, ApiError};
+use crate::errors::ErrorCode;
+use crate::objects::COMMON_VERSION;
+
+#[derive(Encoder, Decoder, Default, Debug)]
+pub struct ClusterEventsRequest {
+    /// kinds of events to receive, all if empty
+    pub kinds: Vec<ClusterEventKind>,
+}
+
+impl ClusterEventsRequest {
+    pub fn new(kinds: Vec<ClusterEventKind>) -> Self {
+        Self { kinds }
+    }
+
+    pub fn is_subscribed(&self, event: &ClusterEvent) -> bool {
+        self.kinds.is_empty() || self.kinds.contains(&event.change.kind())
+    }
+}
+
+impl Request for ClusterEventsRequest {
+    const API_KEY: u16 = AdminPublicApiKey::ClusterEvents as u16;
+    const MIN_API_VERSION: i16 = 25;
+    const DEFAULT_API_VERSION: i16 = COMMON_VERSION;
+    type Response = ClusterEventsResponse;
+}
+
+/// Batch of events, stream carries one response per batch
+#[derive(Encoder, Decoder, Default, Debug)]
+pub struct ClusterEventsResponse {
+    pub error_code: ErrorCode,
+    pub error_message: Option<String>,
+    pub events: Vec<ClusterEvent>,
+}
+
+impl ThrottledResponse for ClusterEventsResponse {
+    fn throttled(error: ErrorCode) -> Option<Self> {
+        let msg = error.to_string();
+        Some(Self::error(error, msg))
+    }
+}
+
+impl ClusterEventsResponse {
+    pub fn new(events: Vec<ClusterEvent>) -> Self {
+        Self {
+            error_code: ErrorCode::None,
+            error_message: None,
+            events,
+        }
+    }
+
+    pub fn error(error_code: ErrorCode, msg: impl Into<String>) -> Self {
+        Self {
+            error_code,
+            error_message: Some(msg.into()),
+            ..Default::default()
+        }
+    }
+
+    pub fn as_result(self) -> Result<Self, ApiError> {
+        if self.error_code.is_ok() {
+            Ok(self)
+        } else {
+            Err(ApiError::Code(self.error_code, self.error_message))
+        }
+    }
+}
+
+#[derive(Encoder, Decoder, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ClusterEventKind {
+    #[default]
+    #[fluvio(tag = 0)]
+    TopicAdded,
+    #[fluvio(tag = 1)]
+    TopicDeleted,
+    #[fluvio(tag = 2)]
+    PartitionLeaderChanged,
+    #[fluvio(tag = 3)]
+    MirrorStateChanged,
+}
+
+impl fmt::Display for ClusterEventKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self {
+            Self::TopicAdded => "topic-added",
+            Self::TopicDeleted => "topic-deleted",
+            Self::PartitionLeaderChanged => "partition-leader-changed",
+            Self::MirrorStateChanged => "mirror-state-changed",
+        };
+        write!(f, "{kind}")
+    }
+}
+
+/// Change observed by SC
+#[derive(Encoder, Decoder, Default, Debug, Clone, PartialEq, Eq)]
+pub struct ClusterEvent {
+    /// unix time when SC observed change, in milliseconds
+    pub timestamp_ms: u64,
+    pub change: ClusterChange,
+}
+
+#[derive(Encoder, Decoder, Debug, Clone, PartialEq, Eq)]
+pub enum ClusterChange {
+    #[fluvio(tag = 0)]
+    TopicAdded(TopicEvent),
+    #[fluvio(tag = 1)]
+    TopicDeleted(TopicEvent),
+    #[fluvio(tag = 2)]
+    PartitionLeaderChanged(LeaderChangeEvent),
+    #[fluvio(tag = 3)]
+    MirrorStateChanged(MirrorStateEvent),
+}
+
+impl Default for ClusterChange {
+    fn default() -> Self {
+        Self::TopicAdded(TopicEvent::default())
+    }
+}
+
+impl ClusterChange {
+    pub fn kind(&self) -> ClusterEventKind {
+        match self {
+            Self::TopicAdded(_) => ClusterEventKind::TopicAdded,
+            Self::TopicDeleted(_) => ClusterEventKind::TopicDeleted,
+            Self::PartitionLeaderChanged(_) => ClusterEventKind::PartitionLeaderChanged,
+            Self::MirrorStateChanged(_) => ClusterEventKind::MirrorStateChanged,
+        }
+    }
+}
+
+impl fmt::Display for ClusterChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TopicAdded(event) | Self::TopicDeleted(event) => {
+                write!(f, "{} {}", self.kind(), event.name)
+            }
+            Self::PartitionLeaderChanged(event) => write!(
+                f,
+                "{} {}-{} leader {}",
+                self.kind(),
+                event.topic,
+                event.partition,
+                event.leader
+            ),
+            Self::MirrorStateChanged(event) => {
+                write!(f, "{} {} {}", self.kind(), event.name, event.state)
+            }
+        }
+    }
+}
+
+#[derive(Encoder, Decoder, Default, Debug, Clone, PartialEq, Eq)]
+pub struct TopicEvent {
+    pub name: String,
+}
+
+#[derive(Encoder, Decoder, Default, Debug, Clone, PartialEq, Eq)]
+pub struct LeaderChangeEvent {
+    pub topic: String,
+    pub partition: PartitionId,
+    pub leader: SpuId,
+    pub previous_leader: SpuId,
+}
+
+#[derive(Encoder, Decoder, Default, Debug, Clone, PartialEq, Eq)]
+pub struct MirrorStateEvent {
+    pub name: String,
+    pub state: String,
+    pub previous_state: String,
+}
@@ -10,6 +10,11 @@ pub mod shared;
 pub mod tableformat;
 pub mod mirror;
 pub mod mirroring;
+pub mod cli_release;
+pub mod audit;
+pub mod acl;
+pub mod diagnostics;
+pub mod events;
 
 pub mod remote_file;
 
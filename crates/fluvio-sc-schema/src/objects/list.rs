@@ -6,6 +6,7 @@ use anyhow::Result;
 use fluvio_controlplane_metadata::store::KeyFilter;
 use fluvio_protocol::{Encoder, Decoder, Version};
 use fluvio_protocol::api::Request;
+use fluvio_protocol::link::throttle::ThrottledResponse;
 
 use crate::{AdminPublicApiKey, AdminSpec, TryEncodableFrom};
 use super::{COMMON_VERSION, Metadata, TypeBuffer};
@@ -115,6 +116,9 @@ impl Request for ObjectApiListRequest {
 #[derive(Debug, Default, Encoder)]
 pub struct ObjectApiListResponse(TypeBuffer);
 
+// list has no error to report, over limit request is delayed
+impl ThrottledResponse for ObjectApiListResponse {}
+
 impl<S> TryEncodableFrom<ListResponse<S>> for ObjectApiListResponse
 where
     S: AdminSpec,
@@ -1,5 +1,43 @@
 pub use fluvio_controlplane_metadata::partition::*;
 
+pub use reassign::*;
+
+mod reassign {
+
+    use fluvio_protocol::{Encoder, Decoder};
+    use fluvio_protocol::api::Request;
+    use fluvio_types::SpuId;
+
+    use crate::{AdminPublicApiKey, Status};
+    use crate::objects::COMMON_VERSION;
+
+    use super::ReplicaKey;
+
+    /// Move partition to new set of SPUs. First SPU of the target becomes leader.
+    /// Request completes once reassignment is accepted, data is moved in the background.
+    #[derive(Encoder, Decoder, Default, Debug)]
+    pub struct ReassignPartitionRequest {
+        pub replica_id: ReplicaKey,
+        pub target: Vec<SpuId>,
+    }
+
+    impl ReassignPartitionRequest {
+        pub fn new(replica_id: impl Into<ReplicaKey>, target: Vec<SpuId>) -> Self {
+            Self {
+                replica_id: replica_id.into(),
+                target,
+            }
+        }
+    }
+
+    impl Request for ReassignPartitionRequest {
+        const API_KEY: u16 = AdminPublicApiKey::ReassignPartition as u16;
+        const MIN_API_VERSION: i16 = 15;
+        const DEFAULT_API_VERSION: i16 = COMMON_VERSION;
+        type Response = Status;
+    }
+}
+
 mod convert {
 
     use crate::{AdminSpec};
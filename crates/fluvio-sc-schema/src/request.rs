@@ -17,6 +17,15 @@ use fluvio_protocol::core::{Decoder};
 use fluvio_protocol::link::versions::ApiVersionsRequest;
 
 use crate::mirroring::ObjectMirroringRequest;
+use crate::partition::ReassignPartitionRequest;
+use crate::topic::AddPartitionsRequest;
+use crate::spu::DrainSpuRequest;
+use crate::topic::SetReadOnlyRequest;
+use crate::cli_release::RecommendedCliRequest;
+use crate::audit::AuditLogRequest;
+use crate::acl::{AclListRequest, AclUpdateRequest};
+use crate::diagnostics::ClusterDiagnosticsRequest;
+use crate::events::ClusterEventsRequest;
 use crate::AdminPublicApiKey;
 use crate::objects::{
     ObjectApiCreateRequest, ObjectApiDeleteRequest, ObjectApiListRequest, ObjectApiWatchRequest,
@@ -31,6 +40,16 @@ pub enum AdminPublicDecodedRequest {
     ListRequest(RequestMessage<ObjectApiListRequest>),
     WatchRequest(RequestMessage<ObjectApiWatchRequest>),
     MirroringRequest(RequestMessage<ObjectMirroringRequest>),
+    ReassignPartitionRequest(RequestMessage<ReassignPartitionRequest>),
+    AddPartitionsRequest(RequestMessage<AddPartitionsRequest>),
+    DrainSpuRequest(RequestMessage<DrainSpuRequest>),
+    SetReadOnlyRequest(RequestMessage<SetReadOnlyRequest>),
+    RecommendedCliRequest(RequestMessage<RecommendedCliRequest>),
+    AuditLogRequest(RequestMessage<AuditLogRequest>),
+    AclListRequest(RequestMessage<AclListRequest>),
+    AclUpdateRequest(RequestMessage<AclUpdateRequest>),
+    ClusterDiagnosticsRequest(RequestMessage<ClusterDiagnosticsRequest>),
+    ClusterEventsRequest(RequestMessage<ClusterEventsRequest>),
 }
 
 impl Default for AdminPublicDecodedRequest {
@@ -87,6 +106,30 @@ impl ApiMessage for AdminPublicDecodedRequest {
                 header,
                 ObjectMirroringRequest::decode_from(src, version)?,
             ))),
+            AdminPublicApiKey::ReassignPartition => {
+                api_decode!(Self, ReassignPartitionRequest, src, header)
+            }
+            AdminPublicApiKey::AddPartitions => {
+                api_decode!(Self, AddPartitionsRequest, src, header)
+            }
+            AdminPublicApiKey::DrainSpu => api_decode!(Self, DrainSpuRequest, src, header),
+            AdminPublicApiKey::SetReadOnly => {
+                api_decode!(Self, SetReadOnlyRequest, src, header)
+            }
+            AdminPublicApiKey::RecommendedCli => {
+                api_decode!(Self, RecommendedCliRequest, src, header)
+            }
+            AdminPublicApiKey::AuditLog => {
+                api_decode!(Self, AuditLogRequest, src, header)
+            }
+            AdminPublicApiKey::AclList => api_decode!(Self, AclListRequest, src, header),
+            AdminPublicApiKey::AclUpdate => api_decode!(Self, AclUpdateRequest, src, header),
+            AdminPublicApiKey::ClusterDiagnostics => {
+                api_decode!(Self, ClusterDiagnosticsRequest, src, header)
+            }
+            AdminPublicApiKey::ClusterEvents => {
+                api_decode!(Self, ClusterEventsRequest, src, header)
+            }
         }
     }
 }
@@ -8,6 +8,7 @@
 use std::fmt::Display;
 
 use fluvio_protocol::{Encoder, Decoder};
+use fluvio_protocol::link::throttle::ThrottledResponse;
 use crate::errors::ErrorCode;
 
 use crate::ApiError;
@@ -29,6 +30,13 @@ impl Display for Status {
     }
 }
 
+impl ThrottledResponse for Status {
+    fn throttled(error: ErrorCode) -> Option<Self> {
+        let msg = error.to_string();
+        Some(Self::new(String::new(), error, Some(msg)))
+    }
+}
+
 impl Status {
     pub fn new_ok(name: String) -> Self {
         Self {
@@ -3,3 +3,98 @@ pub use fluvio_controlplane_metadata::spu::{SpuSpec};
 use crate::{AdminSpec};
 
 impl AdminSpec for SpuSpec {}
+
+pub use drain::*;
+
+mod drain {
+
+    use fluvio_protocol::{Encoder, Decoder};
+    use fluvio_protocol::api::Request;
+    use fluvio_protocol::link::throttle::ThrottledResponse;
+    use fluvio_types::SpuId;
+
+    use crate::{AdminPublicApiKey, ApiError};
+    use crate::errors::ErrorCode;
+    use crate::objects::COMMON_VERSION;
+
+    /// Start draining SPU, or stop it with `cancel`.
+    /// Leadership of its partitions is moved to caught up followers and no new replicas are
+    /// assigned to it. Request can be repeated to follow progress of drain.
+    #[derive(Encoder, Decoder, Default, Debug)]
+    pub struct DrainSpuRequest {
+        pub spu_id: SpuId,
+        pub cancel: bool,
+    }
+
+    impl DrainSpuRequest {
+        pub fn new(spu_id: SpuId) -> Self {
+            Self {
+                spu_id,
+                cancel: false,
+            }
+        }
+
+        pub fn cancel(spu_id: SpuId) -> Self {
+            Self {
+                spu_id,
+                cancel: true,
+            }
+        }
+    }
+
+    impl Request for DrainSpuRequest {
+        const API_KEY: u16 = AdminPublicApiKey::DrainSpu as u16;
+        const MIN_API_VERSION: i16 = 16;
+        const DEFAULT_API_VERSION: i16 = COMMON_VERSION;
+        type Response = DrainSpuResponse;
+    }
+
+    #[derive(Encoder, Decoder, Default, Debug)]
+    pub struct DrainSpuResponse {
+        pub error_code: ErrorCode,
+        pub error_message: Option<String>,
+        /// partitions still led by SPU
+        pub leaders: u32,
+        /// partitions led by SPU which have no other replica to take over leadership
+        pub blocked: u32,
+    }
+
+    impl ThrottledResponse for DrainSpuResponse {
+        fn throttled(error: ErrorCode) -> Option<Self> {
+            let msg = error.to_string();
+            Some(Self::error(error, msg))
+        }
+    }
+
+    impl DrainSpuResponse {
+        pub fn new(leaders: u32, blocked: u32) -> Self {
+            Self {
+                error_code: ErrorCode::None,
+                error_message: None,
+                leaders,
+                blocked,
+            }
+        }
+
+        pub fn error(error_code: ErrorCode, msg: impl Into<String>) -> Self {
+            Self {
+                error_code,
+                error_message: Some(msg.into()),
+                ..Default::default()
+            }
+        }
+
+        /// SPU doesn't lead any partition and can be shut down without disruption
+        pub fn is_drained(&self) -> bool {
+            self.error_code.is_ok() && self.leaders == 0
+        }
+
+        pub fn as_result(self) -> Result<Self, ApiError> {
+            if self.error_code.is_ok() {
+                Ok(self)
+            } else {
+                Err(ApiError::Code(self.error_code, self.error_message))
+            }
+        }
+    }
+}
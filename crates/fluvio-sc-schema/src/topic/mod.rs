@@ -11,6 +11,91 @@ pub mod validate {
     }
 }
 
+pub use add_partitions::*;
+
+mod add_partitions {
+
+    use fluvio_protocol::{Encoder, Decoder};
+    use fluvio_protocol::api::Request;
+    use fluvio_types::PartitionCount;
+
+    use crate::{AdminPublicApiKey, Status};
+    use crate::objects::COMMON_VERSION;
+
+    /// Increase partition count of existing topic with computed replicas.
+    /// `partitions` is the new total count and must be greater than current count.
+    ///
+    /// Existing partitions and their records are not moved. Since records with key are
+    /// routed by hashing key over partition count, a key may be routed to a different
+    /// partition after new partitions are added. Producers created before the increase
+    /// keep using the old partition count, so ordering by key is kept for their lifetime.
+    #[derive(Encoder, Decoder, Default, Debug)]
+    pub struct AddPartitionsRequest {
+        pub topic: String,
+        pub partitions: PartitionCount,
+    }
+
+    impl AddPartitionsRequest {
+        pub fn new(topic: impl Into<String>, partitions: PartitionCount) -> Self {
+            Self {
+                topic: topic.into(),
+                partitions,
+            }
+        }
+    }
+
+    impl Request for AddPartitionsRequest {
+        const API_KEY: u16 = AdminPublicApiKey::AddPartitions as u16;
+        const MIN_API_VERSION: i16 = 15;
+        const DEFAULT_API_VERSION: i16 = COMMON_VERSION;
+        type Response = Status;
+    }
+}
+
+pub use read_only::*;
+
+mod read_only {
+
+    use fluvio_protocol::{Encoder, Decoder};
+    use fluvio_protocol::api::Request;
+    use fluvio_types::PartitionId;
+
+    use crate::{AdminPublicApiKey, Status};
+    use crate::objects::COMMON_VERSION;
+
+    /// Mark topic or one of its partitions read-only, or writable again.
+    /// Produces to read-only partitions are rejected, fetches are still served.
+    /// Partitions added to read-only topic are read-only too.
+    #[derive(Encoder, Decoder, Default, Debug)]
+    pub struct SetReadOnlyRequest {
+        pub topic: String,
+        /// None applies to topic and all of its partitions
+        pub partition: Option<PartitionId>,
+        pub read_only: bool,
+    }
+
+    impl SetReadOnlyRequest {
+        pub fn new(
+            topic: impl Into<String>,
+            partition: Option<PartitionId>,
+            read_only: bool,
+        ) -> Self {
+            Self {
+                topic: topic.into(),
+                partition,
+                read_only,
+            }
+        }
+    }
+
+    impl Request for SetReadOnlyRequest {
+        const API_KEY: u16 = AdminPublicApiKey::SetReadOnly as u16;
+        const MIN_API_VERSION: i16 = 18;
+        const DEFAULT_API_VERSION: i16 = COMMON_VERSION;
+        type Response = Status;
+    }
+}
+
 mod convert {
 
     use crate::CreatableAdminSpec;
@@ -1,13 +1,25 @@
+#[cfg(not(all(feature = "jemalloc", target_os = "linux", target_env = "gnu")))]
 #[global_allocator]
 static GLOBAL: mimalloc::MiMalloc = mimalloc::MiMalloc;
 
+// jemalloc samples allocations, so heap profiles can be dumped by profiling endpoint.
+// It is only linked on glibc Linux, other targets keep mimalloc even with the feature
+#[cfg(all(feature = "jemalloc", target_os = "linux", target_env = "gnu"))]
+#[global_allocator]
+static GLOBAL: tikv_jemallocator::Jemalloc = tikv_jemallocator::Jemalloc;
+
+#[cfg(all(feature = "jemalloc", target_os = "linux", target_env = "gnu"))]
+#[allow(non_upper_case_globals)]
+#[export_name = "malloc_conf"]
+pub static malloc_conf: &[u8] = b"prof:true,prof_active:true,lg_prof_sample:19\0";
+
 use clap::Parser;
 
 use fluvio_sc::cli::ScOpt;
 use fluvio_sc::start::main_loop;
 
 fn main() {
-    fluvio_future::subscriber::init_tracer(None);
+    let _telemetry = fluvio_service::telemetry::init_tracer("fluvio-sc");
 
     let opt = ScOpt::parse();
     main_loop(opt);
@@ -24,11 +24,15 @@ use fluvio_types::print_cli_err;
 use fluvio_types::defaults::TLS_SERVER_SECRET_NAME;
 use fluvio_future::openssl::TlsAcceptor;
 use fluvio_future::openssl::SslVerifyMode;
+use fluvio_auth::token::TokenAuthOpt;
+use fluvio_auth::tls::{ReloadableTlsAcceptor, SharedTlsAcceptor};
+use fluvio_service::rate_limit::RateLimitConfig;
 
+use crate::services::auth::AuthConfig;
 use crate::services::auth::basic::BasicRbacPolicy;
-use crate::config::ScConfig;
+use crate::config::{ScConfig, VersionSkewPolicy};
 
-type Config = (ScConfig, Option<BasicRbacPolicy>);
+type Config = (ScConfig, AuthConfig);
 
 /// cli options
 #[derive(Debug, Parser)]
@@ -45,6 +49,15 @@ pub struct ScOpt {
     /// Address for internal service
     bind_private: Option<String>,
 
+    /// Address for Prometheus metrics endpoint, e.g. "0.0.0.0:9005". Disabled if not given
+    #[arg(long, value_name = "addr", env)]
+    bind_metrics: Option<String>,
+
+    /// Address for CPU and heap profiling endpoint, e.g. "127.0.0.1:9006". Disabled if not given.
+    /// Requires build with profiling feature
+    #[arg(long, value_name = "addr", env)]
+    bind_profiling: Option<String>,
+
     // k8 namespace
     #[arg(short = 'n', long = "namespace", value_name = "namespace")]
     namespace: Option<String>,
@@ -69,6 +82,36 @@ pub struct ScOpt {
     /// only allow white list of controllers
     #[arg(long)]
     white_list: Vec<String>,
+
+    /// package index to mirror Fluvio CLI releases from, e.g. "https://packages.fluvio.io/v1/".
+    /// Clients are recommended the CLI release matching the cluster
+    #[arg(long, value_name = "index url", env)]
+    cli_release_index: Option<String>,
+
+    /// File to append audit log of admin operations to, as JSON lines.
+    /// Recent operations are kept in memory if not given
+    #[arg(long, value_name = "path", env)]
+    audit_log: Option<PathBuf>,
+
+    /// Enforce ACLs on topics and consumer groups once there are entries.
+    /// Entries are stored as Acl metadata objects
+    #[arg(long, env)]
+    enable_acl: bool,
+
+    /// max admin requests per second of a client connection, unlimited if not set
+    #[arg(long, value_name = "integer", env)]
+    max_requests_per_connection: Option<u32>,
+
+    /// max admin requests per second of all connections of a principal, unlimited if not set
+    #[arg(long, value_name = "integer", env)]
+    max_requests_per_principal: Option<u32>,
+
+    /// Handling of SPUs whose major or minor version differs from SC during partial upgrades
+    #[arg(long, value_enum, default_value_t, env)]
+    version_skew_policy: VersionSkewPolicy,
+
+    #[command(flatten)]
+    token_auth: TokenAuthOpt,
 }
 
 #[derive(Debug, Args)]
@@ -130,6 +173,16 @@ impl ScOpt {
         config.x509_auth_scopes = self.x509_auth_scopes;
         config.white_list = self.white_list.into_iter().collect();
         config.read_only_metadata = self.run_mode.read_only.is_some();
+        config.cli_release_index = self.cli_release_index;
+        config.metrics_endpoint = self.bind_metrics;
+        config.profiling_endpoint = self.bind_profiling;
+        config.audit_log = self.audit_log;
+        config.acl_enabled = self.enable_acl;
+        config.version_skew_policy = self.version_skew_policy;
+        config.rate_limit = RateLimitConfig {
+            connection_requests_per_sec: self.max_requests_per_connection,
+            principal_requests_per_sec: self.max_requests_per_principal,
+        };
 
         // Set Configuration Authorization Policy
 
@@ -139,6 +192,10 @@ impl ScOpt {
             // Use root-only default policy if no policy path is found;
             None => None,
         };
+        let auth = AuthConfig {
+            policy,
+            token_validator: self.token_auth.validator()?,
+        };
 
         let mut tls = self.tls;
 
@@ -159,9 +216,9 @@ impl ScOpt {
                 .get_or_insert(TLS_SERVER_SECRET_NAME.to_string());
             info!("{:?}", tls);
 
-            Ok(((config, policy), Some((proxy_addr, tls))))
+            Ok(((config, auth), Some((proxy_addr, tls))))
         } else {
-            Ok(((config, policy), None))
+            Ok(((config, auth), None))
         }
     }
 
@@ -208,6 +265,22 @@ pub struct TlsConfig {
 }
 
 impl TlsConfig {
+    /// acceptor which is rebuilt when certificate, key or ca file changes
+    pub fn try_build_reloadable_tls_acceptor(&self) -> Result<SharedTlsAcceptor, IoError> {
+        let mut files: Vec<PathBuf> = self
+            .server_cert
+            .iter()
+            .chain(self.server_key.iter())
+            .map(PathBuf::from)
+            .collect();
+        if self.enable_client_cert {
+            files.extend(self.ca_cert.iter().map(PathBuf::from));
+        }
+
+        let config = self.clone();
+        ReloadableTlsAcceptor::shared(files, move || config.try_build_tls_acceptor())
+    }
+
     pub fn try_build_tls_acceptor(&self) -> Result<TlsAcceptor, IoError> {
         let server_crt_path = self
             .server_cert
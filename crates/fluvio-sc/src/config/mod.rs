@@ -3,6 +3,7 @@ mod sc_config;
 pub use self::sc_config::ScConfig;
 pub use self::sc_config::ScConfigBuilder;
 pub use self::sc_config::DEFAULT_NAMESPACE;
+pub use self::sc_config::VersionSkewPolicy;
 
 macro_rules! whitelist {
     ($config:expr,$name:expr,$start:expr) => {
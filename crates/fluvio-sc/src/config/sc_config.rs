@@ -8,9 +8,20 @@ use std::{io::Error as IoError, path::PathBuf};
 
 use fluvio_types::defaults::SC_PUBLIC_PORT;
 use fluvio_types::defaults::SC_PRIVATE_PORT;
+use fluvio_service::rate_limit::RateLimitConfig;
 
 pub const DEFAULT_NAMESPACE: &str = "default";
 
+/// how SC handles SPUs whose version is incompatible with its own
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum VersionSkewPolicy {
+    /// allow SPU to join, but log warning
+    #[default]
+    Warn,
+    /// reject registration of SPU
+    Deny,
+}
+
 // -----------------------------------
 // Traits
 // -----------------------------------
@@ -29,6 +40,21 @@ pub struct ScConfig {
     pub namespace: String,
     pub x509_auth_scopes: Option<PathBuf>,
     pub white_list: HashSet<String>,
+    /// package index to mirror CLI releases from, mirror is disabled if none
+    pub cli_release_index: Option<String>,
+    /// address of Prometheus metrics endpoint, endpoint is disabled if none
+    pub metrics_endpoint: Option<String>,
+    /// address of CPU and heap profiling endpoint, endpoint is disabled if none
+    pub profiling_endpoint: Option<String>,
+    /// file admin mutations are appended to, only recent ones are kept in memory if none
+    pub audit_log: Option<PathBuf>,
+    /// enforce ACL entries, everything is allowed if false
+    pub acl_enabled: bool,
+    /// bearer token authentication of public clients, disabled if no validator is configured
+    /// request rate limits of public clients
+    pub rate_limit: RateLimitConfig,
+    /// handling of SPUs with major or minor version different from SC
+    pub version_skew_policy: VersionSkewPolicy,
 }
 
 impl ::std::default::Default for ScConfig {
@@ -40,6 +66,13 @@ impl ::std::default::Default for ScConfig {
             namespace: DEFAULT_NAMESPACE.to_owned(),
             x509_auth_scopes: None,
             white_list: HashSet::new(),
+            cli_release_index: None,
+            metrics_endpoint: None,
+            profiling_endpoint: None,
+            audit_log: None,
+            acl_enabled: false,
+            rate_limit: RateLimitConfig::default(),
+            version_skew_policy: VersionSkewPolicy::default(),
         }
     }
 }
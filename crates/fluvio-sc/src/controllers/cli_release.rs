@@ -0,0 +1,76 @@
+//!
+//! # CLI Release Controller
+//!
+//! Mirrors the Fluvio CLI package from the package index, so clients can be recommended
+//! the CLI release matching the cluster. Only runs when a package index is configured.
+//!
+use std::time::Duration;
+
+use tracing::{debug, error, info, instrument};
+use anyhow::Result;
+
+use fluvio_future::{http_client, task::spawn, timer::sleep};
+use fluvio_future::http_client::ResponseExt;
+use fluvio_index::{HttpAgent, PackageId};
+use fluvio_stream_model::core::MetadataItem;
+
+use crate::core::SharedContext;
+use crate::stores::cli_release::SharedCliReleaseMirror;
+
+const FLUVIO_CLI_PACKAGE_ID: &str = "fluvio/fluvio";
+const CLI_RELEASE_REFRESH_INTERVAL: u64 = 3600;
+const CLI_RELEASE_RETRY_INTERVAL: u64 = 60;
+
+pub struct CliReleaseController {
+    agent: HttpAgent,
+    mirror: SharedCliReleaseMirror,
+}
+
+impl CliReleaseController {
+    pub fn start<C: MetadataItem>(ctx: SharedContext<C>) {
+        let Some(index) = &ctx.config().cli_release_index else {
+            debug!("no package index configured, not mirroring cli releases");
+            return;
+        };
+        let agent = match HttpAgent::with_base_url(index) {
+            Ok(agent) => agent,
+            Err(err) => {
+                error!(index, %err, "invalid package index, not mirroring cli releases");
+                return;
+            }
+        };
+        let controller = Self {
+            agent,
+            mirror: ctx.cli_releases().clone(),
+        };
+
+        info!(index, "starting cli release controller");
+        spawn(controller.dispatch_loop());
+    }
+
+    #[instrument(skip(self), name = "CliReleaseControllerLoop")]
+    async fn dispatch_loop(self) {
+        info!("started");
+        loop {
+            let interval = match self.refresh().await {
+                Ok(()) => CLI_RELEASE_REFRESH_INTERVAL,
+                Err(err) => {
+                    error!("unable to mirror cli releases: {:#?}", err);
+                    CLI_RELEASE_RETRY_INTERVAL
+                }
+            };
+            debug!("sleeping {} seconds to refresh", interval);
+            sleep(Duration::from_secs(interval)).await;
+        }
+    }
+
+    async fn refresh(&self) -> Result<()> {
+        let id: PackageId = FLUVIO_CLI_PACKAGE_ID.parse()?;
+        let uri = self.agent.request_package(&id)?.uri().to_string();
+        let body = http_client::get(&uri).await?.bytes().await?;
+        let package = self.agent.package_from_response(&body).await?;
+        debug!(uri, "mirrored cli releases");
+        self.mirror.update(package).await;
+        Ok(())
+    }
+}
@@ -1,4 +1,4 @@
-use std::time::{Duration, SystemTime};
+use std::time::{Duration, Instant, SystemTime};
 use tracing::{debug, error, info, instrument};
 use anyhow::{anyhow, Result};
 
@@ -15,7 +15,7 @@ use fluvio_sc_schema::{
 use fluvio_stream_dispatcher::store::StoreContext;
 use fluvio_controlplane_metadata::mirroring::{MirrorConnect, MirroringRemoteClusterRequest};
 
-use crate::core::SharedContext;
+use crate::core::{SharedContext, SharedScMetrics};
 
 const MIRRORING_CONTROLLER_INTERVAL: u64 = 5;
 const MIRRORING_CONTROLLER_RETRY_INTERVAL: u64 = 10;
@@ -25,6 +25,7 @@ const MIRRORING_CONTROLLER_RETRY_INTERVAL: u64 = 10;
 pub struct RemoteMirrorController<C: MetadataItem> {
     mirrors: StoreContext<MirrorSpec, C>,
     topics: StoreContext<TopicSpec, C>,
+    metrics: SharedScMetrics,
 }
 
 impl<C: MetadataItem> RemoteMirrorController<C> {
@@ -32,6 +33,7 @@ impl<C: MetadataItem> RemoteMirrorController<C> {
         let controller = Self {
             mirrors: ctx.mirrors().clone(),
             topics: ctx.topics().clone(),
+            metrics: ctx.metrics().clone(),
         };
 
         info!("starting mirroring controller");
@@ -101,6 +103,7 @@ impl<C: MetadataItem> RemoteMirrorController<C> {
                     .await?;
 
                 while let Some(response) = stream.next().await {
+                    let start = Instant::now();
                     let now = SystemTime::now()
                         .duration_since(SystemTime::UNIX_EPOCH)?
                         .as_millis();
@@ -171,6 +174,7 @@ impl<C: MetadataItem> RemoteMirrorController<C> {
                                 now as u64,
                             );
                             self.mirrors.update_status(home.id.clone(), status).await?;
+                            self.metrics.record_reconcile("mirroring", start.elapsed());
                         }
                         Err(err) => {
                             debug!("received error: {:#?}", err);
@@ -2,7 +2,7 @@
 //! # Partition Controller
 //!
 
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use fluvio_controlplane_metadata::store::ChangeListener;
 use fluvio_future::timer::sleep;
@@ -12,6 +12,7 @@ use fluvio_future::task::spawn;
 use fluvio_controlplane_metadata::core::MetadataItem;
 use fluvio_controlplane_metadata::store::k8::K8MetaItem;
 
+use crate::core::SharedScMetrics;
 use crate::stores::StoreContext;
 use crate::stores::partition::PartitionSpec;
 use crate::stores::spu::SpuSpec;
@@ -24,17 +25,23 @@ pub struct PartitionController<C: MetadataItem = K8MetaItem> {
     partitions: StoreContext<PartitionSpec, C>,
     spus: StoreContext<SpuSpec, C>,
     reducer: PartitionReducer<C>,
+    metrics: SharedScMetrics,
 }
 
 impl<C> PartitionController<C>
 where
     C: MetadataItem + 'static,
 {
-    pub fn start(partitions: StoreContext<PartitionSpec, C>, spus: StoreContext<SpuSpec, C>) {
+    pub fn start(
+        partitions: StoreContext<PartitionSpec, C>,
+        spus: StoreContext<SpuSpec, C>,
+        metrics: SharedScMetrics,
+    ) {
         let controller = Self {
             reducer: PartitionReducer::new(partitions.store().clone(), spus.store().clone()),
             partitions,
             spus,
+            metrics,
         };
 
         spawn(controller.dispatch_loop());
@@ -67,11 +74,28 @@ where
         let mut partition_listener = self.partitions.change_listener();
         let _ = partition_listener.wait_for_initial_sync().await;
 
+        let mut reassignment_listener = self.partitions.change_listener();
+        let _ = reassignment_listener.wait_for_initial_sync().await;
+
+        let mut drain_spu_listener = self.spus.change_listener();
+        let _ = drain_spu_listener.wait_for_initial_sync().await;
+
+        let mut drain_partition_listener = self.partitions.change_listener();
+        let _ = drain_partition_listener.wait_for_initial_sync().await;
+
         debug!("finish initializing listeners");
 
+        // drains in progress before start
+        self.process_drains().await;
+
         loop {
+            let start = Instant::now();
             self.sync_spu_changes(&mut spu_status_listener).await;
             self.sync_partition_changes(&mut partition_listener).await;
+            self.sync_reassignments(&mut reassignment_listener).await;
+            self.sync_drains(&mut drain_spu_listener, &mut drain_partition_listener)
+                .await;
+            self.metrics.record_reconcile("partition", start.elapsed());
 
             trace!("waiting for events");
 
@@ -82,6 +106,15 @@ where
                 },
                 _ = partition_listener.listen() => {
                     debug!("detected partition changes");
+                },
+                _ = reassignment_listener.listen() => {
+                    debug!("detected partition spec or status changes");
+                },
+                _ = drain_spu_listener.listen() => {
+                    debug!("detected spu spec changes");
+                },
+                _ = drain_partition_listener.listen() => {
+                    debug!("detected partition changes for drain");
                 }
 
             }
@@ -115,6 +148,67 @@ where
         }
     }
 
+    /// progress reassignments, leader offsets are reported in status
+    #[instrument(skip(self, listener))]
+    async fn sync_reassignments(&mut self, listener: &mut ChangeListener<PartitionSpec, C>) {
+        if !listener.has_change() {
+            trace!("no partitions change");
+            return;
+        }
+
+        let changes = listener.sync_changes().await;
+        if changes.is_empty() {
+            return;
+        }
+
+        let (updates, _) = changes.parts();
+        let reassigning: Vec<_> = updates
+            .into_iter()
+            .filter(|partition| partition.spec.is_reassigning())
+            .collect();
+        if reassigning.is_empty() {
+            return;
+        }
+
+        let actions = self.reducer.process_reassignments(reassigning).await;
+
+        debug!("generated reassignment actions: {}", actions.len());
+        for action in actions.into_iter() {
+            self.partitions.send_action(action).await;
+        }
+    }
+
+    /// move leaders off draining spus, followers catching up are reported in partition status
+    #[instrument(skip(self, spu_listener, partition_listener))]
+    async fn sync_drains(
+        &mut self,
+        spu_listener: &mut ChangeListener<SpuSpec, C>,
+        partition_listener: &mut ChangeListener<PartitionSpec, C>,
+    ) {
+        let mut changed = false;
+        if spu_listener.has_change() {
+            changed |= !spu_listener.sync_spec_changes().await.is_empty();
+        }
+        if partition_listener.has_change() {
+            changed |= !partition_listener.sync_changes().await.is_empty();
+        }
+        if !changed {
+            trace!("no drain changes");
+            return;
+        }
+
+        self.process_drains().await;
+    }
+
+    async fn process_drains(&mut self) {
+        let actions = self.reducer.process_drains().await;
+
+        debug!("generated drain actions: {}", actions.len());
+        for action in actions.into_iter() {
+            self.partitions.send_action(action).await;
+        }
+    }
+
     /// sync spu states to partition
     /// check to make sure
     async fn sync_spu_changes(&mut self, listener: &mut ChangeListener<SpuSpec, C>) {
@@ -3,6 +3,7 @@
 //!
 //! Partition metadata information on cached in the local Controller.
 //!
+use std::collections::HashSet;
 use std::sync::Arc;
 
 use fluvio_controlplane::PartitionMetadata;
@@ -10,10 +11,11 @@ use tracing::{debug, info, instrument};
 
 use fluvio_controlplane_metadata::store::k8::K8MetaItem;
 use fluvio_controlplane_metadata::core::MetadataItem;
+use fluvio_types::SpuId;
 
 use crate::stores::partition::{
     PartitionSpec, PartitionResolution, PartitionLocalStore, SimplePolicy, PartitonStatusExtension,
-    ElectionPolicy,
+    ElectionPolicy, PartitionLocalStorePolicy, DrainPolicy, AffinityPolicy,
 };
 use crate::stores::actions::WSAction;
 use crate::stores::spu::{SpuLocalStorePolicy, SpuLocalStore, SpuMetadata};
@@ -102,6 +104,60 @@ impl<C: MetadataItem> PartitionReducer<C> {
             .collect()
     }
 
+    /// move partitions with reassignment forward once target replicas are in sync with leader.
+    /// Leader is switched to target leader first, then replicas outside of target are removed.
+    #[instrument(skip(self, updates))]
+    pub async fn process_reassignments(
+        &self,
+        updates: Vec<PartitionMetadata<C>>,
+    ) -> Vec<PartitionWSAction<C>> {
+        let mut actions = vec![];
+        for partition in updates.into_iter() {
+            let Some(reassignment) = &partition.spec.reassignment else {
+                continue;
+            };
+            let Some(target_leader) = reassignment.target_leader() else {
+                continue;
+            };
+            if !is_target_in_sync(&partition) {
+                debug!(partition = %partition.key(), "target replicas are not in sync yet");
+                continue;
+            }
+
+            let mut spec = partition.spec.clone();
+            if spec.leader != target_leader {
+                info!(
+                    partition = %partition.key(),
+                    old_leader = spec.leader,
+                    target_leader,
+                    "reassignment: switching leader",
+                );
+                spec.set_leader(target_leader);
+                actions.push(PartitionWSAction::UpdateSpec((partition.key_owned(), spec)));
+            } else {
+                info!(
+                    partition = %partition.key(),
+                    replicas = ?reassignment.target,
+                    "reassignment: removing old replicas",
+                );
+                spec.replicas.clone_from(&reassignment.target);
+                spec.reassignment = None;
+
+                let mut status = partition.status.clone();
+                status
+                    .replicas
+                    .retain(|replica| spec.replicas.contains(&replica.spu));
+
+                actions.push(PartitionWSAction::UpdateSpec((partition.key_owned(), spec)));
+                actions.push(PartitionWSAction::UpdateStatus((
+                    partition.key_owned(),
+                    status,
+                )));
+            }
+        }
+        actions
+    }
+
     ///
     /// based on spu change, update election
     ///
@@ -119,24 +175,30 @@ impl<C: MetadataItem> PartitionReducer<C> {
         // remove init
         offline_spus.retain(|v| !v.status.is_init());
 
+        // load is tracked across elections so partitions of same spu are spread out
+        let mut policy = SimplePolicy::with_load(&self.partition_store.group_by_spu().await);
+
         // election due to offline spu
         debug!(offline = offline_spus.len(), "offline spus");
         for offline_spu in offline_spus.into_iter() {
-            self.force_election_spu_off(offline_spu, &mut actions).await;
+            self.force_election_spu_off(offline_spu, &mut policy, &mut actions)
+                .await;
         }
 
         // election due to online spu
         for online_spu in online_spus.into_iter() {
-            self.force_election_spu_on(online_spu, &mut actions).await;
+            self.force_election_spu_on(online_spu, &mut policy, &mut actions)
+                .await;
         }
         actions
     }
 
     /// perform election when spu goes offline
-    #[instrument(skip(self, offline_spu, actions))]
+    #[instrument(skip(self, offline_spu, policy, actions))]
     async fn force_election_spu_off(
         &self,
         offline_spu: SpuMetadata<C>,
+        policy: &mut SimplePolicy,
         actions: &mut Vec<PartitionWSAction<C>>,
     ) {
         debug!(
@@ -146,20 +208,34 @@ impl<C: MetadataItem> PartitionReducer<C> {
         let offline_leader_spu_id = offline_spu.spec.id;
 
         let spu_status = self.spu_store.online_status().await;
-
-        let policy = SimplePolicy::new();
+        // draining spus only take over leadership when there is no other candidate
+        let draining = self.spu_store.draining_spus().await;
+        let active_status: HashSet<SpuId> = spu_status.difference(&draining).copied().collect();
 
         // go thru each partitions whose leader matches offline spu.
-        for partition_kv_epoch in self.partition_store.read().await.values() {
+        let partitions = self.partition_store.read().await;
+        for partition_kv_epoch in partitions.values() {
             let partition_kv = partition_kv_epoch.inner();
             // find partition who's leader is same as offline spu
             if partition_kv.spec.leader == offline_leader_spu_id {
+                let affinity_policy = AffinityPolicy::new(
+                    &*policy,
+                    partition_kv.spec.leader_affinity.as_ref(),
+                    partitions.values().map(|partition| partition.inner()),
+                );
                 // find suitable leader
-                if let Some(candidate_leader) =
-                    partition_kv.status.candidate_leader(&spu_status, &policy)
-                {
+                let candidate_leader = partition_kv
+                    .status
+                    .candidate_leader(&active_status, &affinity_policy)
+                    .or_else(|| {
+                        partition_kv
+                            .status
+                            .candidate_leader(&spu_status, &affinity_policy)
+                    });
+                if let Some(candidate_leader) = candidate_leader {
+                    policy.add_leader(candidate_leader);
                     let mut part_kv_change = partition_kv.clone();
-                    part_kv_change.spec.leader = candidate_leader;
+                    part_kv_change.spec.set_leader(candidate_leader);
 
                     // we only change leader, status happens next cycle
                     actions.push(PartitionWSAction::UpdateSpec((
@@ -200,17 +276,76 @@ impl<C: MetadataItem> PartitionReducer<C> {
         }
     }
 
+    /// move leadership of partitions led by draining spus to replicas which have all
+    /// committed records. Partitions without such replica keep their leader until one catches up.
+    #[instrument(skip(self))]
+    pub async fn process_drains(&self) -> Vec<PartitionWSAction<C>> {
+        let mut actions = vec![];
+        let draining = self.spu_store.draining_spus().await;
+        if draining.is_empty() {
+            return actions;
+        }
+
+        let online: HashSet<SpuId> = self
+            .spu_store
+            .online_status()
+            .await
+            .difference(&draining)
+            .copied()
+            .collect();
+        let mut load = SimplePolicy::with_load(&self.partition_store.group_by_spu().await);
+
+        let partitions = self.partition_store.read().await;
+        for partition_kv_epoch in partitions.values() {
+            let partition = partition_kv_epoch.inner();
+            if !draining.contains(&partition.spec.leader)
+                // reassignment controls leader until it's completed
+                || partition.spec.is_reassigning()
+                // wait for current leader to report its offsets
+                || !partition.status.is_online()
+                || partition.status.leader.spu != partition.spec.leader
+            {
+                continue;
+            }
+
+            let drain_policy = DrainPolicy(&load);
+            let affinity_policy = AffinityPolicy::new(
+                &drain_policy,
+                partition.spec.leader_affinity.as_ref(),
+                partitions.values().map(|partition| partition.inner()),
+            );
+            let Some(candidate_leader) =
+                partition.status.candidate_leader(&online, &affinity_policy)
+            else {
+                debug!(partition = %partition.key(), "waiting for replica to catch up");
+                continue;
+            };
+            load.add_leader(candidate_leader);
+
+            info!(
+                partition = %partition.key(),
+                old_leader = partition.spec.leader,
+                candidate_leader,
+                "draining: moving leader",
+            );
+            let mut spec = partition.spec.clone();
+            spec.set_leader(candidate_leader);
+            actions.push(PartitionWSAction::UpdateSpec((partition.key_owned(), spec)));
+        }
+        actions
+    }
+
     /// perform election when spu become online
-    #[instrument(skip(self, online_spu, actions))]
+    #[instrument(skip(self, online_spu, policy, actions))]
     async fn force_election_spu_on(
         &self,
         online_spu: SpuMetadata<C>,
+        policy: &mut SimplePolicy,
         actions: &mut Vec<PartitionWSAction<C>>,
     ) {
         debug!(spu = %online_spu.key(),"performing election check spu online");
         let online_leader_spu_id = online_spu.spec.id;
 
-        let policy = SimplePolicy::new();
         // go thru each partitions which are not online and try to promote given online spu
 
         for partition_kv_epoch in self.partition_store.read().await.values() {
@@ -224,8 +359,9 @@ impl<C: MetadataItem> PartitionReducer<C> {
                                 .potential_leader_score(replica_status, &partition_kv.status.leader)
                                 .is_suitable()
                         {
+                            policy.add_leader(online_leader_spu_id);
                             let mut part_kv_change = partition_kv.clone();
-                            part_kv_change.spec.leader = online_leader_spu_id;
+                            part_kv_change.spec.set_leader(online_leader_spu_id);
                             actions.push(PartitionWSAction::UpdateSpec((
                                 part_kv_change.key_owned(),
                                 part_kv_change.spec,
@@ -258,6 +394,26 @@ impl<C: MetadataItem> PartitionReducer<C> {
     }
 }
 
+/// target replicas have all records committed by current leader
+fn is_target_in_sync<C: MetadataItem>(partition: &PartitionMetadata<C>) -> bool {
+    let Some(reassignment) = &partition.spec.reassignment else {
+        return false;
+    };
+    let status = &partition.status;
+    if !status.is_online() || status.leader.spu != partition.spec.leader || status.leader.hw < 0 {
+        return false;
+    }
+    reassignment
+        .target
+        .iter()
+        .filter(|spu| **spu != status.leader.spu)
+        .all(|spu| {
+            status
+                .replica_iter()
+                .any(|replica| replica.spu == *spu && replica.leo >= status.leader.hw)
+        })
+}
+
 // -----------------------------------
 //  Unit Tests
 //      >> utils::init_logger();
@@ -267,6 +423,203 @@ impl<C: MetadataItem> PartitionReducer<C> {
 #[cfg(test)]
 pub mod test {
 
+    use fluvio_controlplane_metadata::partition::{
+        PartitionReassignment, PartitionStatus, ReplicaStatus,
+    };
+
+    use crate::stores::spu::SpuMd;
+
+    use super::*;
+
+    fn reassigning_partition(
+        leader: ReplicaStatus,
+        replicas: Vec<ReplicaStatus>,
+    ) -> PartitionMetadata<K8MetaItem> {
+        let mut spec = PartitionSpec::new(leader.spu, vec![0, 1, 2]);
+        spec.reassignment = Some(PartitionReassignment::new(vec![2, 1]));
+        let mut partition = PartitionMetadata::with_spec(("topic1", 0), spec);
+        partition.set_status(PartitionStatus::new2(
+            leader,
+            replicas,
+            0,
+            PartitionResolution::Online,
+        ));
+        partition
+    }
+
+    #[fluvio_future::test]
+    async fn test_reassignment_waits_for_catch_up() {
+        let reducer = PartitionReducer::<K8MetaItem>::default();
+        let partition = reassigning_partition(
+            ReplicaStatus::new(0, 10, 10),
+            vec![ReplicaStatus::new(1, 10, 10), ReplicaStatus::new(2, 4, 5)],
+        );
+
+        let actions = reducer.process_reassignments(vec![partition]).await;
+        assert!(actions.is_empty());
+    }
+
+    #[fluvio_future::test]
+    async fn test_reassignment_switches_leader_then_completes() {
+        let reducer = PartitionReducer::<K8MetaItem>::default();
+        let partition = reassigning_partition(
+            ReplicaStatus::new(0, 10, 10),
+            vec![ReplicaStatus::new(1, 10, 10), ReplicaStatus::new(2, 10, 10)],
+        );
+
+        let actions = reducer.process_reassignments(vec![partition.clone()]).await;
+        assert_eq!(actions.len(), 1);
+        let PartitionWSAction::UpdateSpec((_, spec)) = &actions[0] else {
+            panic!("expected spec update");
+        };
+        assert_eq!(spec.leader, 2);
+        assert_eq!(spec.leader_epoch, 1);
+        assert_eq!(spec.replicas, vec![0, 1, 2]);
+        assert!(spec.is_reassigning());
+
+        // new leader reports status
+        let mut switched = partition;
+        switched.spec.leader = 2;
+        switched.set_status(PartitionStatus::new2(
+            ReplicaStatus::new(2, 10, 10),
+            vec![ReplicaStatus::new(1, 10, 10), ReplicaStatus::new(0, 10, 10)],
+            0,
+            PartitionResolution::Online,
+        ));
+
+        let actions = reducer.process_reassignments(vec![switched]).await;
+        assert_eq!(actions.len(), 2);
+        let PartitionWSAction::UpdateSpec((_, spec)) = &actions[0] else {
+            panic!("expected spec update");
+        };
+        assert_eq!(spec.leader, 2);
+        assert_eq!(spec.replicas, vec![2, 1]);
+        assert!(!spec.is_reassigning());
+        let PartitionWSAction::UpdateStatus((_, status)) = &actions[1] else {
+            panic!("expected status update");
+        };
+        assert_eq!(status.replicas, vec![ReplicaStatus::new(1, 10, 10)]);
+    }
+
+    #[fluvio_future::test]
+    async fn test_election_spreads_leaders_of_offline_spu() {
+        let partition = |idx: u32| {
+            let mut partition =
+                PartitionMetadata::with_spec(("topic1", idx), PartitionSpec::new(0, vec![0, 1, 2]));
+            partition.set_status(PartitionStatus::new2(
+                ReplicaStatus::new(0, 10, 10),
+                vec![ReplicaStatus::new(1, 10, 10), ReplicaStatus::new(2, 10, 10)],
+                0,
+                PartitionResolution::Online,
+            ));
+            partition
+        };
+        let partition_store = PartitionLocalStore::bulk_new(vec![partition(0), partition(1)]);
+        let spu_store = SpuLocalStore::<K8MetaItem>::quick(vec![
+            (0, false, None),
+            (1, true, None),
+            (2, true, None),
+        ]);
+        let reducer = PartitionReducer::new(partition_store, spu_store);
+
+        let mut offline_spu = SpuMetadata::quick(("spu-0", 0, false, None));
+        offline_spu.status.set_offline();
+        let actions = reducer
+            .update_election_from_spu_changes(vec![offline_spu])
+            .await;
+
+        // both followers are caught up, second partition goes to spu which doesn't lead yet
+        let mut leaders: Vec<_> = actions
+            .iter()
+            .map(|action| match action {
+                PartitionWSAction::UpdateSpec((_, spec)) => spec.leader,
+                _ => panic!("expected spec update"),
+            })
+            .collect();
+        leaders.sort();
+        assert_eq!(leaders, vec![1, 2]);
+    }
+
+    #[fluvio_future::test]
+    async fn test_election_honors_leader_affinity() {
+        use fluvio_controlplane_metadata::topic::LeaderAffinity;
+
+        let mut spec = PartitionSpec::new(0, vec![0, 1, 2]);
+        spec.leader_affinity = Some(LeaderAffinity {
+            preferred_leaders: vec![2],
+            ..Default::default()
+        });
+        let mut partition = PartitionMetadata::with_spec(("topic1", 0), spec);
+        // spu 1 is more caught up, but spu 2 is preferred
+        partition.set_status(PartitionStatus::new2(
+            ReplicaStatus::new(0, 10, 10),
+            vec![ReplicaStatus::new(1, 10, 10), ReplicaStatus::new(2, 8, 9)],
+            0,
+            PartitionResolution::Online,
+        ));
+        let partition_store = PartitionLocalStore::bulk_new(vec![partition]);
+        let spu_store = SpuLocalStore::<K8MetaItem>::quick(vec![
+            (0, false, None),
+            (1, true, None),
+            (2, true, None),
+        ]);
+        let reducer = PartitionReducer::new(partition_store, spu_store);
+
+        let mut offline_spu = SpuMetadata::quick(("spu-0", 0, false, None));
+        offline_spu.status.set_offline();
+        let actions = reducer
+            .update_election_from_spu_changes(vec![offline_spu])
+            .await;
+        assert_eq!(actions.len(), 1);
+        let PartitionWSAction::UpdateSpec((_, spec)) = &actions[0] else {
+            panic!("expected spec update");
+        };
+        assert_eq!(spec.leader, 2);
+    }
+
+    #[fluvio_future::test]
+    async fn test_drain_moves_leader_to_caught_up_replica() {
+        let partition = |idx: u32, replicas: Vec<ReplicaStatus>| {
+            let mut partition =
+                PartitionMetadata::with_spec(("topic1", idx), PartitionSpec::new(0, vec![0, 1, 2]));
+            partition.set_status(PartitionStatus::new2(
+                ReplicaStatus::new(0, 10, 12),
+                replicas,
+                0,
+                PartitionResolution::Online,
+            ));
+            partition
+        };
+        let partition_store = PartitionLocalStore::bulk_new(vec![
+            partition(
+                0,
+                vec![ReplicaStatus::new(1, 10, 10), ReplicaStatus::new(2, 5, 6)],
+            ),
+            // no replica has all committed records yet
+            partition(
+                1,
+                vec![ReplicaStatus::new(1, 5, 8), ReplicaStatus::new(2, 5, 6)],
+            ),
+        ]);
+        let mut draining = SpuMetadata::quick(("spu-0", 0, true, None));
+        draining.spec.draining = true;
+        let spu_store = SpuLocalStore::<K8MetaItem>::bulk_new(vec![
+            draining,
+            SpuMetadata::quick(("spu-1", 1, true, None)),
+            SpuMetadata::quick(("spu-2", 2, true, None)),
+        ]);
+        let reducer = PartitionReducer::new(partition_store, spu_store);
+
+        let actions = reducer.process_drains().await;
+        assert_eq!(actions.len(), 1);
+        let PartitionWSAction::UpdateSpec((key, spec)) = &actions[0] else {
+            panic!("expected spec update");
+        };
+        assert_eq!(key.partition, 0);
+        assert_eq!(spec.leader, 1);
+        assert_eq!(spec.replicas, vec![0, 1, 2]);
+    }
+
     /*
     #[fluvio_future::test]
     async fn test_process_partition_actions_without_partitions()  {
@@ -1,10 +1,13 @@
-use std::{collections::BTreeMap, ops::Deref};
+use std::{
+    collections::{BTreeMap, HashMap},
+    ops::Deref,
+};
 
-use tracing::{instrument, debug, trace};
+use tracing::{instrument, debug, trace, warn};
 
-use fluvio_controlplane_metadata::topic::{TopicReplicaParam, PartitionMaps};
+use fluvio_controlplane_metadata::topic::{TopicReplicaParam, PartitionMaps, LeaderAffinity};
 use fluvio_stream_model::core::MetadataItem;
-use fluvio_types::{PartitionId, SpuId, ReplicaMap, ReplicationFactor};
+use fluvio_types::{PartitionCount, PartitionId, SpuId, ReplicaMap, ReplicationFactor};
 
 use crate::stores::{
     spu::{SpuLocalStore, SpuLocalStorePolicy},
@@ -69,6 +72,9 @@ pub(crate) struct PartitionScheduler<'a, C: MetadataItem> {
     spus: &'a SpuLocalStore<C>,
     partitions: &'a PartitionLocalStore<C>,
     scheduling_groups: ReplicaSchedulingGroups,
+    rack_warning: Option<String>,
+    /// affinity of leaders and spus they avoid
+    leader_affinity: Option<(LeaderAffinity, Vec<SpuId>)>,
 }
 
 impl<'a, C> PartitionScheduler<'a, C>
@@ -84,7 +90,58 @@ where
             spus,
             partitions,
             scheduling_groups,
+            rack_warning: None,
+            leader_affinity: None,
+        }
+    }
+
+    /// leaders of generated partitions follow affinity when possible
+    pub(crate) async fn set_leader_affinity(&mut self, affinity: Option<&LeaderAffinity>) {
+        self.leader_affinity = match affinity {
+            Some(affinity) => Some((
+                affinity.clone(),
+                self.partitions.avoided_leaders(affinity).await,
+            )),
+            None => None,
+        };
+    }
+
+    /// find spu for replica, leader is chosen from spus with best affinity rank
+    fn find_spu(
+        &self,
+        spus: &[SpuId],
+        reserved: &[SpuId],
+        r_idx: ReplicationFactor,
+    ) -> Option<SpuId> {
+        if r_idx != 0 {
+            return self.scheduling_groups.find_suitable_spu(
+                spus,
+                reserved,
+                SpuWeightSelection::Follower,
+            );
         }
+        let Some((affinity, avoided)) = &self.leader_affinity else {
+            return self.scheduling_groups.find_suitable_spu(
+                spus,
+                reserved,
+                SpuWeightSelection::Leader,
+            );
+        };
+        let mut ranks: Vec<u16> = spus
+            .iter()
+            .map(|spu| affinity.rank(*spu, avoided))
+            .collect();
+        ranks.sort_unstable();
+        ranks.dedup();
+        ranks.into_iter().find_map(|rank| {
+            let ranked: Vec<SpuId> = spus
+                .iter()
+                .filter(|spu| affinity.rank(**spu, avoided) == rank)
+                .copied()
+                .collect();
+            self.scheduling_groups
+                .find_suitable_spu(&ranked, reserved, SpuWeightSelection::Leader)
+        })
     }
 
     pub(crate) fn spus(&self) -> &'a SpuLocalStore<C> {
@@ -95,10 +152,15 @@ where
         self.partitions
     }
 
+    /// warning from last rack aware generation if replicas could not be spread across racks
+    pub(crate) fn take_rack_warning(&mut self) -> Option<String> {
+        self.rack_warning.take()
+    }
+
     /// Generate replica map for a specific topic
     #[instrument(level = "debug")]
     pub async fn generate_replica_map_for_topic(
-        &'a mut self,
+        &mut self,
         param: &TopicReplicaParam,
     ) -> ReplicaPartitionMap {
         let spu_count = self.spus.count().await as ReplicationFactor;
@@ -108,17 +170,115 @@ where
                 spu_count, "insufficient spu count"
             );
             ReplicaPartitionMap::default()
+        } else if !param.ignore_rack_assignment && self.spus.spus_in_rack_count().await > 0 {
+            self.generate_partitions_with_rack(param).await
         } else {
             self.generate_partitions_without_rack(param).await
         }
     }
 
+    /// Generate replica map for partitions added to a topic which already has `existing` partitions.
+    /// Existing partitions are not rescheduled, new partitions are numbered after them.
+    pub async fn generate_replica_map_for_new_partitions(
+        &mut self,
+        param: &TopicReplicaParam,
+        existing: PartitionCount,
+    ) -> ReplicaPartitionMap {
+        let added = TopicReplicaParam {
+            partitions: param.partitions.saturating_sub(existing),
+            ..param.clone()
+        };
+        let replica_map = self.generate_replica_map_for_topic(&added).await;
+        replica_map
+            .0
+            .into_iter()
+            .map(|(idx, replicas)| (idx + existing, replicas))
+            .collect::<ReplicaMap>()
+            .into()
+    }
+
+    /// Generate partitions with replicas of each partition spread across racks.
+    /// SPUs without rack are considered to be in their own rack.
+    /// If there are not enough racks, remaining replicas are placed in already used racks
+    /// and rack warning is set.
+    pub(crate) async fn generate_partitions_with_rack(
+        &mut self,
+        param: &TopicReplicaParam,
+    ) -> ReplicaPartitionMap {
+        let mut online_spus = self.spus.schedulable_spu_ids().await;
+        online_spus.sort_unstable();
+
+        let spu_racks: HashMap<SpuId, String> = self
+            .spus
+            .online_spu_rack_map()
+            .await
+            .into_iter()
+            .flat_map(|(rack, spus)| spus.into_iter().map(move |spu| (spu, rack.clone())))
+            .collect();
+
+        trace!(?online_spus, ?spu_racks, "online");
+        let mut spread_violated = false;
+        let mut partition_map = BTreeMap::new();
+        for p_idx in 0..param.partitions {
+            let mut reserved_spus: Vec<SpuId> = vec![];
+            let mut used_racks: Vec<&String> = vec![];
+            for r_idx in 0..param.replication_factor {
+                let unused_rack_spus: Vec<SpuId> = online_spus
+                    .iter()
+                    .filter(|spu| {
+                        spu_racks
+                            .get(spu)
+                            .map_or(true, |rack| !used_racks.contains(&rack))
+                    })
+                    .copied()
+                    .collect();
+
+                let spu = match self.find_spu(&unused_rack_spus, &reserved_spus, r_idx) {
+                    Some(spu) => spu,
+                    None => {
+                        spread_violated = true;
+                        match self.find_spu(&online_spus, &reserved_spus, r_idx) {
+                            Some(spu) => spu,
+                            None => {
+                                trace!("no suitable spu found");
+                                return BTreeMap::new().into();
+                            }
+                        }
+                    }
+                };
+
+                trace!(spu, "found spu");
+                reserved_spus.push(spu);
+                if let Some(rack) = spu_racks.get(&spu) {
+                    used_racks.push(rack);
+                }
+                if r_idx == 0 {
+                    self.scheduling_groups.increase_leaders(spu);
+                } else {
+                    self.scheduling_groups.increase_followers(spu);
+                }
+            }
+            partition_map.insert(p_idx as PartitionId, reserved_spus);
+        }
+
+        if spread_violated {
+            let warning = format!(
+                "not enough racks to place {} replicas in different racks",
+                param.replication_factor
+            );
+            warn!("{warning}");
+            self.rack_warning = Some(warning);
+        }
+
+        partition_map.into()
+    }
+
     /// Generate partitions without taking rack assignments into consideration
     pub(crate) async fn generate_partitions_without_rack(
         &mut self,
         param: &TopicReplicaParam,
     ) -> ReplicaPartitionMap {
-        let mut online_spus = self.spus.online_spu_ids().await;
+        let mut online_spus = self.spus.schedulable_spu_ids().await;
         online_spus.sort_unstable();
 
         trace!(?online_spus, "online");
@@ -127,15 +287,7 @@ where
             let mut reserved_spus: Vec<i32> = vec![]; // spu reserved
             for r_idx in 0..param.replication_factor {
                 // for each replica, they must be on different spu, anti-affinity
-                if let Some(spu) = self.scheduling_groups.find_suitable_spu(
-                    &online_spus,
-                    &reserved_spus,
-                    if r_idx == 0 {
-                        SpuWeightSelection::Leader
-                    } else {
-                        SpuWeightSelection::Follower
-                    },
-                ) {
+                if let Some(spu) = self.find_spu(&online_spus, &reserved_spus, r_idx) {
                     trace!(spu, "found spu");
                     reserved_spus.push(spu);
                     if r_idx == 0 {
@@ -162,7 +314,7 @@ where
 pub mod replica_map_test {
 
     use crate::stores::{
-        spu::{SpuAdminStore, DefaultSpuStore},
+        spu::{SpuAdminStore, DefaultSpuStore, SpuMd, SpuMetadata},
         partition::{PartitionAdminStore, DefaultPartitionStore},
     };
 
@@ -188,6 +340,30 @@ pub mod replica_map_test {
         );
     }
 
+    #[fluvio_future::test]
+    async fn generate_replica_skips_draining_spu() {
+        let mut draining = SpuMetadata::quick(("spu-1", 1, true, None));
+        draining.spec.draining = true;
+        let spus = SpuAdminStore::bulk_new(vec![
+            SpuMetadata::quick(("spu-0", 0, true, None)),
+            draining,
+            SpuMetadata::quick(("spu-2", 2, true, None)),
+        ]);
+        let partitions = PartitionAdminStore::new_shared();
+
+        let param = TopicReplicaParam {
+            partitions: 2,
+            replication_factor: 2,
+            ignore_rack_assignment: false,
+        };
+        let mut scheduler = PartitionScheduler::init(&spus, &partitions).await;
+        let expected: ReplicaPartitionMap = vec![(0, vec![0, 2]), (1, vec![2, 0])].into();
+        assert_eq!(
+            scheduler.generate_partitions_without_rack(&param).await,
+            expected
+        );
+    }
+
     #[fluvio_future::test]
     async fn generate_replica_partition_more_spu() {
         let spus = SpuAdminStore::quick(vec![(0, true, None), (1, true, None)]);
@@ -208,6 +384,24 @@ pub mod replica_map_test {
         );
     }
 
+    #[fluvio_future::test]
+    async fn generate_replica_map_for_new_partitions() {
+        let spus = SpuAdminStore::quick(vec![(0, true, None), (1, true, None)]);
+        let partitions = PartitionAdminStore::new_shared();
+
+        let param = TopicReplicaParam {
+            partitions: 4,
+            replication_factor: 1,
+            ignore_rack_assignment: false,
+        };
+        let mut scheduler = PartitionScheduler::init(&spus, &partitions).await;
+        let added = scheduler
+            .generate_replica_map_for_new_partitions(&param, 2)
+            .await;
+        assert!(added.scheduled());
+        assert_eq!(added.keys().copied().collect::<Vec<_>>(), vec![2, 3]);
+    }
+
     #[fluvio_future::test]
     async fn generate_replica_partition_not_enough_follower() {
         let spus = SpuAdminStore::quick(vec![(0, true, None), (1, true, None)]);
@@ -295,4 +489,91 @@ pub mod replica_map_test {
 
         assert_eq!(actual, expect);
     }
+
+    #[fluvio_future::test]
+    async fn generate_replica_map_spread_across_racks() {
+        let spus = SpuAdminStore::quick(vec![
+            (0, true, Some("r1".to_string())),
+            (1, true, Some("r1".to_string())),
+            (2, true, Some("r2".to_string())),
+            (3, true, Some("r2".to_string())),
+        ]);
+        let partitions = PartitionAdminStore::new_shared();
+
+        let param = TopicReplicaParam {
+            partitions: 4,
+            replication_factor: 2,
+            ignore_rack_assignment: false,
+        };
+        let mut scheduler = PartitionScheduler::init(&spus, &partitions).await;
+        let replica_map = scheduler.generate_replica_map_for_topic(&param).await;
+
+        assert_eq!(replica_map.len(), 4);
+        for replicas in replica_map.values() {
+            let racks: Vec<bool> = replicas.iter().map(|spu| *spu < 2).collect();
+            assert_ne!(racks[0], racks[1], "replicas {replicas:?} in same rack");
+        }
+        assert!(scheduler.take_rack_warning().is_none());
+    }
+
+    #[fluvio_future::test]
+    async fn generate_replica_map_not_enough_racks() {
+        let spus = SpuAdminStore::quick(vec![
+            (0, true, Some("r1".to_string())),
+            (1, true, Some("r1".to_string())),
+            (2, true, Some("r1".to_string())),
+        ]);
+        let partitions = PartitionAdminStore::new_shared();
+
+        let param = TopicReplicaParam {
+            partitions: 1,
+            replication_factor: 2,
+            ignore_rack_assignment: false,
+        };
+        let mut scheduler = PartitionScheduler::init(&spus, &partitions).await;
+        let replica_map = scheduler.generate_replica_map_for_topic(&param).await;
+
+        assert_eq!(replica_map.len(), 1);
+        assert!(scheduler.take_rack_warning().is_some());
+    }
+
+    #[fluvio_future::test]
+    async fn generate_replica_map_with_leader_affinity() {
+        let spus = SpuAdminStore::quick(vec![
+            (0, true, None),
+            (1, true, None),
+            (2, true, None),
+            (3, true, None),
+        ]);
+        let partitions = PartitionAdminStore::bulk_load(vec![(("noisy", 0), vec![1])]);
+
+        let param = TopicReplicaParam {
+            partitions: 2,
+            replication_factor: 1,
+            ignore_rack_assignment: false,
+        };
+        let mut scheduler = PartitionScheduler::init(&spus, &partitions).await;
+        let affinity = LeaderAffinity {
+            preferred_leaders: vec![1, 2],
+            anti_affinity_topics: vec!["noisy".to_string()],
+        };
+        scheduler.set_leader_affinity(Some(&affinity)).await;
+        // spu 1 is preferred but leads noisy topic
+        let expected: ReplicaPartitionMap = vec![(0, vec![2]), (1, vec![2])].into();
+        assert_eq!(
+            scheduler.generate_replica_map_for_topic(&param).await,
+            expected
+        );
+
+        // no preferred spu is online, affinity is ignored
+        let affinity = LeaderAffinity {
+            preferred_leaders: vec![7],
+            ..Default::default()
+        };
+        scheduler.set_leader_affinity(Some(&affinity)).await;
+        assert_eq!(
+            scheduler.generate_replica_map_for_topic(&param).await.len(),
+            2
+        );
+    }
 }
@@ -1,7 +1,7 @@
 //!
 //! # Spu Controller
 
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use std::io::Error as IoError;
 
 use fluvio_future::timer::sleep;
@@ -10,7 +10,7 @@ use tracing::{debug, info, error, trace, instrument};
 
 use fluvio_future::task::spawn;
 
-use crate::core::SharedContext;
+use crate::core::{SharedContext, SharedScMetrics};
 use crate::stores::StoreContext;
 use crate::stores::spu::*;
 
@@ -19,6 +19,7 @@ use crate::stores::spu::*;
 pub struct SpuController<C: MetadataItem> {
     spus: StoreContext<SpuSpec, C>,
     health_check: SharedHealthCheck,
+    metrics: SharedScMetrics,
     counter: u64, // how many time we have been sync
 }
 
@@ -27,6 +28,7 @@ impl<C: MetadataItem + 'static> SpuController<C> {
         let controller = Self {
             spus: ctx.spus().clone(),
             health_check: ctx.health().clone(),
+            metrics: ctx.metrics().clone(),
             counter: 0,
         };
 
@@ -58,7 +60,9 @@ impl<C: MetadataItem + 'static> SpuController<C> {
         debug!("finished initializing listeners");
 
         loop {
+            let start = Instant::now();
             self.sync_store().await?;
+            self.metrics.record_reconcile("spu", start.elapsed());
 
             select! {
                 _ = spu_listener.listen() => {
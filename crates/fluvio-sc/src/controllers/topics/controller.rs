@@ -14,12 +14,12 @@ use fluvio_stream_dispatcher::actions::WSAction;
 use fluvio_stream_model::core::MetadataItem;
 use fluvio_stream_model::store::ChangeListener;
 use fluvio_stream_model::store::k8::K8MetaItem;
-use fluvio_types::defaults::{STORAGE_RETENTION_SECONDS, CONSUMER_STORAGE_TOPIC};
+use fluvio_types::defaults::{STORAGE_RETENTION_SECONDS, CONSUMER_STORAGE_TOPIC, EVENTS_TOPIC};
 use tracing::{info, instrument, trace, debug};
 
 use fluvio_future::task::spawn;
 
-use crate::core::SharedContext;
+use crate::core::{SharedContext, SharedScMetrics};
 use crate::stores::topic::TopicSpec;
 use crate::stores::partition::PartitionSpec;
 use crate::stores::StoreContext;
@@ -30,6 +30,9 @@ use super::reducer::TopicReducer;
 const OFFSET_TOPIC_SEGMENT_SIZE: u32 = 512_000_000; // 512MB
 const OFFSET_TOPIC_PARTITION_SIZE: u64 = OFFSET_TOPIC_SEGMENT_SIZE as u64 * 4; // 2GB
 const OFFSET_TOPIC_RETENTION_SEC: u32 = STORAGE_RETENTION_SECONDS; // 7 days
+const EVENTS_TOPIC_SEGMENT_SIZE: u32 = 64_000_000; // 64MB
+const EVENTS_TOPIC_PARTITION_SIZE: u64 = EVENTS_TOPIC_SEGMENT_SIZE as u64 * 16; // 1GB
+const EVENTS_TOPIC_RETENTION_SEC: u32 = STORAGE_RETENTION_SECONDS; // 7 days
 
 #[derive(Debug)]
 pub struct TopicController<C: MetadataItem = K8MetaItem> {
@@ -37,6 +40,7 @@ pub struct TopicController<C: MetadataItem = K8MetaItem> {
     topics: StoreContext<TopicSpec, C>,
     partitions: StoreContext<PartitionSpec, C>,
     reducer: TopicReducer<C>,
+    metrics: SharedScMetrics,
 }
 
 impl<C> TopicController<C>
@@ -59,6 +63,7 @@ where
             topics,
             partitions,
             spus,
+            metrics: ctx.metrics().clone(),
         };
 
         spawn(controller.dispatch_loop());
@@ -68,7 +73,7 @@ where
 impl<C: MetadataItem> TopicController<C> {
     #[instrument(name = "TopicController", skip(self))]
     async fn dispatch_loop(mut self) {
-        use std::time::Duration;
+        use std::time::{Duration, Instant};
 
         use tokio::select;
         use fluvio_future::timer::sleep;
@@ -79,8 +84,10 @@ impl<C: MetadataItem> TopicController<C> {
         let mut spus_listener = self.spus.change_listener();
 
         loop {
+            let start = Instant::now();
             self.sync_topics(&mut topics_listener).await;
             self.sync_spus(&mut spus_listener).await;
+            self.metrics.record_reconcile("topic", start.elapsed());
 
             select! {
 
@@ -198,38 +205,60 @@ where
 
         loop {
             sleep(Duration::from_secs(interval_secs)).await;
-            self.ensure_offsets_topic_exists().await;
+            self.ensure_topic_exists(
+                CONSUMER_STORAGE_TOPIC,
+                OFFSET_TOPIC_RETENTION_SEC,
+                OFFSET_TOPIC_SEGMENT_SIZE,
+                OFFSET_TOPIC_PARTITION_SIZE,
+            )
+            .await;
+            self.ensure_topic_exists(
+                EVENTS_TOPIC,
+                EVENTS_TOPIC_RETENTION_SEC,
+                EVENTS_TOPIC_SEGMENT_SIZE,
+                EVENTS_TOPIC_PARTITION_SIZE,
+            )
+            .await;
             interval_secs = min(MAX_INTERVAL, interval_secs.add(INTERVAL_STEP));
         }
     }
 
-    async fn ensure_offsets_topic_exists(&mut self) {
+    /// create single partition system topic if it doesn't exist
+    async fn ensure_topic_exists(
+        &mut self,
+        topic: &str,
+        retention_secs: u32,
+        segment_size: u32,
+        max_partition_size: u64,
+    ) {
         if self
             .topics
             .store()
             .read()
             .await
             .values()
-            .any(|value| value.key().eq(CONSUMER_STORAGE_TOPIC))
+            .any(|value| value.key().eq(topic))
         {
-            trace!(CONSUMER_STORAGE_TOPIC, "topic exists");
+            trace!(topic, "topic exists");
         } else {
             let mut spec = TopicSpec::new_computed(1, 1, None);
             spec.set_system(true);
             spec.set_cleanup_policy(CleanupPolicy::Segment(SegmentBasedPolicy {
-                time_in_seconds: OFFSET_TOPIC_RETENTION_SEC,
+                time_in_seconds: retention_secs,
             }));
             spec.set_storage(TopicStorageConfig {
-                segment_size: Some(OFFSET_TOPIC_SEGMENT_SIZE),
-                max_partition_size: Some(OFFSET_TOPIC_PARTITION_SIZE),
+                segment_size: Some(segment_size),
+                max_partition_size: Some(max_partition_size),
+                durability: None,
+                backend: None,
+                min_in_sync_replicas: None,
+                index_interval_bytes: None,
+                index_max_bytes: None,
             });
             self.topics
-                .send_action(WSAction::UpdateSpec((
-                    CONSUMER_STORAGE_TOPIC.to_string(),
-                    spec,
-                )))
+                .send_action(WSAction::UpdateSpec((topic.to_string(), spec)))
                 .await;
-            info!(CONSUMER_STORAGE_TOPIC, "topic created");
+            info!(topic, "topic created");
         }
     }
 }
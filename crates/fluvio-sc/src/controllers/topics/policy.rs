@@ -4,7 +4,7 @@ use tracing::{debug, instrument};
 
 use fluvio_controlplane_metadata::partition::PartitionMirrorConfig;
 use fluvio_controlplane_metadata::partition::RemotePartitionConfig;
-use fluvio_types::PartitionId;
+use fluvio_types::{PartitionCount, PartitionId};
 
 use fluvio_controlplane::PartitionMetadata;
 use fluvio_controlplane_metadata::topic::MirrorConfig;
@@ -177,11 +177,56 @@ impl<C: MetadataItem> TopicNextState<C> {
         }
     }
 
+    /// partition count of provisioned topic has been increased.
+    /// Schedule replicas only for new partitions and keep existing replica map as is
+    async fn add_partitions<'a>(
+        topic: &'a TopicMetadata<C>,
+        param: &TopicReplicaParam,
+        existing: PartitionCount,
+        scheduler: &'a mut PartitionScheduler<'a, C>,
+    ) -> TopicNextState<C> {
+        let added = scheduler
+            .generate_replica_map_for_new_partitions(param, existing)
+            .await;
+        if !added.scheduled() {
+            return TopicNextState {
+                resolution: TopicResolution::Provisioned,
+                reason: format!(
+                    "insufficient resources to add partitions {existing}..{}",
+                    param.partitions
+                ),
+                ..Default::default()
+            };
+        }
+
+        debug!(
+            topic = %topic.key(),
+            existing,
+            partitions = param.partitions,
+            "adding partitions"
+        );
+        let mut replica_map = topic.status.replica_map.clone();
+        replica_map.extend(ReplicaMap::from(added));
+
+        let mut updated = topic.clone();
+        updated.status.set_replica_map(replica_map.clone());
+        TopicNextState {
+            resolution: TopicResolution::Provisioned,
+            reason: scheduler.take_rack_warning().unwrap_or_default(),
+            partitions: updated.create_new_partitions(scheduler.partitions()).await,
+            replica_map: replica_map.into(),
+            ..Default::default()
+        }
+    }
+
     /// given topic, compute next state
     pub async fn compute_next_state<'a>(
         topic: &'a TopicMetadata<C>,
         scheduler: &'a mut PartitionScheduler<'a, C>,
     ) -> TopicNextState<C> {
+        scheduler
+            .set_leader_affinity(topic.spec().get_leader_affinity())
+            .await;
         match topic.spec().replicas() {
             // Computed Topic
             ReplicaSpec::Computed(ref param) => match topic.status.resolution {
@@ -197,6 +242,7 @@ impl<C: MetadataItem> TopicNextState<C> {
                         );
                         TopicNextState {
                             resolution: TopicResolution::Provisioned,
+                            reason: scheduler.take_rack_warning().unwrap_or_default(),
                             replica_map,
                             ..Default::default()
                         }
@@ -215,6 +261,10 @@ impl<C: MetadataItem> TopicNextState<C> {
                     );
                     let mut next_state = TopicNextState::same_next_state(topic);
                     if next_state.resolution == TopicResolution::Provisioned {
+                        let existing = topic.status.replica_map.len() as PartitionCount;
+                        if param.partitions > existing {
+                            return Self::add_partitions(topic, param, existing, scheduler).await;
+                        }
                         debug!("creating new partitions");
                         next_state.partitions =
                             topic.create_new_partitions(scheduler.partitions()).await;
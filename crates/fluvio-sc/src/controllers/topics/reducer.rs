@@ -166,6 +166,7 @@ impl<C: MetadataItem> TopicReducer<C> {
         // apply changes to topics
         if updated_topic.status.resolution != topic.status.resolution
             || updated_topic.status.reason != topic.status.reason
+            || updated_topic.status.replica_map != topic.status.replica_map
         {
             debug!(
                 topic = %topic.key(),
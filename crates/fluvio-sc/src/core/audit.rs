@@ -0,0 +1,152 @@
+//!
+//! # Audit Log
+//!
+//! Records admin mutations with the principal who requested them. Recent entries are kept in
+//! memory to be queried by clients, and every entry is appended as a JSON line to the audit
+//! file if one is configured.
+//!
+use std::collections::VecDeque;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use serde_json::{json, Value};
+use tracing::{error, info};
+
+use fluvio_sc_schema::audit::AuditEntry;
+
+/// number of recent entries kept in memory
+const RECENT_ENTRIES: usize = 1000;
+
+pub type SharedAuditLog = Arc<AuditLog>;
+
+#[derive(Debug, Default)]
+pub struct AuditLog {
+    recent: Mutex<VecDeque<AuditEntry>>,
+    sink: Option<Mutex<File>>,
+}
+
+impl AuditLog {
+    /// audit log appending to `path`, entries are only kept in memory if none
+    pub fn shared(path: Option<&Path>) -> SharedAuditLog {
+        let sink =
+            path.and_then(
+                |path| match OpenOptions::new().create(true).append(true).open(path) {
+                    Ok(file) => {
+                        info!(path = %path.display(), "appending audit log");
+                        Some(Mutex::new(file))
+                    }
+                    Err(err) => {
+                        error!(path = %path.display(), %err, "unable to open audit log");
+                        None
+                    }
+                },
+            );
+
+        Arc::new(Self {
+            recent: Mutex::default(),
+            sink,
+        })
+    }
+
+    pub fn record(&self, entry: AuditEntry) {
+        info!(
+            principal = entry.principal.as_deref().unwrap_or_default(),
+            operation = %entry.operation,
+            object = %entry.object,
+            name = %entry.name,
+            error_code = ?entry.error_code,
+            "audit"
+        );
+
+        if let Some(sink) = &self.sink {
+            if let Ok(mut file) = sink.lock() {
+                if let Err(err) = writeln!(file, "{}", json_line(&entry)) {
+                    error!(%err, "unable to append audit log");
+                }
+            }
+        }
+
+        if let Ok(mut recent) = self.recent.lock() {
+            if recent.len() == RECENT_ENTRIES {
+                recent.pop_front();
+            }
+            recent.push_back(entry);
+        }
+    }
+
+    /// up to `limit` most recent entries, oldest first. All kept entries if `limit` is 0
+    pub fn recent(&self, limit: usize) -> Vec<AuditEntry> {
+        let Ok(recent) = self.recent.lock() else {
+            return vec![];
+        };
+        let skip = if limit == 0 {
+            0
+        } else {
+            recent.len().saturating_sub(limit)
+        };
+        recent.iter().skip(skip).cloned().collect()
+    }
+}
+
+fn json_line(entry: &AuditEntry) -> Value {
+    json!({
+        "ts": entry.timestamp_ms,
+        "principal": entry.principal,
+        "operation": entry.operation,
+        "object": entry.object,
+        "name": entry.name,
+        "summary": entry.summary,
+        "error_code": if entry.is_ok() {
+            Value::Null
+        } else {
+            format!("{:?}", entry.error_code).into()
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use fluvio_protocol::link::ErrorCode;
+
+    use super::*;
+
+    fn entry(name: &str) -> AuditEntry {
+        AuditEntry {
+            principal: Some("alice".to_owned()),
+            operation: "create".to_owned(),
+            object: "Topic".to_owned(),
+            name: name.to_owned(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_recent_entries() {
+        let audit = AuditLog::shared(None);
+        for i in 0..(RECENT_ENTRIES + 5) {
+            audit.record(entry(&format!("topic-{i}")));
+        }
+
+        let all = audit.recent(0);
+        assert_eq!(all.len(), RECENT_ENTRIES);
+        assert_eq!(all[0].name, "topic-5");
+
+        let last = audit.recent(2);
+        assert_eq!(last.len(), 2);
+        assert_eq!(last[1].name, format!("topic-{}", RECENT_ENTRIES + 4));
+    }
+
+    #[test]
+    fn test_json_line() {
+        let mut failed = entry("topic-1");
+        failed.error_code = ErrorCode::TopicAlreadyExists;
+
+        let line = json_line(&failed);
+        assert_eq!(line["principal"], "alice");
+        assert_eq!(line["name"], "topic-1");
+        assert_eq!(line["error_code"], "TopicAlreadyExists");
+        assert!(json_line(&entry("topic-2"))["error_code"].is_null());
+    }
+}
@@ -6,17 +6,23 @@
 use std::sync::Arc;
 
 use fluvio_sc_schema::mirror::MirrorSpec;
+use fluvio_service::rate_limit::{RateLimiter, SharedRateLimiter};
 use fluvio_stream_model::core::MetadataItem;
 
 use crate::config::ScConfig;
+use crate::stores::acl::*;
 use crate::stores::spu::*;
 use crate::stores::partition::*;
 use crate::stores::topic::*;
 use crate::stores::spg::*;
 use crate::stores::smartmodule::*;
 use crate::stores::tableformat::*;
+use crate::stores::cli_release::*;
 use crate::stores::*;
 
+use super::audit::{AuditLog, SharedAuditLog};
+use super::metrics::{ScMetrics, SharedScMetrics};
+
 pub type SharedContext<C> = Arc<Context<C>>;
 pub type K8SharedContext = Arc<Context<K8MetaItem>>;
 
@@ -31,7 +37,12 @@ pub struct Context<C: MetadataItem> {
     smartmodules: StoreContext<SmartModuleSpec, C>,
     tableformats: StoreContext<TableFormatSpec, C>,
     mirrors: StoreContext<MirrorSpec, C>,
+    acls: StoreContext<AclSpec, C>,
     health: SharedHealthCheck,
+    cli_releases: SharedCliReleaseMirror,
+    metrics: SharedScMetrics,
+    audit: SharedAuditLog,
+    rate_limiter: SharedRateLimiter,
     config: ScConfig,
 }
 
@@ -54,7 +65,12 @@ impl<C: MetadataItem> Context<C> {
             smartmodules: StoreContext::new(),
             tableformats: StoreContext::new(),
             mirrors: StoreContext::new(),
+            acls: StoreContext::new(),
             health: HealthCheck::shared(),
+            cli_releases: CliReleaseMirror::shared(),
+            metrics: ScMetrics::shared(),
+            audit: AuditLog::shared(config.audit_log.as_deref()),
+            rate_limiter: RateLimiter::shared(config.rate_limit),
             config,
         }
     }
@@ -90,11 +106,36 @@ impl<C: MetadataItem> Context<C> {
         &self.mirrors
     }
 
+    /// access control lists, enforced if enabled in config
+    pub fn acls(&self) -> &StoreContext<AclSpec, C> {
+        &self.acls
+    }
+
     /// spu health channel
     pub fn health(&self) -> &SharedHealthCheck {
         &self.health
     }
 
+    /// CLI releases mirrored from package index
+    pub fn cli_releases(&self) -> &SharedCliReleaseMirror {
+        &self.cli_releases
+    }
+
+    /// controller metrics, exported by metrics endpoint
+    pub fn metrics(&self) -> &SharedScMetrics {
+        &self.metrics
+    }
+
+    /// admin mutations
+    pub fn audit(&self) -> &SharedAuditLog {
+        &self.audit
+    }
+
+    /// request budgets of public clients
+    pub fn rate_limiter(&self) -> &SharedRateLimiter {
+        &self.rate_limiter
+    }
+
     /// reference to config
     pub fn config(&self) -> &ScConfig {
         &self.config
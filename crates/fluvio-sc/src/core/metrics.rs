@@ -0,0 +1,136 @@
+//!
+//! # SC Metrics
+//!
+//! Counts reconciliation loops of controllers and their latency. Exported in Prometheus text
+//! format by the metrics endpoint, together with gauges computed from the metadata stores.
+//!
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// upper bounds of reconciliation latency buckets, in seconds
+const RECONCILE_BUCKETS: [f64; 8] = [0.001, 0.005, 0.01, 0.05, 0.1, 0.5, 1.0, 5.0];
+
+pub type SharedScMetrics = Arc<ScMetrics>;
+
+#[derive(Debug, Default)]
+pub struct ScMetrics {
+    controllers: Mutex<BTreeMap<&'static str, ReconcileStats>>,
+}
+
+#[derive(Debug, Default, Clone)]
+struct ReconcileStats {
+    loops: u64,
+    /// observations per bucket, not cumulative
+    buckets: [u64; RECONCILE_BUCKETS.len()],
+    sum_secs: f64,
+}
+
+impl ScMetrics {
+    pub fn shared() -> SharedScMetrics {
+        Arc::new(Self::default())
+    }
+
+    /// record one loop of `controller`, which took `elapsed` to reconcile
+    pub fn record_reconcile(&self, controller: &'static str, elapsed: Duration) {
+        let secs = elapsed.as_secs_f64();
+        if let Ok(mut controllers) = self.controllers.lock() {
+            let stats = controllers.entry(controller).or_default();
+            stats.loops += 1;
+            stats.sum_secs += secs;
+            if let Some(bucket) = RECONCILE_BUCKETS.iter().position(|le| secs <= *le) {
+                stats.buckets[bucket] += 1;
+            }
+        }
+    }
+
+    /// write controller metrics in Prometheus text format
+    pub fn write_prometheus(&self, out: &mut String) {
+        let controllers = self
+            .controllers
+            .lock()
+            .map(|controllers| controllers.clone())
+            .unwrap_or_default();
+
+        let _ = writeln!(
+            out,
+            "# HELP fluvio_sc_controller_loops_total Reconciliation loops run by controller"
+        );
+        let _ = writeln!(out, "# TYPE fluvio_sc_controller_loops_total counter");
+        for (controller, stats) in &controllers {
+            let _ = writeln!(
+                out,
+                "fluvio_sc_controller_loops_total{{controller=\"{controller}\"}} {}",
+                stats.loops
+            );
+        }
+
+        let _ = writeln!(
+            out,
+            "# HELP fluvio_sc_reconcile_duration_seconds Latency of reconciliation loops"
+        );
+        let _ = writeln!(out, "# TYPE fluvio_sc_reconcile_duration_seconds histogram");
+        for (controller, stats) in &controllers {
+            let mut cumulative = 0;
+            for (le, count) in RECONCILE_BUCKETS.iter().zip(stats.buckets) {
+                cumulative += count;
+                let _ = writeln!(
+                    out,
+                    "fluvio_sc_reconcile_duration_seconds_bucket{{controller=\"{controller}\",le=\"{le}\"}} {cumulative}"
+                );
+            }
+            let _ = writeln!(
+                out,
+                "fluvio_sc_reconcile_duration_seconds_bucket{{controller=\"{controller}\",le=\"+Inf\"}} {}",
+                stats.loops
+            );
+            let _ = writeln!(
+                out,
+                "fluvio_sc_reconcile_duration_seconds_sum{{controller=\"{controller}\"}} {}",
+                stats.sum_secs
+            );
+            let _ = writeln!(
+                out,
+                "fluvio_sc_reconcile_duration_seconds_count{{controller=\"{controller}\"}} {}",
+                stats.loops
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reconcile_histogram() {
+        let metrics = ScMetrics::default();
+        metrics.record_reconcile("spu", Duration::from_micros(500));
+        metrics.record_reconcile("spu", Duration::from_millis(20));
+        metrics.record_reconcile("spu", Duration::from_secs(10));
+        metrics.record_reconcile("topic", Duration::from_millis(2));
+
+        let mut out = String::new();
+        metrics.write_prometheus(&mut out);
+        let lines: Vec<&str> = out.lines().collect();
+
+        assert!(lines.contains(&"fluvio_sc_controller_loops_total{controller=\"spu\"} 3"));
+        assert!(lines.contains(&"fluvio_sc_controller_loops_total{controller=\"topic\"} 1"));
+        assert!(lines.contains(
+            &"fluvio_sc_reconcile_duration_seconds_bucket{controller=\"spu\",le=\"0.001\"} 1"
+        ));
+        assert!(lines.contains(
+            &"fluvio_sc_reconcile_duration_seconds_bucket{controller=\"spu\",le=\"0.05\"} 2"
+        ));
+        assert!(lines.contains(
+            &"fluvio_sc_reconcile_duration_seconds_bucket{controller=\"spu\",le=\"5\"} 2"
+        ));
+        assert!(lines.contains(
+            &"fluvio_sc_reconcile_duration_seconds_bucket{controller=\"spu\",le=\"+Inf\"} 3"
+        ));
+        assert!(
+            lines.contains(&"fluvio_sc_reconcile_duration_seconds_count{controller=\"topic\"} 1")
+        );
+    }
+}
@@ -1,2 +1,7 @@
+mod audit;
 mod context;
+mod metrics;
+
+pub use self::audit::*;
 pub use self::context::*;
+pub use self::metrics::*;
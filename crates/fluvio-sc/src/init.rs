@@ -9,20 +9,22 @@ use std::sync::Arc;
 use fluvio_sc_schema::mirror::MirrorSpec;
 use fluvio_stream_dispatcher::metadata::{SharedClient, MetadataClient};
 use fluvio_stream_model::core::MetadataItem;
+use fluvio_service::profiling::start_profiling;
 
 use crate::controllers::mirroring::controller::RemoteMirrorController;
+use crate::controllers::cli_release::CliReleaseController;
 use crate::core::Context;
 use crate::core::SharedContext;
 use crate::controllers::partitions::PartitionController;
 use crate::controllers::spus::SpuController;
 use crate::controllers::topics::controller::{TopicController, SystemTopicController};
 use crate::config::ScConfig;
-use crate::services::start_internal_server;
+use crate::services::{start_internal_server, start_metrics_server};
 use crate::dispatcher::dispatcher::MetadataDispatcher;
-use crate::services::auth::basic::BasicRbacPolicy;
+use crate::services::auth::AuthConfig;
 
 pub async fn start_main_loop<C, M>(
-    sc_config_policy: (ScConfig, Option<BasicRbacPolicy>),
+    sc_config_auth: (ScConfig, AuthConfig),
     metadata_client: SharedClient<C>,
 ) -> crate::core::SharedContext<M>
 where
@@ -36,8 +38,9 @@ where
     use crate::stores::spg::SpuGroupSpec;
     use crate::stores::tableformat::TableFormatSpec;
     use crate::stores::smartmodule::SmartModuleSpec;
+    use crate::stores::acl::AclSpec;
 
-    let (sc_config, auth_policy) = sc_config_policy;
+    let (sc_config, auth) = sc_config_auth;
 
     let namespace = sc_config.namespace.clone();
     let ctx = Context::shared_metadata(sc_config);
@@ -84,14 +87,17 @@ where
         ctx.mirrors().clone(),
     );
 
-    start_main_loop_services(ctx, auth_policy).await
+    MetadataDispatcher::<AclSpec, C, M>::start(
+        namespace.clone(),
+        metadata_client.clone(),
+        ctx.acls().clone(),
+    );
+
+    start_main_loop_services(ctx, auth).await
 }
 
 /// start the main loop
-async fn start_main_loop_services<C>(
-    ctx: Arc<Context<C>>,
-    auth_policy: Option<BasicRbacPolicy>,
-) -> SharedContext<C>
+async fn start_main_loop_services<C>(ctx: Arc<Context<C>>, auth: AuthConfig) -> SharedContext<C>
 where
     C: MetadataItem + 'static,
     C::UId: Send + Sync,
@@ -104,20 +110,29 @@ where
     whitelist!(
         config,
         "partition",
-        PartitionController::start(ctx.partitions().clone(), ctx.spus().clone())
+        PartitionController::start(
+            ctx.partitions().clone(),
+            ctx.spus().clone(),
+            ctx.metrics().clone()
+        )
     );
 
     whitelist!(config, "internal", start_internal_server(ctx.clone()));
+    whitelist!(config, "public", pub_server::start(ctx.clone(), auth));
     whitelist!(
         config,
-        "public",
-        pub_server::start(ctx.clone(), auth_policy)
+        "mirroring",
+        RemoteMirrorController::start(ctx.clone())
     );
     whitelist!(
         config,
-        "mirroring",
-        RemoteMirrorController::start(ctx.clone())
+        "cli_release",
+        CliReleaseController::start(ctx.clone())
     );
+    whitelist!(config, "metrics", start_metrics_server(ctx.clone()));
+    if let Some(addr) = &config.profiling_endpoint {
+        start_profiling(addr);
+    }
 
     mod pub_server {
 
@@ -128,15 +143,24 @@ where
         use crate::core::SharedContext;
 
         use fluvio_controlplane_metadata::core::MetadataItem;
-        use crate::services::auth::{AuthGlobalContext, RootAuthorization, ReadOnlyAuthorization};
-        use crate::services::auth::basic::{BasicAuthorization, BasicRbacPolicy};
+        use crate::services::auth::{
+            AuthConfig, AuthGlobalContext, RootAuthorization, ReadOnlyAuthorization,
+        };
+        use crate::services::auth::basic::BasicAuthorization;
+        use crate::services::auth::token::TokenAuthorization;
 
-        pub fn start<C>(ctx: SharedContext<C>, auth_policy_option: Option<BasicRbacPolicy>)
+        pub fn start<C>(ctx: SharedContext<C>, auth: AuthConfig)
         where
             C: MetadataItem + 'static,
             C::UId: Send + Sync,
         {
-            if let Some(policy) = auth_policy_option {
+            if let Some(validator) = auth.token_validator {
+                info!("using token authorization");
+                start_public_server(AuthGlobalContext::new(
+                    ctx,
+                    Arc::new(TokenAuthorization::new(validator, auth.policy)),
+                ));
+            } else if let Some(policy) = auth.policy {
                 info!("using basic authorization");
                 start_public_server(AuthGlobalContext::new(
                     ctx,
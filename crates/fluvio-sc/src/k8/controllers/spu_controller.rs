@@ -164,7 +164,11 @@ impl K8SpuController {
             let spec = spg_obj.spec();
             let replicas = spec.replicas;
             for i in 0..replicas {
-                let (spu_name, spu) = spg_obj.as_spu(i, &services);
+                let (spu_name, mut spu) = spg_obj.as_spu(i, &services);
+                // drain is set by admin, not by group
+                if let Some(current) = self.spus.store().spec(&spu_name).await {
+                    spu.spec.draining = current.draining;
+                }
 
                 debug!(id=i,spu=?spu,"applying spu");
 
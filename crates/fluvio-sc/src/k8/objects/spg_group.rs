@@ -137,6 +137,7 @@ impl SpuGroupObj {
                 port: spu_public_ep.port,
                 encryption: spu_public_ep.encryption,
             }),
+            draining: false,
         };
 
         /*
@@ -187,7 +188,7 @@ mod k8_convert {
     };
     use fluvio_types::defaults::{
         SPU_DEFAULT_NAME, SPU_PUBLIC_PORT, SPU_PRIVATE_PORT, SC_PRIVATE_PORT, PRODUCT_NAME,
-        TLS_SERVER_SECRET_NAME,
+        TLS_SERVER_SECRET_NAME, FLV_LOG_FORMAT,
     };
 
     use crate::stores::spg::SpuGroupSpec;
@@ -233,6 +234,10 @@ mod k8_convert {
             env.push(Env::key_value("RUST_LOG", &rust_log));
         }
 
+        if let Ok(log_format) = std::env::var(FLV_LOG_FORMAT) {
+            env.push(Env::key_value(FLV_LOG_FORMAT, &log_format));
+        }
+
         env.append(&mut spu_pod_config.extra_env.clone());
 
         let mut volume_mounts = vec![VolumeMount {
@@ -70,6 +70,10 @@ impl AuthContext for BasicAuthContext {
     ) -> Result<bool, AuthError> {
         Ok(true)
     }
+
+    fn principal(&self) -> Option<&str> {
+        Some(&self.identity.principal)
+    }
 }
 
 /// basic policy module
@@ -1,4 +1,5 @@
 pub mod basic;
+pub mod token;
 
 pub use common::*;
 
@@ -6,16 +7,31 @@ mod common {
 
     use std::sync::Arc;
     use std::fmt::Debug;
+    use std::time::{SystemTime, UNIX_EPOCH};
 
     use async_trait::async_trait;
 
     use fluvio_auth::{AuthContext, Authorization, TypeAction, InstanceAction, AuthError};
+    use fluvio_auth::token::SharedTokenValidator;
     use fluvio_socket::FluvioSocket;
     use fluvio_controlplane_metadata::extended::ObjectType;
     use fluvio_stream_model::core::MetadataItem;
+    use fluvio_protocol::link::ErrorCode;
+    use fluvio_sc_schema::audit::AuditEntry;
+    use fluvio_sc_schema::acl::{is_allowed, AclPermission, AclResourceType};
 
     use crate::core::SharedContext;
 
+    use super::basic::BasicRbacPolicy;
+
+    /// Authorization configured at startup
+    #[derive(Debug, Default, Clone)]
+    pub struct AuthConfig {
+        pub policy: Option<BasicRbacPolicy>,
+        /// clients must authenticate with bearer token if set
+        pub token_validator: Option<SharedTokenValidator>,
+    }
+
     /// SC global context with authorization
     /// auth is trait object which contains global auth auth policy
     #[derive(Clone, Debug)]
@@ -97,6 +113,58 @@ mod common {
         }
     }
 
+    impl<AC, C> AuthServiceContext<AC, C>
+    where
+        AC: AuthContext,
+        C: MetadataItem,
+    {
+        /// record admin mutation requested by this context in audit log
+        pub fn audit(
+            &self,
+            operation: &str,
+            object: &str,
+            name: &str,
+            summary: impl Into<String>,
+            error_code: &ErrorCode,
+        ) {
+            let timestamp_ms = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|elapsed| elapsed.as_millis() as u64)
+                .unwrap_or_default();
+            self.global_ctx.audit().record(AuditEntry {
+                timestamp_ms,
+                principal: self.auth.principal().map(str::to_owned),
+                operation: operation.to_owned(),
+                object: object.to_owned(),
+                name: name.to_owned(),
+                summary: summary.into(),
+                error_code: error_code.clone(),
+            });
+        }
+
+        /// check ACL of principal of this context, everything is allowed if ACLs are disabled
+        /// or there are no entries
+        pub async fn allow_acl(
+            &self,
+            resource_type: AclResourceType,
+            resource: &str,
+            permission: AclPermission,
+        ) -> bool {
+            if !self.global_ctx.config().acl_enabled {
+                return true;
+            }
+            let entries = self.global_ctx.acls().store().clone_specs().await;
+            entries.is_empty()
+                || is_allowed(
+                    &entries,
+                    self.auth.principal(),
+                    resource_type,
+                    resource,
+                    permission,
+                )
+        }
+    }
+
     /// Authorization that allows only read only ops
     #[derive(Debug, Clone)]
     pub struct ReadOnlyAuthorization {}
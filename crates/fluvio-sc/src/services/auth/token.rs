@@ -0,0 +1,130 @@
+use std::sync::Arc;
+
+use tracing::instrument;
+use async_trait::async_trait;
+
+use fluvio_auth::{AuthContext, Authorization, TypeAction, InstanceAction, AuthError};
+use fluvio_auth::token::{authenticate, SharedTokenValidator};
+use fluvio_auth::x509::X509Identity;
+use fluvio_controlplane_metadata::extended::ObjectType;
+
+use super::basic::BasicRbacPolicy;
+
+/// Authorization of clients presenting bearer token.
+/// Token identity is evaluated against policy, without policy only tokens with `Root` scope
+/// are allowed, same as default basic policy.
+#[derive(Debug, Clone)]
+pub struct TokenAuthorization {
+    validator: SharedTokenValidator,
+    policy: Arc<BasicRbacPolicy>,
+}
+
+impl TokenAuthorization {
+    pub fn new(validator: SharedTokenValidator, policy: Option<BasicRbacPolicy>) -> Self {
+        Self {
+            validator,
+            policy: Arc::new(policy.unwrap_or_default()),
+        }
+    }
+}
+
+#[async_trait]
+impl Authorization for TokenAuthorization {
+    type Context = TokenAuthContext;
+
+    #[instrument(level = "trace", skip(self, socket))]
+    async fn create_auth_context(
+        &self,
+        socket: &mut fluvio_socket::FluvioSocket,
+    ) -> Result<Self::Context, AuthError> {
+        let identity = authenticate(socket, self.validator.as_ref())
+            .await
+            .map_err(|err| {
+                tracing::error!(%err, "token authentication failed");
+                err
+            })?;
+        Ok(TokenAuthContext {
+            identity,
+            policy: self.policy.clone(),
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct TokenAuthContext {
+    identity: X509Identity,
+    policy: Arc<BasicRbacPolicy>,
+}
+
+#[async_trait]
+impl AuthContext for TokenAuthContext {
+    async fn allow_type_action(
+        &self,
+        ty: ObjectType,
+        action: TypeAction,
+    ) -> Result<bool, AuthError> {
+        self.policy
+            .evaluate(action.into(), ty, None, &self.identity)
+            .await
+    }
+
+    /// check if specific instance of spec can be deleted
+    async fn allow_instance_action(
+        &self,
+        ty: ObjectType,
+        action: InstanceAction,
+        key: &str,
+    ) -> Result<bool, AuthError> {
+        self.policy
+            .evaluate(action.into(), ty, Some(key), &self.identity)
+            .await
+    }
+
+    fn principal(&self) -> Option<&str> {
+        Some(&self.identity.principal)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use fluvio_auth::{AuthContext, TypeAction, InstanceAction};
+    use fluvio_auth::x509::X509Identity;
+    use fluvio_controlplane_metadata::extended::ObjectType;
+
+    use super::{BasicRbacPolicy, TokenAuthContext};
+
+    /// only scopes bound to roles of policy are allowed, default policy allows `Root` scope
+    #[fluvio_future::test]
+    async fn test_token_context() {
+        let policy = Arc::new(BasicRbacPolicy::default());
+
+        let reader = TokenAuthContext {
+            identity: X509Identity::new("alice".to_owned(), vec!["Reader".to_owned()]),
+            policy: policy.clone(),
+        };
+        assert_eq!(reader.principal(), Some("alice"));
+        assert!(!reader
+            .allow_type_action(ObjectType::Topic, TypeAction::Create)
+            .await
+            .unwrap());
+        assert!(!reader
+            .allow_instance_action(ObjectType::Topic, InstanceAction::Delete, "orders")
+            .await
+            .unwrap());
+
+        let root = TokenAuthContext {
+            identity: X509Identity::new("bob".to_owned(), vec!["Root".to_owned()]),
+            policy,
+        };
+        assert!(root
+            .allow_type_action(ObjectType::Topic, TypeAction::Create)
+            .await
+            .unwrap());
+        assert!(root
+            .allow_instance_action(ObjectType::Topic, InstanceAction::Delete, "orders")
+            .await
+            .unwrap());
+    }
+}
@@ -0,0 +1,228 @@
+//!
+//! # Metrics Endpoint
+//!
+//! Serves SC metrics over HTTP at `/metrics` in Prometheus text format: controller loops and
+//! reconciliation latencies, connected SPUs, partition states and mirrors.
+//!
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::io::Error as IoError;
+
+use async_net::{TcpListener, TcpStream};
+use futures_util::{AsyncReadExt, AsyncWriteExt, StreamExt};
+use tracing::{debug, error, info};
+
+use fluvio_future::task::spawn;
+use fluvio_sc_schema::mirror::{ConnectionStatus, MirrorPairStatus, MirrorType};
+use fluvio_stream_model::core::MetadataItem;
+
+use crate::core::{Context, SharedContext};
+use crate::stores::partition::PartitionResolution;
+use crate::stores::spu::SpuStatusResolution;
+
+const METRICS_PATH: &str = "/metrics";
+const MAX_REQUEST_BYTES: usize = 4096;
+
+/// start metrics endpoint, if an address to bind is configured
+pub fn start_metrics_server<C>(ctx: SharedContext<C>)
+where
+    C: MetadataItem + 'static,
+{
+    let Some(addr) = ctx.config().metrics_endpoint.clone() else {
+        debug!("no metrics address, metrics endpoint disabled");
+        return;
+    };
+    spawn(async move {
+        if let Err(err) = serve(&addr, ctx).await {
+            error!(addr, "error running metrics endpoint: {}", err);
+        }
+    });
+}
+
+async fn serve<C>(addr: &str, ctx: SharedContext<C>) -> Result<(), IoError>
+where
+    C: MetadataItem + 'static,
+{
+    let listener = TcpListener::bind(addr).await?;
+    info!(addr, "metrics endpoint started");
+    let mut incoming = listener.incoming();
+    while let Some(stream) = incoming.next().await {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(err) => {
+                error!("error accepting connection: {}", err);
+                continue;
+            }
+        };
+        let ctx = ctx.clone();
+        spawn(async move {
+            if let Err(err) = respond(stream, &ctx).await {
+                debug!("error responding to metrics request: {}", err);
+            }
+        });
+    }
+    Ok(())
+}
+
+async fn respond<C: MetadataItem>(mut stream: TcpStream, ctx: &Context<C>) -> Result<(), IoError> {
+    let mut buf = [0u8; MAX_REQUEST_BYTES];
+    let read = stream.read(&mut buf).await?;
+    let request = String::from_utf8_lossy(&buf[..read]);
+    let (status, body) = match request_path(&request) {
+        Some(METRICS_PATH) => ("200 OK", render_metrics(ctx).await),
+        _ => ("404 Not Found", "not found\n".to_owned()),
+    };
+    let response = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.close().await
+}
+
+/// path of GET request, without query
+fn request_path(request: &str) -> Option<&str> {
+    let mut parts = request.lines().next()?.split_whitespace();
+    if parts.next()? != "GET" {
+        return None;
+    }
+    parts.next()?.split('?').next()
+}
+
+/// all metrics of SC in Prometheus text format
+pub async fn render_metrics<C: MetadataItem>(ctx: &Context<C>) -> String {
+    let mut out = String::new();
+    ctx.metrics().write_prometheus(&mut out);
+
+    let connected = ctx
+        .health()
+        .read()
+        .await
+        .values()
+        .filter(|online| **online)
+        .count();
+    write_gauge(
+        &mut out,
+        "fluvio_sc_spus_connected",
+        "SPUs with a live connection to the SC",
+        [(String::new(), connected)],
+    );
+
+    let mut spus: BTreeMap<String, usize> = BTreeMap::new();
+    for spu in ctx.spus().store().read().await.values() {
+        let status = match spu.status.resolution {
+            SpuStatusResolution::Online => "online",
+            SpuStatusResolution::Offline => "offline",
+            SpuStatusResolution::Init => "init",
+        };
+        *spus.entry(format!("status=\"{status}\"")).or_default() += 1;
+    }
+    write_gauge(&mut out, "fluvio_sc_spus", "SPUs by status", spus);
+
+    let mut partitions: BTreeMap<String, usize> = BTreeMap::new();
+    for partition in ctx.partitions().store().read().await.values() {
+        let state = match partition.status.resolution {
+            PartitionResolution::Offline => "offline",
+            PartitionResolution::Online => "online",
+            PartitionResolution::LeaderOffline => "leader_offline",
+            PartitionResolution::ElectionLeaderFound => "election_leader_found",
+        };
+        *partitions.entry(format!("state=\"{state}\"")).or_default() += 1;
+    }
+    write_gauge(
+        &mut out,
+        "fluvio_sc_partitions",
+        "Partitions by state",
+        partitions,
+    );
+
+    let mut mirrors: BTreeMap<String, usize> = BTreeMap::new();
+    for mirror in ctx.mirrors().store().read().await.values() {
+        let mirror_type = match mirror.spec.mirror_type {
+            MirrorType::Home(_) => "home",
+            MirrorType::Remote(_) => "remote",
+        };
+        let pairing = match mirror.status.pairing {
+            MirrorPairStatus::Waiting => "waiting",
+            MirrorPairStatus::Succesful => "successful",
+            MirrorPairStatus::Failed => "failed",
+            MirrorPairStatus::Disabled => "disabled",
+        };
+        let connection = match mirror.status.connection_status {
+            ConnectionStatus::Online => "online",
+            ConnectionStatus::Offline => "offline",
+        };
+        *mirrors
+            .entry(format!(
+                "type=\"{mirror_type}\",pairing=\"{pairing}\",connection=\"{connection}\""
+            ))
+            .or_default() += 1;
+    }
+    write_gauge(
+        &mut out,
+        "fluvio_sc_mirrors",
+        "Mirrors registered with the SC, by type, pairing and connection",
+        mirrors,
+    );
+
+    out
+}
+
+/// write gauge with a value per set of labels, labels are empty for a single value
+fn write_gauge(
+    out: &mut String,
+    name: &str,
+    help: &str,
+    values: impl IntoIterator<Item = (String, usize)>,
+) {
+    let _ = writeln!(out, "# HELP {name} {help}");
+    let _ = writeln!(out, "# TYPE {name} gauge");
+    for (labels, value) in values {
+        if labels.is_empty() {
+            let _ = writeln!(out, "{name} {value}");
+        } else {
+            let _ = writeln!(out, "{name}{{{labels}}} {value}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_path() {
+        assert_eq!(
+            request_path("GET /metrics HTTP/1.1\r\nHost: sc\r\n\r\n"),
+            Some("/metrics")
+        );
+        assert_eq!(
+            request_path("GET /metrics?name=spu HTTP/1.1\r\n\r\n"),
+            Some("/metrics")
+        );
+        assert_eq!(request_path("POST /metrics HTTP/1.1\r\n\r\n"), None);
+        assert_eq!(request_path(""), None);
+    }
+
+    #[test]
+    fn test_write_gauge() {
+        let mut out = String::new();
+        write_gauge(
+            &mut out,
+            "fluvio_sc_spus",
+            "SPUs by status",
+            [("status=\"online\"".to_owned(), 2)],
+        );
+        write_gauge(
+            &mut out,
+            "fluvio_sc_spus_connected",
+            "Connected SPUs",
+            [(String::new(), 2)],
+        );
+        assert_eq!(
+            out,
+            "# HELP fluvio_sc_spus SPUs by status\n# TYPE fluvio_sc_spus gauge\nfluvio_sc_spus{status=\"online\"} 2\n\
+             # HELP fluvio_sc_spus_connected Connected SPUs\n# TYPE fluvio_sc_spus_connected gauge\nfluvio_sc_spus_connected 2\n"
+        );
+    }
+}
@@ -1,8 +1,11 @@
 // pub mod send_channels;
 mod public_api;
 mod private_api;
+mod metrics;
+pub(crate) mod version_skew;
 
 pub mod auth;
 
 pub use public_api::start_public_server;
 pub use private_api::start_internal_server;
+pub use metrics::start_metrics_server;
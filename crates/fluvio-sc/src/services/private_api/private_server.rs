@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::marker::PhantomData;
 use std::sync::Arc;
 use std::io::Error as IoError;
@@ -12,18 +13,20 @@ use fluvio_controlplane::sc_api::api::InternalScKey;
 use fluvio_controlplane::sc_api::api::InternalScRequest;
 use fluvio_controlplane::sc_api::register_spu::RegisterSpuResponse;
 use fluvio_controlplane::sc_api::remove::ReplicaRemovedRequest;
-use fluvio_controlplane::sc_api::update_lrs::UpdateLrsRequest;
+use fluvio_controlplane::sc_api::update_lrs::{ReplicaDigestsRequest, UpdateLrsRequest};
+use fluvio_controlplane::spu_api::update_acl::UpdateAclRequest;
 use fluvio_controlplane::spu_api::update_mirror::MirrorMsg;
 use fluvio_controlplane::spu_api::update_mirror::UpdateMirrorRequest;
 use fluvio_controlplane::spu_api::update_replica::UpdateReplicaRequest;
 use fluvio_controlplane::spu_api::update_smartmodule::UpdateSmartModuleRequest;
 use fluvio_controlplane::spu_api::update_spu::UpdateSpuRequest;
 use fluvio_controlplane_metadata::message::Message;
+use fluvio_sc_schema::acl::AclSpec;
 use fluvio_sc_schema::mirror::MirrorSpec;
 use fluvio_stream_model::core::MetadataItem;
 use fluvio_stream_model::store::ChangeListener;
 use tracing::warn;
-use tracing::{debug, info, trace, instrument, error};
+use tracing::{debug, info, trace, instrument, error, Span};
 use async_trait::async_trait;
 use futures_util::stream::Stream;
 use anyhow::Result;
@@ -34,6 +37,7 @@ use fluvio_controlplane_metadata::smartmodule::SmartModuleSpec;
 use fluvio_types::SpuId;
 use fluvio_protocol::api::RequestMessage;
 use fluvio_service::{FluvioService, wait_for_request};
+use fluvio_service::telemetry::{current_traceparent, set_remote_parent};
 use fluvio_socket::{FluvioSocket, SocketError, FluvioSink};
 
 use crate::core::SharedContext;
@@ -42,6 +46,7 @@ use crate::stores::partition::{PartitionSpec, PartitionStatus, PartitionResoluti
 use crate::stores::spu::SpuLocalStorePolicy;
 use crate::stores::spu::SpuSpec;
 use crate::stores::actions::WSAction;
+use crate::services::version_skew::VersionSkew;
 
 const HEALTH_DURATION: u64 = 90;
 
@@ -78,15 +83,31 @@ where
         let spu_id = wait_for_request!(api_stream,
             InternalScRequest::RegisterSpuRequest(req_msg) => {
                 let spu_id = req_msg.request.spu();
+                let spu_version = req_msg.request.version();
                 let mut status = true;
-                debug!(spu_id,"registration req");
+                debug!(spu_id, spu_version, "registration req");
 
-                let register_res = if context.spus().store().validate_spu_for_registered(spu_id).await {
-                    RegisterSpuResponse::ok()
-                } else {
+                let sc_version = crate::VERSION.trim();
+                let skew = VersionSkew::detect(sc_version, spu_version);
+                let register_res = if !context.spus().store().validate_spu_for_registered(spu_id).await {
                     status = false;
                     debug!(spu_id,"spu validation failed");
                     RegisterSpuResponse::failed_registration()
+                } else if !skew.allowed_by(context.config().version_skew_policy) {
+                    status = false;
+                    warn!(spu_id, spu_version, sc_version, ?skew, "rejecting spu with incompatible version");
+                    RegisterSpuResponse::rejected(format!(
+                        "SPU version {} is incompatible with SC version {sc_version}",
+                        spu_version.unwrap_or("unknown")
+                    ))
+                } else {
+                    if !skew.is_compatible() {
+                        warn!(spu_id, spu_version, sc_version, ?skew, "spu version is incompatible with sc");
+                    } else if skew == VersionSkew::Patch {
+                        info!(spu_id, spu_version, sc_version, "spu version differs from sc");
+                    }
+                    context.health().update_version(spu_id, spu_version).await;
+                    RegisterSpuResponse::ok()
                 };
 
                 let response = req_msg.new_response(register_res);
@@ -133,8 +154,11 @@ where
     let mut partition_spec_listener = context.partitions().change_listener();
     let mut sm_spec_listener = context.smartmodules().change_listener();
     let mut mirror_spec_listener = context.mirrors().change_listener();
+    let mut acl_listener = context.acls().change_listener();
 
-    // send initial changes
+    // ACLs are sent as full set, initial set is sent even if there are no entries
+    let _ = acl_listener.sync_changes().await;
+    send_acl(&context, &mut sink).await?;
 
     let mut health_check_timer = sleep(Duration::from_secs(HEALTH_DURATION));
 
@@ -146,6 +170,7 @@ where
         send_smartmodule_changes(&mut sm_spec_listener, &mut sink, spu_id).await?;
         send_replica_spec_changes(&mut partition_spec_listener, &mut sink, spu_id).await?;
         send_mirror_changes(&mut mirror_spec_listener, &mut sink, spu_id).await?;
+        send_acl_changes(&context, &mut acl_listener, &mut sink).await?;
 
         trace!(spu_id, "waiting for SPU channel");
 
@@ -164,7 +189,7 @@ where
                     if let Ok(req_message) = spu_request {
                         match req_message {
                             InternalScRequest::UpdateLrsRequest(msg) => {
-                                receive_lrs_update(&context,msg.request).await;
+                                receive_lrs_update(&context, spu_id, msg.request).await;
                             },
                             InternalScRequest::RegisterSpuRequest(msg) => {
                                 error!("registration req only valid during initialization: {:#?}",msg);
@@ -203,6 +228,10 @@ where
                 debug!("mirror lister changed");
             }
 
+            _ = acl_listener.listen() => {
+                debug!("acl lister changed");
+            }
+
         }
     }
 
@@ -211,35 +240,41 @@ where
 
 /// send lrs update to metadata stores
 #[instrument(skip(ctx, requests))]
-async fn receive_lrs_update<C>(ctx: &SharedContext<C>, requests: UpdateLrsRequest)
+async fn receive_lrs_update<C>(ctx: &SharedContext<C>, spu_id: SpuId, requests: UpdateLrsRequest)
 where
     C: MetadataItem,
 {
-    let requests = requests.into_requests();
-    if requests.is_empty() {
+    set_remote_parent(&Span::current(), requests.traceparent());
+    if requests.stat_ref().is_reported() {
+        trace!(spu_id, stat = ?requests.stat_ref(), "received spu stat");
+        ctx.health()
+            .update_stat(spu_id, requests.stat_ref().clone())
+            .await;
+    }
+    let (requests, digests) = requests.into_parts();
+    if requests.is_empty() && digests.is_empty() {
         trace!("no requests, just health check");
         return;
     } else {
-        debug!(?requests, "received lr requests");
+        debug!(?requests, ?digests, "received lr requests");
     }
-    let mut actions = vec![];
+    let mut statuses = HashMap::new();
     let read_guard = ctx.partitions().store().read().await;
     for lrs_req in requests.into_iter() {
         if let Some(partition) = read_guard.get(&lrs_req.id) {
             let mut current_status = partition.inner().status().clone();
             let key = lrs_req.id.clone();
-            let new_status = PartitionStatus::new2(
+            let mut new_status = PartitionStatus::new2(
                 lrs_req.leader,
                 lrs_req.replicas,
                 lrs_req.size,
                 PartitionResolution::Online,
             );
+            new_status.corrupt_ranges = lrs_req.corrupt_ranges;
+            new_status.usage = lrs_req.usage;
             current_status.merge(new_status);
 
-            actions.push(WSAction::<PartitionSpec, C>::UpdateStatus((
-                key,
-                current_status,
-            )));
+            statuses.insert(key, current_status);
         } else {
             error!(
                 "trying to update replica: {}, that doesn't exist",
@@ -249,10 +284,32 @@ where
         }
     }
 
+    for ReplicaDigestsRequest { id, digests } in digests.into_iter() {
+        let Some(partition) = read_guard.get(&id) else {
+            debug!(replica = %id, "digests of replica that doesn't exist");
+            continue;
+        };
+        let replicas = &partition.inner().spec().replicas;
+        let status = statuses
+            .entry(id.clone())
+            .or_insert_with(|| partition.inner().status().clone());
+        let was_divergent = status.divergent_replicas.clone();
+        status.update_digests(digests, replicas);
+        if status.divergent_replicas != was_divergent {
+            warn!(
+                replica = %id,
+                divergent = ?status.divergent_replicas,
+                "replica divergence changed"
+            );
+        }
+    }
+
     drop(read_guard);
 
-    for action in actions.into_iter() {
-        ctx.partitions().send_action(action).await;
+    for (key, status) in statuses.into_iter() {
+        ctx.partitions()
+            .send_action(WSAction::<PartitionSpec, C>::UpdateStatus((key, status)))
+            .await;
     }
 }
 
@@ -322,6 +379,7 @@ async fn send_spu_spec_changes<C: MetadataItem>(
         UpdateSpuRequest::with_changes(epoch, changes)
     };
 
+    let request = request.with_traceparent(current_traceparent());
     let mut message = RequestMessage::new_request(request);
     message.get_mut_header().set_client_id("sc");
 
@@ -399,6 +457,7 @@ async fn send_replica_spec_changes<C: MetadataItem>(
 
     debug!(?request, "sending replica to spu");
 
+    let request = request.with_traceparent(current_traceparent());
     let mut message = RequestMessage::new_request(request);
     message.get_mut_header().set_client_id("sc");
 
@@ -453,6 +512,41 @@ async fn send_smartmodule_changes<C: MetadataItem>(
 
     debug!(?request, "sending sm to spu");
 
+    let request = request.with_traceparent(current_traceparent());
+    let mut message = RequestMessage::new_request(request);
+    message.get_mut_header().set_client_id("sc");
+
+    sink.send_request(&message).await?;
+    Ok(())
+}
+
+/// send full set of ACL entries if any of them changed
+#[instrument(level = "trace", skip(ctx, listener, sink))]
+async fn send_acl_changes<C: MetadataItem>(
+    ctx: &SharedContext<C>,
+    listener: &mut ChangeListener<AclSpec, C>,
+    sink: &mut FluvioSink,
+) -> Result<(), SocketError> {
+    if !listener.has_change() {
+        trace!("acl changes is empty, skipping");
+        return Ok(());
+    }
+
+    let _ = listener.sync_changes().await;
+    send_acl(ctx, sink).await
+}
+
+/// send full set of ACL entries
+#[instrument(level = "trace", skip(ctx, sink))]
+async fn send_acl<C: MetadataItem>(
+    ctx: &SharedContext<C>,
+    sink: &mut FluvioSink,
+) -> Result<(), SocketError> {
+    let entries = ctx.acls().store().clone_specs().await;
+    let request = UpdateAclRequest::new(ctx.config().acl_enabled, entries);
+
+    debug!(entries = request.entries.len(), "sending acl to spu");
+
     let mut message = RequestMessage::new_request(request);
     message.get_mut_header().set_client_id("sc");
 
@@ -507,6 +601,7 @@ async fn send_mirror_changes<C: MetadataItem>(
 
     debug!(?request, "sending mirror to spu");
 
+    let request = request.with_traceparent(current_traceparent());
     let mut message = RequestMessage::new_request(request);
     message.get_mut_header().set_client_id("sc");
 
@@ -0,0 +1,167 @@
+//!
+//! # ACL Requests
+//!
+//! List and update ACL entries. Entries are stored as Acl metadata objects and pushed to SPUs.
+//!
+
+use std::io::Error as IoError;
+
+use tracing::{info, trace, instrument};
+use anyhow::{anyhow, Result};
+
+use fluvio_protocol::api::{RequestMessage, ResponseMessage};
+use fluvio_protocol::link::ErrorCode;
+use fluvio_sc_schema::acl::{AclListRequest, AclListResponse, AclUpdateRequest, AclUpdateResponse};
+use fluvio_sc_schema::spu::SpuSpec;
+use fluvio_controlplane_metadata::extended::SpecExt;
+use fluvio_auth::{AuthContext, TypeAction};
+use fluvio_stream_model::core::MetadataItem;
+
+use crate::services::auth::AuthServiceContext;
+use crate::stores::StoreContext;
+use crate::stores::acl::{acl_key, AclEntry, AclSpec};
+
+/// ACLs grant access to every topic, restrict them to cluster admins
+async fn is_cluster_admin<AC: AuthContext, C: MetadataItem>(
+    auth_ctx: &AuthServiceContext<AC, C>,
+) -> Result<bool> {
+    auth_ctx
+        .auth
+        .allow_type_action(SpuSpec::OBJECT_TYPE, TypeAction::Create)
+        .await
+        .map_err(|_| anyhow!("authorization io error"))
+}
+
+/// Handler for ACL list request
+#[instrument(skip(request, auth_ctx))]
+pub async fn handle_acl_list_request<AC: AuthContext, C: MetadataItem>(
+    request: RequestMessage<AclListRequest>,
+    auth_ctx: &AuthServiceContext<AC, C>,
+) -> Result<ResponseMessage<AclListResponse>> {
+    let (header, _req) = request.get_header_request();
+
+    let response = if is_cluster_admin(auth_ctx).await? {
+        let mut entries = auth_ctx.global_ctx.acls().store().clone_specs().await;
+        entries.sort_by_cached_key(|entry| entry.to_string());
+        AclListResponse::new(auth_ctx.global_ctx.config().acl_enabled, entries)
+    } else {
+        trace!("authorization failed");
+        AclListResponse::error(ErrorCode::PermissionDenied, "permission denied")
+    };
+
+    trace!(entries = response.entries.len(), "acl list resp");
+    Ok(ResponseMessage::from_header(&header, response))
+}
+
+/// Handler for ACL update request
+#[instrument(skip(request, auth_ctx))]
+pub async fn handle_acl_update_request<AC: AuthContext, C: MetadataItem>(
+    request: RequestMessage<AclUpdateRequest>,
+    auth_ctx: &AuthServiceContext<AC, C>,
+) -> Result<ResponseMessage<AclUpdateResponse>> {
+    let (header, req) = request.get_header_request();
+    let summary = format!("add={} remove={}", req.add.len(), req.remove.len());
+
+    let response = if is_cluster_admin(auth_ctx).await? {
+        match update_acls(auth_ctx.global_ctx.acls(), req.add, &req.remove).await {
+            Ok(()) => {
+                info!(summary, "ACLs updated");
+                AclUpdateResponse::default()
+            }
+            Err(err) => AclUpdateResponse::error(
+                ErrorCode::Other(err.to_string()),
+                format!("unable to update ACLs: {err}"),
+            ),
+        }
+    } else {
+        trace!("authorization failed");
+        AclUpdateResponse::error(ErrorCode::PermissionDenied, "permission denied")
+    };
+
+    auth_ctx.audit("update", "Acl", "", summary, &response.error_code);
+    Ok(ResponseMessage::from_header(&header, response))
+}
+
+/// remove then add entries, entries which are already stored are not added again
+async fn update_acls<C: MetadataItem>(
+    acls: &StoreContext<AclSpec, C>,
+    add: Vec<AclEntry>,
+    remove: &[AclEntry],
+) -> Result<(), IoError> {
+    for entry in remove {
+        let key = acl_key(entry);
+        if acls.store().contains_key(&key).await {
+            acls.delete(key).await?;
+        }
+    }
+    for entry in add {
+        let key = acl_key(&entry);
+        if acls.store().contains_key(&key).await {
+            continue;
+        }
+        acls.create_spec(key, entry).await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use fluvio_stream_dispatcher::metadata::local::{LocalMetadataItem, LocalMetadataStorage};
+    use flv_util::fixture::ensure_clean_dir;
+
+    use crate::dispatcher::dispatcher::MetadataDispatcher;
+    use crate::stores::acl::{AclPermission, AclResourceType};
+
+    use super::*;
+
+    fn entry(principal: &str, topic: &str) -> AclEntry {
+        AclEntry::new(
+            principal,
+            AclResourceType::Topic,
+            topic,
+            AclPermission::Produce,
+        )
+    }
+
+    fn start_acls(path: &std::path::Path) -> StoreContext<AclSpec, LocalMetadataItem> {
+        let acls = StoreContext::new();
+        MetadataDispatcher::<AclSpec, LocalMetadataStorage, LocalMetadataItem>::start(
+            "default",
+            Arc::new(LocalMetadataStorage::new(path)),
+            acls.clone(),
+        );
+        acls
+    }
+
+    #[fluvio_future::test]
+    async fn test_update_acls_stores_objects() {
+        let path = std::env::temp_dir().join("sc-acl-update");
+        ensure_clean_dir(&path);
+
+        let acls = start_acls(&path);
+        update_acls(&acls, vec![entry("alice", "t1"), entry("bob", "t2")], &[])
+            .await
+            .expect("add");
+        // adding stored entry again is no-op
+        update_acls(&acls, vec![entry("alice", "t1")], &[entry("bob", "t2")])
+            .await
+            .expect("remove");
+
+        assert_eq!(acls.store().clone_specs().await, vec![entry("alice", "t1")]);
+        assert!(
+            acls.store()
+                .contains_key(&acl_key(&entry("alice", "t1")))
+                .await
+        );
+
+        // entries are loaded back from metadata storage
+        let reloaded = start_acls(&path);
+        reloaded.store().wait_for_first_change().await;
+        assert_eq!(
+            reloaded.store().clone_specs().await,
+            vec![entry("alice", "t1")]
+        );
+    }
+}
@@ -1,10 +1,19 @@
 use fluvio_sc_schema::mirroring::ObjectMirroringRequest;
+use fluvio_sc_schema::partition::ReassignPartitionRequest;
+use fluvio_sc_schema::topic::{AddPartitionsRequest, SetReadOnlyRequest};
+use fluvio_sc_schema::spu::DrainSpuRequest;
+use fluvio_sc_schema::cli_release::RecommendedCliRequest;
+use fluvio_sc_schema::audit::AuditLogRequest;
+use fluvio_sc_schema::acl::{AclListRequest, AclUpdateRequest};
+use fluvio_sc_schema::diagnostics::ClusterDiagnosticsRequest;
+use fluvio_sc_schema::events::ClusterEventsRequest;
 use tracing::{trace, instrument, debug};
 use semver::Version;
 use once_cell::sync::Lazy;
 use anyhow::Result;
 
 use fluvio_protocol::api::{RequestMessage, ResponseMessage, Request};
+use fluvio_protocol::link::capabilities::Capabilities;
 use fluvio_protocol::link::versions::{
     ApiVersionKey, ApiVersionsRequest, ApiVersionsResponse, PlatformVersion,
 };
@@ -23,6 +32,7 @@ pub async fn handle_api_versions_request(
 ) -> Result<ResponseMessage<ApiVersionsResponse>> {
     let mut response = ApiVersionsResponse {
         platform_version: PlatformVersion::new(&PLATFORM_VER),
+        capabilities: Capabilities::supported(),
         ..Default::default()
     };
 
@@ -59,6 +69,66 @@ pub async fn handle_api_versions_request(
         ObjectMirroringRequest::MAX_API_VERSION,
     ));
 
+    response.api_keys.push(make_version_key(
+        AdminPublicApiKey::ReassignPartition,
+        ReassignPartitionRequest::MIN_API_VERSION,
+        ReassignPartitionRequest::MAX_API_VERSION,
+    ));
+
+    response.api_keys.push(make_version_key(
+        AdminPublicApiKey::AddPartitions,
+        AddPartitionsRequest::MIN_API_VERSION,
+        AddPartitionsRequest::MAX_API_VERSION,
+    ));
+
+    response.api_keys.push(make_version_key(
+        AdminPublicApiKey::DrainSpu,
+        DrainSpuRequest::MIN_API_VERSION,
+        DrainSpuRequest::MAX_API_VERSION,
+    ));
+
+    response.api_keys.push(make_version_key(
+        AdminPublicApiKey::SetReadOnly,
+        SetReadOnlyRequest::MIN_API_VERSION,
+        SetReadOnlyRequest::MAX_API_VERSION,
+    ));
+
+    response.api_keys.push(make_version_key(
+        AdminPublicApiKey::RecommendedCli,
+        RecommendedCliRequest::MIN_API_VERSION,
+        RecommendedCliRequest::MAX_API_VERSION,
+    ));
+
+    response.api_keys.push(make_version_key(
+        AdminPublicApiKey::AuditLog,
+        AuditLogRequest::MIN_API_VERSION,
+        AuditLogRequest::MAX_API_VERSION,
+    ));
+
+    response.api_keys.push(make_version_key(
+        AdminPublicApiKey::AclList,
+        AclListRequest::MIN_API_VERSION,
+        AclListRequest::MAX_API_VERSION,
+    ));
+
+    response.api_keys.push(make_version_key(
+        AdminPublicApiKey::AclUpdate,
+        AclUpdateRequest::MIN_API_VERSION,
+        AclUpdateRequest::MAX_API_VERSION,
+    ));
+
+    response.api_keys.push(make_version_key(
+        AdminPublicApiKey::ClusterDiagnostics,
+        ClusterDiagnosticsRequest::MIN_API_VERSION,
+        ClusterDiagnosticsRequest::MAX_API_VERSION,
+    ));
+
+    response.api_keys.push(make_version_key(
+        AdminPublicApiKey::ClusterEvents,
+        ClusterEventsRequest::MIN_API_VERSION,
+        ClusterEventsRequest::MAX_API_VERSION,
+    ));
+
     trace!("flv api versions response: {:#?}", response);
 
     Ok(request.new_response(response))
@@ -0,0 +1,44 @@
+//!
+//! # Audit Log Request
+//!
+//! Returns most recent admin mutations recorded by SC.
+//!
+
+use tracing::{trace, instrument};
+use anyhow::{anyhow, Result};
+
+use fluvio_protocol::api::{RequestMessage, ResponseMessage};
+use fluvio_protocol::link::ErrorCode;
+use fluvio_sc_schema::audit::{AuditLogRequest, AuditLogResponse};
+use fluvio_sc_schema::spu::SpuSpec;
+use fluvio_controlplane_metadata::extended::SpecExt;
+use fluvio_auth::{AuthContext, TypeAction};
+use fluvio_stream_model::core::MetadataItem;
+
+use crate::services::auth::AuthServiceContext;
+
+/// Handler for audit log request
+#[instrument(skip(request, auth_ctx))]
+pub async fn handle_audit_log_request<AC: AuthContext, C: MetadataItem>(
+    request: RequestMessage<AuditLogRequest>,
+    auth_ctx: &AuthServiceContext<AC, C>,
+) -> Result<ResponseMessage<AuditLogResponse>> {
+    let (header, req) = request.get_header_request();
+
+    // audit log reveals operations on every object, restrict it to cluster admins
+    let response = if auth_ctx
+        .auth
+        .allow_type_action(SpuSpec::OBJECT_TYPE, TypeAction::Create)
+        .await
+        .map_err(|_| anyhow!("authorization io error"))?
+    {
+        let entries = auth_ctx.global_ctx.audit().recent(req.limit as usize);
+        AuditLogResponse::new(entries)
+    } else {
+        trace!("authorization failed");
+        AuditLogResponse::error(ErrorCode::PermissionDenied, "permission denied")
+    };
+
+    trace!(entries = response.entries.len(), "audit log resp");
+    Ok(ResponseMessage::from_header(&header, response))
+}
@@ -0,0 +1,62 @@
+//!
+//! # Recommended CLI Request
+//!
+//! Recommends the CLI release matching the cluster: the latest release of the cluster's
+//! minor version in the mirrored package index, or the platform version when the index
+//! isn't mirrored.
+//!
+
+use std::str::FromStr;
+
+use tracing::{trace, instrument};
+use anyhow::Result;
+use semver::Version;
+
+use fluvio_protocol::api::{RequestMessage, ResponseMessage};
+use fluvio_protocol::link::ErrorCode;
+use fluvio_sc_schema::cli_release::{RecommendedCliRequest, RecommendedCliResponse};
+use fluvio_auth::AuthContext;
+use fluvio_index::Target;
+use fluvio_stream_model::core::MetadataItem;
+
+use crate::services::auth::AuthServiceContext;
+
+/// Handler for recommended cli request
+#[instrument(skip(request, auth_ctx))]
+pub async fn handle_recommended_cli_request<AC: AuthContext, C: MetadataItem>(
+    request: RequestMessage<RecommendedCliRequest>,
+    auth_ctx: &AuthServiceContext<AC, C>,
+) -> Result<ResponseMessage<RecommendedCliResponse>> {
+    let (header, req) = request.get_header_request();
+    let response = recommended_cli(req, auth_ctx).await?;
+    trace!("recommended cli resp {:#?}", response);
+    Ok(ResponseMessage::from_header(&header, response))
+}
+
+async fn recommended_cli<AC: AuthContext, C: MetadataItem>(
+    req: RecommendedCliRequest,
+    auth_ctx: &AuthServiceContext<AC, C>,
+) -> Result<RecommendedCliResponse> {
+    let target = match Target::from_str(&req.target) {
+        Ok(target) => target,
+        Err(err) => {
+            return Ok(RecommendedCliResponse::error(
+                ErrorCode::Other("invalid target".to_owned()),
+                err.to_string(),
+            ))
+        }
+    };
+
+    let platform = Version::parse(crate::VERSION.trim())?;
+    let mirror = auth_ctx.global_ctx.cli_releases();
+    if !mirror.is_mirrored().await {
+        return Ok(RecommendedCliResponse::new(platform.to_string(), false));
+    }
+    match mirror.recommended(&platform, &target).await {
+        Some(version) => Ok(RecommendedCliResponse::new(version.to_string(), true)),
+        None => Ok(RecommendedCliResponse::error(
+            ErrorCode::Other("no recommended cli".to_owned()),
+            format!("no cli release of platform {platform} for {target}"),
+        )),
+    }
+}
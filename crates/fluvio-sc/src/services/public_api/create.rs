@@ -1,6 +1,6 @@
 use fluvio_protocol::link::ErrorCode;
 use fluvio_sc_schema::mirror::MirrorSpec;
-use fluvio_stream_model::core::MetadataItem;
+use fluvio_stream_model::core::{MetadataItem, Spec};
 use tracing::{instrument, debug, error};
 use anyhow::Result;
 
@@ -25,28 +25,50 @@ pub async fn handle_create_request<AC: AuthContext, C: MetadataItem>(
     let (header, req) = request.get_header_request();
 
     debug!(?req, "create request");
-    let status = if let Some(create) = req.downcast()? as Option<CreateRequest<TopicSpec>> {
-        super::topic::handle_create_topics_request(create, auth_context).await?
+    let (object, dry_run, status) = if let Some(create) =
+        req.downcast()? as Option<CreateRequest<TopicSpec>>
+    {
+        let dry_run = create.common.dry_run;
+        let status = super::topic::handle_create_topics_request(create, auth_context).await?;
+        (TopicSpec::LABEL, dry_run, status)
     } else if let Some(create) = req.downcast()? as Option<CreateRequest<SpuGroupSpec>> {
-        super::spg::handle_create_spu_group_request(create, auth_context).await?
+        let dry_run = create.common.dry_run;
+        let status = super::spg::handle_create_spu_group_request(create, auth_context).await?;
+        (SpuGroupSpec::LABEL, dry_run, status)
     } else if let Some(create) = req.downcast()? as Option<CreateRequest<CustomSpuSpec>> {
-        super::spu::RegisterCustomSpu::handle_register_custom_spu_request(create, auth_context)
-            .await
+        let dry_run = create.common.dry_run;
+        let status =
+            super::spu::RegisterCustomSpu::handle_register_custom_spu_request(create, auth_context)
+                .await;
+        (CustomSpuSpec::LABEL, dry_run, status)
     } else if let Some(create) = req.downcast()? as Option<CreateRequest<SmartModuleSpec>> {
-        super::smartmodule::handle_create_smartmodule_request(create, auth_context).await?
+        let dry_run = create.common.dry_run;
+        let status =
+            super::smartmodule::handle_create_smartmodule_request(create, auth_context).await?;
+        (SmartModuleSpec::LABEL, dry_run, status)
     } else if let Some(create) = req.downcast()? as Option<CreateRequest<TableFormatSpec>> {
-        super::tableformat::handle_create_tableformat_request(create, auth_context).await?
+        let dry_run = create.common.dry_run;
+        let status =
+            super::tableformat::handle_create_tableformat_request(create, auth_context).await?;
+        (TableFormatSpec::LABEL, dry_run, status)
     } else if let Some(create) = req.downcast()? as Option<CreateRequest<MirrorSpec>> {
-        super::mirror::handle_register_mirror(create, auth_context).await?
+        let dry_run = create.common.dry_run;
+        let status = super::mirror::handle_register_mirror(create, auth_context).await?;
+        (MirrorSpec::LABEL, dry_run, status)
     } else {
         error!("unknown create request: {:#?}", req);
-        Status::new(
+        let status = Status::new(
             "create error".to_owned(),
             ErrorCode::Other("unknown admin object type".to_owned()),
             None,
-        )
+        );
+        return Ok(ResponseMessage::from_header(&header, status));
     };
 
+    if !dry_run {
+        auth_context.audit("create", object, &status.name, "", &status.error_code);
+    }
+
     Ok(ResponseMessage::from_header(&header, status))
 }
 
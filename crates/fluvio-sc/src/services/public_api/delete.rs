@@ -7,7 +7,7 @@
 
 use fluvio_protocol::link::ErrorCode;
 use fluvio_sc_schema::mirror::MirrorSpec;
-use fluvio_stream_model::core::MetadataItem;
+use fluvio_stream_model::core::{MetadataItem, Spec};
 use tracing::{instrument, trace, debug, error};
 use anyhow::Result;
 
@@ -33,28 +33,39 @@ pub async fn handle_delete_request<AC: AuthContext, C: MetadataItem>(
 
     debug!(?del_req, "del request");
 
-    let status = if let Some(req) = del_req.downcast()? as Option<DeleteRequest<TopicSpec>> {
+    let (object, summary, status) = if let Some(req) =
+        del_req.downcast()? as Option<DeleteRequest<TopicSpec>>
+    {
         let force = req.is_force();
-        super::topic::handle_delete_topic(req.key(), force, auth_ctx).await?
+        let status = super::topic::handle_delete_topic(req.key(), force, auth_ctx).await?;
+        (TopicSpec::LABEL, if force { "force" } else { "" }, status)
     } else if let Some(req) = del_req.downcast()? as Option<DeleteRequest<CustomSpuSpec>> {
-        super::spu::handle_un_register_custom_spu_request(req.key(), auth_ctx).await?
+        let status = super::spu::handle_un_register_custom_spu_request(req.key(), auth_ctx).await?;
+        (CustomSpuSpec::LABEL, "", status)
     } else if let Some(req) = del_req.downcast()? as Option<DeleteRequest<SpuGroupSpec>> {
-        super::spg::handle_delete_spu_group(req.key(), auth_ctx).await?
+        let status = super::spg::handle_delete_spu_group(req.key(), auth_ctx).await?;
+        (SpuGroupSpec::LABEL, "", status)
     } else if let Some(req) = del_req.downcast()? as Option<DeleteRequest<SmartModuleSpec>> {
-        super::smartmodule::handle_delete_smartmodule(req.key(), auth_ctx).await?
+        let status = super::smartmodule::handle_delete_smartmodule(req.key(), auth_ctx).await?;
+        (SmartModuleSpec::LABEL, "", status)
     } else if let Some(req) = del_req.downcast()? as Option<DeleteRequest<TableFormatSpec>> {
-        super::tableformat::handle_delete_tableformat(req.key(), auth_ctx).await?
+        let status = super::tableformat::handle_delete_tableformat(req.key(), auth_ctx).await?;
+        (TableFormatSpec::LABEL, "", status)
     } else if let Some(req) = del_req.downcast()? as Option<DeleteRequest<MirrorSpec>> {
-        super::mirror::handle_unregister_mirror(req.key(), auth_ctx).await?
+        let status = super::mirror::handle_unregister_mirror(req.key(), auth_ctx).await?;
+        (MirrorSpec::LABEL, "", status)
     } else {
         error!("unknown create request: {:#?}", del_req);
-        Status::new(
+        let status = Status::new(
             "create error".to_owned(),
             ErrorCode::Other("unknown admin object type".to_owned()),
             None,
-        )
+        );
+        return Ok(ResponseMessage::from_header(&header, status));
     };
 
+    auth_ctx.audit("delete", object, &status.name, summary, &status.error_code);
+
     trace!("flv delete topics resp {:#?}", status);
 
     Ok(ResponseMessage::from_header(&header, status))
@@ -0,0 +1,339 @@
+//!
+//! # Cluster Diagnostics Request
+//!
+//! Aggregate health of SPUs, partitions and mirrors known to SC into single report.
+//!
+
+use tracing::{trace, instrument};
+use anyhow::{anyhow, Result};
+
+use fluvio_protocol::api::{RequestMessage, ResponseMessage};
+use fluvio_protocol::link::ErrorCode;
+use fluvio_sc_schema::diagnostics::{
+    ClusterDiagnosticsRequest, ClusterDiagnosticsResponse, ClusterHealthReport, HealthIssue,
+    MirrorHealth, PartitionHealth, SpuHealth,
+};
+use fluvio_sc_schema::spu::SpuSpec;
+use fluvio_controlplane_metadata::extended::SpecExt;
+use fluvio_auth::{AuthContext, TypeAction};
+use fluvio_stream_model::core::MetadataItem;
+
+use crate::core::Context;
+use crate::services::auth::AuthServiceContext;
+use crate::services::version_skew::VersionSkew;
+
+/// followers further behind leader are reported
+const MAX_REPLICA_LAG: i64 = 10_000;
+const DISK_WARNING_PERCENT: f64 = 15.0;
+const DISK_CRITICAL_PERCENT: f64 = 5.0;
+
+/// Handler for cluster diagnostics request
+#[instrument(skip(request, auth_ctx))]
+pub async fn handle_cluster_diagnostics_request<AC: AuthContext, C: MetadataItem>(
+    request: RequestMessage<ClusterDiagnosticsRequest>,
+    auth_ctx: &AuthServiceContext<AC, C>,
+) -> Result<ResponseMessage<ClusterDiagnosticsResponse>> {
+    let (header, _req) = request.get_header_request();
+
+    let allowed = auth_ctx
+        .auth
+        .allow_type_action(SpuSpec::OBJECT_TYPE, TypeAction::Read)
+        .await
+        .map_err(|_| anyhow!("authorization io error"))?;
+
+    let response = if allowed {
+        ClusterDiagnosticsResponse::new(health_report(&auth_ctx.global_ctx).await)
+    } else {
+        trace!("authorization failed");
+        ClusterDiagnosticsResponse::error(ErrorCode::PermissionDenied, "permission denied")
+    };
+
+    trace!(
+        issues = response.report.issues.len(),
+        "cluster diagnostics resp"
+    );
+    Ok(ResponseMessage::from_header(&header, response))
+}
+
+async fn health_report<C: MetadataItem>(ctx: &Context<C>) -> ClusterHealthReport {
+    let stats = ctx.health().stats().await;
+    let versions = ctx.health().versions().await;
+    let partitions = ctx.partitions().store().read().await;
+
+    let mut spus: Vec<SpuHealth> = ctx
+        .spus()
+        .store()
+        .read()
+        .await
+        .values()
+        .map(|spu| {
+            let id = spu.spec.id;
+            let stat = stats.get(&id);
+            let (leaders, leader_bytes) = partitions
+                .values()
+                .filter(|partition| partition.spec.leader == id)
+                .fold((0, 0), |(count, bytes), partition| {
+                    (count + 1, bytes + partition.status.usage.local_bytes())
+                });
+            SpuHealth {
+                id,
+                name: spu.key().to_string(),
+                online: spu.status.is_online(),
+                version: stat
+                    .filter(|stat| stat.is_reported())
+                    .map(|stat| stat.version.clone())
+                    .or_else(|| versions.get(&id).cloned()),
+                disk_total_bytes: stat.map(|stat| stat.disk_total_bytes).unwrap_or_default(),
+                disk_available_bytes: stat
+                    .map(|stat| stat.disk_available_bytes)
+                    .unwrap_or_default(),
+                leaders,
+                leader_bytes,
+            }
+        })
+        .collect();
+    spus.sort_by_key(|spu| spu.id);
+
+    let mut partition_health: Vec<PartitionHealth> = partitions
+        .values()
+        .map(|partition| {
+            let status = &partition.status;
+            PartitionHealth {
+                name: partition.key().to_string(),
+                leader: partition.spec.leader,
+                online: status.is_online(),
+                max_lag: status
+                    .replica_iter()
+                    .map(|replica| replica.leader_lag(&status.leader))
+                    .max()
+                    .unwrap_or_default(),
+                offline_replicas: status.offline_replicas(),
+                divergent_replicas: status.divergent_replicas.clone(),
+                corrupt: status.is_corrupt(),
+            }
+        })
+        .filter(|partition| {
+            !partition.online
+                || partition.max_lag > 0
+                || partition.corrupt
+                || !partition.offline_replicas.is_empty()
+                || !partition.divergent_replicas.is_empty()
+        })
+        .collect();
+    partition_health.sort_by(|a, b| a.name.cmp(&b.name));
+    drop(partitions);
+
+    let mut mirrors: Vec<MirrorHealth> = ctx
+        .mirrors()
+        .store()
+        .read()
+        .await
+        .values()
+        .map(|mirror| MirrorHealth {
+            name: mirror.key().to_string(),
+            status: mirror.status.to_string(),
+        })
+        .collect();
+    mirrors.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let mut report = ClusterHealthReport {
+        sc_version: crate::VERSION.trim().to_owned(),
+        spus,
+        partitions: partition_health,
+        mirrors,
+        issues: vec![],
+    };
+    report.issues = find_issues(&report);
+    report
+}
+
+/// derive issues from health of components
+fn find_issues(report: &ClusterHealthReport) -> Vec<HealthIssue> {
+    let mut issues = vec![];
+
+    for spu in &report.spus {
+        let component = format!("spu {}", spu.id);
+        if !spu.online {
+            issues.push(HealthIssue::critical(&component, "SPU is offline"));
+            continue;
+        }
+        let version = spu.version.as_deref().unwrap_or("unknown");
+        match VersionSkew::detect(&report.sc_version, spu.version.as_deref()) {
+            VersionSkew::None => {}
+            VersionSkew::Patch => issues.push(HealthIssue::warning(
+                &component,
+                format!(
+                    "version {version} differs from SC version {}",
+                    report.sc_version
+                ),
+            )),
+            VersionSkew::Unknown => issues.push(HealthIssue::warning(
+                &component,
+                format!(
+                    "version {version} can't be checked against SC version {}",
+                    report.sc_version
+                ),
+            )),
+            VersionSkew::Incompatible => issues.push(HealthIssue::critical(
+                &component,
+                format!(
+                    "version {version} is incompatible with SC version {}",
+                    report.sc_version
+                ),
+            )),
+        }
+        if let Some(available) = spu.disk_available_percent() {
+            let message = format!("{available:.1}% of disk available");
+            if available < DISK_CRITICAL_PERCENT {
+                issues.push(HealthIssue::critical(&component, message));
+            } else if available < DISK_WARNING_PERCENT {
+                issues.push(HealthIssue::warning(&component, message));
+            }
+        }
+    }
+
+    for partition in &report.partitions {
+        let component = format!("partition {}", partition.name);
+        if !partition.online {
+            issues.push(HealthIssue::critical(&component, "partition is offline"));
+        }
+        if partition.corrupt {
+            issues.push(HealthIssue::critical(
+                &component,
+                "leader storage failed checksum validation",
+            ));
+        }
+        if !partition.divergent_replicas.is_empty() {
+            issues.push(HealthIssue::critical(
+                &component,
+                format!(
+                    "replicas on SPUs {:?} diverge from leader",
+                    partition.divergent_replicas
+                ),
+            ));
+        }
+        if !partition.offline_replicas.is_empty() {
+            issues.push(HealthIssue::warning(
+                &component,
+                format!(
+                    "replicas on SPUs {:?} are offline",
+                    partition.offline_replicas
+                ),
+            ));
+        }
+        if partition.max_lag > MAX_REPLICA_LAG {
+            issues.push(HealthIssue::warning(
+                &component,
+                format!("followers are {} records behind leader", partition.max_lag),
+            ));
+        }
+    }
+
+    for mirror in &report.mirrors {
+        let component = format!("mirror {}", mirror.name);
+        match mirror.status.as_str() {
+            "Failed" => issues.push(HealthIssue::critical(&component, "mirror pairing failed")),
+            "Offline" => issues.push(HealthIssue::warning(&component, "mirror is offline")),
+            _ => {}
+        }
+    }
+
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use fluvio_sc_schema::diagnostics::IssueSeverity;
+
+    use super::*;
+
+    fn spu(id: i32, online: bool, version: &str, available: u64) -> SpuHealth {
+        SpuHealth {
+            id,
+            name: format!("spu-{id}"),
+            online,
+            version: Some(version.to_owned()),
+            disk_total_bytes: 100,
+            disk_available_bytes: available,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_find_issues() {
+        let report = ClusterHealthReport {
+            sc_version: "0.11.9".to_owned(),
+            spus: vec![
+                spu(5001, true, "0.11.9", 50),
+                spu(5002, true, "0.11.8", 10),
+                spu(5003, false, "0.11.9", 1),
+            ],
+            partitions: vec![PartitionHealth {
+                name: "orders-0".to_owned(),
+                leader: 5001,
+                online: true,
+                max_lag: 20_000,
+                offline_replicas: vec![5003],
+                ..Default::default()
+            }],
+            mirrors: vec![
+                MirrorHealth {
+                    name: "edge-1".to_owned(),
+                    status: "Online".to_owned(),
+                },
+                MirrorHealth {
+                    name: "edge-2".to_owned(),
+                    status: "Failed".to_owned(),
+                },
+            ],
+            issues: vec![],
+        };
+
+        let issues = find_issues(&report);
+        let summary: Vec<(IssueSeverity, &str)> = issues
+            .iter()
+            .map(|issue| (issue.severity, issue.component.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (IssueSeverity::Warning, "spu 5002"),
+                (IssueSeverity::Warning, "spu 5002"),
+                (IssueSeverity::Critical, "spu 5003"),
+                (IssueSeverity::Warning, "partition orders-0"),
+                (IssueSeverity::Warning, "partition orders-0"),
+                (IssueSeverity::Critical, "mirror edge-2"),
+            ]
+        );
+        assert!(issues[0].message.contains("0.11.8"));
+    }
+
+    #[test]
+    fn test_version_skew_issues() {
+        let mut unknown = spu(5003, true, "", 50);
+        unknown.version = None;
+        let report = ClusterHealthReport {
+            sc_version: "0.11.9".to_owned(),
+            spus: vec![
+                spu(5001, true, "0.11.9", 50),
+                spu(5002, true, "0.12.0", 50),
+                unknown,
+            ],
+            ..Default::default()
+        };
+
+        let issues = find_issues(&report);
+        let summary: Vec<(IssueSeverity, &str)> = issues
+            .iter()
+            .map(|issue| (issue.severity, issue.component.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (IssueSeverity::Critical, "spu 5002"),
+                (IssueSeverity::Warning, "spu 5003"),
+            ]
+        );
+        assert!(issues[0].message.contains("incompatible"));
+    }
+}
@@ -0,0 +1,296 @@
+//!
+//! # Cluster Events Request
+//!
+//! Stream typed change events derived from topic, partition and mirror stores.
+//!
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use tracing::{debug, error, trace, instrument};
+use anyhow::{anyhow, Result};
+
+use fluvio_protocol::api::{RequestHeader, RequestMessage, ResponseMessage};
+use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::record::ReplicaKey;
+use fluvio_sc_schema::events::{
+    ClusterChange, ClusterEvent, ClusterEventsRequest, ClusterEventsResponse, LeaderChangeEvent,
+    MirrorStateEvent, TopicEvent,
+};
+use fluvio_sc_schema::topic::TopicSpec;
+use fluvio_controlplane_metadata::extended::SpecExt;
+use fluvio_auth::{AuthContext, TypeAction};
+use fluvio_socket::ExclusiveFlvSink;
+use fluvio_stream_model::core::MetadataItem;
+use fluvio_types::SpuId;
+use fluvio_types::event::StickyEvent;
+
+use crate::core::{Context, SharedContext};
+use crate::services::auth::AuthServiceContext;
+
+/// Handler for cluster events request, spawns controller streaming events until connection ends
+#[instrument(skip(request, auth_ctx, sink, end_event))]
+pub async fn handle_cluster_events_request<AC: AuthContext, C: MetadataItem + 'static>(
+    request: RequestMessage<ClusterEventsRequest>,
+    auth_ctx: &AuthServiceContext<AC, C>,
+    mut sink: ExclusiveFlvSink,
+    end_event: Arc<StickyEvent>,
+) -> Result<()> {
+    let (header, req) = request.get_header_request();
+
+    let allowed = auth_ctx
+        .auth
+        .allow_type_action(TopicSpec::OBJECT_TYPE, TypeAction::Read)
+        .await
+        .map_err(|_| anyhow!("authorization io error"))?;
+
+    if !allowed {
+        trace!("authorization failed");
+        let response =
+            ClusterEventsResponse::error(ErrorCode::PermissionDenied, "permission denied");
+        sink.send_response(
+            &ResponseMessage::from_header(&header, response),
+            header.api_version(),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let controller = ClusterEventsController {
+        sink,
+        end_event,
+        header,
+        request: req,
+        ctx: auth_ctx.global_ctx.clone(),
+    };
+    fluvio_future::task::spawn(controller.dispatch_loop());
+    Ok(())
+}
+
+struct ClusterEventsController<C: MetadataItem> {
+    sink: ExclusiveFlvSink,
+    end_event: Arc<StickyEvent>,
+    header: RequestHeader,
+    request: ClusterEventsRequest,
+    ctx: SharedContext<C>,
+}
+
+impl<C: MetadataItem + 'static> ClusterEventsController<C> {
+    #[instrument(skip(self), name = "ClusterEventsLoop", fields(sink = self.sink.id()))]
+    async fn dispatch_loop(mut self) {
+        use tokio::select;
+
+        let mut topic_listener = self.ctx.topics().change_listener();
+        let mut partition_listener = self.ctx.partitions().change_listener();
+        let mut mirror_listener = self.ctx.mirrors().change_listener();
+
+        // changes before subscription are not reported
+        topic_listener.load_last();
+        partition_listener.load_last();
+        mirror_listener.load_last();
+        let mut state = snapshot(&self.ctx).await;
+
+        loop {
+            select! {
+                _ = self.end_event.listen() => {
+                    debug!("connection has been terminated");
+                    break;
+                },
+                _ = topic_listener.listen() => {},
+                _ = partition_listener.listen() => {},
+                _ = mirror_listener.listen() => {},
+            }
+
+            topic_listener.load_last();
+            partition_listener.load_last();
+            mirror_listener.load_last();
+            let current = snapshot(&self.ctx).await;
+            let timestamp_ms = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|elapsed| elapsed.as_millis() as u64)
+                .unwrap_or_default();
+            let events: Vec<ClusterEvent> = state
+                .diff(&current)
+                .into_iter()
+                .map(|change| ClusterEvent {
+                    timestamp_ms,
+                    change,
+                })
+                .filter(|event| self.request.is_subscribed(event))
+                .collect();
+            state = current;
+
+            if events.is_empty() {
+                continue;
+            }
+
+            trace!(events = events.len(), "sending cluster events");
+            let response =
+                ResponseMessage::from_header(&self.header, ClusterEventsResponse::new(events));
+            if let Err(err) = self
+                .sink
+                .send_response(&response, self.header.api_version())
+                .await
+            {
+                error!(
+                    correlation_id = self.header.correlation_id(),
+                    "error sending cluster events: {err}"
+                );
+                self.end_event.notify();
+                break;
+            }
+        }
+
+        debug!("cluster events stream is done, terminating");
+    }
+}
+
+/// cluster state events are derived from
+#[derive(Debug, Default)]
+struct EventState {
+    topics: HashSet<String>,
+    leaders: HashMap<ReplicaKey, SpuId>,
+    mirrors: HashMap<String, String>,
+}
+
+impl EventState {
+    /// changes from self to current state
+    fn diff(&self, current: &Self) -> Vec<ClusterChange> {
+        let mut changes = vec![];
+
+        let mut added: Vec<&String> = current.topics.difference(&self.topics).collect();
+        added.sort();
+        changes.extend(added.into_iter().map(|name| {
+            ClusterChange::TopicAdded(TopicEvent {
+                name: name.to_owned(),
+            })
+        }));
+        let mut deleted: Vec<&String> = self.topics.difference(&current.topics).collect();
+        deleted.sort();
+        changes.extend(deleted.into_iter().map(|name| {
+            ClusterChange::TopicDeleted(TopicEvent {
+                name: name.to_owned(),
+            })
+        }));
+
+        // new partitions are covered by topic added
+        let mut leaders: Vec<LeaderChangeEvent> = current
+            .leaders
+            .iter()
+            .filter_map(|(replica, leader)| {
+                let previous = self.leaders.get(replica)?;
+                (previous != leader).then(|| LeaderChangeEvent {
+                    topic: replica.topic.clone(),
+                    partition: replica.partition,
+                    leader: *leader,
+                    previous_leader: *previous,
+                })
+            })
+            .collect();
+        leaders.sort_by(|a, b| (&a.topic, a.partition).cmp(&(&b.topic, b.partition)));
+        changes.extend(
+            leaders
+                .into_iter()
+                .map(ClusterChange::PartitionLeaderChanged),
+        );
+
+        let mut mirrors: Vec<MirrorStateEvent> = current
+            .mirrors
+            .iter()
+            .filter_map(|(name, state)| {
+                let previous = self.mirrors.get(name).cloned().unwrap_or_default();
+                (&previous != state).then(|| MirrorStateEvent {
+                    name: name.clone(),
+                    state: state.clone(),
+                    previous_state: previous,
+                })
+            })
+            .collect();
+        mirrors.sort_by(|a, b| a.name.cmp(&b.name));
+        changes.extend(mirrors.into_iter().map(ClusterChange::MirrorStateChanged));
+
+        changes
+    }
+}
+
+async fn snapshot<C: MetadataItem>(ctx: &Context<C>) -> EventState {
+    EventState {
+        topics: ctx
+            .topics()
+            .store()
+            .clone_keys()
+            .await
+            .into_iter()
+            .collect(),
+        leaders: ctx
+            .partitions()
+            .store()
+            .read()
+            .await
+            .values()
+            .map(|partition| (partition.key().clone(), partition.spec.leader))
+            .collect(),
+        mirrors: ctx
+            .mirrors()
+            .store()
+            .read()
+            .await
+            .values()
+            .map(|mirror| (mirror.key().to_string(), mirror.status.to_string()))
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_state_diff() {
+        let before = EventState {
+            topics: ["orders", "payments"].map(String::from).into(),
+            leaders: [
+                (ReplicaKey::new("orders", 0u32), 5001),
+                (ReplicaKey::new("payments", 0u32), 5002),
+            ]
+            .into(),
+            mirrors: [("edge-1".to_owned(), "Online".to_owned())].into(),
+        };
+        let after = EventState {
+            topics: ["orders", "users"].map(String::from).into(),
+            leaders: [
+                (ReplicaKey::new("orders", 0u32), 5002),
+                (ReplicaKey::new("users", 0u32), 5001),
+            ]
+            .into(),
+            mirrors: [("edge-1".to_owned(), "Offline".to_owned())].into(),
+        };
+
+        let changes = before.diff(&after);
+        assert_eq!(
+            changes,
+            vec![
+                ClusterChange::TopicAdded(TopicEvent {
+                    name: "users".to_owned()
+                }),
+                ClusterChange::TopicDeleted(TopicEvent {
+                    name: "payments".to_owned()
+                }),
+                ClusterChange::PartitionLeaderChanged(LeaderChangeEvent {
+                    topic: "orders".to_owned(),
+                    partition: 0,
+                    leader: 5002,
+                    previous_leader: 5001,
+                }),
+                ClusterChange::MirrorStateChanged(MirrorStateEvent {
+                    name: "edge-1".to_owned(),
+                    state: "Offline".to_owned(),
+                    previous_state: "Online".to_owned(),
+                }),
+            ]
+        );
+
+        assert!(after.diff(&after).is_empty());
+    }
+}
@@ -13,6 +13,11 @@ mod tableformat;
 mod derivedstream;
 mod mirror;
 mod mirroring;
+mod cli_release;
+mod audit;
+mod acl;
+mod diagnostics;
+mod events;
 
 pub use server::start_public_server;
 
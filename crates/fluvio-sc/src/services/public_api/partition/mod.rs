@@ -1,3 +1,7 @@
+mod reassign;
+
+pub use reassign::handle_reassign_partition_request;
+
 use std::io::{Error, ErrorKind};
 
 use fluvio_stream_model::core::MetadataItem;
@@ -0,0 +1,177 @@
+//!
+//! # Reassign Partition Request
+//!
+//! Validates target replicas and starts reassignment. Target SPUs are added to the
+//! partition replicas, the partition controller switches leader and drops old replicas
+//! once target replicas are in sync.
+//!
+
+use std::collections::HashSet;
+
+use tracing::{info, debug, trace, instrument};
+use anyhow::{anyhow, Result};
+
+use fluvio_protocol::api::{RequestMessage, ResponseMessage};
+use fluvio_protocol::link::ErrorCode;
+use fluvio_sc_schema::acl::{AclPermission, AclResourceType};
+use fluvio_sc_schema::Status;
+use fluvio_sc_schema::partition::{PartitionReassignment, PartitionSpec, ReassignPartitionRequest};
+use fluvio_controlplane_metadata::extended::SpecExt;
+use fluvio_auth::{AuthContext, TypeAction};
+use fluvio_stream_model::core::{MetadataItem, Spec};
+use fluvio_types::SpuId;
+
+use crate::services::auth::AuthServiceContext;
+use crate::stores::spu::SpuLocalStorePolicy;
+
+/// Handler for reassign partition request
+#[instrument(skip(request, auth_ctx))]
+pub async fn handle_reassign_partition_request<AC: AuthContext, C: MetadataItem>(
+    request: RequestMessage<ReassignPartitionRequest>,
+    auth_ctx: &AuthServiceContext<AC, C>,
+) -> Result<ResponseMessage<Status>> {
+    let (header, req) = request.get_header_request();
+    let summary = format!("target={:?}", req.target);
+    let status = reassign_partition(req, auth_ctx).await?;
+    auth_ctx.audit(
+        "reassign-partition",
+        PartitionSpec::LABEL,
+        &status.name,
+        summary,
+        &status.error_code,
+    );
+    trace!("reassign partition resp {:#?}", status);
+    Ok(ResponseMessage::from_header(&header, status))
+}
+
+async fn reassign_partition<AC: AuthContext, C: MetadataItem>(
+    req: ReassignPartitionRequest,
+    auth_ctx: &AuthServiceContext<AC, C>,
+) -> Result<Status> {
+    let ReassignPartitionRequest { replica_id, target } = req;
+    let name = replica_id.to_string();
+
+    info!(replica = %replica_id, ?target, "reassigning partition");
+
+    if let Ok(authorized) = auth_ctx
+        .auth
+        .allow_type_action(PartitionSpec::OBJECT_TYPE, TypeAction::Create)
+        .await
+    {
+        if !authorized {
+            trace!("authorization failed");
+            return Ok(Status::new(
+                name,
+                ErrorCode::PermissionDenied,
+                Some(String::from("permission denied")),
+            ));
+        }
+    } else {
+        return Err(anyhow!("authorization io error"));
+    }
+
+    if !auth_ctx
+        .allow_acl(
+            AclResourceType::Topic,
+            &replica_id.topic,
+            AclPermission::Admin,
+        )
+        .await
+    {
+        trace!("acl denied");
+        return Ok(Status::new(
+            name,
+            ErrorCode::PermissionDenied,
+            Some(String::from("permission denied by ACL")),
+        ));
+    }
+
+    let ctx = &auth_ctx.global_ctx;
+    let Some(mut spec) = ctx.partitions().store().spec(&replica_id).await else {
+        return Ok(Status::new(
+            name.clone(),
+            ErrorCode::Other("partition not found".to_owned()),
+            Some(format!("partition {name} not found")),
+        ));
+    };
+
+    if let Some(reassignment) = &spec.reassignment {
+        return Ok(Status::new(
+            name,
+            ErrorCode::PartitionReassignmentInProgress,
+            Some(format!(
+                "partition is already moving to {:?}",
+                reassignment.target
+            )),
+        ));
+    }
+
+    if let Err(msg) = validate_target(&target, spec.replicas.len()) {
+        return Ok(Status::new(name, ErrorCode::Other(msg.clone()), Some(msg)));
+    }
+
+    for spu in &target {
+        let Some(spu_md) = ctx.spus().store().get_by_id(*spu).await else {
+            return Ok(Status::new(
+                name,
+                ErrorCode::SpuNotFound,
+                Some(format!("spu {spu} not found")),
+            ));
+        };
+        if spu_md.spec.draining && !spec.replicas.contains(spu) {
+            let msg = format!("spu {spu} is draining");
+            return Ok(Status::new(name, ErrorCode::Other(msg.clone()), Some(msg)));
+        }
+    }
+
+    if spec.leader == target[0] && spec.replicas == target {
+        debug!("partition already on target replicas");
+        return Ok(Status::new_ok(name));
+    }
+
+    // new replicas are added as followers so they can catch up before leader is switched
+    for spu in &target {
+        if !spec.replicas.contains(spu) {
+            spec.replicas.push(*spu);
+        }
+    }
+    spec.reassignment = Some(PartitionReassignment::new(target));
+
+    if let Err(err) = ctx.partitions().create_spec(replica_id, spec).await {
+        return Ok(Status::new(
+            name,
+            ErrorCode::Other("unable to start reassignment".to_owned()),
+            Some(err.to_string()),
+        ));
+    }
+
+    Ok(Status::new_ok(name))
+}
+
+/// target must have unique spus and keep replication factor
+fn validate_target(target: &[SpuId], replication_factor: usize) -> Result<(), String> {
+    if target.len() != replication_factor {
+        return Err(format!(
+            "target must have {replication_factor} replicas, found {}",
+            target.len()
+        ));
+    }
+    let unique: HashSet<&SpuId> = target.iter().collect();
+    if unique.len() != target.len() {
+        return Err("target contains duplicate spus".to_owned());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::validate_target;
+
+    #[test]
+    fn test_validate_target() {
+        assert!(validate_target(&[1, 2], 2).is_ok());
+        assert!(validate_target(&[1], 2).is_err());
+        assert!(validate_target(&[1, 1], 2).is_err());
+    }
+}
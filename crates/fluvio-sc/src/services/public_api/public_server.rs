@@ -18,7 +18,7 @@ use anyhow::Result;
 
 use fluvio_service::ConnectInfo;
 use fluvio_types::event::StickyEvent;
-use fluvio_auth::Authorization;
+use fluvio_auth::{AuthContext, Authorization};
 use fluvio_stream_model::core::MetadataItem;
 use fluvio_service::api_loop;
 use fluvio_service::call_service;
@@ -73,6 +73,11 @@ where
             auth_context,
         ));
 
+        let mut rate_limit = ctx
+            .global_ctx
+            .rate_limiter()
+            .connection(service_context.auth.principal());
+
         let (sink, mut stream) = socket.split();
         let mut api_stream = stream.api_stream::<AdminPublicDecodedRequest, AdminPublicApiKey>();
         let mut shared_sink = sink.as_shared();
@@ -94,30 +99,109 @@ where
                 request,
                 super::create::handle_create_request(request, &service_context),
                 shared_sink,
-                "create  handler"
+                "create  handler",
+                rate_limit
             ),
             AdminPublicDecodedRequest::DeleteRequest(request) => call_service!(
                 request,
                 super::delete::handle_delete_request(request, &service_context),
                 shared_sink,
-                "delete  handler"
+                "delete  handler",
+                rate_limit
             ),
 
             AdminPublicDecodedRequest::ListRequest(request) => call_service!(
                 request,
                 super::list::handle_list_request(request, &service_context),
                 shared_sink,
-                "list handler"
+                "list handler",
+                rate_limit
+            ),
+            AdminPublicDecodedRequest::ReassignPartitionRequest(request) => call_service!(
+                request,
+                super::partition::handle_reassign_partition_request(request, &service_context),
+                shared_sink,
+                "reassign partition handler",
+                rate_limit
+            ),
+            AdminPublicDecodedRequest::AddPartitionsRequest(request) => call_service!(
+                request,
+                super::topic::handle_add_partitions_request(request, &service_context),
+                shared_sink,
+                "add partitions handler",
+                rate_limit
+            ),
+            AdminPublicDecodedRequest::DrainSpuRequest(request) => call_service!(
+                request,
+                super::spu::handle_drain_spu_request(request, &service_context),
+                shared_sink,
+                "drain spu handler",
+                rate_limit
+            ),
+            AdminPublicDecodedRequest::SetReadOnlyRequest(request) => call_service!(
+                request,
+                super::topic::handle_set_read_only_request(request, &service_context),
+                shared_sink,
+                "set read-only handler",
+                rate_limit
+            ),
+            AdminPublicDecodedRequest::RecommendedCliRequest(request) => call_service!(
+                request,
+                super::cli_release::handle_recommended_cli_request(request, &service_context),
+                shared_sink,
+                "recommended cli handler",
+                rate_limit
             ),
-            AdminPublicDecodedRequest::MirroringRequest(request) =>
-                super::mirroring::handle_mirroring_request(request, &service_context, shared_sink.clone(), end_event.clone())?,
-            AdminPublicDecodedRequest::WatchRequest(request) =>
+            AdminPublicDecodedRequest::AuditLogRequest(request) => call_service!(
+                request,
+                super::audit::handle_audit_log_request(request, &service_context),
+                shared_sink,
+                "audit log handler",
+                rate_limit
+            ),
+            AdminPublicDecodedRequest::AclListRequest(request) => call_service!(
+                request,
+                super::acl::handle_acl_list_request(request, &service_context),
+                shared_sink,
+                "acl list handler",
+                rate_limit
+            ),
+            AdminPublicDecodedRequest::AclUpdateRequest(request) => call_service!(
+                request,
+                super::acl::handle_acl_update_request(request, &service_context),
+                shared_sink,
+                "acl update handler",
+                rate_limit
+            ),
+            AdminPublicDecodedRequest::ClusterDiagnosticsRequest(request) => call_service!(
+                request,
+                super::diagnostics::handle_cluster_diagnostics_request(request, &service_context),
+                shared_sink,
+                "cluster diagnostics handler",
+                rate_limit
+            ),
+            AdminPublicDecodedRequest::ClusterEventsRequest(request) => {
+                rate_limit.acquire().await;
+                super::events::handle_cluster_events_request(
+                    request,
+                    &service_context,
+                    shared_sink.clone(),
+                    end_event.clone(),
+                ).await?
+            },
+            AdminPublicDecodedRequest::MirroringRequest(request) => {
+                rate_limit.acquire().await;
+                super::mirroring::handle_mirroring_request(request, &service_context, shared_sink.clone(), end_event.clone())?
+            },
+            AdminPublicDecodedRequest::WatchRequest(request) => {
+                rate_limit.acquire().await;
                 super::watch::handle_watch_request(
                     request,
                     &service_context,
                     shared_sink.clone(),
                     end_event.clone(),
                 )?
+            }
 
         );
 
@@ -0,0 +1,146 @@
+//!
+//! # Drain SPU Request
+//!
+//! Marks SPU as draining, or clears it. Partition controller moves leadership of
+//! partitions led by draining SPU to caught up followers, scheduler doesn't assign
+//! new replicas to it. Response reports partitions still led by SPU.
+//!
+
+use std::collections::HashSet;
+
+use tracing::{info, debug, trace, instrument};
+use anyhow::{anyhow, Result};
+
+use fluvio_protocol::api::{RequestMessage, ResponseMessage};
+use fluvio_protocol::link::ErrorCode;
+use fluvio_sc_schema::partition::PartitionSpec;
+use fluvio_sc_schema::spu::{DrainSpuRequest, DrainSpuResponse, SpuSpec};
+use fluvio_controlplane_metadata::extended::SpecExt;
+use fluvio_auth::{AuthContext, TypeAction};
+use fluvio_stream_model::core::{MetadataItem, Spec};
+use fluvio_types::SpuId;
+
+use crate::services::auth::AuthServiceContext;
+use crate::stores::spu::SpuLocalStorePolicy;
+
+/// Handler for drain spu request
+#[instrument(skip(request, auth_ctx))]
+pub async fn handle_drain_spu_request<AC: AuthContext, C: MetadataItem>(
+    request: RequestMessage<DrainSpuRequest>,
+    auth_ctx: &AuthServiceContext<AC, C>,
+) -> Result<ResponseMessage<DrainSpuResponse>> {
+    let (header, req) = request.get_header_request();
+    let response = drain_spu(req, auth_ctx).await?;
+    trace!("drain spu resp {:#?}", response);
+    Ok(ResponseMessage::from_header(&header, response))
+}
+
+async fn drain_spu<AC: AuthContext, C: MetadataItem>(
+    req: DrainSpuRequest,
+    auth_ctx: &AuthServiceContext<AC, C>,
+) -> Result<DrainSpuResponse> {
+    let DrainSpuRequest { spu_id, cancel } = req;
+
+    if let Ok(authorized) = auth_ctx
+        .auth
+        .allow_type_action(SpuSpec::OBJECT_TYPE, TypeAction::Create)
+        .await
+    {
+        if !authorized {
+            trace!("authorization failed");
+            return Ok(DrainSpuResponse::error(
+                ErrorCode::PermissionDenied,
+                "permission denied",
+            ));
+        }
+    } else {
+        return Err(anyhow!("authorization io error"));
+    }
+
+    let ctx = &auth_ctx.global_ctx;
+    let Some(spu) = ctx.spus().store().get_by_id(spu_id).await else {
+        return Ok(DrainSpuResponse::error(
+            ErrorCode::SpuNotFound,
+            format!("spu {spu_id} not found"),
+        ));
+    };
+
+    let draining = !cancel;
+    if spu.spec.draining != draining {
+        info!(spu_id, draining, "changing spu drain");
+        let mut spec = spu.spec.clone();
+        spec.draining = draining;
+        // drain is polled until done, only changes are recorded
+        let operation = if draining { "drain" } else { "cancel-drain" };
+        if let Err(err) = ctx.spus().create_spec(spu.key_owned(), spec).await {
+            let error_code = ErrorCode::Other("unable to update spu".to_owned());
+            auth_ctx.audit(
+                operation,
+                SpuSpec::LABEL,
+                &spu_id.to_string(),
+                "",
+                &error_code,
+            );
+            return Ok(DrainSpuResponse::error(error_code, err.to_string()));
+        }
+        auth_ctx.audit(
+            operation,
+            SpuSpec::LABEL,
+            &spu_id.to_string(),
+            "",
+            &ErrorCode::None,
+        );
+    } else {
+        debug!(spu_id, draining, "spu drain is unchanged");
+    }
+
+    let draining_spus = ctx.spus().store().draining_spus().await;
+    let partitions = ctx.partitions().store().clone_specs().await;
+    let (leaders, blocked) = drain_progress(spu_id, &partitions, &draining_spus);
+    Ok(DrainSpuResponse::new(leaders, blocked))
+}
+
+/// partitions led by spu, and those of them which have no replica outside of draining spus
+fn drain_progress(
+    spu_id: SpuId,
+    partitions: &[PartitionSpec],
+    draining: &HashSet<SpuId>,
+) -> (u32, u32) {
+    let mut leaders = 0;
+    let mut blocked = 0;
+    for partition in partitions.iter().filter(|p| p.leader == spu_id) {
+        leaders += 1;
+        if partition
+            .replicas
+            .iter()
+            .all(|replica| *replica == spu_id || draining.contains(replica))
+        {
+            blocked += 1;
+        }
+    }
+    (leaders, blocked)
+}
+
+#[cfg(test)]
+mod test {
+
+    use std::collections::HashSet;
+
+    use fluvio_sc_schema::partition::PartitionSpec;
+
+    use super::drain_progress;
+
+    #[test]
+    fn test_drain_progress() {
+        let partitions = vec![
+            PartitionSpec::new(1, vec![1, 2]),
+            PartitionSpec::new(1, vec![1]),
+            PartitionSpec::new(1, vec![1, 3]),
+            PartitionSpec::new(2, vec![2, 1]),
+        ];
+        let draining = HashSet::from([1, 3]);
+
+        assert_eq!(drain_progress(1, &partitions, &draining), (3, 2));
+        assert_eq!(drain_progress(4, &partitions, &draining), (0, 0));
+    }
+}
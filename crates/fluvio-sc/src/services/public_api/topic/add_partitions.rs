@@ -0,0 +1,171 @@
+//!
+//! # Add Partitions Request
+//!
+//! Increases partition count of topic with computed replicas. Topic controller schedules
+//! replicas for new partitions, existing partitions are not changed.
+//!
+
+use tracing::{info, debug, trace, instrument};
+use anyhow::{anyhow, Result};
+
+use fluvio_protocol::api::{RequestMessage, ResponseMessage};
+use fluvio_protocol::link::ErrorCode;
+use fluvio_sc_schema::acl::{AclPermission, AclResourceType};
+use fluvio_sc_schema::Status;
+use fluvio_sc_schema::topic::{AddPartitionsRequest, ReplicaSpec, TopicSpec};
+use fluvio_controlplane_metadata::extended::SpecExt;
+use fluvio_auth::{AuthContext, TypeAction};
+use fluvio_stream_model::core::{MetadataItem, Spec};
+use fluvio_types::PartitionCount;
+
+use crate::services::auth::AuthServiceContext;
+
+/// Handler for add partitions request
+#[instrument(skip(request, auth_ctx))]
+pub async fn handle_add_partitions_request<AC: AuthContext, C: MetadataItem>(
+    request: RequestMessage<AddPartitionsRequest>,
+    auth_ctx: &AuthServiceContext<AC, C>,
+) -> Result<ResponseMessage<Status>> {
+    let (header, req) = request.get_header_request();
+    let (topic, summary) = (req.topic.clone(), format!("partitions={}", req.partitions));
+    let status = add_partitions(req, auth_ctx).await?;
+    auth_ctx.audit(
+        "add-partitions",
+        TopicSpec::LABEL,
+        &topic,
+        summary,
+        &status.error_code,
+    );
+    trace!("add partitions resp {:#?}", status);
+    Ok(ResponseMessage::from_header(&header, status))
+}
+
+async fn add_partitions<AC: AuthContext, C: MetadataItem>(
+    req: AddPartitionsRequest,
+    auth_ctx: &AuthServiceContext<AC, C>,
+) -> Result<Status> {
+    let AddPartitionsRequest { topic, partitions } = req;
+
+    info!(%topic, partitions, "adding partitions");
+
+    if let Ok(authorized) = auth_ctx
+        .auth
+        .allow_type_action(TopicSpec::OBJECT_TYPE, TypeAction::Create)
+        .await
+    {
+        if !authorized {
+            trace!("authorization failed");
+            return Ok(Status::new(
+                topic,
+                ErrorCode::PermissionDenied,
+                Some(String::from("permission denied")),
+            ));
+        }
+    } else {
+        return Err(anyhow!("authorization io error"));
+    }
+
+    if !auth_ctx
+        .allow_acl(AclResourceType::Topic, &topic, AclPermission::Admin)
+        .await
+    {
+        trace!("acl denied");
+        return Ok(Status::new(
+            topic.clone(),
+            ErrorCode::PermissionDenied,
+            Some(String::from("permission denied by ACL")),
+        ));
+    }
+
+    let ctx = &auth_ctx.global_ctx;
+    let Some(topic_obj) = ctx.topics().store().value(&topic).await else {
+        return Ok(Status::new(
+            topic.clone(),
+            ErrorCode::TopicNotFound,
+            Some(format!("topic {topic} not found")),
+        ));
+    };
+    if topic_obj.inner().status().resolution.is_being_deleted() {
+        return Ok(Status::new(
+            topic,
+            ErrorCode::TopicNotFound,
+            Some("topic is being deleted".to_owned()),
+        ));
+    }
+
+    let mut spec = topic_obj.inner().spec().clone();
+    let replicas = match increase_partitions(spec.replicas(), partitions) {
+        Ok(Some(replicas)) => replicas,
+        Ok(None) => {
+            debug!("topic already has requested partitions");
+            return Ok(Status::new_ok(topic));
+        }
+        Err(msg) => {
+            return Ok(Status::new(
+                topic,
+                ErrorCode::TopicInvalidConfiguration,
+                Some(msg),
+            ))
+        }
+    };
+    spec.set_replicas(replicas);
+
+    if let Err(err) = ctx.topics().create_spec(topic.clone(), spec).await {
+        return Ok(Status::new(
+            topic,
+            ErrorCode::TopicError,
+            Some(err.to_string()),
+        ));
+    }
+
+    Ok(Status::new_ok(topic))
+}
+
+/// compute replica spec with new partition count, None if count is unchanged.
+/// Only computed replicas can be increased, partition count can never decrease
+fn increase_partitions(
+    replicas: &ReplicaSpec,
+    partitions: PartitionCount,
+) -> Result<Option<ReplicaSpec>, String> {
+    let ReplicaSpec::Computed(param) = replicas else {
+        return Err("partitions can be added only to topic with computed replicas".to_owned());
+    };
+
+    if partitions == param.partitions {
+        return Ok(None);
+    }
+    if partitions < param.partitions {
+        return Err(format!(
+            "partition count can't be decreased from {} to {partitions}",
+            param.partitions
+        ));
+    }
+
+    let mut param = param.clone();
+    param.partitions = partitions;
+    Ok(Some(ReplicaSpec::Computed(param)))
+}
+
+#[cfg(test)]
+mod test {
+
+    use fluvio_sc_schema::topic::{PartitionMaps, ReplicaSpec, TopicReplicaParam};
+
+    use super::increase_partitions;
+
+    #[test]
+    fn test_increase_partitions() {
+        let computed = ReplicaSpec::Computed(TopicReplicaParam::new(2, 1, false));
+
+        let increased = increase_partitions(&computed, 4)
+            .expect("valid")
+            .expect("changed");
+        assert_eq!(increased.partitions(), 4);
+
+        assert!(increase_partitions(&computed, 2).expect("valid").is_none());
+        assert!(increase_partitions(&computed, 1).is_err());
+
+        let assigned = ReplicaSpec::Assigned(PartitionMaps::default());
+        assert!(increase_partitions(&assigned, 4).is_err());
+    }
+}
@@ -13,6 +13,7 @@ use tracing::{info, debug, trace, instrument};
 use anyhow::{anyhow, Result};
 
 use fluvio_protocol::link::ErrorCode;
+use fluvio_sc_schema::acl::{AclPermission, AclResourceType};
 use fluvio_controlplane_metadata::topic::ReplicaSpec;
 use fluvio_sc_schema::objects::CreateRequest;
 use fluvio_sc_schema::shared::validate_resource_name;
@@ -58,6 +59,18 @@ pub(crate) async fn handle_create_topics_request<AC: AuthContext, C: MetadataIte
         return Err(anyhow!("authorization io error"));
     }
 
+    if !auth_ctx
+        .allow_acl(AclResourceType::Topic, &name, AclPermission::Admin)
+        .await
+    {
+        trace!("acl denied");
+        return Ok(Status::new(
+            name.clone(),
+            ErrorCode::PermissionDenied,
+            Some(String::from("permission denied by ACL")),
+        ));
+    }
+
     // validate topic request
     let mut status = validate_topic_request::<C>(&name, &topic, &auth_ctx.global_ctx).await;
     if status.is_error() {
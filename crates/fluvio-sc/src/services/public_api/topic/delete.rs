@@ -9,6 +9,7 @@ use tracing::{info, trace, instrument};
 use std::io::{Error, ErrorKind};
 
 use fluvio_protocol::link::ErrorCode;
+use fluvio_sc_schema::acl::{AclPermission, AclResourceType};
 use fluvio_sc_schema::Status;
 use fluvio_controlplane_metadata::topic::TopicSpec;
 use fluvio_auth::{AuthContext, InstanceAction};
@@ -42,6 +43,18 @@ pub async fn handle_delete_topic<AC: AuthContext, C: MetadataItem>(
         return Err(Error::new(ErrorKind::Interrupted, "authorization io error"));
     }
 
+    if !auth_ctx
+        .allow_acl(AclResourceType::Topic, &topic_name, AclPermission::Admin)
+        .await
+    {
+        trace!("acl denied");
+        return Ok(Status::new(
+            topic_name.clone(),
+            ErrorCode::PermissionDenied,
+            Some(String::from("permission denied by ACL")),
+        ));
+    }
+
     let status = if let Some(spec) = auth_ctx
         .global_ctx
         .topics()
@@ -1,7 +1,11 @@
 mod create;
 mod delete;
 mod fetch;
+mod add_partitions;
+mod read_only;
 
 pub(crate) use create::*;
 pub(crate) use delete::*;
 pub(crate) use fetch::*;
+pub(crate) use add_partitions::handle_add_partitions_request;
+pub(crate) use read_only::handle_set_read_only_request;
@@ -0,0 +1,140 @@
+//!
+//! # Set Read-Only Request
+//!
+//! Marks topic or one of its partitions read-only. Flag is kept in partition spec and sent to
+//! SPUs with replica, so leader rejects produces while fetches are still served.
+//! Topic flag is copied to partitions added later.
+//!
+
+use tracing::{info, trace, instrument};
+use anyhow::{anyhow, Result};
+
+use fluvio_protocol::api::{RequestMessage, ResponseMessage};
+use fluvio_protocol::link::ErrorCode;
+use fluvio_sc_schema::acl::{AclPermission, AclResourceType};
+use fluvio_sc_schema::Status;
+use fluvio_sc_schema::topic::{SetReadOnlyRequest, TopicSpec};
+use fluvio_controlplane_metadata::extended::SpecExt;
+use fluvio_auth::{AuthContext, TypeAction};
+use fluvio_stream_model::core::{MetadataItem, Spec};
+
+use crate::services::auth::AuthServiceContext;
+use crate::stores::partition::PartitionLocalStorePolicy;
+
+/// Handler for set read-only request
+#[instrument(skip(request, auth_ctx))]
+pub async fn handle_set_read_only_request<AC: AuthContext, C: MetadataItem>(
+    request: RequestMessage<SetReadOnlyRequest>,
+    auth_ctx: &AuthServiceContext<AC, C>,
+) -> Result<ResponseMessage<Status>> {
+    let (header, req) = request.get_header_request();
+    let topic = req.topic.clone();
+    let summary = format!("partition={:?} read_only={}", req.partition, req.read_only);
+    let status = set_read_only(req, auth_ctx).await?;
+    auth_ctx.audit(
+        "set-read-only",
+        TopicSpec::LABEL,
+        &topic,
+        summary,
+        &status.error_code,
+    );
+    trace!("set read-only resp {:#?}", status);
+    Ok(ResponseMessage::from_header(&header, status))
+}
+
+async fn set_read_only<AC: AuthContext, C: MetadataItem>(
+    req: SetReadOnlyRequest,
+    auth_ctx: &AuthServiceContext<AC, C>,
+) -> Result<Status> {
+    let SetReadOnlyRequest {
+        topic,
+        partition,
+        read_only,
+    } = req;
+
+    info!(%topic, ?partition, read_only, "setting read-only");
+
+    if let Ok(authorized) = auth_ctx
+        .auth
+        .allow_type_action(TopicSpec::OBJECT_TYPE, TypeAction::Create)
+        .await
+    {
+        if !authorized {
+            trace!("authorization failed");
+            return Ok(Status::new(
+                topic,
+                ErrorCode::PermissionDenied,
+                Some(String::from("permission denied")),
+            ));
+        }
+    } else {
+        return Err(anyhow!("authorization io error"));
+    }
+
+    if !auth_ctx
+        .allow_acl(AclResourceType::Topic, &topic, AclPermission::Admin)
+        .await
+    {
+        trace!("acl denied");
+        return Ok(Status::new(
+            topic.clone(),
+            ErrorCode::PermissionDenied,
+            Some(String::from("permission denied by ACL")),
+        ));
+    }
+
+    let ctx = &auth_ctx.global_ctx;
+    let Some(topic_obj) = ctx.topics().store().value(&topic).await else {
+        return Ok(Status::new(
+            topic.clone(),
+            ErrorCode::TopicNotFound,
+            Some(format!("topic {topic} not found")),
+        ));
+    };
+    if topic_obj.inner().status().resolution.is_being_deleted() {
+        return Ok(Status::new(
+            topic,
+            ErrorCode::TopicNotFound,
+            Some("topic is being deleted".to_owned()),
+        ));
+    }
+
+    let mut partitions = ctx.partitions().store().topic_partitions(&topic).await;
+    if let Some(partition) = partition {
+        partitions.retain(|p| p.key.partition == partition);
+        if partitions.is_empty() {
+            return Ok(Status::new(
+                topic.clone(),
+                ErrorCode::TopicInvalidConfiguration,
+                Some(format!("partition {topic}/{partition} not found")),
+            ));
+        }
+    } else if topic_obj.inner().spec().is_read_only() != read_only {
+        let mut spec = topic_obj.inner().spec().clone();
+        spec.set_read_only(read_only);
+        if let Err(err) = ctx.topics().create_spec(topic.clone(), spec).await {
+            return Ok(Status::new(
+                topic,
+                ErrorCode::TopicError,
+                Some(err.to_string()),
+            ));
+        }
+    }
+
+    for partition in partitions {
+        if partition.spec.read_only == read_only {
+            continue;
+        }
+        let mut spec = partition.spec;
+        spec.read_only = read_only;
+        if let Err(err) = ctx.partitions().create_spec(partition.key, spec).await {
+            return Ok(Status::new(
+                topic,
+                ErrorCode::TopicError,
+                Some(err.to_string()),
+            ));
+        }
+    }
+
+    Ok(Status::new_ok(topic))
+}
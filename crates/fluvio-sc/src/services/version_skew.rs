@@ -0,0 +1,85 @@
+//!
+//! # Version Skew
+//!
+//! During partial upgrades SPUs may run different version than SC. Versions differing only in
+//! patch are compatible. Versions differing in major or minor, or SPUs not reporting version,
+//! may not agree on protocol and are handled according to [`VersionSkewPolicy`].
+//!
+use semver::Version;
+
+use crate::config::VersionSkewPolicy;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VersionSkew {
+    None,
+    /// same major and minor version
+    Patch,
+    /// different major or minor version
+    Incompatible,
+    /// SPU didn't report version or it is not valid semver
+    Unknown,
+}
+
+impl VersionSkew {
+    /// skew of SPU version from SC version
+    pub fn detect(sc_version: &str, spu_version: Option<&str>) -> Self {
+        let Some(spu_version) = spu_version.map(str::trim) else {
+            return Self::Unknown;
+        };
+        let sc_version = sc_version.trim();
+        if spu_version == sc_version {
+            return Self::None;
+        }
+        match (Version::parse(sc_version), Version::parse(spu_version)) {
+            (Ok(sc), Ok(spu)) if sc.major == spu.major && sc.minor == spu.minor => Self::Patch,
+            (Ok(_), Ok(_)) => Self::Incompatible,
+            _ => Self::Unknown,
+        }
+    }
+
+    pub fn is_compatible(&self) -> bool {
+        matches!(self, Self::None | Self::Patch)
+    }
+
+    /// whether SPU with this skew is allowed to join cluster
+    pub fn allowed_by(&self, policy: VersionSkewPolicy) -> bool {
+        match policy {
+            VersionSkewPolicy::Warn => true,
+            VersionSkewPolicy::Deny => self.is_compatible(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_version_skew() {
+        assert_eq!(
+            VersionSkew::detect("0.11.9", Some("0.11.9")),
+            VersionSkew::None
+        );
+        assert_eq!(
+            VersionSkew::detect("0.11.9\n", Some("0.11.8")),
+            VersionSkew::Patch
+        );
+        assert_eq!(
+            VersionSkew::detect("0.11.9", Some("0.12.0")),
+            VersionSkew::Incompatible
+        );
+        assert_eq!(
+            VersionSkew::detect("0.11.9", Some("dev")),
+            VersionSkew::Unknown
+        );
+        assert_eq!(VersionSkew::detect("0.11.9", None), VersionSkew::Unknown);
+    }
+
+    #[test]
+    fn test_skew_policy() {
+        assert!(VersionSkew::Incompatible.allowed_by(VersionSkewPolicy::Warn));
+        assert!(VersionSkew::Patch.allowed_by(VersionSkewPolicy::Deny));
+        assert!(!VersionSkew::Incompatible.allowed_by(VersionSkewPolicy::Deny));
+        assert!(!VersionSkew::Unknown.allowed_by(VersionSkewPolicy::Deny));
+    }
+}
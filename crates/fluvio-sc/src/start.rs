@@ -14,7 +14,7 @@ use k8_client::{K8Client, K8Config, memory::MemoryClient};
 
 use crate::{
     cli::{ScOpt, TlsConfig, RunMode},
-    services::auth::basic::BasicRbacPolicy,
+    services::auth::AuthConfig,
     config::ScConfig,
     config::DEFAULT_NAMESPACE,
 };
@@ -30,25 +30,25 @@ pub fn main_loop(opt: ScOpt) {
         RunMode::Local(metadata) => {
             info!(?metadata, "Running in local mode");
             let client = create_local_metadata_store(metadata);
-            let ((sc_config, auth_policy), tls_option) = opt.parse_cli_or_exit();
-            local_main_loop(sc_config, client, auth_policy, tls_option)
+            let ((sc_config, auth), tls_option) = opt.parse_cli_or_exit();
+            local_main_loop(sc_config, client, auth, tls_option)
         }
         RunMode::ReadOnly(read_only_path) => {
             let read_only_path = read_only_path.to_path_buf();
             info!("Running in read only mode");
-            let ((sc_config, auth_policy), tls_option) = opt.parse_cli_or_exit();
+            let ((sc_config, auth), tls_option) = opt.parse_cli_or_exit();
 
             info!("initializing metadata from read only configuration");
             let client = fluvio_future::task::run_block_on(async move {
                 create_memory_client(read_only_path).await
             })
             .expect("failed to initialize metadata from read only configuration");
-            local_main_loop(sc_config, client, auth_policy, tls_option)
+            local_main_loop(sc_config, client, auth, tls_option)
         }
         RunMode::K8s => {
             info!("Running with K8");
 
-            let ((mut sc_config, auth_policy), tls_option) = opt.parse_cli_or_exit();
+            let ((mut sc_config, auth), tls_option) = opt.parse_cli_or_exit();
 
             let k8_config = K8Config::load().expect("no k8 config founded");
             info!(?k8_config, "k8 config");
@@ -61,7 +61,7 @@ pub fn main_loop(opt: ScOpt) {
             }
 
             let client = create_k8_client(k8_config).expect("failed to create k8 client");
-            k8_main_loop(sc_config, client, auth_policy, tls_option)
+            k8_main_loop(sc_config, client, auth, tls_option)
         }
     }
 }
@@ -87,7 +87,7 @@ fn inspect_system() {
 fn k8_main_loop<C>(
     sc_config: ScConfig,
     client: SharedClient<C>,
-    auth_policy: Option<BasicRbacPolicy>,
+    auth: AuthConfig,
     tls_option: Option<(String, TlsConfig)>,
 ) where
     C: MetadataClient<K8MetaItem> + 'static,
@@ -95,8 +95,7 @@ fn k8_main_loop<C>(
     run_block_on(async move {
         info!("starting k8 main loop");
 
-        let ctx =
-            crate::init::start_main_loop((sc_config.clone(), auth_policy), client.clone()).await;
+        let ctx = crate::init::start_main_loop((sc_config.clone(), auth), client.clone()).await;
 
         crate::k8::controllers::run_k8_operators(
             sc_config.namespace.clone(),
@@ -119,7 +118,7 @@ fn k8_main_loop<C>(
 fn local_main_loop<C, M>(
     sc_config: ScConfig,
     client: SharedClient<C>,
-    auth_policy: Option<BasicRbacPolicy>,
+    auth: AuthConfig,
     tls_option: Option<(String, TlsConfig)>,
 ) where
     C: MetadataClient<M> + 'static,
@@ -129,7 +128,7 @@ fn local_main_loop<C, M>(
     run_block_on(async move {
         info!("starting local main loop");
 
-        crate::init::start_main_loop((sc_config.clone(), auth_policy), client).await;
+        crate::init::start_main_loop((sc_config.clone(), auth), client).await;
         proxy::start_if(sc_config, tls_option).await;
 
         println!("Streaming Controller started successfully");
@@ -142,38 +141,36 @@ fn local_main_loop<C, M>(
 
 mod proxy {
     use std::process;
+    use std::sync::Arc;
+
     use tracing::info;
 
     use fluvio_types::print_cli_err;
-    pub use fluvio_future::openssl::TlsAcceptor;
-
+    use fluvio_auth::tls::{start_proxy as proxy_start, SharedTlsAcceptor, CERT_RELOAD_INTERVAL};
     use fluvio_auth::x509::X509Authenticator;
-    use flv_tls_proxy::{
-        start as proxy_start, start_with_authenticator as proxy_start_with_authenticator,
-    };
+    use flv_tls_proxy::authenticator::Authenticator;
 
     use crate::{config::ScConfig, cli::TlsConfig};
 
     pub async fn start_if(sc_config: ScConfig, tls_option: Option<(String, TlsConfig)>) {
         if let Some((proxy_port, tls_config)) = tls_option {
             let tls_acceptor = tls_config
-                .try_build_tls_acceptor()
+                .try_build_reloadable_tls_acceptor()
                 .expect("can't build tls acceptor");
+            tls_acceptor.watch(CERT_RELOAD_INTERVAL);
             start_proxy(sc_config, (tls_acceptor, proxy_port)).await;
         }
     }
 
-    async fn start_proxy(config: ScConfig, acceptor: (TlsAcceptor, String)) {
+    async fn start_proxy(config: ScConfig, acceptor: (SharedTlsAcceptor, String)) {
         let (tls_acceptor, proxy_addr) = acceptor;
         let target = config.public_endpoint;
         info!("starting TLS proxy: {}", proxy_addr);
 
-        let result = if let Some(x509_auth_scopes) = config.x509_auth_scopes {
-            let authenticator = Box::new(X509Authenticator::new(&x509_auth_scopes));
-            proxy_start_with_authenticator(&proxy_addr, tls_acceptor, target, authenticator).await
-        } else {
-            proxy_start(&proxy_addr, tls_acceptor, target).await
-        };
+        let authenticator = config.x509_auth_scopes.map(|x509_auth_scopes| {
+            Arc::new(X509Authenticator::new(&x509_auth_scopes)) as Arc<dyn Authenticator>
+        });
+        let result = proxy_start(&proxy_addr, tls_acceptor, target, authenticator).await;
 
         if let Err(err) = result {
             print_cli_err!(err);
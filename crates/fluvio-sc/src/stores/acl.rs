@@ -0,0 +1,52 @@
+//!
+//! # ACL Store
+//!
+//! Each ACL entry is stored as Acl metadata object. Object is named after the entry,
+//! so the same entry always maps to the same object.
+//!
+use sha2::{Digest, Sha256};
+
+pub use fluvio_controlplane_metadata::acl::*;
+pub use fluvio_controlplane_metadata::store::k8::K8MetaItem;
+
+/// name of metadata object storing the entry, valid as K8 object name
+pub fn acl_key(entry: &AclSpec) -> String {
+    let mut hasher = Sha256::new();
+    for field in [
+        entry.principal.as_str(),
+        &entry.resource_type.to_string(),
+        entry.resource.as_str(),
+        &entry.permission.to_string(),
+    ] {
+        hasher.update(field.as_bytes());
+        hasher.update([0]);
+    }
+    format!("acl-{:x}", hasher.finalize())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_acl_key() {
+        let entry = AclSpec::new(
+            "orders-service",
+            AclResourceType::Topic,
+            "orders-*",
+            AclPermission::Produce,
+        );
+        assert_eq!(acl_key(&entry), acl_key(&entry.clone()));
+        assert!(acl_key(&entry).starts_with("acl-"));
+        assert_eq!(acl_key(&entry).len(), 68);
+
+        // fields are separated, moving text between them changes the key
+        let shifted = AclSpec::new(
+            "orders-",
+            AclResourceType::Topic,
+            "service",
+            AclPermission::Produce,
+        );
+        assert_ne!(acl_key(&entry), acl_key(&shifted));
+    }
+}
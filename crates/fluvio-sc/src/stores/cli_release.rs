@@ -0,0 +1,105 @@
+//!
+//! # CLI Release Mirror
+//!
+//! Copy of the Fluvio CLI package from the package index, refreshed by the CLI release
+//! controller. Used to recommend clients the CLI release matching the cluster.
+//!
+use std::sync::Arc;
+
+use async_lock::RwLock;
+use semver::Version;
+
+use fluvio_index::{Package, Target};
+
+pub type SharedCliReleaseMirror = Arc<CliReleaseMirror>;
+
+#[derive(Debug, Default)]
+pub struct CliReleaseMirror {
+    package: RwLock<Option<Package>>,
+}
+
+impl CliReleaseMirror {
+    pub fn shared() -> SharedCliReleaseMirror {
+        Arc::new(Self::default())
+    }
+
+    pub async fn update(&self, package: Package) {
+        *self.package.write().await = Some(package);
+    }
+
+    /// whether the package has been mirrored at least once
+    pub async fn is_mirrored(&self) -> bool {
+        self.package.read().await.is_some()
+    }
+
+    /// CLI release recommended for clients on target, none if not mirrored or not published
+    pub async fn recommended(&self, platform: &Version, target: &Target) -> Option<Version> {
+        let package = self.package.read().await;
+        recommended_release(package.as_ref()?, platform, target)
+    }
+}
+
+/// latest release for target of the same minor version as the platform,
+/// prereleases are only recommended for prerelease platforms
+fn recommended_release(package: &Package, platform: &Version, target: &Target) -> Option<Version> {
+    package
+        .releases_for_target(target)
+        .into_iter()
+        .rev()
+        .filter(|release| !release.yanked)
+        .map(|release| &release.version)
+        .find(|version| {
+            version.major == platform.major
+                && version.minor == platform.minor
+                && (version.pre.is_empty() || !platform.pre.is_empty())
+        })
+        .cloned()
+}
+
+#[cfg(test)]
+mod test {
+
+    use semver::Version;
+
+    use fluvio_index::{Package, PackageId, Target};
+
+    use super::recommended_release;
+
+    #[test]
+    fn test_recommended_release() {
+        let id: PackageId = "fluvio/fluvio".parse().unwrap();
+        let target = Target::X86_64UnknownLinuxMusl;
+        let mut package = Package::new_binary(
+            &id,
+            "Fluvio",
+            "Fluvio CLI",
+            "https://github.com/infinyon/fluvio",
+        );
+        for version in ["0.10.9", "0.11.0", "0.11.1", "0.11.2-beta.1", "0.12.0"] {
+            package
+                .add_release(Version::parse(version).unwrap(), target.clone())
+                .unwrap();
+        }
+
+        let recommended = |platform: &str| {
+            recommended_release(&package, &Version::parse(platform).unwrap(), &target)
+        };
+        assert_eq!(
+            recommended("0.11.0"),
+            Some(Version::parse("0.11.1").unwrap())
+        );
+        assert_eq!(
+            recommended("0.11.2-beta.1"),
+            Some(Version::parse("0.11.2-beta.1").unwrap())
+        );
+        assert_eq!(recommended("0.13.0"), None);
+        assert_eq!(
+            recommended_release(
+                &package,
+                &Version::parse("0.11.0").unwrap(),
+                &Target::X86_64AppleDarwin
+            ),
+            None
+        );
+    }
+}
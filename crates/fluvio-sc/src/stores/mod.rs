@@ -1,9 +1,11 @@
+pub mod acl;
 pub mod spu;
 pub mod topic;
 pub mod partition;
 pub mod spg;
 pub mod smartmodule;
 pub mod tableformat;
+pub mod cli_release;
 
 pub use crate::dispatcher::store::*;
 
@@ -1,4 +1,12 @@
+use std::collections::HashMap;
+
+use fluvio_controlplane::PartitionMetadata;
+use fluvio_controlplane_metadata::topic::LeaderAffinity;
+use fluvio_stream_model::core::MetadataItem;
+use fluvio_types::SpuId;
+
 use super::ReplicaStatus;
+use super::ReplicaSchedulingGroups;
 
 pub enum ElectionScoring {
     NotSuitable,
@@ -21,13 +29,38 @@ pub(crate) trait ElectionPolicy {
         replica_status: &ReplicaStatus,
         leader: &ReplicaStatus,
     ) -> ElectionScoring;
+
+    /// load of spu, used to choose between replicas with same score. less is preferred
+    fn leader_load(&self, _spu: SpuId) -> u16 {
+        0
+    }
+
+    /// rank of spu by leader affinity of partition, preferred over score. less is preferred
+    fn affinity_rank(&self, _spu: SpuId) -> u16 {
+        0
+    }
 }
 
-pub(crate) struct SimplePolicy {}
+#[derive(Default)]
+pub(crate) struct SimplePolicy {
+    // partitions led by each spu
+    leaders: HashMap<SpuId, u16>,
+}
 
 impl SimplePolicy {
-    pub(crate) fn new() -> Self {
-        SimplePolicy {}
+    /// policy which prefers spus leading less partitions
+    pub(crate) fn with_load(groups: &ReplicaSchedulingGroups) -> Self {
+        let leaders = groups
+            .iter()
+            .map(|(spu, group)| (*spu, group.leader_weight()))
+            .collect();
+        SimplePolicy { leaders }
+    }
+
+    /// account for partition whose leadership moved to spu
+    pub(crate) fn add_leader(&mut self, spu: SpuId) {
+        let leaders = self.leaders.entry(spu).or_default();
+        *leaders = leaders.saturating_add(1);
     }
 }
 
@@ -44,4 +77,99 @@ impl ElectionPolicy for SimplePolicy {
             ElectionScoring::NotSuitable
         }
     }
+
+    fn leader_load(&self, spu: SpuId) -> u16 {
+        self.leaders.get(&spu).copied().unwrap_or_default()
+    }
+}
+
+/// Only replicas with all records committed by leader can take over leadership
+/// of partition led by draining spu
+pub(crate) struct DrainPolicy<'a>(pub(crate) &'a SimplePolicy);
+
+impl ElectionPolicy for DrainPolicy<'_> {
+    fn potential_leader_score(
+        &self,
+        replica_status: &ReplicaStatus,
+        leader: &ReplicaStatus,
+    ) -> ElectionScoring {
+        if leader.hw >= 0 && replica_status.leo >= leader.hw {
+            ElectionScoring::Score(0)
+        } else {
+            ElectionScoring::NotSuitable
+        }
+    }
+
+    fn leader_load(&self, spu: SpuId) -> u16 {
+        self.0.leader_load(spu)
+    }
+}
+
+/// Prefers suitable replicas matching leader affinity of partition
+pub(crate) struct AffinityPolicy<'a, P> {
+    policy: &'a P,
+    affinity: Option<&'a LeaderAffinity>,
+    /// spus leading partitions of anti-affinity topics
+    avoided: Vec<SpuId>,
+}
+
+impl<'a, P> AffinityPolicy<'a, P> {
+    pub(crate) fn new<'b, C>(
+        policy: &'a P,
+        affinity: Option<&'a LeaderAffinity>,
+        partitions: impl Iterator<Item = &'b PartitionMetadata<C>>,
+    ) -> Self
+    where
+        C: MetadataItem + 'b,
+    {
+        let avoided = affinity
+            .map(|affinity| avoided_leaders(affinity, partitions))
+            .unwrap_or_default();
+        Self {
+            policy,
+            affinity,
+            avoided,
+        }
+    }
+}
+
+impl<P: ElectionPolicy> ElectionPolicy for AffinityPolicy<'_, P> {
+    fn potential_leader_score(
+        &self,
+        replica_status: &ReplicaStatus,
+        leader: &ReplicaStatus,
+    ) -> ElectionScoring {
+        self.policy.potential_leader_score(replica_status, leader)
+    }
+
+    fn leader_load(&self, spu: SpuId) -> u16 {
+        self.policy.leader_load(spu)
+    }
+
+    fn affinity_rank(&self, spu: SpuId) -> u16 {
+        self.affinity
+            .map(|affinity| affinity.rank(spu, &self.avoided))
+            .unwrap_or_default()
+    }
+}
+
+/// leaders of partitions of anti-affinity topics
+pub(crate) fn avoided_leaders<'a, C>(
+    affinity: &LeaderAffinity,
+    partitions: impl Iterator<Item = &'a PartitionMetadata<C>>,
+) -> Vec<SpuId>
+where
+    C: MetadataItem + 'a,
+{
+    let mut leaders: Vec<SpuId> = partitions
+        .filter(|partition| {
+            affinity
+                .anti_affinity_topics
+                .contains(&partition.key().topic)
+        })
+        .map(|partition| partition.spec.leader)
+        .collect();
+    leaders.sort_unstable();
+    leaders.dedup();
+    leaders
 }
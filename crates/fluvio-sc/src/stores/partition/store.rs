@@ -15,6 +15,7 @@ use async_trait::async_trait;
 
 use fluvio_controlplane::replica::Replica;
 use fluvio_controlplane::replica::ReplicaLeader;
+use fluvio_controlplane_metadata::topic::LeaderAffinity;
 use fluvio_stream_model::core::MetadataItem;
 
 use fluvio_stream_model::store::LocalStore;
@@ -25,6 +26,7 @@ use tracing::trace;
 use super::*;
 use super::policy::ElectionPolicy;
 use super::policy::ElectionScoring;
+use super::policy::avoided_leaders;
 
 pub type SharedPartitionStore<C> = Arc<PartitionLocalStore<C>>;
 
@@ -82,6 +84,9 @@ where
 
     /// group replicas by spu
     async fn group_by_spu(&self) -> ReplicaSchedulingGroups;
+
+    /// spus leading partitions of anti-affinity topics
+    async fn avoided_leaders(&self, affinity: &LeaderAffinity) -> Vec<SpuId>;
 }
 
 /// List of Replica groups for scheduling
@@ -107,8 +112,8 @@ impl ReplicaSchedulingGroups {
     /// this is done by scanning all spu and find the one with least weight
     pub(crate) fn find_suitable_spu(
         &self,
-        spu_list: &Vec<SpuId>,
-        anti_affinity: &Vec<SpuId>,
+        spu_list: &[SpuId],
+        anti_affinity: &[SpuId],
         weight: SpuWeightSelection,
     ) -> Option<SpuId> {
         trace!(?spu_list, ?anti_affinity, "find_suitable_spu");
@@ -340,6 +345,16 @@ where
 
         groups
     }
+
+    async fn avoided_leaders(&self, affinity: &LeaderAffinity) -> Vec<SpuId> {
+        avoided_leaders(
+            affinity,
+            self.read()
+                .await
+                .values()
+                .map(|partition| partition.inner()),
+        )
+    }
 }
 
 /// find status matching it,
@@ -363,7 +378,7 @@ impl PartitonStatusExtension for PartitionStatus {
         P: ElectionPolicy,
     {
         let mut candidate_spu = None;
-        let mut best_score = 0;
+        let mut best_score = (0, 0, 0);
 
         for candidate in &self.replicas {
             // only do for live replicas
@@ -371,6 +386,13 @@ impl PartitonStatusExtension for PartitionStatus {
                 if let ElectionScoring::Score(score) =
                     policy.potential_leader_score(candidate, &self.leader)
                 {
+                    // replica matching leader affinity wins, then most caught up,
+                    // ties go to least loaded spu
+                    let score = (
+                        policy.affinity_rank(candidate.spu),
+                        score,
+                        policy.leader_load(candidate.spu),
+                    );
                     if candidate_spu.is_some() {
                         if score < best_score {
                             best_score = score;
@@ -391,6 +413,9 @@ impl PartitonStatusExtension for PartitionStatus {
     fn merge(&mut self, other: Self) {
         self.resolution = other.resolution;
         self.size = other.size;
+        self.usage = other.usage;
+        // leader always reports all ranges it knows about
+        self.corrupt_ranges = other.corrupt_ranges;
         if let Some(old) = self.leader.merge(&other.leader) {
             self.replicas.push(old); // move old leader to replicas
         }
@@ -472,8 +497,12 @@ mod test {
 
     use std::collections::HashSet;
 
+    use fluvio_types::SpuId;
+
     use crate::stores::partition::PartitonStatusExtension;
 
+    use fluvio_controlplane_metadata::partition::{CorruptRange, ReplicaDigests, SegmentDigest};
+
     use super::PartitionStatus;
     use super::ReplicaStatus;
     use super::ElectionPolicy;
@@ -543,6 +572,50 @@ mod test {
         assert_eq!(status.candidate_leader(&online_spu, &policy), Some(5001)); // 5001 has least lag
     }
 
+    /// replicas with same lag are chosen by load of their spu
+    #[test]
+    fn test_candidate_spu_least_loaded() {
+        struct LoadPolicy {}
+
+        impl ElectionPolicy for LoadPolicy {
+            fn potential_leader_score(
+                &self,
+                replica_status: &ReplicaStatus,
+                leader: &ReplicaStatus,
+            ) -> ElectionScoring {
+                SimplePolicy {}.potential_leader_score(replica_status, leader)
+            }
+
+            fn leader_load(&self, spu: SpuId) -> u16 {
+                if spu == 5001 {
+                    3
+                } else {
+                    1
+                }
+            }
+        }
+
+        let status = PartitionStatus::new(
+            (5000, 100, 110),
+            vec![
+                (5001, 100, 110).into(), // caught up but leading more partitions
+                (5002, 100, 110).into(), // caught up (best)
+                (5003, 100, 108).into(), // least loaded but behind
+            ],
+        );
+        let online_spu = HashSet::from([5001, 5002, 5003]);
+
+        assert_eq!(
+            status.candidate_leader(&online_spu, &LoadPolicy {}),
+            Some(5002)
+        );
+        // without load, first caught up replica is chosen
+        assert_eq!(
+            status.candidate_leader(&online_spu, &SimplePolicy {}),
+            Some(5001)
+        );
+    }
+
     /// check when we don't have any online
     #[test]
     fn test_candidate_spu_no_online() {
@@ -620,6 +693,62 @@ mod test {
         assert_eq!(target.replicas.len(), 1);
         assert_eq!(target.replicas[0], (5001, 0, 0).into());
     }
+
+    #[test]
+    fn test_merge_corrupt_ranges() {
+        let mut target = PartitionStatus::leader((5000, 10, 10));
+
+        let mut source = PartitionStatus::leader((5000, 10, 10));
+        source.corrupt_ranges = vec![CorruptRange { start: 2, end: 5 }];
+        target.merge(source);
+        assert!(target.is_corrupt());
+
+        // repaired, leader no longer reports range
+        target.merge(PartitionStatus::leader((5000, 10, 10)));
+        assert!(!target.is_corrupt());
+    }
+
+    #[test]
+    fn test_update_digests_detects_divergence() {
+        let segment = |base_offset, end_offset, digest| SegmentDigest {
+            base_offset,
+            end_offset,
+            digest,
+        };
+        let replicas = vec![5000, 5001, 5002];
+        let mut status = PartitionStatus::leader((5000, 10, 10));
+
+        // followers are compared only once leader reports
+        status.update_digests(
+            ReplicaDigests::new(5001, vec![segment(0, 5, 1), segment(5, 10, 3)]),
+            &replicas,
+        );
+        assert!(!status.is_divergent());
+
+        status.update_digests(
+            ReplicaDigests::new(5000, vec![segment(0, 5, 1), segment(5, 10, 2)]),
+            &replicas,
+        );
+        assert_eq!(status.divergent_replicas, vec![5001]);
+
+        // segment not rolled on follower yet is not compared
+        status.update_digests(
+            ReplicaDigests::new(5002, vec![segment(0, 5, 1), segment(5, 8, 9)]),
+            &replicas,
+        );
+        assert_eq!(status.divergent_replicas, vec![5001]);
+
+        // follower repaired
+        status.update_digests(
+            ReplicaDigests::new(5001, vec![segment(0, 5, 1), segment(5, 10, 2)]),
+            &replicas,
+        );
+        assert!(!status.is_divergent());
+
+        // digests of removed replica are dropped
+        status.update_digests(ReplicaDigests::new(5000, vec![]), &[5000, 5001]);
+        assert_eq!(status.digests.len(), 2);
+    }
 }
 
 #[cfg(test)]
@@ -658,21 +787,21 @@ mod test2 {
     fn test_spu_scheduling_simple() {
         let group = ReplicaSchedulingGroups::default();
         assert_eq!(
-            group.find_suitable_spu(&vec![0, 1, 2], &vec![], SpuWeightSelection::Leader),
+            group.find_suitable_spu(&[0, 1, 2], &[], SpuWeightSelection::Leader),
             Some(0)
         );
 
         assert_eq!(
-            group.find_suitable_spu(&vec![0, 1, 2], &vec![0], SpuWeightSelection::Leader),
+            group.find_suitable_spu(&[0, 1, 2], &[0], SpuWeightSelection::Leader),
             Some(1)
         ); // anti-affinity
         assert_eq!(
-            group.find_suitable_spu(&vec![0, 1, 2], &vec![0, 1], SpuWeightSelection::Leader),
+            group.find_suitable_spu(&[0, 1, 2], &[0, 1], SpuWeightSelection::Leader),
             Some(2)
         ); // anti-affinity
 
         assert_eq!(
-            group.find_suitable_spu(&vec![1, 2], &vec![], SpuWeightSelection::Follower),
+            group.find_suitable_spu(&[1, 2], &[], SpuWeightSelection::Follower),
             Some(1)
         );
 
@@ -702,20 +831,20 @@ mod test2 {
         // 6 -> leaders: 0, followers: 1
 
         assert_eq!(
-            groups.find_suitable_spu(&vec![0, 1, 2], &vec![], SpuWeightSelection::Leader),
+            groups.find_suitable_spu(&[0, 1, 2], &[], SpuWeightSelection::Leader),
             Some(1)
         );
         assert_eq!(
-            groups.find_suitable_spu(&vec![0, 1, 2], &vec![2], SpuWeightSelection::Leader),
+            groups.find_suitable_spu(&[0, 1, 2], &[2], SpuWeightSelection::Leader),
             Some(1)
         ); // anti-affinity
         assert_eq!(
-            groups.find_suitable_spu(&vec![1, 2, 3, 6], &vec![], SpuWeightSelection::Leader),
+            groups.find_suitable_spu(&[1, 2, 3, 6], &[], SpuWeightSelection::Leader),
             Some(3)
         ); // anti-affinity
 
         assert_eq!(
-            groups.find_suitable_spu(&vec![2, 3, 6], &vec![], SpuWeightSelection::Follower),
+            groups.find_suitable_spu(&[2, 3, 6], &[], SpuWeightSelection::Follower),
             Some(2)
         ); // anti-affinity
     }
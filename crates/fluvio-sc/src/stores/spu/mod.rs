@@ -37,6 +37,7 @@ mod health_check {
         SpuId,
         event::offsets::{OffsetChangeListener, OffsetPublisher},
     };
+    use fluvio_controlplane::sc_api::update_lrs::SpuStat;
 
     pub type SharedHealthCheck = Arc<HealthCheck>;
 
@@ -45,6 +46,10 @@ mod health_check {
     pub struct HealthCheck {
         health: RwLock<HashMap<SpuId, bool>>,
         event: Arc<OffsetPublisher>,
+        /// last stat reported by SPU
+        stats: RwLock<HashMap<SpuId, SpuStat>>,
+        /// version reported by SPU at registration
+        versions: RwLock<HashMap<SpuId, String>>,
     }
 
     impl Deref for HealthCheck {
@@ -64,6 +69,8 @@ mod health_check {
             Self {
                 health: RwLock::new(HashMap::new()),
                 event: OffsetPublisher::shared(0),
+                stats: RwLock::new(HashMap::new()),
+                versions: RwLock::new(HashMap::new()),
             }
         }
 
@@ -71,6 +78,28 @@ mod health_check {
             self.event.change_listener()
         }
 
+        pub async fn update_stat(&self, spu: SpuId, stat: SpuStat) {
+            self.stats.write().await.insert(spu, stat);
+        }
+
+        /// last stat reported by each SPU
+        pub async fn stats(&self) -> HashMap<SpuId, SpuStat> {
+            self.stats.read().await.clone()
+        }
+
+        pub async fn update_version(&self, spu: SpuId, version: Option<&str>) {
+            let mut versions = self.versions.write().await;
+            match version {
+                Some(version) => versions.insert(spu, version.to_owned()),
+                None => versions.remove(&spu),
+            };
+        }
+
+        /// version reported by each SPU at registration
+        pub async fn versions(&self) -> HashMap<SpuId, String> {
+            self.versions.read().await.clone()
+        }
+
         /// update health check
         // TODO: Determine if we can follow the clippy suggestion w/o negatively affecting functionality
         #[allow(clippy::branches_sharing_code)]
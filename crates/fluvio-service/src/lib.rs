@@ -4,6 +4,11 @@ mod server;
 #[cfg(test)]
 pub mod test_request;
 
+pub mod logging;
+pub mod profiling;
+pub mod rate_limit;
+pub mod telemetry;
+
 pub use self::server::*;
 pub use fluvio_protocol::codec::FluvioCodec;
 
@@ -27,6 +32,28 @@ macro_rules! call_service {
         }
     }};
 
+    // request over rate limit of connection is rejected or delayed
+    ($req:expr,$handler:expr,$sink:expr,$msg:expr,$rate_limit:expr) => {{
+        match $rate_limit.check() {
+            Ok(()) => $crate::call_service!($req, $handler, $sink, $msg),
+            Err(error) => match $crate::rate_limit::throttled_response(&$req, error) {
+                Some(response) => {
+                    tracing::debug!(api = $msg, "request throttled");
+                    if let Err(err) = $sink
+                        .send_response(&response, $req.header.api_version())
+                        .await
+                    {
+                        tracing::warn!("sending throttled response failed: {}", err);
+                    }
+                }
+                None => {
+                    $rate_limit.acquire().await;
+                    $crate::call_service!($req, $handler, $sink, $msg)
+                }
+            },
+        }
+    }};
+
     ($handler:expr,$sink:expr) => {{
         call_service!($handler, $sink, "")
     }};
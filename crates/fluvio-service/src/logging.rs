@@ -0,0 +1,213 @@
+//!
+//! # Log Format
+//!
+//! SC and SPU log as text by default. With `FLV_LOG_FORMAT=json`, each event is written as a
+//! single JSON line with stable keys, so logs can be ingested by Loki or Elastic as is:
+//!
+//! - `ts`: RFC 3339 timestamp
+//! - `level`, `target`, `message`
+//! - `component`: `fluvio-sc` or `fluvio-spu`
+//! - `span`: name of innermost span
+//!
+//! Fields of the event and its spans are flattened next to them, so fields such as
+//! `replica`, `remote` and `error_code` keep their names across components.
+//!
+use std::fmt;
+use std::str::FromStr;
+use std::time::SystemTime;
+
+use serde_json::{Map, Value};
+use tracing::field::{Field, Visit};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::fmt::format::{FormatEvent, FormatFields, Writer};
+use tracing_subscriber::fmt::{FmtContext, FormattedFields};
+use tracing_subscriber::registry::LookupSpan;
+
+pub use fluvio_types::defaults::FLV_LOG_FORMAT;
+
+pub const TIMESTAMP_KEY: &str = "ts";
+pub const LEVEL_KEY: &str = "level";
+pub const TARGET_KEY: &str = "target";
+pub const MESSAGE_KEY: &str = "message";
+pub const COMPONENT_KEY: &str = "component";
+pub const SPAN_KEY: &str = "span";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LogFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+impl LogFormat {
+    /// format set by `FLV_LOG_FORMAT`, text if not set or unknown
+    pub fn from_env() -> Self {
+        std::env::var(FLV_LOG_FORMAT)
+            .ok()
+            .and_then(|format| format.parse().ok())
+            .unwrap_or_default()
+    }
+}
+
+impl FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            other => Err(format!(
+                "unknown log format: {other}, expected text or json"
+            )),
+        }
+    }
+}
+
+/// Formats events as JSON lines, use with [`tracing_subscriber::fmt::format::JsonFields`]
+/// so span fields can be flattened
+#[derive(Debug, Clone)]
+pub struct JsonFormat {
+    component: String,
+}
+
+impl JsonFormat {
+    pub fn new(component: impl Into<String>) -> Self {
+        Self {
+            component: component.into(),
+        }
+    }
+}
+
+impl<S, N> FormatEvent<S, N> for JsonFormat
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+{
+    fn format_event(
+        &self,
+        ctx: &FmtContext<'_, S, N>,
+        mut writer: Writer<'_>,
+        event: &Event<'_>,
+    ) -> fmt::Result {
+        let mut line = Map::new();
+
+        if let Some(scope) = ctx.event_scope() {
+            let mut innermost = None;
+            for span in scope.from_root() {
+                if let Some(fields) = span.extensions().get::<FormattedFields<N>>() {
+                    if let Ok(Value::Object(fields)) = serde_json::from_str(fields.as_str()) {
+                        line.extend(fields);
+                    }
+                }
+                innermost = Some(span.name());
+            }
+            if let Some(name) = innermost {
+                line.insert(SPAN_KEY.to_owned(), name.into());
+            }
+        }
+
+        event.record(&mut JsonVisitor(&mut line));
+
+        let meta = event.metadata();
+        line.insert(
+            TIMESTAMP_KEY.to_owned(),
+            humantime::format_rfc3339_micros(SystemTime::now())
+                .to_string()
+                .into(),
+        );
+        line.insert(LEVEL_KEY.to_owned(), meta.level().as_str().into());
+        line.insert(TARGET_KEY.to_owned(), meta.target().into());
+        line.insert(COMPONENT_KEY.to_owned(), self.component.clone().into());
+
+        let line = serde_json::to_string(&line).map_err(|_| fmt::Error)?;
+        writeln!(writer, "{line}")
+    }
+}
+
+struct JsonVisitor<'a>(&'a mut Map<String, Value>);
+
+impl Visit for JsonVisitor<'_> {
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.insert(field.name().to_owned(), value.into());
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.insert(field.name().to_owned(), value.into());
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.0.insert(field.name().to_owned(), value.into());
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.0.insert(field.name().to_owned(), value.into());
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_owned(), value.into());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0
+            .insert(field.name().to_owned(), format!("{value:?}").into());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+    use std::sync::{Arc, Mutex};
+
+    use tracing::{info, info_span};
+    use tracing_subscriber::fmt::format::JsonFields;
+
+    use super::*;
+
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_log_format() {
+        assert_eq!("json".parse::<LogFormat>(), Ok(LogFormat::Json));
+        assert_eq!("Text".parse::<LogFormat>(), Ok(LogFormat::Text));
+        assert!("yaml".parse::<LogFormat>().is_err());
+    }
+
+    #[test]
+    fn test_json_format() {
+        let buffer = Buffer::default();
+        let writer = buffer.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .event_format(JsonFormat::new("fluvio-spu"))
+            .fmt_fields(JsonFields::new())
+            .with_writer(move || writer.clone())
+            .finish();
+
+        tracing::subscriber::with_default(subscriber, || {
+            let span = info_span!("mirror_sync", replica = "topic-0");
+            let _guard = span.enter();
+            info!(remote = "edge1", offset = 10, "synced");
+        });
+
+        let output = buffer.0.lock().unwrap().clone();
+        let line: Value = serde_json::from_slice(&output).expect("json line");
+        assert_eq!(line[COMPONENT_KEY], "fluvio-spu");
+        assert_eq!(line[LEVEL_KEY], "INFO");
+        assert_eq!(line[MESSAGE_KEY], "synced");
+        assert_eq!(line[SPAN_KEY], "mirror_sync");
+        assert_eq!(line["replica"], "topic-0");
+        assert_eq!(line["remote"], "edge1");
+        assert_eq!(line["offset"], 10);
+        assert!(line[TIMESTAMP_KEY].is_string());
+    }
+}
@@ -0,0 +1,221 @@
+//!
+//! # Profiling Endpoint
+//!
+//! Opt-in HTTP endpoint capturing profiles of running SC or SPU in pprof format:
+//!
+//! * `GET /debug/pprof/profile?seconds=30` samples CPU for given seconds
+//! * `GET /debug/pprof/heap` dumps heap profile, only in jemalloc builds on glibc Linux
+//!
+//! Profiles can be viewed with `go tool pprof` or any pprof compatible viewer.
+//! Endpoint is only compiled with `profiling` feature. It's not authenticated,
+//! so it should be bound to loopback or other private address.
+//!
+
+/// start profiling endpoint on address, if build includes profiling
+pub fn start_profiling(addr: &str) {
+    #[cfg(feature = "profiling")]
+    {
+        fluvio_future::task::spawn(endpoint::serve(addr.to_owned()));
+    }
+
+    #[cfg(not(feature = "profiling"))]
+    tracing::warn!(
+        addr,
+        "profiling requested, but build doesn't include profiling feature"
+    );
+}
+
+#[cfg(feature = "profiling")]
+mod endpoint {
+    use std::time::Duration;
+
+    use anyhow::{anyhow, Result};
+    use futures_util::{AsyncReadExt, AsyncWriteExt, StreamExt};
+    use tracing::{debug, error, info};
+
+    use fluvio_future::net::{TcpListener, TcpStream};
+    use fluvio_future::task::spawn;
+    use fluvio_future::timer::sleep;
+
+    const MAX_REQUEST_BYTES: usize = 4096;
+
+    /// CPU profile duration if not specified
+    const DEFAULT_PROFILE_SECS: u64 = 30;
+
+    /// longest CPU profile which can be requested
+    const MAX_PROFILE_SECS: u64 = 300;
+
+    /// CPU samples per second, prime so sampling doesn't align with periodic work
+    const PROFILE_FREQUENCY: i32 = 99;
+
+    enum Response {
+        Profile(Vec<u8>),
+        BadRequest(String),
+        NotFound,
+        Failed(String),
+    }
+
+    pub(super) async fn serve(addr: String) {
+        let listener = match TcpListener::bind(&addr).await {
+            Ok(listener) => listener,
+            Err(err) => {
+                error!(%addr, %err, "unable to start profiling endpoint");
+                return;
+            }
+        };
+        info!(%addr, "profiling endpoint started");
+
+        let mut incoming = listener.incoming();
+        while let Some(stream) = incoming.next().await {
+            match stream {
+                Ok(stream) => {
+                    spawn(async move {
+                        if let Err(err) = handle_connection(stream).await {
+                            debug!(%err, "profiling connection terminated");
+                        }
+                    });
+                }
+                Err(err) => error!(%err, "error accepting profiling connection"),
+            }
+        }
+    }
+
+    async fn handle_connection(mut stream: TcpStream) -> Result<()> {
+        let mut buf = [0u8; MAX_REQUEST_BYTES];
+        let read = stream.read(&mut buf).await?;
+        let request = String::from_utf8_lossy(&buf[..read]);
+        let response = match request_target(&request) {
+            Some(target) => respond(target).await,
+            None => Response::BadRequest("only GET is supported".to_owned()),
+        };
+        write_response(stream, response).await
+    }
+
+    /// target of GET request, including query
+    fn request_target(request: &str) -> Option<&str> {
+        let mut parts = request.lines().next()?.split_whitespace();
+        if parts.next()? != "GET" {
+            return None;
+        }
+        parts.next()
+    }
+
+    async fn respond(target: &str) -> Response {
+        let (path, query) = target.split_once('?').unwrap_or((target, ""));
+        match path {
+            "/debug/pprof/profile" => match profile_seconds(query) {
+                Ok(seconds) => cpu_profile(seconds).await.into(),
+                Err(err) => Response::BadRequest(err.to_string()),
+            },
+            "/debug/pprof/heap" => heap_profile().await.into(),
+            _ => Response::NotFound,
+        }
+    }
+
+    fn profile_seconds(query: &str) -> Result<u64> {
+        let seconds = query
+            .split('&')
+            .find_map(|param| param.strip_prefix("seconds="))
+            .map(|seconds| {
+                seconds
+                    .parse::<u64>()
+                    .map_err(|_| anyhow!("invalid seconds: {seconds}"))
+            })
+            .transpose()?
+            .unwrap_or(DEFAULT_PROFILE_SECS);
+        if seconds == 0 || seconds > MAX_PROFILE_SECS {
+            return Err(anyhow!("seconds must be between 1 and {MAX_PROFILE_SECS}"));
+        }
+        Ok(seconds)
+    }
+
+    async fn cpu_profile(seconds: u64) -> Result<Vec<u8>> {
+        use pprof::protos::Message;
+
+        info!(seconds, "capturing cpu profile");
+        let guard = pprof::ProfilerGuardBuilder::default()
+            .frequency(PROFILE_FREQUENCY)
+            .blocklist(&["libc", "libgcc", "pthread", "vdso"])
+            .build()
+            .map_err(|err| anyhow!("unable to start cpu profiler: {err}"))?;
+        sleep(Duration::from_secs(seconds)).await;
+
+        let profile = guard.report().build()?.pprof()?;
+        let mut bytes = Vec::new();
+        profile.encode(&mut bytes)?;
+        Ok(bytes)
+    }
+
+    #[cfg(all(feature = "jemalloc", target_os = "linux", target_env = "gnu"))]
+    async fn heap_profile() -> Result<Vec<u8>> {
+        let prof_ctl = jemalloc_pprof::PROF_CTL
+            .as_ref()
+            .ok_or_else(|| anyhow!("jemalloc profiling is not available"))?;
+        let mut prof_ctl = prof_ctl.lock().await;
+        if !prof_ctl.activated() {
+            return Err(anyhow!("jemalloc profiling is not activated"));
+        }
+        info!("dumping heap profile");
+        prof_ctl.dump_pprof()
+    }
+
+    #[cfg(not(all(feature = "jemalloc", target_os = "linux", target_env = "gnu")))]
+    async fn heap_profile() -> Result<Vec<u8>> {
+        Err(anyhow!(
+            "heap profiling requires build with jemalloc feature on glibc Linux"
+        ))
+    }
+
+    impl From<Result<Vec<u8>>> for Response {
+        fn from(result: Result<Vec<u8>>) -> Self {
+            match result {
+                Ok(profile) => Self::Profile(profile),
+                Err(err) => Self::Failed(err.to_string()),
+            }
+        }
+    }
+
+    async fn write_response(mut stream: TcpStream, response: Response) -> Result<()> {
+        let (status, content_type, body) = match response {
+            Response::Profile(profile) => ("200 OK", "application/octet-stream", profile),
+            Response::BadRequest(msg) => ("400 Bad Request", "text/plain", msg.into_bytes()),
+            Response::NotFound => ("404 Not Found", "text/plain", b"not found".to_vec()),
+            Response::Failed(msg) => {
+                error!(%msg, "profiling failed");
+                ("500 Internal Server Error", "text/plain", msg.into_bytes())
+            }
+        };
+        let header = format!(
+            "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+            body.len()
+        );
+        stream.write_all(header.as_bytes()).await?;
+        stream.write_all(&body).await?;
+        stream.close().await?;
+        Ok(())
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_profile_seconds() {
+            assert_eq!(profile_seconds("").unwrap(), DEFAULT_PROFILE_SECS);
+            assert_eq!(profile_seconds("debug=1&seconds=5").unwrap(), 5);
+            assert!(profile_seconds("seconds=0").is_err());
+            assert!(profile_seconds("seconds=abc").is_err());
+            assert!(profile_seconds(&format!("seconds={}", MAX_PROFILE_SECS + 1)).is_err());
+        }
+
+        #[test]
+        fn test_request_target() {
+            let request = "GET /debug/pprof/profile?seconds=5 HTTP/1.1\r\nHost: localhost\r\n\r\n";
+            assert_eq!(
+                request_target(request),
+                Some("/debug/pprof/profile?seconds=5")
+            );
+            assert_eq!(request_target("POST /debug/pprof/heap HTTP/1.1\r\n"), None);
+        }
+    }
+}
@@ -0,0 +1,245 @@
+//!
+//! # Request Rate Limiting
+//!
+//! Limits requests per second of each connection and of all connections of same principal,
+//! so clients stuck in retry loop can't overload public servers. Budgets are token buckets
+//! which allow burst of one second worth of requests.
+//!
+//! Request over budget is rejected with `RequestThrottled` error carrying time until budget
+//! is available, if its response implements [`ThrottledResponse`] with error. Otherwise it's
+//! delayed until it fits in budget.
+//!
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use tracing::debug;
+
+use fluvio_future::timer::sleep;
+use fluvio_protocol::api::{Request, RequestMessage, ResponseMessage};
+use fluvio_protocol::link::throttle::ThrottledResponse;
+use fluvio_protocol::link::ErrorCode;
+
+/// principal of connections which are not authenticated
+const ANONYMOUS_PRINCIPAL: &str = "anonymous";
+
+/// buckets of principals idle for longer than this are dropped
+const IDLE_PRINCIPAL_TIMEOUT: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RateLimitConfig {
+    /// requests per second of single connection, None is unlimited
+    pub connection_requests_per_sec: Option<u32>,
+    /// requests per second of all connections of principal, None is unlimited
+    pub principal_requests_per_sec: Option<u32>,
+}
+
+impl RateLimitConfig {
+    pub fn is_unlimited(&self) -> bool {
+        self.connection_requests_per_sec.is_none() && self.principal_requests_per_sec.is_none()
+    }
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    rate: f64,
+    tokens: f64,
+    updated: Instant,
+}
+
+impl TokenBucket {
+    fn new(requests_per_sec: u32, now: Instant) -> Self {
+        let rate = requests_per_sec.max(1) as f64;
+        Self {
+            rate,
+            tokens: rate,
+            updated: now,
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.rate);
+        self.updated = now;
+    }
+
+    /// time until a token is available
+    fn wait_time(&self) -> Duration {
+        if self.tokens >= 1.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64((1.0 - self.tokens) / self.rate)
+        }
+    }
+}
+
+pub type SharedRateLimiter = Arc<RateLimiter>;
+
+/// Budgets of principals shared by connections of server
+#[derive(Debug)]
+pub struct RateLimiter {
+    config: RateLimitConfig,
+    principals: Mutex<HashMap<String, TokenBucket>>,
+}
+
+impl RateLimiter {
+    pub fn shared(config: RateLimitConfig) -> SharedRateLimiter {
+        Arc::new(Self {
+            config,
+            principals: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// budget of new connection, unauthenticated connections share anonymous principal budget
+    pub fn connection(self: &Arc<Self>, principal: Option<&str>) -> ConnectionRateLimit {
+        ConnectionRateLimit {
+            limiter: self.clone(),
+            principal: principal.unwrap_or(ANONYMOUS_PRINCIPAL).to_owned(),
+            bucket: self
+                .config
+                .connection_requests_per_sec
+                .map(|rate| TokenBucket::new(rate, Instant::now())),
+        }
+    }
+}
+
+/// Budget of single connection
+#[derive(Debug)]
+pub struct ConnectionRateLimit {
+    limiter: SharedRateLimiter,
+    principal: String,
+    bucket: Option<TokenBucket>,
+}
+
+impl ConnectionRateLimit {
+    /// take budget of one request, or fail with `RequestThrottled` if connection or
+    /// principal is over limit. Rejected requests don't use budget
+    pub fn check(&mut self) -> Result<(), ErrorCode> {
+        if self.limiter.config.is_unlimited() {
+            return Ok(());
+        }
+        let now = Instant::now();
+        let mut wait = Duration::ZERO;
+
+        if let Some(bucket) = &mut self.bucket {
+            bucket.refill(now);
+            wait = bucket.wait_time();
+        }
+
+        let mut principals = self
+            .limiter
+            .principals
+            .lock()
+            .unwrap_or_else(|err| err.into_inner());
+        let mut principal_bucket = None;
+        if let Some(rate) = self.limiter.config.principal_requests_per_sec {
+            if !principals.contains_key(&self.principal) {
+                principals.retain(|_, bucket| {
+                    now.saturating_duration_since(bucket.updated) < IDLE_PRINCIPAL_TIMEOUT
+                });
+            }
+            let bucket = principals
+                .entry(self.principal.clone())
+                .or_insert_with(|| TokenBucket::new(rate, now));
+            bucket.refill(now);
+            wait = wait.max(bucket.wait_time());
+            principal_bucket = Some(bucket);
+        }
+
+        if !wait.is_zero() {
+            debug!(principal = %self.principal, ?wait, "request over rate limit");
+            return Err(ErrorCode::RequestThrottled {
+                retry_after_ms: wait.as_millis().max(1) as u64,
+            });
+        }
+
+        if let Some(bucket) = principal_bucket {
+            bucket.tokens -= 1.0;
+        }
+        if let Some(bucket) = &mut self.bucket {
+            bucket.tokens -= 1.0;
+        }
+        Ok(())
+    }
+
+    /// wait until request fits in budget, then take it
+    pub async fn acquire(&mut self) {
+        while let Err(ErrorCode::RequestThrottled { retry_after_ms }) = self.check() {
+            sleep(Duration::from_millis(retry_after_ms)).await;
+        }
+    }
+}
+
+/// response rejecting request over rate limit, none if response can't carry error
+pub fn throttled_response<R>(
+    request: &RequestMessage<R>,
+    error: ErrorCode,
+) -> Option<ResponseMessage<R::Response>>
+where
+    R: Request,
+    R::Response: ThrottledResponse,
+{
+    R::Response::throttled(error)
+        .map(|response| ResponseMessage::from_header(&request.header, response))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn retry_after(result: Result<(), ErrorCode>) -> u64 {
+        match result {
+            Err(ErrorCode::RequestThrottled { retry_after_ms }) => retry_after_ms,
+            other => panic!("expected throttled, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_connection_limit() {
+        let limiter = RateLimiter::shared(RateLimitConfig {
+            connection_requests_per_sec: Some(2),
+            ..Default::default()
+        });
+        let mut first = limiter.connection(Some("alice"));
+        assert!(first.check().is_ok());
+        assert!(first.check().is_ok());
+        let retry_after_ms = retry_after(first.check());
+        assert!(retry_after_ms > 0 && retry_after_ms <= 500);
+
+        // other connection has own budget
+        let mut second = limiter.connection(Some("alice"));
+        assert!(second.check().is_ok());
+    }
+
+    #[test]
+    fn test_principal_limit() {
+        let limiter = RateLimiter::shared(RateLimitConfig {
+            principal_requests_per_sec: Some(2),
+            ..Default::default()
+        });
+        let mut first = limiter.connection(Some("alice"));
+        let mut second = limiter.connection(Some("alice"));
+        let mut other = limiter.connection(None);
+        assert!(first.check().is_ok());
+        assert!(second.check().is_ok());
+        retry_after(first.check());
+        retry_after(second.check());
+        assert!(other.check().is_ok());
+    }
+
+    #[fluvio_future::test]
+    async fn test_acquire_waits() {
+        let limiter = RateLimiter::shared(RateLimitConfig {
+            connection_requests_per_sec: Some(20),
+            ..Default::default()
+        });
+        let mut connection = limiter.connection(None);
+        for _ in 0..20 {
+            assert!(connection.check().is_ok());
+        }
+
+        let start = Instant::now();
+        connection.acquire().await;
+        assert!(start.elapsed() >= Duration::from_millis(40));
+    }
+}
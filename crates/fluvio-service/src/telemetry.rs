@@ -0,0 +1,268 @@
+//!
+//! # Trace Export
+//!
+//! With the `telemetry` feature, `tracing` spans of SC and SPU are exported over OTLP to a
+//! collector such as Jaeger or Tempo. Export is configured by the standard OpenTelemetry
+//! environment variables:
+//!
+//! - `OTEL_EXPORTER_OTLP_ENDPOINT`: collector to export to, e.g. `http://localhost:4318`.
+//!   Spans are only logged if not set
+//! - `OTEL_SERVICE_NAME`: overrides the service name of the component
+//! - `OTEL_TRACES_SAMPLER_ARG`: ratio of traces sampled, from 0.0 to 1.0, all by default
+//!
+//! Spans are exported in batches by a background task, remaining ones are flushed when
+//! [`TelemetryGuard`] is dropped.
+//!
+//! The trace context of a span is propagated to other components as W3C `traceparent`,
+//! so requests between SC and SPU are traced end to end.
+//!
+//! Filter of logged events starts from `RUST_LOG` and can be changed at runtime by
+//! [`set_log_filter`].
+//!
+use std::sync::OnceLock;
+
+use tracing::{Span, Subscriber};
+use tracing_subscriber::fmt::format::JsonFields;
+use tracing_subscriber::prelude::*;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::{reload, EnvFilter, Layer, Registry};
+
+use crate::logging::{JsonFormat, LogFormat};
+
+pub const OTEL_EXPORTER_OTLP_ENDPOINT: &str = "OTEL_EXPORTER_OTLP_ENDPOINT";
+pub const OTEL_SERVICE_NAME: &str = "OTEL_SERVICE_NAME";
+pub const OTEL_TRACES_SAMPLER_ARG: &str = "OTEL_TRACES_SAMPLER_ARG";
+
+/// changes filter of installed subscriber
+static LOG_FILTER: OnceLock<reload::Handle<EnvFilter, Registry>> = OnceLock::new();
+
+/// Flushes exported spans when dropped, keep it until the component exits
+#[must_use]
+#[derive(Debug, Default)]
+pub struct TelemetryGuard {
+    #[cfg(feature = "telemetry")]
+    exporting: bool,
+}
+
+impl Drop for TelemetryGuard {
+    fn drop(&mut self) {
+        #[cfg(feature = "telemetry")]
+        if self.exporting {
+            opentelemetry::global::shutdown_tracer_provider();
+        }
+    }
+}
+
+/// Initialize logging of spans in format set by `FLV_LOG_FORMAT`,
+/// and export of them if an OTLP endpoint is configured
+pub fn init_tracer(service_name: &str) -> TelemetryGuard {
+    let format = LogFormat::from_env();
+
+    #[cfg(feature = "telemetry")]
+    if std::env::var_os(OTEL_EXPORTER_OTLP_ENDPOINT).is_some() {
+        match otlp::tracer(service_name) {
+            Ok(tracer) => {
+                subscriber(format, service_name)
+                    .with(tracing_opentelemetry::layer().with_tracer(tracer))
+                    .init();
+                return TelemetryGuard { exporting: true };
+            }
+            Err(err) => eprintln!("unable to export traces, only logging them: {err}"),
+        }
+    }
+
+    subscriber(format, service_name).init();
+    tracing::debug!(service_name, "trace export disabled");
+    TelemetryGuard::default()
+}
+
+fn subscriber(
+    format: LogFormat,
+    service_name: &str,
+) -> impl Subscriber + Send + Sync + for<'a> LookupSpan<'a> {
+    let fmt_layer = match format {
+        LogFormat::Text => tracing_subscriber::fmt::layer().boxed(),
+        LogFormat::Json => tracing_subscriber::fmt::layer()
+            .event_format(JsonFormat::new(service_name))
+            .fmt_fields(JsonFields::new())
+            .boxed(),
+    };
+
+    let (filter, handle) = reload::Layer::new(EnvFilter::from_default_env());
+    let _ = LOG_FILTER.set(handle);
+
+    tracing_subscriber::registry().with(filter).with(fmt_layer)
+}
+
+/// check if `directives` are valid filter in `RUST_LOG` syntax
+pub fn validate_log_filter(directives: &str) -> Result<(), String> {
+    EnvFilter::try_new(directives)
+        .map(|_| ())
+        .map_err(|err| err.to_string())
+}
+
+/// change filter of logged events to `directives` in `RUST_LOG` syntax, none restores `RUST_LOG`
+pub fn set_log_filter(directives: Option<&str>) -> Result<(), String> {
+    let filter = match directives {
+        Some(directives) => EnvFilter::try_new(directives).map_err(|err| err.to_string())?,
+        None => EnvFilter::from_default_env(),
+    };
+    LOG_FILTER
+        .get()
+        .ok_or_else(|| "logging is not initialized".to_owned())?
+        .reload(filter)
+        .map_err(|err| err.to_string())
+}
+
+/// W3C `traceparent` of current span, none if traces are not exported
+pub fn current_traceparent() -> Option<String> {
+    #[cfg(feature = "telemetry")]
+    return otlp::traceparent(&Span::current());
+    #[cfg(not(feature = "telemetry"))]
+    None
+}
+
+/// continue trace of another component in `span`, from the `traceparent` it sent
+pub fn set_remote_parent(span: &Span, traceparent: Option<&str>) {
+    #[cfg(feature = "telemetry")]
+    if let Some(traceparent) = traceparent {
+        otlp::set_parent(span, traceparent);
+    }
+    #[cfg(not(feature = "telemetry"))]
+    let _ = (span, traceparent);
+}
+
+#[cfg(feature = "telemetry")]
+mod otlp {
+    use std::collections::HashMap;
+
+    use opentelemetry::global;
+    use opentelemetry::propagation::TextMapPropagator;
+    use opentelemetry::trace::TraceError;
+    use opentelemetry::KeyValue;
+    use opentelemetry_sdk::propagation::TraceContextPropagator;
+    use opentelemetry_sdk::trace::{self as sdktrace, Sampler, Tracer};
+    use opentelemetry_otlp::HttpExporterBuilder;
+    use opentelemetry_sdk::{runtime, Resource};
+    use tracing::Span;
+    use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+    use super::{OTEL_SERVICE_NAME, OTEL_TRACES_SAMPLER_ARG};
+
+    const TRACEPARENT: &str = "traceparent";
+
+    pub(super) fn tracer(service_name: &str) -> Result<Tracer, TraceError> {
+        // endpoint is read by exporter from environment
+        pipeline(service_name, opentelemetry_otlp::new_exporter().http())
+    }
+
+    fn pipeline(service_name: &str, exporter: HttpExporterBuilder) -> Result<Tracer, TraceError> {
+        let service_name =
+            std::env::var(OTEL_SERVICE_NAME).unwrap_or_else(|_| service_name.to_owned());
+        let ratio = std::env::var(OTEL_TRACES_SAMPLER_ARG)
+            .ok()
+            .and_then(|it| it.parse::<f64>().ok())
+            .unwrap_or(1.0);
+
+        let tracer = opentelemetry_otlp::new_pipeline()
+            .tracing()
+            .with_exporter(exporter)
+            .with_trace_config(
+                sdktrace::config()
+                    .with_sampler(Sampler::ParentBased(Box::new(Sampler::TraceIdRatioBased(
+                        ratio,
+                    ))))
+                    .with_resource(Resource::new(vec![KeyValue::new(
+                        "service.name",
+                        service_name,
+                    )])),
+            )
+            // spans are queued and exported in background, request path never waits on collector
+            .install_batch(runtime::AsyncStd)?;
+        global::set_text_map_propagator(TraceContextPropagator::new());
+        Ok(tracer)
+    }
+
+    pub(super) fn traceparent(span: &Span) -> Option<String> {
+        let mut carrier = HashMap::new();
+        TraceContextPropagator::new().inject_context(&span.context(), &mut carrier);
+        carrier.remove(TRACEPARENT)
+    }
+
+    pub(super) fn set_parent(span: &Span, traceparent: &str) {
+        let carrier = HashMap::from([(TRACEPARENT.to_owned(), traceparent.to_owned())]);
+        span.set_parent(TraceContextPropagator::new().extract(&carrier));
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use std::io::{BufRead, BufReader, Read, Write};
+        use std::net::TcpListener;
+        use std::sync::mpsc::{self, Receiver};
+        use std::time::Duration;
+
+        use opentelemetry::trace::Tracer as _;
+        use opentelemetry_otlp::WithExportConfig;
+
+        use super::*;
+
+        /// accepts OTLP exports, sends back request line of each
+        fn collector() -> (String, Receiver<String>) {
+            let listener = TcpListener::bind("127.0.0.1:0").expect("bind");
+            let endpoint = format!("http://{}", listener.local_addr().expect("addr"));
+            let (sender, receiver) = mpsc::channel();
+            std::thread::spawn(move || {
+                for stream in listener.incoming() {
+                    let mut stream = stream.expect("stream");
+                    let mut reader = BufReader::new(stream.try_clone().expect("clone"));
+                    let mut request_line = String::new();
+                    reader.read_line(&mut request_line).expect("request line");
+                    let mut content_length = 0;
+                    loop {
+                        let mut line = String::new();
+                        reader.read_line(&mut line).expect("header");
+                        if line.trim().is_empty() {
+                            break;
+                        }
+                        if let Some((name, value)) = line.split_once(':') {
+                            if name.eq_ignore_ascii_case("content-length") {
+                                content_length = value.trim().parse().expect("length");
+                            }
+                        }
+                    }
+                    let mut body = vec![0; content_length];
+                    reader.read_exact(&mut body).expect("body");
+                    stream
+                        .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n")
+                        .expect("response");
+                    if sender.send(request_line).is_err() {
+                        break;
+                    }
+                }
+            });
+            (endpoint, receiver)
+        }
+
+        #[test]
+        fn test_spans_flushed_on_shutdown() {
+            let (endpoint, requests) = collector();
+            let tracer = pipeline(
+                "test",
+                opentelemetry_otlp::new_exporter()
+                    .http()
+                    .with_endpoint(endpoint),
+            )
+            .expect("pipeline");
+
+            tracer.in_span("flushed", |_| {});
+
+            // span is still queued in batch, shutdown must export it before returning
+            global::shutdown_tracer_provider();
+            let request = requests
+                .recv_timeout(Duration::from_secs(1))
+                .expect("spans exported");
+            assert!(request.starts_with("POST "));
+            assert!(request.contains("/v1/traces"));
+        }
+    }
+}
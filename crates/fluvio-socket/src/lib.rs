@@ -1,5 +1,6 @@
 mod error;
 mod multiplexing;
+mod observer;
 mod sink;
 mod socket;
 mod stream;
@@ -13,6 +14,7 @@ pub use fluvio_future::net::{BoxConnection, Connection};
 pub use self::error::SocketError;
 pub use self::socket::FluvioSocket;
 pub use multiplexing::*;
+pub use observer::*;
 pub use sink::*;
 
 pub use stream::*;
@@ -31,12 +31,14 @@ use futures_util::ready;
 use fluvio_protocol::api::Request;
 use fluvio_protocol::api::RequestHeader;
 use fluvio_protocol::api::RequestMessage;
-use fluvio_protocol::{Decoder};
+use fluvio_protocol::{Decoder, Encoder};
 
 use crate::SocketError;
 use crate::ExclusiveFlvSink;
 use crate::FluvioSocket;
 use crate::FluvioStream;
+use crate::observer::RequestTimer;
+use crate::{SocketObserver, SocketObservers};
 
 pub type SharedMultiplexerSocket = Arc<MultiplexerSocket>;
 
@@ -60,6 +62,7 @@ pub struct MultiplexerSocket {
     sink: ExclusiveFlvSink,
     stale: Arc<AtomicBool>,
     terminate: Arc<Event>,
+    observers: SocketObservers,
 }
 
 impl fmt::Debug for MultiplexerSocket {
@@ -80,10 +83,19 @@ impl MultiplexerSocket {
         Arc::new(Self::new(socket))
     }
 
+    pub fn shared_with_observers(socket: FluvioSocket, observers: SocketObservers) -> Arc<Self> {
+        Arc::new(Self::with_observers(socket, observers))
+    }
+
     /// create new multiplexer socket, this always starts with correlation id of 1
     /// correlation id of 0 means shared
-    #[allow(clippy::clone_on_copy)]
     pub fn new(socket: FluvioSocket) -> Self {
+        Self::with_observers(socket, SocketObservers::default())
+    }
+
+    /// create new multiplexer socket reporting bytes and request latency to observers
+    #[allow(clippy::clone_on_copy)]
+    pub fn with_observers(socket: FluvioSocket, observers: SocketObservers) -> Self {
         let id = socket.id().clone();
         debug!(socket = %id, "spawning dispatcher");
 
@@ -96,6 +108,7 @@ impl MultiplexerSocket {
             sink: ExclusiveFlvSink::new(sink),
             terminate: Arc::new(Event::new()),
             stale: stale.clone(),
+            observers: observers.clone(),
         };
 
         MultiPlexingResponseDispatcher::run(
@@ -104,6 +117,7 @@ impl MultiplexerSocket {
             multiplexer.senders.clone(),
             multiplexer.terminate.clone(),
             stale,
+            observers,
         );
 
         multiplexer
@@ -156,10 +170,11 @@ impl MultiplexerSocket {
         let listener = msg_event.listen();
 
         debug!(api = R::API_KEY, correlation_id, "sending request");
-        self.sink.send_request(&req_msg).await?;
+        let timer = RequestTimer::start();
+        self.send_request(&req_msg).await?;
         trace!(correlation_id, "waiting");
 
-        select! {
+        let result: Result<R::Response, SocketError> = select! {
 
             _ = sleep(Duration::from_secs(*MAX_WAIT_TIME)) => {
 
@@ -212,7 +227,24 @@ impl MultiplexerSocket {
                     ).into())
                 }
             },
+        };
+
+        self.observers
+            .request_completed(R::API_KEY, timer.elapsed(), result.is_ok());
+        result
+    }
+
+    /// send request and report its size to observers
+    async fn send_request<R>(&self, req_msg: &RequestMessage<R>) -> Result<(), SocketError>
+    where
+        R: Request,
+    {
+        self.sink.send_request(req_msg).await?;
+        if !self.observers.is_empty() {
+            // frame is prefixed with 4 bytes of size
+            self.observers.bytes_sent(req_msg.write_size(0) + 4);
         }
+        Ok(())
     }
     /// send request and get response asynchronously
     #[instrument(skip(req_msg))]
@@ -258,7 +290,7 @@ impl MultiplexerSocket {
 
         trace!(correlation_id, "created new channel");
 
-        self.sink.send_request(&req_msg).await?;
+        self.send_request(&req_msg).await?;
 
         trace!(correlation_id, "request send");
 
@@ -360,6 +392,7 @@ struct MultiPlexingResponseDispatcher {
     senders: Senders,
     terminate: Arc<Event>,
     stale: Arc<AtomicBool>,
+    observers: SocketObservers,
 }
 
 impl fmt::Debug for MultiPlexingResponseDispatcher {
@@ -375,6 +408,7 @@ impl MultiPlexingResponseDispatcher {
         senders: Senders,
         terminate: Arc<Event>,
         stale: Arc<AtomicBool>,
+        observers: SocketObservers,
     ) {
         use fluvio_future::task::spawn;
 
@@ -383,6 +417,7 @@ impl MultiPlexingResponseDispatcher {
             senders,
             terminate,
             stale,
+            observers,
         };
 
         spawn(dispatcher.dispatcher_loop(stream));
@@ -399,6 +434,7 @@ impl MultiPlexingResponseDispatcher {
                 frame = frame_stream.next() => {
                     match frame {
                         Some(Ok(mut msg)) => {
+                            self.observers.bytes_received(msg.len() + 4);
                             let mut correlation_id: i32 = 0;
                             match correlation_id.decode(&mut msg, 0) {
                                 Ok(_) => {
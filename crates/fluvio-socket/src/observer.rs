@@ -0,0 +1,140 @@
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+pub type SharedSocketObserver = Arc<dyn SocketObserver>;
+
+/// Receives transport level events from sockets.
+/// Callbacks are invoked inline on the socket path, so they must not block.
+pub trait SocketObserver: Send + Sync {
+    /// request frame written to connection, including size header
+    fn bytes_sent(&self, _bytes: usize) {}
+
+    /// response frame read from connection, including size header
+    fn bytes_received(&self, _bytes: usize) {}
+
+    /// request/response round trip completed
+    fn request_completed(&self, _api_key: u16, _latency: Duration, _success: bool) {}
+
+    /// new connection established
+    fn connected(&self, _addr: &str) {}
+
+    /// connection re-established after previous one went stale or was closed
+    fn reconnected(&self, _addr: &str) {}
+}
+
+/// Set of observers notified together
+#[derive(Clone, Default)]
+pub struct SocketObservers(Vec<SharedSocketObserver>);
+
+impl fmt::Debug for SocketObservers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SocketObservers({})", self.0.len())
+    }
+}
+
+impl SocketObservers {
+    pub fn add(&mut self, observer: SharedSocketObserver) {
+        self.0.push(observer);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl SocketObserver for SocketObservers {
+    fn bytes_sent(&self, bytes: usize) {
+        self.0.iter().for_each(|o| o.bytes_sent(bytes));
+    }
+
+    fn bytes_received(&self, bytes: usize) {
+        self.0.iter().for_each(|o| o.bytes_received(bytes));
+    }
+
+    fn request_completed(&self, api_key: u16, latency: Duration, success: bool) {
+        self.0
+            .iter()
+            .for_each(|o| o.request_completed(api_key, latency, success));
+    }
+
+    fn connected(&self, addr: &str) {
+        self.0.iter().for_each(|o| o.connected(addr));
+    }
+
+    fn reconnected(&self, addr: &str) {
+        self.0.iter().for_each(|o| o.reconnected(addr));
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(target_arch = "wasm32")] {
+        /// no monotonic clock in wasm, latency is reported as zero
+        pub(crate) struct RequestTimer;
+
+        impl RequestTimer {
+            pub(crate) fn start() -> Self {
+                Self
+            }
+
+            pub(crate) fn elapsed(&self) -> Duration {
+                Duration::ZERO
+            }
+        }
+    } else {
+        pub(crate) struct RequestTimer(std::time::Instant);
+
+        impl RequestTimer {
+            pub(crate) fn start() -> Self {
+                Self(std::time::Instant::now())
+            }
+
+            pub(crate) fn elapsed(&self) -> Duration {
+                self.0.elapsed()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    #[derive(Default)]
+    struct Counter {
+        sent: AtomicUsize,
+        reconnects: AtomicUsize,
+    }
+
+    impl SocketObserver for Counter {
+        fn bytes_sent(&self, bytes: usize) {
+            self.sent.fetch_add(bytes, Ordering::Relaxed);
+        }
+
+        fn reconnected(&self, _addr: &str) {
+            self.reconnects.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    #[test]
+    fn test_observers_fan_out() {
+        let first = Arc::new(Counter::default());
+        let second = Arc::new(Counter::default());
+
+        let mut observers = SocketObservers::default();
+        assert!(observers.is_empty());
+        observers.add(first.clone());
+        observers.add(second.clone());
+
+        observers.bytes_sent(10);
+        observers.bytes_received(5);
+        observers.reconnected("localhost:9010");
+
+        for counter in [first, second] {
+            assert_eq!(counter.sent.load(Ordering::Relaxed), 10);
+            assert_eq!(counter.reconnects.load(Ordering::Relaxed), 1);
+        }
+    }
+}
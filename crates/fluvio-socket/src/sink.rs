@@ -23,6 +23,9 @@ pub struct FluvioSink {
     inner: SinkFrame,
     fd: ConnectionFd,
     enable_zero_copy: bool,
+    /// scope matched by injected faults, peer address of outgoing connections
+    #[cfg(feature = "fault-injection")]
+    fault_scope: String,
 }
 
 impl fmt::Debug for FluvioSink {
@@ -52,9 +55,16 @@ impl FluvioSink {
             fd,
             enable_zero_copy: true,
             inner: SinkFrame::new(sink.compat_write(), FluvioCodec::new()),
+            #[cfg(feature = "fault-injection")]
+            fault_scope: String::new(),
         }
     }
 
+    #[cfg(feature = "fault-injection")]
+    pub fn set_fault_scope(&mut self, scope: impl Into<String>) {
+        self.fault_scope = scope.into();
+    }
+
     /// don't use zero copy
     pub fn disable_zerocopy(&mut self) {
         self.enable_zero_copy = false;
@@ -66,6 +76,10 @@ impl FluvioSink {
     where
         RequestMessage<R>: FlvEncoder + Debug,
     {
+        #[cfg(feature = "fault-injection")]
+        if inject_fault(&self.fault_scope).await? {
+            return Ok(());
+        }
         self.inner.send((req_msg, 0)).await?;
         Ok(())
     }
@@ -81,11 +95,34 @@ impl FluvioSink {
         ResponseMessage<P>: FlvEncoder + Debug,
     {
         trace!("sending response {:#?}", &resp_msg);
+        #[cfg(feature = "fault-injection")]
+        if inject_fault(&self.fault_scope).await? {
+            return Ok(());
+        }
         self.inner.send((resp_msg, version)).await?;
         Ok(())
     }
 }
 
+/// apply fault injected at socket send, returns true if message must be dropped
+#[cfg(feature = "fault-injection")]
+async fn inject_fault(scope: &str) -> Result<bool, SocketError> {
+    use fluvio_types::fault::{check, FaultAction, SOCKET_SEND};
+
+    match check(SOCKET_SEND, scope) {
+        Some(FaultAction::Delay(delay)) => {
+            fluvio_future::timer::sleep(delay).await;
+            Ok(false)
+        }
+        Some(FaultAction::Drop) => Ok(true),
+        Some(FaultAction::Error(kind)) => Err(SocketError::Io {
+            source: std::io::Error::new(kind, "injected fault"),
+            msg: format!("injected fault at {SOCKET_SEND}"),
+        }),
+        None => Ok(false),
+    }
+}
+
 #[cfg(unix)]
 mod fd {
 
@@ -129,6 +166,10 @@ mod file {
             T: FileWrite,
         {
             trace!("encoding file slices version: {}", version);
+            #[cfg(feature = "fault-injection")]
+            if super::inject_fault(&self.fault_scope).await? {
+                return Ok(0);
+            }
             let mut buf = BytesMut::with_capacity(1000);
             let mut data: Vec<StoreValue> = vec![];
             msg.file_encode(&mut buf, &mut data, version)?;
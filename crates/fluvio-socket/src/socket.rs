@@ -103,7 +103,11 @@ impl FluvioSocket {
             }
         })?;
 
-        Ok(Self::from_stream(write, read, fd))
+        #[allow(unused_mut)]
+        let mut socket = Self::from_stream(write, read, fd);
+        #[cfg(feature = "fault-injection")]
+        socket.sink.set_fault_scope(addr);
+        Ok(socket)
     }
 }
 
@@ -1,6 +1,7 @@
 use std::default::Default;
 use std::fmt;
 use std::fmt::{Debug, Display};
+use std::io::{Error as IoError, ErrorKind};
 use std::ops::Deref;
 use std::sync::Arc;
 use std::time::Duration;
@@ -10,11 +11,14 @@ use tracing::{debug, instrument, info};
 
 use fluvio_protocol::api::RequestMessage;
 use fluvio_protocol::api::Request;
+use fluvio_protocol::link::auth_token::AuthTokenRequest;
+use fluvio_protocol::link::capabilities::{Capabilities, Capability};
 use fluvio_protocol::link::versions::{ApiVersions, ApiVersionsRequest, ApiVersionsResponse};
 use fluvio_future::net::{DomainConnector, DefaultDomainConnector};
 use fluvio_future::retry::retry_if;
 
 use crate::{SocketError, FluvioSocket, SharedMultiplexerSocket, AsyncResponse};
+use crate::{SharedSocketObserver, SocketObserver, SocketObservers};
 
 /// Frame with request and response
 pub trait SerialFrame: Display {
@@ -49,6 +53,23 @@ impl VersionedSocket {
         mut socket: FluvioSocket,
         config: Arc<ClientConfig>,
     ) -> Result<Self, SocketError> {
+        // server requiring tokens expects token before any other request
+        if let Some(token) = &config.auth_token {
+            debug!("authenticating with token");
+            let mut req_msg = RequestMessage::new_request(AuthTokenRequest::new(token));
+            req_msg.get_mut_header().set_client_id(&config.client_id);
+            let response = socket.send(&req_msg).await?.response;
+            if !response.error_code.is_ok() {
+                let msg = response
+                    .error_message
+                    .unwrap_or_else(|| "token rejected".to_owned());
+                return Err(SocketError::Io {
+                    source: IoError::new(ErrorKind::PermissionDenied, msg.clone()),
+                    msg: format!("authentication failed: {msg}"),
+                });
+            }
+        }
+
         // now get versions
         // Query for API versions
 
@@ -56,6 +77,7 @@ impl VersionedSocket {
             client_version: crate::built_info::PKG_VERSION.into(),
             client_os: crate::built_info::CFG_OS.into(),
             client_arch: crate::built_info::CFG_TARGET_ARCH.into(),
+            capabilities: Capabilities::supported(),
         };
 
         debug!(client_version = %version.client_version, "querying versions");
@@ -86,6 +108,8 @@ pub struct ClientConfig {
     client_id: String,
     connector: DomainConnector,
     use_spu_local_address: bool,
+    auth_token: Option<String>,
+    observers: SocketObservers,
 }
 
 impl Debug for ClientConfig {
@@ -121,6 +145,8 @@ impl ClientConfig {
             client_id: "fluvio".to_owned(),
             connector,
             use_spu_local_address,
+            auth_token: None,
+            observers: SocketObservers::default(),
         }
     }
 
@@ -149,12 +175,27 @@ impl ClientConfig {
         self.addr = domain
     }
 
+    /// bearer token sent to server when connecting
+    pub fn set_auth_token(&mut self, token: Option<String>) {
+        self.auth_token = token;
+    }
+
+    /// observer notified of transport events of connections made with this config
+    pub fn add_observer(&mut self, observer: SharedSocketObserver) {
+        self.observers.add(observer);
+    }
+
+    pub fn observers(&self) -> &SocketObservers {
+        &self.observers
+    }
+
     #[instrument(skip(self))]
     pub async fn connect(self) -> Result<VersionedSocket, SocketError> {
         debug!(add = %self.addr, "try connection to");
         let socket =
             FluvioSocket::connect_with_connector(&self.addr, self.connector.as_ref()).await?;
         info!(add = %self.addr, "connect to socket");
+        self.observers.connected(&self.addr);
         VersionedSocket::connect(socket, Arc::new(self)).await
     }
 
@@ -170,6 +211,8 @@ impl ClientConfig {
             client_id: self.client_id.clone(),
             connector,
             use_spu_local_address: self.use_spu_local_address,
+            auth_token: self.auth_token.clone(),
+            observers: self.observers.clone(),
         }
     }
 }
@@ -179,16 +222,55 @@ impl ClientConfig {
 pub struct Versions {
     api_versions: ApiVersions,
     platform_version: semver::Version,
+    /// capabilities supported by both sides, none if peer predates negotiation
+    capabilities: Option<Capabilities>,
 }
 
 impl Versions {
     pub fn new(version_response: ApiVersionsResponse) -> Self {
+        let capabilities = if version_response.capabilities.is_empty() {
+            None
+        } else {
+            Some(
+                version_response
+                    .capabilities
+                    .intersect(Capabilities::supported()),
+            )
+        };
         Self {
             api_versions: version_response.api_keys,
             platform_version: version_response.platform_version.to_semver(),
+            capabilities,
         }
     }
 
+    /// capabilities negotiated with peer
+    pub fn capabilities(&self) -> Capabilities {
+        self.capabilities.unwrap_or_default()
+    }
+
+    pub fn supports(&self, capability: Capability) -> bool {
+        self.capabilities().contains(capability)
+    }
+
+    /// Peers which predate negotiation don't advertise capabilities,
+    /// derive them from maximum version of `R` instead.
+    /// `implied` lists minimum version of `R` which provides capability.
+    pub fn infer_capabilities<R: Request>(&mut self, implied: &[(Capability, i16)]) {
+        if self.capabilities.is_some() {
+            return;
+        }
+        let version = self.lookup_version::<R>();
+        let capabilities = implied
+            .iter()
+            .filter(|(_, min_version)| version.is_some_and(|version| version >= *min_version))
+            .fold(Capabilities::NONE, |caps, (capability, _)| {
+                caps.with(*capability)
+            });
+        debug!(?version, ?capabilities, "inferred capabilities");
+        self.capabilities = Some(capabilities);
+    }
+
     /// Tells the platform version reported by the SC
     ///
     /// The platform version refers to the value in the VERSION
@@ -381,6 +463,7 @@ mod test {
     use fluvio_protocol::Encoder;
     use fluvio_protocol::api::Request;
     use fluvio_protocol::link::versions::ApiVersionKey;
+    use fluvio_protocol::link::capabilities::{Capabilities, Capability};
 
     use super::ApiVersionsResponse;
     use super::Versions;
@@ -423,4 +506,34 @@ mod test {
         assert_eq!(versions.lookup_version::<T1>(), Some(9));
         assert_eq!(versions.lookup_version::<T2>(), None);
     }
+
+    #[test]
+    fn test_capabilities() {
+        let mut response = ApiVersionsResponse::default();
+        response.api_keys.push(ApiVersionKey {
+            api_key: 1000,
+            min_version: 5,
+            max_version: 7,
+        });
+        let implied = [
+            (Capability::SmartModuleChain, 6),
+            (Capability::BrokerTimestamps, 8),
+        ];
+
+        // legacy peer, derived from version
+        let mut versions = Versions::new(response);
+        versions.infer_capabilities::<T1>(&implied);
+        assert!(versions.supports(Capability::SmartModuleChain));
+        assert!(!versions.supports(Capability::BrokerTimestamps));
+
+        // advertised capabilities take precedence
+        let response = ApiVersionsResponse {
+            capabilities: Capabilities::NONE.with(Capability::BrokerTimestamps),
+            ..Default::default()
+        };
+        let mut versions = Versions::new(response);
+        versions.infer_capabilities::<T1>(&implied);
+        assert!(!versions.supports(Capability::SmartModuleChain));
+        assert!(versions.supports(Capability::BrokerTimestamps));
+    }
 }
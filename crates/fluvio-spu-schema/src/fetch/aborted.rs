@@ -0,0 +1,86 @@
+use std::collections::HashSet;
+
+use fluvio_protocol::record::{BatchHeader, Offset, TxnMarker};
+
+use super::AbortedTransaction;
+
+/// Drops batches which are not meant for consumers: control batches carrying transaction
+/// markers and batches of aborted transactions.
+/// Batches must be passed in offset order.
+#[derive(Debug, Default)]
+pub struct AbortedTxnFilter {
+    /// aborted transactions not yet reached, last one starts first
+    pending: Vec<AbortedTransaction>,
+    /// producers whose batches are aborted until their abort marker
+    active: HashSet<i64>,
+}
+
+impl AbortedTxnFilter {
+    pub fn new(mut aborted: Vec<AbortedTransaction>) -> Self {
+        aborted.sort_by_key(|txn| std::cmp::Reverse(txn.first_offset));
+        Self {
+            pending: aborted,
+            active: HashSet::new(),
+        }
+    }
+
+    /// true if batch at `base_offset` should be returned to consumer
+    pub fn keep(&mut self, base_offset: Offset, header: &BatchHeader) -> bool {
+        while let Some(txn) = self.pending.last() {
+            if txn.first_offset > base_offset {
+                break;
+            }
+            self.active.insert(txn.producer_id);
+            self.pending.pop();
+        }
+
+        if header.is_control() {
+            if header.txn_marker() == Some(TxnMarker::Abort) {
+                self.active.remove(&header.producer_id);
+            }
+            return false;
+        }
+        !(header.is_transactional() && self.active.contains(&header.producer_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header(producer_id: i64, transactional: bool, marker: Option<TxnMarker>) -> BatchHeader {
+        let mut header = BatchHeader {
+            producer_id,
+            ..Default::default()
+        };
+        if transactional {
+            header.set_transactional();
+        }
+        if let Some(marker) = marker {
+            header.set_txn_marker(marker);
+        }
+        header
+    }
+
+    #[test]
+    fn test_aborted_txn_filter() {
+        let mut filter = AbortedTxnFilter::new(vec![AbortedTransaction {
+            producer_id: 1,
+            first_offset: 10,
+        }]);
+
+        // transaction of same producer before aborted one is committed
+        assert!(filter.keep(0, &header(1, true, None)));
+        assert!(!filter.keep(2, &header(1, true, Some(TxnMarker::Commit))));
+
+        assert!(filter.keep(5, &header(-1, false, None)));
+        assert!(!filter.keep(10, &header(1, true, None)));
+        assert!(filter.keep(12, &header(2, true, None)));
+        // non transactional batches of producer are kept
+        assert!(filter.keep(13, &header(1, false, None)));
+        assert!(!filter.keep(14, &header(1, true, None)));
+        assert!(!filter.keep(16, &header(1, true, Some(TxnMarker::Abort))));
+
+        assert!(filter.keep(17, &header(1, true, None)));
+    }
+}
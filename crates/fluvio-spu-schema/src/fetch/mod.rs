@@ -1,5 +1,9 @@
 mod request;
 mod response;
+mod aborted;
+mod session;
 
 pub use request::*;
 pub use response::*;
+pub use aborted::*;
+pub use session::*;
@@ -14,6 +14,15 @@ use super::FetchResponse;
 
 pub type DefaultFetchRequest = FetchRequest<RecordSet>;
 
+/// first version with incremental fetch sessions
+pub const FETCH_SESSION_MIN_VERSION: i16 = 26;
+
+/// epoch of request creating new fetch session
+pub const INITIAL_EPOCH: i32 = 0;
+
+/// epoch of request fetching without session, closes session if one is given
+pub const FINAL_EPOCH: i32 = -1;
+
 #[derive(Encoder, Decoder, FluvioDefault, Debug)]
 pub struct FetchRequest<R> {
     /// The maximum time in milliseconds to wait for the response.
@@ -42,9 +51,31 @@ pub struct FetchRequest<R> {
     #[fluvio(min_version = 7)]
     pub forgotten: Vec<ForgottenTopic>,
 
+    /// The fetch session id, 0 to fetch without existing session.
+    #[fluvio(min_version = 26)]
+    pub session_id: i32,
+
+    /// The fetch session epoch. `INITIAL_EPOCH` creates new session with partitions of request,
+    /// following requests of session increase epoch by one and only carry partitions whose fetch
+    /// offset changed, added partitions and forgotten ones.
+    #[fluvio(min_version = 26, default = "FINAL_EPOCH")]
+    pub session_epoch: i32,
+
     pub data: PhantomData<R>,
 }
 
+impl<R> FetchRequest<R> {
+    /// request creating new fetch session, closing session given by id
+    pub fn is_new_session(&self) -> bool {
+        self.session_epoch == INITIAL_EPOCH
+    }
+
+    /// request continuing existing fetch session
+    pub fn is_incremental(&self) -> bool {
+        self.session_id != 0 && self.session_epoch > INITIAL_EPOCH
+    }
+}
+
 impl<R> Request for FetchRequest<R>
 where
     R: Debug + Decoder + Encoder,
@@ -57,7 +88,7 @@ where
     type Response = FetchResponse<R>;
 }
 
-#[derive(Encoder, Decoder, FluvioDefault, Debug)]
+#[derive(Encoder, Decoder, FluvioDefault, Debug, Clone)]
 pub struct FetchableTopic {
     /// The name of the topic to fetch.
     pub name: String,
@@ -77,13 +108,13 @@ pub struct ForgottenTopic {
     pub forgotten_partition_indexes: Vec<i32>,
 }
 
-#[derive(Encoder, Decoder, FluvioDefault, Debug)]
+#[derive(Encoder, Decoder, FluvioDefault, Debug, Clone)]
 pub struct FetchPartition {
     /// The partition index.
     pub partition_index: PartitionId,
 
-    /// The current leader epoch of the partition.
-    #[fluvio(min_version = 9, ignorable)]
+    /// The current leader epoch of the partition, -1 if unknown.
+    #[fluvio(min_version = 9, ignorable, default = "-1")]
     pub current_leader_epoch: i32,
 
     /// The message offset.
@@ -74,6 +74,10 @@ pub struct FetchablePartitionResponse<R> {
     /// The aborted transactions.
     pub aborted: Option<Vec<AbortedTransaction>>,
 
+    /// Broker times of first batch in records, set if consumer asked for them
+    #[fluvio(min_version = 27)]
+    pub broker_timestamps: Option<BrokerTimestamps>,
+
     /// The record data.
     pub records: R,
 }
@@ -91,12 +95,23 @@ impl<R: BatchRecords> FetchablePartitionResponse<RecordSet<R>> {
     }
 }
 
-#[derive(Encoder, Decoder, FluvioDefault, Debug)]
+#[derive(Encoder, Decoder, FluvioDefault, Debug, Clone, Eq, PartialEq)]
 pub struct AbortedTransaction {
     pub producer_id: i64,
     pub first_offset: i64,
 }
 
+/// Times in milliseconds since epoch when batch went through the leader
+#[derive(Encoder, Decoder, FluvioDefault, Debug, Clone, Copy, Eq, PartialEq)]
+pub struct BrokerTimestamps {
+    /// batch was appended to leader log
+    pub append_time: i64,
+    /// batch was committed by in-sync replicas, -1 if not yet committed
+    pub commit_time: i64,
+    /// batch was read to be sent to consumer
+    pub fetch_time: i64,
+}
+
 // -----------------------------------
 // Implementation
 // -----------------------------------
@@ -152,6 +167,9 @@ mod file {
             }
             self.log_start_offset.encode(src, version)?;
             self.aborted.encode(src, version)?;
+            if version >= 27 {
+                self.broker_timestamps.encode(src, version)?;
+            }
             self.records.file_encode(src, data, version)?;
             Ok(())
         }
@@ -191,3 +209,43 @@ mod file {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+
+    use fluvio_protocol::{Decoder, Encoder};
+    use fluvio_protocol::record::RecordSet;
+
+    use super::{BrokerTimestamps, FetchablePartitionResponse};
+
+    #[test]
+    fn test_broker_timestamps_version() {
+        let timestamps = BrokerTimestamps {
+            append_time: 100,
+            commit_time: 105,
+            fetch_time: 120,
+        };
+        let response = FetchablePartitionResponse::<RecordSet> {
+            partition_index: 1,
+            broker_timestamps: Some(timestamps),
+            ..Default::default()
+        };
+
+        let mut dest = vec![];
+        response.encode(&mut dest, 27).expect("encode");
+        let mut decoded = FetchablePartitionResponse::<RecordSet>::default();
+        decoded
+            .decode(&mut std::io::Cursor::new(dest), 27)
+            .expect("decode");
+        assert_eq!(decoded.broker_timestamps, Some(timestamps));
+
+        let mut dest = vec![];
+        response.encode(&mut dest, 26).expect("encode");
+        let mut decoded = FetchablePartitionResponse::<RecordSet>::default();
+        decoded
+            .decode(&mut std::io::Cursor::new(dest), 26)
+            .expect("decode");
+        assert_eq!(decoded.partition_index, 1);
+        assert!(decoded.broker_timestamps.is_none());
+    }
+}
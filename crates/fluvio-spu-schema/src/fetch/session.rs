@@ -0,0 +1,189 @@
+use std::collections::BTreeMap;
+
+use fluvio_protocol::link::ErrorCode;
+use fluvio_types::PartitionId;
+
+use super::{
+    FetchPartition, FetchRequest, FetchResponse, FetchableTopic, ForgottenTopic, FINAL_EPOCH,
+    INITIAL_EPOCH,
+};
+
+/// Client side of incremental fetch session.
+/// Turns full list of partitions to fetch into request of session, which only carries
+/// partitions changed since previous request.
+#[derive(Debug)]
+pub struct FetchSessionHandler {
+    session_id: i32,
+    epoch: i32,
+    /// partitions known by session
+    partitions: BTreeMap<(String, PartitionId), FetchPartition>,
+}
+
+impl Default for FetchSessionHandler {
+    fn default() -> Self {
+        Self {
+            session_id: 0,
+            epoch: INITIAL_EPOCH,
+            partitions: BTreeMap::new(),
+        }
+    }
+}
+
+impl FetchSessionHandler {
+    pub fn session_id(&self) -> i32 {
+        self.session_id
+    }
+
+    /// fill partitions of request from partitions to fetch.
+    /// Full list is sent until session is established
+    pub fn build_request<R>(&mut self, request: &mut FetchRequest<R>, topics: Vec<FetchableTopic>) {
+        request.session_id = self.session_id;
+        request.session_epoch = self.epoch;
+        request.forgotten.clear();
+
+        if self.epoch == INITIAL_EPOCH {
+            self.partitions = topics
+                .iter()
+                .flat_map(|topic| {
+                    topic.fetch_partitions.iter().map(|partition| {
+                        (
+                            (topic.name.clone(), partition.partition_index),
+                            partition.clone(),
+                        )
+                    })
+                })
+                .collect();
+            request.topics = topics;
+            return;
+        }
+
+        let mut wanted = BTreeMap::new();
+        let mut changed: Vec<FetchableTopic> = vec![];
+        for topic in topics {
+            for partition in topic.fetch_partitions {
+                let key = (topic.name.clone(), partition.partition_index);
+                let unchanged = self.partitions.get(&key).is_some_and(|existing| {
+                    existing.fetch_offset == partition.fetch_offset
+                        && existing.max_bytes == partition.max_bytes
+                });
+                if !unchanged {
+                    match changed.iter_mut().find(|t| t.name == topic.name) {
+                        Some(t) => t.fetch_partitions.push(partition.clone()),
+                        None => changed.push(FetchableTopic {
+                            name: topic.name.clone(),
+                            fetch_partitions: vec![partition.clone()],
+                        }),
+                    }
+                }
+                wanted.insert(key, partition);
+            }
+        }
+
+        for (name, partition) in self.partitions.keys() {
+            if wanted.contains_key(&(name.clone(), *partition)) {
+                continue;
+            }
+            match request.forgotten.iter_mut().find(|t| &t.name == name) {
+                Some(t) => t.forgotten_partition_indexes.push(*partition as i32),
+                None => request.forgotten.push(ForgottenTopic {
+                    name: name.clone(),
+                    forgotten_partition_indexes: vec![*partition as i32],
+                }),
+            }
+        }
+
+        self.partitions = wanted;
+        request.topics = changed;
+    }
+
+    /// update session from response of request built by this handler
+    pub fn handle_response<R>(&mut self, response: &FetchResponse<R>) {
+        match response.error_code {
+            ErrorCode::None if response.session_id != 0 => {
+                self.session_id = response.session_id;
+                self.epoch = if self.epoch == i32::MAX {
+                    1
+                } else {
+                    self.epoch + 1
+                };
+            }
+            // SPU fetched without session, next request starts new one
+            _ => self.reset(),
+        }
+    }
+
+    /// request that closes session, fetching without session
+    pub fn close<R>(&mut self, request: &mut FetchRequest<R>) {
+        request.session_id = self.session_id;
+        request.session_epoch = FINAL_EPOCH;
+        self.reset();
+    }
+
+    fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fetch::{DefaultFetchRequest, DefaultFetchResponse};
+
+    fn topic(partitions: &[(PartitionId, i64)]) -> Vec<FetchableTopic> {
+        vec![FetchableTopic {
+            name: "test".to_owned(),
+            fetch_partitions: partitions
+                .iter()
+                .map(|(partition_index, fetch_offset)| FetchPartition {
+                    partition_index: *partition_index,
+                    fetch_offset: *fetch_offset,
+                    ..Default::default()
+                })
+                .collect(),
+        }]
+    }
+
+    fn session_response(session_id: i32) -> DefaultFetchResponse {
+        DefaultFetchResponse {
+            session_id,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_session_sends_only_changed_partitions() {
+        let mut handler = FetchSessionHandler::default();
+
+        let mut request = DefaultFetchRequest::default();
+        handler.build_request(&mut request, topic(&[(0, 0), (1, 0), (2, 0)]));
+        assert!(request.is_new_session());
+        assert_eq!(request.topics[0].fetch_partitions.len(), 3);
+        handler.handle_response(&session_response(5));
+
+        let mut request = DefaultFetchRequest::default();
+        handler.build_request(&mut request, topic(&[(0, 0), (1, 10)]));
+        assert!(request.is_incremental());
+        assert_eq!(request.session_id, 5);
+        assert_eq!(request.session_epoch, 1);
+        assert_eq!(request.topics[0].fetch_partitions.len(), 1);
+        assert_eq!(request.topics[0].fetch_partitions[0].partition_index, 1);
+        assert_eq!(request.forgotten[0].forgotten_partition_indexes, vec![2]);
+        handler.handle_response(&session_response(5));
+
+        let mut request = DefaultFetchRequest::default();
+        handler.build_request(&mut request, topic(&[(0, 0), (1, 10)]));
+        assert_eq!(request.session_epoch, 2);
+        assert!(request.topics.is_empty());
+        assert!(request.forgotten.is_empty());
+
+        // lost session starts over with full request
+        handler.handle_response(&DefaultFetchResponse {
+            error_code: ErrorCode::FetchSessionNotFoud,
+            ..Default::default()
+        });
+        let mut request = DefaultFetchRequest::default();
+        handler.build_request(&mut request, topic(&[(0, 0), (1, 10)]));
+        assert!(request.is_new_session());
+        assert_eq!(request.topics[0].fetch_partitions.len(), 2);
+    }
+}
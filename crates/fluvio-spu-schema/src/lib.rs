@@ -17,4 +17,4 @@ pub use fluvio_protocol::link::versions::{ApiVersions, ApiVersionsRequest, ApiVe
 pub use isolation::*;
 
 /// Default API version for all API
-pub const COMMON_VERSION: i16 = 23;
+pub const COMMON_VERSION: i16 = 29;
@@ -24,6 +24,7 @@ pub type DefaultPartitionRequest = PartitionProduceData<RecordSet<RawRecords>>;
 pub type DefaultTopicRequest = TopicProduceData<RecordSet<RawRecords>>;
 
 const PRODUCER_TRANSFORMATION_API_VERSION: i16 = 8;
+pub const LEADER_EPOCH_API: i16 = 28;
 
 #[derive(FluvioDefault, Debug)]
 pub struct ProduceRequest<R> {
@@ -76,6 +77,10 @@ pub struct PartitionProduceData<R> {
 
     /// The record data to be produced.
     pub records: R,
+
+    /// Leader epoch known to producer, -1 if unknown.
+    #[fluvio(min_version = 28, default = "-1")]
+    pub leader_epoch: i32,
 }
 
 impl<R> Encoder for ProduceRequest<R>
@@ -156,6 +161,7 @@ impl<R: Encoder + Decoder + Default + Debug + Clone> Clone for PartitionProduceD
         Self {
             partition_index: self.partition_index,
             records: self.records.clone(),
+            leader_epoch: self.leader_epoch,
         }
     }
 }
@@ -276,6 +282,9 @@ mod file {
             trace!("file encoding for partition request");
             self.partition_index.encode(src, version)?;
             self.records.file_encode(src, data, version)?;
+            if version >= LEADER_EPOCH_API {
+                self.leader_epoch.encode(src, version)?;
+            }
             Ok(())
         }
     }
@@ -289,7 +298,7 @@ mod tests {
     use fluvio_protocol::{Decoder, Encoder};
     use fluvio_protocol::api::Request;
     use fluvio_protocol::record::Batch;
-    use fluvio_protocol::record::{Record, RecordData, RecordSet};
+    use fluvio_protocol::record::{Record, RecordData, RecordSet, RawRecords};
     use fluvio_smartmodule::dataplane::smartmodule::{SmartModuleExtraParams, Lookback};
 
     use crate::produce::DefaultProduceRequest;
@@ -297,6 +306,7 @@ mod tests {
     use crate::produce::PartitionProduceData;
     use crate::isolation::Isolation;
     use crate::produce::request::PRODUCER_TRANSFORMATION_API_VERSION;
+    use crate::produce::request::LEADER_EPOCH_API;
     use crate::server::smartmodule::{
         SmartModuleInvocation, SmartModuleInvocationWasm, SmartModuleKind,
     };
@@ -355,6 +365,7 @@ mod tests {
                         .try_into()
                         .expect("compressed batch")],
                     },
+                    leader_epoch: 3,
                 }],
                 data: Default::default(),
             }],
@@ -373,6 +384,30 @@ mod tests {
         assert_eq!(bytes, cloned_bytes);
     }
 
+    #[test]
+    fn test_encode_decode_partition_leader_epoch() {
+        let partition = PartitionProduceData::<RecordSet<RawRecords>> {
+            partition_index: 1,
+            leader_epoch: 3,
+            ..Default::default()
+        };
+
+        let mut bytes = partition
+            .as_bytes(LEADER_EPOCH_API)
+            .expect("encoded partition");
+        let decoded: PartitionProduceData<RecordSet<RawRecords>> =
+            Decoder::decode_from(&mut bytes, LEADER_EPOCH_API).expect("decoded");
+        assert_eq!(decoded.leader_epoch, 3);
+
+        // older producers don't know leader epoch
+        let mut bytes = partition
+            .as_bytes(LEADER_EPOCH_API - 1)
+            .expect("encoded partition");
+        let decoded: PartitionProduceData<RecordSet<RawRecords>> =
+            Decoder::decode_from(&mut bytes, LEADER_EPOCH_API - 1).expect("decoded");
+        assert_eq!(decoded.leader_epoch, -1);
+    }
+
     #[test]
     fn test_encode_produce_request() {
         //given
@@ -24,8 +24,14 @@ use super::stream_fetch::FileStreamFetchRequest;
 use super::consumer_offset::{
     UpdateConsumerOffsetRequest, DeleteConsumerOffsetRequest, FetchConsumerOffsetsRequest,
 };
+use super::consumer_group::{JoinGroupRequest, HeartbeatRequest, LeaveGroupRequest};
+use super::transaction::{
+    InitTransactionRequest, AddTxnPartitionsRequest, EndTransactionRequest, WriteTxnMarkerRequest,
+};
 use super::update_offset::UpdateOffsetsRequest;
 use super::mirror::StartMirrorRequest;
+use super::snapshot::{SnapshotPartitionRequest, RestorePartitionRequest};
+use super::delete_records::DeleteRecordsRequest;
 
 #[allow(clippy::large_enum_variant)]
 /// Request to Spu Server
@@ -54,6 +60,26 @@ pub enum SpuServerRequest {
     FetchConsumerOffsetsRequest(RequestMessage<FetchConsumerOffsetsRequest>),
     #[fluvio(tag = 9)]
     StartMirrorRequest(RequestMessage<StartMirrorRequest>),
+    #[fluvio(tag = 10)]
+    JoinGroupRequest(RequestMessage<JoinGroupRequest>),
+    #[fluvio(tag = 11)]
+    HeartbeatRequest(RequestMessage<HeartbeatRequest>),
+    #[fluvio(tag = 12)]
+    LeaveGroupRequest(RequestMessage<LeaveGroupRequest>),
+    #[fluvio(tag = 13)]
+    InitTransactionRequest(RequestMessage<InitTransactionRequest>),
+    #[fluvio(tag = 14)]
+    AddTxnPartitionsRequest(RequestMessage<AddTxnPartitionsRequest>),
+    #[fluvio(tag = 15)]
+    EndTransactionRequest(RequestMessage<EndTransactionRequest>),
+    #[fluvio(tag = 16)]
+    WriteTxnMarkerRequest(RequestMessage<WriteTxnMarkerRequest>),
+    #[fluvio(tag = 17)]
+    SnapshotPartitionRequest(RequestMessage<SnapshotPartitionRequest>),
+    #[fluvio(tag = 18)]
+    RestorePartitionRequest(RequestMessage<RestorePartitionRequest>),
+    #[fluvio(tag = 19)]
+    DeleteRecordsRequest(RequestMessage<DeleteRecordsRequest>),
 }
 
 impl fmt::Display for SpuServerRequest {
@@ -69,6 +95,16 @@ impl fmt::Display for SpuServerRequest {
             Self::DeleteConsumerOffsetRequest(_) => write!(f, "DeleteConsumerOffsetRequest"),
             Self::FetchConsumerOffsetsRequest(_) => write!(f, "FetchConsumerOffsetsRequest"),
             Self::StartMirrorRequest(_) => write!(f, "StartMirrorRequest"),
+            Self::JoinGroupRequest(_) => write!(f, "JoinGroupRequest"),
+            Self::HeartbeatRequest(_) => write!(f, "HeartbeatRequest"),
+            Self::LeaveGroupRequest(_) => write!(f, "LeaveGroupRequest"),
+            Self::InitTransactionRequest(_) => write!(f, "InitTransactionRequest"),
+            Self::AddTxnPartitionsRequest(_) => write!(f, "AddTxnPartitionsRequest"),
+            Self::EndTransactionRequest(_) => write!(f, "EndTransactionRequest"),
+            Self::WriteTxnMarkerRequest(_) => write!(f, "WriteTxnMarkerRequest"),
+            Self::SnapshotPartitionRequest(_) => write!(f, "SnapshotPartitionRequest"),
+            Self::RestorePartitionRequest(_) => write!(f, "RestorePartitionRequest"),
+            Self::DeleteRecordsRequest(_) => write!(f, "DeleteRecordsRequest"),
         }
     }
 }
@@ -111,6 +147,30 @@ impl ApiMessage for SpuServerRequest {
                 api_decode!(Self, FetchConsumerOffsetsRequest, src, header)
             }
             SpuServerApiKey::StartMirror => api_decode!(Self, StartMirrorRequest, src, header),
+            SpuServerApiKey::JoinGroup => api_decode!(Self, JoinGroupRequest, src, header),
+            SpuServerApiKey::Heartbeat => api_decode!(Self, HeartbeatRequest, src, header),
+            SpuServerApiKey::LeaveGroup => api_decode!(Self, LeaveGroupRequest, src, header),
+            SpuServerApiKey::InitTransaction => {
+                api_decode!(Self, InitTransactionRequest, src, header)
+            }
+            SpuServerApiKey::AddTxnPartitions => {
+                api_decode!(Self, AddTxnPartitionsRequest, src, header)
+            }
+            SpuServerApiKey::EndTransaction => {
+                api_decode!(Self, EndTransactionRequest, src, header)
+            }
+            SpuServerApiKey::WriteTxnMarker => {
+                api_decode!(Self, WriteTxnMarkerRequest, src, header)
+            }
+            SpuServerApiKey::SnapshotPartition => {
+                api_decode!(Self, SnapshotPartitionRequest, src, header)
+            }
+            SpuServerApiKey::RestorePartition => {
+                api_decode!(Self, RestorePartitionRequest, src, header)
+            }
+            SpuServerApiKey::DeleteRecords => {
+                api_decode!(Self, DeleteRecordsRequest, src, header)
+            }
         }
     }
 }
@@ -23,6 +23,16 @@ pub enum SpuServerApiKey {
     UpdateConsumerOffset = 1006,
     DeleteConsumerOffset = 1007,
     FetchConsumerOffsets = 1008,
+    JoinGroup = 1009,
+    Heartbeat = 1010,
+    LeaveGroup = 1011,
+    InitTransaction = 1012,
+    AddTxnPartitions = 1013,
+    EndTransaction = 1014,
+    WriteTxnMarker = 1015,
+    SnapshotPartition = 1016,
+    RestorePartition = 1017,
+    DeleteRecords = 1018,
 
     StartMirror = 2000,
 }
@@ -0,0 +1,118 @@
+//!
+//! # Consumer Groups
+//!
+//! Members of a consumer group join the group coordinator and keep their membership alive
+//! with heartbeats. The coordinator assigns disjoint sets of partitions to the members and
+//! bumps the generation each time membership changes.
+//!
+//! Partition moved to other member is handed to it only after its previous owner has revoked it,
+//! which owner acknowledges by heartbeat with generation of its new assignment. Once all moved
+//! partitions are revoked, generation is bumped again and members get their full assignment.
+//!
+
+use fluvio_protocol::api::Request;
+use fluvio_protocol::{Encoder, Decoder};
+use fluvio_types::{PartitionCount, PartitionId};
+
+use crate::COMMON_VERSION;
+use crate::errors::ErrorCode;
+use super::SpuServerApiKey;
+
+pub type GenerationId = i32;
+
+/// Join consumer group. Empty `member_id` means new member, its id is generated by coordinator.
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct JoinGroupRequest {
+    pub group_id: String,
+    pub member_id: String,
+    pub topic: String,
+    /// ignored by coordinator, which uses partition count of topic known to SPU
+    pub partitions: PartitionCount,
+    pub session_timeout_ms: u32,
+}
+
+impl Request for JoinGroupRequest {
+    const API_KEY: u16 = SpuServerApiKey::JoinGroup as u16;
+    const DEFAULT_API_VERSION: i16 = COMMON_VERSION;
+    type Response = JoinGroupResponse;
+}
+
+impl JoinGroupRequest {
+    pub fn new(
+        group_id: impl Into<String>,
+        topic: impl Into<String>,
+        partitions: PartitionCount,
+        session_timeout_ms: u32,
+    ) -> Self {
+        Self {
+            group_id: group_id.into(),
+            member_id: String::new(),
+            topic: topic.into(),
+            partitions,
+            session_timeout_ms,
+        }
+    }
+}
+
+#[derive(Encoder, Decoder, Default, Debug)]
+pub struct JoinGroupResponse {
+    pub error_code: ErrorCode,
+    pub member_id: String,
+    pub assignment: GroupAssignment,
+}
+
+/// Keep membership alive and learn about rebalances
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct HeartbeatRequest {
+    pub group_id: String,
+    pub member_id: String,
+    /// generation of assignment member consumes, partitions of older assignments are revoked
+    pub generation: GenerationId,
+}
+
+impl Request for HeartbeatRequest {
+    const API_KEY: u16 = SpuServerApiKey::Heartbeat as u16;
+    const DEFAULT_API_VERSION: i16 = COMMON_VERSION;
+    type Response = HeartbeatResponse;
+}
+
+/// If the generation differs from the one known by the member, group has been rebalanced
+/// and `assignment` contains new partitions of the member.
+#[derive(Encoder, Decoder, Default, Debug)]
+pub struct HeartbeatResponse {
+    pub error_code: ErrorCode,
+    pub assignment: GroupAssignment,
+}
+
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct LeaveGroupRequest {
+    pub group_id: String,
+    pub member_id: String,
+}
+
+impl Request for LeaveGroupRequest {
+    const API_KEY: u16 = SpuServerApiKey::LeaveGroup as u16;
+    const DEFAULT_API_VERSION: i16 = COMMON_VERSION;
+    type Response = LeaveGroupResponse;
+}
+
+#[derive(Encoder, Decoder, Default, Debug)]
+pub struct LeaveGroupResponse {
+    pub error_code: ErrorCode,
+}
+
+/// Partitions assigned to a member for the given generation
+#[derive(Encoder, Decoder, Default, Debug, Clone, PartialEq, Eq)]
+pub struct GroupAssignment {
+    pub generation: GenerationId,
+    pub partitions: Vec<PartitionId>,
+}
+
+/// Consumer group member fetching partition, fetch is rejected unless generation is current
+/// and partition is assigned to the member
+#[derive(Encoder, Decoder, Default, Debug, Clone, PartialEq, Eq)]
+pub struct GroupMembership {
+    pub group_id: String,
+    pub member_id: String,
+    pub generation: GenerationId,
+}
@@ -0,0 +1,39 @@
+//!
+//! # Delete Records
+//!
+//! Deletes records of a partition before an offset by advancing its log start offset,
+//! ahead of retention. Leader deletes its records and followers and mirror home follow it.
+//!
+//! Deletion is logical: reads before the log start offset fail with `OffsetEvicted`, and
+//! segments whose records all precede it are removed. Records before it in the remaining
+//! segment stay on disk until that segment is removed by retention.
+//!
+
+use fluvio_protocol::api::Request;
+use fluvio_protocol::record::{Offset, ReplicaKey};
+use fluvio_protocol::{Encoder, Decoder};
+
+use crate::COMMON_VERSION;
+use crate::errors::ErrorCode;
+use super::SpuServerApiKey;
+
+/// Delete records of partition before `offset`.
+/// Only committed records are deleted, offset is capped at high watermark
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct DeleteRecordsRequest {
+    pub replica: ReplicaKey,
+    pub offset: Offset,
+}
+
+impl Request for DeleteRecordsRequest {
+    const API_KEY: u16 = SpuServerApiKey::DeleteRecords as u16;
+    const DEFAULT_API_VERSION: i16 = COMMON_VERSION;
+    type Response = DeleteRecordsResponse;
+}
+
+#[derive(Encoder, Decoder, Default, Debug)]
+pub struct DeleteRecordsResponse {
+    pub error_code: ErrorCode,
+    /// log start offset of partition after delete
+    pub log_start_offset: Offset,
+}
@@ -7,6 +7,10 @@ pub mod fetch_offset;
 pub mod stream_fetch;
 pub mod update_offset;
 pub mod consumer_offset;
+pub mod consumer_group;
+pub mod transaction;
+pub mod snapshot;
+pub mod delete_records;
 pub mod mirror;
 
 pub use self::api_key::*;
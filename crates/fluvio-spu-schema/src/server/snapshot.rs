@@ -0,0 +1,62 @@
+//!
+//! # Partition Snapshots
+//!
+//! Leader of a partition writes its log up to an offset to a snapshot archive in snapshot
+//! directory of the SPU. Archive can be copied to another cluster and restored into an empty
+//! partition led by SPU which has it in its snapshot directory.
+//!
+
+use fluvio_protocol::api::Request;
+use fluvio_protocol::record::{Offset, ReplicaKey};
+use fluvio_protocol::{Encoder, Decoder};
+
+use crate::COMMON_VERSION;
+use crate::errors::ErrorCode;
+use super::SpuServerApiKey;
+
+/// Write batches of partition before `end_offset` to snapshot `name`.
+/// High watermark is used if end offset is not set
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct SnapshotPartitionRequest {
+    pub replica: ReplicaKey,
+    pub name: String,
+    pub end_offset: Option<Offset>,
+}
+
+impl Request for SnapshotPartitionRequest {
+    const API_KEY: u16 = SpuServerApiKey::SnapshotPartition as u16;
+    const DEFAULT_API_VERSION: i16 = COMMON_VERSION;
+    type Response = SnapshotPartitionResponse;
+}
+
+#[derive(Encoder, Decoder, Default, Debug)]
+pub struct SnapshotPartitionResponse {
+    pub error_code: ErrorCode,
+    /// offset of first record in snapshot
+    pub start_offset: Offset,
+    /// offset after last record in snapshot
+    pub end_offset: Offset,
+    pub batches: u32,
+    pub bytes: u64,
+}
+
+/// Write batches of snapshot `name` to empty partition
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct RestorePartitionRequest {
+    pub replica: ReplicaKey,
+    pub name: String,
+}
+
+impl Request for RestorePartitionRequest {
+    const API_KEY: u16 = SpuServerApiKey::RestorePartition as u16;
+    const DEFAULT_API_VERSION: i16 = COMMON_VERSION;
+    type Response = RestorePartitionResponse;
+}
+
+#[derive(Encoder, Decoder, Default, Debug)]
+pub struct RestorePartitionResponse {
+    pub error_code: ErrorCode,
+    /// end offset of partition after restore
+    pub leo: Offset,
+    pub batches: u32,
+}
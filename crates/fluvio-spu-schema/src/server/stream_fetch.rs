@@ -12,6 +12,7 @@ use derive_builder::Builder;
 use fluvio_protocol::record::RawRecords;
 use fluvio_protocol::{Encoder, Decoder};
 use fluvio_protocol::api::Request;
+use fluvio_protocol::link::capabilities::Capability;
 use fluvio_protocol::record::RecordSet;
 use fluvio_smartmodule::dataplane::smartmodule::SmartModuleExtraParams;
 use fluvio_types::{PartitionId, defaults::FLUVIO_CLIENT_MAX_FETCH_BYTES};
@@ -24,6 +25,7 @@ pub type DefaultStreamFetchResponse = StreamFetchResponse<RecordSet<RawRecords>>
 pub type DefaultStreamFetchRequest = StreamFetchRequest<RecordSet<RawRecords>>;
 
 use super::SpuServerApiKey;
+use super::consumer_group::GroupMembership;
 #[allow(deprecated)]
 use super::smartmodule::{LegacySmartModulePayload, SmartModuleInvocation};
 
@@ -54,6 +56,25 @@ pub const SMARTMODULE_TIMESTAMP: i16 = 22;
 
 pub const OFFSET_MANAGEMENT_API: i16 = 23;
 
+pub const READ_FROM_FOLLOWER_API: i16 = 24;
+
+pub const DEAD_LETTER_API: i16 = 25;
+
+pub const BROKER_TIMESTAMPS_API: i16 = 27;
+
+pub const LEADER_EPOCH_API: i16 = 28;
+
+pub const CONSUMER_GROUP_API: i16 = 29;
+
+/// capabilities implied by stream fetch version for SPUs which don't advertise them
+pub const STREAM_FETCH_CAPABILITIES: &[(Capability, i16)] = &[
+    (Capability::SmartModuleChain, CHAIN_SMARTMODULE_API),
+    (Capability::OffsetManagement, OFFSET_MANAGEMENT_API),
+    (Capability::ReadFromFollower, READ_FROM_FOLLOWER_API),
+    (Capability::DeadLetterTopic, DEAD_LETTER_API),
+    (Capability::BrokerTimestamps, BROKER_TIMESTAMPS_API),
+];
+
 /// Fetch records continuously
 /// Output will be send back as stream
 #[allow(deprecated)]
@@ -90,6 +111,29 @@ pub struct StreamFetchRequest<R> {
     #[builder(default)]
     #[fluvio(min_version = 23)]
     pub consumer_id: Option<String>,
+    /// If set, request can be served by a follower replica which was in sync
+    /// with the leader within this many milliseconds
+    #[builder(default)]
+    #[fluvio(min_version = 24)]
+    pub max_staleness_ms: Option<u32>,
+    /// If set, records failing in SmartModule are written to this topic
+    /// instead of ending the stream with error
+    #[builder(default)]
+    #[fluvio(min_version = 25)]
+    pub dead_letter_topic: Option<String>,
+    /// If set, responses carry broker append, commit and fetch times of their first batch
+    #[builder(default)]
+    #[fluvio(min_version = 27)]
+    pub broker_timestamps: bool,
+    /// If set, request is rejected unless partition leader is in this epoch
+    #[builder(default)]
+    #[fluvio(min_version = 28)]
+    pub leader_epoch: Option<i32>,
+    /// If set, request is rejected unless consumer group member is in current generation
+    /// and partition is assigned to it
+    #[builder(default)]
+    #[fluvio(min_version = 29)]
+    pub consumer_group: Option<GroupMembership>,
     #[builder(setter(skip))]
     data: PhantomData<R>,
 }
@@ -222,7 +266,7 @@ mod tests {
             0x00, 0x03, 0x6f, 0x6e, 0x65, 0x00, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00,
             0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x01, 0x00,
             0x00, 0x00, 0x04, 0xde, 0xad, 0xbe, 0xef, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00,
-            0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
         ];
         assert_eq!(dest, expected);
     }
@@ -251,7 +295,7 @@ mod tests {
             0x00, 0x03, 0x6f, 0x6e, 0x65, 0x00, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00,
             0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x01, 0x00,
             0x00, 0x00, 0x04, 0xde, 0xad, 0xbe, 0xef, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00,
-            0x00, 0x00, 0x00, 0x00, 0x01, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
         ];
         assert_eq!(dest, expected);
     }
@@ -289,7 +333,7 @@ mod tests {
             0x00, 0x03, 0x6f, 0x6e, 0x65, 0x00, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00,
             0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x01, 0x00,
             0x00, 0x00, 0x04, 0xde, 0xad, 0xbe, 0xef, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00,
-            0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
         ];
         let mut value = DefaultStreamFetchRequest::default();
         value
@@ -320,7 +364,7 @@ mod tests {
             0x00, 0x03, 0x6f, 0x6e, 0x65, 0x00, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00,
             0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x01, 0x00,
             0x00, 0x00, 0x04, 0xde, 0xad, 0xbe, 0xef, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00,
-            0x00, 0x00, 0x00, 0x00, 0x01, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
         ];
         let mut value = DefaultStreamFetchRequest::default();
         value
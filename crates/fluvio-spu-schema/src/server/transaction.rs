@@ -0,0 +1,96 @@
+//!
+//! # Transactions
+//!
+//! Transactional producer gets producer id and epoch from the transaction coordinator,
+//! registers partitions it writes to and then asks the coordinator to commit or abort.
+//! The coordinator ends the transaction by writing marker to each registered partition.
+//!
+
+use fluvio_protocol::api::Request;
+use fluvio_protocol::record::ReplicaKey;
+use fluvio_protocol::{Encoder, Decoder};
+
+use crate::COMMON_VERSION;
+use crate::errors::ErrorCode;
+use super::SpuServerApiKey;
+
+/// Get producer id and new epoch for transactional id.
+/// Transaction left in progress by previous epoch is aborted.
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct InitTransactionRequest {
+    pub transactional_id: String,
+    pub timeout_ms: u32,
+}
+
+impl Request for InitTransactionRequest {
+    const API_KEY: u16 = SpuServerApiKey::InitTransaction as u16;
+    const DEFAULT_API_VERSION: i16 = COMMON_VERSION;
+    type Response = InitTransactionResponse;
+}
+
+#[derive(Encoder, Decoder, Default, Debug)]
+pub struct InitTransactionResponse {
+    pub error_code: ErrorCode,
+    pub producer_id: i64,
+    pub producer_epoch: i16,
+}
+
+/// Register partitions before writing to them, starts transaction if none is in progress
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct AddTxnPartitionsRequest {
+    pub transactional_id: String,
+    pub producer_id: i64,
+    pub producer_epoch: i16,
+    pub partitions: Vec<ReplicaKey>,
+}
+
+impl Request for AddTxnPartitionsRequest {
+    const API_KEY: u16 = SpuServerApiKey::AddTxnPartitions as u16;
+    const DEFAULT_API_VERSION: i16 = COMMON_VERSION;
+    type Response = AddTxnPartitionsResponse;
+}
+
+#[derive(Encoder, Decoder, Default, Debug)]
+pub struct AddTxnPartitionsResponse {
+    pub error_code: ErrorCode,
+}
+
+/// Commit or abort transaction in progress
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct EndTransactionRequest {
+    pub transactional_id: String,
+    pub producer_id: i64,
+    pub producer_epoch: i16,
+    pub commit: bool,
+}
+
+impl Request for EndTransactionRequest {
+    const API_KEY: u16 = SpuServerApiKey::EndTransaction as u16;
+    const DEFAULT_API_VERSION: i16 = COMMON_VERSION;
+    type Response = EndTransactionResponse;
+}
+
+#[derive(Encoder, Decoder, Default, Debug)]
+pub struct EndTransactionResponse {
+    pub error_code: ErrorCode,
+}
+
+/// Sent by the coordinator to leader of the partition to write transaction marker
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct WriteTxnMarkerRequest {
+    pub replica: ReplicaKey,
+    pub producer_id: i64,
+    pub producer_epoch: i16,
+    pub commit: bool,
+}
+
+impl Request for WriteTxnMarkerRequest {
+    const API_KEY: u16 = SpuServerApiKey::WriteTxnMarker as u16;
+    const DEFAULT_API_VERSION: i16 = COMMON_VERSION;
+    type Response = WriteTxnMarkerResponse;
+}
+
+#[derive(Encoder, Decoder, Default, Debug)]
+pub struct WriteTxnMarkerResponse {
+    pub error_code: ErrorCode,
+}
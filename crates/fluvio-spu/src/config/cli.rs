@@ -5,6 +5,7 @@
 //! system parameters.
 //!
 use std::io::Error as IoError;
+use std::path::PathBuf;
 use std::process;
 use std::io::ErrorKind;
 
@@ -15,8 +16,19 @@ use clap::Parser;
 use fluvio_types::print_cli_err;
 use fluvio_types::SpuId;
 use fluvio_future::openssl::TlsAcceptor;
+use fluvio_types::secret::SecretRef;
+use fluvio_auth::token::{SharedTokenValidator, TokenAuthOpt};
+use fluvio_auth::tls::{ReloadableTlsAcceptor, SharedTlsAcceptor};
+use fluvio_service::rate_limit::RateLimitConfig;
 
-use super::SpuConfig;
+use super::{ClientQuota, SpuConfig};
+
+/// configuration, validator of client tokens and TLS acceptor with proxy address
+pub type SpuStartConfig = (
+    SpuConfig,
+    Option<SharedTokenValidator>,
+    Option<(SharedTlsAcceptor, String)>,
+);
 
 /// cli options
 #[derive(Debug, Default, Parser)]
@@ -50,6 +62,30 @@ pub struct SpuOpt {
     #[arg(long, value_name = "integer", env = "FLV_LOG_INDEX_MAX_INTERVAL_BYTES")]
     pub index_max_interval_bytes: Option<u32>,
 
+    /// seconds between background checksum scrubs of stored segments, 0 disables
+    #[arg(long, value_name = "integer", env = "FLV_LOG_SCRUB_INTERVAL_SECS")]
+    pub scrub_interval_secs: Option<u64>,
+
+    /// bytes of log tail of each leader replica to preload into page cache on startup, 0 disables
+    #[arg(long, value_name = "integer", env = "FLV_LOG_PRELOAD_BYTES")]
+    pub preload_bytes: Option<u64>,
+
+    /// directory with keys of encrypted topics, one hex encoded key per file named by key id
+    #[arg(long, value_name = "dir", env = "FLV_ENCRYPTION_KEY_DIR")]
+    pub encryption_key_dir: Option<String>,
+
+    /// directory of partition snapshot archives
+    #[arg(long, value_name = "dir", env = "FLV_SNAPSHOT_DIR")]
+    pub snapshot_dir: Option<String>,
+
+    /// on startup, truncate torn writes of all segments and log a recovery report of each replica
+    #[arg(long, env = "FLV_LOG_RECOVERY_AUDIT")]
+    pub recovery_audit: bool,
+
+    /// for topics with batch durability, fsync concurrent produces to same partition together
+    #[arg(long, env = "FLV_LOG_GROUP_COMMIT")]
+    pub group_commit: bool,
+
     /// max bytes to transfer between leader and follower
     #[arg(
         long,
@@ -59,6 +95,10 @@ pub struct SpuOpt {
     )]
     pub peer_max_bytes: u32,
 
+    /// max bytes per second leader sends to each follower, unlimited if not set
+    #[arg(long, value_name = "integer", env = "FLV_REPLICATION_THROTTLE")]
+    pub replication_throttle: Option<u64>,
+
     #[arg(
         long,
         value_name = "integer",
@@ -66,23 +106,102 @@ pub struct SpuOpt {
     )]
     pub smart_engine_max_memory: Option<usize>,
 
+    /// max bytes per second a client can produce, unlimited if not set
+    #[arg(long, value_name = "integer", env = "FLV_CLIENT_PRODUCE_QUOTA")]
+    pub client_produce_quota: Option<u64>,
+
+    /// max bytes per second a client can fetch, unlimited if not set
+    #[arg(long, value_name = "integer", env = "FLV_CLIENT_FETCH_QUOTA")]
+    pub client_fetch_quota: Option<u64>,
+
+    /// max produce and fetch requests per second of a client, unlimited if not set
+    #[arg(long, value_name = "integer", env = "FLV_CLIENT_REQUEST_QUOTA")]
+    pub client_request_quota: Option<u64>,
+
+    /// quota of specific client, overrides default quota.
+    /// Format is <client-id>=<produce bytes>,<fetch bytes>,<requests>, empty value is unlimited
+    #[arg(long, value_name = "client quota", value_parser = parse_client_quota)]
+    pub client_quota: Vec<(String, ClientQuota)>,
+
+    /// max bytes of produce requests, fetch responses and SmartModules held in memory,
+    /// unlimited if not set
+    #[arg(long, value_name = "integer", env = "FLV_SPU_MAX_MEMORY_BYTES")]
+    pub max_memory_bytes: Option<u64>,
+
+    /// days after which offsets of inactive consumers are deleted, kept forever if not set
+    #[arg(
+        long,
+        value_name = "integer",
+        env = "FLV_CONSUMER_OFFSET_RETENTION_DAYS"
+    )]
+    pub consumer_offset_retention_days: Option<u64>,
+
+    /// longest time in milliseconds fetch request waits for min bytes, 0 disables waiting
+    #[arg(long, value_name = "integer", env = "FLV_FETCH_MAX_WAIT_MS")]
+    pub fetch_max_wait_ms: Option<u64>,
+
+    /// max produce and fetch requests processed concurrently, unlimited if not set
+    #[arg(long, value_name = "integer", env = "FLV_SPU_MAX_CONCURRENT_REQUESTS")]
+    pub max_concurrent_requests: Option<u64>,
+
+    /// max requests waiting for concurrency limit, further requests are rejected as busy
+    #[arg(long, value_name = "integer", env = "FLV_SPU_MAX_QUEUED_REQUESTS")]
+    pub max_queued_requests: Option<u64>,
+
+    /// JSON file overriding fetch wait, client quotas, replication throttle, mirror backoff
+    /// and log level at runtime, polled for changes
+    #[arg(long, value_name = "path", env = "FLV_SPU_DYNAMIC_CONFIG")]
+    pub dynamic_config: Option<PathBuf>,
+
+    /// max requests per second of a client connection, unlimited if not set
+    #[arg(
+        long,
+        value_name = "integer",
+        env = "FLV_SPU_MAX_REQUESTS_PER_CONNECTION"
+    )]
+    pub max_requests_per_connection: Option<u32>,
+
+    /// max requests per second of all connections of a principal, unlimited if not set
+    #[arg(
+        long,
+        value_name = "integer",
+        env = "FLV_SPU_MAX_REQUESTS_PER_PRINCIPAL"
+    )]
+    pub max_requests_per_principal: Option<u32>,
+
+    /// longest time in seconds to drain requests and sync storage on shutdown
+    #[arg(long, value_name = "integer", env = "FLV_SPU_SHUTDOWN_TIMEOUT_SECS")]
+    pub shutdown_timeout_secs: Option<u64>,
+
+    /// address for CPU and heap profiling endpoint, e.g. "127.0.0.1:9006", disabled if not set.
+    /// Requires build with profiling feature
+    #[arg(long, value_name = "host:port", env = "FLV_SPU_BIND_PROFILING")]
+    pub bind_profiling: Option<String>,
+
+    #[command(flatten)]
+    pub token_auth: TokenAuthOpt,
+
+    /// secret with bearer token this SPU presents to other SPUs, e.g. `file:/var/run/fluvio/token`.
+    /// Required if SPUs authenticate clients with tokens
+    #[arg(long, value_name = "secret", env = "FLV_SPU_PEER_AUTH_TOKEN")]
+    pub peer_auth_token: Option<SecretRef>,
+
     #[clap(flatten)]
     tls: TlsConfig,
 }
 
 impl SpuOpt {
     /// Validate SPU (Streaming Processing Unit) cli inputs and generate SpuConfig
-    fn get_spu_config(self) -> Result<(SpuConfig, Option<(TlsAcceptor, String)>), IoError> {
+    fn get_spu_config(self) -> Result<SpuStartConfig, IoError> {
         let tls_acceptor = self.try_build_tls_acceptor()?;
+        let token_validator = self.token_auth.validator()?;
         let (spu_config, tls_addr_opt) = self.as_spu_config()?;
         let tls_config = tls_acceptor.map(|it| (it, tls_addr_opt.unwrap()));
-        Ok((spu_config, tls_config))
+        Ok((spu_config, token_validator, tls_config))
     }
 
     #[allow(clippy::wrong_self_convention)]
     fn as_spu_config(self) -> Result<(SpuConfig, Option<String>), IoError> {
-        use std::path::PathBuf;
-
         let mut config = SpuConfig {
             id: match self.id {
                 Some(id) => id,
@@ -119,6 +238,36 @@ impl SpuOpt {
             config.log.index_max_interval_bytes = index_max_interval_bytes;
         }
 
+        if let Some(scrub_interval_secs) = self.scrub_interval_secs {
+            info!("overriding scrub interval: {}", scrub_interval_secs);
+            config.log.scrub_interval_secs = scrub_interval_secs;
+        }
+
+        if let Some(preload_bytes) = self.preload_bytes {
+            info!("overriding preload bytes: {}", preload_bytes);
+            config.log.preload_bytes = preload_bytes;
+        }
+
+        if let Some(encryption_key_dir) = self.encryption_key_dir {
+            info!("overriding encryption key dir: {}", encryption_key_dir);
+            config.log.encryption_key_dir = PathBuf::from(encryption_key_dir);
+        }
+
+        if let Some(snapshot_dir) = self.snapshot_dir {
+            info!("overriding snapshot dir: {}", snapshot_dir);
+            config.log.snapshot_dir = PathBuf::from(snapshot_dir);
+        }
+
+        if self.recovery_audit {
+            info!("enabling recovery audit");
+            config.log.recovery_audit = true;
+        }
+
+        if self.group_commit {
+            info!("enabling group commit");
+            config.log.group_commit = true;
+        }
+
         if let Some(public_addr) = self.bind_public {
             info!("overriding public addr: {}", public_addr);
             config.public_endpoint = public_addr;
@@ -145,6 +294,11 @@ impl SpuOpt {
 
         config.peer_max_bytes = self.peer_max_bytes;
 
+        if let Some(bytes_per_sec) = self.replication_throttle {
+            info!(bytes_per_sec, "throttling replication to followers");
+            config.replication.follower_throttle_bytes_per_sec = Some(bytes_per_sec);
+        }
+
         if let Some(smart_engine_max_memory) = self.smart_engine_max_memory {
             info!(
                 "overriding smart engine max memory: {}",
@@ -153,43 +307,91 @@ impl SpuOpt {
             config.smart_engine.store_max_memory = smart_engine_max_memory;
         }
 
+        config.quota.default = ClientQuota {
+            produce_bytes_per_sec: self.client_produce_quota,
+            fetch_bytes_per_sec: self.client_fetch_quota,
+            requests_per_sec: self.client_request_quota,
+        };
+        if !config.quota.default.is_unlimited() {
+            info!(quota = ?config.quota.default, "using client quota");
+        }
+        for (client_id, quota) in self.client_quota {
+            info!(client_id, ?quota, "overriding client quota");
+            config.quota.overrides.insert(client_id, quota);
+        }
+
+        if let Some(max_bytes) = self.max_memory_bytes {
+            info!(max_bytes, "limiting memory of buffers");
+            config.memory.max_bytes = Some(max_bytes);
+        }
+
+        if let Some(days) = self.consumer_offset_retention_days {
+            info!(days, "overriding consumer offset retention");
+            config.consumer_offset.retention_secs = days * 24 * 3600;
+        }
+
+        if let Some(max_wait_ms) = self.fetch_max_wait_ms {
+            info!(max_wait_ms, "overriding fetch max wait");
+            config.fetch.max_wait_ms = max_wait_ms;
+        }
+
+        if let Some(max_concurrent_requests) = self.max_concurrent_requests {
+            info!(max_concurrent_requests, "limiting concurrent requests");
+            config.admission.max_concurrent_requests = Some(max_concurrent_requests);
+        }
+
+        if let Some(max_queued_requests) = self.max_queued_requests {
+            info!(max_queued_requests, "overriding max queued requests");
+            config.admission.max_queued_requests = max_queued_requests;
+        }
+
+        config.secrets = self.token_auth.secrets;
+        config.peer_auth_token = self.peer_auth_token;
+
+        config.rate_limit = RateLimitConfig {
+            connection_requests_per_sec: self.max_requests_per_connection,
+            principal_requests_per_sec: self.max_requests_per_principal,
+        };
+        if !config.rate_limit.is_unlimited() {
+            info!(rate_limit = ?config.rate_limit, "limiting client request rate");
+        }
+
+        if let Some(shutdown_timeout_secs) = self.shutdown_timeout_secs {
+            info!(shutdown_timeout_secs, "overriding shutdown timeout");
+            config.shutdown_timeout_secs = shutdown_timeout_secs;
+        }
+
+        config.profiling_endpoint = self.bind_profiling;
+
+        if let Some(path) = self.dynamic_config {
+            info!(path = %path.display(), "using dynamic config");
+            config.dynamic_config = Some(path);
+        }
+
         Ok((config, tls_port))
     }
 
-    fn try_build_tls_acceptor(&self) -> Result<Option<TlsAcceptor>, IoError> {
-        let tls_config = &self.tls;
-        if !tls_config.tls {
+    fn try_build_tls_acceptor(&self) -> Result<Option<SharedTlsAcceptor>, IoError> {
+        if !self.tls.tls {
             return Ok(None);
         }
 
-        let server_crt_path = tls_config
+        let tls_config = &self.tls;
+        let mut files: Vec<PathBuf> = tls_config
             .server_cert
-            .as_ref()
-            .ok_or_else(|| IoError::new(ErrorKind::NotFound, "missing server cert"))?;
-        let server_key_path = tls_config
-            .server_key
-            .as_ref()
-            .ok_or_else(|| IoError::new(ErrorKind::NotFound, "missing server key"))?;
-
-        let builder = (if tls_config.enable_client_cert {
-            let ca_path = tls_config
-                .ca_cert
-                .as_ref()
-                .ok_or_else(|| IoError::new(ErrorKind::NotFound, "missing ca cert"))?;
-            TlsAcceptor::builder()
-                .map_err(|err| err.into_io_error())?
-                .with_ca_from_pem_file(ca_path)
-                .map_err(|err| err.into_io_error())?
-        } else {
-            TlsAcceptor::builder().map_err(|err| err.into_io_error())?
-        })
-        .with_certifiate_and_key_from_pem_files(server_crt_path, server_key_path)
-        .map_err(|err| err.into_io_error())?;
+            .iter()
+            .chain(tls_config.server_key.iter())
+            .map(PathBuf::from)
+            .collect();
+        if tls_config.enable_client_cert {
+            files.extend(tls_config.ca_cert.iter().map(PathBuf::from));
+        }
 
-        Ok(Some(builder.build()))
+        let tls_config = tls_config.clone();
+        ReloadableTlsAcceptor::shared(files, move || tls_config.try_build_tls_acceptor()).map(Some)
     }
 
-    pub fn process_spu_cli_or_exit(self) -> (SpuConfig, Option<(TlsAcceptor, String)>) {
+    pub fn process_spu_cli_or_exit(self) -> SpuStartConfig {
         match self.get_spu_config() {
             Err(err) => {
                 print_cli_err!(err);
@@ -200,6 +402,38 @@ impl SpuOpt {
     }
 }
 
+/// parse client quota in form of <client-id>=<produce bytes>,<fetch bytes>,<requests>
+fn parse_client_quota(value: &str) -> Result<(String, ClientQuota), String> {
+    let (client_id, limits) = value
+        .split_once('=')
+        .ok_or_else(|| format!("client quota must be <client-id>=<limits>: {value}"))?;
+    let limits = limits
+        .split(',')
+        .map(|limit| {
+            let limit = limit.trim();
+            if limit.is_empty() {
+                Ok(None)
+            } else {
+                limit
+                    .parse()
+                    .map(Some)
+                    .map_err(|err| format!("invalid quota {limit}: {err}"))
+            }
+        })
+        .collect::<Result<Vec<Option<u64>>, String>>()?;
+    let [produce_bytes_per_sec, fetch_bytes_per_sec, requests_per_sec] = limits[..] else {
+        return Err(format!("client quota must have 3 limits: {value}"));
+    };
+    Ok((
+        client_id.to_owned(),
+        ClientQuota {
+            produce_bytes_per_sec,
+            fetch_bytes_per_sec,
+            requests_per_sec,
+        },
+    ))
+}
+
 /// find spu id from env, if not found, return error
 fn find_spu_id_from_env() -> Result<SpuId, IoError> {
     use std::env;
@@ -249,7 +483,7 @@ fn find_spu_id_from_env() -> Result<SpuId, IoError> {
 }
 
 /// same in the SC
-#[derive(Debug, Parser, Default)]
+#[derive(Debug, Parser, Default, Clone)]
 struct TlsConfig {
     /// enable tls
     #[arg(long)]
@@ -272,3 +506,33 @@ struct TlsConfig {
     /// TLS: address of non tls public service, required
     pub bind_non_tls_public: Option<String>,
 }
+
+impl TlsConfig {
+    fn try_build_tls_acceptor(&self) -> Result<TlsAcceptor, IoError> {
+        let server_crt_path = self
+            .server_cert
+            .as_ref()
+            .ok_or_else(|| IoError::new(ErrorKind::NotFound, "missing server cert"))?;
+        let server_key_path = self
+            .server_key
+            .as_ref()
+            .ok_or_else(|| IoError::new(ErrorKind::NotFound, "missing server key"))?;
+
+        let builder = (if self.enable_client_cert {
+            let ca_path = self
+                .ca_cert
+                .as_ref()
+                .ok_or_else(|| IoError::new(ErrorKind::NotFound, "missing ca cert"))?;
+            TlsAcceptor::builder()
+                .map_err(|err| err.into_io_error())?
+                .with_ca_from_pem_file(ca_path)
+                .map_err(|err| err.into_io_error())?
+        } else {
+            TlsAcceptor::builder().map_err(|err| err.into_io_error())?
+        })
+        .with_certifiate_and_key_from_pem_files(server_crt_path, server_key_path)
+        .map_err(|err| err.into_io_error())?;
+
+        Ok(builder.build())
+    }
+}
@@ -3,4 +3,6 @@ mod spu_config;
 
 pub use self::cli::SpuOpt;
 
-pub use self::spu_config::{SpuConfig, ReplicationConfig};
+pub use self::spu_config::{
+    SpuConfig, ReplicationConfig, ClientQuota, QuotaConfig, MemoryConfig, AdmissionConfig,
+};
@@ -8,6 +8,7 @@
 //!     3) custom configuration or default configuration (from file)
 //!
 
+use std::collections::BTreeMap;
 use std::env;
 use std::path::PathBuf;
 
@@ -20,6 +21,15 @@ use fluvio_types::defaults::SPU_LOG_SIZE;
 use fluvio_types::defaults::SPU_LOG_INDEX_MAX_BYTES;
 use fluvio_types::defaults::SPU_LOG_INDEX_MAX_INTERVAL_BYTES;
 use fluvio_types::defaults::SPU_LOG_SEGMENT_MAX_BYTES;
+use fluvio_types::defaults::SPU_LOG_SCRUB_INTERVAL_SECS;
+use fluvio_types::defaults::SPU_LOG_PRELOAD_BYTES;
+use fluvio_types::defaults::SPU_CONSUMER_OFFSET_RETENTION_SECS;
+use fluvio_types::defaults::SPU_CONSUMER_OFFSET_EXPIRY_INTERVAL_SECS;
+use fluvio_types::defaults::SPU_FETCH_MAX_WAIT_MS;
+use fluvio_types::defaults::SPU_MAX_QUEUED_REQUESTS;
+use fluvio_types::defaults::SPU_ENCRYPTION_KEY_DIR;
+use fluvio_types::defaults::SPU_SNAPSHOT_DIR;
+use fluvio_types::defaults::SPU_SHUTDOWN_TIMEOUT_SECS;
 use fluvio_types::defaults::SPU_RETRY_SC_TIMEOUT_MS;
 use fluvio_types::defaults::SPU_SMARTENGINE_STORE_MAX_BYTES;
 
@@ -29,7 +39,11 @@ use fluvio_types::defaults::SPU_MIN_IN_SYNC_REPLICAS;
 use fluvio_types::defaults::FLV_LOG_BASE_DIR;
 use fluvio_types::defaults::FLV_LOG_SIZE;
 use fluvio_types::SpuId;
+use fluvio_service::rate_limit::RateLimitConfig;
+use fluvio_auth::secret::SecretOpt;
+use fluvio_types::secret::SecretRef;
 use fluvio_storage::config::ReplicaConfig;
+use fluvio_storage::backend::{BackendReplicaConfig, StorageBackends};
 use fluvio_types::defaults::{
     STORAGE_FLUSH_IDLE_MSEC, STORAGE_FLUSH_WRITE_COUNT, STORAGE_MAX_BATCH_SIZE,
 };
@@ -37,12 +51,15 @@ use fluvio_types::defaults::{
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub struct ReplicationConfig {
     pub min_in_sync_replicas: u16,
+    /// max bytes of records sent to each follower per second, unlimited if not set
+    pub follower_throttle_bytes_per_sec: Option<u64>,
 }
 
 impl Default for ReplicationConfig {
     fn default() -> Self {
         Self {
             min_in_sync_replicas: SPU_MIN_IN_SYNC_REPLICAS,
+            follower_throttle_bytes_per_sec: None,
         }
     }
 }
@@ -57,6 +74,18 @@ pub struct Log {
     pub flush_write_count: u32,
     pub flush_idle_msec: u32,
     pub max_batch_size: u32,
+    /// interval between storage scrubs, 0 disables scrubber
+    pub scrub_interval_secs: u64,
+    /// bytes of log tail loaded into page cache when leader replica is loaded, 0 disables preloading
+    pub preload_bytes: u64,
+    /// directory with keys of encrypted topics, one hex encoded key per file named by key id
+    pub encryption_key_dir: PathBuf,
+    /// directory of partition snapshot archives
+    pub snapshot_dir: PathBuf,
+    /// truncate torn writes of all segments on load and report them
+    pub recovery_audit: bool,
+    /// sync concurrent writes to same partition together
+    pub group_commit: bool,
 }
 
 impl Default for Log {
@@ -72,6 +101,46 @@ impl Default for Log {
             flush_write_count: STORAGE_FLUSH_WRITE_COUNT,
             flush_idle_msec: STORAGE_FLUSH_IDLE_MSEC,
             max_batch_size: STORAGE_MAX_BATCH_SIZE,
+            scrub_interval_secs: SPU_LOG_SCRUB_INTERVAL_SECS,
+            preload_bytes: SPU_LOG_PRELOAD_BYTES,
+            encryption_key_dir: PathBuf::from(SPU_ENCRYPTION_KEY_DIR),
+            snapshot_dir: PathBuf::from(SPU_SNAPSHOT_DIR),
+            recovery_audit: false,
+            group_commit: false,
+        }
+    }
+}
+
+/// retention of stored consumer offsets
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct ConsumerOffsetConfig {
+    /// offsets not updated for this many seconds are deleted, 0 keeps offsets forever
+    pub retention_secs: u64,
+    /// interval between checks for expired offsets
+    pub expiry_interval_secs: u64,
+}
+
+impl Default for ConsumerOffsetConfig {
+    fn default() -> Self {
+        Self {
+            retention_secs: SPU_CONSUMER_OFFSET_RETENTION_SECS,
+            expiry_interval_secs: SPU_CONSUMER_OFFSET_EXPIRY_INTERVAL_SECS,
+        }
+    }
+}
+
+/// long polling of fetch requests
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct FetchConfig {
+    /// longest time fetch waits for `min_bytes`, longer `max_wait` of request is shortened.
+    /// 0 disables waiting
+    pub max_wait_ms: u64,
+}
+
+impl Default for FetchConfig {
+    fn default() -> Self {
+        Self {
+            max_wait_ms: SPU_FETCH_MAX_WAIT_MS,
         }
     }
 }
@@ -89,6 +158,55 @@ impl Default for SmartEngineConfig {
     }
 }
 
+/// rate limits of single client, None is unlimited
+#[derive(Debug, Default, Eq, PartialEq, Clone, serde::Deserialize)]
+#[serde(rename_all = "camelCase", default, deny_unknown_fields)]
+pub struct ClientQuota {
+    pub produce_bytes_per_sec: Option<u64>,
+    pub fetch_bytes_per_sec: Option<u64>,
+    pub requests_per_sec: Option<u64>,
+}
+
+impl ClientQuota {
+    pub fn is_unlimited(&self) -> bool {
+        self.produce_bytes_per_sec.is_none()
+            && self.fetch_bytes_per_sec.is_none()
+            && self.requests_per_sec.is_none()
+    }
+}
+
+#[derive(Debug, Default, Eq, PartialEq, Clone)]
+pub struct QuotaConfig {
+    /// quota of clients without override
+    pub default: ClientQuota,
+    /// quota by client id
+    pub overrides: BTreeMap<String, ClientQuota>,
+}
+
+/// memory limit of buffers held by SPU, None is unlimited
+#[derive(Debug, Default, Eq, PartialEq, Clone)]
+pub struct MemoryConfig {
+    pub max_bytes: Option<u64>,
+}
+
+/// limit of produce and fetch requests processed concurrently
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct AdmissionConfig {
+    /// None is unlimited
+    pub max_concurrent_requests: Option<u64>,
+    /// requests waiting over the limit, further ones are rejected as busy
+    pub max_queued_requests: u64,
+}
+
+impl Default for AdmissionConfig {
+    fn default() -> Self {
+        Self {
+            max_concurrent_requests: None,
+            max_queued_requests: SPU_MAX_QUEUED_REQUESTS,
+        }
+    }
+}
+
 /// streaming processing unit configuration file
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub struct SpuConfig {
@@ -111,6 +229,37 @@ pub struct SpuConfig {
     pub peer_max_bytes: u32,
 
     pub smart_engine: SmartEngineConfig,
+
+    pub quota: QuotaConfig,
+
+    pub memory: MemoryConfig,
+
+    pub consumer_offset: ConsumerOffsetConfig,
+
+    pub fetch: FetchConfig,
+
+    pub admission: AdmissionConfig,
+
+    /// backends of replicas stored by `BackendReplica`
+    pub storage_backends: StorageBackends,
+
+    /// providers of secrets, such as Vault
+    pub secrets: SecretOpt,
+
+    /// bearer token presented to other SPUs
+    pub peer_auth_token: Option<SecretRef>,
+
+    /// JSON file overriding runtime parameters, polled for changes
+    pub dynamic_config: Option<PathBuf>,
+
+    /// request rate limits of public clients
+    pub rate_limit: RateLimitConfig,
+
+    /// longest time in seconds to drain requests and sync storage on shutdown
+    pub shutdown_timeout_secs: u64,
+
+    /// address of CPU and heap profiling endpoint, endpoint is disabled if none
+    pub profiling_endpoint: Option<String>,
 }
 
 impl Default for SpuConfig {
@@ -126,6 +275,18 @@ impl Default for SpuConfig {
             log: Log::default(),
             peer_max_bytes: fluvio_storage::FileReplica::PREFER_MAX_LEN,
             smart_engine: SmartEngineConfig::default(),
+            quota: QuotaConfig::default(),
+            memory: MemoryConfig::default(),
+            consumer_offset: ConsumerOffsetConfig::default(),
+            fetch: FetchConfig::default(),
+            admission: AdmissionConfig::default(),
+            storage_backends: StorageBackends::default(),
+            secrets: SecretOpt::default(),
+            peer_auth_token: None,
+            dynamic_config: None,
+            rate_limit: RateLimitConfig::default(),
+            shutdown_timeout_secs: SPU_SHUTDOWN_TIMEOUT_SECS,
+            profiling_endpoint: None,
         }
     }
 }
@@ -174,10 +335,18 @@ impl From<&SpuConfig> for ReplicaConfig {
             .flush_write_count(log.flush_write_count)
             .flush_idle_msec(log.flush_idle_msec)
             .max_batch_size(log.max_batch_size)
+            .recovery_audit(log.recovery_audit)
+            .group_commit(log.group_commit)
             .build()
     }
 }
 
+impl From<&SpuConfig> for BackendReplicaConfig {
+    fn from(config: &SpuConfig) -> Self {
+        BackendReplicaConfig::new(config.storage_backends.clone(), config.into())
+    }
+}
+
 impl From<&SpuConfig> for ReplicationConfig {
     fn from(config: &SpuConfig) -> ReplicationConfig {
         config.replication.clone()
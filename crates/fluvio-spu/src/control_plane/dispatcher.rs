@@ -1,13 +1,14 @@
-use std::time::Duration;
+use std::path::Path;
+use std::time::{Duration, Instant};
 
 use fluvio_controlplane::spu_api::update_mirror::UpdateMirrorRequest;
-use tracing::{info, trace, error, debug, warn, instrument};
+use tracing::{info, trace, error, debug, warn, instrument, Span};
 use tokio::select;
 use futures_util::stream::StreamExt;
 use anyhow::{anyhow, Result};
 
 use fluvio_controlplane::sc_api::register_spu::RegisterSpuRequest;
-use fluvio_controlplane::sc_api::update_lrs::UpdateLrsRequest;
+use fluvio_controlplane::sc_api::update_lrs::{SpuStat, UpdateLrsRequest};
 use fluvio_controlplane::spu_api::api::{InternalSpuRequest, InternalSpuApi};
 use fluvio_controlplane::spu_api::update_replica::UpdateReplicaRequest;
 use fluvio_controlplane::spu_api::update_smartmodule::UpdateSmartModuleRequest;
@@ -16,6 +17,7 @@ use flv_util::print_cli_err;
 use fluvio_future::task::spawn;
 use fluvio_future::timer::sleep;
 use fluvio_protocol::api::RequestMessage;
+use fluvio_service::telemetry::{current_traceparent, set_remote_parent};
 use fluvio_socket::{FluvioSocket, FluvioSink};
 use fluvio_storage::FileReplica;
 
@@ -23,6 +25,9 @@ use crate::core::SharedGlobalContext;
 
 use super::message_sink::SharedStatusUpdate;
 
+/// interval between reports of SPU version and disk space
+const SPU_STAT_INTERVAL: Duration = Duration::from_secs(30);
+
 // keep track of various internal state of dispatcher
 #[derive(Default)]
 struct DispatcherCounter {
@@ -38,6 +43,8 @@ pub struct ScDispatcher<S> {
     ctx: SharedGlobalContext<S>,
     status_update: SharedStatusUpdate,
     counter: DispatcherCounter,
+    /// last time stat was reported, none if not reported on current connection
+    stat_sent: Option<Instant>,
 }
 
 impl ScDispatcher<FileReplica> {
@@ -46,6 +53,7 @@ impl ScDispatcher<FileReplica> {
             status_update: ctx.status_update_owned(),
             ctx,
             counter: DispatcherCounter::default(),
+            stat_sent: None,
         }
     }
 
@@ -123,6 +131,7 @@ impl ScDispatcher<FileReplica> {
         let mut api_stream = stream.api_stream::<InternalSpuRequest, InternalSpuApi>();
 
         let mut status_timer = Timer::interval(MIN_SC_SINK_TIME);
+        self.stat_sent = None;
 
         loop {
             trace!("waiting");
@@ -160,6 +169,10 @@ impl ScDispatcher<FileReplica> {
                                 break;
                             }
                         },
+                        Some(Ok(InternalSpuRequest::UpdateAclRequest(request))) => {
+                            let (_, request) = request.get_header_request();
+                            self.ctx.acl().sync(request.enabled, request.entries);
+                        },
                         Some(Err(err)) => {
                             error!(%err, "Api error");
                             break;
@@ -183,13 +196,19 @@ impl ScDispatcher<FileReplica> {
     #[instrument(skip(self))]
     async fn send_status_back_to_sc(&mut self, sc_sink: &mut FluvioSink) -> Result<()> {
         let requests = self.status_update.remove_all().await;
+        let digests = self.status_update.remove_all_digests().await;
 
-        if requests.is_empty() {
+        if requests.is_empty() && digests.is_empty() {
             trace!("sending empty status");
         } else {
-            trace!(requests = ?requests, "sending status back to sc");
+            trace!(requests = ?requests, ?digests, "sending status back to sc");
         }
-        let message = RequestMessage::new_request(UpdateLrsRequest::new(requests));
+        let message = RequestMessage::new_request(
+            UpdateLrsRequest::new(requests)
+                .with_digests(digests)
+                .with_stat(self.stat_if_due())
+                .with_traceparent(current_traceparent()),
+        );
 
         sc_sink
             .send_request(&message)
@@ -197,6 +216,19 @@ impl ScDispatcher<FileReplica> {
             .map_err(|err| anyhow!("error sending status back to sc: {}", err))
     }
 
+    /// stat is reported on connect and then every `SPU_STAT_INTERVAL`
+    fn stat_if_due(&mut self) -> SpuStat {
+        if self
+            .stat_sent
+            .is_some_and(|sent| sent.elapsed() < SPU_STAT_INTERVAL)
+        {
+            return SpuStat::default();
+        }
+        self.stat_sent = Some(Instant::now());
+        let (total, available) = disk_space(&self.ctx.config().log.base_dir);
+        SpuStat::new(crate::VERSION.trim(), total, available)
+    }
+
     /// register local spu to sc
     #[instrument(
         skip(self),
@@ -207,7 +239,8 @@ impl ScDispatcher<FileReplica> {
 
         debug!(%local_spu_id, "sending spu registration request",);
 
-        let register_req = RegisterSpuRequest::new(local_spu_id);
+        let register_req =
+            RegisterSpuRequest::new(local_spu_id).with_version(crate::VERSION.trim());
         let mut message = RequestMessage::new_request(register_req);
         message
             .get_mut_header()
@@ -274,6 +307,7 @@ impl ScDispatcher<FileReplica> {
         use crate::core::ReplicaChange;
 
         let (_, request) = req_msg.get_header_request();
+        set_remote_parent(&Span::current(), request.traceparent());
 
         debug!( message = ?request,"replica request");
 
@@ -301,6 +335,7 @@ impl ScDispatcher<FileReplica> {
         req_msg: RequestMessage<UpdateSpuRequest>,
     ) -> Result<()> {
         let (_, request) = req_msg.get_header_request();
+        set_remote_parent(&Span::current(), request.traceparent());
 
         debug!( message = ?request,"starting spu update");
 
@@ -338,6 +373,7 @@ impl ScDispatcher<FileReplica> {
         req_msg: RequestMessage<UpdateSmartModuleRequest>,
     ) -> Result<()> {
         let (_, request) = req_msg.get_header_request();
+        set_remote_parent(&Span::current(), request.traceparent());
 
         debug!( message = ?request,"starting SmartModule update");
 
@@ -375,6 +411,7 @@ impl ScDispatcher<FileReplica> {
         req_msg: RequestMessage<UpdateMirrorRequest>,
     ) -> anyhow::Result<()> {
         let (_, request) = req_msg.get_header_request();
+        set_remote_parent(&Span::current(), request.traceparent());
 
         debug!( message = ?request,"starting remote cluster update");
 
@@ -404,3 +441,18 @@ impl ScDispatcher<FileReplica> {
         Ok(())
     }
 }
+
+/// total and available bytes of disk on which `path` is stored, zero if not found
+fn disk_space(path: &Path) -> (u64, u64) {
+    use sysinfo::{DiskExt, System, SystemExt};
+
+    let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    let mut sys = System::new();
+    sys.refresh_disks_list();
+    sys.disks()
+        .iter()
+        .filter(|disk| path.starts_with(disk.mount_point()))
+        .max_by_key(|disk| disk.mount_point().as_os_str().len())
+        .map(|disk| (disk.total_space(), disk.available_space()))
+        .unwrap_or_default()
+}
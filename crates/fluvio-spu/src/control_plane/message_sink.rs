@@ -1,29 +1,48 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 use async_lock::Mutex;
-use fluvio_controlplane::sc_api::update_lrs::LrsRequest;
+use fluvio_controlplane::sc_api::update_lrs::{LrsRequest, ReplicaDigestsRequest};
+use fluvio_controlplane_metadata::partition::ReplicaKey;
 
 pub type SharedStatusUpdate = Arc<StatusMessageSink>;
 
 /// channel used to send message to sc
 #[derive(Debug)]
-pub struct StatusMessageSink(Mutex<HashSet<LrsRequest>>);
+pub struct StatusMessageSink {
+    lrs: Mutex<HashSet<LrsRequest>>,
+    digests: Mutex<HashMap<ReplicaKey, ReplicaDigestsRequest>>,
+}
 
 impl StatusMessageSink {
     pub fn shared() -> Arc<Self> {
-        Arc::new(Self(Mutex::new(HashSet::new())))
+        Arc::new(Self {
+            lrs: Mutex::new(HashSet::new()),
+            digests: Mutex::new(HashMap::new()),
+        })
     }
 
     /// send lrs request sc
     /// newer entry will overwrite previous if it has not been cleared
     pub async fn send(&self, request: LrsRequest) {
-        let mut lock = self.0.lock().await;
+        let mut lock = self.lrs.lock().await;
         lock.replace(request);
     }
 
     pub async fn remove_all(&self) -> Vec<LrsRequest> {
-        let mut lock = self.0.lock().await;
+        let mut lock = self.lrs.lock().await;
         lock.drain().collect()
     }
+
+    /// send segment digests of replica to sc
+    /// newer digests overwrite previous if they have not been cleared
+    pub async fn send_digests(&self, request: ReplicaDigestsRequest) {
+        let mut lock = self.digests.lock().await;
+        lock.insert(request.id.clone(), request);
+    }
+
+    pub async fn remove_all_digests(&self) -> Vec<ReplicaDigestsRequest> {
+        let mut lock = self.digests.lock().await;
+        lock.drain().map(|(_, request)| request).collect()
+    }
 }
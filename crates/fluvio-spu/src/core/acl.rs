@@ -0,0 +1,119 @@
+//!
+//! # ACL Local Store
+//!
+//! Copy of ACL entries managed by the SC, replaced whenever SC sends update.
+//! Everything is allowed until SC sends the first update, and while ACLs are disabled or
+//! there are no entries. Once SC enables ACLs with entries, requests not granted are denied.
+//!
+use std::sync::RwLock;
+
+use tracing::debug;
+
+use fluvio_controlplane_metadata::acl::{is_allowed, AclEntry, AclPermission, AclResourceType};
+
+#[derive(Debug, Default)]
+pub(crate) struct AclLocalStore {
+    inner: RwLock<AclState>,
+}
+
+#[derive(Debug, Default)]
+struct AclState {
+    enabled: bool,
+    entries: Vec<AclEntry>,
+}
+
+impl AclState {
+    fn is_enforced(&self) -> bool {
+        self.enabled && !self.entries.is_empty()
+    }
+}
+
+impl AclLocalStore {
+    pub(crate) fn sync(&self, enabled: bool, entries: Vec<AclEntry>) {
+        debug!(enabled, entries = entries.len(), "syncing acl");
+        if let Ok(mut state) = self.inner.write() {
+            *state = AclState { enabled, entries };
+        }
+    }
+
+    /// check if principal is granted permission, everything is allowed if ACLs are not synced
+    /// from SC or not configured
+    pub(crate) fn allows(
+        &self,
+        principal: Option<&str>,
+        resource_type: AclResourceType,
+        resource: &str,
+        permission: AclPermission,
+    ) -> bool {
+        self.inner
+            .read()
+            .map(|state| {
+                !state.is_enforced()
+                    || is_allowed(
+                        &state.entries,
+                        principal,
+                        resource_type,
+                        resource,
+                        permission,
+                    )
+            })
+            .unwrap_or(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn alice_produce() -> AclEntry {
+        AclEntry::new(
+            "alice",
+            AclResourceType::Topic,
+            "t1",
+            AclPermission::Produce,
+        )
+    }
+
+    #[test]
+    fn test_acl_unsynced_allows_all() {
+        let acl = AclLocalStore::default();
+        assert!(acl.allows(None, AclResourceType::Topic, "t1", AclPermission::Produce));
+        assert!(acl.allows(
+            Some("bob"),
+            AclResourceType::ConsumerGroup,
+            "g1",
+            AclPermission::Admin
+        ));
+    }
+
+    #[test]
+    fn test_acl_sync() {
+        let acl = AclLocalStore::default();
+
+        acl.sync(false, vec![]);
+        assert!(acl.allows(None, AclResourceType::Topic, "t1", AclPermission::Produce));
+
+        acl.sync(true, vec![alice_produce()]);
+        assert!(acl.allows(
+            Some("alice"),
+            AclResourceType::Topic,
+            "t1",
+            AclPermission::Produce
+        ));
+        assert!(!acl.allows(None, AclResourceType::Topic, "t1", AclPermission::Produce));
+        assert!(!acl.allows(
+            Some("alice"),
+            AclResourceType::Topic,
+            "t1",
+            AclPermission::Consume
+        ));
+
+        // enabled without entries is not configured
+        acl.sync(true, vec![]);
+        assert!(acl.allows(None, AclResourceType::Topic, "t1", AclPermission::Produce));
+
+        // entries are not enforced while disabled
+        acl.sync(false, vec![alice_produce()]);
+        assert!(acl.allows(None, AclResourceType::Topic, "t1", AclPermission::Produce));
+    }
+}
@@ -0,0 +1,178 @@
+//!
+//! # Request Admission
+//!
+//! Limits produce and fetch requests processed concurrently by SPU. Requests over the limit
+//! wait in a bounded queue until running ones complete. Once the queue is full, new requests
+//! are rejected with retriable `ServerBusy` error, so clients back off instead of every
+//! request slowing down as load grows.
+//!
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use event_listener::Event;
+use tracing::debug;
+
+use fluvio_protocol::link::ErrorCode;
+
+use crate::config::AdmissionConfig;
+use crate::core::metrics::SpuMetrics;
+
+#[derive(Debug)]
+pub(crate) struct RequestAdmission {
+    /// None is unlimited
+    max_active: Option<u64>,
+    max_queued: u64,
+    active: AtomicU64,
+    queued: AtomicU64,
+    released: Event,
+    metrics: Arc<SpuMetrics>,
+}
+
+impl RequestAdmission {
+    pub(crate) fn shared(config: &AdmissionConfig, metrics: Arc<SpuMetrics>) -> Arc<Self> {
+        Arc::new(Self {
+            max_active: config.max_concurrent_requests,
+            max_queued: config.max_queued_requests,
+            active: AtomicU64::new(0),
+            queued: AtomicU64::new(0),
+            released: Event::new(),
+            metrics,
+        })
+    }
+
+    pub(crate) fn active(&self) -> u64 {
+        self.active.load(Ordering::SeqCst)
+    }
+
+    pub(crate) fn queued(&self) -> u64 {
+        self.queued.load(Ordering::SeqCst)
+    }
+
+    /// admit request, waiting in queue while limit is reached.
+    /// Fails with `ServerBusy` if queue is full
+    pub(crate) async fn admit(self: &Arc<Self>) -> Result<RequestPermit, ErrorCode> {
+        if let Some(permit) = self.try_admit() {
+            self.metrics.admission().add_admitted(false);
+            return Ok(permit);
+        }
+        let entered = self
+            .queued
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |queued| {
+                (queued < self.max_queued).then_some(queued + 1)
+            })
+            .is_ok();
+        if !entered {
+            self.metrics.admission().add_rejected();
+            debug!(
+                active = self.active(),
+                queued = self.queued(),
+                "request shed"
+            );
+            return Err(ErrorCode::ServerBusy);
+        }
+        let permit = loop {
+            let listener = self.released.listen();
+            // request may have completed before listener was registered
+            if let Some(permit) = self.try_admit() {
+                break permit;
+            }
+            listener.await;
+        };
+        self.queued.fetch_sub(1, Ordering::SeqCst);
+        self.metrics.admission().add_admitted(true);
+        Ok(permit)
+    }
+
+    fn try_admit(self: &Arc<Self>) -> Option<RequestPermit> {
+        self.active
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |active| {
+                match self.max_active {
+                    Some(max_active) if active >= max_active => None,
+                    _ => Some(active + 1),
+                }
+            })
+            .ok()?;
+        Some(RequestPermit {
+            admission: self.clone(),
+        })
+    }
+
+    fn release(&self) {
+        self.active.fetch_sub(1, Ordering::SeqCst);
+        self.released.notify(1);
+    }
+}
+
+/// slot of admitted request, released on drop
+#[derive(Debug)]
+pub(crate) struct RequestPermit {
+    admission: Arc<RequestAdmission>,
+}
+
+impl Drop for RequestPermit {
+    fn drop(&mut self) {
+        self.admission.release();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use fluvio_future::timer::sleep;
+    use fluvio_future::task::spawn;
+
+    use super::*;
+
+    fn admission(
+        max_concurrent_requests: Option<u64>,
+        max_queued_requests: u64,
+    ) -> Arc<RequestAdmission> {
+        let config = AdmissionConfig {
+            max_concurrent_requests,
+            max_queued_requests,
+        };
+        RequestAdmission::shared(&config, Arc::new(SpuMetrics::new()))
+    }
+
+    #[fluvio_future::test]
+    async fn test_queued_request_admitted_on_release() {
+        let admission = admission(Some(1), 1);
+        let running = admission.admit().await.expect("admitted");
+
+        let waiting = admission.clone();
+        let handle = spawn(async move { waiting.admit().await });
+        sleep(Duration::from_millis(50)).await;
+        assert_eq!(admission.queued(), 1);
+
+        // queue is full
+        assert_eq!(admission.admit().await.unwrap_err(), ErrorCode::ServerBusy);
+        assert_eq!(admission.metrics.admission().rejected(), 1);
+
+        drop(running);
+        let queued = handle.await.expect("admitted after release");
+        assert_eq!(admission.active(), 1);
+        assert_eq!(admission.queued(), 0);
+        assert_eq!(admission.metrics.admission().queued(), 1);
+        drop(queued);
+        assert_eq!(admission.active(), 0);
+    }
+
+    #[fluvio_future::test]
+    async fn test_no_queue_sheds_immediately() {
+        let admission = admission(Some(1), 0);
+        let _running = admission.admit().await.expect("admitted");
+        assert!(admission.admit().await.is_err());
+    }
+
+    #[fluvio_future::test]
+    async fn test_unlimited_admission() {
+        let admission = admission(None, 0);
+        let permits: Vec<_> = (0..100)
+            .map(|_| admission.try_admit().expect("unlimited"))
+            .collect();
+        assert_eq!(admission.active(), 100);
+        drop(permits);
+        assert_eq!(admission.active(), 0);
+    }
+}
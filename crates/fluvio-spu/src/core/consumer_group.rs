@@ -0,0 +1,517 @@
+//!
+//! # Consumer Group Coordinator
+//!
+//! Keeps membership of consumer groups and assigns partitions of the group topic to members.
+//! Coordinator runs on the leader of the consumer offsets partition. Membership is not
+//! persisted: members that stop sending heartbeats within their session timeout are removed.
+//! Member ids are generated by coordinator when member first joins, so they can't be chosen
+//! by clients to take over assignment of other member.
+//!
+//! Rebalance doesn't hand partition to its new member while previous member may still consume it.
+//! Member revokes partitions it lost by heartbeat with generation of its new assignment, then
+//! generation is bumped again and partitions are handed to their new members. Members which
+//! leave or expire don't hold partitions.
+//!
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_lock::Mutex;
+use rand::Rng;
+use tracing::{debug, info};
+
+use fluvio_protocol::link::ErrorCode;
+use fluvio_spu_schema::server::consumer_group::{GenerationId, GroupAssignment};
+use fluvio_types::{PartitionCount, PartitionId};
+
+pub(crate) type SharedGroupCoordinator = Arc<GroupCoordinator>;
+
+#[derive(Debug)]
+pub(crate) struct GroupCoordinator {
+    id_prefix: String,
+    groups: Mutex<GroupMap>,
+}
+
+#[derive(Debug, Default)]
+struct GroupMap {
+    next_member: u64,
+    groups: HashMap<String, ConsumerGroup>,
+}
+
+#[derive(Debug)]
+struct Member {
+    last_heartbeat: Instant,
+    session_timeout: Duration,
+    /// partitions handed to member which it may still consume
+    owned: Vec<PartitionId>,
+}
+
+impl Member {
+    fn new(now: Instant, session_timeout: Duration) -> Self {
+        Self {
+            last_heartbeat: now,
+            session_timeout,
+            owned: vec![],
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct ConsumerGroup {
+    topic: String,
+    partitions: PartitionCount,
+    generation: GenerationId,
+    members: BTreeMap<String, Member>,
+    /// target assignment of members, moved partitions are handed once previous member revokes them
+    assignments: HashMap<String, Vec<PartitionId>>,
+    /// some partitions of current generation wait for revoke
+    revoking: bool,
+}
+
+impl GroupCoordinator {
+    pub(crate) fn new_shared(id_prefix: impl Into<String>) -> SharedGroupCoordinator {
+        Arc::new(Self {
+            id_prefix: id_prefix.into(),
+            groups: Mutex::new(GroupMap::default()),
+        })
+    }
+
+    /// add member to group, returns member id and its assignment.
+    /// Empty `member_id` joins new member, otherwise member must be already in group.
+    /// `partitions` is partition count of topic known to SPU
+    pub(crate) async fn join(
+        &self,
+        group_id: &str,
+        member_id: &str,
+        topic: &str,
+        partitions: PartitionCount,
+        session_timeout: Duration,
+    ) -> Result<(String, GroupAssignment), ErrorCode> {
+        let now = Instant::now();
+        let mut groups = self.groups.lock().await;
+        groups.expire(now);
+
+        let member_id = if member_id.is_empty() {
+            groups.next_member += 1;
+            format!(
+                "{}-{}-{:016x}",
+                self.id_prefix,
+                groups.next_member,
+                rand::thread_rng().gen::<u64>()
+            )
+        } else if groups
+            .groups
+            .get(group_id)
+            .is_some_and(|group| group.members.contains_key(member_id))
+        {
+            member_id.to_owned()
+        } else {
+            return Err(unknown_member(group_id, member_id));
+        };
+
+        let group = groups.groups.entry(group_id.to_owned()).or_default();
+        if group.members.is_empty() {
+            topic.clone_into(&mut group.topic);
+        } else if group.topic != topic {
+            return Err(ErrorCode::ConsumerGroupTopicMismatch {
+                group_id: group_id.to_owned(),
+                topic: group.topic.clone(),
+            });
+        }
+
+        let is_new = match group.members.get_mut(&member_id) {
+            Some(member) => {
+                member.last_heartbeat = now;
+                member.session_timeout = session_timeout;
+                false
+            }
+            None => {
+                group
+                    .members
+                    .insert(member_id.clone(), Member::new(now, session_timeout));
+                true
+            }
+        };
+        if is_new || partitions != group.partitions {
+            group.partitions = partitions;
+            group.rebalance(group_id);
+        }
+
+        let assignment = group.hand_out(&member_id);
+        Ok((member_id, assignment))
+    }
+
+    /// refresh member session, returns current assignment of the member.
+    /// Heartbeat with current generation acknowledges that member revoked partitions it lost
+    pub(crate) async fn heartbeat(
+        &self,
+        group_id: &str,
+        member_id: &str,
+        generation: GenerationId,
+    ) -> Result<GroupAssignment, ErrorCode> {
+        let now = Instant::now();
+        let mut groups = self.groups.lock().await;
+        groups.expire(now);
+
+        let Some(group) = groups
+            .groups
+            .get_mut(group_id)
+            .filter(|group| group.members.contains_key(member_id))
+        else {
+            return Err(unknown_member(group_id, member_id));
+        };
+        if generation == group.generation {
+            group.revoke(group_id, member_id);
+        }
+        if let Some(member) = group.members.get_mut(member_id) {
+            member.last_heartbeat = now;
+        }
+
+        Ok(group.hand_out(member_id))
+    }
+
+    pub(crate) async fn leave(&self, group_id: &str, member_id: &str) -> Result<(), ErrorCode> {
+        let mut groups = self.groups.lock().await;
+        let Some(group) = groups.groups.get_mut(group_id) else {
+            return Err(unknown_member(group_id, member_id));
+        };
+        if group.members.remove(member_id).is_none() {
+            return Err(unknown_member(group_id, member_id));
+        }
+        if group.members.is_empty() {
+            groups.groups.remove(group_id);
+        } else {
+            group.rebalance(group_id);
+        }
+        Ok(())
+    }
+}
+
+impl GroupMap {
+    /// remove members with expired sessions and rebalance affected groups
+    fn expire(&mut self, now: Instant) {
+        self.groups.retain(|group_id, group| {
+            let before = group.members.len();
+            group.members.retain(|member_id, member| {
+                let alive = now.duration_since(member.last_heartbeat) <= member.session_timeout;
+                if !alive {
+                    info!(group_id, member_id, "consumer group member session expired");
+                }
+                alive
+            });
+            if group.members.len() != before && !group.members.is_empty() {
+                group.rebalance(group_id);
+            }
+            !group.members.is_empty()
+        });
+    }
+}
+
+impl ConsumerGroup {
+    /// spread partitions across members, member ids are sorted so assignment is stable
+    fn rebalance(&mut self, group_id: &str) {
+        self.generation += 1;
+        self.assignments.clear();
+        let members: Vec<&String> = self.members.keys().collect();
+        for partition in 0..self.partitions {
+            let member = members[partition as usize % members.len()];
+            self.assignments
+                .entry(member.clone())
+                .or_default()
+                .push(partition);
+        }
+        self.revoking = self.has_unrevoked();
+        debug!(
+            group_id,
+            generation = self.generation,
+            members = members.len(),
+            revoking = self.revoking,
+            "consumer group rebalanced"
+        );
+    }
+
+    /// member is consuming current generation, so it no longer holds partitions it lost.
+    /// Once all lost partitions are revoked, new generation hands them to their members
+    fn revoke(&mut self, group_id: &str, member_id: &str) {
+        let target = self.assignments.get(member_id);
+        if let Some(member) = self.members.get_mut(member_id) {
+            member
+                .owned
+                .retain(|partition| target.is_some_and(|target| target.contains(partition)));
+        }
+        if self.revoking && !self.has_unrevoked() {
+            self.generation += 1;
+            self.revoking = false;
+            debug!(
+                group_id,
+                generation = self.generation,
+                "consumer group partitions revoked"
+            );
+        }
+    }
+
+    /// some member still holds partition assigned to other member
+    fn has_unrevoked(&self) -> bool {
+        self.assignments.iter().any(|(member_id, partitions)| {
+            partitions
+                .iter()
+                .any(|partition| self.held_by_other(member_id, *partition))
+        })
+    }
+
+    fn held_by_other(&self, member_id: &str, partition: PartitionId) -> bool {
+        self.members
+            .iter()
+            .any(|(id, member)| id != member_id && member.owned.contains(&partition))
+    }
+
+    /// assignment of member without partitions other members haven't revoked yet
+    fn assignment(&self, member_id: &str) -> GroupAssignment {
+        let partitions = self
+            .assignments
+            .get(member_id)
+            .map(|partitions| {
+                partitions
+                    .iter()
+                    .copied()
+                    .filter(|partition| !self.held_by_other(member_id, *partition))
+                    .collect()
+            })
+            .unwrap_or_default();
+        GroupAssignment {
+            generation: self.generation,
+            partitions,
+        }
+    }
+
+    /// assignment sent to member, which may consume it from now on
+    fn hand_out(&mut self, member_id: &str) -> GroupAssignment {
+        let assignment = self.assignment(member_id);
+        if let Some(member) = self.members.get_mut(member_id) {
+            for partition in &assignment.partitions {
+                if !member.owned.contains(partition) {
+                    member.owned.push(*partition);
+                }
+            }
+        }
+        assignment
+    }
+}
+
+fn unknown_member(group_id: &str, member_id: &str) -> ErrorCode {
+    ErrorCode::ConsumerGroupUnknownMember {
+        group_id: group_id.to_owned(),
+        member_id: member_id.to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TIMEOUT: Duration = Duration::from_secs(10);
+
+    #[fluvio_future::test]
+    async fn test_join_assigns_disjoint_partitions() {
+        let coordinator = GroupCoordinator::new_shared("spu-1");
+
+        let (first, assignment) = coordinator
+            .join("group", "", "topic", 3, TIMEOUT)
+            .await
+            .expect("join");
+        assert_eq!(assignment.generation, 1);
+        assert_eq!(assignment.partitions, vec![0, 1, 2]);
+
+        // partition moving to second member waits until first revokes it
+        let (second, assignment) = coordinator
+            .join("group", "", "topic", 3, TIMEOUT)
+            .await
+            .expect("join");
+        assert_ne!(first, second);
+        assert_eq!(assignment.generation, 2);
+        assert!(assignment.partitions.is_empty());
+
+        let first_assignment = coordinator.heartbeat("group", &first, 1).await.expect("hb");
+        assert_eq!(first_assignment.generation, 2);
+        assert_eq!(first_assignment.partitions.len(), 2);
+        let second_assignment = coordinator
+            .heartbeat("group", &second, 2)
+            .await
+            .expect("hb");
+        assert_eq!(second_assignment.generation, 2);
+        assert!(second_assignment.partitions.is_empty());
+
+        // first acknowledges new assignment, so revoke is done
+        let first_assignment = coordinator.heartbeat("group", &first, 2).await.expect("hb");
+        assert_eq!(first_assignment.generation, 3);
+        let second_assignment = coordinator
+            .heartbeat("group", &second, 2)
+            .await
+            .expect("hb");
+        assert_eq!(second_assignment.generation, 3);
+
+        let mut all: Vec<_> = first_assignment
+            .partitions
+            .iter()
+            .chain(second_assignment.partitions.iter())
+            .copied()
+            .collect();
+        all.sort();
+        assert_eq!(all, vec![0, 1, 2]);
+    }
+
+    #[fluvio_future::test]
+    async fn test_leave_rebalances() {
+        let coordinator = GroupCoordinator::new_shared("spu-1");
+        let (first, _) = coordinator
+            .join("group", "", "topic", 2, TIMEOUT)
+            .await
+            .expect("join");
+        let (second, _) = coordinator
+            .join("group", "", "topic", 2, TIMEOUT)
+            .await
+            .expect("join");
+
+        coordinator.leave("group", &second).await.expect("leave");
+
+        let assignment = coordinator.heartbeat("group", &first, 2).await.expect("hb");
+        assert_eq!(assignment.generation, 3);
+        assert_eq!(assignment.partitions, vec![0, 1]);
+        assert!(matches!(
+            coordinator.heartbeat("group", &second, 3).await,
+            Err(ErrorCode::ConsumerGroupUnknownMember { .. })
+        ));
+    }
+
+    /// member consuming its assignment until it learns about new one
+    struct Consumer {
+        id: String,
+        assignment: GroupAssignment,
+    }
+
+    /// record partitions member consumes, failing if other member consumes any of them
+    async fn consume(consuming: &Mutex<HashMap<String, Vec<PartitionId>>>, member: &Consumer) {
+        let mut consuming = consuming.lock().await;
+        for (other, partitions) in consuming.iter() {
+            if *other != member.id {
+                for partition in &member.assignment.partitions {
+                    assert!(
+                        !partitions.contains(partition),
+                        "partition {partition} consumed by {other} and {}",
+                        member.id
+                    );
+                }
+            }
+        }
+        consuming.insert(member.id.clone(), member.assignment.partitions.clone());
+    }
+
+    #[fluvio_future::test]
+    async fn test_concurrent_join_leave_never_overlaps() {
+        use fluvio_future::timer::sleep;
+
+        const PARTITIONS: PartitionCount = 4;
+        let coordinator = GroupCoordinator::new_shared("spu-1");
+        let consuming = Mutex::new(HashMap::new());
+
+        let run_member = |rounds: u32, pause: Duration| {
+            let coordinator = coordinator.clone();
+            let consuming = &consuming;
+            async move {
+                let (id, assignment) = coordinator
+                    .join("group", "", "topic", PARTITIONS, TIMEOUT)
+                    .await
+                    .expect("join");
+                let mut member = Consumer { id, assignment };
+                consume(consuming, &member).await;
+                for _ in 0..rounds {
+                    sleep(pause).await;
+                    member.assignment = coordinator
+                        .heartbeat("group", &member.id, member.assignment.generation)
+                        .await
+                        .expect("hb");
+                    consume(consuming, &member).await;
+                }
+                // stop consuming before leaving
+                member.assignment.partitions.clear();
+                consume(consuming, &member).await;
+                coordinator.leave("group", &member.id).await.expect("leave");
+            }
+        };
+
+        futures_util::join!(
+            run_member(20, Duration::from_millis(3)),
+            run_member(10, Duration::from_millis(5)),
+            run_member(15, Duration::from_millis(2)),
+        );
+
+        // last member has left, group is gone
+        let (member, assignment) = coordinator
+            .join("group", "", "topic", PARTITIONS, TIMEOUT)
+            .await
+            .expect("join");
+        assert_eq!(assignment.partitions, vec![0, 1, 2, 3]);
+        coordinator.leave("group", &member).await.expect("leave");
+    }
+
+    #[fluvio_future::test]
+    async fn test_member_ids_are_generated() {
+        let coordinator = GroupCoordinator::new_shared("spu-1");
+        let (member, _) = coordinator
+            .join("group", "", "topic", 2, TIMEOUT)
+            .await
+            .expect("join");
+
+        // member can rejoin with its id, but can't pick one
+        let (rejoined, assignment) = coordinator
+            .join("group", &member, "topic", 2, TIMEOUT)
+            .await
+            .expect("rejoin");
+        assert_eq!(rejoined, member);
+        assert_eq!(assignment.partitions, vec![0, 1]);
+        assert!(matches!(
+            coordinator
+                .join("group", "chosen", "topic", 2, TIMEOUT)
+                .await,
+            Err(ErrorCode::ConsumerGroupUnknownMember { .. })
+        ));
+    }
+
+    #[fluvio_future::test]
+    async fn test_topic_mismatch() {
+        let coordinator = GroupCoordinator::new_shared("spu-1");
+        coordinator
+            .join("group", "", "topic", 1, TIMEOUT)
+            .await
+            .expect("join");
+        assert!(matches!(
+            coordinator.join("group", "", "other", 1, TIMEOUT).await,
+            Err(ErrorCode::ConsumerGroupTopicMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_expired_members_removed() {
+        let now = Instant::now();
+        let mut groups = GroupMap::default();
+        let mut group = ConsumerGroup {
+            topic: "topic".to_string(),
+            partitions: 2,
+            ..Default::default()
+        };
+        group
+            .members
+            .insert("alive".to_string(), Member::new(now, TIMEOUT));
+        group
+            .members
+            .insert("dead".to_string(), Member::new(now, Duration::ZERO));
+        group.rebalance("group");
+        groups.groups.insert("group".to_string(), group);
+
+        groups.expire(now + Duration::from_secs(1));
+
+        let group = &groups.groups["group"];
+        assert_eq!(group.generation, 2);
+        assert_eq!(group.assignment("alive").partitions, vec![0, 1]);
+    }
+}
@@ -0,0 +1,401 @@
+//!
+//! # Dynamic Configuration
+//!
+//! Runtime parameters which can be changed without restarting SPU. They start from SPU
+//! configuration and are overridden by JSON file set by `--dynamic-config`, which is polled
+//! for changes. Removing a parameter from file restores its startup value.
+//!
+//! File is validated as a whole, invalid file is rejected and current parameters are kept.
+//! Every applied change is logged with its old and new value.
+//!
+use std::collections::BTreeMap;
+use std::io::{Error as IoError, ErrorKind};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, SystemTime};
+
+use serde::Deserialize;
+use tracing::{debug, info, warn};
+
+use fluvio_future::task::spawn;
+use fluvio_future::timer::sleep;
+
+use crate::config::{ClientQuota, QuotaConfig, SpuConfig};
+
+use super::quota::ClientQuotas;
+
+/// how often dynamic config file is checked for changes
+pub(crate) const DYNAMIC_CONFIG_INTERVAL: Duration = Duration::from_secs(10);
+
+/// upper bound of fetch wait, so client can't hold request for long
+const MAX_FETCH_WAIT_MS: u64 = 60_000;
+
+/// upper bound of mirror backoff
+pub(crate) const MAX_MIRROR_BACKOFF: Duration = Duration::from_secs(3600);
+
+const MIRROR_BACKOFF_MIN: Duration = Duration::from_secs(1);
+const MIRROR_BACKOFF_MAX: Duration = Duration::from_secs(300);
+
+/// modification time and length of file, none if file is missing
+type FileStamp = Option<(Option<SystemTime>, u64)>;
+
+/// content of dynamic config file, parameters not set keep their startup value
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase", default, deny_unknown_fields)]
+struct DynamicConfigFile {
+    fetch_max_wait_ms: Option<u64>,
+    follower_throttle_bytes_per_sec: Option<u64>,
+    client_quota: Option<ClientQuota>,
+    client_quota_overrides: Option<BTreeMap<String, ClientQuota>>,
+    mirror_backoff_min_ms: Option<u64>,
+    mirror_backoff_max_ms: Option<u64>,
+    /// filter in `RUST_LOG` syntax
+    log_level: Option<String>,
+}
+
+/// values of runtime parameters
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct DynamicParams {
+    fetch_max_wait_ms: u64,
+    /// None is unlimited
+    follower_throttle_bytes_per_sec: Option<u64>,
+    quota: QuotaConfig,
+    mirror_backoff_min: Duration,
+    mirror_backoff_max: Duration,
+    /// None is `RUST_LOG`
+    log_level: Option<String>,
+}
+
+impl From<&SpuConfig> for DynamicParams {
+    fn from(config: &SpuConfig) -> Self {
+        Self {
+            fetch_max_wait_ms: config.fetch.max_wait_ms,
+            follower_throttle_bytes_per_sec: config.replication.follower_throttle_bytes_per_sec,
+            quota: config.quota.clone(),
+            mirror_backoff_min: MIRROR_BACKOFF_MIN,
+            mirror_backoff_max: MIRROR_BACKOFF_MAX,
+            log_level: None,
+        }
+    }
+}
+
+impl DynamicParams {
+    /// apply overrides of file and validate result
+    fn with_overrides(&self, file: DynamicConfigFile) -> Result<Self, String> {
+        let mut params = self.clone();
+        if let Some(max_wait_ms) = file.fetch_max_wait_ms {
+            params.fetch_max_wait_ms = max_wait_ms;
+        }
+        if let Some(bytes_per_sec) = file.follower_throttle_bytes_per_sec {
+            params.follower_throttle_bytes_per_sec = Some(bytes_per_sec);
+        }
+        if let Some(quota) = file.client_quota {
+            params.quota.default = quota;
+        }
+        if let Some(overrides) = file.client_quota_overrides {
+            params.quota.overrides = overrides;
+        }
+        if let Some(min_ms) = file.mirror_backoff_min_ms {
+            params.mirror_backoff_min = Duration::from_millis(min_ms);
+        }
+        if let Some(max_ms) = file.mirror_backoff_max_ms {
+            params.mirror_backoff_max = Duration::from_millis(max_ms);
+        }
+        if file.log_level.is_some() {
+            params.log_level = file.log_level;
+        }
+
+        params.validate()?;
+        Ok(params)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.fetch_max_wait_ms > MAX_FETCH_WAIT_MS {
+            return Err(format!(
+                "fetchMaxWaitMs {} exceeds {MAX_FETCH_WAIT_MS}",
+                self.fetch_max_wait_ms
+            ));
+        }
+        if self.follower_throttle_bytes_per_sec == Some(0) {
+            return Err("followerThrottleBytesPerSec must be positive".to_owned());
+        }
+        for (client, quota) in std::iter::once(("default", &self.quota.default)).chain(
+            self.quota
+                .overrides
+                .iter()
+                .map(|(client, quota)| (client.as_str(), quota)),
+        ) {
+            if [
+                quota.produce_bytes_per_sec,
+                quota.fetch_bytes_per_sec,
+                quota.requests_per_sec,
+            ]
+            .contains(&Some(0))
+            {
+                return Err(format!("quota of client {client} must be positive"));
+            }
+        }
+        if self.mirror_backoff_min.is_zero() {
+            return Err("mirrorBackoffMinMs must be positive".to_owned());
+        }
+        if self.mirror_backoff_min > self.mirror_backoff_max {
+            return Err("mirrorBackoffMinMs exceeds mirrorBackoffMaxMs".to_owned());
+        }
+        if self.mirror_backoff_max > MAX_MIRROR_BACKOFF {
+            return Err(format!(
+                "mirrorBackoffMaxMs exceeds {}",
+                MAX_MIRROR_BACKOFF.as_millis()
+            ));
+        }
+        if let Some(log_level) = &self.log_level {
+            fluvio_service::telemetry::validate_log_filter(log_level)
+                .map_err(|err| format!("invalid logLevel {log_level}: {err}"))?;
+        }
+        Ok(())
+    }
+
+    /// parameters which differ from `other`, with their old and new value
+    fn changes(&self, other: &Self) -> Vec<(&'static str, String, String)> {
+        let mut changes = vec![];
+        let mut diff = |name, old: String, new: String| {
+            if old != new {
+                changes.push((name, old, new));
+            }
+        };
+        diff(
+            "fetch_max_wait_ms",
+            self.fetch_max_wait_ms.to_string(),
+            other.fetch_max_wait_ms.to_string(),
+        );
+        diff(
+            "follower_throttle_bytes_per_sec",
+            format!("{:?}", self.follower_throttle_bytes_per_sec),
+            format!("{:?}", other.follower_throttle_bytes_per_sec),
+        );
+        diff(
+            "client_quota",
+            format!("{:?}", self.quota.default),
+            format!("{:?}", other.quota.default),
+        );
+        diff(
+            "client_quota_overrides",
+            format!("{:?}", self.quota.overrides),
+            format!("{:?}", other.quota.overrides),
+        );
+        diff(
+            "mirror_backoff_min",
+            format!("{:?}", self.mirror_backoff_min),
+            format!("{:?}", other.mirror_backoff_min),
+        );
+        diff(
+            "mirror_backoff_max",
+            format!("{:?}", self.mirror_backoff_max),
+            format!("{:?}", other.mirror_backoff_max),
+        );
+        diff(
+            "log_level",
+            format!("{:?}", self.log_level),
+            format!("{:?}", other.log_level),
+        );
+        changes
+    }
+}
+
+pub(crate) type SharedDynamicConfig = Arc<DynamicConfig>;
+
+/// runtime parameters, overridden by dynamic config file if configured
+#[derive(Debug)]
+pub(crate) struct DynamicConfig {
+    path: Option<PathBuf>,
+    /// startup values
+    base: DynamicParams,
+    current: RwLock<DynamicParams>,
+    stamp: Mutex<FileStamp>,
+    quotas: Arc<ClientQuotas>,
+}
+
+impl DynamicConfig {
+    /// parameters starting from `config`, quota changes are applied to `quotas`
+    pub(crate) fn shared(config: &SpuConfig, quotas: Arc<ClientQuotas>) -> SharedDynamicConfig {
+        let base = DynamicParams::from(config);
+        Arc::new(Self {
+            path: config.dynamic_config.clone(),
+            current: RwLock::new(base.clone()),
+            base,
+            stamp: Mutex::new(None),
+            quotas,
+        })
+    }
+
+    pub(crate) fn fetch_max_wait_ms(&self) -> u64 {
+        self.current
+            .read()
+            .map(|params| params.fetch_max_wait_ms)
+            .unwrap_or(self.base.fetch_max_wait_ms)
+    }
+
+    pub(crate) fn follower_throttle_bytes_per_sec(&self) -> Option<u64> {
+        self.current
+            .read()
+            .map(|params| params.follower_throttle_bytes_per_sec)
+            .unwrap_or(self.base.follower_throttle_bytes_per_sec)
+    }
+
+    /// min and max wait between mirror connection attempts
+    pub(crate) fn mirror_backoff(&self) -> (Duration, Duration) {
+        self.current
+            .read()
+            .map(|params| (params.mirror_backoff_min, params.mirror_backoff_max))
+            .unwrap_or((self.base.mirror_backoff_min, self.base.mirror_backoff_max))
+    }
+
+    /// apply file if it has changed since last check, returns true if any parameter changed.
+    /// Invalid file is rejected once, and checked again when it changes.
+    pub(crate) fn reload_if_changed(&self) -> Result<bool, IoError> {
+        let Some(path) = &self.path else {
+            return Ok(false);
+        };
+
+        let stamp = stamp_file(path);
+        {
+            let mut last = self
+                .stamp
+                .lock()
+                .map_err(|_| IoError::new(ErrorKind::Other, "dynamic config lock poisoned"))?;
+            if *last == stamp && stamp.is_some() {
+                return Ok(false);
+            }
+            *last = stamp;
+        }
+
+        let params = self
+            .base
+            .with_overrides(load(path)?)
+            .map_err(|err| IoError::new(ErrorKind::InvalidData, err))?;
+        Ok(self.apply(params))
+    }
+
+    /// replace parameters, returns true if any changed
+    fn apply(&self, params: DynamicParams) -> bool {
+        let Ok(mut current) = self.current.write() else {
+            return false;
+        };
+        let changes = current.changes(&params);
+        if changes.is_empty() {
+            return false;
+        }
+
+        for (parameter, old, new) in &changes {
+            info!(parameter, %old, %new, "dynamic config changed");
+        }
+        if current.quota != params.quota {
+            self.quotas.set_config(params.quota.clone());
+        }
+        if current.log_level != params.log_level {
+            if let Err(err) = fluvio_service::telemetry::set_log_filter(params.log_level.as_deref())
+            {
+                warn!(%err, "unable to change log level");
+            }
+        }
+        *current = params;
+        true
+    }
+
+    /// check file for changes every `interval` until config is dropped
+    pub(crate) fn watch(self: &Arc<Self>, interval: Duration) {
+        let Some(path) = self.path.clone() else {
+            return;
+        };
+        if let Err(err) = self.reload_if_changed() {
+            warn!(path = %path.display(), %err, "invalid dynamic config, using startup values");
+        }
+
+        let config = Arc::downgrade(self);
+        spawn(async move {
+            debug!(path = %path.display(), ?interval, "watching dynamic config");
+            loop {
+                sleep(interval).await;
+                let Some(config) = config.upgrade() else {
+                    break;
+                };
+                if let Err(err) = config.reload_if_changed() {
+                    warn!(path = %path.display(), %err, "dynamic config rejected, keeping current");
+                }
+            }
+        });
+    }
+}
+
+/// missing file has no overrides
+fn load(path: &Path) -> Result<DynamicConfigFile, IoError> {
+    match std::fs::read(path) {
+        Ok(content) => Ok(serde_json::from_slice(&content)?),
+        Err(err) if err.kind() == ErrorKind::NotFound => Ok(DynamicConfigFile::default()),
+        Err(err) => Err(err),
+    }
+}
+
+fn stamp_file(path: &Path) -> FileStamp {
+    std::fs::metadata(path)
+        .ok()
+        .map(|meta| (meta.modified().ok(), meta.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // contents written differ in length, so change is detected within same mtime
+    fn write(path: &Path, content: &str) {
+        std::fs::write(path, content).expect("write");
+    }
+
+    #[test]
+    fn test_reload_dynamic_config() {
+        let path = std::env::temp_dir().join(format!(
+            "fluvio-spu-dynamic-config-{}.json",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+
+        let mut spu_config = SpuConfig::default();
+        spu_config.fetch.max_wait_ms = 500;
+        spu_config.dynamic_config = Some(path.clone());
+        let quotas = Arc::new(ClientQuotas::new(QuotaConfig::default()));
+        let config = DynamicConfig::shared(&spu_config, quotas);
+
+        // missing file keeps startup values
+        assert!(!config.reload_if_changed().expect("missing"));
+        assert_eq!(config.fetch_max_wait_ms(), 500);
+
+        write(
+            &path,
+            r#"{"fetchMaxWaitMs": 100, "followerThrottleBytesPerSec": 1000, "mirrorBackoffMaxMs": 5000}"#,
+        );
+        assert!(config.reload_if_changed().expect("reload"));
+        assert!(!config.reload_if_changed().expect("unchanged"));
+        assert_eq!(config.fetch_max_wait_ms(), 100);
+        assert_eq!(config.follower_throttle_bytes_per_sec(), Some(1000));
+        assert_eq!(
+            config.mirror_backoff(),
+            (Duration::from_secs(1), Duration::from_secs(5))
+        );
+
+        // invalid file is rejected as whole
+        write(&path, r#"{"fetchMaxWaitMs": 10, "mirrorBackoffMinMs": 0}"#);
+        assert!(config.reload_if_changed().is_err());
+        assert_eq!(config.fetch_max_wait_ms(), 100);
+        write(&path, r#"{"fetchMaxWait": 10}"#);
+        assert!(config.reload_if_changed().is_err());
+
+        // removed parameters are restored
+        write(&path, r#"{"clientQuota": {"requestsPerSec": 10}}"#);
+        assert!(config.reload_if_changed().expect("reload"));
+        let params = config.current.read().expect("params").clone();
+        assert_eq!(params.fetch_max_wait_ms, 500);
+        assert_eq!(params.follower_throttle_bytes_per_sec, None);
+        assert_eq!(params.quota.default.requests_per_sec, Some(10));
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
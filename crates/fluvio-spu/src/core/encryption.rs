@@ -0,0 +1,143 @@
+//!
+//! # Replica Ciphers
+//!
+//! Resolves encryption configured on topics to ciphers of replicas using key provider of the SPU.
+//! Keys are fetched once per key id and cached for the lifetime of the SPU,
+//! a rotated key is picked up after restart.
+//!
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_lock::RwLock;
+use tracing::debug;
+
+use fluvio_controlplane_metadata::partition::ReplicaKey;
+use fluvio_controlplane_metadata::topic::TopicEncryption;
+use fluvio_storage::encryption::{BatchCipher, EncryptionError, EncryptionKey, KeyProvider};
+
+#[derive(Debug)]
+pub(crate) struct ReplicaCiphers {
+    provider: Arc<dyn KeyProvider>,
+    keys: RwLock<HashMap<String, EncryptionKey>>,
+}
+
+impl ReplicaCiphers {
+    pub(crate) fn new(provider: Arc<dyn KeyProvider>) -> Self {
+        Self {
+            provider,
+            keys: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// cipher of replica for topic encryption, None if topic is not encrypted
+    pub(crate) async fn cipher(
+        &self,
+        replica: &ReplicaKey,
+        encryption: Option<&TopicEncryption>,
+    ) -> Result<Option<Arc<BatchCipher>>, EncryptionError> {
+        let Some(encryption) = encryption else {
+            return Ok(None);
+        };
+        let key = self.key(&encryption.key_id).await?;
+        Ok(Some(Arc::new(BatchCipher::new(&key, replica.clone()))))
+    }
+
+    async fn key(&self, key_id: &str) -> Result<EncryptionKey, EncryptionError> {
+        if let Some(key) = self.keys.read().await.get(key_id) {
+            return Ok(key.clone());
+        }
+
+        let key = self.provider.key(key_id).await?;
+        debug!(key_id, "loaded encryption key");
+        self.keys
+            .write()
+            .await
+            .insert(key_id.to_owned(), key.clone());
+        Ok(key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use async_trait::async_trait;
+
+    use fluvio_storage::encryption::{EncryptionKey, KEY_LEN};
+
+    use super::*;
+
+    #[derive(Debug, Default)]
+    struct CountingProvider(AtomicU32);
+
+    #[async_trait]
+    impl KeyProvider for CountingProvider {
+        async fn key(&self, key_id: &str) -> Result<EncryptionKey, EncryptionError> {
+            if key_id == "missing" {
+                return Err(EncryptionError::KeyNotFound(key_id.to_owned()));
+            }
+            self.0.fetch_add(1, Ordering::SeqCst);
+            Ok(EncryptionKey::new([3u8; KEY_LEN]))
+        }
+    }
+
+    #[fluvio_future::test]
+    async fn test_keys_are_cached() {
+        let provider = Arc::new(CountingProvider::default());
+        let ciphers = ReplicaCiphers::new(provider.clone());
+        let replica = ReplicaKey::new("orders", 0u32);
+
+        assert!(ciphers
+            .cipher(&replica, None)
+            .await
+            .expect("cipher")
+            .is_none());
+
+        let encryption = TopicEncryption::new("key1");
+        let first = ciphers
+            .cipher(&replica, Some(&encryption))
+            .await
+            .expect("cipher")
+            .expect("some");
+        let second = ciphers
+            .cipher(&ReplicaKey::new("orders", 1u32), Some(&encryption))
+            .await
+            .expect("cipher")
+            .expect("some");
+        assert_eq!(provider.0.load(Ordering::SeqCst), 1);
+
+        // ciphers of partitions share key, but records are bound to their partition
+        let sealed = first.seal(0, b"record").expect("seal");
+        assert_eq!(first.open(0, &sealed).expect("open"), b"record");
+        assert!(second.open(0, &sealed).is_err());
+
+        assert!(ciphers
+            .cipher(&replica, Some(&TopicEncryption::new("missing")))
+            .await
+            .is_err());
+    }
+
+    #[fluvio_future::test]
+    async fn test_spu_uses_custom_key_provider() {
+        use fluvio_storage::FileReplica;
+
+        use crate::config::SpuConfig;
+        use crate::core::GlobalContext;
+
+        let provider = Arc::new(CountingProvider::default());
+        let ctx = GlobalContext::<FileReplica>::new(SpuConfig::default())
+            .with_key_provider(provider.clone());
+
+        // default provider would look for key file which doesn't exist
+        assert!(ctx
+            .ciphers()
+            .cipher(
+                &ReplicaKey::new("orders", 0u32),
+                Some(&TopicEncryption::new("kms-key"))
+            )
+            .await
+            .expect("cipher")
+            .is_some());
+        assert_eq!(provider.0.load(Ordering::SeqCst), 1);
+    }
+}
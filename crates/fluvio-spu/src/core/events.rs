@@ -0,0 +1,233 @@
+//!
+//! # Cluster Events
+//!
+//! Lifecycle events of the cluster are written to the built-in events topic, so operators can
+//! consume cluster history with ordinary consumers. Records are keyed by event type and their
+//! value is the event in JSON.
+//!
+//! Topic and leader changes are seen by every SPU in metadata sent by SC, so only the leader of
+//! events partition writes them. Events which happen on a SPU, such as joining cluster or
+//! accepting mirror connection, are sent by that SPU to the events leader.
+//!
+use async_channel::{Receiver, Sender, TrySendError};
+use chrono::Utc;
+use serde::Serialize;
+use tracing::{debug, info, instrument};
+
+use fluvio_controlplane::replica::Replica;
+use fluvio_future::task::spawn;
+use fluvio_protocol::record::{Record, ReplicaKey};
+use fluvio_types::defaults::EVENTS_TOPIC;
+use fluvio_types::{PartitionId, SpuId};
+
+use crate::core::{DefaultSharedGlobalContext, SpecChange};
+
+/// events waiting to be written, events published while it is full are dropped
+const EVENTS_QUEUE_SIZE: usize = 1000;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub(crate) enum ClusterEvent {
+    TopicCreated {
+        topic: String,
+    },
+    LeaderChanged {
+        topic: String,
+        partition: PartitionId,
+        leader: SpuId,
+        previous_leader: SpuId,
+        leader_epoch: i32,
+    },
+    SpuJoined {
+        spu: SpuId,
+    },
+    MirrorConnected {
+        remote_cluster: String,
+        topic: String,
+        partition: PartitionId,
+    },
+}
+
+impl ClusterEvent {
+    /// event of replica metadata change, replicas from full sync are not new to cluster
+    pub(crate) fn from_replica_change(
+        change: &SpecChange<Replica>,
+        sync_all: bool,
+    ) -> Option<Self> {
+        match change {
+            SpecChange::Add(replica)
+                if !sync_all && replica.id.partition == 0 && !replica.is_being_deleted =>
+            {
+                Some(Self::TopicCreated {
+                    topic: replica.id.topic.clone(),
+                })
+            }
+            SpecChange::Mod(new_replica, old_replica)
+                if new_replica.leader != old_replica.leader && !new_replica.is_being_deleted =>
+            {
+                Some(Self::LeaderChanged {
+                    topic: new_replica.id.topic.clone(),
+                    partition: new_replica.id.partition,
+                    leader: new_replica.leader,
+                    previous_leader: old_replica.leader,
+                    leader_epoch: new_replica.leader_epoch,
+                })
+            }
+            _ => None,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Self::TopicCreated { .. } => "topic_created",
+            Self::LeaderChanged { .. } => "leader_changed",
+            Self::SpuJoined { .. } => "spu_joined",
+            Self::MirrorConnected { .. } => "mirror_connected",
+        }
+    }
+
+    /// event is derived from SC metadata which all SPUs receive
+    fn is_observed(&self) -> bool {
+        matches!(self, Self::TopicCreated { .. } | Self::LeaderChanged { .. })
+    }
+
+    fn into_record(self, source_spu: SpuId) -> Result<Record, serde_json::Error> {
+        let name = self.name();
+        let value = serde_json::to_vec(&EventRecord {
+            timestamp: Utc::now().timestamp_millis(),
+            source_spu,
+            event: self,
+        })?;
+        Ok(Record::new_key_value(name, value))
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct EventRecord {
+    timestamp: i64,
+    source_spu: SpuId,
+    #[serde(flatten)]
+    event: ClusterEvent,
+}
+
+/// queue of events to be written to events topic
+#[derive(Debug)]
+pub(crate) struct ClusterEvents {
+    sender: Sender<ClusterEvent>,
+    receiver: Receiver<ClusterEvent>,
+}
+
+impl Default for ClusterEvents {
+    fn default() -> Self {
+        let (sender, receiver) = async_channel::bounded(EVENTS_QUEUE_SIZE);
+        Self { sender, receiver }
+    }
+}
+
+impl ClusterEvents {
+    pub(crate) fn publish(&self, event: ClusterEvent) {
+        if let Err(TrySendError::Full(event)) = self.sender.try_send(event) {
+            debug!(?event, "events queue is full, dropping event");
+        }
+    }
+}
+
+pub(crate) struct ClusterEventPublisher {
+    ctx: DefaultSharedGlobalContext,
+    replica: ReplicaKey,
+}
+
+impl ClusterEventPublisher {
+    pub(crate) fn start(ctx: DefaultSharedGlobalContext) {
+        let publisher = Self {
+            ctx,
+            replica: ReplicaKey::new(EVENTS_TOPIC, <PartitionId as Default>::default()),
+        };
+        spawn(publisher.dispatch_loop());
+    }
+
+    #[instrument(name = "ClusterEventPublisher", skip(self))]
+    async fn dispatch_loop(self) {
+        info!(topic = EVENTS_TOPIC, "starting cluster event publisher");
+        let receiver = self.ctx.events().receiver.clone();
+        while let Ok(event) = receiver.recv().await {
+            if event.is_observed() && self.ctx.leaders_state().get(&self.replica).await.is_none() {
+                continue;
+            }
+            let record = match event.clone().into_record(self.ctx.local_spu_id()) {
+                Ok(record) => record,
+                Err(err) => {
+                    debug!(?event, %err, "cluster event can't be encoded");
+                    continue;
+                }
+            };
+            if let Err(err) = self.ctx.write_records(&self.replica, vec![record]).await {
+                debug!(?event, %err, "cluster event not written");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_replica_change_events() {
+        let replica = Replica::new(("topic", 0), 5001, vec![5001, 5002]);
+
+        assert_eq!(
+            ClusterEvent::from_replica_change(&SpecChange::Add(replica.clone()), false),
+            Some(ClusterEvent::TopicCreated {
+                topic: "topic".to_owned()
+            })
+        );
+        // replicas of full sync existed before
+        assert_eq!(
+            ClusterEvent::from_replica_change(&SpecChange::Add(replica.clone()), true),
+            None
+        );
+        // added partition is not new topic
+        assert_eq!(
+            ClusterEvent::from_replica_change(
+                &SpecChange::Add(Replica::new(("topic", 1), 5001, vec![5001])),
+                false
+            ),
+            None
+        );
+
+        let mut moved = replica.clone();
+        moved.leader = 5002;
+        moved.leader_epoch = 1;
+        assert_eq!(
+            ClusterEvent::from_replica_change(&SpecChange::Mod(moved, replica.clone()), true),
+            Some(ClusterEvent::LeaderChanged {
+                topic: "topic".to_owned(),
+                partition: 0,
+                leader: 5002,
+                previous_leader: 5001,
+                leader_epoch: 1,
+            })
+        );
+        assert_eq!(
+            ClusterEvent::from_replica_change(
+                &SpecChange::Mod(replica.clone(), replica.clone()),
+                false
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_event_record() {
+        let record = ClusterEvent::SpuJoined { spu: 5001 }
+            .into_record(5001)
+            .expect("record");
+        assert_eq!(record.key().expect("key").as_ref(), "spu_joined".as_bytes());
+        let value: serde_json::Value =
+            serde_json::from_slice(record.value().as_ref()).expect("json");
+        assert_eq!(value["type"], "spu_joined");
+        assert_eq!(value["spu"], 5001);
+        assert_eq!(value["source_spu"], 5001);
+    }
+}
@@ -0,0 +1,359 @@
+//!
+//! # Fetch Sessions
+//!
+//! Incremental fetch sessions let client fetching many partitions send its partition list once.
+//! Session remembers partitions and their fetch offsets, following requests of session only
+//! carry partitions whose fetch offset changed, new partitions and forgotten ones.
+//! Responses of incremental requests skip partitions without records or offset changes.
+//!
+//! Sessions are kept in memory only, client whose session was evicted or lost with restart of
+//! SPU gets `FetchSessionNotFoud` and starts new session.
+//!
+use std::collections::{BTreeMap, HashMap};
+use std::time::{Duration, Instant};
+
+use async_lock::Mutex;
+use tracing::debug;
+
+use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::record::Offset;
+use fluvio_spu_schema::fetch::{
+    FetchPartition, FetchableTopic, FileFetchResponse, ForgottenTopic, INITIAL_EPOCH,
+};
+use fluvio_types::PartitionId;
+
+/// upper bound of sessions cached by SPU
+const MAX_FETCH_SESSIONS: usize = 1000;
+
+/// sessions not used for longer than this are dropped
+const IDLE_SESSION_TIMEOUT: Duration = Duration::from_secs(120);
+
+#[derive(Debug, Default)]
+pub(crate) struct FetchSessions {
+    inner: Mutex<SessionCache>,
+}
+
+#[derive(Debug)]
+struct SessionCache {
+    next_id: i32,
+    sessions: HashMap<i32, FetchSession>,
+}
+
+impl Default for SessionCache {
+    fn default() -> Self {
+        Self {
+            next_id: 1,
+            sessions: HashMap::new(),
+        }
+    }
+}
+
+impl SessionCache {
+    /// session ids are positive, 0 means no session
+    fn next_id(&mut self) -> i32 {
+        loop {
+            let id = self.next_id;
+            self.next_id = if id == i32::MAX { 1 } else { id + 1 };
+            if !self.sessions.contains_key(&id) {
+                return id;
+            }
+        }
+    }
+
+    fn evict_idle(&mut self, now: Instant) {
+        self.sessions
+            .retain(|_, session| now.duration_since(session.last_used) < IDLE_SESSION_TIMEOUT);
+    }
+}
+
+#[derive(Debug)]
+struct FetchSession {
+    /// epoch expected in next request
+    epoch: i32,
+    last_used: Instant,
+    topics: BTreeMap<String, BTreeMap<PartitionId, SessionPartition>>,
+}
+
+impl FetchSession {
+    fn new(topics: &[FetchableTopic], now: Instant) -> Self {
+        let mut session = Self {
+            epoch: next_epoch(INITIAL_EPOCH),
+            last_used: now,
+            topics: BTreeMap::new(),
+        };
+        session.add_partitions(topics);
+        session
+    }
+
+    /// add new partitions and update fetch offsets of existing ones
+    fn add_partitions(&mut self, topics: &[FetchableTopic]) {
+        for topic in topics {
+            let partitions = self.topics.entry(topic.name.clone()).or_default();
+            for partition in &topic.fetch_partitions {
+                partitions
+                    .entry(partition.partition_index)
+                    .and_modify(|existing| existing.fetch = partition.clone())
+                    .or_insert_with(|| SessionPartition::new(partition.clone()));
+            }
+        }
+    }
+
+    fn forget_partitions(&mut self, forgotten: &[ForgottenTopic]) {
+        for topic in forgotten {
+            if let Some(partitions) = self.topics.get_mut(&topic.name) {
+                for index in &topic.forgotten_partition_indexes {
+                    partitions.remove(&(*index as PartitionId));
+                }
+                if partitions.is_empty() {
+                    self.topics.remove(&topic.name);
+                }
+            }
+        }
+    }
+
+    fn fetchable_topics(&self) -> Vec<FetchableTopic> {
+        self.topics
+            .iter()
+            .map(|(name, partitions)| FetchableTopic {
+                name: name.clone(),
+                fetch_partitions: partitions
+                    .values()
+                    .map(|partition| partition.fetch.clone())
+                    .collect(),
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug)]
+struct SessionPartition {
+    fetch: FetchPartition,
+    /// offsets sent in last response, None until partition is part of response
+    high_watermark: Option<Offset>,
+    log_start_offset: Option<Offset>,
+}
+
+impl SessionPartition {
+    fn new(fetch: FetchPartition) -> Self {
+        Self {
+            fetch,
+            high_watermark: None,
+            log_start_offset: None,
+        }
+    }
+}
+
+/// epochs wrap around to 1, 0 is reserved for new session
+fn next_epoch(epoch: i32) -> i32 {
+    if epoch == i32::MAX {
+        1
+    } else {
+        epoch + 1
+    }
+}
+
+impl FetchSessions {
+    /// create session with partitions of full request.
+    /// Returns None when cache is full, client then fetches without session
+    pub(crate) async fn create(&self, topics: &[FetchableTopic]) -> Option<i32> {
+        let now = Instant::now();
+        let mut cache = self.inner.lock().await;
+        cache.evict_idle(now);
+        if cache.sessions.len() >= MAX_FETCH_SESSIONS {
+            debug!(
+                sessions = cache.sessions.len(),
+                "fetch session cache is full"
+            );
+            return None;
+        }
+        let session_id = cache.next_id();
+        cache
+            .sessions
+            .insert(session_id, FetchSession::new(topics, now));
+        debug!(session_id, "created fetch session");
+        Some(session_id)
+    }
+
+    /// apply incremental request to session, returns all partitions of session to fetch
+    pub(crate) async fn update(
+        &self,
+        session_id: i32,
+        epoch: i32,
+        topics: &[FetchableTopic],
+        forgotten: &[ForgottenTopic],
+    ) -> Result<Vec<FetchableTopic>, ErrorCode> {
+        let mut cache = self.inner.lock().await;
+        let Some(session) = cache.sessions.get_mut(&session_id) else {
+            return Err(ErrorCode::FetchSessionNotFoud);
+        };
+        if session.epoch != epoch {
+            return Err(ErrorCode::FetchSessionEpochMismatch {
+                session_id,
+                expected: session.epoch,
+                received: epoch,
+            });
+        }
+        session.epoch = next_epoch(epoch);
+        session.last_used = Instant::now();
+        session.forget_partitions(forgotten);
+        session.add_partitions(topics);
+        Ok(session.fetchable_topics())
+    }
+
+    pub(crate) async fn remove(&self, session_id: i32) {
+        if self
+            .inner
+            .lock()
+            .await
+            .sessions
+            .remove(&session_id)
+            .is_some()
+        {
+            debug!(session_id, "closed fetch session");
+        }
+    }
+
+    /// remember offsets sent to client. Incremental response only keeps partitions
+    /// with records, errors or offsets changed since last response
+    pub(crate) async fn record_response(
+        &self,
+        session_id: i32,
+        response: &mut FileFetchResponse,
+        incremental: bool,
+    ) {
+        let mut cache = self.inner.lock().await;
+        let Some(session) = cache.sessions.get_mut(&session_id) else {
+            return;
+        };
+        for topic in &mut response.topics {
+            let Some(partitions) = session.topics.get_mut(&topic.name) else {
+                continue;
+            };
+            topic.partitions.retain(|partition| {
+                let Some(state) = partitions.get_mut(&partition.partition_index) else {
+                    return true;
+                };
+                let changed = state.high_watermark != Some(partition.high_watermark)
+                    || state.log_start_offset != Some(partition.log_start_offset);
+                state.high_watermark = Some(partition.high_watermark);
+                state.log_start_offset = Some(partition.log_start_offset);
+                !incremental
+                    || changed
+                    || partition.error_code.is_error()
+                    || partition.records.len() > 0
+            });
+        }
+        response
+            .topics
+            .retain(|topic| !incremental || !topic.partitions.is_empty());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fluvio_spu_schema::fetch::{FilePartitionResponse, FileTopicResponse};
+
+    use super::*;
+
+    fn fetch_topic(name: &str, partitions: &[(PartitionId, Offset)]) -> FetchableTopic {
+        FetchableTopic {
+            name: name.to_owned(),
+            fetch_partitions: partitions
+                .iter()
+                .map(|(partition_index, fetch_offset)| FetchPartition {
+                    partition_index: *partition_index,
+                    fetch_offset: *fetch_offset,
+                    ..Default::default()
+                })
+                .collect(),
+        }
+    }
+
+    fn response(name: &str, partitions: &[(PartitionId, Offset)]) -> FileFetchResponse {
+        FileFetchResponse {
+            topics: vec![FileTopicResponse {
+                name: name.to_owned(),
+                partitions: partitions
+                    .iter()
+                    .map(|(partition_index, high_watermark)| FilePartitionResponse {
+                        partition_index: *partition_index,
+                        high_watermark: *high_watermark,
+                        ..Default::default()
+                    })
+                    .collect(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[fluvio_future::test]
+    async fn test_incremental_session_keeps_partitions() {
+        let sessions = FetchSessions::default();
+        let session_id = sessions
+            .create(&[fetch_topic("test", &[(0, 0), (1, 0), (2, 0)])])
+            .await
+            .expect("session");
+        assert!(session_id > 0);
+
+        // only offset of partition 1 changed, partition 2 is forgotten
+        let topics = sessions
+            .update(
+                session_id,
+                1,
+                &[fetch_topic("test", &[(1, 10)])],
+                &[ForgottenTopic {
+                    name: "test".to_owned(),
+                    forgotten_partition_indexes: vec![2],
+                }],
+            )
+            .await
+            .expect("update");
+        assert_eq!(topics.len(), 1);
+        let offsets: Vec<_> = topics[0]
+            .fetch_partitions
+            .iter()
+            .map(|p| (p.partition_index, p.fetch_offset))
+            .collect();
+        assert_eq!(offsets, vec![(0, 0), (1, 10)]);
+
+        // epoch must follow previous request
+        assert!(matches!(
+            sessions.update(session_id, 1, &[], &[]).await,
+            Err(ErrorCode::FetchSessionEpochMismatch { expected: 2, .. })
+        ));
+
+        sessions.remove(session_id).await;
+        assert!(matches!(
+            sessions.update(session_id, 2, &[], &[]).await,
+            Err(ErrorCode::FetchSessionNotFoud)
+        ));
+    }
+
+    #[fluvio_future::test]
+    async fn test_incremental_response_skips_unchanged_partitions() {
+        let sessions = FetchSessions::default();
+        let session_id = sessions
+            .create(&[fetch_topic("test", &[(0, 0), (1, 0)])])
+            .await
+            .expect("session");
+
+        let mut full = response("test", &[(0, 5), (1, 5)]);
+        sessions.record_response(session_id, &mut full, false).await;
+        assert_eq!(full.topics[0].partitions.len(), 2);
+
+        // partition 0 is unchanged and has no records
+        let mut incremental = response("test", &[(0, 5), (1, 7)]);
+        sessions
+            .record_response(session_id, &mut incremental, true)
+            .await;
+        assert_eq!(incremental.topics[0].partitions.len(), 1);
+        assert_eq!(incremental.topics[0].partitions[0].partition_index, 1);
+
+        let mut unchanged = response("test", &[(0, 5), (1, 7)]);
+        sessions
+            .record_response(session_id, &mut unchanged, true)
+            .await;
+        assert!(unchanged.topics.is_empty());
+    }
+}
@@ -9,7 +9,10 @@ use std::fmt::Debug;
 use tracing::{debug, error, instrument};
 
 use fluvio_types::SpuId;
+use fluvio_types::event::StickyEvent;
 use fluvio_storage::ReplicaStorage;
+use fluvio_storage::encryption::{FileKeyProvider, KeyProvider};
+use fluvio_service::rate_limit::{RateLimiter, SharedRateLimiter};
 
 use crate::config::SpuConfig;
 use crate::kv::consumer::SharedConsumerOffsetStorages;
@@ -22,6 +25,17 @@ use crate::control_plane::{StatusMessageSink, SharedStatusUpdate};
 use crate::core::metrics::SpuMetrics;
 use crate::smartengine::SmartEngine;
 
+use super::consumer_group::{GroupCoordinator, SharedGroupCoordinator};
+use super::encryption::ReplicaCiphers;
+use super::fetch_session::FetchSessions;
+use super::quota::ClientQuotas;
+use super::schema::{FormatValidator, SchemaValidator};
+use super::memory::MemoryBudget;
+use super::admission::RequestAdmission;
+use super::events::ClusterEvents;
+use super::acl::AclLocalStore;
+use super::dynamic_config::{DynamicConfig, SharedDynamicConfig};
+use super::txn_coordinator::{TxnCoordinator, SharedTxnCoordinator};
 use super::leader_client::LeaderConnections;
 use super::mirror::MirrorLocalStore;
 use super::mirror::SharedMirrorLocalStore;
@@ -50,6 +64,19 @@ pub struct GlobalContext<S> {
     mirrors: SharedMirrorLocalStore,
     metrics: Arc<SpuMetrics>,
     consumer_offset: SharedConsumerOffsetStorages,
+    group_coordinator: SharedGroupCoordinator,
+    quotas: Arc<ClientQuotas>,
+    txn_coordinator: SharedTxnCoordinator,
+    ciphers: Arc<ReplicaCiphers>,
+    fetch_sessions: FetchSessions,
+    memory: Arc<MemoryBudget>,
+    admission: Arc<RequestAdmission>,
+    events: ClusterEvents,
+    acl: AclLocalStore,
+    dynamic_config: SharedDynamicConfig,
+    rate_limiter: SharedRateLimiter,
+    shutdown: Arc<StickyEvent>,
+    schema_validator: Arc<dyn SchemaValidator>,
 }
 
 // -----------------------------------
@@ -68,6 +95,21 @@ where
         let spus = SpuLocalStore::new_shared();
         let replicas = ReplicaStore::new_shared();
         let metrics = Arc::new(SpuMetrics::new());
+        let started_at = chrono::Utc::now().timestamp_millis();
+        let member_prefix = format!("{}-{}", spu_config.id, started_at);
+
+        let quotas = Arc::new(ClientQuotas::new(spu_config.quota.clone()));
+        let memory = MemoryBudget::shared(&spu_config.memory);
+        let admission = RequestAdmission::shared(&spu_config.admission, metrics.clone());
+        let key_provider = Arc::new(FileKeyProvider::new(&spu_config.log.encryption_key_dir));
+        let dynamic_config = DynamicConfig::shared(&spu_config, quotas.clone());
+        let rate_limiter = RateLimiter::shared(spu_config.rate_limit);
+        let leaders = LeaderConnections::shared(
+            spus.clone(),
+            replicas.clone(),
+            spu_config.peer_auth_token.clone(),
+            spu_config.secrets.resolver(),
+        );
 
         GlobalContext {
             spu_localstore: spus.clone(),
@@ -79,13 +121,41 @@ where
             spu_followers: FollowerNotifier::shared(),
             status_update: StatusMessageSink::shared(),
             sm_engine: SmartEngine::new(),
-            leaders: LeaderConnections::shared(spus, replicas),
+            leaders,
             mirrors: MirrorLocalStore::new_shared(),
             metrics,
             consumer_offset: SharedConsumerOffsetStorages::default(),
+            group_coordinator: GroupCoordinator::new_shared(member_prefix),
+            quotas,
+            // producer ids of restarted coordinator don't overlap ones assigned before
+            txn_coordinator: TxnCoordinator::new_shared(started_at << 10),
+            ciphers: Arc::new(ReplicaCiphers::new(key_provider)),
+            fetch_sessions: FetchSessions::default(),
+            memory,
+            admission,
+            events: ClusterEvents::default(),
+            acl: AclLocalStore::default(),
+            dynamic_config,
+            rate_limiter,
+            shutdown: StickyEvent::shared(),
+            schema_validator: Arc::new(FormatValidator),
         }
     }
 
+    /// replace key provider of encrypted topics, such as one backed by external KMS
+    #[allow(dead_code)]
+    pub fn with_key_provider(mut self, provider: Arc<dyn KeyProvider>) -> Self {
+        self.ciphers = Arc::new(ReplicaCiphers::new(provider));
+        self
+    }
+
+    /// replace validator of records of topics with schema, such as one backed by schema registry
+    #[allow(dead_code)]
+    pub fn with_schema_validator(mut self, validator: Arc<dyn SchemaValidator>) -> Self {
+        self.schema_validator = validator;
+        self
+    }
+
     pub fn spu_localstore_owned(&self) -> SharedSpuLocalStore {
         self.spu_localstore.clone()
     }
@@ -173,20 +243,82 @@ where
     pub(crate) fn consumer_offset(&self) -> &SharedConsumerOffsetStorages {
         &self.consumer_offset
     }
+
+    pub(crate) fn group_coordinator(&self) -> &GroupCoordinator {
+        &self.group_coordinator
+    }
+
+    pub(crate) fn quotas(&self) -> Arc<ClientQuotas> {
+        self.quotas.clone()
+    }
+
+    pub(crate) fn txn_coordinator(&self) -> &TxnCoordinator {
+        &self.txn_coordinator
+    }
+
+    pub(crate) fn fetch_sessions(&self) -> &FetchSessions {
+        &self.fetch_sessions
+    }
+
+    pub(crate) fn memory(&self) -> &Arc<MemoryBudget> {
+        &self.memory
+    }
+
+    pub(crate) fn admission(&self) -> &Arc<RequestAdmission> {
+        &self.admission
+    }
+
+    pub(crate) fn events(&self) -> &ClusterEvents {
+        &self.events
+    }
+
+    pub(crate) fn acl(&self) -> &AclLocalStore {
+        &self.acl
+    }
+
+    pub(crate) fn dynamic_config(&self) -> &SharedDynamicConfig {
+        &self.dynamic_config
+    }
+
+    pub(crate) fn rate_limiter(&self) -> &SharedRateLimiter {
+        &self.rate_limiter
+    }
+
+    /// notified when spu starts shutting down
+    pub(crate) fn shutdown(&self) -> &Arc<StickyEvent> {
+        &self.shutdown
+    }
+
+    pub(crate) fn ciphers(&self) -> &ReplicaCiphers {
+        &self.ciphers
+    }
+
+    pub(crate) fn schema_validator(&self) -> &dyn SchemaValidator {
+        self.schema_validator.as_ref()
+    }
 }
 
 mod file_replica {
 
+    use chrono::Utc;
+    use fluvio::spu::SpuDirectory;
     use fluvio_controlplane::{
         sc_api::remove::ReplicaRemovedRequest, replica::Replica,
         spu_api::update_replica::UpdateReplicaRequest,
     };
+    use fluvio_protocol::link::ErrorCode;
+    use fluvio_protocol::record::{Batch, RawRecords, Record, RecordSet, ReplicaKey};
+    use fluvio_spu_schema::produce::{
+        DefaultPartitionRequest, DefaultProduceRequest, DefaultTopicRequest,
+    };
     use tracing::{trace, warn};
 
     use fluvio_storage::FileReplica;
+    use fluvio_storage::backend::FILE_BACKEND;
     use flv_util::actions::Actions;
 
     use crate::core::SpecChange;
+    use crate::core::events::ClusterEvent;
 
     use super::*;
 
@@ -196,7 +328,83 @@ mod file_replica {
         StorageError(anyhow::Error),
     }
 
+    /// replicas of this context are stored in files, other backends need `BackendReplica`
+    fn check_storage_backend(replica: &Replica) -> anyhow::Result<()> {
+        match replica
+            .storage
+            .as_ref()
+            .and_then(|storage| storage.backend.as_deref())
+        {
+            Some(backend) if backend != FILE_BACKEND => Err(anyhow::anyhow!(
+                "storage backend {backend} of replica {} is not supported by file storage",
+                replica.id
+            )),
+            _ => Ok(()),
+        }
+    }
+
     impl GlobalContext<FileReplica> {
+        /// write records to replica, locally if this SPU leads it or sent to its leader otherwise
+        pub(crate) async fn write_records(
+            &self,
+            replica: &ReplicaKey,
+            records: Vec<Record>,
+        ) -> Result<(), ErrorCode> {
+            let mut batch: Batch = Batch::default();
+            for record in records {
+                batch.add_record(record);
+            }
+            let now = Utc::now().timestamp_millis();
+            let header = batch.get_mut_header();
+            header.first_timestamp = now;
+            header.max_time_stamp = now;
+            let batch = Batch::<RawRecords>::try_from(batch)
+                .map_err(|err| ErrorCode::Other(err.to_string()))?;
+            let mut records = RecordSet {
+                batches: vec![batch],
+            };
+
+            if let Some(leader) = self.leaders_state().get(replica).await {
+                leader
+                    .write_record_set(&mut records, self.follower_notifier())
+                    .await
+                    .map_err(|err| ErrorCode::Other(err.to_string()))?;
+                return Ok(());
+            }
+
+            let partition_request = DefaultPartitionRequest {
+                partition_index: replica.partition,
+                records,
+                ..Default::default()
+            };
+            let mut topic_request = DefaultTopicRequest {
+                name: replica.topic.clone(),
+                ..Default::default()
+            };
+            topic_request.partitions.push(partition_request);
+            let mut request = DefaultProduceRequest::default();
+            request.topics.push(topic_request);
+
+            let socket = self
+                .leaders()
+                .create_serial_socket(replica)
+                .await
+                .map_err(|err| ErrorCode::Other(err.to_string()))?;
+            let response = socket
+                .send_receive(request)
+                .await
+                .map_err(|err| ErrorCode::Other(err.to_string()))?;
+            match response
+                .responses
+                .into_iter()
+                .flat_map(|topic| topic.partitions)
+                .find(|partition| partition.error_code.is_error())
+            {
+                Some(partition) => Err(partition.error_code),
+                None => Ok(()),
+            }
+        }
+
         /// Promote follower replica as leader,
         /// This is done in 3 steps
         /// // 1: Remove follower replica from followers state
@@ -249,11 +457,19 @@ mod file_replica {
             &self,
             request: UpdateReplicaRequest,
         ) -> Vec<ReplicaChange> {
+            let sync_all = !request.all.is_empty();
             let changes = self
                 .replica_localstore()
                 .apply(request.all, request.changes);
 
-            self.apply_replica_actions(changes).await
+            let outputs = self.apply_replica_actions(changes, sync_all).await;
+            // SPU syncs all replicas when it connects to SC
+            if sync_all {
+                self.events().publish(ClusterEvent::SpuJoined {
+                    spu: self.local_spu_id(),
+                });
+            }
+            outputs
         }
 
         /// apply changes to
@@ -263,6 +479,7 @@ mod file_replica {
         async fn apply_replica_actions(
             &self,
             actions: Actions<SpecChange<Replica>>,
+            sync_all: bool,
         ) -> Vec<ReplicaChange> {
             trace!( actions = ?actions,"replica actions");
 
@@ -276,10 +493,15 @@ mod file_replica {
             let mut outputs = vec![];
             for replica_action in actions.into_iter() {
                 debug!(action = ?replica_action,"applying");
+                if let Some(event) = ClusterEvent::from_replica_change(&replica_action, sync_all) {
+                    self.events().publish(event);
+                }
 
                 match replica_action {
                     SpecChange::Add(new_replica) => {
-                        if new_replica.is_being_deleted {
+                        if let Err(err) = check_storage_backend(&new_replica) {
+                            outputs.push(ReplicaChange::StorageError(err));
+                        } else if new_replica.is_being_deleted {
                             outputs.push(ReplicaChange::Remove(
                                 self.remove_leader_replica(new_replica).await,
                             ));
@@ -347,12 +569,34 @@ mod file_replica {
                                     }
                                 }
                             } else if new_replica.leader == local_id {
-                                if self.leaders_state().get(&new_replica.id).await.is_some() {
+                                if let Some(leader) =
+                                    self.leaders_state().get(&new_replica.id).await
+                                {
+                                    if new_replica.replicas != old_replica.replicas {
+                                        leader.update_followers(&new_replica.replicas).await;
+                                    }
                                 } else {
                                     error!("leader controller was not found: {}", new_replica.id);
                                 }
                             } else {
-                                self.followers_state().update_replica(new_replica).await;
+                                // replica set changes when partition is reassigned
+                                let was_follower = old_replica.replicas.contains(&local_id);
+                                let is_follower = new_replica.replicas.contains(&local_id);
+                                if is_follower && !was_follower {
+                                    debug!(replica = %new_replica.id, "added as follower");
+                                    if let Err(err) = self
+                                        .followers_state_owned()
+                                        .add_replica(self, new_replica)
+                                        .await
+                                    {
+                                        outputs.push(ReplicaChange::StorageError(err));
+                                    }
+                                } else if was_follower && !is_follower {
+                                    debug!(replica = %new_replica.id, "removed as follower");
+                                    self.remove_follower_replica(new_replica).await;
+                                } else {
+                                    self.followers_state().update_replica(new_replica).await;
+                                }
                             }
                         }
                     }
@@ -411,6 +655,15 @@ mod file_replica {
             }
         }
 
+        /// drop follower storage and replicate it again from leader
+        pub async fn resync_follower_replica(&self, replica: Replica) -> anyhow::Result<()> {
+            self.remove_follower_replica(replica.clone()).await;
+            self.followers_state_owned()
+                .add_replica(self, replica)
+                .await?;
+            Ok(())
+        }
+
         /// Demote leader replica as follower.
         /// This only happens on manual election
         #[instrument(
@@ -422,6 +675,7 @@ mod file_replica {
         pub async fn demote_replica(&self, replica: Replica) {
             if let Some(leader_replica_state) = self.leaders_state().remove(&replica.id).await {
                 drop(leader_replica_state);
+                self.metrics().storage().remove(&replica.id);
                 if let Err(err) = self
                     .followers_state_owned()
                     .add_replica(self, replica)
@@ -9,6 +9,7 @@ use fluvio::spu::SpuDirectory;
 use fluvio_controlplane_metadata::partition::ReplicaKey;
 use fluvio_socket::{ClientConfig, MultiplexerSocket, StreamSocket, VersionedSerialSocket};
 use fluvio_types::{SpuId, PartitionId};
+use fluvio_types::secret::{SecretRef, SecretResolver};
 use tracing::{debug, instrument};
 
 use super::SharedReplicaLocalStore;
@@ -22,6 +23,9 @@ pub struct LeaderConnections {
     replicas: SharedReplicaLocalStore,
     leaders: Arc<Mutex<HashMap<SpuId, StreamSocket>>>,
     metrics: Arc<ClientMetrics>,
+    /// bearer token presented to leaders which authenticate clients
+    auth_token: Option<SecretRef>,
+    secrets: SecretResolver,
 }
 
 impl LeaderConnections {
@@ -31,10 +35,22 @@ impl LeaderConnections {
             replicas,
             leaders: Default::default(),
             metrics: Arc::new(ClientMetrics::new()),
+            auth_token: None,
+            secrets: SecretResolver::default(),
         }
     }
-    pub fn shared(spus: SharedSpuLocalStore, replicas: SharedReplicaLocalStore) -> Arc<Self> {
-        Arc::new(LeaderConnections::new(spus, replicas))
+
+    pub fn shared(
+        spus: SharedSpuLocalStore,
+        replicas: SharedReplicaLocalStore,
+        auth_token: Option<SecretRef>,
+        secrets: SecretResolver,
+    ) -> Arc<Self> {
+        Arc::new(LeaderConnections {
+            auth_token,
+            secrets,
+            ..LeaderConnections::new(spus, replicas)
+        })
     }
 
     /// create a connection to leader, it can't find it, return
@@ -42,7 +58,15 @@ impl LeaderConnections {
     async fn connect_to_leader(&self, leader: SpuId) -> Result<StreamSocket, FluvioError> {
         if let Some(spu) = self.spus.spec(&leader) {
             debug!("connecting to spu : {:#?}", spu);
-            let client_config = ClientConfig::with_addr(spu.public_endpoint.addr());
+            let mut client_config = ClientConfig::with_addr(spu.public_endpoint.addr());
+            if let Some(secret) = &self.auth_token {
+                // resolved on every connection to pick up rotated token
+                let token = self
+                    .secrets
+                    .resolve(secret)
+                    .map_err(|err| FluvioError::Other(format!("peer auth token: {err}")))?;
+                client_config.set_auth_token(Some(token));
+            }
             let versioned_socket = client_config.connect().await?;
             let (socket, config, versions) = versioned_socket.split();
             Ok(StreamSocket::new(
@@ -0,0 +1,208 @@
+//!
+//! # Memory Budget
+//!
+//! Accounts memory held by in-flight produce requests, fetch responses built in memory and
+//! SmartModule instances against one SPU wide limit. Memory is reserved before it's used and
+//! released when its permit is dropped.
+//! Produce requests and fetch responses wait until enough memory is released, which slows
+//! down their connection. SmartModule instances are rejected instead, they live as long as
+//! their stream.
+//!
+use std::fmt;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use event_listener::Event;
+use tracing::debug;
+
+use crate::config::MemoryConfig;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub(crate) enum MemoryKind {
+    Produce,
+    Fetch,
+    SmartModule,
+}
+
+/// memory reserved by kind
+#[derive(Debug, Default)]
+pub(crate) struct MemoryUsage {
+    produce: AtomicU64,
+    fetch: AtomicU64,
+    smartmodule: AtomicU64,
+}
+
+impl MemoryUsage {
+    fn get(&self, kind: MemoryKind) -> &AtomicU64 {
+        match kind {
+            MemoryKind::Produce => &self.produce,
+            MemoryKind::Fetch => &self.fetch,
+            MemoryKind::SmartModule => &self.smartmodule,
+        }
+    }
+}
+
+pub(crate) struct MemoryBudget {
+    /// None is unlimited, usage is still counted
+    limit: Option<u64>,
+    used: AtomicU64,
+    usage: MemoryUsage,
+    released: Event,
+}
+
+impl fmt::Debug for MemoryBudget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MemoryBudget")
+            .field("limit", &self.limit)
+            .field("used", &self.used())
+            .field("usage", &self.usage)
+            .finish()
+    }
+}
+
+impl MemoryBudget {
+    pub(crate) fn shared(config: &MemoryConfig) -> Arc<Self> {
+        Arc::new(Self {
+            limit: config.max_bytes,
+            used: AtomicU64::new(0),
+            usage: MemoryUsage::default(),
+            released: Event::new(),
+        })
+    }
+
+    pub(crate) fn used(&self) -> u64 {
+        self.used.load(Ordering::SeqCst)
+    }
+
+    /// reserve memory, waiting until enough is released.
+    /// Reservation larger than limit is granted once nothing else is reserved
+    pub(crate) async fn acquire(self: &Arc<Self>, kind: MemoryKind, bytes: u64) -> MemoryPermit {
+        loop {
+            if let Some(permit) = self.reserve(kind, bytes, true) {
+                return permit;
+            }
+            let listener = self.released.listen();
+            // memory may have been released before listener was registered
+            if let Some(permit) = self.reserve(kind, bytes, true) {
+                return permit;
+            }
+            debug!(?kind, bytes, budget = ?self, "waiting for memory");
+            listener.await;
+        }
+    }
+
+    /// reserve memory if available now
+    pub(crate) fn try_acquire(
+        self: &Arc<Self>,
+        kind: MemoryKind,
+        bytes: u64,
+    ) -> Option<MemoryPermit> {
+        self.reserve(kind, bytes, false)
+    }
+
+    fn reserve(
+        self: &Arc<Self>,
+        kind: MemoryKind,
+        bytes: u64,
+        allow_oversized: bool,
+    ) -> Option<MemoryPermit> {
+        let reserved = self
+            .used
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |used| {
+                match self.limit {
+                    Some(limit) if used + bytes > limit && !(allow_oversized && used == 0) => None,
+                    _ => Some(used + bytes),
+                }
+            })
+            .is_ok();
+        if !reserved {
+            return None;
+        }
+        self.usage.get(kind).fetch_add(bytes, Ordering::SeqCst);
+        Some(MemoryPermit {
+            budget: self.clone(),
+            kind,
+            bytes,
+        })
+    }
+
+    fn release(&self, kind: MemoryKind, bytes: u64) {
+        self.usage.get(kind).fetch_sub(bytes, Ordering::SeqCst);
+        self.used.fetch_sub(bytes, Ordering::SeqCst);
+        self.released.notify(usize::MAX);
+    }
+}
+
+/// memory reserved in budget, released on drop
+#[derive(Debug)]
+pub(crate) struct MemoryPermit {
+    budget: Arc<MemoryBudget>,
+    kind: MemoryKind,
+    bytes: u64,
+}
+
+impl Drop for MemoryPermit {
+    fn drop(&mut self) {
+        self.budget.release(self.kind, self.bytes);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use fluvio_future::timer::sleep;
+    use fluvio_future::task::spawn;
+
+    use super::*;
+
+    fn budget(max_bytes: Option<u64>) -> Arc<MemoryBudget> {
+        MemoryBudget::shared(&MemoryConfig { max_bytes })
+    }
+
+    #[fluvio_future::test]
+    async fn test_permit_released_on_drop() {
+        let budget = budget(Some(100));
+        let permit = budget.acquire(MemoryKind::Produce, 60).await;
+        assert_eq!(budget.used(), 60);
+        assert!(budget.try_acquire(MemoryKind::SmartModule, 50).is_none());
+        let fetch = budget
+            .try_acquire(MemoryKind::Fetch, 40)
+            .expect("available");
+        assert_eq!(budget.usage.fetch.load(Ordering::SeqCst), 40);
+        drop(permit);
+        drop(fetch);
+        assert_eq!(budget.used(), 0);
+        assert_eq!(budget.usage.produce.load(Ordering::SeqCst), 0);
+    }
+
+    #[fluvio_future::test]
+    async fn test_acquire_waits_for_release() {
+        let budget = budget(Some(100));
+        let permit = budget.acquire(MemoryKind::Produce, 80).await;
+
+        let waiting = budget.clone();
+        let handle = spawn(async move { waiting.acquire(MemoryKind::Fetch, 50).await });
+        sleep(Duration::from_millis(50)).await;
+        assert_eq!(budget.used(), 80);
+
+        drop(permit);
+        let fetch = handle.await;
+        assert_eq!(budget.used(), 50);
+        drop(fetch);
+
+        // oversized reservation is granted when budget is empty
+        let oversized = budget.acquire(MemoryKind::Produce, 200).await;
+        assert_eq!(budget.used(), 200);
+        drop(oversized);
+    }
+
+    #[fluvio_future::test]
+    async fn test_unlimited_budget_counts_usage() {
+        let budget = budget(None);
+        let _permit = budget
+            .try_acquire(MemoryKind::SmartModule, u32::MAX as u64)
+            .expect("unlimited");
+        assert_eq!(budget.used(), u32::MAX as u64);
+    }
+}
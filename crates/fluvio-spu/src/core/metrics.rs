@@ -1,11 +1,18 @@
 use std::{
-    sync::atomic::{AtomicU64, Ordering},
+    collections::BTreeMap,
+    sync::{
+        Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
     ops::AddAssign,
 };
 
+use fluvio_controlplane_metadata::partition::{ReplicaKey, StorageUsage};
 use fluvio_protocol::record::Batch;
 use fluvio_spu_schema::fetch::FilePartitionResponse;
+use fluvio_types::Timestamp;
 use serde::Serialize;
+use serde::ser::{Serializer, SerializeStruct};
 
 use crate::smartengine::SmartModuleChainMetrics;
 
@@ -14,6 +21,12 @@ pub(crate) struct SpuMetrics {
     inbound: Activity,
     outbound: Activity,
     smartmodule: SmartModuleChainMetrics,
+    scrubber: ScrubberMetrics,
+    consumer_offsets: ConsumerOffsetMetrics,
+    schema: SchemaMetrics,
+    latency: LatencyMetrics,
+    storage: StorageMetrics,
+    admission: AdmissionMetrics,
 }
 
 impl SpuMetrics {
@@ -32,6 +45,272 @@ impl SpuMetrics {
     pub fn chain_metrics(&self) -> &SmartModuleChainMetrics {
         &self.smartmodule
     }
+
+    pub fn scrubber(&self) -> &ScrubberMetrics {
+        &self.scrubber
+    }
+
+    pub fn consumer_offsets(&self) -> &ConsumerOffsetMetrics {
+        &self.consumer_offsets
+    }
+
+    pub fn schema(&self) -> &SchemaMetrics {
+        &self.schema
+    }
+
+    pub fn latency(&self) -> &LatencyMetrics {
+        &self.latency
+    }
+
+    pub fn storage(&self) -> &StorageMetrics {
+        &self.storage
+    }
+
+    pub fn admission(&self) -> &AdmissionMetrics {
+        &self.admission
+    }
+}
+
+/// Produce and fetch requests admitted under concurrency limit
+#[derive(Default, Debug, Serialize)]
+pub(crate) struct AdmissionMetrics {
+    admitted: AtomicU64,
+    /// admitted after waiting in queue
+    queued: AtomicU64,
+    /// shed because queue was full
+    rejected: AtomicU64,
+}
+
+impl AdmissionMetrics {
+    pub(crate) fn add_admitted(&self, queued: bool) {
+        self.admitted.fetch_add(1, Ordering::SeqCst);
+        if queued {
+            self.queued.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    pub(crate) fn add_rejected(&self) {
+        self.rejected.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+impl AdmissionMetrics {
+    pub(crate) fn queued(&self) -> u64 {
+        self.queued.load(Ordering::SeqCst)
+    }
+
+    pub(crate) fn rejected(&self) -> u64 {
+        self.rejected.load(Ordering::SeqCst)
+    }
+}
+
+/// Disk usage of leader replicas, last reported by storage of each replica
+#[derive(Default, Debug)]
+pub(crate) struct StorageMetrics {
+    replicas: Mutex<BTreeMap<ReplicaKey, StorageUsage>>,
+}
+
+impl StorageMetrics {
+    pub(crate) fn update(&self, replica: &ReplicaKey, usage: StorageUsage) {
+        if let Ok(mut replicas) = self.replicas.lock() {
+            replicas.insert(replica.clone(), usage);
+        }
+    }
+
+    pub(crate) fn remove(&self, replica: &ReplicaKey) {
+        if let Ok(mut replicas) = self.replicas.lock() {
+            replicas.remove(replica);
+        }
+    }
+
+    /// usage summed by topic
+    fn topics(replicas: &BTreeMap<ReplicaKey, StorageUsage>) -> BTreeMap<&str, StorageUsage> {
+        let mut topics: BTreeMap<&str, StorageUsage> = BTreeMap::new();
+        for (replica, usage) in replicas {
+            *topics.entry(replica.topic.as_str()).or_default() += *usage;
+        }
+        topics
+    }
+}
+
+impl Serialize for StorageMetrics {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let replicas = self
+            .replicas
+            .lock()
+            .map(|replicas| replicas.clone())
+            .unwrap_or_default();
+        let topics: BTreeMap<&str, UsageGauges> = Self::topics(&replicas)
+            .into_iter()
+            .map(|(topic, usage)| (topic, usage.into()))
+            .collect();
+        let partitions: BTreeMap<String, UsageGauges> = replicas
+            .iter()
+            .map(|(replica, usage)| (replica.to_string(), (*usage).into()))
+            .collect();
+        let mut state = serializer.serialize_struct("StorageMetrics", 2)?;
+        state.serialize_field("topics", &topics)?;
+        state.serialize_field("partitions", &partitions)?;
+        state.end()
+    }
+}
+
+#[derive(Serialize)]
+struct UsageGauges {
+    segment_bytes: u64,
+    index_bytes: u64,
+    tiered_bytes: u64,
+}
+
+impl From<StorageUsage> for UsageGauges {
+    fn from(usage: StorageUsage) -> Self {
+        Self {
+            segment_bytes: usage.segment_bytes,
+            index_bytes: usage.index_bytes,
+            tiered_bytes: usage.tiered_bytes,
+        }
+    }
+}
+
+/// Latencies of records going through leader replicas
+#[derive(Default, Debug, Serialize)]
+pub(crate) struct LatencyMetrics {
+    /// from producer timestamp of batch to append in leader log
+    produce_to_append: LatencyHistogram,
+    /// from append to commit by in-sync replicas
+    append_to_commit: LatencyHistogram,
+    /// from append to batch being sent to consumer
+    append_to_fetch: LatencyHistogram,
+}
+
+impl LatencyMetrics {
+    pub(crate) fn produce_to_append(&self) -> &LatencyHistogram {
+        &self.produce_to_append
+    }
+
+    pub(crate) fn append_to_commit(&self) -> &LatencyHistogram {
+        &self.append_to_commit
+    }
+
+    pub(crate) fn append_to_fetch(&self) -> &LatencyHistogram {
+        &self.append_to_fetch
+    }
+}
+
+const LATENCY_BUCKETS: usize = 24;
+
+/// Latency distribution in milliseconds.
+/// Bucket `i` counts latencies below `2^i` ms, last bucket counts everything above
+#[derive(Default, Debug)]
+pub(crate) struct LatencyHistogram {
+    buckets: [AtomicU64; LATENCY_BUCKETS],
+    count: AtomicU64,
+    sum: AtomicU64,
+    max: AtomicU64,
+}
+
+impl LatencyHistogram {
+    pub(crate) fn record(&self, latency_ms: u64) {
+        let bucket = (u64::BITS - latency_ms.leading_zeros()) as usize;
+        self.buckets[bucket.min(LATENCY_BUCKETS - 1)].fetch_add(1, Ordering::SeqCst);
+        self.count.fetch_add(1, Ordering::SeqCst);
+        self.sum.fetch_add(latency_ms, Ordering::SeqCst);
+        self.max.fetch_max(latency_ms, Ordering::SeqCst);
+    }
+
+    /// record latency between two timestamps, clocks going backwards are counted as zero
+    pub(crate) fn record_between(&self, start: Timestamp, end: Timestamp) {
+        self.record((end - start).max(0) as u64);
+    }
+
+    /// upper bound of latency of `quantile` of records
+    pub(crate) fn percentile(&self, quantile: f64) -> u64 {
+        let count = self.count.load(Ordering::SeqCst);
+        if count == 0 {
+            return 0;
+        }
+        let max = self.max.load(Ordering::SeqCst);
+        let target = ((count as f64 * quantile).ceil() as u64).max(1);
+        let mut seen = 0;
+        for (bucket, counter) in self.buckets.iter().enumerate() {
+            seen += counter.load(Ordering::SeqCst);
+            if seen >= target {
+                return (1u64 << bucket).min(max);
+            }
+        }
+        max
+    }
+}
+
+impl Serialize for LatencyHistogram {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let count = self.count.load(Ordering::SeqCst);
+        let sum = self.sum.load(Ordering::SeqCst);
+        let mut state = serializer.serialize_struct("LatencyHistogram", 6)?;
+        state.serialize_field("count", &count)?;
+        state.serialize_field("mean_ms", &sum.checked_div(count).unwrap_or_default())?;
+        state.serialize_field("max_ms", &self.max.load(Ordering::SeqCst))?;
+        state.serialize_field("p50_ms", &self.percentile(0.5))?;
+        state.serialize_field("p90_ms", &self.percentile(0.9))?;
+        state.serialize_field("p99_ms", &self.percentile(0.99))?;
+        state.end()
+    }
+}
+
+/// Results of background storage scrubbing
+#[derive(Default, Debug, Serialize)]
+pub(crate) struct ScrubberMetrics {
+    segments: AtomicU64,
+    batches: AtomicU64,
+    corrupt_ranges: AtomicU64,
+    repairs: AtomicU64,
+}
+
+impl ScrubberMetrics {
+    pub(crate) fn add_segment(&self, batches: u64, corrupt_ranges: u64) {
+        self.segments.fetch_add(1, Ordering::SeqCst);
+        self.batches.fetch_add(batches, Ordering::SeqCst);
+        self.corrupt_ranges
+            .fetch_add(corrupt_ranges, Ordering::SeqCst);
+    }
+
+    pub(crate) fn add_repair(&self) {
+        self.repairs.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+/// Records validated against topic schemas
+#[derive(Default, Debug, Serialize)]
+pub(crate) struct SchemaMetrics {
+    validated: AtomicU64,
+    invalid: AtomicU64,
+    rejected_produces: AtomicU64,
+}
+
+impl SchemaMetrics {
+    pub(crate) fn add_validated(&self, records: u64, invalid: u64) {
+        self.validated.fetch_add(records, Ordering::SeqCst);
+        self.invalid.fetch_add(invalid, Ordering::SeqCst);
+    }
+
+    pub(crate) fn add_rejected(&self) {
+        self.rejected_produces.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+/// Results of consumer offset expiry
+#[derive(Default, Debug, Serialize)]
+pub(crate) struct ConsumerOffsetMetrics {
+    expiry_runs: AtomicU64,
+    expired: AtomicU64,
+}
+
+impl ConsumerOffsetMetrics {
+    pub(crate) fn add_expired(&self, expired: u64) {
+        self.expiry_runs.fetch_add(1, Ordering::SeqCst);
+        self.expired.fetch_add(expired, Ordering::SeqCst);
+    }
 }
 
 #[derive(Default, Debug, Serialize)]
@@ -94,6 +373,10 @@ impl IncreaseValue {
     pub(crate) fn new(records: u64, bytes: u64) -> Self {
         Self { records, bytes }
     }
+
+    pub(crate) fn bytes(&self) -> u64 {
+        self.bytes
+    }
 }
 
 // Measuring of serialized data. `bytes` is length of file slice, `records` is an offset's change
@@ -170,4 +453,47 @@ mod tests {
         assert_eq!(activity.connector.records.load(Ordering::SeqCst), 1);
         assert_eq!(activity.connector.bytes.load(Ordering::SeqCst), 123);
     }
+
+    #[test]
+    fn test_latency_histogram_percentiles() {
+        let histogram = LatencyHistogram::default();
+        assert_eq!(histogram.percentile(0.99), 0);
+
+        for latency in 1..=100 {
+            histogram.record(latency);
+        }
+        histogram.record_between(50, 40);
+
+        assert_eq!(histogram.count.load(Ordering::SeqCst), 101);
+        assert_eq!(histogram.buckets[0].load(Ordering::SeqCst), 1);
+        // 32..64 ms fall in bucket below 64 ms
+        assert_eq!(histogram.percentile(0.5), 64);
+        // above largest bucket bound, capped by max
+        assert_eq!(histogram.percentile(0.99), 100);
+        assert_eq!(histogram.percentile(0.01), 2);
+    }
+
+    #[test]
+    fn test_storage_usage_by_topic() {
+        let usage = |segment_bytes, index_bytes| StorageUsage {
+            segment_bytes,
+            index_bytes,
+            tiered_bytes: 0,
+        };
+        let metrics = StorageMetrics::default();
+        metrics.update(&ReplicaKey::new("orders", 0u32), usage(100, 10));
+        metrics.update(&ReplicaKey::new("orders", 1u32), usage(50, 5));
+        metrics.update(&ReplicaKey::new("events", 0u32), usage(7, 0));
+        // latest report replaces previous one
+        metrics.update(&ReplicaKey::new("orders", 1u32), usage(60, 6));
+
+        let replicas = metrics.replicas.lock().unwrap().clone();
+        let topics = StorageMetrics::topics(&replicas);
+        assert_eq!(topics["orders"], usage(160, 16));
+        assert_eq!(topics["events"], usage(7, 0));
+
+        metrics.remove(&ReplicaKey::new("events", 0u32));
+        let replicas = metrics.replicas.lock().unwrap().clone();
+        assert!(!StorageMetrics::topics(&replicas).contains_key("events"));
+    }
 }
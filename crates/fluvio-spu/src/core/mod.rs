@@ -1,12 +1,23 @@
 mod global_context;
 mod store;
 mod leader_client;
+mod consumer_group;
+pub(crate) mod txn_coordinator;
+pub(crate) mod quota;
+pub(crate) mod encryption;
+pub(crate) mod fetch_session;
+pub(crate) mod memory;
+pub(crate) mod admission;
+pub(crate) mod events;
+pub(crate) mod acl;
+pub(crate) mod dynamic_config;
 
 pub mod spus;
 pub mod replica;
 pub mod smartmodule;
 pub mod metrics;
 pub mod mirror;
+pub mod schema;
 
 pub use self::global_context::{GlobalContext, ReplicaChange};
 pub use self::store::Spec;
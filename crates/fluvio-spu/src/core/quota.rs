@@ -0,0 +1,244 @@
+//!
+//! # Client Quotas
+//!
+//! Limits produce/fetch byte rate and request rate of each client, keyed by client id.
+//! Usage is counted in one second windows. Client exceeding its quota is throttled:
+//! its response is delayed until usage falls back under the quota and the delay is
+//! reported in `throttle_time_ms` of the response.
+//!
+use std::collections::HashMap;
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
+
+use async_lock::Mutex;
+use tracing::debug;
+
+use crate::config::{ClientQuota, QuotaConfig};
+
+const QUOTA_WINDOW: Duration = Duration::from_secs(1);
+
+/// upper bound of single throttle, so tiny quota doesn't stall client for long
+const MAX_THROTTLE: Duration = Duration::from_secs(30);
+
+/// usage of clients idle for longer than this is dropped
+const IDLE_CLIENT_TIMEOUT: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum QuotaType {
+    Produce,
+    Fetch,
+}
+
+#[derive(Debug)]
+pub(crate) struct ClientQuotas {
+    config: RwLock<QuotaConfig>,
+    usage: Mutex<HashMap<String, ClientUsage>>,
+}
+
+#[derive(Debug)]
+struct ClientUsage {
+    window_start: Instant,
+    produce_bytes: u64,
+    fetch_bytes: u64,
+    requests: u64,
+}
+
+impl ClientUsage {
+    fn new(now: Instant) -> Self {
+        Self {
+            window_start: now,
+            produce_bytes: 0,
+            fetch_bytes: 0,
+            requests: 0,
+        }
+    }
+
+    /// start new window if current one has passed
+    fn roll(&mut self, now: Instant) {
+        if now.duration_since(self.window_start) >= QUOTA_WINDOW {
+            *self = Self::new(now);
+        }
+    }
+}
+
+impl ClientQuotas {
+    pub(crate) fn new(config: QuotaConfig) -> Self {
+        Self {
+            config: RwLock::new(config),
+            usage: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// replace quotas, usage in current window is kept
+    pub(crate) fn set_config(&self, config: QuotaConfig) {
+        if let Ok(mut current) = self.config.write() {
+            *current = config;
+        }
+    }
+
+    /// record request with its bytes, returns how long client must be throttled
+    pub(crate) async fn record_request(
+        &self,
+        client_id: &str,
+        quota_type: QuotaType,
+        bytes: u64,
+    ) -> Duration {
+        self.record(client_id, quota_type, bytes, 1).await
+    }
+
+    /// record bytes sent without new request, as in stream fetch
+    pub(crate) async fn record_bytes(
+        &self,
+        client_id: &str,
+        quota_type: QuotaType,
+        bytes: u64,
+    ) -> Duration {
+        self.record(client_id, quota_type, bytes, 0).await
+    }
+
+    async fn record(
+        &self,
+        client_id: &str,
+        quota_type: QuotaType,
+        bytes: u64,
+        requests: u64,
+    ) -> Duration {
+        let quota = self
+            .config
+            .read()
+            .map(|config| config.quota(client_id).clone())
+            .unwrap_or_default();
+        if quota.is_unlimited() {
+            return Duration::ZERO;
+        }
+
+        let now = Instant::now();
+        let mut usage = self.usage.lock().await;
+        if !usage.contains_key(client_id) {
+            usage.retain(|_, client| now.duration_since(client.window_start) < IDLE_CLIENT_TIMEOUT);
+        }
+        let client = usage
+            .entry(client_id.to_owned())
+            .or_insert_with(|| ClientUsage::new(now));
+        client.roll(now);
+        client.requests += requests;
+        let (used_bytes, byte_rate) = match quota_type {
+            QuotaType::Produce => {
+                client.produce_bytes += bytes;
+                (client.produce_bytes, quota.produce_bytes_per_sec)
+            }
+            QuotaType::Fetch => {
+                client.fetch_bytes += bytes;
+                (client.fetch_bytes, quota.fetch_bytes_per_sec)
+            }
+        };
+
+        let elapsed = now.duration_since(client.window_start);
+        let throttle = throttle_time(used_bytes, byte_rate, elapsed).max(throttle_time(
+            client.requests,
+            quota.requests_per_sec,
+            elapsed,
+        ));
+        if !throttle.is_zero() {
+            debug!(client_id, ?quota_type, ?throttle, "client quota exceeded");
+        }
+        throttle
+    }
+}
+
+impl QuotaConfig {
+    fn quota(&self, client_id: &str) -> &ClientQuota {
+        self.overrides.get(client_id).unwrap_or(&self.default)
+    }
+}
+
+/// time until `used` falls under `limit` per window, counted from now
+fn throttle_time(used: u64, limit: Option<u64>, elapsed: Duration) -> Duration {
+    match limit {
+        Some(limit) if used > limit => {
+            // window's own quota is already allowed, only excess over it has to be waited out
+            let allowed_at = QUOTA_WINDOW.mul_f64((used - limit) as f64 / limit.max(1) as f64);
+            allowed_at.saturating_sub(elapsed).min(MAX_THROTTLE)
+        }
+        _ => Duration::ZERO,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[fluvio_future::test]
+    async fn test_produce_byte_quota() {
+        let quotas = ClientQuotas::new(QuotaConfig {
+            default: ClientQuota {
+                produce_bytes_per_sec: Some(1000),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+
+        let throttle = quotas
+            .record_request("client", QuotaType::Produce, 1000)
+            .await;
+        assert_eq!(throttle, Duration::ZERO);
+
+        let throttle = quotas
+            .record_request("client", QuotaType::Produce, 1000)
+            .await;
+        assert!(throttle > Duration::from_millis(900));
+        assert!(throttle <= Duration::from_secs(1));
+
+        // fetch is not limited
+        let throttle = quotas
+            .record_request("client", QuotaType::Fetch, 10_000)
+            .await;
+        assert_eq!(throttle, Duration::ZERO);
+
+        // other clients have their own usage
+        let throttle = quotas
+            .record_request("other", QuotaType::Produce, 500)
+            .await;
+        assert_eq!(throttle, Duration::ZERO);
+    }
+
+    #[fluvio_future::test]
+    async fn test_request_quota_override() {
+        let mut config = QuotaConfig {
+            default: ClientQuota {
+                requests_per_sec: Some(1),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        config
+            .overrides
+            .insert("unlimited".to_owned(), ClientQuota::default());
+        let quotas = ClientQuotas::new(config);
+
+        // stream fetch bytes don't count as requests
+        for _ in 0..3 {
+            assert_eq!(
+                quotas.record_bytes("client", QuotaType::Fetch, 100).await,
+                Duration::ZERO
+            );
+        }
+        assert_eq!(
+            quotas.record_request("client", QuotaType::Fetch, 0).await,
+            Duration::ZERO
+        );
+        assert!(!quotas
+            .record_request("client", QuotaType::Fetch, 0)
+            .await
+            .is_zero());
+
+        for _ in 0..10 {
+            assert_eq!(
+                quotas
+                    .record_request("unlimited", QuotaType::Fetch, 0)
+                    .await,
+                Duration::ZERO
+            );
+        }
+    }
+}
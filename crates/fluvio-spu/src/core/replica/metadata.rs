@@ -1,5 +1,6 @@
 use fluvio_controlplane::replica::Replica;
 use fluvio_protocol::record::ReplicaKey;
+use fluvio_types::PartitionCount;
 
 use crate::core::Spec;
 use crate::core::LocalStore;
@@ -19,3 +20,13 @@ impl Spec for Replica {
 }
 
 pub type ReplicaStore = LocalStore<Replica>;
+
+impl ReplicaStore {
+    /// number of partitions of topic, SC syncs replicas of all partitions to every SPU
+    pub fn topic_partitions(&self, topic: &str) -> PartitionCount {
+        self.read()
+            .keys()
+            .filter(|replica| replica.topic == topic)
+            .count() as PartitionCount
+    }
+}
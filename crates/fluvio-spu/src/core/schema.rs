@@ -0,0 +1,142 @@
+//!
+//! # Record Schema Validation
+//!
+//! Leader validates values of produced records against schema associated with topic before
+//! records are written. Validator is pluggable, registry backed validator can replace the default
+//! one which only checks that JSON values are well-formed. Avro and Protobuf values are accepted
+//! by default validator since resolving their schemas requires a registry.
+//!
+use std::fmt::Debug;
+
+use fluvio_controlplane_metadata::topic::{SchemaFormat, TopicSchema};
+use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::record::{Batch, RawRecords};
+
+/// Checks record values against topic schema
+pub trait SchemaValidator: Debug + Send + Sync {
+    /// reason why value doesn't conform to schema
+    fn validate(&self, schema: &TopicSchema, value: &[u8]) -> Result<(), String>;
+}
+
+/// Validates well-formedness of values of formats which don't need registry
+#[derive(Debug, Default)]
+pub struct FormatValidator;
+
+impl SchemaValidator for FormatValidator {
+    fn validate(&self, schema: &TopicSchema, value: &[u8]) -> Result<(), String> {
+        match schema.format {
+            SchemaFormat::Json => serde_json::from_slice::<serde_json::Value>(value)
+                .map(|_| ())
+                .map_err(|err| format!("invalid json: {err}")),
+            SchemaFormat::Avro | SchemaFormat::Protobuf => Ok(()),
+        }
+    }
+}
+
+/// Outcome of validating records of produce request
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct SchemaReport {
+    pub records: u64,
+    pub invalid: u64,
+    /// reason of first invalid record
+    pub first_error: Option<String>,
+}
+
+/// validate values of all records in batches
+pub(crate) fn validate_batches(
+    validator: &dyn SchemaValidator,
+    schema: &TopicSchema,
+    batches: &[Batch<RawRecords>],
+) -> Result<SchemaReport, ErrorCode> {
+    let mut report = SchemaReport::default();
+    for batch in batches {
+        let records = batch
+            .memory_records()
+            .map_err(|_| ErrorCode::CompressionError)?;
+        for record in records {
+            report.records += 1;
+            if let Err(reason) = validator.validate(schema, record.value.as_ref()) {
+                report.invalid += 1;
+                report.first_error.get_or_insert(reason);
+            }
+        }
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use fluvio_protocol::record::{Record, RecordData};
+
+    use super::*;
+
+    fn raw_batch(values: &[&str]) -> Batch<RawRecords> {
+        let records: Vec<Record> = values
+            .iter()
+            .map(|value| Record::new(RecordData::from(value.as_bytes().to_vec())))
+            .collect();
+        Batch::from(records).try_into().expect("raw batch")
+    }
+
+    #[test]
+    fn test_validate_json_batches() {
+        let schema = TopicSchema::new(SchemaFormat::Json, "orders-value");
+        let batches = vec![
+            raw_batch(&[r#"{"id":1}"#, "not json"]),
+            raw_batch(&["[1,2]", "{"]),
+        ];
+
+        let report = validate_batches(&FormatValidator, &schema, &batches).expect("report");
+
+        assert_eq!(report.records, 4);
+        assert_eq!(report.invalid, 2);
+        assert!(report
+            .first_error
+            .expect("error")
+            .starts_with("invalid json"));
+    }
+
+    #[test]
+    fn test_avro_accepted_without_registry() {
+        let schema = TopicSchema::new(SchemaFormat::Avro, "orders-value");
+        let batches = vec![raw_batch(&["not json"])];
+
+        let report = validate_batches(&FormatValidator, &schema, &batches).expect("report");
+
+        assert_eq!(report.records, 1);
+        assert_eq!(report.invalid, 0);
+        assert!(report.first_error.is_none());
+    }
+
+    #[derive(Debug)]
+    struct RejectAll;
+
+    impl SchemaValidator for RejectAll {
+        fn validate(&self, schema: &TopicSchema, _value: &[u8]) -> Result<(), String> {
+            Err(format!("unknown subject {}", schema.reference))
+        }
+    }
+
+    #[test]
+    fn test_spu_uses_custom_validator() {
+        use std::sync::Arc;
+
+        use fluvio_storage::FileReplica;
+
+        use crate::config::SpuConfig;
+        use crate::core::GlobalContext;
+
+        let ctx = GlobalContext::<FileReplica>::new(SpuConfig::default())
+            .with_schema_validator(Arc::new(RejectAll));
+        let schema = TopicSchema::new(SchemaFormat::Avro, "orders-value");
+        let batches = vec![raw_batch(&["accepted by format validator"])];
+
+        let report = validate_batches(ctx.schema_validator(), &schema, &batches).expect("report");
+
+        assert_eq!(report.invalid, 1);
+        assert_eq!(
+            report.first_error.as_deref(),
+            Some("unknown subject orders-value")
+        );
+    }
+}
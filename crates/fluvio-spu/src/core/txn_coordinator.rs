@@ -0,0 +1,359 @@
+//!
+//! # Transaction Coordinator
+//!
+//! Assigns producer id and epoch to transactional producers and keeps partitions of their
+//! open transactions. Transaction is ended by writing commit or abort marker to each of its
+//! partitions; until then read committed consumers don't read past its first record.
+//!
+//! Coordinator runs on the leader of the consumer offsets partition, same as consumer groups.
+//! Its state is not persisted: transactions open when coordinator moves are never ended,
+//! and they keep holding back read committed consumers of their partitions.
+//!
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_lock::Mutex;
+use tracing::{debug, info};
+
+use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::record::ReplicaKey;
+
+pub(crate) type SharedTxnCoordinator = Arc<TxnCoordinator>;
+
+#[derive(Debug)]
+pub(crate) struct TxnCoordinator {
+    transactions: Mutex<TxnMap>,
+}
+
+#[derive(Debug)]
+struct TxnMap {
+    next_producer_id: i64,
+    transactions: HashMap<String, TxnEntry>,
+}
+
+#[derive(Debug)]
+struct TxnEntry {
+    producer_id: i64,
+    epoch: i16,
+    timeout: Duration,
+    state: TxnState,
+}
+
+#[derive(Debug)]
+enum TxnState {
+    Empty,
+    Ongoing {
+        partitions: BTreeSet<ReplicaKey>,
+        started: Instant,
+    },
+    /// outcome is decided, markers are being written
+    Ending {
+        partitions: BTreeSet<ReplicaKey>,
+        commit: bool,
+    },
+}
+
+/// markers to write for ended transaction
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) struct TxnEnd {
+    pub(crate) transactional_id: String,
+    pub(crate) producer_id: i64,
+    pub(crate) producer_epoch: i16,
+    pub(crate) commit: bool,
+    pub(crate) partitions: Vec<ReplicaKey>,
+}
+
+impl TxnEntry {
+    /// decide outcome of open transaction, returns markers to write
+    fn end(&mut self, transactional_id: &str, commit: bool) -> Option<TxnEnd> {
+        let (partitions, commit) = match std::mem::replace(&mut self.state, TxnState::Empty) {
+            TxnState::Empty => return None,
+            TxnState::Ongoing { partitions, .. } => (partitions, commit),
+            // outcome can't change once decided
+            TxnState::Ending { partitions, commit } => (partitions, commit),
+        };
+        let end = TxnEnd {
+            transactional_id: transactional_id.to_owned(),
+            producer_id: self.producer_id,
+            producer_epoch: self.epoch,
+            commit,
+            partitions: partitions.iter().cloned().collect(),
+        };
+        self.state = TxnState::Ending { partitions, commit };
+        Some(end)
+    }
+
+    fn check_producer(&self, producer_id: i64, epoch: i16) -> Result<(), ErrorCode> {
+        if producer_id != self.producer_id || epoch != self.epoch {
+            Err(ErrorCode::ProducerFenced {
+                producer_id,
+                epoch,
+                current_epoch: self.epoch,
+            })
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl TxnCoordinator {
+    /// producer ids are assigned starting from `first_producer_id`
+    pub(crate) fn new_shared(first_producer_id: i64) -> SharedTxnCoordinator {
+        Arc::new(Self {
+            transactions: Mutex::new(TxnMap {
+                next_producer_id: first_producer_id,
+                transactions: HashMap::new(),
+            }),
+        })
+    }
+
+    /// assign producer id and new epoch to transactional id.
+    /// Returns markers of transaction left open by previous epoch
+    pub(crate) async fn init(
+        &self,
+        transactional_id: &str,
+        timeout: Duration,
+    ) -> (i64, i16, Option<TxnEnd>) {
+        let mut txns = self.transactions.lock().await;
+        let next_producer_id = txns.next_producer_id;
+        let mut new_producer = false;
+        let entry = txns
+            .transactions
+            .entry(transactional_id.to_owned())
+            .or_insert_with(|| {
+                new_producer = true;
+                TxnEntry {
+                    producer_id: next_producer_id,
+                    epoch: -1,
+                    timeout,
+                    state: TxnState::Empty,
+                }
+            });
+
+        let pending = entry.end(transactional_id, false);
+        entry.timeout = timeout;
+        if entry.epoch == i16::MAX {
+            // epoch is exhausted, continue as new producer
+            entry.producer_id = next_producer_id;
+            entry.epoch = 0;
+            new_producer = true;
+        } else {
+            entry.epoch += 1;
+        }
+        let (producer_id, epoch) = (entry.producer_id, entry.epoch);
+        if new_producer {
+            txns.next_producer_id += 1;
+        }
+
+        info!(
+            transactional_id,
+            producer_id, epoch, "transactional producer initialized"
+        );
+        (producer_id, epoch, pending)
+    }
+
+    /// add partitions to transaction, starting new one if none is open
+    pub(crate) async fn add_partitions(
+        &self,
+        transactional_id: &str,
+        producer_id: i64,
+        epoch: i16,
+        partitions: Vec<ReplicaKey>,
+    ) -> Result<(), ErrorCode> {
+        let mut txns = self.transactions.lock().await;
+        let entry = txns.entry(transactional_id)?;
+        entry.check_producer(producer_id, epoch)?;
+
+        match &mut entry.state {
+            TxnState::Empty => {
+                debug!(transactional_id, "transaction started");
+                entry.state = TxnState::Ongoing {
+                    partitions: partitions.into_iter().collect(),
+                    started: Instant::now(),
+                };
+            }
+            TxnState::Ongoing {
+                partitions: current,
+                ..
+            } => current.extend(partitions),
+            TxnState::Ending { .. } => {
+                return Err(ErrorCode::TransactionEnding {
+                    transactional_id: transactional_id.to_owned(),
+                })
+            }
+        }
+        Ok(())
+    }
+
+    /// decide outcome of open transaction, returns markers to write.
+    /// Ending transaction again returns same markers, so failed writes can be retried
+    pub(crate) async fn end(
+        &self,
+        transactional_id: &str,
+        producer_id: i64,
+        epoch: i16,
+        commit: bool,
+    ) -> Result<TxnEnd, ErrorCode> {
+        let mut txns = self.transactions.lock().await;
+        let entry = txns.entry(transactional_id)?;
+        entry.check_producer(producer_id, epoch)?;
+
+        if let TxnState::Ending {
+            commit: decided, ..
+        } = entry.state
+        {
+            if decided != commit {
+                return Err(ErrorCode::TransactionEnding {
+                    transactional_id: transactional_id.to_owned(),
+                });
+            }
+        }
+        entry
+            .end(transactional_id, commit)
+            .ok_or_else(|| ErrorCode::TransactionNotInProgress {
+                transactional_id: transactional_id.to_owned(),
+            })
+    }
+
+    /// all markers of transaction have been written
+    pub(crate) async fn complete(&self, end: &TxnEnd) {
+        let mut txns = self.transactions.lock().await;
+        if let Some(entry) = txns.transactions.get_mut(&end.transactional_id) {
+            if entry.producer_id == end.producer_id
+                && matches!(entry.state, TxnState::Ending { .. })
+            {
+                debug!(
+                    transactional_id = end.transactional_id,
+                    commit = end.commit,
+                    "transaction ended"
+                );
+                entry.state = TxnState::Empty;
+            }
+        }
+    }
+
+    /// abort transactions open for longer than their timeout, returns markers to write
+    pub(crate) async fn expire(&self) -> Vec<TxnEnd> {
+        let now = Instant::now();
+        let mut txns = self.transactions.lock().await;
+        txns.transactions
+            .iter_mut()
+            .filter(|(_, entry)| match entry.state {
+                TxnState::Ongoing { started, .. } => now.duration_since(started) > entry.timeout,
+                _ => false,
+            })
+            .filter_map(|(transactional_id, entry)| {
+                info!(transactional_id, "transaction timed out, aborting");
+                entry.end(transactional_id, false)
+            })
+            .collect()
+    }
+}
+
+impl TxnMap {
+    fn entry(&mut self, transactional_id: &str) -> Result<&mut TxnEntry, ErrorCode> {
+        self.transactions.get_mut(transactional_id).ok_or_else(|| {
+            ErrorCode::TransactionNotInProgress {
+                transactional_id: transactional_id.to_owned(),
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TIMEOUT: Duration = Duration::from_secs(60);
+
+    fn replica(partition: u32) -> ReplicaKey {
+        ReplicaKey::new("topic", partition)
+    }
+
+    #[fluvio_future::test]
+    async fn test_commit_transaction() {
+        let coordinator = TxnCoordinator::new_shared(100);
+        let (producer_id, epoch, pending) = coordinator.init("txn", TIMEOUT).await;
+        assert_eq!((producer_id, epoch, pending), (100, 0, None));
+
+        coordinator
+            .add_partitions("txn", producer_id, epoch, vec![replica(0)])
+            .await
+            .expect("add");
+        coordinator
+            .add_partitions("txn", producer_id, epoch, vec![replica(1), replica(0)])
+            .await
+            .expect("add");
+
+        let end = coordinator
+            .end("txn", producer_id, epoch, true)
+            .await
+            .expect("end");
+        assert!(end.commit);
+        assert_eq!(end.partitions, vec![replica(0), replica(1)]);
+
+        // decided outcome can be retried but not changed
+        assert_eq!(
+            coordinator.end("txn", producer_id, epoch, true).await,
+            Ok(end.clone())
+        );
+        assert!(coordinator
+            .end("txn", producer_id, epoch, false)
+            .await
+            .is_err());
+
+        coordinator.complete(&end).await;
+        assert!(coordinator
+            .end("txn", producer_id, epoch, true)
+            .await
+            .is_err());
+    }
+
+    #[fluvio_future::test]
+    async fn test_init_fences_previous_epoch() {
+        let coordinator = TxnCoordinator::new_shared(100);
+        let (producer_id, epoch, _) = coordinator.init("txn", TIMEOUT).await;
+        coordinator
+            .add_partitions("txn", producer_id, epoch, vec![replica(0)])
+            .await
+            .expect("add");
+
+        let (new_producer_id, new_epoch, pending) = coordinator.init("txn", TIMEOUT).await;
+        assert_eq!((new_producer_id, new_epoch), (producer_id, epoch + 1));
+        let pending = pending.expect("open transaction is aborted");
+        assert!(!pending.commit);
+        assert_eq!(pending.partitions, vec![replica(0)]);
+
+        assert_eq!(
+            coordinator
+                .add_partitions("txn", producer_id, epoch, vec![replica(1)])
+                .await,
+            Err(ErrorCode::ProducerFenced {
+                producer_id,
+                epoch,
+                current_epoch: new_epoch
+            })
+        );
+
+        // other transactional ids get their own producer
+        let (other_producer_id, _, _) = coordinator.init("other", TIMEOUT).await;
+        assert_eq!(other_producer_id, 101);
+    }
+
+    #[fluvio_future::test]
+    async fn test_expire_transaction() {
+        let coordinator = TxnCoordinator::new_shared(0);
+        let (producer_id, epoch, _) = coordinator.init("txn", Duration::ZERO).await;
+        coordinator
+            .add_partitions("txn", producer_id, epoch, vec![replica(0)])
+            .await
+            .expect("add");
+        fluvio_future::timer::sleep(Duration::from_millis(5)).await;
+
+        let expired = coordinator.expire().await;
+        assert_eq!(expired.len(), 1);
+        assert!(!expired[0].commit);
+        assert!(coordinator.expire().await.is_empty());
+    }
+}
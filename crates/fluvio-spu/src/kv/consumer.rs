@@ -7,7 +7,7 @@ use std::{
 
 use anyhow::Result;
 use async_rwlock::RwLock;
-use tracing::trace;
+use tracing::{debug, trace};
 
 use fluvio_kv_storage::KVStorage;
 use fluvio_protocol::{record::ReplicaKey, Encoder, Decoder};
@@ -180,6 +180,29 @@ impl SharableConsumerOffsetStorage {
     pub async fn list(&self) -> Result<Vec<(ConsumerOffsetKey, ConsumerOffset)>> {
         self.0.read().await.entries().await
     }
+
+    /// delete offsets not modified for `retention_secs`, returns number of deleted offsets
+    pub async fn expire(&self, retention_secs: u64) -> Result<usize> {
+        let mut storage = self.0.write().await;
+        let expired = expired_offsets(storage.entries().await?, now_timestamp(), retention_secs);
+        for key in &expired {
+            debug!(?key, "expiring consumer offset");
+            storage.delete(key).await?;
+        }
+        Ok(expired.len())
+    }
+}
+
+fn expired_offsets(
+    entries: Vec<(ConsumerOffsetKey, ConsumerOffset)>,
+    now: TimestampSecs,
+    retention_secs: u64,
+) -> Vec<ConsumerOffsetKey> {
+    entries
+        .into_iter()
+        .filter(|(_, offset)| now.saturating_sub(offset.modified_time) >= retention_secs)
+        .map(|(key, _)| key)
+        .collect()
 }
 
 fn now_timestamp() -> TimestampSecs {
@@ -247,6 +270,53 @@ mod tests {
         //then
     }
 
+    #[test]
+    fn test_expired_offsets() {
+        let active = ConsumerOffsetKey::new(("topic1", 0), "active");
+        let inactive = ConsumerOffsetKey::new(("topic1", 0), "inactive");
+        let entries = vec![
+            (active, ConsumerOffset::with(1, 950)),
+            (inactive.clone(), ConsumerOffset::with(2, 900)),
+        ];
+
+        assert_eq!(expired_offsets(entries.clone(), 1000, 100), vec![inactive]);
+        assert!(expired_offsets(entries.clone(), 1000, 101).is_empty());
+        // clock going backwards doesn't expire anything
+        assert!(expired_offsets(entries, 800, 100).is_empty());
+    }
+
+    #[fluvio_future::test]
+    async fn test_expire_inactive_consumers() {
+        //given
+        let leader = create_offset_replica("test_expire_inactive_consumers").await;
+        let notifier = FollowerNotifier::shared();
+        let storage: SharableConsumerOffsetStorage =
+            ConsumerOffsetStorage::new(leader.clone(), notifier).into();
+        let active = ConsumerOffsetKey::new(("topic1", 0), "active");
+        let inactive = ConsumerOffsetKey::new(("topic1", 0), "inactive");
+        storage
+            .put(active.clone(), ConsumerOffset::new(1))
+            .await
+            .expect("put active");
+        storage
+            .put(
+                inactive.clone(),
+                ConsumerOffset::with(2, now_timestamp() - 3600),
+            )
+            .await
+            .expect("put inactive");
+
+        //when
+        let expired = storage.expire(60).await.expect("expire");
+
+        //then
+        assert_eq!(expired, 1);
+        assert!(storage.get(&active).await.expect("get").is_some());
+        assert!(storage.get(&inactive).await.expect("get").is_none());
+
+        leader.remove().await.expect("removed");
+    }
+
     async fn create_offset_replica(dir: impl AsRef<Path>) -> LeaderReplicaState<FileReplica> {
         let base_dir = temp_dir().join(dir);
         ensure_clean_dir(&base_dir);
@@ -0,0 +1,78 @@
+//!
+//! # Consumer Offset Expiry
+//!
+//! Periodically deletes stored offsets of consumers which haven't committed an offset
+//! within configured retention. Only leader of consumer offsets partition expires offsets,
+//! deletions are replicated to followers as any other change.
+//!
+use std::time::Duration;
+
+use tracing::{debug, error, info};
+
+use fluvio_future::task::spawn;
+use fluvio_future::timer::sleep;
+use fluvio_protocol::record::ReplicaKey;
+use fluvio_types::{PartitionId, defaults::CONSUMER_STORAGE_TOPIC};
+
+use crate::core::DefaultSharedGlobalContext;
+
+pub(crate) struct ConsumerOffsetExpiry {
+    ctx: DefaultSharedGlobalContext,
+    retention_secs: u64,
+    interval: Duration,
+}
+
+impl ConsumerOffsetExpiry {
+    pub(crate) fn start(ctx: DefaultSharedGlobalContext) {
+        let config = ctx.config().consumer_offset.clone();
+        if config.retention_secs == 0 {
+            info!("consumer offset expiry is disabled");
+            return;
+        }
+
+        let expiry = Self {
+            ctx,
+            retention_secs: config.retention_secs,
+            interval: Duration::from_secs(config.expiry_interval_secs),
+        };
+        spawn(expiry.dispatch_loop());
+    }
+
+    async fn dispatch_loop(self) {
+        info!(
+            retention_secs = self.retention_secs,
+            interval = ?self.interval,
+            "starting consumer offset expiry"
+        );
+        let consumers_replica_id =
+            ReplicaKey::new(CONSUMER_STORAGE_TOPIC, <PartitionId as Default>::default());
+        loop {
+            sleep(self.interval).await;
+            let Some(ref replica) = self.ctx.leaders_state().get(&consumers_replica_id).await
+            else {
+                debug!("not leader of consumer offsets, skipping expiry");
+                continue;
+            };
+
+            let expired = match self
+                .ctx
+                .consumer_offset()
+                .get_or_insert(replica, self.ctx.follower_notifier())
+                .await
+            {
+                Ok(consumers) => consumers.expire(self.retention_secs).await,
+                Err(err) => Err(err),
+            };
+            match expired {
+                Ok(expired) => {
+                    debug!(expired, "expired consumer offsets");
+                    self.ctx
+                        .metrics()
+                        .consumer_offsets()
+                        .add_expired(expired as u64);
+                }
+                Err(err) => error!(%err, "unable to expire consumer offsets"),
+            }
+        }
+    }
+}
@@ -1 +1,4 @@
 pub(crate) mod consumer;
+mod expiry;
+
+pub(crate) use self::expiry::ConsumerOffsetExpiry;
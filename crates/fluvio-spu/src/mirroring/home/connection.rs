@@ -13,6 +13,7 @@ use futures_util::StreamExt;
 use fluvio_socket::{FluvioStream, ExclusiveFlvSink};
 
 use crate::core::DefaultSharedGlobalContext;
+use crate::core::events::ClusterEvent;
 use crate::mirroring::remote::api_key::MirrorRemoteApiEnum;
 use crate::mirroring::remote::remote_api::RemoteMirrorRequest;
 use crate::mirroring::remote::sync::DefaultPartitionSyncRequest;
@@ -76,6 +77,11 @@ impl MirrorHomeHandler {
             .await
         {
             debug!(leader = %leader.id(), "found leader replica for this mirror request");
+            ctx.events().publish(ClusterEvent::MirrorConnected {
+                remote_cluster: remote_cluster_id,
+                topic: leader.id().topic.clone(),
+                partition: leader.id().partition,
+            });
             // map to actual home
             let metrics = Arc::new(MirrorRequestMetrics::new());
 
@@ -177,6 +183,14 @@ impl MirrorHomeHandler {
             .append_record_set(&mut req.records, self.ctx.follower_notifier())
             .await?;
         debug!(append_flag, "leader appended");
+
+        // records deleted on remote are deleted on home too
+        let (log_start, _) = self.leader.start_offset_info().await;
+        if req.log_start_offset > log_start {
+            self.leader
+                .delete_records_before(req.log_start_offset, self.ctx.follower_notifier())
+                .await?;
+        }
         self.send_offsets_to_remote(sink).await
     }
 }
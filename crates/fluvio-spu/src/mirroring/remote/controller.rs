@@ -21,14 +21,19 @@ use fluvio_controlplane_metadata::{
 };
 use fluvio_storage::{ReplicaStorage, FileReplica};
 
-use fluvio_socket::{FluvioSocket, FluvioSink};
+use fluvio_socket::{FluvioSocket, FluvioSink, SocketObserver};
 use fluvio_spu_schema::{Isolation, server::mirror::StartMirrorRequest};
 use fluvio_future::{task::spawn, timer::sleep};
-use fluvio_protocol::{record::Offset, api::RequestMessage};
+use fluvio_protocol::{record::Offset, api::RequestMessage, Encoder};
 use fluvio_types::event::offsets::OffsetChangeListener;
+use fluvio_types::event::StickyEvent;
 
 use crate::{
-    core::{mirror::SharedMirrorLocalStore, GlobalContext},
+    core::{
+        dynamic_config::{SharedDynamicConfig, MAX_MIRROR_BACKOFF},
+        mirror::SharedMirrorLocalStore,
+        GlobalContext,
+    },
     replication::leader::SharedLeaderState,
 };
 use crate::mirroring::home::{
@@ -36,7 +41,9 @@ use crate::mirroring::home::{
     update_offsets::UpdateHomeOffsetRequest,
 };
 
-use super::sync::FilePartitionSyncRequest;
+use crate::storage::read_slice;
+
+use super::sync::{DefaultPartitionSyncRequest, FilePartitionSyncRequest};
 
 pub(crate) type SharedMirrorControllerState = Arc<MirrorControllerState>;
 
@@ -46,6 +53,8 @@ pub(crate) struct MirrorControllerMetrics {
     loop_count: AtomicU64,
     connect_count: AtomicU64,
     connect_failure: AtomicU64,
+    reconnect_count: AtomicU64,
+    bytes_sent: AtomicU64,
     home_leo: AtomicI64,
 }
 
@@ -82,6 +91,24 @@ impl MirrorControllerMetrics {
     fn get_conn_failure(&self) -> u64 {
         self.connect_failure.load(Ordering::Relaxed)
     }
+
+    fn get_reconnect_count(&self) -> u64 {
+        self.reconnect_count.load(Ordering::Relaxed)
+    }
+
+    fn get_bytes_sent(&self) -> u64 {
+        self.bytes_sent.load(Ordering::Relaxed)
+    }
+}
+
+impl SocketObserver for MirrorControllerMetrics {
+    fn bytes_sent(&self, bytes: usize) {
+        self.bytes_sent.fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+
+    fn reconnected(&self, _addr: &str) {
+        self.reconnect_count.fetch_add(1, Ordering::Relaxed);
+    }
 }
 
 /// State for mirror controller which can be shared across tasks
@@ -98,6 +125,8 @@ impl MirrorControllerState {
                 home_leo: AtomicI64::new(-1), // -1 indicate this is unknown
                 connect_count: AtomicU64::new(0),
                 connect_failure: AtomicU64::new(0),
+                reconnect_count: AtomicU64::new(0),
+                bytes_sent: AtomicU64::new(0),
             },
         }
     }
@@ -123,6 +152,8 @@ pub(crate) struct MirrorRemoteToHomeController<S> {
     remote_config: RemotePartitionConfig,
     state: Arc<MirrorControllerState>,
     mirror_store: SharedMirrorLocalStore,
+    dynamic_config: SharedDynamicConfig,
+    shutdown: Arc<StickyEvent>,
     max_bytes: u32,
     isolation: Isolation,
 }
@@ -165,6 +196,8 @@ where
             state: state.clone(),
             max_bytes,
             mirror_store: ctx.mirrors_localstore_owned(),
+            dynamic_config: ctx.dynamic_config().clone(),
+            shutdown: ctx.shutdown().clone(),
         };
         spawn(controller.dispatch_loop());
         state
@@ -179,7 +212,7 @@ where
         debug!("initial delay to wait for home cluster to be ready");
         sleep(Duration::from_secs(CLUSTER_LOOKUP_SEC)).await;
 
-        loop {
+        while !self.shutdown.is_set() {
             // first find home cluster
             if let Some(home) = self.find_home_cluster() {
                 self.state.metrics.increase_loop_count();
@@ -200,6 +233,7 @@ where
                 sleep(Duration::from_secs(CLUSTER_LOOKUP_SEC)).await;
             }
         }
+        debug!("spu is shutting down, terminating mirror controller");
     }
 
     #[instrument]
@@ -243,6 +277,11 @@ where
 
             select! {
 
+                    _ = self.shutdown.listen() => {
+                        debug!("spu is shutting down, closing connection to home");
+                        break;
+                    }
+
                     _ = leader_offset_listner.listen() => {
                         debug!("leader offset has changed, home cluster needs to be updated");
                         home_updated_needed = true;
@@ -341,10 +380,29 @@ where
         debug!("updating home cluster");
         if let Some(sync_request) = self.generate_home_sync(home_leo).await? {
             debug!(?sync_request, "home sync");
-            let request = RequestMessage::new_request(sync_request)
-                .set_client_id(format!("leader: {}", self.leader.id()));
-            sink.encode_file_slices(&request, request.header.api_version())
-                .await?;
+            let client_id = format!("leader: {}", self.leader.id());
+            let bytes = match self.leader.cipher() {
+                // records are encrypted for this partition, home encrypts them for its own
+                Some(cipher) => {
+                    let sync_request = DefaultPartitionSyncRequest {
+                        hw: sync_request.hw,
+                        leo: sync_request.leo,
+                        log_start_offset: sync_request.log_start_offset,
+                        records: read_slice(sync_request.records.raw_slice(), Some(cipher))?,
+                    };
+                    let request =
+                        RequestMessage::new_request(sync_request).set_client_id(client_id);
+                    sink.send_request(&request).await?;
+                    request.write_size(request.header.api_version())
+                }
+                None => {
+                    let request =
+                        RequestMessage::new_request(sync_request).set_client_id(client_id);
+                    sink.encode_file_slices(&request, request.header.api_version())
+                        .await?
+                }
+            };
+            self.state.metrics.bytes_sent(bytes);
             Ok(())
         } else {
             Ok(())
@@ -380,9 +438,11 @@ where
             return Ok(None);
         }
 
+        let (log_start_offset, _) = self.leader.start_offset_info().await;
         let mut partition_response = FilePartitionSyncRequest {
             leo: leader_offset.leo,
             hw: leader_offset.hw,
+            log_start_offset,
             ..Default::default()
         };
 
@@ -446,6 +506,12 @@ where
             match res {
                 Ok(socket) => {
                     debug!("connected");
+                    // every loop after the first one replaces lost connection
+                    if self.state.metrics.get_loop_count() > 1 {
+                        self.state.metrics.reconnected(endpoint);
+                    } else {
+                        self.state.metrics.connected(endpoint);
+                    }
                     return (socket, false);
                 }
 
@@ -458,7 +524,8 @@ where
     }
 
     async fn backoff_and_wait(&self, backoff: &mut ExponentialBackoff) {
-        let wait = backoff.wait();
+        let (min, max) = self.dynamic_config.mirror_backoff();
+        let wait = backoff.wait().clamp(min, max);
         debug!(seconds = wait.as_secs(), "starting backing off, sleeping");
         sleep(wait).await;
         debug!("resume from backing off");
@@ -469,7 +536,7 @@ where
 fn create_backoff() -> ExponentialBackoff {
     ExponentialBackoffBuilder::default()
         .min(Duration::from_secs(1))
-        .max(Duration::from_secs(300))
+        .max(MAX_MIRROR_BACKOFF)
         .build()
         .unwrap()
 }
@@ -29,6 +29,9 @@ where
     pub hw: i64,
     pub leo: i64,
     pub records: R,
+    /// log start offset of remote, advanced when records are deleted
+    #[fluvio(min_version = 1)]
+    pub log_start_offset: i64,
 }
 
 impl<R> fmt::Display for MirrorPartitionSyncRequest<R>
@@ -66,6 +69,9 @@ impl FileWrite for FilePartitionSyncRequest {
         self.hw.encode(src, version)?;
         self.leo.encode(src, version)?;
         self.records.file_encode(src, data, version)?;
+        if version >= 1 {
+            self.log_start_offset.encode(src, version)?;
+        }
         Ok(())
     }
 }
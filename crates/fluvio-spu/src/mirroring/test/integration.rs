@@ -64,7 +64,7 @@ async fn test_mirroring_new_records() {
 
     // start home server
     debug!("starting home server");
-    let _remote_end = create_public_server(home_port.clone(), home_gctx.clone()).run();
+    let _remote_end = create_public_server(home_port.clone(), home_gctx.clone(), None).run();
 
     // sleep 1 seconds
     debug!("waiting for home public server to up");
@@ -157,3 +157,51 @@ async fn test_mirroring_new_records() {
     // home should have recods
     assert_eq!(home_replica1.leo(), 2);
 }
+
+/// remote reconnects to home after its request fails, then mirrors records
+#[cfg(feature = "fault-injection")]
+#[fluvio_future::test(ignore)]
+async fn test_mirroring_home_connection_fault() {
+    use std::io::ErrorKind;
+    use fluvio_types::fault::{self, FaultAction, SOCKET_SEND};
+
+    let home_port = local_port();
+
+    let home_builder = ReplicaConfig::builder()
+        .remote_clusters(vec!["edge1".to_owned()])
+        .generate("mirror_home_fault");
+    let home_gctx = home_builder.init_mirror_home().await;
+    let home_replica0 = home_gctx
+        .leaders_state()
+        .get(&ReplicaKey::new("temp", 0u32))
+        .await
+        .expect("leader");
+
+    let _remote_end = create_public_server(home_port.clone(), home_gctx.clone(), None).run();
+    sleep(Duration::from_secs(1)).await;
+
+    // first request from remote to home fails
+    let _fault = fault::inject_scoped(
+        SOCKET_SEND,
+        &home_port,
+        FaultAction::Error(ErrorKind::ConnectionReset),
+        Some(1),
+    );
+
+    let remote_builder = ReplicaConfig::builder()
+        .home_port(home_port.clone())
+        .home_cluster("edge1".to_owned())
+        .generate("mirror_remote_fault");
+    let (remote_ctx, remote_replica) = remote_builder.init_mirror_remote().await;
+    sleep(Duration::from_secs(1)).await;
+
+    remote_replica
+        .write_record_set(&mut create_raw_recordset(2), remote_ctx.follower_notifier())
+        .await
+        .expect("write");
+
+    // remote backs off before reconnecting
+    debug!("waiting for mirroring");
+    sleep(Duration::from_secs(10)).await;
+    assert_eq!(home_replica0.leo(), 2);
+}
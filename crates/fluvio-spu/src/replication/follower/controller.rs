@@ -281,7 +281,10 @@ mod inner {
                     base_offset = p.records.base_offset(),
                     "update from leader");
                     if let Some(replica) = self.states.get(&replica_key).await {
-                        match replica.update_from_leader(&mut p.records, p.hw).await {
+                        match replica
+                            .update_from_leader(&mut p.records, p.hw, p.log_start_offset)
+                            .await
+                        {
                             Ok(changes) => {
                                 if changes {
                                     debug!("changes occur, need to send back offset");
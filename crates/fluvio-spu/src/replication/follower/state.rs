@@ -2,11 +2,14 @@ use std::{fmt::Display, sync::Arc};
 use std::fmt::Debug;
 use std::collections::{HashMap, hash_map::Entry};
 use std::ops::{Deref, DerefMut};
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::time::Duration;
 
 use fluvio_controlplane::replica::Replica;
 use tracing::{debug, warn, instrument};
 use async_rwlock::RwLock;
 use anyhow::Result;
+use chrono::Utc;
 
 use fluvio_protocol::record::{BatchRecords, ReplicaKey};
 use fluvio_storage::config::ReplicaConfig;
@@ -97,8 +100,13 @@ impl FollowersState<FileReplica> {
                 let mut replica_config: ReplicaConfig = ctx.config().into();
                 replica_config.update_from_replica(&replica);
 
-                let replica_state =
+                let cipher = ctx
+                    .ciphers()
+                    .cipher(&replica.id, replica.encryption.as_ref())
+                    .await?;
+                let mut replica_state =
                     FollowerReplicaState::create(leader, replica.id, replica_config).await?;
+                replica_state.set_cipher(cipher);
 
                 entry.insert(replica_state.clone());
                 self.groups.check_new(ctx, leader).await;
@@ -148,6 +156,8 @@ impl FollowersState<FileReplica> {
 pub struct FollowerReplicaState<S> {
     leader: SpuId,
     inner: SharableReplicaStorage<S>,
+    /// last time (ms) follower had all records committed by leader, -1 if never
+    in_sync_at: Arc<AtomicI64>,
 }
 
 impl<S> Clone for FollowerReplicaState<S> {
@@ -155,6 +165,7 @@ impl<S> Clone for FollowerReplicaState<S> {
         Self {
             leader: self.leader,
             inner: self.inner.clone(),
+            in_sync_at: self.in_sync_at.clone(),
         }
     }
 }
@@ -197,6 +208,7 @@ where
         Ok(Self {
             leader,
             inner: replica_storage,
+            in_sync_at: Arc::new(AtomicI64::new(-1)),
         })
     }
 
@@ -209,6 +221,7 @@ where
         &self,
         records: &mut RecordSet<R>,
         leader_hw: Offset,
+        leader_log_start: Offset,
     ) -> Result<bool> {
         let mut changes = false;
 
@@ -240,6 +253,21 @@ where
             }
         }
 
+        // records deleted on leader are deleted here once committed
+        let (log_start, _) = self.start_offset_info().await;
+        if leader_log_start > log_start {
+            debug!(
+                log_start,
+                leader_log_start, "deleting records before leader log start"
+            );
+            self.delete_records_before(leader_log_start).await?;
+        }
+
+        if self.hw() == leader_hw {
+            self.in_sync_at
+                .store(Utc::now().timestamp_millis(), Ordering::SeqCst);
+        }
+
         Ok(changes)
     }
 
@@ -270,6 +298,13 @@ where
         }
     }
 
+    /// true if follower was in sync with leader within max_staleness
+    pub fn is_fresh(&self, max_staleness: Duration) -> bool {
+        let in_sync_at = self.in_sync_at.load(Ordering::SeqCst);
+        in_sync_at >= 0
+            && Utc::now().timestamp_millis() - in_sync_at <= max_staleness.as_millis() as i64
+    }
+
     pub fn inner_owned(self) -> SharableReplicaStorage<S> {
         self.inner
     }
@@ -283,6 +318,7 @@ mod follower_tests {
     use flv_util::fixture::ensure_clean_dir;
     use fluvio_types::{SpuId, PartitionId};
     use fluvio_storage::config::ReplicaConfig;
+    use fluvio_protocol::record::RawRecords;
 
     use super::*;
 
@@ -310,4 +346,27 @@ mod follower_tests {
         assert_eq!(follower_replica.hw(), 0);
         assert!(PathBuf::from(test_path).join("spu-5002").exists());
     }
+
+    #[fluvio_future::test]
+    async fn test_follower_freshness() {
+        let test_path = "/tmp/follower_freshness";
+        ensure_clean_dir(test_path);
+
+        let config = ReplicaConfig {
+            base_dir: PathBuf::from(test_path).join("spu-5002"),
+            ..Default::default()
+        };
+
+        let follower_replica: FollowerReplicaState<FileReplica> =
+            FollowerReplicaState::create(LEADER, TEST_REPLICA.into(), config)
+                .await
+                .expect("create");
+        assert!(!follower_replica.is_fresh(Duration::from_secs(60)));
+
+        follower_replica
+            .update_from_leader(&mut RecordSet::<RawRecords>::default(), 0, 0)
+            .await
+            .expect("update");
+        assert!(follower_replica.is_fresh(Duration::from_secs(60)));
+    }
 }
@@ -50,14 +50,14 @@ where
 }
 
 // Request trait
-// Note that DEFAULT_API_VERSION is 7 which is required in order to map all fields for file encoding
+// Note that DEFAULT_API_VERSION must be at least 7 in order to map all fields for file encoding
 // TODO: come up with unify encoding
 impl<R> Request for SyncRequest<R>
 where
     R: Encoder + Decoder + Debug,
 {
     const API_KEY: u16 = FollowerPeerApiEnum::SyncRecords as u16;
-    const DEFAULT_API_VERSION: i16 = 7;
+    const DEFAULT_API_VERSION: i16 = 8;
     type Response = SyncResponse;
 }
 
@@ -111,6 +111,9 @@ where
     pub hw: i64,
     pub leo: i64,
     pub records: R,
+    /// log start offset of leader, advanced when records are deleted
+    #[fluvio(min_version = 8)]
+    pub log_start_offset: i64,
 }
 
 impl<R> fmt::Display for PeerFetchablePartitionResponse<R>
@@ -153,6 +156,9 @@ impl FileWrite for PeerFilePartitionResponse {
         self.hw.encode(src, version)?;
         self.leo.encode(src, version)?;
         self.records.file_encode(src, data, version)?;
+        if version >= 8 {
+            self.log_start_offset.encode(src, version)?;
+        }
         Ok(())
     }
 }
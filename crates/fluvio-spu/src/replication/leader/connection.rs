@@ -18,6 +18,7 @@ use super::LeaderPeerApiEnum;
 use super::LeaderPeerRequest;
 use super::UpdateOffsetRequest;
 use super::spu::SharedSpuPendingUpdate;
+use super::throttle::ReplicationThrottle;
 use super::super::follower::RejectOffsetRequest;
 
 /// Handle connection request from follower
@@ -28,6 +29,7 @@ pub struct FollowerHandler {
     follower_id: SpuId,
     max_bytes: u32,
     spu_update: SharedSpuPendingUpdate,
+    throttle: ReplicationThrottle,
 }
 
 impl fmt::Debug for FollowerHandler {
@@ -50,6 +52,9 @@ impl FollowerHandler {
             max_bytes: ctx.config().peer_max_bytes,
             follower_id,
             spu_update,
+            throttle: ReplicationThrottle::new(
+                ctx.dynamic_config().follower_throttle_bytes_per_sec(),
+            ),
         };
 
         connection.dispatch(sink, stream).await;
@@ -126,6 +131,9 @@ impl FollowerHandler {
     // send out any updates from other leaders to this followers
     #[instrument(skip(self))]
     async fn update_from_leaders(&mut self, sink: &mut FluvioSink) -> Result<(), SocketError> {
+        self.throttle
+            .set_rate(self.ctx.dynamic_config().follower_throttle_bytes_per_sec());
+        self.throttle.wait().await;
         let replicas = self.spu_update.drain_replicas().await;
 
         if replicas.is_empty() {
@@ -140,10 +148,16 @@ impl FollowerHandler {
 
         for replica in replicas {
             if let Some(leader) = leaders.get(&replica).await {
-                if let Some(topic_response) = leader
-                    .follower_updates(&self.follower_id, self.max_bytes)
-                    .await
+                let max_bytes = self.throttle.max_bytes(self.max_bytes);
+                if let Some(topic_response) =
+                    leader.follower_updates(&self.follower_id, max_bytes).await
                 {
+                    let bytes: usize = topic_response
+                        .partitions
+                        .iter()
+                        .map(|partition| partition.records.len())
+                        .sum();
+                    self.throttle.record(bytes as u64);
                     sync_request.topics.push(topic_response);
                 }
             } else {
@@ -0,0 +1,217 @@
+//!
+//! # Deduplication window
+//!
+//! Native record deduplication done by the leader before records are appended.
+//! SHA-256 digests of record keys (or values) seen within the configured bounds are remembered
+//! and records matching one of them are dropped from the incoming batch.
+//! Age of records is measured by the broker clock, timestamps set by producers are not trusted.
+//!
+use std::collections::{HashSet, VecDeque};
+use std::time::Duration;
+
+use anyhow::Result;
+use sha2::{Digest, Sha256};
+use tracing::{debug, trace};
+
+use fluvio_controlplane_metadata::topic::{DeduplicationKey, DeduplicationWindow};
+use fluvio_protocol::record::{Batch, RawRecords, Record};
+use fluvio_types::Timestamp;
+
+type RecordDigest = [u8; 32];
+
+#[derive(Debug)]
+pub struct DedupWindow {
+    key: DeduplicationKey,
+    max_count: usize,
+    max_age: Option<Duration>,
+    entries: VecDeque<(RecordDigest, Timestamp)>,
+    seen: HashSet<RecordDigest>,
+}
+
+impl DedupWindow {
+    pub fn new(config: &DeduplicationWindow) -> Self {
+        Self {
+            key: config.key,
+            max_count: config.bounds.count as usize,
+            max_age: config.bounds.age,
+            entries: VecDeque::new(),
+            seen: HashSet::new(),
+        }
+    }
+
+    /// remember record seen at timestamp, return false if record is duplicate
+    pub fn insert(&mut self, record: &Record, timestamp: Timestamp) -> bool {
+        let Some(digest) = self.digest(record) else {
+            return true;
+        };
+
+        if !self.seen.insert(digest) {
+            trace!("duplicate record");
+            return false;
+        }
+
+        self.entries.push_back((digest, timestamp));
+        while self.entries.len() > self.max_count {
+            if let Some((digest, _)) = self.entries.pop_front() {
+                self.seen.remove(&digest);
+            }
+        }
+        true
+    }
+
+    /// remove duplicate records from batch received at `now`
+    /// return None if all records were duplicates
+    pub fn filter_batch(
+        &mut self,
+        batch: Batch<RawRecords>,
+        now: Timestamp,
+    ) -> Result<Option<Batch<RawRecords>>> {
+        self.evict_older(now);
+
+        let records = batch.memory_records()?;
+        let total = records.len();
+
+        let unique: Vec<Record> = records
+            .into_iter()
+            .filter(|record| self.insert(record, now))
+            .collect();
+
+        if unique.len() == total {
+            return Ok(Some(batch));
+        }
+        debug!(total, unique = unique.len(), "removed duplicate records");
+        if unique.is_empty() {
+            return Ok(None);
+        }
+
+        let mut unique_batch = Batch::from(unique);
+        unique_batch.base_offset = batch.base_offset;
+        unique_batch.header = batch.header.clone();
+        unique_batch.update_offset_deltas();
+        Ok(Some(Batch::<RawRecords>::try_from(unique_batch)?))
+    }
+
+    /// forget records seen before age bound at `now` of broker clock
+    pub fn evict_older(&mut self, now: Timestamp) {
+        let Some(age) = self.max_age else {
+            return;
+        };
+        let min_timestamp = now.saturating_sub(age.as_millis() as Timestamp);
+        while let Some((digest, timestamp)) = self.entries.front() {
+            if *timestamp >= min_timestamp {
+                break;
+            }
+            self.seen.remove(digest);
+            self.entries.pop_front();
+        }
+    }
+
+    fn digest(&self, record: &Record) -> Option<RecordDigest> {
+        let bytes: &[u8] = match self.key {
+            DeduplicationKey::RecordKey => record.key()?.as_ref(),
+            DeduplicationKey::ValueHash => record.value().as_ref(),
+        };
+        Some(Sha256::digest(bytes).into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use fluvio_controlplane_metadata::topic::Bounds;
+
+    use super::*;
+
+    fn window(count: u64, age: Option<Duration>, key: DeduplicationKey) -> DedupWindow {
+        DedupWindow::new(&DeduplicationWindow {
+            bounds: Bounds { count, age },
+            key,
+        })
+    }
+
+    #[test]
+    fn test_dedup_by_key() {
+        let mut window = window(10, None, DeduplicationKey::RecordKey);
+        assert!(window.insert(&Record::new_key_value("a", "1"), 0));
+        assert!(!window.insert(&Record::new_key_value("a", "2"), 0));
+        assert!(window.insert(&Record::new_key_value("b", "1"), 0));
+
+        // records without keys are never duplicates
+        assert!(window.insert(&Record::new("1"), 0));
+        assert!(window.insert(&Record::new("1"), 0));
+    }
+
+    #[test]
+    fn test_dedup_by_value() {
+        let mut window = window(10, None, DeduplicationKey::ValueHash);
+        assert!(window.insert(&Record::new("1"), 0));
+        assert!(!window.insert(&Record::new_key_value("a", "1"), 0));
+        assert!(window.insert(&Record::new("2"), 0));
+    }
+
+    #[test]
+    fn test_count_bound() {
+        let mut window = window(2, None, DeduplicationKey::ValueHash);
+        assert!(window.insert(&Record::new("1"), 0));
+        assert!(window.insert(&Record::new("2"), 0));
+        assert!(window.insert(&Record::new("3"), 0));
+
+        // "1" has been evicted from the window
+        assert!(window.insert(&Record::new("1"), 0));
+        assert!(!window.insert(&Record::new("3"), 0));
+    }
+
+    #[test]
+    fn test_age_bound() {
+        let mut window = window(
+            10,
+            Some(Duration::from_secs(1)),
+            DeduplicationKey::ValueHash,
+        );
+        assert!(window.insert(&Record::new("1"), 1000));
+        window.evict_older(1500);
+        assert!(!window.insert(&Record::new("1"), 1500));
+        window.evict_older(2500);
+        assert!(window.insert(&Record::new("1"), 2500));
+    }
+
+    #[test]
+    fn test_age_by_broker_clock() {
+        let mut window = window(
+            10,
+            Some(Duration::from_secs(1)),
+            DeduplicationKey::ValueHash,
+        );
+        let batch = Batch::from(vec![Record::new("1")]);
+        let raw = Batch::<RawRecords>::try_from(batch).expect("raw");
+        assert!(window.filter_batch(raw, 1000).expect("filter").is_some());
+
+        // producer clock far ahead doesn't evict records seen by broker within the age
+        let mut batch = Batch::from(vec![Record::new("1")]);
+        batch.get_mut_header().first_timestamp = 1_000_000;
+        let raw = Batch::<RawRecords>::try_from(batch).expect("raw");
+        assert!(window.filter_batch(raw, 1500).expect("filter").is_none());
+
+        let batch = Batch::from(vec![Record::new("1")]);
+        let raw = Batch::<RawRecords>::try_from(batch).expect("raw");
+        assert!(window.filter_batch(raw, 2500).expect("filter").is_some());
+    }
+
+    #[test]
+    fn test_filter_batch() {
+        let mut window = window(10, None, DeduplicationKey::ValueHash);
+        let batch = Batch::from(vec![Record::new("1"), Record::new("1"), Record::new("2")]);
+        let raw = Batch::<RawRecords>::try_from(batch).expect("raw");
+
+        let filtered = window
+            .filter_batch(raw, 0)
+            .expect("filter")
+            .expect("some records");
+        assert_eq!(filtered.records_len(), 2);
+        assert_eq!(filtered.memory_records().expect("records").len(), 2);
+
+        let batch = Batch::from(vec![Record::new("1"), Record::new("2")]);
+        let raw = Batch::<RawRecords>::try_from(batch).expect("raw");
+        assert!(window.filter_batch(raw, 0).expect("filter").is_none());
+    }
+}
@@ -12,6 +12,7 @@ use fluvio_storage::{FileReplica, ReplicaStorage};
 use crate::{control_plane::SharedStatusUpdate, core::GlobalContext};
 use crate::config::ReplicationConfig;
 use crate::replication::follower::FollowerReplicaState;
+use crate::storage::SegmentPreloader;
 
 use super::{LeaderReplicaState, replica_state::SharedLeaderState};
 
@@ -116,6 +117,7 @@ impl ReplicaLeadersState<FileReplica> {
         let leader_replica =
             LeaderReplicaState::create(replica, ctx.config(), status_update).await?;
         let leader_replica = leader_replica.init(ctx).await?;
+        SegmentPreloader::start(&leader_replica, ctx.config().log.preload_bytes);
         self.insert_leader(replica_id, leader_replica.clone()).await;
         Ok(leader_replica)
     }
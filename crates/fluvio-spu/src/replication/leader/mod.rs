@@ -7,6 +7,9 @@ mod update_offsets;
 mod actions;
 mod spu;
 mod kv;
+mod producer_state;
+mod dedup_window;
+mod throttle;
 
 pub use self::leaders_state::{ReplicaLeadersState, SharedReplicaLeadersState};
 pub use self::replica_state::{SharedFileLeaderState, SharedLeaderState, LeaderReplicaState};
@@ -0,0 +1,610 @@
+//!
+//! # Idempotent producer state
+//!
+//! Batches written by an idempotent producer carry `producer_id`, `producer_epoch` and
+//! `first_sequence` in their header. The leader keeps track of the last batches written by each
+//! producer so a retried batch is acknowledged with its original offsets instead of being
+//! appended twice.
+//!
+//! Control batches carrying transaction markers are written by the leader on behalf of
+//! the producer and don't take part in sequence tracking.
+//!
+//! Because the header is stored with the batch, the table is rebuilt from the log whenever
+//! a leader is created, which keeps the state across leader restarts and leadership changes.
+//! Table is saved as checkpoint next to the log as it grows, so only batches written after
+//! the checkpoint are read back.
+//!
+use std::collections::{HashMap, VecDeque};
+use std::io::{Error as IoError, ErrorKind};
+
+use tracing::{debug, trace};
+
+use fluvio_protocol::{Decoder, Encoder};
+use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::record::{Batch, BatchHeader, Offset, RecordSet};
+
+/// producer id used by non idempotent producers
+pub const NO_PRODUCER_ID: i64 = -1;
+
+/// offset of batch whose records were not written
+const NO_OFFSET: Offset = -1;
+
+/// number of recent batches remembered for each producer
+const MAX_CACHED_BATCHES: usize = 5;
+
+/// checkpoint is saved after this many offsets are written since the last one
+const CHECKPOINT_INTERVAL: Offset = 10_000;
+
+/// format of checkpoint, checkpoint of other version is not loaded
+const CHECKPOINT_VERSION: i16 = 0;
+
+/// outcome of checking a batch against producer state
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum SequenceCheck {
+    /// batch is new and must be appended
+    Append,
+    /// batch has been written before, offsets are the ones assigned on first write
+    Duplicate {
+        base_offset: Offset,
+        last_offset: Offset,
+    },
+}
+
+#[derive(Debug, Default, Clone, Eq, PartialEq, Encoder, Decoder)]
+struct BatchMetadata {
+    first_sequence: i32,
+    last_sequence: i32,
+    base_offset: Offset,
+    last_offset: Offset,
+}
+
+#[derive(Debug, Default)]
+struct ProducerEntry {
+    epoch: i16,
+    batches: VecDeque<BatchMetadata>,
+}
+
+impl ProducerEntry {
+    fn last_sequence(&self) -> Option<i32> {
+        self.batches.back().map(|batch| batch.last_sequence)
+    }
+}
+
+/// producer state of batches before `offset`
+#[derive(Debug, Default, Encoder, Decoder)]
+struct Checkpoint {
+    offset: Offset,
+    producers: Vec<ProducerCheckpoint>,
+}
+
+#[derive(Debug, Default, Encoder, Decoder)]
+struct ProducerCheckpoint {
+    producer_id: i64,
+    epoch: i16,
+    batches: Vec<BatchMetadata>,
+}
+
+/// sequence and epoch of each known producer
+#[derive(Debug, Default)]
+pub struct ProducerStateTable {
+    producers: HashMap<i64, ProducerEntry>,
+    /// offset of last saved or loaded checkpoint
+    checkpoint_offset: Offset,
+}
+
+impl ProducerStateTable {
+    /// check all batches in record set
+    /// batches from the same producer inside a record set must be in sequence
+    pub fn check_record_set<R>(
+        &self,
+        records: &RecordSet<R>,
+    ) -> Result<Vec<SequenceCheck>, ErrorCode> {
+        // sequences accepted so far in this record set
+        let mut pending: HashMap<i64, (i16, i32)> = HashMap::new();
+        let mut checks = Vec::with_capacity(records.batches.len());
+
+        for batch in &records.batches {
+            let header = batch.get_header();
+            if header.producer_id == NO_PRODUCER_ID || header.is_control() {
+                checks.push(SequenceCheck::Append);
+                continue;
+            }
+
+            let check = match pending.get(&header.producer_id) {
+                Some((epoch, last_sequence)) => {
+                    check_epoch(header, *epoch)?;
+                    check_next_sequence(header, *last_sequence)?;
+                    SequenceCheck::Append
+                }
+                None => self.check(header)?,
+            };
+
+            if check == SequenceCheck::Append {
+                pending.insert(
+                    header.producer_id,
+                    (header.producer_epoch, last_sequence(header)),
+                );
+            }
+            checks.push(check);
+        }
+
+        Ok(checks)
+    }
+
+    /// check single batch against known state of its producer
+    pub fn check(&self, header: &BatchHeader) -> Result<SequenceCheck, ErrorCode> {
+        if header.producer_id == NO_PRODUCER_ID || header.is_control() {
+            return Ok(SequenceCheck::Append);
+        }
+
+        let Some(entry) = self.producers.get(&header.producer_id) else {
+            // unknown producer, state may have been removed with old segments
+            trace!(producer_id = header.producer_id, "new producer");
+            return Ok(SequenceCheck::Append);
+        };
+
+        check_epoch(header, entry.epoch)?;
+
+        if header.producer_epoch > entry.epoch {
+            // producer has been re-initialized, sequence starts over
+            return if header.first_sequence == 0 {
+                Ok(SequenceCheck::Append)
+            } else {
+                Err(ErrorCode::OutOfOrderSequence {
+                    producer_id: header.producer_id,
+                    expected: 0,
+                    received: header.first_sequence,
+                })
+            };
+        }
+
+        let last = last_sequence(header);
+        if let Some(duplicate) = entry.batches.iter().find(|batch| {
+            batch.first_sequence == header.first_sequence && batch.last_sequence == last
+        }) {
+            if duplicate.base_offset == NO_OFFSET {
+                // records were dropped before, retry goes through the same path
+                trace!(
+                    producer_id = header.producer_id,
+                    first_sequence = header.first_sequence,
+                    "retry of dropped batch"
+                );
+                return Ok(SequenceCheck::Append);
+            }
+            debug!(
+                producer_id = header.producer_id,
+                first_sequence = header.first_sequence,
+                base_offset = duplicate.base_offset,
+                "duplicate batch"
+            );
+            return Ok(SequenceCheck::Duplicate {
+                base_offset: duplicate.base_offset,
+                last_offset: duplicate.last_offset,
+            });
+        }
+
+        match entry.last_sequence() {
+            Some(last_sequence) => {
+                check_next_sequence(header, last_sequence)?;
+                Ok(SequenceCheck::Append)
+            }
+            None => Ok(SequenceCheck::Append),
+        }
+    }
+
+    /// record batch that has been written to the log
+    pub fn update(&mut self, header: &BatchHeader, base_offset: Offset) {
+        self.update_written(
+            header,
+            base_offset,
+            base_offset + header.last_offset_delta as Offset,
+        );
+    }
+
+    /// record batch whose records were all dropped, its sequence is taken without offsets
+    pub fn update_dropped(&mut self, header: &BatchHeader) {
+        self.update_written(header, NO_OFFSET, NO_OFFSET);
+    }
+
+    /// record batches whose records were transformed before written from `base_offset` up to `leo`.
+    /// Written records can't be traced back to batches, so each batch is given offsets following
+    /// the ones before it, limited to written offsets
+    pub fn update_transformed(
+        &mut self,
+        headers: &[BatchHeader],
+        base_offset: Offset,
+        leo: Offset,
+    ) {
+        let last_written = (leo - 1).max(base_offset);
+        let mut offset = base_offset;
+        for header in headers {
+            let last_offset = offset + header.last_offset_delta.max(0) as Offset;
+            self.update_written(
+                header,
+                offset.min(last_written),
+                last_offset.min(last_written),
+            );
+            offset = last_offset + 1;
+        }
+    }
+
+    /// record batch written from `base_offset` to `last_offset`, which may hold fewer records
+    /// than the batch sent by producer
+    pub fn update_written(
+        &mut self,
+        header: &BatchHeader,
+        base_offset: Offset,
+        last_offset: Offset,
+    ) {
+        if header.producer_id == NO_PRODUCER_ID || header.is_control() {
+            return;
+        }
+
+        let entry = self.producers.entry(header.producer_id).or_default();
+        if header.producer_epoch != entry.epoch {
+            entry.epoch = header.producer_epoch;
+            entry.batches.clear();
+        }
+
+        let metadata = BatchMetadata {
+            first_sequence: header.first_sequence,
+            last_sequence: last_sequence(header),
+            base_offset,
+            last_offset,
+        };
+        // retry of dropped batch takes its place, so sequence doesn't go back
+        if let Some(retried) = entry.batches.iter_mut().find(|batch| {
+            batch.first_sequence == metadata.first_sequence
+                && batch.last_sequence == metadata.last_sequence
+        }) {
+            *retried = metadata;
+            return;
+        }
+
+        if entry.batches.len() == MAX_CACHED_BATCHES {
+            entry.batches.pop_front();
+        }
+        entry.batches.push_back(metadata);
+    }
+
+    /// record batch that was read back from the log
+    pub fn update_from_batch<R>(&mut self, batch: &Batch<R>) {
+        self.update(batch.get_header(), batch.get_base_offset());
+    }
+
+    /// encoded state of batches before `leo` if log has grown by checkpoint interval
+    /// since the last checkpoint
+    pub fn checkpoint(&mut self, leo: Offset) -> Result<Option<Vec<u8>>, IoError> {
+        if leo - self.checkpoint_offset < CHECKPOINT_INTERVAL {
+            return Ok(None);
+        }
+        let checkpoint = Checkpoint {
+            offset: leo,
+            producers: self
+                .producers
+                .iter()
+                .map(|(producer_id, entry)| ProducerCheckpoint {
+                    producer_id: *producer_id,
+                    epoch: entry.epoch,
+                    batches: entry.batches.iter().cloned().collect(),
+                })
+                .collect(),
+        };
+        let mut bytes = vec![];
+        CHECKPOINT_VERSION.encode(&mut bytes, 0)?;
+        checkpoint.encode(&mut bytes, 0)?;
+        self.checkpoint_offset = leo;
+        Ok(Some(bytes))
+    }
+
+    /// load table from checkpoint, returns it with offset of the first batch not included
+    pub fn from_checkpoint(mut bytes: &[u8]) -> Result<(Offset, Self), IoError> {
+        let version = i16::decode_from(&mut bytes, 0)?;
+        if version != CHECKPOINT_VERSION {
+            return Err(IoError::new(
+                ErrorKind::InvalidData,
+                format!("unknown producer state checkpoint version: {version}"),
+            ));
+        }
+        let checkpoint = Checkpoint::decode_from(&mut bytes, 0)?;
+        let producers = checkpoint
+            .producers
+            .into_iter()
+            .map(|producer| {
+                (
+                    producer.producer_id,
+                    ProducerEntry {
+                        epoch: producer.epoch,
+                        batches: producer.batches.into(),
+                    },
+                )
+            })
+            .collect();
+        Ok((
+            checkpoint.offset,
+            Self {
+                producers,
+                checkpoint_offset: checkpoint.offset,
+            },
+        ))
+    }
+}
+
+fn check_epoch(header: &BatchHeader, current_epoch: i16) -> Result<(), ErrorCode> {
+    if header.producer_epoch < current_epoch {
+        Err(ErrorCode::ProducerFenced {
+            producer_id: header.producer_id,
+            epoch: header.producer_epoch,
+            current_epoch,
+        })
+    } else {
+        Ok(())
+    }
+}
+
+fn check_next_sequence(header: &BatchHeader, last_sequence: i32) -> Result<(), ErrorCode> {
+    let expected = next_sequence(last_sequence);
+    if header.first_sequence == expected {
+        Ok(())
+    } else {
+        Err(ErrorCode::OutOfOrderSequence {
+            producer_id: header.producer_id,
+            expected,
+            received: header.first_sequence,
+        })
+    }
+}
+
+/// sequence wraps around to 0 after i32::MAX
+fn next_sequence(sequence: i32) -> i32 {
+    if sequence == i32::MAX {
+        0
+    } else {
+        sequence + 1
+    }
+}
+
+fn last_sequence(header: &BatchHeader) -> i32 {
+    let last = header.first_sequence as i64 + header.last_offset_delta.max(0) as i64;
+    (last % (i32::MAX as i64 + 1)) as i32
+}
+
+#[cfg(test)]
+mod test {
+
+    use fluvio_protocol::record::{Batch, RawRecords, RecordSet};
+
+    use super::*;
+
+    fn header(producer_id: i64, epoch: i16, first_sequence: i32, records: i32) -> BatchHeader {
+        BatchHeader {
+            producer_id,
+            producer_epoch: epoch,
+            first_sequence,
+            last_offset_delta: records - 1,
+            ..Default::default()
+        }
+    }
+
+    fn record_set(headers: Vec<BatchHeader>) -> RecordSet<RawRecords> {
+        let batches = headers
+            .into_iter()
+            .map(|header| {
+                let mut batch = Batch::<RawRecords>::default();
+                *batch.get_mut_header() = header;
+                batch
+            })
+            .collect();
+        RecordSet { batches }
+    }
+
+    #[test]
+    fn test_non_idempotent_batches_are_appended() {
+        let table = ProducerStateTable::default();
+        assert_eq!(
+            table.check(&BatchHeader::default()).expect("check"),
+            SequenceCheck::Append
+        );
+    }
+
+    #[test]
+    fn test_duplicate_batch() {
+        let mut table = ProducerStateTable::default();
+        let first = header(1, 0, 0, 10);
+        assert_eq!(table.check(&first).expect("check"), SequenceCheck::Append);
+        table.update(&first, 100);
+
+        assert_eq!(
+            table.check(&first).expect("check"),
+            SequenceCheck::Duplicate {
+                base_offset: 100,
+                last_offset: 109
+            }
+        );
+
+        let second = header(1, 0, 10, 5);
+        assert_eq!(table.check(&second).expect("check"), SequenceCheck::Append);
+    }
+
+    #[test]
+    fn test_out_of_order_sequence() {
+        let mut table = ProducerStateTable::default();
+        table.update(&header(1, 0, 0, 10), 0);
+
+        assert_eq!(
+            table.check(&header(1, 0, 12, 1)),
+            Err(ErrorCode::OutOfOrderSequence {
+                producer_id: 1,
+                expected: 10,
+                received: 12
+            })
+        );
+    }
+
+    #[test]
+    fn test_epoch_fencing() {
+        let mut table = ProducerStateTable::default();
+        table.update(&header(1, 2, 0, 10), 0);
+
+        assert_eq!(
+            table.check(&header(1, 1, 10, 1)),
+            Err(ErrorCode::ProducerFenced {
+                producer_id: 1,
+                epoch: 1,
+                current_epoch: 2
+            })
+        );
+
+        // new epoch must start from sequence 0
+        assert!(table.check(&header(1, 3, 10, 1)).is_err());
+        assert_eq!(
+            table.check(&header(1, 3, 0, 1)).expect("check"),
+            SequenceCheck::Append
+        );
+        table.update(&header(1, 3, 0, 1), 10);
+        assert!(table.check(&header(1, 2, 1, 1)).is_err());
+    }
+
+    #[test]
+    fn test_check_record_set_in_sequence() {
+        let mut table = ProducerStateTable::default();
+        table.update(&header(1, 0, 0, 2), 0);
+
+        let records = record_set(vec![
+            header(1, 0, 0, 2),
+            header(1, 0, 2, 3),
+            header(1, 0, 5, 1),
+            BatchHeader::default(),
+        ]);
+        let checks = table.check_record_set(&records).expect("check");
+        assert_eq!(
+            checks,
+            vec![
+                SequenceCheck::Duplicate {
+                    base_offset: 0,
+                    last_offset: 1
+                },
+                SequenceCheck::Append,
+                SequenceCheck::Append,
+                SequenceCheck::Append
+            ]
+        );
+
+        let records = record_set(vec![header(1, 0, 2, 3), header(1, 0, 6, 1)]);
+        assert!(table.check_record_set(&records).is_err());
+    }
+
+    #[test]
+    fn test_control_batch_is_not_tracked() {
+        let mut table = ProducerStateTable::default();
+        table.update(&header(1, 0, 0, 2), 0);
+
+        let mut marker = header(1, 0, -1, 1);
+        marker.set_txn_marker(fluvio_protocol::record::TxnMarker::Commit);
+        assert_eq!(table.check(&marker).expect("check"), SequenceCheck::Append);
+        table.update(&marker, 2);
+
+        assert_eq!(
+            table.check(&header(1, 0, 2, 1)).expect("check"),
+            SequenceCheck::Append
+        );
+    }
+
+    #[test]
+    fn test_transformed_batches_offsets() {
+        let mut table = ProducerStateTable::default();
+        // 2 batches of 3 records transformed into 4 records written at 10
+        table.update_transformed(&[header(1, 0, 0, 3), header(1, 0, 3, 3)], 10, 14);
+
+        assert_eq!(
+            table.check(&header(1, 0, 0, 3)).expect("check"),
+            SequenceCheck::Duplicate {
+                base_offset: 10,
+                last_offset: 12
+            }
+        );
+        assert_eq!(
+            table.check(&header(1, 0, 3, 3)).expect("check"),
+            SequenceCheck::Duplicate {
+                base_offset: 13,
+                last_offset: 13
+            }
+        );
+    }
+
+    #[test]
+    fn test_dropped_batch() {
+        let mut table = ProducerStateTable::default();
+        table.update(&header(1, 0, 0, 2), 0);
+        table.update_dropped(&header(1, 0, 2, 3));
+
+        // retry is not acknowledged with offsets that were never written
+        assert_eq!(
+            table.check(&header(1, 0, 2, 3)).expect("check"),
+            SequenceCheck::Append
+        );
+        assert_eq!(
+            table.check(&header(1, 0, 5, 1)).expect("check"),
+            SequenceCheck::Append
+        );
+        assert!(table.check(&header(1, 0, 6, 1)).is_err());
+
+        // retry written after later batch keeps sequence of the later one
+        table.update(&header(1, 0, 5, 1), 2);
+        table.update(&header(1, 0, 2, 3), 3);
+        assert_eq!(
+            table.check(&header(1, 0, 6, 1)).expect("check"),
+            SequenceCheck::Append
+        );
+        assert_eq!(
+            table.check(&header(1, 0, 2, 3)).expect("check"),
+            SequenceCheck::Duplicate {
+                base_offset: 3,
+                last_offset: 5
+            }
+        );
+    }
+
+    #[test]
+    fn test_checkpoint() {
+        let mut table = ProducerStateTable::default();
+        table.update(&header(1, 2, 0, 10), 0);
+        table.update(&header(1, 2, 10, 5), 10);
+        table.update(&header(2, 0, 0, 1), 15);
+        assert!(table.checkpoint(16).expect("checkpoint").is_none());
+
+        let bytes = table
+            .checkpoint(CHECKPOINT_INTERVAL)
+            .expect("checkpoint")
+            .expect("due");
+        assert!(table
+            .checkpoint(CHECKPOINT_INTERVAL + 1)
+            .expect("checkpoint")
+            .is_none());
+
+        let (offset, loaded) = ProducerStateTable::from_checkpoint(&bytes).expect("load");
+        assert_eq!(offset, CHECKPOINT_INTERVAL);
+        assert_eq!(
+            loaded.check(&header(1, 2, 10, 5)).expect("check"),
+            SequenceCheck::Duplicate {
+                base_offset: 10,
+                last_offset: 14
+            }
+        );
+        assert_eq!(
+            loaded.check(&header(2, 0, 1, 1)).expect("check"),
+            SequenceCheck::Append
+        );
+        assert!(loaded.check(&header(1, 1, 15, 1)).is_err());
+    }
+
+    #[test]
+    fn test_sequence_wrap_around() {
+        let mut table = ProducerStateTable::default();
+        table.update(&header(1, 0, i32::MAX - 1, 2), 0);
+        assert_eq!(
+            table.check(&header(1, 0, 0, 1)).expect("check"),
+            SequenceCheck::Append
+        );
+    }
+}
@@ -3,20 +3,28 @@ use std::{
     collections::{BTreeMap, HashSet, BinaryHeap},
     ops::{Deref, DerefMut},
     sync::Arc,
+    time::{Duration, Instant},
 };
 use std::iter::FromIterator;
 use std::fmt;
 
 use async_lock::Mutex;
+use chrono::Utc;
 use fluvio_controlplane::{replica::Replica, sc_api::update_lrs::LrsRequest};
 use tracing::{debug, error, warn};
 use tracing::instrument;
 use async_rwlock::RwLock;
 use anyhow::{Result, Context};
 
-use fluvio_protocol::record::{RecordSet, Offset, ReplicaKey, RawRecords, Batch};
-use fluvio_controlplane_metadata::partition::{PartitionMirrorConfig, PartitionStatus, ReplicaStatus};
+use fluvio_protocol::record::{
+    RecordSet, Offset, ReplicaKey, RawRecords, Batch, BatchHeader, NO_TIMESTAMP, TxnMarker,
+};
+use fluvio_controlplane_metadata::partition::{
+    CorruptRange, PartitionMirrorConfig, PartitionStatus, ReplicaStatus,
+};
 use fluvio_storage::{FileReplica, ReplicaStorage, OffsetInfo, ReplicaStorageConfig};
+use fluvio_storage::iterators::{FileBatch, FileBatchIterator, FileRecordIterator};
+use fluvio_storage::encryption::BatchCipher;
 use fluvio_types::{
     event::offsets::{SharedOffsetPublisher, WeakSharedOffsetPublisher, TOPIC_DELETED},
     SpuId,
@@ -38,12 +46,20 @@ use crate::replication::follower::sync::{PeerFileTopicResponse, PeerFilePartitio
 use crate::storage::SharableReplicaStorage;
 
 use super::FollowerNotifier;
+use super::producer_state::{ProducerStateTable, SequenceCheck};
+use super::dedup_window::DedupWindow;
 
 pub type SharedLeaderState<S> = LeaderReplicaState<S>;
 pub type SharedFileLeaderState = LeaderReplicaState<FileReplica>;
 
 pub const CLEANUP_FREQUENCY: usize = 10;
 
+/// follower is out of sync if it hasn't caught up with leader for this long
+pub const FOLLOWER_MAX_LAG: Duration = Duration::from_secs(10);
+
+/// name of producer state checkpoint saved next to the log
+const PRODUCER_STATE_CHECKPOINT: &str = "producer_state.chk";
+
 #[derive(Debug)]
 pub struct LeaderReplicaState<S> {
     replica: Replica,
@@ -55,6 +71,13 @@ pub struct LeaderReplicaState<S> {
     sm_ctx: Option<SharedSmartModuleContext>,
     consumer_offset_publishers: Arc<Mutex<Vec<WeakSharedOffsetPublisher>>>,
     mirror_controller_state: Option<SharedMirrorControllerState>,
+    producers: Arc<Mutex<ProducerStateTable>>,
+    dedup_window: Option<Arc<Mutex<DedupWindow>>>,
+    corrupt_ranges: Arc<Mutex<Vec<CorruptRange>>>,
+    /// followers not yet sent log start offset advanced by deleting records
+    log_start_pending: Arc<Mutex<HashSet<SpuId>>>,
+    /// last time followers reported leo same as leader's
+    follower_caught_up: Arc<Mutex<BTreeMap<SpuId, Instant>>>,
 }
 
 impl<S> Clone for LeaderReplicaState<S> {
@@ -69,6 +92,11 @@ impl<S> Clone for LeaderReplicaState<S> {
             sm_ctx: self.sm_ctx.clone(),
             consumer_offset_publishers: self.consumer_offset_publishers.clone(),
             mirror_controller_state: self.mirror_controller_state.clone(),
+            producers: self.producers.clone(),
+            dedup_window: self.dedup_window.clone(),
+            corrupt_ranges: self.corrupt_ranges.clone(),
+            log_start_pending: self.log_start_pending.clone(),
+            follower_caught_up: self.follower_caught_up.clone(),
         }
     }
 }
@@ -140,6 +168,11 @@ where
             sm_ctx: None,
             consumer_offset_publishers: Arc::new(Mutex::new(Vec::new())),
             mirror_controller_state: None,
+            producers: Arc::new(Mutex::new(ProducerStateTable::default())),
+            dedup_window: None,
+            corrupt_ranges: Arc::new(Mutex::new(Vec::new())),
+            log_start_pending: Arc::new(Mutex::new(HashSet::new())),
+            follower_caught_up: Arc::new(Mutex::new(BTreeMap::new())),
         })
     }
 
@@ -205,6 +238,12 @@ where
         // get follower info
         let mut followers = self.followers.write().await;
         let update = if let Some(current_follow_info) = followers.get_mut(&follower_id) {
+            if follower_pos.leo == leader_pos.leo {
+                self.follower_caught_up
+                    .lock()
+                    .await
+                    .insert(follower_id, Instant::now());
+            }
             if current_follow_info.update(&follower_pos) {
                 // if our leo and hw is same there is no need to recompute hw
                 if !leader_pos.is_committed() {
@@ -250,15 +289,23 @@ where
 
         let reader = self.followers.read().await;
         if let Some(follower_info) = reader.get(follower_id) {
-            if follower_info.is_valid() && !follower_info.is_same(&leader_offset) {
+            let log_start_pending = self.log_start_pending.lock().await.contains(follower_id);
+            if follower_info.is_valid()
+                && (log_start_pending || !follower_info.is_same(&leader_offset))
+            {
                 let mut topic_response = PeerFileTopicResponse {
                     name: self.id().topic.to_owned(),
                     ..Default::default()
                 };
+                let (log_start_offset, _) = self.start_offset_info().await;
                 let mut partition_response = PeerFilePartitionResponse {
                     partition: self.id().partition,
+                    log_start_offset,
                     ..Default::default()
                 };
+                if log_start_pending {
+                    self.log_start_pending.lock().await.remove(follower_id);
+                }
 
                 // if this follower's leo is less than leader's leo then send diff
                 if follower_info.leo < leader_offset.leo {
@@ -324,8 +371,24 @@ where
             .get_partition_size()
             .try_into()
             .unwrap_or(PartitionStatus::SIZE_ERROR);
+        drop(storage_reader);
 
-        LrsRequest::new(self.id().to_owned(), leader, replicas, size)
+        let mut lrs = LrsRequest::new(self.id().to_owned(), leader, replicas, size);
+        lrs.corrupt_ranges
+            .clone_from(&*self.corrupt_ranges.lock().await);
+        lrs.usage = self.storage.storage_usage().await;
+        lrs
+    }
+
+    /// replace ranges which failed checksum validation, SC is notified if they changed
+    pub async fn set_corrupt_ranges(&self, ranges: Vec<CorruptRange>) {
+        let mut current = self.corrupt_ranges.lock().await;
+        if *current == ranges {
+            return;
+        }
+        *current = ranges;
+        drop(current);
+        self.update_status().await;
     }
 
     #[instrument(skip(self))]
@@ -337,30 +400,358 @@ where
 
     /// write records to storage
     /// then update our follower's leo
+    /// batches already written by idempotent producer are skipped
     #[instrument(skip(self, records, notifiers))]
     pub async fn write_record_set(
         &self,
         records: &mut RecordSet<RawRecords>,
         notifiers: &FollowerNotifier,
     ) -> Result<(Offset, Offset, usize)> {
+        // producer state is locked until write is done so sequences are checked in write order
+        let mut producers = self.producers.lock().await;
+        let checks = producers.check_record_set(records)?;
+
+        let mut duplicate_range: Option<(Offset, Offset)> = None;
+        let mut checks = checks.into_iter();
+        records.batches.retain(|_| match checks.next() {
+            Some(SequenceCheck::Duplicate {
+                base_offset,
+                last_offset,
+            }) => {
+                duplicate_range = Some(match duplicate_range {
+                    Some((base, last)) => (base.min(base_offset), last.max(last_offset)),
+                    None => (base_offset, last_offset),
+                });
+                false
+            }
+            _ => true,
+        });
+
+        if records.batches.is_empty() {
+            if let Some((base_offset, last_offset)) = duplicate_range {
+                debug!(base_offset, last_offset, "all batches are duplicates");
+                return Ok((base_offset, last_offset + 1, 0));
+            }
+        }
+
+        // deduplication and transform may drop or replace batches, so remember producer headers before them
+        let headers: Vec<_> = records
+            .batches
+            .iter()
+            .map(|batch| batch.get_header().clone())
+            .collect();
+
+        self.remove_duplicates(records).await?;
         self.transform(records).await?;
         if records.total_records() == 0 {
+            // nothing was written, batches keep their sequence without offsets
+            for header in &headers {
+                producers.update_dropped(header);
+            }
             return Ok((self.hw(), self.leo(), 0));
         }
+        if let Some(cipher) = self.cipher() {
+            self.encrypt_at_leo(&cipher, records)?;
+        }
 
         let offsets = self
             .storage
             .write_record_set(records, self.in_sync_replica == 1)
             .await?;
 
+        if self.sm_ctx.is_none() {
+            // deduplication keeps headers of batches, but drops batches without unique records
+            let mut written = records.batches.iter().peekable();
+            for header in &headers {
+                match written.next_if(|batch| is_same_batch(batch.get_header(), header)) {
+                    Some(batch) => producers.update_written(
+                        header,
+                        batch.get_base_offset(),
+                        batch.get_last_offset(),
+                    ),
+                    None => producers.update_dropped(header),
+                }
+            }
+        } else {
+            producers.update_transformed(&headers, offsets.0, offsets.1);
+        }
+        self.save_producer_state(&mut producers, offsets.1).await;
+        drop(producers);
+
+        self.notify_followers(notifiers).await;
+        self.update_status().await;
+
+        Ok(offsets)
+    }
+
+    /// encrypt batches at offsets storage assigns to them when they are written after leo.
+    /// Leo doesn't move while producers lock is held
+    fn encrypt_at_leo(
+        &self,
+        cipher: &BatchCipher,
+        records: &mut RecordSet<RawRecords>,
+    ) -> Result<()> {
+        let mut offset = self.leo();
+        for batch in records.batches.iter_mut() {
+            batch.set_base_offset(offset);
+            offset = batch.get_last_offset() + 1;
+            cipher.encrypt(batch)?;
+        }
+        Ok(())
+    }
+
+    /// end open transaction of the producer by writing its marker.
+    /// Nothing is written if producer has no open transaction, so marker can be retried
+    #[instrument(skip(self, notifiers))]
+    pub async fn write_txn_marker(
+        &self,
+        producer_id: i64,
+        producer_epoch: i16,
+        marker: TxnMarker,
+        notifiers: &FollowerNotifier,
+    ) -> Result<()> {
+        // serialize with producer writes
+        let producers = self.producers.lock().await;
+        if !self.storage.is_txn_ongoing(producer_id).await {
+            debug!(producer_id, "no open transaction, marker skipped");
+            return Ok(());
+        }
+
+        let batch: Batch<RawRecords> =
+            Batch::txn_marker(producer_id, producer_epoch, marker).try_into()?;
+        let mut records = RecordSet {
+            batches: vec![batch],
+        };
+        self.storage
+            .write_record_set(&mut records, self.in_sync_replica == 1)
+            .await?;
+        drop(producers);
+
         self.notify_followers(notifiers).await;
         self.update_status().await;
+        Ok(())
+    }
+
+    /// write batches restored from snapshot. Batches were already accepted by source partition,
+    /// so they are written without deduplication or transform, producer state is rebuilt from them
+    #[instrument(skip(self, records, notifiers))]
+    pub async fn restore_record_set(
+        &self,
+        records: &mut RecordSet<RawRecords>,
+        notifiers: &FollowerNotifier,
+    ) -> Result<(Offset, Offset, usize)> {
+        let mut producers = self.producers.lock().await;
+        if records
+            .batches
+            .iter()
+            .any(|batch| batch.header.is_encrypted())
+        {
+            self.check_restored_ciphertext(records)?;
+        } else if let Some(cipher) = self.cipher() {
+            self.encrypt_at_leo(&cipher, records)?;
+        }
 
+        let offsets = self
+            .storage
+            .write_record_set(records, self.in_sync_replica == 1)
+            .await?;
+        for batch in &records.batches {
+            producers.update_from_batch(batch);
+        }
+        drop(producers);
+
+        self.notify_followers(notifiers).await;
+        self.update_status().await;
         Ok(offsets)
     }
 
+    /// encrypted records are bound to partition and offset they were written at, so they are
+    /// written as they are only if they land at the same offsets and decrypt with our key
+    fn check_restored_ciphertext(&self, records: &RecordSet<RawRecords>) -> Result<()> {
+        let Some(cipher) = self.cipher() else {
+            return Err(anyhow::anyhow!(
+                "encrypted batches can't be restored to partition without encryption"
+            ));
+        };
+        let mut offset = self.leo();
+        for batch in &records.batches {
+            if !batch.header.is_encrypted() || batch.get_base_offset() != offset {
+                return Err(anyhow::anyhow!(
+                    "encrypted batches can only be restored at offsets they were written at, expected: {offset}, got: {}",
+                    batch.get_base_offset()
+                ));
+            }
+            cipher
+                .decrypt(&mut batch.clone())
+                .context("restored batch doesn't decrypt with partition key")?;
+            offset = batch.get_last_offset() + 1;
+        }
+        Ok(())
+    }
+
+    /// delete committed records before `offset` and send new log start offset to followers.
+    /// Returns new log start offset
+    #[instrument(skip(self, notifier))]
+    pub async fn delete_records_before(
+        &self,
+        offset: Offset,
+        notifier: &FollowerNotifier,
+    ) -> Result<Offset> {
+        let log_start = self.storage.delete_records_before(offset).await?;
+        let followers: Vec<SpuId> = self.followers.read().await.keys().copied().collect();
+        self.log_start_pending
+            .lock()
+            .await
+            .extend(followers.iter().copied());
+        for follower in &followers {
+            notifier.notify_follower(follower, self.id().clone()).await;
+        }
+        self.update_status().await;
+        Ok(log_start)
+    }
+
+    /// drop records already seen in deduplication window
+    async fn remove_duplicates(&self, records: &mut RecordSet<RawRecords>) -> Result<()> {
+        let Some(ref dedup_window) = self.dedup_window else {
+            return Ok(());
+        };
+        let mut dedup_window = dedup_window.lock().await;
+        let now = Utc::now().timestamp_millis();
+        let mut unique = Vec::with_capacity(records.batches.len());
+        for batch in records.batches.drain(..) {
+            if let Some(batch) = dedup_window.filter_batch(batch, now)? {
+                unique.push(batch);
+            }
+        }
+        records.batches = unique;
+        Ok(())
+    }
+
+    /// visit batches stored in the log starting from offset
+    async fn scan_batches<F>(&self, start_offset: Offset, mut visit: F) -> Result<()>
+    where
+        F: FnMut(FileBatch) -> Result<()>,
+    {
+        let mut offset = start_offset;
+        let leo = self.leo();
+        while offset < leo {
+            let slice = self
+                .read_records(offset, u32::MAX, Isolation::ReadUncommitted)
+                .await?;
+            let Some(file_slice) = slice.file_slice else {
+                break;
+            };
+            let mut next_offset = offset;
+            for file_batch in FileBatchIterator::from_raw_slice(file_slice).cipher(self.cipher()) {
+                let file_batch = file_batch?;
+                next_offset = file_batch.batch.get_last_offset() + 1;
+                visit(file_batch)?;
+            }
+            if next_offset <= offset {
+                break;
+            }
+            offset = next_offset;
+        }
+        Ok(())
+    }
+
+    /// save checkpoint of producer state when it is due, failure only means more of the log
+    /// is read on next load
+    async fn save_producer_state(&self, producers: &mut ProducerStateTable, leo: Offset) {
+        let checkpoint = match producers.checkpoint(leo) {
+            Ok(Some(checkpoint)) => checkpoint,
+            Ok(None) => return,
+            Err(err) => {
+                warn!(%err, "failed to encode producer state");
+                return;
+            }
+        };
+        if let Err(err) = self
+            .storage
+            .read()
+            .await
+            .write_state(PRODUCER_STATE_CHECKPOINT, &checkpoint)
+            .await
+        {
+            warn!(%err, "failed to save producer state");
+        } else {
+            debug!(leo, "producer state saved");
+        }
+    }
+
+    /// rebuild idempotent producer state from checkpoint and batches stored in the log after it.
+    /// Whole log is read if there is no usable checkpoint
+    async fn load_producer_state(&self) -> Result<()> {
+        let mut producers = self.producers.lock().await;
+        let (start_offset, _) = self.start_offset_info().await;
+        let leo = self.leo();
+        let mut offset = start_offset;
+        match self
+            .storage
+            .read()
+            .await
+            .read_state(PRODUCER_STATE_CHECKPOINT)
+            .await
+        {
+            Ok(Some(state)) => match ProducerStateTable::from_checkpoint(&state) {
+                Ok((checkpoint_offset, checkpoint)) if checkpoint_offset <= leo => {
+                    *producers = checkpoint;
+                    offset = checkpoint_offset.max(start_offset);
+                }
+                // records after leo have been lost since checkpoint was saved
+                Ok((checkpoint_offset, _)) => {
+                    warn!(checkpoint_offset, leo, "producer state is ahead of log")
+                }
+                Err(err) => warn!(%err, "invalid producer state checkpoint"),
+            },
+            Ok(None) => {}
+            Err(err) => warn!(%err, "failed to read producer state checkpoint"),
+        }
+        self.scan_batches(offset, |file_batch| {
+            producers.update_from_batch(&file_batch.batch);
+            Ok(())
+        })
+        .await?;
+        debug!(offset, "producer state loaded");
+        Ok(())
+    }
+
+    /// fill deduplication window with last records stored in the log
+    async fn load_dedup_window(&self, window: &mut DedupWindow, count: u64) -> Result<()> {
+        let (start_offset, _) = self.start_offset_info().await;
+        let offset = (self.leo() - count as Offset).max(start_offset);
+        let now = Utc::now().timestamp_millis();
+        self.scan_batches(offset, |file_batch| {
+            let has_timestamp = file_batch.batch.get_base_timestamp() != NO_TIMESTAMP;
+            for item in FileRecordIterator::new(std::iter::once(Ok(file_batch)), COMMON_VERSION) {
+                let item = item?;
+                if item.offset < offset {
+                    continue;
+                }
+                // log keeps producer timestamps only, they can't be ahead of broker clock
+                let timestamp = if has_timestamp {
+                    item.timestamp.min(now)
+                } else {
+                    now
+                };
+                window.insert(&item.record, timestamp);
+            }
+            Ok(())
+        })
+        .await?;
+        window.evict_older(now);
+        debug!(offset, "deduplication window loaded");
+        Ok(())
+    }
+
     async fn transform(&self, records: &mut RecordSet<RawRecords>) -> Result<()> {
         if let Some(ref sm_ctx) = self.sm_ctx {
+            let txn_header = records
+                .batches
+                .iter()
+                .map(|batch| batch.get_header())
+                .find(|header| header.is_transactional())
+                .cloned();
             let (sm_result, sm_error) =
                 process_record_set(sm_ctx.write().await.chain_mut(), records)?;
             if let Some(error) = sm_error {
@@ -368,7 +759,14 @@ where
             }
             records.batches.clear();
             if !sm_result.records().is_empty() {
-                let transformed_batch = Batch::<RawRecords>::try_from(sm_result)?;
+                let mut transformed_batch = Batch::<RawRecords>::try_from(sm_result)?;
+                // transformed records stay in transaction of the producer
+                if let Some(txn_header) = txn_header {
+                    let header = transformed_batch.get_mut_header();
+                    header.producer_id = txn_header.producer_id;
+                    header.producer_epoch = txn_header.producer_epoch;
+                    header.set_transactional();
+                }
                 records.batches.push(transformed_batch);
             }
         };
@@ -379,19 +777,37 @@ where
         let leader_offset = self.as_offset();
         let followers = self.followers.read().await;
         debug!(?leader_offset);
-        for follower in &self.replica.replicas {
-            if let Some(follower_info) = followers.get(follower) {
-                debug!(follower, ?follower_info);
-                if follower_info.is_valid() && !follower_info.is_same(&leader_offset) {
-                    debug!(follower, "notify");
-                    notifier.notify_follower(follower, self.id().clone()).await;
-                } else {
-                    debug!(follower, "no update");
-                }
+        // followers may differ from replica metadata while partition is being reassigned
+        for (follower, follower_info) in followers.iter() {
+            debug!(follower, ?follower_info);
+            if follower_info.is_valid() && !follower_info.is_same(&leader_offset) {
+                debug!(follower, "notify");
+                notifier.notify_follower(follower, self.id().clone()).await;
+            } else {
+                debug!(follower, "no update");
             }
         }
     }
 
+    /// sync followers with new replica set.
+    /// Added followers start without offsets until they report back, removed followers are dropped.
+    pub async fn update_followers(&self, replicas: &[SpuId]) {
+        let mut followers = self.followers.write().await;
+        followers.retain(|id, _| replicas.contains(id));
+        for id in replicas.iter().filter(|id| **id != self.leader()) {
+            followers.entry(*id).or_default();
+        }
+        debug!(replica = %self.id(), followers = ?followers.keys(), "updated followers");
+    }
+
+    /// number of in-sync replicas including leader.
+    /// Follower is in sync if it has same leo as leader or had it within `FOLLOWER_MAX_LAG`
+    pub async fn in_sync_replicas(&self) -> u16 {
+        let followers = self.followers.read().await;
+        let caught_up = self.follower_caught_up.lock().await;
+        count_in_sync_replicas(self.leo(), &followers, &caught_up, Instant::now())
+    }
+
     #[allow(dead_code)]
     pub async fn live_replicas(&self) -> Vec<SpuId> {
         self.followers.read().await.keys().cloned().collect()
@@ -471,6 +887,25 @@ where
 {
     pub async fn init(self, ctx: &GlobalContext<FileReplica>) -> Result<LeaderReplicaState<S>> {
         let mut state = self.0;
+        let cipher = ctx
+            .ciphers()
+            .cipher(&state.replica.id, state.replica.encryption.as_ref())
+            .await
+            .context("leader encryption key load failed")?;
+        state.storage.set_cipher(cipher);
+        state.storage.set_metrics(ctx.metrics());
+        state
+            .load_producer_state()
+            .await
+            .context("leader producer state load failed")?;
+        if let Some(config) = &state.replica.deduplication_window {
+            let mut window = DedupWindow::new(config);
+            state
+                .load_dedup_window(&mut window, config.bounds.count)
+                .await
+                .context("leader deduplication window load failed")?;
+            state.dedup_window = Some(Arc::new(Mutex::new(window)));
+        }
         if let Some(dedup) = &state.replica.deduplication {
             debug!(?state.replica.deduplication, "init leader smartmodule context");
             let dedup_filter = dedup_to_invocation(dedup);
@@ -579,6 +1014,32 @@ fn compute_hw(
     sorted_leos.peek().map(|r| r.0)
 }
 
+/// header of batch written by same producer with same sequence
+fn is_same_batch(written: &BatchHeader, header: &BatchHeader) -> bool {
+    written.producer_id == header.producer_id
+        && written.producer_epoch == header.producer_epoch
+        && written.first_sequence == header.first_sequence
+}
+
+fn count_in_sync_replicas(
+    leo: Offset,
+    followers: &BTreeMap<SpuId, OffsetInfo>,
+    caught_up: &BTreeMap<SpuId, Instant>,
+    now: Instant,
+) -> u16 {
+    let in_sync_followers = followers
+        .iter()
+        .filter(|(id, info)| {
+            info.is_valid()
+                && (info.leo == leo
+                    || caught_up
+                        .get(id)
+                        .is_some_and(|at| now.duration_since(*at) <= FOLLOWER_MAX_LAG))
+        })
+        .count();
+    1 + in_sync_followers as u16
+}
+
 impl<S> LeaderReplicaState<S> where S: ReplicaStorage {}
 
 impl LeaderReplicaState<FileReplica> {}
@@ -820,6 +1281,32 @@ mod test_hw_updates {
             Some(8)
         );
     }
+
+    #[test]
+    fn test_count_in_sync_replicas() {
+        let now = Instant::now();
+        let followers = offsets_maps(vec![
+            (5001, OffsetInfo { leo: 10, hw: 8 }),
+            (5002, OffsetInfo { leo: 8, hw: 8 }),
+            (5003, OffsetInfo { leo: 5, hw: 5 }),
+            (5004, OffsetInfo::default()),
+        ]);
+        let caught_up = BTreeMap::from([
+            (5002, now - Duration::from_secs(1)),
+            (5003, now - FOLLOWER_MAX_LAG - Duration::from_secs(1)),
+        ]);
+
+        // leader, 5001 has same leo, 5002 caught up recently
+        assert_eq!(count_in_sync_replicas(10, &followers, &caught_up, now), 3);
+        assert_eq!(
+            count_in_sync_replicas(10, &followers, &caught_up, now + FOLLOWER_MAX_LAG),
+            2
+        );
+        assert_eq!(
+            count_in_sync_replicas(10, &BTreeMap::new(), &BTreeMap::new(), now),
+            1
+        );
+    }
 }
 
 #[cfg(test)]
@@ -851,6 +1338,7 @@ mod test_leader {
     #[derive(Default)]
     struct MockStorage {
         pos: OffsetInfo,
+        log_start: Offset,
     }
 
     impl From<&SpuConfig> for MockConfig {
@@ -867,6 +1355,7 @@ mod test_leader {
         ) -> Result<Self> {
             Ok(MockStorage {
                 pos: OffsetInfo { leo: 0, hw: 0 },
+                log_start: 0,
             })
         }
 
@@ -890,6 +1379,18 @@ mod test_leader {
             })
         }
 
+        async fn read_partition_slice_before(
+            &self,
+            offset: Offset,
+            _max_offset: Offset,
+            _max_len: u32,
+        ) -> Result<ReplicaSlice, ErrorCode> {
+            Ok(ReplicaSlice {
+                end: OffsetInfo { leo: offset, hw: 0 },
+                ..Default::default()
+            })
+        }
+
         // do dummy implementations of write
         async fn write_recordset<R: BatchRecords>(
             &mut self,
@@ -917,15 +1418,38 @@ mod test_leader {
             Ok(true)
         }
 
+        async fn delete_records_before(
+            &mut self,
+            offset: Offset,
+        ) -> Result<Offset, fluvio_storage::StorageError> {
+            self.log_start = self.log_start.max(offset.min(self.pos.hw));
+            Ok(self.log_start)
+        }
+
         type ReplicaConfig = MockConfig;
 
         fn get_log_start_offset(&self) -> Offset {
-            todo!()
+            self.log_start
         }
 
         async fn remove(&self) -> Result<(), fluvio_storage::StorageError> {
             todo!()
         }
+
+        async fn read_state(
+            &self,
+            _name: &str,
+        ) -> Result<Option<Vec<u8>>, fluvio_storage::StorageError> {
+            Ok(None)
+        }
+
+        async fn write_state(
+            &self,
+            _name: &str,
+            _state: &[u8],
+        ) -> Result<(), fluvio_storage::StorageError> {
+            Ok(())
+        }
     }
 
     #[fluvio_future::test]
@@ -949,6 +1473,43 @@ mod test_leader {
         assert_eq!(state.in_sync_replica, 1);
     }
 
+    #[fluvio_future::test]
+    async fn test_update_followers() {
+        let leader_config = SpuConfig {
+            id: 5000,
+            ..Default::default()
+        };
+
+        let replica: ReplicaKey = ("test", 1).into();
+        let state: LeaderReplicaState<MockStorage> = LeaderReplicaState::create(
+            Replica::new(replica, 5000, vec![5000, 5001, 5002]),
+            &leader_config,
+            StatusMessageSink::shared(),
+        )
+        .await
+        .expect("state")
+        .0;
+
+        state
+            .followers
+            .write()
+            .await
+            .get_mut(&5001)
+            .expect("follower")
+            .update(&OffsetInfo { leo: 4, hw: 2 });
+
+        // 5002 is moved to 5003
+        state.update_followers(&[5000, 5001, 5003]).await;
+
+        let followers = state.followers_info().await;
+        assert_eq!(
+            followers.keys().copied().collect::<Vec<_>>(),
+            vec![5001, 5003]
+        );
+        assert_eq!(followers[&5001], OffsetInfo { leo: 4, hw: 2 });
+        assert!(!followers[&5003].is_valid());
+    }
+
     #[fluvio_future::test]
     async fn test_follower_update() {
         let leader_config = SpuConfig {
@@ -1028,6 +1589,30 @@ mod test_leader {
         drop(followers);
         assert!(state.follower_updates(&5002, MAX_BYTES).await.is_none()); // 5002 is still invalid
         assert!(state.follower_updates(&5001, MAX_BYTES).await.is_some()); // 5001 is still need to besync
+
+        // deleted records are sent to caught up follower once, delete is capped at hw
+        assert_eq!(
+            state
+                .delete_records_before(5, &notifier)
+                .await
+                .expect("delete"),
+            2
+        );
+        let updates = state
+            .follower_updates(&5002, MAX_BYTES)
+            .await
+            .expect("some");
+        assert_eq!(updates.partitions[0].log_start_offset, 2);
+        assert!(state.follower_updates(&5002, MAX_BYTES).await.is_none());
+        assert!(matches!(
+            state
+                .read_visible_records(1, MAX_BYTES, Isolation::ReadUncommitted)
+                .await,
+            Err(ErrorCode::OffsetEvicted {
+                offset: 1,
+                next_available: 2,
+            })
+        ));
     }
 
     #[fluvio_future::test]
@@ -0,0 +1,112 @@
+//!
+//! # Replication Throttle
+//!
+//! Limits bytes of records leader sends to one follower per second, so follower rebuilding
+//! its replicas doesn't saturate leader's disk and network. Usage is counted in one second
+//! windows. Once budget of window is used up, records are not read until next window starts,
+//! offset updates are still exchanged.
+//!
+use std::time::{Duration, Instant};
+
+use tracing::debug;
+
+use fluvio_future::timer::sleep;
+
+const THROTTLE_WINDOW: Duration = Duration::from_secs(1);
+
+#[derive(Debug)]
+pub(crate) struct ReplicationThrottle {
+    /// None is unlimited
+    bytes_per_sec: Option<u64>,
+    window_start: Instant,
+    sent: u64,
+}
+
+impl ReplicationThrottle {
+    pub(crate) fn new(bytes_per_sec: Option<u64>) -> Self {
+        Self {
+            bytes_per_sec,
+            window_start: Instant::now(),
+            sent: 0,
+        }
+    }
+
+    /// change rate limit, None is unlimited
+    pub(crate) fn set_rate(&mut self, bytes_per_sec: Option<u64>) {
+        self.bytes_per_sec = bytes_per_sec;
+    }
+
+    /// bytes which can still be sent in window, None if unlimited
+    fn remaining(&mut self, now: Instant) -> Option<u64> {
+        let limit = self.bytes_per_sec?;
+        if now.duration_since(self.window_start) >= THROTTLE_WINDOW {
+            self.window_start = now;
+            self.sent = 0;
+        }
+        Some(limit.saturating_sub(self.sent))
+    }
+
+    /// wait until window has budget left
+    pub(crate) async fn wait(&mut self) {
+        let now = Instant::now();
+        if self.remaining(now) == Some(0) {
+            let delay = THROTTLE_WINDOW.saturating_sub(now.duration_since(self.window_start));
+            debug!(?delay, "throttling replication");
+            sleep(delay).await;
+        }
+    }
+
+    /// max bytes to read for next replica, capped by budget left in window
+    pub(crate) fn max_bytes(&mut self, max_bytes: u32) -> u32 {
+        match self.remaining(Instant::now()) {
+            Some(remaining) => remaining.min(max_bytes as u64) as u32,
+            None => max_bytes,
+        }
+    }
+
+    /// record bytes sent to follower
+    pub(crate) fn record(&mut self, bytes: u64) {
+        if self.bytes_per_sec.is_some() {
+            self.sent += bytes;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_throttle_budget_per_window() {
+        let mut throttle = ReplicationThrottle::new(Some(100));
+        assert_eq!(throttle.max_bytes(1000), 100);
+
+        throttle.record(70);
+        assert_eq!(throttle.max_bytes(1000), 30);
+        assert_eq!(throttle.max_bytes(10), 10);
+
+        throttle.record(30);
+        assert_eq!(throttle.max_bytes(1000), 0);
+
+        let next_window = throttle.window_start + THROTTLE_WINDOW;
+        assert_eq!(throttle.remaining(next_window), Some(100));
+    }
+
+    #[test]
+    fn test_unlimited_throttle() {
+        let mut throttle = ReplicationThrottle::new(None);
+        throttle.record(u32::MAX as u64);
+        assert_eq!(throttle.max_bytes(1000), 1000);
+        assert_eq!(throttle.sent, 0);
+    }
+
+    #[fluvio_future::test]
+    async fn test_wait_for_next_window() {
+        let mut throttle = ReplicationThrottle::new(Some(10));
+        throttle.record(10);
+        let start = Instant::now();
+        throttle.wait().await;
+        assert!(start.elapsed() > Duration::from_millis(500));
+        assert_eq!(throttle.max_bytes(100), 10);
+    }
+}
@@ -771,3 +771,109 @@ async fn test_replica_state_cleans_up_offset_producers() {
     let publishers = shared_publishers.lock().await;
     assert!(publishers.len() == 1);
 }
+
+/// follower reconnects to leader after its request fails, then catches up
+#[cfg(feature = "fault-injection")]
+#[fluvio_future::test(ignore)]
+async fn test_replication2_leader_connection_fault() {
+    use std::io::ErrorKind;
+    use fluvio_types::fault::{self, FaultAction, SOCKET_SEND};
+
+    let builder = TestConfig::builder()
+        .followers(1_u16)
+        .base_port(13080_u16)
+        .generate("replication2_connection_fault");
+
+    let (leader_gctx, leader_replica) = builder.leader_replica().await;
+    leader_replica
+        .write_record_set(
+            &mut create_raw_recordset(2),
+            leader_gctx.follower_notifier(),
+        )
+        .await
+        .expect("write");
+
+    let spu_server = create_internal_server(builder.leader_addr(), leader_gctx.clone()).run();
+    sleep(Duration::from_millis(MAX_WAIT_LEADER)).await;
+
+    // first request from follower to leader fails
+    let _fault = fault::inject_scoped(
+        SOCKET_SEND,
+        &builder.leader_addr(),
+        FaultAction::Error(ErrorKind::ConnectionReset),
+        Some(1),
+    );
+    let (_, follower_replica) = builder.follower_replica(0).await;
+
+    sleep(Duration::from_millis(*MAX_WAIT_REPLICATION)).await;
+    assert_eq!(follower_replica.leo(), 0);
+    assert_eq!(leader_replica.hw(), 0);
+
+    // follower retries connection after 5 seconds
+    sleep(Duration::from_millis(5000 + *MAX_WAIT_REPLICATION)).await;
+    assert_eq!(follower_replica.leo(), 2);
+    assert_eq!(follower_replica.hw(), 2);
+    assert_eq!(leader_replica.hw(), 2);
+
+    spu_server.notify();
+}
+
+/// failed write on leader is not replicated, followers get records written after storage recovers
+#[cfg(feature = "fault-injection")]
+#[fluvio_future::test(ignore)]
+async fn test_replication2_leader_storage_fault() {
+    use std::io::ErrorKind;
+    use fluvio_types::fault::{self, FaultAction, STORAGE_WRITE};
+
+    let builder = TestConfig::builder()
+        .followers(1_u16)
+        .base_port(13090_u16)
+        .generate("replication2_storage_fault");
+
+    let (leader_gctx, leader_replica) = builder.leader_replica().await;
+    let spu_server = create_internal_server(builder.leader_addr(), leader_gctx.clone()).run();
+    sleep(Duration::from_millis(MAX_WAIT_LEADER)).await;
+
+    let (_, follower_replica) = builder.follower_replica(0).await;
+    sleep(Duration::from_millis(MAX_WAIT_FOLLOWER)).await;
+
+    // only leader storage fails, follower shares base dir but has its own spu dir
+    let leader_dir = builder
+        .leader_config()
+        .log
+        .base_dir
+        .join(format!("spu-logs-{LEADER}"));
+    let guard = fault::inject_scoped(
+        STORAGE_WRITE,
+        &leader_dir.to_string_lossy(),
+        FaultAction::Error(ErrorKind::Other),
+        None,
+    );
+    assert!(leader_replica
+        .write_record_set(
+            &mut create_raw_recordset(2),
+            leader_gctx.follower_notifier(),
+        )
+        .await
+        .is_err());
+    drop(guard);
+
+    sleep(Duration::from_millis(*MAX_WAIT_REPLICATION)).await;
+    assert_eq!(leader_replica.leo(), 0);
+    assert_eq!(follower_replica.leo(), 0);
+
+    leader_replica
+        .write_record_set(
+            &mut create_raw_recordset(2),
+            leader_gctx.follower_notifier(),
+        )
+        .await
+        .expect("write");
+
+    sleep(Duration::from_millis(*MAX_WAIT_REPLICATION)).await;
+    assert_eq!(follower_replica.leo(), 2);
+    assert_eq!(follower_replica.hw(), 2);
+    assert_eq!(leader_replica.hw(), 2);
+
+    spu_server.notify();
+}
@@ -4,10 +4,17 @@ use anyhow::Result;
 use fluvio_protocol::api::{RequestMessage, ResponseMessage, Request};
 use fluvio_spu_schema::produce::DefaultProduceRequest;
 use fluvio_spu_schema::fetch::DefaultFetchRequest;
+use fluvio_protocol::link::capabilities::Capabilities;
 use fluvio_protocol::link::versions::ApiVersionKey;
 use fluvio_spu_schema::server::SpuServerApiKey;
+use fluvio_spu_schema::server::consumer_group::{JoinGroupRequest, HeartbeatRequest, LeaveGroupRequest};
+use fluvio_spu_schema::server::delete_records::DeleteRecordsRequest;
 use fluvio_spu_schema::server::fetch_offset::FetchOffsetsRequest;
+use fluvio_spu_schema::server::snapshot::{SnapshotPartitionRequest, RestorePartitionRequest};
 use fluvio_spu_schema::server::stream_fetch::DefaultStreamFetchRequest;
+use fluvio_spu_schema::server::transaction::{
+    InitTransactionRequest, AddTxnPartitionsRequest, EndTransactionRequest, WriteTxnMarkerRequest,
+};
 use fluvio_spu_schema::server::update_offset::UpdateOffsetsRequest;
 use fluvio_spu_schema::{ApiVersionsRequest, ApiVersionsResponse};
 
@@ -16,7 +23,10 @@ pub async fn handle_api_version_request(
     request: RequestMessage<ApiVersionsRequest>,
 ) -> Result<ResponseMessage<ApiVersionsResponse>> {
     let client_version = &request.request.client_version;
-    let mut response = ApiVersionsResponse::default();
+    let mut response = ApiVersionsResponse {
+        capabilities: Capabilities::supported(),
+        ..Default::default()
+    };
     response.api_keys.push(make_version_key(
         SpuServerApiKey::Produce,
         DefaultProduceRequest::MIN_API_VERSION,
@@ -47,6 +57,56 @@ pub async fn handle_api_version_request(
         0,
         UpdateOffsetsRequest::DEFAULT_API_VERSION,
     ));
+    response.api_keys.push(make_version_key(
+        SpuServerApiKey::JoinGroup,
+        0,
+        JoinGroupRequest::DEFAULT_API_VERSION,
+    ));
+    response.api_keys.push(make_version_key(
+        SpuServerApiKey::Heartbeat,
+        0,
+        HeartbeatRequest::DEFAULT_API_VERSION,
+    ));
+    response.api_keys.push(make_version_key(
+        SpuServerApiKey::LeaveGroup,
+        0,
+        LeaveGroupRequest::DEFAULT_API_VERSION,
+    ));
+    response.api_keys.push(make_version_key(
+        SpuServerApiKey::InitTransaction,
+        0,
+        InitTransactionRequest::DEFAULT_API_VERSION,
+    ));
+    response.api_keys.push(make_version_key(
+        SpuServerApiKey::AddTxnPartitions,
+        0,
+        AddTxnPartitionsRequest::DEFAULT_API_VERSION,
+    ));
+    response.api_keys.push(make_version_key(
+        SpuServerApiKey::EndTransaction,
+        0,
+        EndTransactionRequest::DEFAULT_API_VERSION,
+    ));
+    response.api_keys.push(make_version_key(
+        SpuServerApiKey::WriteTxnMarker,
+        0,
+        WriteTxnMarkerRequest::DEFAULT_API_VERSION,
+    ));
+    response.api_keys.push(make_version_key(
+        SpuServerApiKey::SnapshotPartition,
+        0,
+        SnapshotPartitionRequest::DEFAULT_API_VERSION,
+    ));
+    response.api_keys.push(make_version_key(
+        SpuServerApiKey::RestorePartition,
+        0,
+        RestorePartitionRequest::DEFAULT_API_VERSION,
+    ));
+    response.api_keys.push(make_version_key(
+        SpuServerApiKey::DeleteRecords,
+        0,
+        DeleteRecordsRequest::DEFAULT_API_VERSION,
+    ));
 
     trace!("Returning ApiVersionsResponse: {:#?}", &response);
     Ok(request.new_response(response))
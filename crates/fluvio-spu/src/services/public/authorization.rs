@@ -0,0 +1,419 @@
+//!
+//! # Request Authorization
+//!
+//! Every public request is checked against ACLs here before it's dispatched to its handler.
+//! Requests which modify or remove data of partition need `Admin` permission on the topic.
+//! Requests which refer to replica through stream or mirror are checked against topic of
+//! that replica, they are denied if it can't be found.
+//!
+use std::borrow::Cow;
+use std::fmt::Debug;
+
+use tracing::debug;
+
+use fluvio_protocol::Encoder;
+use fluvio_protocol::api::{Request, RequestMessage, ResponseMessage};
+use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::record::ReplicaKey;
+use fluvio_socket::{ExclusiveFlvSink, SocketError};
+use fluvio_spu_schema::fetch::FileFetchResponse;
+use fluvio_spu_schema::server::SpuServerRequest;
+use fluvio_spu_schema::server::consumer_group::{
+    JoinGroupResponse, HeartbeatResponse, LeaveGroupResponse,
+};
+use fluvio_spu_schema::server::consumer_offset::{
+    UpdateConsumerOffsetResponse, DeleteConsumerOffsetResponse, FetchConsumerOffsetsResponse,
+};
+use fluvio_spu_schema::server::fetch_offset::{
+    FetchOffsetsResponse, FetchOffsetTopicResponse, FetchOffsetPartitionResponse,
+};
+use fluvio_spu_schema::server::snapshot::{SnapshotPartitionResponse, RestorePartitionResponse};
+use fluvio_spu_schema::server::stream_fetch::StreamFetchResponse;
+use fluvio_spu_schema::server::transaction::{
+    InitTransactionResponse, AddTxnPartitionsResponse, EndTransactionResponse,
+    WriteTxnMarkerResponse,
+};
+use fluvio_spu_schema::server::delete_records::DeleteRecordsResponse;
+use fluvio_spu_schema::server::update_offset::{UpdateOffsetsResponse, OffsetUpdateStatus};
+use fluvio_spu_schema::fetch::FilePartitionResponse;
+use fluvio_controlplane_metadata::acl::{AclPermission, AclResourceType};
+
+use crate::core::DefaultSharedGlobalContext;
+
+use super::conn_context::ConnectionContext;
+use super::produce_handler::error_response as produce_error_response;
+
+/// permission needed on resource
+type Requirement<'a> = (AclResourceType, Cow<'a, str>, AclPermission);
+
+/// permissions needed by request, all of them must be granted
+fn requirements(request: &SpuServerRequest) -> Vec<Requirement<'_>> {
+    use AclPermission::*;
+    use AclResourceType::*;
+
+    match request {
+        SpuServerRequest::ApiVersionsRequest(_) => vec![],
+        SpuServerRequest::ProduceRequest(req) => req
+            .request
+            .topics
+            .iter()
+            .map(|topic| (Topic, topic.name.as_str().into(), Produce))
+            .collect(),
+        SpuServerRequest::FileFetchRequest(req) => req
+            .request
+            .topics
+            .iter()
+            .map(|topic| (Topic, topic.name.as_str().into(), Consume))
+            .collect(),
+        SpuServerRequest::FetchOffsetsRequest(req) => {
+            let mut requirements: Vec<Requirement<'_>> = req
+                .request
+                .topics
+                .iter()
+                .map(|topic| (Topic, topic.name.as_str().into(), Consume))
+                .collect();
+            if let Some(consumer_id) = &req.request.consumer_id {
+                requirements.push((ConsumerGroup, consumer_id.as_str().into(), Consume));
+            }
+            requirements
+        }
+        SpuServerRequest::FileStreamFetchRequest(req) => {
+            let mut requirements = vec![(Topic, req.request.topic.as_str().into(), Consume)];
+            if let Some(consumer_id) = &req.request.consumer_id {
+                requirements.push((ConsumerGroup, consumer_id.as_str().into(), Consume));
+            }
+            requirements
+        }
+        // replicas are resolved from streams of connection, see `replica_requirements`
+        SpuServerRequest::UpdateOffsetsRequest(_) => vec![],
+        SpuServerRequest::UpdateConsumerOffsetRequest(_) => vec![],
+        SpuServerRequest::DeleteConsumerOffsetRequest(req) => {
+            vec![(
+                ConsumerGroup,
+                req.request.consumer_id.as_str().into(),
+                Admin,
+            )]
+        }
+        // lists offsets of every consumer
+        SpuServerRequest::FetchConsumerOffsetsRequest(_) => {
+            vec![(ConsumerGroup, "*".into(), Admin)]
+        }
+        // replica is resolved from mirror, see `replica_requirements`
+        SpuServerRequest::StartMirrorRequest(_) => vec![],
+        SpuServerRequest::JoinGroupRequest(req) => vec![
+            (ConsumerGroup, req.request.group_id.as_str().into(), Consume),
+            (Topic, req.request.topic.as_str().into(), Consume),
+        ],
+        SpuServerRequest::HeartbeatRequest(req) => {
+            vec![(ConsumerGroup, req.request.group_id.as_str().into(), Consume)]
+        }
+        SpuServerRequest::LeaveGroupRequest(req) => {
+            vec![(ConsumerGroup, req.request.group_id.as_str().into(), Consume)]
+        }
+        SpuServerRequest::InitTransactionRequest(req) => vec![(
+            TransactionalId,
+            req.request.transactional_id.as_str().into(),
+            Produce,
+        )],
+        SpuServerRequest::AddTxnPartitionsRequest(req) => {
+            let mut requirements = vec![(
+                TransactionalId,
+                req.request.transactional_id.as_str().into(),
+                Produce,
+            )];
+            requirements.extend(
+                req.request
+                    .partitions
+                    .iter()
+                    .map(|replica| (Topic, replica.topic.as_str().into(), Produce)),
+            );
+            requirements
+        }
+        SpuServerRequest::EndTransactionRequest(req) => vec![(
+            TransactionalId,
+            req.request.transactional_id.as_str().into(),
+            Produce,
+        )],
+        SpuServerRequest::WriteTxnMarkerRequest(req) => {
+            vec![(Topic, req.request.replica.topic.as_str().into(), Admin)]
+        }
+        SpuServerRequest::SnapshotPartitionRequest(req) => {
+            vec![(Topic, req.request.replica.topic.as_str().into(), Admin)]
+        }
+        SpuServerRequest::RestorePartitionRequest(req) => {
+            vec![(Topic, req.request.replica.topic.as_str().into(), Admin)]
+        }
+        SpuServerRequest::DeleteRecordsRequest(req) => {
+            vec![(Topic, req.request.replica.topic.as_str().into(), Admin)]
+        }
+    }
+}
+
+/// permissions on replicas which request refers to by stream or mirror.
+/// None if replica can't be resolved, such request is denied
+async fn replica_requirements(
+    ctx: &DefaultSharedGlobalContext,
+    conn_ctx: &ConnectionContext,
+    request: &SpuServerRequest,
+) -> Option<Vec<Requirement<'static>>> {
+    use AclPermission::*;
+    use AclResourceType::*;
+
+    let stream_ids: Vec<u32> = match request {
+        SpuServerRequest::UpdateOffsetsRequest(req) => req
+            .request
+            .offsets
+            .iter()
+            .map(|update| update.session_id)
+            .collect(),
+        SpuServerRequest::UpdateConsumerOffsetRequest(req) => vec![req.request.session_id],
+        // mirror writes records of remote into home replica
+        SpuServerRequest::StartMirrorRequest(req) => {
+            let leader = ctx
+                .leaders_state()
+                .find_mirror_home_leader(
+                    &req.request.remote_cluster_id,
+                    &req.request.remote_replica,
+                )
+                .await?;
+            return Some(vec![(Topic, leader.id().topic.clone().into(), Produce)]);
+        }
+        _ => return Some(vec![]),
+    };
+
+    let mut requirements = vec![];
+    for stream_id in stream_ids {
+        let publisher = conn_ctx
+            .stream_publishers()
+            .get_publisher(stream_id)
+            .await?;
+        requirements.push((Topic, publisher.topic.into(), Consume));
+        if let Some(consumer) = publisher.consumer {
+            requirements.push((ConsumerGroup, consumer.consumer_id.into(), Consume));
+        }
+    }
+    Some(requirements)
+}
+
+/// check if connection principal is granted every permission request needs
+pub(crate) async fn is_authorized(
+    ctx: &DefaultSharedGlobalContext,
+    conn_ctx: &ConnectionContext,
+    request: &SpuServerRequest,
+) -> bool {
+    let mut required = requirements(request);
+    match replica_requirements(ctx, conn_ctx, request).await {
+        Some(replica_required) => required.extend(replica_required),
+        None => {
+            debug!(%request, "replica of request not found, denied");
+            return false;
+        }
+    }
+
+    match required
+        .into_iter()
+        .find(|(resource_type, resource, permission)| {
+            !conn_ctx.allows(ctx, *resource_type, resource, *permission)
+        }) {
+        Some((resource_type, resource, permission)) => {
+            debug!(%request, %resource_type, %resource, %permission, "request denied by acl");
+            false
+        }
+        None => true,
+    }
+}
+
+/// send `PermissionDenied` response to request
+pub(crate) async fn send_denied_response(
+    request: SpuServerRequest,
+    sink: &mut ExclusiveFlvSink,
+) -> Result<(), SocketError> {
+    let error_code = ErrorCode::PermissionDenied;
+    match request {
+        SpuServerRequest::ApiVersionsRequest(_) | SpuServerRequest::StartMirrorRequest(_) => Ok(()),
+        SpuServerRequest::ProduceRequest(req) => {
+            let response = produce_error_response(&req.request, &error_code);
+            send(sink, &req, response).await
+        }
+        SpuServerRequest::FileFetchRequest(req) => {
+            let response = req.new_response(FileFetchResponse {
+                error_code,
+                ..Default::default()
+            });
+            let mut inner = sink.lock().await;
+            inner
+                .encode_file_slices(&response, req.header.api_version())
+                .await?;
+            Ok(())
+        }
+        SpuServerRequest::FetchOffsetsRequest(req) => {
+            let topics = req
+                .request
+                .topics
+                .iter()
+                .map(|topic| FetchOffsetTopicResponse {
+                    name: topic.name.clone(),
+                    partitions: topic
+                        .partitions
+                        .iter()
+                        .map(|partition| FetchOffsetPartitionResponse {
+                            error_code: error_code.clone(),
+                            partition_index: partition.partition_index,
+                            ..Default::default()
+                        })
+                        .collect(),
+                })
+                .collect();
+            send(sink, &req, FetchOffsetsResponse { topics }).await
+        }
+        SpuServerRequest::FileStreamFetchRequest(req) => {
+            let replica = ReplicaKey::new(req.request.topic.clone(), req.request.partition);
+            let response = StreamFetchResponse {
+                topic: replica.topic,
+                stream_id: 0,
+                partition: FilePartitionResponse {
+                    partition_index: replica.partition,
+                    error_code,
+                    ..Default::default()
+                },
+            };
+            send(sink, &req, response).await
+        }
+        SpuServerRequest::UpdateOffsetsRequest(req) => {
+            let status = req
+                .request
+                .offsets
+                .iter()
+                .map(|update| OffsetUpdateStatus {
+                    session_id: update.session_id,
+                    error: error_code.clone(),
+                })
+                .collect();
+            send(sink, &req, UpdateOffsetsResponse { status }).await
+        }
+        SpuServerRequest::UpdateConsumerOffsetRequest(req) => {
+            let response = UpdateConsumerOffsetResponse {
+                offset: req.request.offset,
+                error_code,
+            };
+            send(sink, &req, response).await
+        }
+        SpuServerRequest::DeleteConsumerOffsetRequest(req) => {
+            send(sink, &req, DeleteConsumerOffsetResponse { error_code }).await
+        }
+        SpuServerRequest::FetchConsumerOffsetsRequest(req) => {
+            let response = FetchConsumerOffsetsResponse {
+                error_code,
+                ..Default::default()
+            };
+            send(sink, &req, response).await
+        }
+        SpuServerRequest::JoinGroupRequest(req) => {
+            let response = JoinGroupResponse {
+                error_code,
+                ..Default::default()
+            };
+            send(sink, &req, response).await
+        }
+        SpuServerRequest::HeartbeatRequest(req) => {
+            let response = HeartbeatResponse {
+                error_code,
+                ..Default::default()
+            };
+            send(sink, &req, response).await
+        }
+        SpuServerRequest::LeaveGroupRequest(req) => {
+            send(sink, &req, LeaveGroupResponse { error_code }).await
+        }
+        SpuServerRequest::InitTransactionRequest(req) => {
+            let response = InitTransactionResponse {
+                error_code,
+                ..Default::default()
+            };
+            send(sink, &req, response).await
+        }
+        SpuServerRequest::AddTxnPartitionsRequest(req) => {
+            send(sink, &req, AddTxnPartitionsResponse { error_code }).await
+        }
+        SpuServerRequest::EndTransactionRequest(req) => {
+            send(sink, &req, EndTransactionResponse { error_code }).await
+        }
+        SpuServerRequest::WriteTxnMarkerRequest(req) => {
+            send(sink, &req, WriteTxnMarkerResponse { error_code }).await
+        }
+        SpuServerRequest::SnapshotPartitionRequest(req) => {
+            let response = SnapshotPartitionResponse {
+                error_code,
+                ..Default::default()
+            };
+            send(sink, &req, response).await
+        }
+        SpuServerRequest::RestorePartitionRequest(req) => {
+            let response = RestorePartitionResponse {
+                error_code,
+                ..Default::default()
+            };
+            send(sink, &req, response).await
+        }
+        SpuServerRequest::DeleteRecordsRequest(req) => {
+            let response = DeleteRecordsResponse {
+                error_code,
+                ..Default::default()
+            };
+            send(sink, &req, response).await
+        }
+    }
+}
+
+async fn send<R: Request>(
+    sink: &mut ExclusiveFlvSink,
+    request: &RequestMessage<R>,
+    response: R::Response,
+) -> Result<(), SocketError>
+where
+    ResponseMessage<R::Response>: Encoder + Debug,
+{
+    let response = request.new_response(response);
+    sink.send_response(&response, request.header.api_version())
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use fluvio_spu_schema::server::consumer_group::HeartbeatRequest;
+    use fluvio_spu_schema::server::delete_records::DeleteRecordsRequest;
+
+    use super::*;
+
+    #[test]
+    fn test_request_requirements() {
+        let delete = SpuServerRequest::DeleteRecordsRequest(RequestMessage::new_request(
+            DeleteRecordsRequest {
+                replica: ReplicaKey::new("orders", 0u32),
+                ..Default::default()
+            },
+        ));
+        assert_eq!(
+            requirements(&delete),
+            vec![(
+                AclResourceType::Topic,
+                "orders".into(),
+                AclPermission::Admin
+            )]
+        );
+
+        let heartbeat =
+            SpuServerRequest::HeartbeatRequest(RequestMessage::new_request(HeartbeatRequest {
+                group_id: "readers".to_owned(),
+                ..Default::default()
+            }));
+        assert_eq!(
+            requirements(&heartbeat),
+            vec![(
+                AclResourceType::ConsumerGroup,
+                "readers".into(),
+                AclPermission::Consume
+            )]
+        );
+    }
+}
@@ -1,17 +1,42 @@
+use fluvio_auth::x509::X509Identity;
+use fluvio_controlplane_metadata::acl::{AclPermission, AclResourceType};
+
+use crate::core::DefaultSharedGlobalContext;
 use crate::services::public::StreamPublishers;
 
 #[derive(Debug)]
 pub(crate) struct ConnectionContext {
     stream_publishers: StreamPublishers,
+    /// verified identity, none if connection is not authenticated
+    identity: Option<X509Identity>,
 }
 
 impl ConnectionContext {
-    pub(crate) fn new() -> Self {
+    pub(crate) fn new(identity: Option<X509Identity>) -> Self {
         Self {
             stream_publishers: StreamPublishers::new(),
+            identity,
         }
     }
 
+    pub(crate) fn principal(&self) -> Option<&str> {
+        self.identity
+            .as_ref()
+            .map(|identity| identity.principal.as_str())
+    }
+
+    /// check ACL of connection principal
+    pub(crate) fn allows(
+        &self,
+        ctx: &DefaultSharedGlobalContext,
+        resource_type: AclResourceType,
+        resource: &str,
+        permission: AclPermission,
+    ) -> bool {
+        ctx.acl()
+            .allows(self.principal(), resource_type, resource, permission)
+    }
+
     pub(crate) fn stream_publishers(&self) -> &StreamPublishers {
         &self.stream_publishers
     }
@@ -0,0 +1,199 @@
+use std::io::Error as IoError;
+use std::time::Duration;
+
+use tracing::{debug, instrument};
+
+use fluvio_protocol::api::{RequestMessage, ResponseMessage};
+use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::record::ReplicaKey;
+use fluvio::spu::SpuDirectory;
+use fluvio_spu_schema::server::consumer_group::{
+    GroupAssignment, GroupMembership, JoinGroupRequest, JoinGroupResponse, HeartbeatRequest,
+    HeartbeatResponse, LeaveGroupRequest, LeaveGroupResponse,
+};
+use fluvio_types::{PartitionId, defaults::CONSUMER_STORAGE_TOPIC};
+
+use crate::core::DefaultSharedGlobalContext;
+
+#[instrument(skip(req_msg, ctx))]
+pub(crate) async fn handle_join_group_request(
+    req_msg: RequestMessage<JoinGroupRequest>,
+    ctx: DefaultSharedGlobalContext,
+) -> Result<ResponseMessage<JoinGroupResponse>, IoError> {
+    // partition count sent by client is ignored, topic partitions are known to SPU
+    let JoinGroupRequest {
+        group_id,
+        member_id,
+        topic,
+        session_timeout_ms,
+        ..
+    } = &req_msg.request;
+
+    let partitions = ctx.replica_localstore().topic_partitions(topic);
+    let response = match ensure_coordinator(&ctx).await {
+        Ok(()) if partitions == 0 => JoinGroupResponse {
+            error_code: ErrorCode::TopicNotFound,
+            ..Default::default()
+        },
+        Ok(()) => match ctx
+            .group_coordinator()
+            .join(
+                group_id,
+                member_id,
+                topic,
+                partitions,
+                Duration::from_millis(*session_timeout_ms as u64),
+            )
+            .await
+        {
+            Ok((member_id, assignment)) => JoinGroupResponse {
+                error_code: ErrorCode::None,
+                member_id,
+                assignment,
+            },
+            Err(error_code) => JoinGroupResponse {
+                error_code,
+                ..Default::default()
+            },
+        },
+        Err(error_code) => JoinGroupResponse {
+            error_code,
+            ..Default::default()
+        },
+    };
+
+    debug!(?response, "join group result");
+    Ok(req_msg.new_response(response))
+}
+
+#[instrument(skip(req_msg, ctx))]
+pub(crate) async fn handle_heartbeat_request(
+    req_msg: RequestMessage<HeartbeatRequest>,
+    ctx: DefaultSharedGlobalContext,
+) -> Result<ResponseMessage<HeartbeatResponse>, IoError> {
+    let HeartbeatRequest {
+        group_id,
+        member_id,
+        generation,
+    } = &req_msg.request;
+
+    let result = match ensure_coordinator(&ctx).await {
+        Ok(()) => {
+            ctx.group_coordinator()
+                .heartbeat(group_id, member_id, *generation)
+                .await
+        }
+        Err(error_code) => Err(error_code),
+    };
+    let response = match result {
+        Ok(assignment) => HeartbeatResponse {
+            error_code: ErrorCode::None,
+            assignment,
+        },
+        Err(error_code) => HeartbeatResponse {
+            error_code,
+            ..Default::default()
+        },
+    };
+
+    Ok(req_msg.new_response(response))
+}
+
+#[instrument(skip(req_msg, ctx))]
+pub(crate) async fn handle_leave_group_request(
+    req_msg: RequestMessage<LeaveGroupRequest>,
+    ctx: DefaultSharedGlobalContext,
+) -> Result<ResponseMessage<LeaveGroupResponse>, IoError> {
+    let LeaveGroupRequest {
+        group_id,
+        member_id,
+    } = &req_msg.request;
+
+    let result = match ensure_coordinator(&ctx).await {
+        Ok(()) => ctx.group_coordinator().leave(group_id, member_id).await,
+        Err(error_code) => Err(error_code),
+    };
+    let error_code = result.err().unwrap_or(ErrorCode::None);
+
+    debug!(?error_code, "leave group result");
+    Ok(req_msg.new_response(LeaveGroupResponse { error_code }))
+}
+
+/// Check that consumer group member fetching partition is in current generation and the partition
+/// is assigned to it. Fetch counts as heartbeat, group is asked on its coordinator which may be
+/// other SPU
+pub(super) async fn check_group_membership(
+    ctx: &DefaultSharedGlobalContext,
+    membership: &GroupMembership,
+    partition: PartitionId,
+) -> Result<(), ErrorCode> {
+    let GroupMembership {
+        group_id,
+        member_id,
+        generation,
+    } = membership;
+
+    let assignment = match ensure_coordinator(ctx).await {
+        Ok(()) => {
+            ctx.group_coordinator()
+                .heartbeat(group_id, member_id, *generation)
+                .await?
+        }
+        Err(_) => remote_heartbeat(ctx, membership).await?,
+    };
+    if assignment.generation != *generation || !assignment.partitions.contains(&partition) {
+        debug!(
+            group_id,
+            member_id,
+            generation,
+            current = assignment.generation,
+            partition,
+            "fencing fetch of stale consumer group member"
+        );
+        return Err(ErrorCode::ConsumerGroupStaleGeneration {
+            group_id: group_id.to_owned(),
+            generation: *generation,
+        });
+    }
+    Ok(())
+}
+
+async fn remote_heartbeat(
+    ctx: &DefaultSharedGlobalContext,
+    membership: &GroupMembership,
+) -> Result<GroupAssignment, ErrorCode> {
+    let consumers_replica_id =
+        ReplicaKey::new(CONSUMER_STORAGE_TOPIC, <PartitionId as Default>::default());
+    let socket = ctx
+        .leaders()
+        .create_serial_socket(&consumers_replica_id)
+        .await
+        .map_err(|err| ErrorCode::Other(err.to_string()))?;
+    let response = socket
+        .send_receive(HeartbeatRequest {
+            group_id: membership.group_id.clone(),
+            member_id: membership.member_id.clone(),
+            generation: membership.generation,
+        })
+        .await
+        .map_err(|err| ErrorCode::Other(err.to_string()))?;
+    if response.error_code != ErrorCode::None {
+        return Err(response.error_code);
+    }
+    Ok(response.assignment)
+}
+
+/// groups and transactions are coordinated by the leader of consumer offsets partition
+pub(super) async fn ensure_coordinator(ctx: &DefaultSharedGlobalContext) -> Result<(), ErrorCode> {
+    let consumers_replica_id =
+        ReplicaKey::new(CONSUMER_STORAGE_TOPIC, <PartitionId as Default>::default());
+    if ctx
+        .leaders_state()
+        .get(&consumers_replica_id)
+        .await
+        .is_none()
+    {
+        return Err(ErrorCode::PartitionNotLeader);
+    }
+    Ok(())
+}
@@ -0,0 +1,52 @@
+//!
+//! # Dead-letter Topic
+//!
+//! Records failing in consumer SmartModules are written to dead-letter topic chosen by
+//! consumer, so stream can continue past them. Dead letters go to the first partition
+//! of the topic, written locally if this SPU leads it or sent to its leader otherwise.
+//!
+use tracing::debug;
+
+use fluvio_controlplane_metadata::partition::ReplicaKey;
+use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::link::smartmodule::{DeadLetter, SmartModuleTransformRuntimeError};
+
+use crate::core::DefaultSharedGlobalContext;
+
+pub(crate) struct DeadLetterTopic {
+    ctx: DefaultSharedGlobalContext,
+    replica: ReplicaKey,
+}
+
+impl DeadLetterTopic {
+    pub(crate) fn new(ctx: DefaultSharedGlobalContext, topic: String) -> Self {
+        Self {
+            ctx,
+            replica: ReplicaKey::new(topic, 0u32),
+        }
+    }
+
+    /// write record which failed in SmartModule while reading from `source`
+    pub(crate) async fn send(
+        &self,
+        source: &ReplicaKey,
+        error: &SmartModuleTransformRuntimeError,
+    ) -> Result<(), ErrorCode> {
+        let record = DeadLetter {
+            topic: source.topic.clone(),
+            partition: source.partition,
+            error: error.clone(),
+        }
+        .into_record()
+        .map_err(|err| ErrorCode::Other(err.to_string()))?;
+
+        self.ctx.write_records(&self.replica, vec![record]).await?;
+
+        debug!(
+            dead_letter_topic = %self.replica.topic,
+            offset = error.offset,
+            "record routed to dead-letter topic"
+        );
+        Ok(())
+    }
+}
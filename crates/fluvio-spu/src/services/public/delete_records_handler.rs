@@ -0,0 +1,54 @@
+use std::io::Error as IoError;
+
+use tracing::{debug, info, instrument};
+
+use fluvio_protocol::api::{RequestMessage, ResponseMessage};
+use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::record::Offset;
+use fluvio_spu_schema::server::delete_records::{DeleteRecordsRequest, DeleteRecordsResponse};
+
+use crate::core::DefaultSharedGlobalContext;
+
+#[instrument(skip(req_msg, ctx))]
+pub(crate) async fn handle_delete_records_request(
+    req_msg: RequestMessage<DeleteRecordsRequest>,
+    ctx: DefaultSharedGlobalContext,
+) -> Result<ResponseMessage<DeleteRecordsResponse>, IoError> {
+    let response = match delete_records(&ctx, &req_msg.request).await {
+        Ok(log_start_offset) => DeleteRecordsResponse {
+            error_code: ErrorCode::None,
+            log_start_offset,
+        },
+        Err(error_code) => DeleteRecordsResponse {
+            error_code,
+            ..Default::default()
+        },
+    };
+
+    debug!(?response, "delete records result");
+    Ok(req_msg.new_response(response))
+}
+
+/// delete records of leader, followers delete same records once they are synced
+async fn delete_records(
+    ctx: &DefaultSharedGlobalContext,
+    request: &DeleteRecordsRequest,
+) -> Result<Offset, ErrorCode> {
+    if request.offset < 0 {
+        return Err(ErrorCode::Other(format!(
+            "invalid offset: {}",
+            request.offset
+        )));
+    }
+    let Some(leader) = ctx.leaders_state().get(&request.replica).await else {
+        return Err(ErrorCode::NotLeaderForPartition);
+    };
+
+    let log_start_offset = leader
+        .delete_records_before(request.offset, ctx.follower_notifier())
+        .await
+        .map_err(|err| ErrorCode::Other(err.to_string()))?;
+
+    info!(replica = %request.replica, offset = request.offset, log_start_offset, "records deleted");
+    Ok(log_start_offset)
+}
@@ -1,39 +1,116 @@
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
 use tracing::{debug, trace, instrument};
 use anyhow::Result;
+use futures_util::FutureExt;
+use futures_util::future::select_all;
+use tokio::select;
 
 use fluvio_spu_schema::file::FileRecordSet;
+use fluvio_spu_schema::Isolation;
 use fluvio_socket::ExclusiveFlvSink;
 use fluvio_socket::SocketError;
-use fluvio_protocol::{link::ErrorCode, api::RequestMessage};
+use fluvio_protocol::{link::ErrorCode, api::RequestMessage, api::ResponseMessage};
+use fluvio_protocol::record::{RecordSet, RawRecords};
 use fluvio_spu_schema::fetch::{
     FileFetchResponse, FileFetchRequest, FilePartitionResponse, FileTopicResponse,
     FetchablePartitionResponse, FetchPartition, FetchableTopic, FetchableTopicResponse,
+    FetchResponse, FETCH_SESSION_MIN_VERSION,
 };
+use fluvio_protocol::Version;
 use fluvio_controlplane_metadata::partition::ReplicaKey;
+use fluvio_types::event::StickyEvent;
+use fluvio_types::event::offsets::OffsetChangeListener;
+
+use fluvio_future::timer::sleep;
 
 use crate::core::DefaultSharedGlobalContext;
+use crate::core::quota::QuotaType;
+use crate::storage::read_slice;
 use crate::traffic::TrafficType;
 
-/// perform log fetch request using zero copy write
+use super::check_leader_epoch;
+
+/// perform log fetch request using zero copy write.
+/// Response is held until it has `min_bytes` of records, `max_wait` expires or connection ends
 #[instrument(
-    skip(request, ctx, sink),
+    skip(request, ctx, sink, end_event),
     fields(
         max_bytes = request.request.max_bytes,
+        min_bytes = request.request.min_bytes,
+        max_wait = request.request.max_wait,
     ),
 )]
 pub async fn handle_fetch_request(
     request: RequestMessage<FileFetchRequest>,
     ctx: DefaultSharedGlobalContext,
     sink: ExclusiveFlvSink,
+    end_event: Arc<StickyEvent>,
 ) -> Result<()> {
     let (header, fetch_request) = request.get_header_request();
     trace!("Handling FileFetchRequest: {:#?}", fetch_request);
     let mut fetch_response = FileFetchResponse::default();
 
-    for topic_request in &fetch_request.topics {
-        let topic_response =
-            handle_fetch_topic(&ctx, &fetch_request, topic_request, header.is_connector()).await?;
-        fetch_response.topics.push(topic_response);
+    // permit is held until file slices are sent
+    let _permit = match ctx.admission().admit().await {
+        Ok(permit) => {
+            match open_session(&ctx, header.api_version(), &fetch_request).await {
+                Ok(session) => {
+                    let topics = session.topics.as_deref().unwrap_or(&fetch_request.topics);
+                    fetch_response.topics = fetch_topics(
+                        &ctx,
+                        &fetch_request,
+                        topics,
+                        header.is_connector(),
+                        &end_event,
+                    )
+                    .await?;
+                    if session.session_id != 0 && fetch_response.error_code.is_ok() {
+                        fetch_response.session_id = session.session_id;
+                        ctx.fetch_sessions()
+                            .record_response(
+                                session.session_id,
+                                &mut fetch_response,
+                                session.topics.is_some(),
+                            )
+                            .await;
+                    }
+                }
+                Err(err) => {
+                    debug!(%err, session_id = fetch_request.session_id, "fetch session rejected");
+                    fetch_response.error_code = err;
+                }
+            }
+            Some(permit)
+        }
+        Err(err) => {
+            debug!(%err, "fetch request rejected");
+            fetch_response.error_code = err;
+            None
+        }
+    };
+
+    let bytes = response_bytes(&fetch_response.topics);
+    let throttle = ctx
+        .quotas()
+        .record_request(header.client_id(), QuotaType::Fetch, bytes)
+        .await;
+    if !throttle.is_zero() {
+        fetch_response.throttle_time_ms = throttle.as_millis() as i32;
+        sleep(throttle).await;
+    }
+
+    if let Some(decrypted) = decrypt_response(&ctx, &fetch_response).await? {
+        let response = ResponseMessage::new(header.correlation_id(), decrypted);
+        trace!("Sending decrypted FetchResponse: {:#?}", response);
+        sink.lock()
+            .await
+            .send_response(&response, header.api_version())
+            .await?;
+        return Ok(());
     }
 
     let response =
@@ -51,6 +128,221 @@ pub async fn handle_fetch_request(
     Ok(())
 }
 
+/// fetch topics again whenever visible offset of any partition changes, until there are
+/// enough bytes, wait expires, partition fails, connection ends or SPU shuts down
+async fn fetch_topics(
+    ctx: &DefaultSharedGlobalContext,
+    fetch_request: &FileFetchRequest,
+    topics: &[FetchableTopic],
+    is_connector: bool,
+    end_event: &StickyEvent,
+) -> Result<Vec<FetchableTopicResponse<FileRecordSet>>> {
+    let min_bytes = fetch_request.min_bytes.max(0) as u64;
+    let max_wait = Duration::from_millis(
+        (fetch_request.max_wait.max(0) as u64).min(ctx.dynamic_config().fetch_max_wait_ms()),
+    );
+    // listeners are created before first read, so records written meanwhile are not missed
+    let mut listeners = if min_bytes > 0 && !max_wait.is_zero() {
+        offset_listeners(ctx, topics, fetch_request.isolation_level).await
+    } else {
+        vec![]
+    };
+    let deadline = Instant::now() + max_wait;
+
+    loop {
+        let mut responses = Vec::with_capacity(topics.len());
+        for topic_request in topics {
+            responses
+                .push(handle_fetch_topic(ctx, fetch_request, topic_request, is_connector).await?);
+        }
+
+        let bytes = response_bytes(&responses);
+        let failed = responses
+            .iter()
+            .flat_map(|topic| topic.partitions.iter())
+            .any(|partition| partition.error_code.is_error());
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if bytes >= min_bytes
+            || failed
+            || listeners.is_empty()
+            || remaining.is_zero()
+            || ctx.shutdown().is_set()
+            || end_event.is_set()
+        {
+            return Ok(responses);
+        }
+
+        trace!(bytes, min_bytes, ?remaining, "waiting for records");
+        let changes = select_all(
+            listeners
+                .iter_mut()
+                .map(|listener| listener.listen().boxed()),
+        );
+        select! {
+            _ = changes => {
+                trace!("offset changed, fetching again");
+            },
+            _ = sleep(remaining) => {
+                debug!(bytes, min_bytes, "fetch wait expired");
+                return Ok(responses);
+            }
+            _ = ctx.shutdown().listen() => {
+                debug!(bytes, min_bytes, "spu is shutting down, fetch wait cut short");
+                return Ok(responses);
+            }
+            _ = end_event.listen() => {
+                debug!(bytes, min_bytes, "connection ended, fetch wait cut short");
+                return Ok(responses);
+            }
+        }
+    }
+}
+
+/// listeners of visible offsets of partitions led by this SPU, primed with current offsets
+async fn offset_listeners(
+    ctx: &DefaultSharedGlobalContext,
+    topics: &[FetchableTopic],
+    isolation: Isolation,
+) -> Vec<OffsetChangeListener> {
+    let mut listeners = vec![];
+    for topic_request in topics {
+        for partition_request in &topic_request.fetch_partitions {
+            let replica_id = ReplicaKey::new(
+                topic_request.name.clone(),
+                partition_request.partition_index,
+            );
+            if let Some(leader_state) = ctx.leaders_state().get(&replica_id).await {
+                let mut listener = leader_state.visible_offset_listener(&isolation);
+                // consume current offset, so only later changes wake up the fetch
+                let _ = listener.listen().now_or_never();
+                listeners.push(listener);
+            }
+        }
+    }
+    listeners
+}
+
+/// records of encrypted partitions can't be sent as file slices. If response has any,
+/// records of all partitions are read into memory and encrypted ones are decrypted
+async fn decrypt_response(
+    ctx: &DefaultSharedGlobalContext,
+    response: &FileFetchResponse,
+) -> Result<Option<FetchResponse<RecordSet<RawRecords>>>> {
+    let mut ciphers = HashMap::new();
+    for topic in &response.topics {
+        for partition in &topic.partitions {
+            let replica_id = ReplicaKey::new(topic.name.clone(), partition.partition_index);
+            if let Some(cipher) = ctx
+                .leaders_state()
+                .get(&replica_id)
+                .await
+                .and_then(|leader_state| leader_state.cipher())
+            {
+                ciphers.insert(replica_id, cipher);
+            }
+        }
+    }
+    if ciphers.is_empty() {
+        return Ok(None);
+    }
+
+    let mut topics = Vec::with_capacity(response.topics.len());
+    for topic in &response.topics {
+        let mut partitions = Vec::with_capacity(topic.partitions.len());
+        for partition in &topic.partitions {
+            let replica_id = ReplicaKey::new(topic.name.clone(), partition.partition_index);
+            let records = if partition.records.len() == 0 {
+                RecordSet::default()
+            } else {
+                read_slice(
+                    partition.records.raw_slice(),
+                    ciphers.get(&replica_id).cloned(),
+                )?
+            };
+            partitions.push(FetchablePartitionResponse {
+                partition_index: partition.partition_index,
+                error_code: partition.error_code.clone(),
+                high_watermark: partition.high_watermark,
+                next_filter_offset: partition.next_filter_offset,
+                log_start_offset: partition.log_start_offset,
+                aborted: partition.aborted.clone(),
+                broker_timestamps: partition.broker_timestamps,
+                records,
+            });
+        }
+        topics.push(FetchableTopicResponse {
+            name: topic.name.clone(),
+            partitions,
+            data: PhantomData,
+        });
+    }
+
+    Ok(Some(FetchResponse {
+        throttle_time_ms: response.throttle_time_ms,
+        error_code: response.error_code.clone(),
+        session_id: response.session_id,
+        topics,
+    }))
+}
+
+fn response_bytes(topics: &[FetchableTopicResponse<FileRecordSet>]) -> u64 {
+    topics
+        .iter()
+        .flat_map(|topic| topic.partitions.iter())
+        .map(|partition| partition.records.len() as u64)
+        .sum()
+}
+
+/// fetch session used by request
+#[derive(Debug, Default)]
+struct SessionScope {
+    /// 0 when request is fetched without session
+    session_id: i32,
+    /// all partitions of session for incremental request, which only carries changed ones
+    topics: Option<Vec<FetchableTopic>>,
+}
+
+async fn open_session(
+    ctx: &DefaultSharedGlobalContext,
+    version: Version,
+    fetch_request: &FileFetchRequest,
+) -> Result<SessionScope, ErrorCode> {
+    if version < FETCH_SESSION_MIN_VERSION {
+        return Ok(SessionScope::default());
+    }
+    let sessions = ctx.fetch_sessions();
+    if fetch_request.is_incremental() {
+        let topics = sessions
+            .update(
+                fetch_request.session_id,
+                fetch_request.session_epoch,
+                &fetch_request.topics,
+                &fetch_request.forgotten,
+            )
+            .await?;
+        return Ok(SessionScope {
+            session_id: fetch_request.session_id,
+            topics: Some(topics),
+        });
+    }
+
+    if fetch_request.session_id != 0 {
+        sessions.remove(fetch_request.session_id).await;
+    }
+    if fetch_request.is_new_session() {
+        // without room for new session, client keeps sending full requests
+        let session_id = sessions
+            .create(&fetch_request.topics)
+            .await
+            .unwrap_or_default();
+        return Ok(SessionScope {
+            session_id,
+            topics: None,
+        });
+    }
+    Ok(SessionScope::default())
+}
+
 #[instrument(
     skip(ctx, fetch_request, topic_request),
     fields(topic = %topic_request.name),
@@ -112,10 +404,17 @@ async fn handle_fetch_partition(
         }
     };
 
+    if let Err(error_code) =
+        check_leader_epoch(ctx, &replica_id, partition_request.current_leader_epoch)
+    {
+        partition_response.error_code = error_code;
+        return Ok(partition_response);
+    }
+
     let metrics = ctx.metrics();
 
     match leader_state
-        .read_records(
+        .read_visible_records(
             fetch_offset,
             fetch_request.max_bytes as u32,
             fetch_request.isolation_level,
@@ -134,6 +433,12 @@ async fn handle_fetch_partition(
                 );
                 partition_response.records = file_slice.into();
             }
+            if fetch_request.isolation_level == Isolation::ReadCommitted {
+                let aborted = leader_state.aborted_txns(fetch_offset, slice.end.hw).await;
+                if !aborted.is_empty() {
+                    partition_response.aborted = Some(aborted);
+                }
+            }
         }
         Err(err) => {
             debug!(%err,"Failed to read records for partition");
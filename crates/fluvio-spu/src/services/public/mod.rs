@@ -5,6 +5,12 @@ mod offset_request;
 mod offset_update;
 mod stream_fetch;
 mod consumer_handler;
+mod consumer_group_handler;
+mod txn_handler;
+mod snapshot_handler;
+mod delete_records_handler;
+mod dead_letter;
+mod authorization;
 
 #[cfg(test)]
 mod tests;
@@ -17,11 +23,13 @@ use fluvio_protocol::api::RequestMessage;
 use fluvio_protocol::link::ErrorCode;
 use fluvio_protocol::record::ReplicaKey;
 use fluvio_spu_schema::server::mirror::StartMirrorRequest;
-use tracing::{info, debug, trace, instrument};
+use tracing::{info, debug, error, trace, instrument};
 use futures_util::StreamExt;
 use anyhow::Result;
 
+use fluvio_future::task::spawn;
 use fluvio_socket::FluvioSocket;
+use fluvio_auth::token::{authenticate, SharedTokenValidator};
 use fluvio_service::{FluvioApiServer, FluvioService, ConnectInfo, call_service};
 use fluvio_spu_schema::server::SpuServerRequest;
 use fluvio_spu_schema::server::SpuServerApiKey;
@@ -32,6 +40,15 @@ use crate::mirroring::home::connection::MirrorHomeHandler;
 use crate::services::public::consumer_handler::handle_delete_consumer_offset_request;
 use crate::services::public::consumer_handler::handle_fetch_consumer_offsets_request;
 use crate::services::public::consumer_handler::handle_update_consumer_offset_request;
+use self::consumer_group_handler::{
+    handle_join_group_request, handle_heartbeat_request, handle_leave_group_request,
+};
+use self::txn_handler::{
+    handle_init_transaction_request, handle_add_txn_partitions_request,
+    handle_end_transaction_request, handle_write_txn_marker_request,
+};
+use self::snapshot_handler::{handle_snapshot_partition_request, handle_restore_partition_request};
+use self::delete_records_handler::handle_delete_records_request;
 use self::api_versions::handle_api_version_request;
 use self::produce_handler::handle_produce_request;
 use self::fetch_handler::handle_fetch_request;
@@ -39,28 +56,35 @@ use self::offset_request::handle_offset_request;
 use self::offset_update::handle_offset_update;
 use self::stream_fetch::{StreamFetchHandler, publishers::StreamPublishers};
 use self::conn_context::ConnectionContext;
+use self::authorization::{is_authorized, send_denied_response};
 
 pub(crate) type SpuPublicServer =
     FluvioApiServer<SpuServerRequest, SpuServerApiKey, DefaultSharedGlobalContext, PublicService>;
 
-pub fn create_public_server(addr: String, ctx: DefaultSharedGlobalContext) -> SpuPublicServer {
+/// clients must authenticate with bearer token if `token_validator` is set
+pub fn create_public_server(
+    addr: String,
+    ctx: DefaultSharedGlobalContext,
+    token_validator: Option<SharedTokenValidator>,
+) -> SpuPublicServer {
     info!(
         spu_id = ctx.local_spu_id(),
         %addr,
         "Starting SPU public service:",
     );
 
-    FluvioApiServer::new(addr, ctx, PublicService::new())
+    FluvioApiServer::new(addr, ctx, PublicService::new(token_validator))
 }
 
 #[derive(Debug)]
 pub struct PublicService {
-    _0: (), // Prevent construction
+    /// clients must authenticate with bearer token if set
+    token_validator: Option<SharedTokenValidator>,
 }
 
 impl PublicService {
-    pub fn new() -> Self {
-        PublicService { _0: () }
+    pub fn new(token_validator: Option<SharedTokenValidator>) -> Self {
+        PublicService { token_validator }
     }
 }
 
@@ -73,9 +97,18 @@ impl FluvioService for PublicService {
     async fn respond(
         self: Arc<Self>,
         context: DefaultSharedGlobalContext,
-        socket: FluvioSocket,
+        mut socket: FluvioSocket,
         _connection: ConnectInfo,
     ) -> Result<()> {
+        let identity = match &self.token_validator {
+            Some(validator) => {
+                let identity = authenticate(&mut socket, validator.as_ref()).await?;
+                debug!(principal = %identity.principal, "client authenticated");
+                Some(identity)
+            }
+            None => None,
+        };
+
         let (sink, mut stream) = socket.split();
 
         let mut mirror_request: Option<RequestMessage<StartMirrorRequest>> = None;
@@ -85,7 +118,8 @@ impl FluvioService for PublicService {
         {
             let api_stream = stream.api_stream::<SpuServerRequest, SpuServerApiKey>();
             let mut event_stream = api_stream.take_until(shutdown.listen_pinned());
-            let mut conn_ctx = ConnectionContext::new();
+            let mut conn_ctx = ConnectionContext::new(identity);
+            let mut rate_limit = context.rate_limiter().connection(conn_ctx.principal());
 
             loop {
                 let event = event_stream.next().await;
@@ -98,6 +132,15 @@ impl FluvioService for PublicService {
                             shared_sink.id(),
                             req_message
                         );
+                        // data requests carry quota throttle instead of error,
+                        // so requests over rate limit are delayed
+                        if !matches!(req_message, SpuServerRequest::ApiVersionsRequest(_)) {
+                            rate_limit.acquire().await;
+                        }
+                        if !is_authorized(&context, &conn_ctx, &req_message).await {
+                            send_denied_response(req_message, &mut shared_sink).await?;
+                            continue;
+                        }
                         match req_message {
                             SpuServerRequest::ApiVersionsRequest(request) => call_service!(
                                 request,
@@ -112,8 +155,20 @@ impl FluvioService for PublicService {
                                 "ProduceRequest"
                             ),
                             SpuServerRequest::FileFetchRequest(request) => {
-                                handle_fetch_request(request, context.clone(), shared_sink.clone())
-                                    .await?
+                                // fetch may wait for records up to max wait, so it is answered
+                                // in background and following requests of connection are served
+                                let ctx = context.clone();
+                                let sink = shared_sink.clone();
+                                let end_event = shutdown.clone();
+                                spawn(async move {
+                                    if let Err(err) =
+                                        handle_fetch_request(request, ctx, sink, end_event.clone())
+                                            .await
+                                    {
+                                        error!(%err, "error handling fetch request, ending connection");
+                                        end_event.notify();
+                                    }
+                                });
                             }
                             SpuServerRequest::FetchOffsetsRequest(request) => call_service!(
                                 request,
@@ -165,6 +220,66 @@ impl FluvioService for PublicService {
                                     "FetchConsumersRequest"
                                 )
                             }
+                            SpuServerRequest::JoinGroupRequest(request) => call_service!(
+                                request,
+                                handle_join_group_request(request, context.clone()),
+                                shared_sink,
+                                "JoinGroupRequest"
+                            ),
+                            SpuServerRequest::HeartbeatRequest(request) => call_service!(
+                                request,
+                                handle_heartbeat_request(request, context.clone()),
+                                shared_sink,
+                                "HeartbeatRequest"
+                            ),
+                            SpuServerRequest::LeaveGroupRequest(request) => call_service!(
+                                request,
+                                handle_leave_group_request(request, context.clone()),
+                                shared_sink,
+                                "LeaveGroupRequest"
+                            ),
+                            SpuServerRequest::InitTransactionRequest(request) => call_service!(
+                                request,
+                                handle_init_transaction_request(request, context.clone()),
+                                shared_sink,
+                                "InitTransactionRequest"
+                            ),
+                            SpuServerRequest::AddTxnPartitionsRequest(request) => call_service!(
+                                request,
+                                handle_add_txn_partitions_request(request, context.clone()),
+                                shared_sink,
+                                "AddTxnPartitionsRequest"
+                            ),
+                            SpuServerRequest::EndTransactionRequest(request) => call_service!(
+                                request,
+                                handle_end_transaction_request(request, context.clone()),
+                                shared_sink,
+                                "EndTransactionRequest"
+                            ),
+                            SpuServerRequest::WriteTxnMarkerRequest(request) => call_service!(
+                                request,
+                                handle_write_txn_marker_request(request, context.clone()),
+                                shared_sink,
+                                "WriteTxnMarkerRequest"
+                            ),
+                            SpuServerRequest::SnapshotPartitionRequest(request) => call_service!(
+                                request,
+                                handle_snapshot_partition_request(request, context.clone()),
+                                shared_sink,
+                                "SnapshotPartitionRequest"
+                            ),
+                            SpuServerRequest::RestorePartitionRequest(request) => call_service!(
+                                request,
+                                handle_restore_partition_request(request, context.clone()),
+                                shared_sink,
+                                "RestorePartitionRequest"
+                            ),
+                            SpuServerRequest::DeleteRecordsRequest(request) => call_service!(
+                                request,
+                                handle_delete_records_request(request, context.clone()),
+                                shared_sink,
+                                "DeleteRecordsRequest"
+                            ),
                             SpuServerRequest::StartMirrorRequest(request) => {
                                 // send mirror mode, afer that mirror cycle will be started
                                 mirror_request = Some(request);
@@ -197,6 +312,34 @@ impl FluvioService for PublicService {
     }
 }
 
+/// Fence request made for other leader epoch than the one SC assigned to replica.
+/// Negative epoch means client doesn't track epochs.
+pub(crate) fn check_leader_epoch(
+    ctx: &DefaultSharedGlobalContext,
+    replica_id: &ReplicaKey,
+    leader_epoch: i32,
+) -> Result<(), ErrorCode> {
+    if leader_epoch < 0 {
+        return Ok(());
+    }
+    let Some(replica) = ctx.replica_localstore().spec(replica_id) else {
+        return Ok(());
+    };
+    if replica.leader_epoch != leader_epoch {
+        debug!(
+            %replica_id,
+            received = leader_epoch,
+            current = replica.leader_epoch,
+            "fencing request with stale leader epoch"
+        );
+        return Err(ErrorCode::StaleLeaderEpoch {
+            received: leader_epoch,
+            current: replica.leader_epoch,
+        });
+    }
+    Ok(())
+}
+
 async fn send_private_request_to_leader<R: Request>(
     ctx: &DefaultSharedGlobalContext,
     replica_id: &ReplicaKey,
@@ -29,7 +29,6 @@ pub async fn handle_offset_request(
 
     for topic_request in &request.topics {
         let topic = &topic_request.name;
-
         let mut topic_response = FetchOffsetTopicResponse {
             name: topic.clone(),
             ..Default::default()
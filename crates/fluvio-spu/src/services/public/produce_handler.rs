@@ -10,6 +10,7 @@ use fluvio_spu_schema::Isolation;
 use fluvio_protocol::record::{BatchRecords, Offset, Batch, RawRecords};
 use fluvio::Compression;
 use fluvio_controlplane_metadata::topic::CompressionAlgorithm;
+use fluvio_controlplane_metadata::topic::{SchemaValidationAction, TopicSchema};
 use fluvio_storage::StorageError;
 use fluvio_spu_schema::produce::{
     ProduceResponse, TopicProduceResponse, PartitionProduceResponse, PartitionProduceData,
@@ -18,12 +19,16 @@ use fluvio_spu_schema::produce::{
 use fluvio_spu_schema::server::smartmodule::SmartModuleInvocation;
 use fluvio_protocol::{api::RequestMessage, link::ErrorCode};
 use fluvio_protocol::api::ResponseMessage;
+use fluvio_protocol::Encoder;
 use fluvio_protocol::record::RecordSet;
 use fluvio_controlplane_metadata::partition::ReplicaKey;
 
 use fluvio_future::timer::sleep;
 
 use crate::core::DefaultSharedGlobalContext;
+use crate::core::quota::QuotaType;
+use crate::core::memory::MemoryKind;
+use crate::core::schema::validate_batches;
 use crate::replication::leader::SharedFileLeaderState;
 use crate::smartengine::batch::process_batch;
 use crate::smartengine::context::SmartModuleContext;
@@ -32,6 +37,7 @@ use crate::smartengine::map_engine_error;
 use crate::smartengine::produce_batch::ProduceBatchIterator;
 
 use crate::traffic::TrafficType;
+use super::check_leader_epoch;
 
 struct TopicWriteResult {
     topic: String,
@@ -47,7 +53,7 @@ struct PartitionWriteResult {
 }
 
 #[instrument(
-    skip(request,ctx),
+    skip(request, ctx),
     fields(
         id = request.header.correlation_id(),
         client = %request.header.client_id()
@@ -60,12 +66,39 @@ pub async fn handle_produce_request(
     let (header, produce_request) = request.get_header_request();
     trace!("Handling ProduceRequest: {:#?}", produce_request);
 
+    let write_size = produce_request.write_size(header.api_version()) as u64;
+    let throttle = ctx
+        .quotas()
+        .record_request(header.client_id(), QuotaType::Produce, write_size)
+        .await;
+    // shed load before buffering records, client retries busy partitions
+    let permit = match ctx.admission().admit().await {
+        Ok(permit) => permit,
+        Err(error_code) => {
+            debug!(%error_code, "produce request rejected");
+            return Ok(
+                RequestMessage::<DefaultProduceRequest>::response_with_header(
+                    &header,
+                    error_response(&produce_request, &error_code),
+                ),
+            );
+        }
+    };
+    // records are held until acknowledged, connection waits while SPU is out of memory
+    let memory = ctx.memory().acquire(MemoryKind::Produce, write_size).await;
+
     let smartmodules = produce_request.smartmodules;
 
     let mut topic_results = Vec::with_capacity(produce_request.topics.len());
     for topic_request in produce_request.topics.into_iter() {
-        let topic_result =
-            handle_produce_topic(&ctx, topic_request, &smartmodules, &header).await?;
+        let topic_result = handle_produce_topic(
+            &ctx,
+            topic_request,
+            &smartmodules,
+            &header,
+            produce_request.isolation,
+        )
+        .await?;
         topic_results.push(topic_result);
     }
     wait_for_acks(
@@ -75,7 +108,14 @@ pub async fn handle_produce_request(
         &ctx,
     )
     .await;
-    let response = into_response(topic_results);
+    drop(memory);
+    drop(permit);
+    let mut response = into_response(topic_results);
+    if !throttle.is_zero() {
+        // records are written, only response is delayed so client slows down
+        response.throttle_time_ms = throttle.as_millis() as i32;
+        sleep(throttle).await;
+    }
     trace!("Returning ProduceResponse: {:#?}", &response);
     Ok(RequestMessage::<DefaultProduceRequest>::response_with_header(&header, response))
 }
@@ -89,6 +129,7 @@ async fn handle_produce_topic(
     topic_request: DefaultTopicRequest,
     smartmodules: &[SmartModuleInvocation],
     header: &RequestHeader,
+    isolation: Isolation,
 ) -> Result<TopicWriteResult> {
     let topic = &topic_request.name;
 
@@ -143,6 +184,7 @@ async fn handle_produce_topic(
                 leader_state,
                 partition_request,
                 header.is_connector(),
+                isolation,
             )
             .await
         };
@@ -162,6 +204,7 @@ async fn handle_produce_partition(
     leader_state: SharedFileLeaderState,
     partition_request: PartitionProduceData<RecordSet<RawRecords>>,
     is_connector: bool,
+    isolation: Isolation,
 ) -> PartitionWriteResult {
     trace!("Handling produce request for partition:");
 
@@ -173,6 +216,32 @@ async fn handle_produce_partition(
         }
     };
 
+    if replica_metadata.read_only {
+        debug!(%replica_id, "rejecting produce to read-only partition");
+        return PartitionWriteResult::error(replica_id, ErrorCode::PartitionReadOnly);
+    }
+
+    if let Err(error_code) = check_leader_epoch(ctx, &replica_id, partition_request.leader_epoch) {
+        return PartitionWriteResult::error(replica_id, error_code);
+    }
+
+    // produce waiting for all replicas is rejected before write if it can't be replicated durably
+    if isolation == Isolation::ReadCommitted {
+        let required = replica_metadata
+            .storage
+            .as_ref()
+            .and_then(|storage| storage.min_in_sync_replicas)
+            .unwrap_or(ctx.config().replication.min_in_sync_replicas);
+        let in_sync = leader_state.in_sync_replicas().await;
+        if in_sync < required {
+            debug!(%replica_id, in_sync, required, "not enough in-sync replicas");
+            return PartitionWriteResult::error(
+                replica_id,
+                ErrorCode::NotEnoughReplicas { in_sync, required },
+            );
+        }
+    }
+
     let mut records = partition_request.records;
 
     if validate_records(&records, replica_metadata.compression_type).is_err() {
@@ -180,6 +249,12 @@ async fn handle_produce_partition(
         return PartitionWriteResult::error(replica_id, ErrorCode::CompressionError);
     }
 
+    if let Some(schema) = &replica_metadata.schema {
+        if let Err(error_code) = check_schema(ctx, &replica_id, schema, &records) {
+            return PartitionWriteResult::error(replica_id, error_code);
+        }
+    }
+
     let write_result = leader_state
         .write_record_set(&mut records, ctx.follower_notifier())
         .await;
@@ -198,6 +273,10 @@ async fn handle_produce_partition(
                 error!(%replica_id, "Replica SmartEngine error: {:#?}", engine_err);
                 return PartitionWriteResult::error(replica_id, map_engine_error(engine_err));
             };
+            if let Some(error_code) = err.downcast_ref::<ErrorCode>() {
+                debug!(%replica_id, %error_code, "write rejected");
+                return PartitionWriteResult::error(replica_id, error_code.clone());
+            };
             match err.downcast_ref::<StorageError>() {
                 Some(StorageError::BatchTooBig(_)) => {
                     error!(%replica_id, "Batch is too big: {:#?}", err);
@@ -219,6 +298,33 @@ async fn handle_produce_partition(
     }
 }
 
+fn check_schema(
+    ctx: &DefaultSharedGlobalContext,
+    replica_id: &ReplicaKey,
+    schema: &TopicSchema,
+    records: &RecordSet<RawRecords>,
+) -> Result<(), ErrorCode> {
+    let report = validate_batches(ctx.schema_validator(), schema, &records.batches)?;
+    let metrics = ctx.metrics();
+    metrics
+        .schema()
+        .add_validated(report.records, report.invalid);
+    let Some(reason) = report.first_error else {
+        return Ok(());
+    };
+    match schema.on_failure {
+        SchemaValidationAction::Reject => {
+            debug!(%replica_id, invalid = report.invalid, %reason, "produce rejected by schema");
+            metrics.schema().add_rejected();
+            Err(ErrorCode::SchemaValidation(reason))
+        }
+        SchemaValidationAction::Tag => {
+            debug!(%replica_id, invalid = report.invalid, %reason, "invalid records tagged");
+            Ok(())
+        }
+    }
+}
+
 async fn apply_smartmodules(
     partition_request: &mut PartitionProduceData<RecordSet<RawRecords>>,
     smartmodules: &[SmartModuleInvocation],
@@ -367,6 +473,25 @@ impl From<TopicWriteResult> for TopicProduceResponse {
     }
 }
 
+impl TopicWriteResult {
+    /// all partitions of topic failed with `error_code`
+    fn error(topic: &DefaultTopicRequest, error_code: &ErrorCode) -> Self {
+        Self {
+            partitions: topic
+                .partitions
+                .iter()
+                .map(|partition| {
+                    PartitionWriteResult::error(
+                        ReplicaKey::new(topic.name.clone(), partition.partition_index),
+                        error_code.clone(),
+                    )
+                })
+                .collect(),
+            topic: topic.name.clone(),
+        }
+    }
+}
+
 impl PartitionWriteResult {
     fn error(replica_id: ReplicaKey, error_code: ErrorCode) -> Self {
         Self {
@@ -404,6 +529,19 @@ impl From<PartitionWriteResult> for PartitionProduceResponse {
     }
 }
 
+/// every partition of request failed with `error_code`
+pub(crate) fn error_response(
+    request: &DefaultProduceRequest,
+    error_code: &ErrorCode,
+) -> ProduceResponse {
+    let topic_results = request
+        .topics
+        .iter()
+        .map(|topic| TopicWriteResult::error(topic, error_code))
+        .collect();
+    into_response(topic_results)
+}
+
 fn into_response(topic_results: Vec<TopicWriteResult>) -> ProduceResponse {
     let responses = topic_results
         .into_iter()
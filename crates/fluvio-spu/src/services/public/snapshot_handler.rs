@@ -0,0 +1,196 @@
+use std::io::Error as IoError;
+use std::path::PathBuf;
+
+use tracing::{debug, info, instrument};
+
+use fluvio_protocol::api::{RequestMessage, ResponseMessage};
+use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::record::Offset;
+use fluvio_spu_schema::Isolation;
+use fluvio_spu_schema::server::snapshot::{
+    SnapshotPartitionRequest, SnapshotPartitionResponse, RestorePartitionRequest,
+    RestorePartitionResponse,
+};
+use fluvio_storage::snapshot::{SnapshotHeader, SnapshotInfo, SnapshotReader, SnapshotWriter};
+
+use crate::core::DefaultSharedGlobalContext;
+use crate::replication::leader::SharedFileLeaderState;
+
+/// bytes of log read or restored at once
+const SNAPSHOT_CHUNK_BYTES: u32 = 16 * 1024 * 1024;
+
+#[instrument(skip(req_msg, ctx))]
+pub(crate) async fn handle_snapshot_partition_request(
+    req_msg: RequestMessage<SnapshotPartitionRequest>,
+    ctx: DefaultSharedGlobalContext,
+) -> Result<ResponseMessage<SnapshotPartitionResponse>, IoError> {
+    let response = match snapshot_partition(&ctx, &req_msg.request).await {
+        Ok(info) => SnapshotPartitionResponse {
+            error_code: ErrorCode::None,
+            start_offset: info.start_offset,
+            end_offset: info.end_offset,
+            batches: info.batches,
+            bytes: info.bytes,
+        },
+        Err(error_code) => SnapshotPartitionResponse {
+            error_code,
+            ..Default::default()
+        },
+    };
+
+    debug!(?response, "snapshot partition result");
+    Ok(req_msg.new_response(response))
+}
+
+#[instrument(skip(req_msg, ctx))]
+pub(crate) async fn handle_restore_partition_request(
+    req_msg: RequestMessage<RestorePartitionRequest>,
+    ctx: DefaultSharedGlobalContext,
+) -> Result<ResponseMessage<RestorePartitionResponse>, IoError> {
+    let response = match restore_partition(&ctx, &req_msg.request).await {
+        Ok((leo, batches)) => RestorePartitionResponse {
+            error_code: ErrorCode::None,
+            leo,
+            batches,
+        },
+        Err(error_code) => RestorePartitionResponse {
+            error_code,
+            ..Default::default()
+        },
+    };
+
+    debug!(?response, "restore partition result");
+    Ok(req_msg.new_response(response))
+}
+
+/// write committed batches of leader before end offset to snapshot archive
+async fn snapshot_partition(
+    ctx: &DefaultSharedGlobalContext,
+    request: &SnapshotPartitionRequest,
+) -> Result<SnapshotInfo, ErrorCode> {
+    let path = snapshot_path(ctx, &request.name)?;
+    let Some(leader) = ctx.leaders_state().get(&request.replica).await else {
+        return Err(ErrorCode::NotLeaderForPartition);
+    };
+
+    let hw = leader.hw();
+    let end_offset = request
+        .end_offset
+        .map_or(hw, |end_offset| end_offset.min(hw));
+    let (mut offset, _) = leader.start_offset_info().await;
+
+    // encrypted batches are archived as stored
+    let header = SnapshotHeader {
+        topic: request.replica.topic.clone(),
+        partition: request.replica.partition,
+        key_id: key_id(&leader).map(ToOwned::to_owned),
+    };
+    let mut writer = SnapshotWriter::create(&path, &header)
+        .await
+        .map_err(snapshot_error)?;
+    while offset < end_offset {
+        let slice = leader
+            .read_records(offset, SNAPSHOT_CHUNK_BYTES, Isolation::ReadCommitted)
+            .await?;
+        let Some(file_slice) = slice.file_slice else {
+            break;
+        };
+        match writer
+            .append_slice(&file_slice, end_offset)
+            .await
+            .map_err(snapshot_error)?
+        {
+            Some(next_offset) => offset = next_offset,
+            None => break,
+        }
+    }
+    let info = writer.finish().await.map_err(snapshot_error)?;
+
+    info!(replica = %request.replica, ?path, ?info, "partition snapshot written");
+    Ok(info)
+}
+
+/// write batches of snapshot archive to empty partition led by this SPU
+async fn restore_partition(
+    ctx: &DefaultSharedGlobalContext,
+    request: &RestorePartitionRequest,
+) -> Result<(Offset, u32), ErrorCode> {
+    let path = snapshot_path(ctx, &request.name)?;
+    let Some(leader) = ctx.leaders_state().get(&request.replica).await else {
+        return Err(ErrorCode::NotLeaderForPartition);
+    };
+
+    // restored offsets start from log start of target, mixing with existing records
+    // would shift them
+    let (start_offset, _) = leader.start_offset_info().await;
+    if leader.leo() != start_offset {
+        return Err(ErrorCode::Other(format!(
+            "partition {} is not empty",
+            request.replica
+        )));
+    }
+
+    let mut reader = SnapshotReader::open(&path).await.map_err(snapshot_error)?;
+    debug!(source = ?reader.header(), "restoring snapshot");
+    check_encrypted_source(&leader, reader.header())?;
+    let mut batches = 0;
+    while let Some(mut records) = reader
+        .next_batches(SNAPSHOT_CHUNK_BYTES as usize)
+        .await
+        .map_err(snapshot_error)?
+    {
+        batches += records.batches.len() as u32;
+        leader
+            .restore_record_set(&mut records, ctx.follower_notifier())
+            .await
+            .map_err(|err| ErrorCode::Other(err.to_string()))?;
+    }
+
+    let leo = leader.leo();
+    info!(replica = %request.replica, ?path, leo, batches, "partition restored from snapshot");
+    Ok((leo, batches))
+}
+
+fn key_id(leader: &SharedFileLeaderState) -> Option<&str> {
+    leader
+        .get_replica()
+        .encryption
+        .as_ref()
+        .map(|encryption| encryption.key_id.as_str())
+}
+
+/// encrypted batches are bound to partition they were written to, and can only be read with
+/// its key. Offsets are checked as batches are restored
+fn check_encrypted_source(
+    leader: &SharedFileLeaderState,
+    source: &SnapshotHeader,
+) -> Result<(), ErrorCode> {
+    let Some(source_key_id) = &source.key_id else {
+        return Ok(());
+    };
+    let replica = leader.id();
+    if source.topic != replica.topic || source.partition != replica.partition {
+        return Err(ErrorCode::Other(format!(
+            "snapshot of encrypted partition {}-{} can't be restored to other partition",
+            source.topic, source.partition
+        )));
+    }
+    if key_id(leader) != Some(source_key_id.as_str()) {
+        return Err(ErrorCode::Other(format!(
+            "snapshot is encrypted with key {source_key_id}, partition must be encrypted with it"
+        )));
+    }
+    Ok(())
+}
+
+/// snapshots are only kept in snapshot dir of the SPU
+fn snapshot_path(ctx: &DefaultSharedGlobalContext, name: &str) -> Result<PathBuf, ErrorCode> {
+    if name.is_empty() || name.contains(['/', '\\']) || name.starts_with('.') {
+        return Err(ErrorCode::Other(format!("invalid snapshot name: {name}")));
+    }
+    Ok(ctx.config().log.snapshot_dir.join(name))
+}
+
+fn snapshot_error(err: IoError) -> ErrorCode {
+    ErrorCode::Other(format!("snapshot error: {err}"))
+}
@@ -1,6 +1,8 @@
+use std::ops::Deref;
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
+use chrono::Utc;
 use tracing::{debug, error, instrument, trace, warn};
 use tokio::select;
 
@@ -11,6 +13,7 @@ use fluvio_types::event::{
     StickyEvent,
 };
 use fluvio_future::task::spawn;
+use fluvio_future::timer::sleep;
 use fluvio_protocol::{
     api::{RequestMessage, RequestHeader},
     record::{RecordSet, Offset, RawRecords},
@@ -18,25 +21,42 @@ use fluvio_protocol::{
 use fluvio_protocol::link::{ErrorCode, smartmodule::SmartModuleTransformRuntimeError};
 use fluvio_protocol::record::Batch;
 use fluvio_socket::{ExclusiveFlvSink, SocketError};
+use fluvio_storage::FileReplica;
 use fluvio_storage::iterators::FileBatchIterator;
+use fluvio_storage::encryption::BatchCipher;
 use fluvio_spu_schema::{
     server::stream_fetch::{
         DefaultStreamFetchRequest, FileStreamFetchRequest, StreamFetchRequest, StreamFetchResponse,
     },
-    fetch::{FilePartitionResponse, FetchablePartitionResponse},
+    fetch::{
+        FilePartitionResponse, FetchablePartitionResponse, AbortedTxnFilter, BrokerTimestamps,
+    },
     Isolation,
     file::FileRecordSet,
 };
 use fluvio_types::event::offsets::OffsetChangeListener;
 
 use crate::core::{metrics::IncreaseValue, DefaultSharedGlobalContext};
-use crate::replication::leader::SharedFileLeaderState;
+use crate::replication::follower::FollowerReplicaState;
+use crate::storage::{SharableReplicaStorage, read_slice};
 use crate::services::public::conn_context::ConnectionContext;
 use crate::smartengine::context::SmartModuleContext;
 use crate::smartengine::batch::process_batch;
 use crate::core::metrics::SpuMetrics;
+use crate::core::quota::{ClientQuotas, QuotaType};
+use crate::core::memory::{MemoryBudget, MemoryKind};
 use crate::traffic::TrafficType;
 
+use super::dead_letter::DeadLetterTopic;
+use super::check_leader_epoch;
+use super::consumer_group_handler::check_group_membership;
+
+/// error of stream when follower is too stale, consumer has to read from leader instead
+const STALE_FOLLOWER: ErrorCode = ErrorCode::NotLeaderForPartition;
+
+/// longest time before idle stream notices follower became stale
+const STALENESS_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
 /// Fetch records as stream
 pub struct StreamFetchHandler {
     replica: ReplicaKey,
@@ -47,9 +67,39 @@ pub struct StreamFetchHandler {
     sink: ExclusiveFlvSink,
     end_event: Arc<StickyEvent>,
     consumer_offset_listener: OffsetChangeListener,
-    leader_state: SharedFileLeaderState,
+    replica_storage: SharableReplicaStorage<FileReplica>,
     stream_id: u32,
     metrics: Arc<SpuMetrics>,
+    quotas: Arc<ClientQuotas>,
+    memory: Arc<MemoryBudget>,
+    dead_letter: Option<DeadLetterTopic>,
+    broker_timestamps: bool,
+    follower: Option<FollowerRead>,
+}
+
+/// follower stream reads from, stream ends once follower is more stale than consumer allows
+struct FollowerRead {
+    state: FollowerReplicaState<FileReplica>,
+    max_staleness: Duration,
+}
+
+impl FollowerRead {
+    fn is_fresh(&self) -> bool {
+        self.state.is_fresh(self.max_staleness)
+    }
+
+    /// wait until follower becomes stale, never completes when reading from leader
+    async fn stale(follower: Option<&Self>) {
+        let Some(follower) = follower else {
+            return std::future::pending().await;
+        };
+        loop {
+            sleep(STALENESS_CHECK_INTERVAL).await;
+            if !follower.is_fresh() {
+                return;
+            }
+        }
+    }
 }
 
 impl StreamFetchHandler {
@@ -64,64 +114,112 @@ impl StreamFetchHandler {
         let (header, msg) = request.get_header_request();
         let replica = ReplicaKey::new(msg.topic.clone(), msg.partition);
 
-        if let Some(leader_state) = ctx.leaders_state().get(&replica).await {
-            let (stream_id, offset_publisher) = conn_ctx
-                .stream_publishers_mut()
-                .create_new_publisher(msg.topic.clone(), msg.partition, msg.consumer_id.clone())
-                .await;
-            let consumer_offset_listener = offset_publisher.offset_publisher.change_listener();
-
-            leader_state
-                .register_offset_publisher(&offset_publisher.offset_publisher)
-                .await;
-
-            spawn(async move {
-                if let Err(err) = StreamFetchHandler::fetch(
-                    ctx,
-                    sink,
-                    end_event.clone(),
-                    leader_state,
-                    stream_id,
-                    header,
-                    replica,
-                    consumer_offset_listener,
-                    msg,
-                )
+        let replica_storage = match Self::check_fencing(&ctx, &replica, &msg).await {
+            Ok(()) => Self::replica_storage(&ctx, &replica, &msg)
                 .await
-                {
-                    error!("error starting stream fetch handler: {:#?}", err);
-                    end_event.notify();
+                .ok_or(STALE_FOLLOWER),
+            Err(error_code) => Err(error_code),
+        };
+
+        match replica_storage {
+            Ok((replica_storage, follower)) => {
+                let (stream_id, offset_publisher) = conn_ctx
+                    .stream_publishers_mut()
+                    .create_new_publisher(msg.topic.clone(), msg.partition, msg.consumer_id.clone())
+                    .await;
+                let consumer_offset_listener = offset_publisher.offset_publisher.change_listener();
+
+                if let Some(leader_state) = ctx.leaders_state().get(&replica).await {
+                    leader_state
+                        .register_offset_publisher(&offset_publisher.offset_publisher)
+                        .await;
                 }
-            });
-        } else {
-            debug!(topic = %replica.topic," no leader found, returning");
-            let response = StreamFetchResponse {
-                topic: replica.topic,
-                stream_id: 0,
-                partition: FilePartitionResponse {
-                    partition_index: replica.partition,
-                    error_code: ErrorCode::NotLeaderForPartition,
-                    ..Default::default()
-                },
-            };
 
-            let response_msg =
-                RequestMessage::<FileStreamFetchRequest>::response_with_header(&header, response);
+                spawn(async move {
+                    if let Err(err) = StreamFetchHandler::fetch(
+                        ctx,
+                        sink,
+                        end_event.clone(),
+                        replica_storage,
+                        follower,
+                        stream_id,
+                        header,
+                        replica,
+                        consumer_offset_listener,
+                        msg,
+                    )
+                    .await
+                    {
+                        error!("error starting stream fetch handler: {:#?}", err);
+                        end_event.notify();
+                    }
+                });
+            }
+            Err(error_code) => {
+                debug!(topic = %replica.topic, %error_code, "no leader or fresh follower to fetch from");
+                let response = StreamFetchResponse {
+                    topic: replica.topic,
+                    stream_id: 0,
+                    partition: FilePartitionResponse {
+                        partition_index: replica.partition,
+                        error_code,
+                        ..Default::default()
+                    },
+                };
+
+                let response_msg = RequestMessage::<FileStreamFetchRequest>::response_with_header(
+                    &header, response,
+                );
 
-            trace!("sending back file fetch response msg: {:#?}", response_msg);
+                trace!("sending back file fetch response msg: {:#?}", response_msg);
 
-            let mut inner_sink = sink.lock().await;
-            inner_sink
-                .send_response(&response_msg, header.api_version())
-                .await?;
+                let mut inner_sink = sink.lock().await;
+                inner_sink
+                    .send_response(&response_msg, header.api_version())
+                    .await?;
+            }
         }
 
         Ok(())
     }
 
+    /// reject request of stale leader epoch or stale consumer group member
+    async fn check_fencing(
+        ctx: &DefaultSharedGlobalContext,
+        replica: &ReplicaKey,
+        msg: &StreamFetchRequest<FileRecordSet>,
+    ) -> Result<(), ErrorCode> {
+        check_leader_epoch(ctx, replica, msg.leader_epoch.unwrap_or(-1))?;
+        if let Some(membership) = &msg.consumer_group {
+            check_group_membership(ctx, membership, replica.partition).await?;
+        }
+        Ok(())
+    }
+
+    /// storage to read from, leader or in sync follower if consumer allows stale reads
+    async fn replica_storage(
+        ctx: &DefaultSharedGlobalContext,
+        replica: &ReplicaKey,
+        msg: &StreamFetchRequest<FileRecordSet>,
+    ) -> Option<(SharableReplicaStorage<FileReplica>, Option<FollowerRead>)> {
+        if let Some(leader_state) = ctx.leaders_state().get(replica).await {
+            return Some((leader_state.deref().clone(), None));
+        }
+        let follower = FollowerRead {
+            state: ctx.followers_state().get(replica).await?,
+            max_staleness: Duration::from_millis(msg.max_staleness_ms? as u64),
+        };
+        if !follower.is_fresh() {
+            debug!(%replica, max_staleness = ?follower.max_staleness, "follower is stale");
+            return None;
+        }
+        debug!(%replica, "reading from follower");
+        Some((follower.state.deref().clone(), Some(follower)))
+    }
+
     #[allow(clippy::too_many_arguments)]
     #[instrument(
-        skip(ctx,replica,end_event,leader_state,header,msg,consumer_offset_listener),
+        skip(ctx,replica,end_event,replica_storage,follower,header,msg,consumer_offset_listener),
         fields(
             replica = %replica,
             sink = sink.id()
@@ -131,7 +229,8 @@ impl StreamFetchHandler {
         ctx: DefaultSharedGlobalContext,
         sink: ExclusiveFlvSink,
         end_event: Arc<StickyEvent>,
-        leader_state: SharedFileLeaderState,
+        replica_storage: SharableReplicaStorage<FileReplica>,
+        follower: Option<FollowerRead>,
         stream_id: u32,
         header: RequestHeader,
         replica: ReplicaKey,
@@ -143,7 +242,7 @@ impl StreamFetchHandler {
 
         let sm_ctx = match SmartModuleContext::try_from(msg.smartmodules, version, &ctx).await {
             Ok(Some(mut ctx)) => {
-                if let Err(error_code) = ctx.look_back(&leader_state).await {
+                if let Err(error_code) = ctx.look_back(&replica_storage).await {
                     warn!("smartmodule look_back failed: {:?}", error_code);
                     send_back_error(&sink, &replica, &header, stream_id, error_code).await?;
                     return Ok(());
@@ -169,6 +268,14 @@ impl StreamFetchHandler {
         let starting_offset = msg.fetch_offset;
         let isolation = msg.isolation;
 
+        let throttle = ctx
+            .quotas()
+            .record_request(header.client_id(), QuotaType::Fetch, 0)
+            .await;
+        if !throttle.is_zero() {
+            sleep(throttle).await;
+        }
+
         debug!(
             max_bytes,
             max_fetch_bytes,
@@ -187,9 +294,17 @@ impl StreamFetchHandler {
             header: header.clone(),
             consumer_offset_listener,
             stream_id,
-            leader_state,
+            replica_storage,
             max_fetch_bytes,
             metrics: ctx.metrics(),
+            quotas: ctx.quotas(),
+            memory: ctx.memory().clone(),
+            dead_letter: msg
+                .dead_letter_topic
+                .clone()
+                .map(|topic| DeadLetterTopic::new(ctx.clone(), topic)),
+            broker_timestamps: msg.broker_timestamps,
+            follower,
         };
 
         if let Err(err) = handler.process(starting_offset, sm_ctx).await {
@@ -226,7 +341,9 @@ impl StreamFetchHandler {
             .send_back_records(starting_offset, sm_ctx.as_mut())
             .await?;
 
-        let mut leader_offset_receiver = self.leader_state.offset_listener(&self.isolation);
+        let mut leader_offset_receiver = self
+            .replica_storage
+            .visible_offset_listener(&self.isolation);
         let mut counter: i32 = 0;
         // since we don't need to wait for consumer, can move consumer to same offset as last read
         let mut last_known_consumer_offset: Option<Offset> =
@@ -247,6 +364,11 @@ impl StreamFetchHandler {
                     break;
                 },
 
+                _ = FollowerRead::stale(self.follower.as_ref()) => {
+                    debug!("follower became stale, terminating");
+                    return Err(StreamFetchError::Fetch(STALE_FOLLOWER));
+                },
+
 
                 // Received offset update from consumer, i.e. consumer acknowledged to this offset
                 consumer_offset_update = self.consumer_offset_listener.listen() => {
@@ -352,6 +474,15 @@ impl StreamFetchHandler {
         starting_offset: Offset,
         sm_ctx: Option<&mut SmartModuleContext>,
     ) -> Result<(Offset, bool), StreamFetchError> {
+        if self
+            .follower
+            .as_ref()
+            .is_some_and(|follower| !follower.is_fresh())
+        {
+            debug!("follower is stale");
+            return Err(StreamFetchError::Fetch(STALE_FOLLOWER));
+        }
+
         let now = Instant::now();
 
         let mut file_partition_response = FilePartitionResponse {
@@ -363,8 +494,8 @@ impl StreamFetchHandler {
         // Returns with the HW/LEO of the latest records available in the leader
         // This describes the range of records that can be read in this request
         let read_end_offset = match self
-            .leader_state
-            .read_records(starting_offset, self.max_fetch_bytes, self.isolation)
+            .replica_storage
+            .read_visible_records(starting_offset, self.max_fetch_bytes, self.isolation)
             .await
         {
             Ok(slice) => {
@@ -391,6 +522,16 @@ impl StreamFetchHandler {
             "Starting send_back_records",
         );
 
+        if self.isolation == Isolation::ReadCommitted {
+            let aborted = self
+                .replica_storage
+                .aborted_txns(starting_offset, read_end_offset.hw)
+                .await;
+            if !aborted.is_empty() {
+                file_partition_response.aborted = Some(aborted);
+            }
+        }
+
         let next_offset = read_end_offset.isolation(&self.isolation);
 
         // We were unable to read any records from this starting offset,
@@ -400,14 +541,45 @@ impl StreamFetchHandler {
             return Ok((starting_offset, false));
         }
 
+        // broker times of first batch in response
+        let fetch_time = Utc::now().timestamp_millis();
+        if let Some(times) = self.replica_storage.append_times(starting_offset).await {
+            self.metrics
+                .latency()
+                .append_to_fetch()
+                .record_between(times.append_time, fetch_time);
+            if self.broker_timestamps {
+                file_partition_response.broker_timestamps = Some(BrokerTimestamps {
+                    append_time: times.append_time,
+                    commit_time: times.commit_time.unwrap_or(-1),
+                    fetch_time,
+                });
+            }
+        }
+
         let (offset, wait, metrics_update) = match sm_ctx {
             Some(sm_ctx) => {
                 // If a SmartModule is provided, we need to read records from file to memory
                 // In-memory records are then processed by SmartModule and returned to consumer
 
                 let records = &file_partition_response.records;
+                let _memory = self
+                    .memory
+                    .acquire(MemoryKind::Fetch, records.len() as u64)
+                    .await;
+                // SmartModule output doesn't keep producer of batches, so transaction
+                // markers and aborted batches are dropped before processing
+                let mut txn_filter = AbortedTxnFilter::new(
+                    file_partition_response.aborted.take().unwrap_or_default(),
+                );
                 let mut file_batch_iterator =
-                    FileBatchIterator::from_raw_slice(records.raw_slice());
+                    FileBatchIterator::from_raw_slice(records.raw_slice())
+                        .cipher(self.replica_storage.cipher())
+                        .filter(|file_batch| match file_batch {
+                            Ok(file_batch) => txn_filter
+                                .keep(file_batch.batch.base_offset, file_batch.batch.get_header()),
+                            Err(_) => true,
+                        });
 
                 let (batch, smartmodule_error) = process_batch(
                     sm_ctx.chain_mut(),
@@ -431,29 +603,48 @@ impl StreamFetchHandler {
                 (offset, wait, metrics_update)
             }
             None => {
-                // If no SmartModule is provided, respond using raw file records
-                debug!("No SmartModule, sending back entire log");
                 let metrics_update = IncreaseValue::from(&file_partition_response);
 
-                let response = StreamFetchResponse {
-                    topic: self.replica.topic.clone(),
-                    stream_id: self.stream_id,
-                    partition: file_partition_response,
-                };
-
-                let response_msg = RequestMessage::<FileStreamFetchRequest>::response_with_header(
-                    &self.header,
-                    response,
-                );
-
-                trace!("sending back file fetch response msg: {:#?}", response_msg);
-
-                let mut inner_sink = self.sink.lock().await;
-                inner_sink
-                    .encode_file_slices(&response_msg, self.header.api_version())
-                    .await?;
-
-                drop(inner_sink);
+                match self.replica_storage.cipher() {
+                    Some(cipher) => {
+                        // Encrypted records are decrypted in memory, file can't be sent as it is
+                        debug!("No SmartModule, sending back decrypted log");
+                        let _memory = self
+                            .memory
+                            .acquire(
+                                MemoryKind::Fetch,
+                                file_partition_response.records.len() as u64,
+                            )
+                            .await;
+                        self.send_decrypted_response(file_partition_response, cipher)
+                            .await?;
+                    }
+                    None => {
+                        // If no SmartModule is provided, respond using raw file records
+                        debug!("No SmartModule, sending back entire log");
+
+                        let response = StreamFetchResponse {
+                            topic: self.replica.topic.clone(),
+                            stream_id: self.stream_id,
+                            partition: file_partition_response,
+                        };
+
+                        let response_msg =
+                            RequestMessage::<FileStreamFetchRequest>::response_with_header(
+                                &self.header,
+                                response,
+                            );
+
+                        trace!("sending back file fetch response msg: {:#?}", response_msg);
+
+                        let mut inner_sink = self.sink.lock().await;
+                        inner_sink
+                            .encode_file_slices(&response_msg, self.header.api_version())
+                            .await?;
+
+                        drop(inner_sink);
+                    }
+                }
 
                 debug!(read_time_ms = %now.elapsed().as_millis(),"finish sending back records");
 
@@ -464,9 +655,20 @@ impl StreamFetchHandler {
                 )
             }
         };
+        let bytes = metrics_update.bytes();
         self.metrics
             .outbound()
             .increase_by_value(self.header.is_connector(), metrics_update);
+
+        // stream is paused before next records are sent
+        let throttle = self
+            .quotas
+            .record_bytes(self.header.client_id(), QuotaType::Fetch, bytes)
+            .await;
+        if !throttle.is_zero() {
+            debug!(?throttle, "throttling stream fetch");
+            sleep(throttle).await;
+        }
         Ok((offset, wait))
     }
 
@@ -480,8 +682,20 @@ impl StreamFetchHandler {
     ) -> Result<(Offset, bool), StreamFetchError> {
         type DefaultPartitionResponse = FetchablePartitionResponse<RecordSet<RawRecords>>;
 
+        // offset after record routed to dead-letter topic, processing resumes from there
+        let mut resume_offset = None;
         let error_code = match smartmodule_error {
-            Some(error) => ErrorCode::SmartModuleRuntimeError(error),
+            Some(error) => match self.route_dead_letter(&error).await {
+                Ok(true) => {
+                    resume_offset = Some(error.offset + 1);
+                    file_partition_response.error_code
+                }
+                Ok(false) => ErrorCode::SmartModuleRuntimeError(error),
+                Err(err) => {
+                    warn!(?err, "failed to route record to dead-letter topic");
+                    ErrorCode::SmartModuleRuntimeError(error)
+                }
+            },
             None => file_partition_response.error_code,
         };
         trace!(?error_code, "SmartModule error code output:");
@@ -489,12 +703,14 @@ impl StreamFetchHandler {
         let has_error = !matches!(error_code, ErrorCode::None);
         let has_records = !batch.records().is_empty();
 
-        if !has_records && !has_error {
+        if !has_records && !has_error && resume_offset.is_none() {
             debug!(next_offset, "No records to send back, skipping");
             return Ok((next_offset, false));
         }
 
-        let next_filter_offset = if has_records {
+        let next_filter_offset = if let Some(resume_offset) = resume_offset {
+            resume_offset
+        } else if has_records {
             trace!(?batch, "SmartModule batch:");
             batch.get_last_offset() + 1
         } else {
@@ -515,6 +731,7 @@ impl StreamFetchHandler {
             error_code,
             high_watermark: file_partition_response.high_watermark,
             log_start_offset: file_partition_response.log_start_offset,
+            broker_timestamps: file_partition_response.broker_timestamps,
             records: records.try_into()?,
             next_filter_offset,
             // we mark last offset in the response that we should sync up
@@ -541,6 +758,61 @@ impl StreamFetchHandler {
 
         Ok((next_offset, true))
     }
+
+    async fn send_decrypted_response(
+        &self,
+        file_partition_response: FilePartitionResponse,
+        cipher: Arc<BatchCipher>,
+    ) -> Result<(), StreamFetchError> {
+        type DefaultPartitionResponse = FetchablePartitionResponse<RecordSet<RawRecords>>;
+
+        let records = read_slice(file_partition_response.records.raw_slice(), Some(cipher))
+            .map_err(|err| {
+                StreamFetchError::Fetch(ErrorCode::Other(format!("decryption failed: {err}")))
+            })?;
+        let partition_response = DefaultPartitionResponse {
+            partition_index: file_partition_response.partition_index,
+            error_code: file_partition_response.error_code,
+            high_watermark: file_partition_response.high_watermark,
+            log_start_offset: file_partition_response.log_start_offset,
+            aborted: file_partition_response.aborted,
+            broker_timestamps: file_partition_response.broker_timestamps,
+            records,
+            ..Default::default()
+        };
+
+        let stream_response = StreamFetchResponse {
+            topic: self.replica.topic.clone(),
+            stream_id: self.stream_id,
+            partition: partition_response,
+        };
+
+        let response_msg = RequestMessage::<DefaultStreamFetchRequest>::response_with_header(
+            &self.header,
+            stream_response,
+        );
+
+        let mut inner_sink = self.sink.lock().await;
+        inner_sink
+            .send_response(&response_msg, self.header.api_version())
+            .await?;
+
+        Ok(())
+    }
+
+    /// write failed record to dead-letter topic, false if consumer didn't set one
+    async fn route_dead_letter(
+        &self,
+        error: &SmartModuleTransformRuntimeError,
+    ) -> Result<bool, ErrorCode> {
+        match self.dead_letter {
+            Some(ref dead_letter) => {
+                dead_letter.send(&self.replica, error).await?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
 }
 
 async fn send_back_error(
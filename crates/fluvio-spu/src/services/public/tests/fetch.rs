@@ -0,0 +1,163 @@
+use std::env::temp_dir;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use fluvio_controlplane::replica::Replica;
+use fluvio_future::timer::sleep;
+use fluvio_protocol::api::RequestMessage;
+use fluvio_protocol::fixture::create_raw_recordset;
+use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::record::RecordSet;
+use fluvio_socket::{FluvioSocket, MultiplexerSocket};
+use fluvio_spu_schema::ApiVersionsRequest;
+use fluvio_spu_schema::fetch::{DefaultFetchRequest, FetchPartition, FetchResponse, FetchableTopic};
+use fluvio_storage::FileReplica;
+use fluvio_types::event::StickyEvent;
+use flv_util::fixture::ensure_clean_dir;
+
+use crate::config::SpuConfig;
+use crate::core::{DefaultSharedGlobalContext, GlobalContext};
+use crate::replication::leader::LeaderReplicaState;
+use crate::services::public::create_public_server;
+
+const TOPIC: &str = "fetch";
+
+struct FetchFixture {
+    ctx: DefaultSharedGlobalContext,
+    server_end_event: Arc<StickyEvent>,
+    client_socket: MultiplexerSocket,
+    leader: LeaderReplicaState<FileReplica>,
+}
+
+impl FetchFixture {
+    /// SPU leading single partition, fetch may wait up to 10 seconds
+    async fn start(test_name: &str) -> Self {
+        let test_path = temp_dir().join(test_name);
+        ensure_clean_dir(&test_path);
+        let port = portpicker::pick_unused_port().expect("No free ports left");
+        let addr = format!("127.0.0.1:{port}");
+
+        let mut spu_config = SpuConfig::default();
+        spu_config.log.base_dir = test_path;
+        spu_config.fetch.max_wait_ms = 10_000;
+        let ctx = GlobalContext::new_shared_context(spu_config);
+
+        let server_end_event = create_public_server(addr.clone(), ctx.clone(), None).run();
+
+        // wait for stream controller async to start
+        sleep(Duration::from_millis(100)).await;
+
+        let client_socket =
+            MultiplexerSocket::new(FluvioSocket::connect(&addr).await.expect("connect"));
+
+        let replica = Replica::new((TOPIC, 0), 5001, vec![5001]);
+        let replica_id = replica.id.clone();
+        ctx.replica_localstore().sync_all(vec![replica.clone()]);
+        let leader = LeaderReplicaState::create(replica, ctx.config(), ctx.status_update_owned())
+            .await
+            .expect("replica")
+            .init(&ctx)
+            .await
+            .expect("init succeeded");
+        ctx.leaders_state().insert(replica_id, leader.clone()).await;
+
+        Self {
+            ctx,
+            server_end_event,
+            client_socket,
+            leader,
+        }
+    }
+
+    async fn fetch(&self, min_bytes: i32, max_wait: i32) -> (FetchResponse<RecordSet>, Duration) {
+        let request = DefaultFetchRequest {
+            min_bytes,
+            max_wait,
+            max_bytes: 1_000_000,
+            topics: vec![FetchableTopic {
+                name: TOPIC.to_owned(),
+                fetch_partitions: vec![FetchPartition {
+                    partition_index: 0,
+                    fetch_offset: 0,
+                    max_bytes: 1_000_000,
+                    ..Default::default()
+                }],
+            }],
+            ..Default::default()
+        };
+        let started = Instant::now();
+        let response = self
+            .client_socket
+            .send_and_receive(RequestMessage::new_request(request))
+            .await
+            .expect("fetch");
+        (response, started.elapsed())
+    }
+}
+
+fn fetched_batches(response: &FetchResponse<RecordSet>) -> usize {
+    let partition = &response.topics[0].partitions[0];
+    assert_eq!(partition.error_code, ErrorCode::None);
+    partition.records.batches.len()
+}
+
+#[fluvio_future::test]
+async fn test_fetch_returns_when_min_bytes_written() {
+    let fixture = FetchFixture::start("fetch_min_bytes").await;
+
+    let write = async {
+        sleep(Duration::from_millis(200)).await;
+        // connection keeps serving requests while fetch is waiting
+        fixture
+            .client_socket
+            .send_and_receive(RequestMessage::new_request(ApiVersionsRequest::default()))
+            .await
+            .expect("api versions");
+        fixture
+            .leader
+            .write_record_set(
+                &mut create_raw_recordset(2),
+                fixture.ctx.follower_notifier(),
+            )
+            .await
+            .expect("write");
+    };
+    let ((response, elapsed), _) = futures_util::join!(fixture.fetch(1, 10_000), write);
+
+    assert_eq!(fetched_batches(&response), 1);
+    assert!(elapsed < Duration::from_secs(5), "fetch took {elapsed:?}");
+
+    fixture.server_end_event.notify();
+}
+
+#[fluvio_future::test]
+async fn test_fetch_returns_when_wait_expires() {
+    let fixture = FetchFixture::start("fetch_wait_expires").await;
+
+    let (response, elapsed) = fixture.fetch(1_000_000, 300).await;
+
+    assert_eq!(fetched_batches(&response), 0);
+    assert!(
+        elapsed >= Duration::from_millis(300),
+        "fetch took {elapsed:?}"
+    );
+    assert!(elapsed < Duration::from_secs(5), "fetch took {elapsed:?}");
+
+    fixture.server_end_event.notify();
+}
+
+#[fluvio_future::test]
+async fn test_fetch_returns_on_shutdown() {
+    let fixture = FetchFixture::start("fetch_shutdown").await;
+
+    let shutdown = async {
+        sleep(Duration::from_millis(200)).await;
+        fixture.ctx.shutdown().notify();
+    };
+    let ((response, elapsed), _) = futures_util::join!(fixture.fetch(1, 10_000), shutdown);
+
+    assert_eq!(fetched_batches(&response), 0);
+    assert!(elapsed < Duration::from_secs(5), "fetch took {elapsed:?}");
+
+    fixture.server_end_event.notify();
+}
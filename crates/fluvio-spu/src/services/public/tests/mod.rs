@@ -20,6 +20,7 @@ use crate::core::GlobalContext;
 
 mod stream_fetch;
 mod produce;
+mod fetch;
 
 /// create records that can be filtered
 fn create_filter_records(records: u16) -> RecordSet {
@@ -12,7 +12,7 @@ use fluvio_protocol::{
     Decoder,
 };
 use fluvio_controlplane_metadata::topic::{
-    CompressionAlgorithm, Deduplication, Bounds, Filter, Transform,
+    CompressionAlgorithm, Deduplication, Bounds, Filter, Transform, TopicStorageConfig,
 };
 use fluvio_future::timer::sleep;
 use fluvio_socket::{MultiplexerSocket, FluvioSocket};
@@ -47,7 +47,7 @@ async fn test_produce_basic() {
     spu_config.log.base_dir = test_path;
     let ctx = GlobalContext::new_shared_context(spu_config);
 
-    let server_end_event = create_public_server(addr.to_owned(), ctx.clone()).run();
+    let server_end_event = create_public_server(addr.to_owned(), ctx.clone(), None).run();
 
     // wait for stream controller async to start
     sleep(Duration::from_millis(100)).await;
@@ -81,6 +81,7 @@ async fn test_produce_basic() {
         let partition_produce = DefaultPartitionRequest {
             partition_index: 0,
             records,
+            ..Default::default()
         };
         let topic_produce_request = TopicProduceData {
             name: topic.to_owned(),
@@ -115,6 +116,7 @@ async fn test_produce_basic() {
             records: create_filter_records(records_per_request)
                 .try_into()
                 .expect("partition"),
+            ..Default::default()
         })
         .collect::<Vec<_>>();
     let topic_produce_request = TopicProduceData {
@@ -160,7 +162,7 @@ async fn test_produce_invalid_compression() {
     spu_config.log.base_dir = test_path;
     let ctx = GlobalContext::new_shared_context(spu_config);
 
-    let server_end_event = create_public_server(addr.to_owned(), ctx.clone()).run();
+    let server_end_event = create_public_server(addr.to_owned(), ctx.clone(), None).run();
 
     // wait for stream controller async to start
     sleep(Duration::from_millis(100)).await;
@@ -195,6 +197,7 @@ async fn test_produce_invalid_compression() {
     let partition_produce = DefaultPartitionRequest {
         partition_index: 0,
         records,
+        ..Default::default()
     };
     let topic_produce_request = TopicProduceData {
         name: topic.to_owned(),
@@ -220,6 +223,218 @@ async fn test_produce_invalid_compression() {
     server_end_event.notify();
     debug!("terminated controller");
 }
+
+#[fluvio_future::test(ignore)]
+async fn test_produce_read_only() {
+    let test_path = temp_dir().join("produce_read_only");
+    ensure_clean_dir(&test_path);
+    let port = portpicker::pick_unused_port().expect("No free ports left");
+
+    let addr = format!("127.0.0.1:{port}");
+    let mut spu_config = SpuConfig::default();
+    spu_config.log.base_dir = test_path;
+    let ctx = GlobalContext::new_shared_context(spu_config);
+
+    let server_end_event = create_public_server(addr.to_owned(), ctx.clone(), None).run();
+
+    // wait for stream controller async to start
+    sleep(Duration::from_millis(100)).await;
+
+    let client_socket =
+        MultiplexerSocket::new(FluvioSocket::connect(&addr).await.expect("connect"));
+    let topic = "test_produce";
+    let mut test = Replica::new((topic, 0), 5001, vec![5001]);
+    test.read_only = true;
+    let test_id = test.id.clone();
+    ctx.replica_localstore().sync_all(vec![test.clone()]);
+
+    let replica = LeaderReplicaState::create(test, ctx.config(), ctx.status_update_owned())
+        .await
+        .expect("replica")
+        .init(&ctx)
+        .await
+        .expect("init succeeded");
+
+    ctx.leaders_state().insert(test_id, replica.clone()).await;
+
+    let records = create_filter_records(9).try_into().expect("filter records");
+
+    let mut produce_request = DefaultProduceRequest {
+        ..Default::default()
+    };
+    produce_request.topics.push(TopicProduceData {
+        name: topic.to_owned(),
+        partitions: vec![DefaultPartitionRequest {
+            partition_index: 0,
+            records,
+            ..Default::default()
+        }],
+        ..Default::default()
+    });
+
+    let produce_response = client_socket
+        .send_and_receive(RequestMessage::new_request(produce_request))
+        .await
+        .expect("send offset");
+
+    assert_eq!(
+        produce_response.responses[0].partitions[0].error_code,
+        ErrorCode::PartitionReadOnly
+    );
+    assert_eq!(replica.leo(), 0);
+
+    server_end_event.notify();
+    debug!("terminated controller");
+}
+
+#[fluvio_future::test(ignore)]
+async fn test_produce_stale_leader_epoch() {
+    let test_path = temp_dir().join("produce_stale_leader_epoch");
+    ensure_clean_dir(&test_path);
+    let port = portpicker::pick_unused_port().expect("No free ports left");
+
+    let addr = format!("127.0.0.1:{port}");
+    let mut spu_config = SpuConfig::default();
+    spu_config.log.base_dir = test_path;
+    let ctx = GlobalContext::new_shared_context(spu_config);
+
+    let server_end_event = create_public_server(addr.to_owned(), ctx.clone(), None).run();
+
+    // wait for stream controller async to start
+    sleep(Duration::from_millis(100)).await;
+
+    let client_socket =
+        MultiplexerSocket::new(FluvioSocket::connect(&addr).await.expect("connect"));
+    let topic = "test_produce";
+    let mut test = Replica::new((topic, 0), 5001, vec![5001]);
+    test.leader_epoch = 2;
+    let test_id = test.id.clone();
+    ctx.replica_localstore().sync_all(vec![test.clone()]);
+
+    let replica = LeaderReplicaState::create(test, ctx.config(), ctx.status_update_owned())
+        .await
+        .expect("replica")
+        .init(&ctx)
+        .await
+        .expect("init succeeded");
+
+    ctx.leaders_state().insert(test_id, replica.clone()).await;
+
+    let records = create_filter_records(9).try_into().expect("filter records");
+
+    let mut produce_request = DefaultProduceRequest {
+        ..Default::default()
+    };
+    produce_request.topics.push(TopicProduceData {
+        name: topic.to_owned(),
+        partitions: vec![DefaultPartitionRequest {
+            partition_index: 0,
+            records,
+            leader_epoch: 1,
+        }],
+        ..Default::default()
+    });
+
+    let produce_response = client_socket
+        .send_and_receive(RequestMessage::new_request(produce_request))
+        .await
+        .expect("send offset");
+
+    assert_eq!(
+        produce_response.responses[0].partitions[0].error_code,
+        ErrorCode::StaleLeaderEpoch {
+            received: 1,
+            current: 2
+        }
+    );
+    assert_eq!(replica.leo(), 0);
+
+    server_end_event.notify();
+    debug!("terminated controller");
+}
+
+#[fluvio_future::test(ignore)]
+async fn test_produce_not_enough_replicas() {
+    let test_path = temp_dir().join("produce_not_enough_replicas");
+    ensure_clean_dir(&test_path);
+    let port = portpicker::pick_unused_port().expect("No free ports left");
+
+    let addr = format!("127.0.0.1:{port}");
+    let mut spu_config = SpuConfig::default();
+    spu_config.log.base_dir = test_path;
+    let ctx = GlobalContext::new_shared_context(spu_config);
+
+    let server_end_event = create_public_server(addr.to_owned(), ctx.clone(), None).run();
+
+    // wait for stream controller async to start
+    sleep(Duration::from_millis(100)).await;
+
+    let client_socket =
+        MultiplexerSocket::new(FluvioSocket::connect(&addr).await.expect("connect"));
+    let topic = "test_produce";
+    // follower 5002 never reports its offsets, so only leader is in sync
+    let mut test = Replica::new((topic, 0), 5001, vec![5001, 5002]);
+    test.storage = Some(TopicStorageConfig {
+        min_in_sync_replicas: Some(2),
+        ..Default::default()
+    });
+    let test_id = test.id.clone();
+    ctx.replica_localstore().sync_all(vec![test.clone()]);
+
+    let replica = LeaderReplicaState::create(test, ctx.config(), ctx.status_update_owned())
+        .await
+        .expect("replica")
+        .init(&ctx)
+        .await
+        .expect("init succeeded");
+
+    ctx.leaders_state().insert(test_id, replica.clone()).await;
+
+    let produce_request = |isolation| {
+        let records = create_filter_records(9).try_into().expect("filter records");
+        let mut produce_request = DefaultProduceRequest {
+            isolation,
+            ..Default::default()
+        };
+        produce_request.topics.push(TopicProduceData {
+            name: topic.to_owned(),
+            partitions: vec![DefaultPartitionRequest {
+                partition_index: 0,
+                records,
+                ..Default::default()
+            }],
+            ..Default::default()
+        });
+        RequestMessage::new_request(produce_request)
+    };
+
+    let produce_response = client_socket
+        .send_and_receive(produce_request(Isolation::ReadCommitted))
+        .await
+        .expect("send offset");
+    assert_eq!(
+        produce_response.responses[0].partitions[0].error_code,
+        ErrorCode::NotEnoughReplicas {
+            in_sync: 1,
+            required: 2
+        }
+    );
+    assert_eq!(replica.leo(), 0);
+
+    // produce not waiting for replicas is accepted
+    let produce_response = client_socket
+        .send_and_receive(produce_request(Isolation::ReadUncommitted))
+        .await
+        .expect("send offset");
+    assert_eq!(
+        produce_response.responses[0].partitions[0].error_code,
+        ErrorCode::None
+    );
+    assert_eq!(replica.leo(), 9);
+
+    server_end_event.notify();
+    debug!("terminated controller");
+}
 use crate::replication::test::TestConfig;
 use crate::services::create_internal_server;
 
@@ -234,7 +449,8 @@ async fn test_produce_request_timed_out() {
 
     let (leader_ctx, _) = config.leader_replica().await;
 
-    let server_end_event = create_public_server(public_addr.clone(), leader_ctx.clone()).run();
+    let server_end_event =
+        create_public_server(public_addr.clone(), leader_ctx.clone(), None).run();
 
     // wait for stream controller async to start
     sleep(Duration::from_millis(100)).await;
@@ -257,6 +473,7 @@ async fn test_produce_request_timed_out() {
     let partition_produce = DefaultPartitionRequest {
         partition_index: 0,
         records,
+        ..Default::default()
     };
     let topic_produce_request = TopicProduceData {
         name: topic.to_owned(),
@@ -296,7 +513,8 @@ async fn test_produce_not_waiting_replication() {
     let (leader_ctx, _) = config.leader_replica().await;
     let public_addr = config.leader_public_addr();
 
-    let server_end_event = create_public_server(public_addr.clone(), leader_ctx.clone()).run();
+    let server_end_event =
+        create_public_server(public_addr.clone(), leader_ctx.clone(), None).run();
 
     // wait for stream controller async to start
     sleep(Duration::from_millis(100)).await;
@@ -319,6 +537,7 @@ async fn test_produce_not_waiting_replication() {
     let partition_produce = DefaultPartitionRequest {
         partition_index: 0,
         records,
+        ..Default::default()
     };
     let topic_produce_request = TopicProduceData {
         name: topic.to_owned(),
@@ -356,7 +575,7 @@ async fn test_produce_waiting_replication() {
     let public_addr = config.leader_public_addr();
 
     let public_server_end_event =
-        create_public_server(public_addr.clone(), leader_ctx.clone()).run();
+        create_public_server(public_addr.clone(), leader_ctx.clone(), None).run();
     let private_server_end_event =
         create_internal_server(config.leader_addr(), leader_ctx.clone()).run();
 
@@ -384,6 +603,7 @@ async fn test_produce_waiting_replication() {
     let partition_produce = DefaultPartitionRequest {
         partition_index: 0,
         records,
+        ..Default::default()
     };
     let topic_produce_request = TopicProduceData {
         name: topic.to_owned(),
@@ -425,7 +645,7 @@ async fn test_produce_metrics() {
     spu_config.log.base_dir = test_path;
     let ctx = GlobalContext::new_shared_context(spu_config);
 
-    let server_end_event = create_public_server(addr.to_owned(), ctx.clone()).run();
+    let server_end_event = create_public_server(addr.to_owned(), ctx.clone(), None).run();
 
     // wait for stream controller async to start
     sleep(Duration::from_millis(100)).await;
@@ -463,6 +683,7 @@ async fn test_produce_metrics() {
         let partition_produce = DefaultPartitionRequest {
             partition_index: 0,
             records,
+            ..Default::default()
         };
         let topic_produce_request = TopicProduceData {
             name: topic.to_owned(),
@@ -491,6 +712,7 @@ async fn test_produce_metrics() {
         let partition_produce = DefaultPartitionRequest {
             partition_index: 0,
             records,
+            ..Default::default()
         };
         let topic_produce_request = TopicProduceData {
             name: topic.to_owned(),
@@ -537,7 +759,7 @@ async fn test_produce_basic_with_smartmodule_with_lookback() {
     smartmodule.params.set_lookback(Some(Lookback::last(1)));
     let mut smartmodules = vec![smartmodule];
 
-    let server_end_event = create_public_server(addr.to_owned(), ctx.clone()).run();
+    let server_end_event = create_public_server(addr.to_owned(), ctx.clone(), None).run();
 
     // wait for stream controller async to start
     sleep(Duration::from_millis(100)).await;
@@ -570,6 +792,7 @@ async fn test_produce_basic_with_smartmodule_with_lookback() {
         let partition_produce = DefaultPartitionRequest {
             partition_index: 0,
             records,
+            ..Default::default()
         };
         let topic_produce_request = TopicProduceData {
             name: topic.to_owned(),
@@ -601,6 +824,7 @@ async fn test_produce_basic_with_smartmodule_with_lookback() {
         let partition_produce = DefaultPartitionRequest {
             partition_index: 0,
             records,
+            ..Default::default()
         };
         let topic_produce_request = TopicProduceData {
             name: topic.to_owned(),
@@ -636,6 +860,7 @@ async fn test_produce_basic_with_smartmodule_with_lookback() {
         let partition_produce = DefaultPartitionRequest {
             partition_index: 0,
             records,
+            ..Default::default()
         };
         let topic_produce_request = TopicProduceData {
             name: topic.to_owned(),
@@ -675,6 +900,7 @@ async fn test_produce_basic_with_smartmodule_with_lookback() {
         let partition_produce = DefaultPartitionRequest {
             partition_index: 0,
             records,
+            ..Default::default()
         };
         let topic_produce_request = TopicProduceData {
             name: topic.to_owned(),
@@ -715,6 +941,7 @@ async fn test_produce_basic_with_smartmodule_with_lookback() {
         let partition_produce = DefaultPartitionRequest {
             partition_index: 0,
             records,
+            ..Default::default()
         };
         let topic_produce_request = TopicProduceData {
             name: topic.to_owned(),
@@ -756,6 +983,7 @@ async fn test_produce_basic_with_smartmodule_with_lookback() {
         let partition_produce = DefaultPartitionRequest {
             partition_index: 0,
             records,
+            ..Default::default()
         };
         let topic_produce_request = TopicProduceData {
             name: topic.to_owned(),
@@ -792,6 +1020,7 @@ async fn test_produce_basic_with_smartmodule_with_lookback() {
                 partitions: vec![DefaultPartitionRequest {
                     partition_index: 0,
                     records: vec_to_raw_batch(&["wrong last record"]),
+                    ..Default::default()
                 }],
                 ..Default::default()
             }],
@@ -810,6 +1039,7 @@ async fn test_produce_basic_with_smartmodule_with_lookback() {
                 partitions: vec![DefaultPartitionRequest {
                     partition_index: 0,
                     records: vec_to_raw_batch(&["4"]),
+                    ..Default::default()
                 }],
                 ..Default::default()
             }],
@@ -848,7 +1078,7 @@ async fn test_produce_with_deduplication() {
     let ctx = GlobalContext::new_shared_context(spu_config);
     load_wasm_module(&ctx, FLUVIO_WASM_DEDUPLICATION_FILTER);
 
-    let server_end_event = create_public_server(addr.to_owned(), ctx.clone()).run();
+    let server_end_event = create_public_server(addr.to_owned(), ctx.clone(), None).run();
 
     // wait for stream controller async to start
     sleep(Duration::from_millis(100)).await;
@@ -894,6 +1124,7 @@ async fn test_produce_with_deduplication() {
         let partition_produce = DefaultPartitionRequest {
             partition_index: 0,
             records,
+            ..Default::default()
         };
         let topic_produce_request = TopicProduceData {
             name: topic.to_owned(),
@@ -923,6 +1154,7 @@ async fn test_produce_with_deduplication() {
         let partition_produce = DefaultPartitionRequest {
             partition_index: 0,
             records,
+            ..Default::default()
         };
         let topic_produce_request = TopicProduceData {
             name: topic.to_owned(),
@@ -955,6 +1187,7 @@ async fn test_produce_with_deduplication() {
         let partition_produce = DefaultPartitionRequest {
             partition_index: 0,
             records,
+            ..Default::default()
         };
         let topic_produce_request = TopicProduceData {
             name: topic.to_owned(),
@@ -997,6 +1230,7 @@ async fn test_produce_with_deduplication() {
         let partition_produce = DefaultPartitionRequest {
             partition_index: 0,
             records,
+            ..Default::default()
         };
         let topic_produce_request = TopicProduceData {
             name: topic.to_owned(),
@@ -1038,7 +1272,7 @@ async fn test_produce_smart_engine_memory_overfow() {
     let ctx = GlobalContext::new_shared_context(spu_config);
     load_wasm_module(&ctx, FLUVIO_WASM_DEDUPLICATION_FILTER);
 
-    let server_end_event = create_public_server(addr.to_owned(), ctx.clone()).run();
+    let server_end_event = create_public_server(addr.to_owned(), ctx.clone(), None).run();
 
     // wait for stream controller async to start
     sleep(Duration::from_millis(100)).await;
@@ -1084,6 +1318,7 @@ async fn test_produce_smart_engine_memory_overfow() {
         let partition_produce = DefaultPartitionRequest {
             partition_index: 0,
             records,
+            ..Default::default()
         };
         let topic_produce_request = TopicProduceData {
             name: topic.to_owned(),
@@ -1124,7 +1359,7 @@ async fn test_dedup_init_smart_engine_memory_overfow() {
     let ctx = GlobalContext::new_shared_context(spu_config);
     load_wasm_module(&ctx, FLUVIO_WASM_DEDUPLICATION_FILTER);
 
-    let server_end_event = create_public_server(addr.to_owned(), ctx.clone()).run();
+    let server_end_event = create_public_server(addr.to_owned(), ctx.clone(), None).run();
 
     // wait for stream controller async to start
     sleep(Duration::from_millis(100)).await;
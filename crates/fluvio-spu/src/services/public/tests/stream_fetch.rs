@@ -58,7 +58,7 @@ async fn test_stream_fetch_basic() {
     spu_config.log.base_dir = test_path;
     let ctx = GlobalContext::new_shared_context(spu_config);
 
-    let server_end_event = create_public_server(addr.to_owned(), ctx.clone()).run();
+    let server_end_event = create_public_server(addr.to_owned(), ctx.clone(), None).run();
 
     // wait for stream controller async to start
     sleep(Duration::from_millis(100)).await;
@@ -394,7 +394,7 @@ async fn test_stream_fetch_filter(
 
     let addr = format!("127.0.0.1:{port}");
 
-    let server_end_event = create_public_server(addr.to_owned(), ctx.clone()).run();
+    let server_end_event = create_public_server(addr.to_owned(), ctx.clone(), None).run();
 
     // wait for stream controller async to start
     sleep(Duration::from_millis(100)).await;
@@ -585,7 +585,7 @@ async fn test_stream_fetch_filter_individual(
 
     let addr = format!("127.0.0.1:{port}");
 
-    let server_end_event = create_public_server(addr.to_owned(), ctx.clone()).run();
+    let server_end_event = create_public_server(addr.to_owned(), ctx.clone(), None).run();
 
     // wait for stream controller async to start
     sleep(Duration::from_millis(100)).await;
@@ -711,7 +711,7 @@ async fn test_stream_filter_error_fetch(
 
     let addr = format!("127.0.0.1:{port}");
 
-    let server_end_event = create_public_server(addr.to_owned(), ctx.clone()).run();
+    let server_end_event = create_public_server(addr.to_owned(), ctx.clone(), None).run();
 
     // wait for stream controller async to start
     sleep(Duration::from_millis(100)).await;
@@ -846,7 +846,7 @@ async fn test_stream_filter_max(
 
     let addr = format!("127.0.0.1:{port}");
 
-    let server_end_event = create_public_server(addr.to_owned(), ctx.clone()).run();
+    let server_end_event = create_public_server(addr.to_owned(), ctx.clone(), None).run();
 
     // wait for stream controller async to start
     sleep(Duration::from_millis(100)).await;
@@ -992,7 +992,7 @@ async fn test_stream_fetch_map(
 
     let addr = format!("127.0.0.1:{port}");
 
-    let server_end_event = create_public_server(addr.to_owned(), ctx.clone()).run();
+    let server_end_event = create_public_server(addr.to_owned(), ctx.clone(), None).run();
 
     // wait for stream controller async to start
     sleep(Duration::from_millis(100)).await;
@@ -1147,7 +1147,7 @@ async fn test_stream_fetch_map_chain(
 
     let addr = format!("127.0.0.1:{port}");
 
-    let server_end_event = create_public_server(addr.to_owned(), ctx.clone()).run();
+    let server_end_event = create_public_server(addr.to_owned(), ctx.clone(), None).run();
 
     // wait for stream controller async to start
     sleep(Duration::from_millis(100)).await;
@@ -1307,7 +1307,7 @@ async fn test_stream_fetch_map_error(
 
     let addr = format!("127.0.0.1:{port}");
 
-    let server_end_event = create_public_server(addr.to_owned(), ctx.clone()).run();
+    let server_end_event = create_public_server(addr.to_owned(), ctx.clone(), None).run();
 
     // wait for stream controller async to start
     sleep(Duration::from_millis(100)).await;
@@ -1445,7 +1445,7 @@ async fn test_stream_aggregate_fetch_single_batch(
     let port = portpicker::pick_unused_port().expect("No free ports left");
     let addr = format!("127.0.0.1:{port}");
 
-    let server_end_event = create_public_server(addr.to_owned(), ctx.clone()).run();
+    let server_end_event = create_public_server(addr.to_owned(), ctx.clone(), None).run();
 
     // wait for stream controller async to start
     sleep(Duration::from_millis(100)).await;
@@ -1591,7 +1591,7 @@ async fn test_stream_aggregate_fetch_multiple_batch(
 
     let addr = format!("127.0.0.1:{port}");
 
-    let server_end_event = create_public_server(addr.to_owned(), ctx.clone()).run();
+    let server_end_event = create_public_server(addr.to_owned(), ctx.clone(), None).run();
 
     // wait for stream controller async to start
     sleep(Duration::from_millis(100)).await;
@@ -1729,7 +1729,7 @@ async fn test_stream_fetch_and_new_request(
 
     let addr = format!("127.0.0.1:{port}");
 
-    let server_end_event = create_public_server(addr.to_owned(), ctx.clone()).run();
+    let server_end_event = create_public_server(addr.to_owned(), ctx.clone(), None).run();
 
     // wait for stream controller async to start
     sleep(Duration::from_millis(100)).await;
@@ -1818,7 +1818,7 @@ async fn test_stream_fetch_array_map(
     let port = portpicker::pick_unused_port().expect("No free ports left");
     let addr = format!("127.0.0.1:{port}");
 
-    let server_end_event = create_public_server(addr.to_owned(), ctx.clone()).run();
+    let server_end_event = create_public_server(addr.to_owned(), ctx.clone(), None).run();
 
     // wait for stream controller async to start
     sleep(Duration::from_millis(100)).await;
@@ -1940,7 +1940,7 @@ async fn test_stream_fetch_filter_map(
     let port = portpicker::pick_unused_port().expect("No free ports left");
     let addr = format!("127.0.0.1:{port}");
 
-    let server_end_event = create_public_server(addr.to_owned(), ctx.clone()).run();
+    let server_end_event = create_public_server(addr.to_owned(), ctx.clone(), None).run();
 
     // wait for stream controller async to start
     sleep(Duration::from_millis(100)).await;
@@ -2065,7 +2065,7 @@ async fn test_stream_fetch_filter_with_params(
     let port = portpicker::pick_unused_port().expect("No free ports left");
     let addr = format!("127.0.0.1:{port}");
 
-    let server_end_event = create_public_server(addr.to_owned(), ctx.clone()).run();
+    let server_end_event = create_public_server(addr.to_owned(), ctx.clone(), None).run();
 
     // wait for stream controller async to start
     sleep(Duration::from_millis(100)).await;
@@ -2257,7 +2257,7 @@ async fn test_stream_fetch_invalid_smartmodule(
     let port = portpicker::pick_unused_port().expect("No free ports left");
     let addr = format!("127.0.0.1:{port}");
 
-    let server_end_event = create_public_server(addr.to_owned(), ctx.clone()).run();
+    let server_end_event = create_public_server(addr.to_owned(), ctx.clone(), None).run();
 
     // wait for stream controller async to start
     sleep(Duration::from_millis(100)).await;
@@ -2316,7 +2316,7 @@ async fn test_stream_metrics() {
     spu_config.log.base_dir = test_path;
     let ctx = GlobalContext::new_shared_context(spu_config);
 
-    let server_end_event = create_public_server(addr.to_owned(), ctx.clone()).run();
+    let server_end_event = create_public_server(addr.to_owned(), ctx.clone(), None).run();
 
     // wait for stream controller async to start
     sleep(Duration::from_millis(100)).await;
@@ -2494,7 +2494,7 @@ async fn stream_fetch_filter_lookback(
 
     let addr = format!("127.0.0.1:{port}");
 
-    let server_end_event = create_public_server(addr.to_owned(), ctx.clone()).run();
+    let server_end_event = create_public_server(addr.to_owned(), ctx.clone(), None).run();
 
     // wait for stream controller async to start
     sleep(Duration::from_millis(100)).await;
@@ -2655,7 +2655,7 @@ async fn stream_fetch_filter_lookback_age(
 
     let addr = format!("127.0.0.1:{port}");
 
-    let server_end_event = create_public_server(addr.to_owned(), ctx.clone()).run();
+    let server_end_event = create_public_server(addr.to_owned(), ctx.clone(), None).run();
 
     // wait for stream controller async to start
     sleep(Duration::from_millis(100)).await;
@@ -3037,7 +3037,7 @@ async fn test_stream_fetch_sends_topic_delete_error_on_topic_delete() {
     spu_config.log.base_dir = test_path;
     let ctx = GlobalContext::new_shared_context(spu_config);
 
-    let server_end_event = create_public_server(addr.to_owned(), ctx.clone()).run();
+    let server_end_event = create_public_server(addr.to_owned(), ctx.clone(), None).run();
 
     // wait for stream controller async to start
     sleep(Duration::from_millis(100)).await;
@@ -0,0 +1,232 @@
+use std::io::Error as IoError;
+use std::time::Duration;
+
+use tracing::{debug, instrument, warn};
+
+use fluvio::spu::SpuDirectory;
+use fluvio_future::task::spawn;
+use fluvio_protocol::api::{RequestMessage, ResponseMessage};
+use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::record::TxnMarker;
+use fluvio_spu_schema::server::transaction::{
+    InitTransactionRequest, InitTransactionResponse, AddTxnPartitionsRequest,
+    AddTxnPartitionsResponse, EndTransactionRequest, EndTransactionResponse, WriteTxnMarkerRequest,
+    WriteTxnMarkerResponse,
+};
+
+use crate::core::DefaultSharedGlobalContext;
+use crate::core::txn_coordinator::TxnEnd;
+
+use super::consumer_group_handler::ensure_coordinator;
+
+#[instrument(skip(req_msg, ctx))]
+pub(crate) async fn handle_init_transaction_request(
+    req_msg: RequestMessage<InitTransactionRequest>,
+    ctx: DefaultSharedGlobalContext,
+) -> Result<ResponseMessage<InitTransactionResponse>, IoError> {
+    let InitTransactionRequest {
+        transactional_id,
+        timeout_ms,
+    } = &req_msg.request;
+
+    let result = match ensure_coordinator(&ctx).await {
+        Ok(()) => {
+            abort_expired(&ctx).await;
+            let (producer_id, producer_epoch, pending) = ctx
+                .txn_coordinator()
+                .init(transactional_id, Duration::from_millis(*timeout_ms as u64))
+                .await;
+            // previous epoch must be ended before new one writes
+            match pending {
+                Some(pending) => write_txn_markers(&ctx, &pending)
+                    .await
+                    .map(|_| (producer_id, producer_epoch)),
+                None => Ok((producer_id, producer_epoch)),
+            }
+        }
+        Err(error_code) => Err(error_code),
+    };
+    let response = match result {
+        Ok((producer_id, producer_epoch)) => InitTransactionResponse {
+            error_code: ErrorCode::None,
+            producer_id,
+            producer_epoch,
+        },
+        Err(error_code) => InitTransactionResponse {
+            error_code,
+            ..Default::default()
+        },
+    };
+
+    debug!(?response, "init transaction result");
+    Ok(req_msg.new_response(response))
+}
+
+#[instrument(skip(req_msg, ctx))]
+pub(crate) async fn handle_add_txn_partitions_request(
+    req_msg: RequestMessage<AddTxnPartitionsRequest>,
+    ctx: DefaultSharedGlobalContext,
+) -> Result<ResponseMessage<AddTxnPartitionsResponse>, IoError> {
+    let AddTxnPartitionsRequest {
+        transactional_id,
+        producer_id,
+        producer_epoch,
+        partitions,
+    } = &req_msg.request;
+
+    let result = match ensure_coordinator(&ctx).await {
+        Ok(()) => {
+            abort_expired(&ctx).await;
+            ctx.txn_coordinator()
+                .add_partitions(
+                    transactional_id,
+                    *producer_id,
+                    *producer_epoch,
+                    partitions.clone(),
+                )
+                .await
+        }
+        Err(error_code) => Err(error_code),
+    };
+    let error_code = result.err().unwrap_or(ErrorCode::None);
+
+    Ok(req_msg.new_response(AddTxnPartitionsResponse { error_code }))
+}
+
+#[instrument(skip(req_msg, ctx))]
+pub(crate) async fn handle_end_transaction_request(
+    req_msg: RequestMessage<EndTransactionRequest>,
+    ctx: DefaultSharedGlobalContext,
+) -> Result<ResponseMessage<EndTransactionResponse>, IoError> {
+    let EndTransactionRequest {
+        transactional_id,
+        producer_id,
+        producer_epoch,
+        commit,
+    } = &req_msg.request;
+
+    let result = match ensure_coordinator(&ctx).await {
+        Ok(()) => {
+            abort_expired(&ctx).await;
+            match ctx
+                .txn_coordinator()
+                .end(transactional_id, *producer_id, *producer_epoch, *commit)
+                .await
+            {
+                Ok(end) => write_txn_markers(&ctx, &end).await,
+                Err(error_code) => Err(error_code),
+            }
+        }
+        Err(error_code) => Err(error_code),
+    };
+    let error_code = result.err().unwrap_or(ErrorCode::None);
+
+    debug!(?error_code, "end transaction result");
+    Ok(req_msg.new_response(EndTransactionResponse { error_code }))
+}
+
+#[instrument(skip(req_msg, ctx))]
+pub(crate) async fn handle_write_txn_marker_request(
+    req_msg: RequestMessage<WriteTxnMarkerRequest>,
+    ctx: DefaultSharedGlobalContext,
+) -> Result<ResponseMessage<WriteTxnMarkerResponse>, IoError> {
+    let WriteTxnMarkerRequest {
+        replica,
+        producer_id,
+        producer_epoch,
+        commit,
+    } = &req_msg.request;
+
+    let error_code = match ctx.leaders_state().get(replica).await {
+        Some(leader) => match leader
+            .write_txn_marker(
+                *producer_id,
+                *producer_epoch,
+                marker(*commit),
+                ctx.follower_notifier(),
+            )
+            .await
+        {
+            Ok(()) => ErrorCode::None,
+            Err(err) => {
+                warn!(%replica, %err, "failed to write transaction marker");
+                ErrorCode::StorageError
+            }
+        },
+        None => {
+            debug!(%replica, "Replica not found");
+            ErrorCode::NotLeaderForPartition
+        }
+    };
+
+    Ok(req_msg.new_response(WriteTxnMarkerResponse { error_code }))
+}
+
+/// write markers of ended transaction to all of its partitions.
+/// Transaction stays ending if any write fails, so it can be ended again
+async fn write_txn_markers(
+    ctx: &DefaultSharedGlobalContext,
+    end: &TxnEnd,
+) -> Result<(), ErrorCode> {
+    for replica in &end.partitions {
+        let error_code = match ctx.leaders_state().get(replica).await {
+            Some(leader) => match leader
+                .write_txn_marker(
+                    end.producer_id,
+                    end.producer_epoch,
+                    marker(end.commit),
+                    ctx.follower_notifier(),
+                )
+                .await
+            {
+                Ok(()) => ErrorCode::None,
+                Err(err) => ErrorCode::Other(err.to_string()),
+            },
+            None => {
+                let request = WriteTxnMarkerRequest {
+                    replica: replica.clone(),
+                    producer_id: end.producer_id,
+                    producer_epoch: end.producer_epoch,
+                    commit: end.commit,
+                };
+                match ctx.leaders().create_serial_socket(replica).await {
+                    Ok(socket) => match socket.send_receive(request).await {
+                        Ok(response) => response.error_code,
+                        Err(err) => ErrorCode::Other(err.to_string()),
+                    },
+                    Err(err) => ErrorCode::Other(err.to_string()),
+                }
+            }
+        };
+        if error_code.is_error() {
+            warn!(
+                %replica,
+                transactional_id = end.transactional_id,
+                ?error_code,
+                "transaction marker not written"
+            );
+            return Err(error_code);
+        }
+    }
+
+    ctx.txn_coordinator().complete(end).await;
+    Ok(())
+}
+
+/// abort timed out transactions in background
+async fn abort_expired(ctx: &DefaultSharedGlobalContext) {
+    for end in ctx.txn_coordinator().expire().await {
+        let ctx = ctx.clone();
+        spawn(async move {
+            let _ = write_txn_markers(&ctx, &end).await;
+        });
+    }
+}
+
+fn marker(commit: bool) -> TxnMarker {
+    if commit {
+        TxnMarker::Commit
+    } else {
+        TxnMarker::Abort
+    }
+}
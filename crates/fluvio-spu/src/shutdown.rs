@@ -0,0 +1,192 @@
+//!
+//! # Graceful Shutdown
+//!
+//! On SIGTERM or SIGINT, SPU shuts down in steps so rolling restarts don't fail client requests:
+//!
+//! 1. public and private servers stop accepting connections
+//! 2. mirror controllers close their connections to home
+//! 3. in-flight produce and fetch requests complete, waiting fetches return early
+//! 4. active segments of all replicas are synced to disk
+//!
+//! Steps are bounded by a deadline, SPU exits once it passes even if draining is not done.
+//!
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use async_channel::Receiver;
+use tokio::select;
+use tracing::{debug, info, warn};
+
+use fluvio_future::timer::sleep;
+use fluvio_types::event::StickyEvent;
+
+use crate::core::DefaultSharedGlobalContext;
+
+/// how often in-flight requests are checked while draining
+const DRAIN_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// receiver of SIGTERM and SIGINT
+pub(crate) fn termination_signal() -> Result<Receiver<()>> {
+    let (sender, receiver) = async_channel::bounded(1);
+    ctrlc::set_handler(move || {
+        let _ = sender.try_send(());
+    })
+    .map_err(|err| anyhow!("unable to install termination handler: {err}"))?;
+    Ok(receiver)
+}
+
+pub(crate) struct ShutdownCoordinator {
+    ctx: DefaultSharedGlobalContext,
+    servers: Vec<Arc<StickyEvent>>,
+    deadline: Duration,
+}
+
+impl ShutdownCoordinator {
+    pub(crate) fn new(ctx: DefaultSharedGlobalContext) -> Self {
+        let deadline = Duration::from_secs(ctx.config().shutdown_timeout_secs);
+        Self {
+            ctx,
+            servers: vec![],
+            deadline,
+        }
+    }
+
+    /// server whose listener is closed on shutdown
+    pub(crate) fn add_server(&mut self, shutdown: Arc<StickyEvent>) {
+        self.servers.push(shutdown);
+    }
+
+    /// shut down services, returns once done or deadline has passed
+    pub(crate) async fn shutdown(self) {
+        info!(deadline = ?self.deadline, "shutting down");
+        select! {
+            _ = self.drain() => {
+                info!("shutdown completed");
+            },
+            _ = sleep(self.deadline) => {
+                warn!(
+                    in_flight = self.ctx.admission().active(),
+                    "shutdown deadline passed, exiting"
+                );
+            }
+        }
+    }
+
+    async fn drain(&self) {
+        for server in &self.servers {
+            server.notify();
+        }
+        // wakes up waiting fetches and mirror controllers
+        self.ctx.shutdown().notify();
+        debug!("stopped accepting connections");
+
+        self.wait_in_flight().await;
+        self.sync_replicas().await;
+    }
+
+    async fn wait_in_flight(&self) {
+        loop {
+            let admission = self.ctx.admission();
+            let in_flight = admission.active() + admission.queued();
+            if in_flight == 0 {
+                debug!("in-flight requests drained");
+                return;
+            }
+            debug!(in_flight, "waiting for in-flight requests");
+            sleep(DRAIN_POLL_INTERVAL).await;
+        }
+    }
+
+    /// sync records not yet synced by flush policy of replicas
+    async fn sync_replicas(&self) {
+        let leaders: Vec<_> = self
+            .ctx
+            .leaders_state()
+            .read()
+            .await
+            .values()
+            .cloned()
+            .collect();
+        for leader in leaders {
+            if let Err(err) = leader.write().await.sync().await {
+                warn!(replica = %leader.id(), %err, "unable to sync leader replica");
+            }
+        }
+
+        let followers: Vec<_> = self
+            .ctx
+            .followers_state()
+            .read()
+            .await
+            .iter()
+            .map(|(key, follower)| (key.clone(), follower.clone()))
+            .collect();
+        for (key, follower) in followers {
+            if let Err(err) = follower.write().await.sync().await {
+                warn!(replica = %key, %err, "unable to sync follower replica");
+            }
+        }
+        debug!("replicas synced");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use fluvio_future::task::spawn;
+
+    use crate::config::SpuConfig;
+    use crate::core::GlobalContext;
+
+    use super::*;
+
+    fn context(shutdown_timeout_secs: u64) -> DefaultSharedGlobalContext {
+        let spu_config = SpuConfig {
+            shutdown_timeout_secs,
+            ..Default::default()
+        };
+        GlobalContext::new_shared_context(spu_config)
+    }
+
+    #[fluvio_future::test]
+    async fn test_shutdown_drains_in_flight_requests() {
+        let ctx = context(10);
+        let server = StickyEvent::shared();
+        let mut coordinator = ShutdownCoordinator::new(ctx.clone());
+        coordinator.add_server(server.clone());
+
+        let permit = ctx.admission().admit().await.expect("admitted");
+        spawn(async move {
+            sleep(Duration::from_millis(200)).await;
+            drop(permit);
+        });
+
+        let start = Instant::now();
+        coordinator.shutdown().await;
+        let elapsed = start.elapsed();
+
+        assert!(server.is_set());
+        assert!(ctx.shutdown().is_set());
+        assert_eq!(ctx.admission().active(), 0);
+        assert!(elapsed >= Duration::from_millis(200));
+        assert!(elapsed < Duration::from_secs(10));
+    }
+
+    #[fluvio_future::test]
+    async fn test_shutdown_exits_at_deadline() {
+        let ctx = context(1);
+        let coordinator = ShutdownCoordinator::new(ctx.clone());
+
+        // never completes
+        let _permit = ctx.admission().admit().await.expect("admitted");
+
+        let start = Instant::now();
+        coordinator.shutdown().await;
+
+        assert!(start.elapsed() >= Duration::from_secs(1));
+        assert!(ctx.shutdown().is_set());
+        assert_eq!(ctx.admission().active(), 1);
+    }
+}
@@ -13,7 +13,8 @@ use tracing::{debug, trace, error};
 
 use crate::core::GlobalContext;
 use crate::core::metrics::SpuMetrics;
-use crate::replication::leader::LeaderReplicaState;
+use crate::core::memory::{MemoryKind, MemoryPermit};
+use crate::storage::SharableReplicaStorage;
 
 use crate::smartengine::chain;
 use crate::smartengine::Lookback;
@@ -26,6 +27,8 @@ pub struct SmartModuleContext {
     chain: SmartModuleChainInstance,
     version: Version,
     spu_metrics: Arc<SpuMetrics>,
+    /// store memory of chain, released with context
+    _memory: MemoryPermit,
 }
 
 pub type SharedSmartModuleContext = Arc<RwLock<SmartModuleContext>>;
@@ -45,7 +48,7 @@ impl SmartModuleContext {
 
     pub async fn look_back<R: ReplicaStorage>(
         &mut self,
-        replica: &LeaderReplicaState<R>,
+        replica: &SharableReplicaStorage<R>,
     ) -> Result<(), ErrorCode> {
         self.chain
             .look_back(
@@ -73,8 +76,18 @@ impl SmartModuleContext {
         for invocation in invocations {
             fetched_invocations.push(resolve_invocation(invocation, ctx)?)
         }
+        let store_max_memory = ctx.config().smart_engine.store_max_memory;
+        let Some(memory) = ctx
+            .memory()
+            .try_acquire(MemoryKind::SmartModule, store_max_memory as u64)
+        else {
+            debug!(store_max_memory, "no memory left for SmartModule chain");
+            return Err(ErrorCode::SpuMemoryBudgetExceeded {
+                requested: store_max_memory as u64,
+            });
+        };
         let mut chain_builder = SmartModuleChainBuilder::default();
-        chain_builder.set_store_memory_limit(ctx.config().smart_engine.store_max_memory);
+        chain_builder.set_store_memory_limit(store_max_memory);
 
         let chain = chain::build_chain(
             chain_builder,
@@ -87,6 +100,7 @@ impl SmartModuleContext {
             chain,
             version,
             spu_metrics: ctx.metrics(),
+            _memory: memory,
         }))
     }
 }
@@ -114,7 +128,7 @@ fn resolve_invocation<R: ReplicaStorage>(
 }
 
 async fn read_records<R: ReplicaStorage>(
-    replica: &LeaderReplicaState<R>,
+    replica: &SharableReplicaStorage<R>,
     lookback: Lookback,
     version: Version,
 ) -> anyhow::Result<Vec<Record>> {
@@ -127,7 +141,7 @@ async fn read_records<R: ReplicaStorage>(
 }
 
 async fn lookback_iterator<R: ReplicaStorage>(
-    replica: &LeaderReplicaState<R>,
+    replica: &SharableReplicaStorage<R>,
     lookback: Lookback,
     version: Version,
 ) -> anyhow::Result<Box<dyn Iterator<Item = Result<Record, std::io::Error>>>> {
@@ -140,7 +154,7 @@ async fn lookback_iterator<R: ReplicaStorage>(
 }
 
 async fn lookback_last_iterator<R: ReplicaStorage>(
-    replica: &LeaderReplicaState<R>,
+    replica: &SharableReplicaStorage<R>,
     last: u64,
     version: Version,
 ) -> anyhow::Result<Box<dyn Iterator<Item = Result<RecordItem, std::io::Error>>>> {
@@ -160,7 +174,7 @@ async fn lookback_last_iterator<R: ReplicaStorage>(
         return Ok(Box::new(std::iter::empty()));
     };
 
-    let batch_iter = FileBatchIterator::from_raw_slice(file_slice);
+    let batch_iter = FileBatchIterator::from_raw_slice(file_slice).cipher(replica.cipher());
     let records_iter = FileRecordIterator::new(batch_iter, version);
 
     Ok(Box::new(records_iter.filter(move |r| match r {
@@ -170,7 +184,7 @@ async fn lookback_last_iterator<R: ReplicaStorage>(
 }
 
 async fn lookback_age_iterator<R: ReplicaStorage>(
-    replica: &LeaderReplicaState<R>,
+    replica: &SharableReplicaStorage<R>,
     age: Duration,
     last: u64,
     version: Version,
@@ -198,7 +212,7 @@ async fn lookback_age_iterator<R: ReplicaStorage>(
 }
 
 async fn read_batches_by_age<R: ReplicaStorage>(
-    replica: &LeaderReplicaState<R>,
+    replica: &SharableReplicaStorage<R>,
     min_timestamp: Timestamp,
 ) -> anyhow::Result<Vec<FileBatch>> {
     let mut result = Vec::new();
@@ -215,7 +229,7 @@ async fn read_batches_by_age<R: ReplicaStorage>(
             trace!(?slice);
             break;
         };
-        let mut batch_iter = FileBatchIterator::from_raw_slice(file_slice);
+        let mut batch_iter = FileBatchIterator::from_raw_slice(file_slice).cipher(replica.cipher());
         let Some(batch) = batch_iter.next() else {
             break;
         };
@@ -1,4 +1,5 @@
 use fluvio_storage::FileReplica;
+use fluvio_auth::token::SharedTokenValidator;
 
 use crate::config::{SpuConfig, SpuOpt};
 use crate::services::create_internal_server;
@@ -13,18 +14,22 @@ type FileReplicaContext = GlobalContext<FileReplica>;
 const VERSION: &str = env!("CARGO_PKG_VERSION");
 
 pub fn main_loop(opt: SpuOpt) {
-    use std::time::Duration;
-
     use sysinfo::{System, SystemExt};
     use tracing::info;
 
-    use fluvio_future::task::run_block_on;
-    use fluvio_future::timer::sleep;
+    use flv_util::print_cli_err;
+    use fluvio_future::task::{run_block_on, spawn};
+    use fluvio_service::profiling::start_profiling;
 
     use crate::monitoring::init_monitoring;
+    use crate::storage::StorageScrubber;
+    use crate::kv::ConsumerOffsetExpiry;
+    use crate::core::events::ClusterEventPublisher;
+    use crate::core::dynamic_config::DYNAMIC_CONFIG_INTERVAL;
+    use crate::shutdown::{termination_signal, ShutdownCoordinator};
 
     // parse configuration (program exits on error)
-    let (spu_config, tls_acceptor_option) = opt.process_spu_cli_or_exit();
+    let (spu_config, token_validator, tls_acceptor_option) = opt.process_spu_cli_or_exit();
 
     println!("starting spu server (id:{})", spu_config.id);
 
@@ -41,29 +46,47 @@ pub fn main_loop(opt: SpuOpt) {
     info!(uptime = sys.uptime(), "Uptime in secs");
 
     run_block_on(async move {
-        let (ctx, internal_server, public_server) = create_services(spu_config.clone(), true, true);
-
-        let _public_shutdown = internal_server.unwrap().run();
-        let _private_shutdown = public_server.unwrap().run();
-
-        init_monitoring(ctx);
+        let (ctx, internal_server, public_server) =
+            create_services(spu_config.clone(), token_validator, true, true);
+
+        let termination = match termination_signal() {
+            Ok(termination) => termination,
+            Err(err) => {
+                print_cli_err!(err);
+                std::process::exit(-1);
+            }
+        };
+
+        let mut coordinator = ShutdownCoordinator::new(ctx.clone());
+        coordinator.add_server(internal_server.unwrap().run());
+        coordinator.add_server(public_server.unwrap().run());
+
+        StorageScrubber::start(ctx.clone());
+        ConsumerOffsetExpiry::start(ctx.clone());
+        ClusterEventPublisher::start(ctx.clone());
+        ctx.dynamic_config().watch(DYNAMIC_CONFIG_INTERVAL);
+        init_monitoring(ctx.clone());
+        if let Some(addr) = &spu_config.profiling_endpoint {
+            start_profiling(addr);
+        }
 
         if let Some(tls_config) = tls_acceptor_option {
-            proxy::start_proxy(spu_config, tls_config).await;
+            // proxy keeps accepting until process exits
+            spawn(proxy::start_proxy(spu_config, tls_config));
         }
 
         println!("SPU Version: {VERSION} started successfully");
 
-        // infinite loop
-        loop {
-            sleep(Duration::from_secs(60)).await;
-        }
+        // run until terminated
+        let _ = termination.recv().await;
+        coordinator.shutdown().await;
     });
 }
 
 /// create server and spin up services, but don't run server
 pub fn create_services(
     local_spu: SpuConfig,
+    token_validator: Option<SharedTokenValidator>,
     internal: bool,
     public: bool,
 ) -> (
@@ -77,7 +100,11 @@ pub fn create_services(
     let private_ep_addr = ctx.config().private_socket_addr().to_owned();
 
     let public_server = if public {
-        Some(create_public_server(public_ep_addr, ctx.clone()))
+        Some(create_public_server(
+            public_ep_addr,
+            ctx.clone(),
+            token_validator,
+        ))
     } else {
         None
     };
@@ -101,16 +128,17 @@ mod proxy {
     use tracing::info;
 
     use flv_util::print_cli_err;
-    use fluvio_future::openssl::TlsAcceptor;
+    use fluvio_auth::tls::{start_proxy as proxy_start, SharedTlsAcceptor, CERT_RELOAD_INTERVAL};
     use crate::config::SpuConfig;
-    use flv_tls_proxy::start as proxy_start;
 
-    pub async fn start_proxy(config: SpuConfig, acceptor: (TlsAcceptor, String)) {
+    pub async fn start_proxy(config: SpuConfig, acceptor: (SharedTlsAcceptor, String)) {
         let (tls_acceptor, proxy_addr) = acceptor;
         let target = config.public_endpoint;
         info!("starting TLS proxy: {}", proxy_addr);
 
-        if let Err(err) = proxy_start(&proxy_addr, tls_acceptor, target).await {
+        // mirror connections from remote clusters are served by the same proxy
+        tls_acceptor.watch(CERT_RELOAD_INTERVAL);
+        if let Err(err) = proxy_start(&proxy_addr, tls_acceptor, target, None).await {
             print_cli_err!(err);
             process::exit(-1);
         } else {
@@ -0,0 +1,101 @@
+//!
+//! # Group Commit
+//!
+//! With batch durability, records are synced before they are acknowledged. When storage defers
+//! sync, writers append under replica write lock and sync after releasing it. Only one sync runs
+//! at a time; writers waiting for it are covered by the next one, which includes all records
+//! appended meanwhile, so concurrent small produces to the same partition share one fsync.
+//!
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use async_lock::Mutex;
+use anyhow::Result;
+
+#[derive(Debug, Default)]
+pub(crate) struct GroupCommit {
+    /// writes appended, each write gets its count as ticket
+    appended: AtomicU64,
+    /// tickets covered by completed syncs
+    synced: AtomicU64,
+    /// held by writer doing sync
+    syncing: Mutex<()>,
+    syncs: AtomicU64,
+}
+
+impl GroupCommit {
+    /// record append, must be called while holding replica write lock
+    pub(crate) fn append(&self) -> u64 {
+        self.appended.fetch_add(1, Ordering::SeqCst) + 1
+    }
+
+    /// number of syncs done
+    #[cfg(test)]
+    pub(crate) fn syncs(&self) -> u64 {
+        self.syncs.load(Ordering::SeqCst)
+    }
+
+    /// wait until write of `ticket` is synced, `sync` syncs all records appended before it is called
+    pub(crate) async fn commit<F, Fut>(&self, ticket: u64, sync: F) -> Result<()>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<()>>,
+    {
+        if self.synced.load(Ordering::SeqCst) >= ticket {
+            return Ok(());
+        }
+        let _syncing = self.syncing.lock().await;
+        // sync done while waiting may have covered this write
+        if self.synced.load(Ordering::SeqCst) >= ticket {
+            return Ok(());
+        }
+        let appended = self.appended.load(Ordering::SeqCst);
+        sync().await?;
+        self.synced.fetch_max(appended, Ordering::SeqCst);
+        self.syncs.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use futures_util::future::join_all;
+
+    use fluvio_future::timer::sleep;
+
+    use super::*;
+
+    #[fluvio_future::test]
+    async fn test_concurrent_writes_share_sync() {
+        let group = Arc::new(GroupCommit::default());
+        let tickets: Vec<_> = (0..5).map(|_| group.append()).collect();
+
+        join_all(tickets.into_iter().map(|ticket| {
+            let group = group.clone();
+            async move {
+                group
+                    .commit(ticket, || async {
+                        sleep(Duration::from_millis(20)).await;
+                        Ok(())
+                    })
+                    .await
+            }
+        }))
+        .await
+        .into_iter()
+        .collect::<Result<Vec<_>>>()
+        .expect("commit");
+        assert_eq!(group.syncs(), 1);
+
+        // later write needs own sync
+        let ticket = group.append();
+        group
+            .commit(ticket, || async { Ok(()) })
+            .await
+            .expect("commit");
+        assert_eq!(group.syncs(), 2);
+    }
+}
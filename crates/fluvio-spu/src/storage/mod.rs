@@ -1,21 +1,49 @@
+mod scrubber;
+mod preload;
+mod scheduled;
+mod txn;
+mod visible;
+mod timeline;
+mod group_commit;
+
+pub(crate) use self::scrubber::StorageScrubber;
+pub(crate) use self::preload::SegmentPreloader;
+
+use std::io::Error as IoError;
 use std::sync::Arc;
 use std::fmt::Debug;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
+use chrono::Utc;
 use tracing::{debug, instrument};
 use async_rwlock::{RwLock, RwLockReadGuard, RwLockWriteGuard};
 use anyhow::Result;
 
 use fluvio_protocol::record::BatchRecords;
-use fluvio_controlplane_metadata::partition::ReplicaKey;
+use fluvio_controlplane_metadata::partition::{ReplicaKey, StorageUsage};
 use fluvio_spu_schema::Isolation;
+use fluvio_spu_schema::fetch::AbortedTransaction;
 use fluvio_protocol::Encoder;
-use fluvio_protocol::record::{Offset, RecordSet};
+use fluvio_protocol::record::{Batch, Offset, RawRecords, RecordSet, BATCH_HEADER_SIZE, NO_TIMESTAMP};
+use fluvio_compression::Compression;
 use fluvio_protocol::link::ErrorCode;
+use fluvio_future::task::spawn;
+use fluvio_future::timer::sleep;
+use fluvio_future::file_slice::AsyncFileSlice;
 use fluvio_storage::{ReplicaStorage, StorageError, OffsetInfo, ReplicaSlice};
+use fluvio_storage::iterators::FileBatchIterator;
+use fluvio_storage::encryption::BatchCipher;
 use fluvio_types::event::offsets::OffsetChangeListener;
 use fluvio_types::event::offsets::OffsetPublisher;
 
+use crate::core::metrics::SpuMetrics;
+
+use self::visible::VisibleOffsets;
+use self::timeline::AppendTimeline;
+use self::group_commit::GroupCommit;
+
+pub(crate) use self::timeline::AppendTimes;
+
 pub const REMOVAL_START: Offset = -1000; // indicate that storage about to be removed
 pub const REMOVAL_END: Offset = -1001; // indicate the storage has been removed
 
@@ -26,6 +54,16 @@ pub struct SharableReplicaStorage<S> {
     inner: Arc<RwLock<S>>,
     leo: Arc<OffsetPublisher>,
     hw: Arc<OffsetPublisher>,
+    /// offsets visible to consumers, held back by scheduled records and open transactions
+    visible: Arc<VisibleOffsets>,
+    /// cipher of encrypted topic, records are stored encrypted and decrypted on read
+    cipher: Option<Arc<BatchCipher>>,
+    /// broker times of recently appended batches
+    timeline: Arc<AppendTimeline>,
+    /// latency histograms, only set for leader replicas
+    metrics: Option<Arc<SpuMetrics>>,
+    /// syncs of concurrent writes, used when storage defers sync
+    group_commit: Arc<GroupCommit>,
 }
 
 impl<S> Clone for SharableReplicaStorage<S> {
@@ -35,6 +73,11 @@ impl<S> Clone for SharableReplicaStorage<S> {
             inner: self.inner.clone(),
             leo: self.leo.clone(),
             hw: self.hw.clone(),
+            visible: self.visible.clone(),
+            cipher: self.cipher.clone(),
+            timeline: self.timeline.clone(),
+            metrics: self.metrics.clone(),
+            group_commit: self.group_commit.clone(),
         }
     }
 }
@@ -49,18 +92,91 @@ where
 
         let leo = Arc::new(OffsetPublisher::new(storage.get_leo()));
         let hw = Arc::new(OffsetPublisher::new(storage.get_hw()));
-        Ok(Self {
+        let visible = Arc::new(VisibleOffsets::new(storage.get_hw(), storage.get_leo()));
+        let replica = Self {
             id,
             inner: Arc::new(RwLock::new(storage)),
             leo,
             hw,
-        })
+            visible,
+            cipher: None,
+            timeline: Arc::new(AppendTimeline::default()),
+            metrics: None,
+            group_commit: Arc::new(GroupCommit::default()),
+        };
+        replica.load_txn_state().await?;
+        Ok(replica)
+    }
+
+    /// rebuild transaction index from transactional batches stored in the log
+    async fn load_txn_state(&self) -> Result<()> {
+        let (mut offset, _) = self.start_offset_info().await;
+        let leo = self.leo();
+        while offset < leo {
+            let slice = self
+                .read_records(offset, u32::MAX, Isolation::ReadUncommitted)
+                .await?;
+            let Some(file_slice) = slice.file_slice else {
+                break;
+            };
+            let mut next_offset = offset;
+            for file_batch in FileBatchIterator::from_raw_slice(file_slice) {
+                let batch = file_batch?.batch;
+                if batch.header.is_transactional() {
+                    self.visible
+                        .add_batch(&batch.header, batch.base_offset)
+                        .await;
+                }
+                next_offset = batch.get_last_offset() + 1;
+            }
+            if next_offset <= offset {
+                break;
+            }
+            offset = next_offset;
+        }
+        self.visible.update(self.hw(), leo).await;
+        debug!(replica = %self.id, offset, "transaction state loaded");
+        Ok(())
     }
 
     pub fn id(&self) -> &ReplicaKey {
         &self.id
     }
 
+    /// cipher of encrypted topic, None if records are stored in clear
+    pub fn cipher(&self) -> Option<Arc<BatchCipher>> {
+        self.cipher.clone()
+    }
+
+    /// set before storage is shared, clones made earlier keep previous cipher
+    pub fn set_cipher(&mut self, cipher: Option<Arc<BatchCipher>>) {
+        self.cipher = cipher;
+    }
+
+    /// set before storage is shared, appends and commits are then recorded in latency metrics
+    pub(crate) fn set_metrics(&mut self, metrics: Arc<SpuMetrics>) {
+        self.metrics = Some(metrics);
+    }
+
+    /// broker times of batch containing `offset`, None if it's no longer tracked
+    pub(crate) async fn append_times(&self, offset: Offset) -> Option<AppendTimes> {
+        self.timeline.get(offset).await
+    }
+
+    /// record commit of batches before `hw`
+    async fn record_commit(&self, hw: Offset) {
+        let now = Utc::now().timestamp_millis();
+        let append_times = self.timeline.commit(hw, now).await;
+        if let Some(metrics) = &self.metrics {
+            for append_time in append_times {
+                metrics
+                    .latency()
+                    .append_to_commit()
+                    .record_between(append_time, now);
+            }
+        }
+    }
+
     /// log end offset
     pub fn leo(&self) -> Offset {
         self.leo.current_value()
@@ -86,6 +202,11 @@ where
         }
     }
 
+    /// listen to offset visible to consumers based on isolation
+    pub fn visible_offset_listener(&self, isolation: &Isolation) -> OffsetChangeListener {
+        self.visible.listener(isolation)
+    }
+
     /// readable ref to storage
     pub async fn read(&self) -> RwLockReadGuard<'_, S> {
         self.inner.read().await
@@ -118,16 +239,88 @@ where
             .await
     }
 
+    /// read records visible to consumers, scheduled records which are not yet due,
+    /// records of open transactions for read committed and records after them are excluded
+    #[instrument(skip(self, offset, max_len, isolation))]
+    pub async fn read_visible_records(
+        &self,
+        offset: Offset,
+        max_len: u32,
+        isolation: Isolation,
+    ) -> Result<ReplicaSlice, ErrorCode> {
+        let read_storage = self.read().await;
+
+        // deleted records may still be in storage
+        let log_start = read_storage.get_log_start_offset();
+        if offset < log_start {
+            return Err(ErrorCode::OffsetEvicted {
+                offset,
+                next_available: log_start,
+            });
+        }
+
+        let end = match isolation {
+            Isolation::ReadCommitted => read_storage.get_hw(),
+            Isolation::ReadUncommitted => read_storage.get_leo(),
+        };
+        let visible_end = match self.visible.end(&isolation).await {
+            Some(visible_end) if visible_end < end => visible_end,
+            _ => {
+                return read_storage
+                    .read_partition_slice(offset, max_len, isolation)
+                    .await
+            }
+        };
+
+        let mut slice = if offset < visible_end {
+            read_storage
+                .read_partition_slice_before(offset, visible_end, max_len)
+                .await?
+        } else {
+            ReplicaSlice {
+                start: log_start,
+                end: OffsetInfo {
+                    hw: read_storage.get_hw(),
+                    leo: read_storage.get_leo(),
+                },
+                ..Default::default()
+            }
+        };
+        slice.end.hw = slice.end.hw.min(visible_end);
+        slice.end.leo = slice.end.leo.min(visible_end);
+        Ok(slice)
+    }
+
+    /// aborted transactions with records between `start` and `end`
+    pub async fn aborted_txns(&self, start: Offset, end: Offset) -> Vec<AbortedTransaction> {
+        self.visible.aborted_txns(start, end).await
+    }
+
+    /// true if producer has open transaction in this replica
+    pub async fn is_txn_ongoing(&self, producer_id: i64) -> bool {
+        self.visible.is_txn_ongoing(producer_id).await
+    }
+
     pub async fn update_hw(&self, hw: Offset) -> Result<bool, StorageError> {
         let mut writer = self.write().await;
         if writer.update_high_watermark(hw).await? {
             self.hw.update(hw);
+            self.visible.update(hw, self.leo()).await;
+            self.record_commit(hw).await;
             Ok(true)
         } else {
             Ok(false)
         }
     }
 
+    /// delete committed records before `offset`, returns new log start offset
+    pub async fn delete_records_before(&self, offset: Offset) -> Result<Offset, StorageError> {
+        let mut writer = self.write().await;
+        let log_start = writer.delete_records_before(offset).await?;
+        self.visible.truncate_before(log_start).await;
+        Ok(log_start)
+    }
+
     #[instrument(skip(self, records, hw_update))]
     pub async fn write_record_set<R: BatchRecords>(
         &self,
@@ -144,31 +337,152 @@ where
         );
 
         let mut writer = self.write().await;
+        // deferred sync is done after releasing writer, records are committed once synced
+        let deferred_sync = writer.log_sync().is_some();
+        let commit_now = hw_update && !deferred_sync;
 
         let base_offset = writer.get_leo();
 
         let now = Instant::now();
-        let bytes_written = writer.write_recordset(records, hw_update).await?;
+        let bytes_written = writer.write_recordset(records, commit_now).await?;
+        let ticket = self.group_commit.append();
         debug!(write_time_ms = %now.elapsed().as_millis());
 
         let leo = writer.get_leo();
         debug!(leo, "updated leo");
         self.leo.update(leo);
-        if hw_update {
+        if commit_now {
             let hw = writer.get_hw();
             debug!(hw, "updated hw");
             self.hw.update(hw);
         }
 
+        let appended_at = Utc::now().timestamp_millis();
+        for batch in &records.batches {
+            self.timeline
+                .append(
+                    batch.base_offset,
+                    batch.get_last_offset() + 1,
+                    appended_at,
+                    commit_now,
+                )
+                .await;
+            if let Some(metrics) = &self.metrics {
+                let latency = metrics.latency();
+                let produced_at = batch.header.max_time_stamp;
+                if produced_at != NO_TIMESTAMP {
+                    latency
+                        .produce_to_append()
+                        .record_between(produced_at, appended_at);
+                }
+                if commit_now {
+                    latency.append_to_commit().record(0);
+                }
+            }
+            if let Some(visible_at) = self
+                .visible
+                .add_batch(&batch.header, batch.base_offset)
+                .await
+            {
+                debug!(
+                    base_offset = batch.base_offset,
+                    visible_at, "scheduled batch"
+                );
+                self.release_at(visible_at - Utc::now().timestamp_millis());
+            }
+        }
+        self.visible
+            .truncate_before(writer.get_log_start_offset())
+            .await;
+        self.visible.update(self.hw(), leo).await;
+        drop(writer);
+
+        if deferred_sync {
+            self.commit_synced(ticket, leo, hw_update).await?;
+        }
+
         Ok((base_offset, leo, bytes_written))
     }
 
+    /// sync records of this write together with concurrent ones,
+    /// then advance high watermark to them if it follows leo
+    async fn commit_synced(&self, ticket: u64, leo: Offset, hw_update: bool) -> Result<()> {
+        self.group_commit
+            .commit(ticket, || async {
+                let log_sync = self.read().await.log_sync();
+                if let Some(log_sync) = log_sync {
+                    log_sync.sync().await?;
+                }
+                Ok(())
+            })
+            .await?;
+        if hw_update {
+            let mut writer = self.write().await;
+            // concurrent write may have committed further already
+            if leo > writer.get_hw() && writer.update_high_watermark(leo).await? {
+                self.hw.update(leo);
+                self.visible.update(leo, self.leo()).await;
+                self.record_commit(leo).await;
+            }
+        }
+        Ok(())
+    }
+
+    /// recompute visible offsets once scheduled batch is due
+    fn release_at(&self, delay_ms: i64) {
+        let delay_ms = delay_ms.max(0);
+        let visible = self.visible.clone();
+        let hw = self.hw.clone();
+        let leo = self.leo.clone();
+        spawn(async move {
+            sleep(Duration::from_millis(delay_ms as u64)).await;
+            visible
+                .update(hw.current_value(), leo.current_value())
+                .await;
+        });
+    }
+
+    /// disk usage of replica, leader replicas record it in storage metrics
+    pub(crate) async fn storage_usage(&self) -> StorageUsage {
+        let usage = self.read().await.get_storage_usage();
+        if let Some(metrics) = &self.metrics {
+            metrics.storage().update(&self.id, usage);
+        }
+        usage
+    }
+
     /// perform permanent remove
     pub async fn remove(&self) -> Result<(), StorageError> {
+        if let Some(metrics) = &self.metrics {
+            metrics.storage().remove(&self.id);
+        }
         self.leo.update(REMOVAL_START);
+        self.visible.update(self.hw(), REMOVAL_START).await;
         let writer = self.write().await;
         writer.remove().await?;
         self.leo.update(REMOVAL_END);
+        self.visible.update(self.hw(), REMOVAL_END).await;
         Ok(())
     }
 }
+
+/// read batches of file slice into memory, records of encrypted batches are decrypted by cipher.
+/// Records of encrypted replicas can't be sent as file slices, they are sent uncompressed
+pub(crate) fn read_slice(
+    slice: AsyncFileSlice,
+    cipher: Option<Arc<BatchCipher>>,
+) -> Result<RecordSet<RawRecords>, IoError> {
+    let mut records = RecordSet::default();
+    for file_batch in FileBatchIterator::from_raw_slice(slice).cipher(cipher) {
+        let file_batch = file_batch?;
+        let mut header = file_batch.batch.header;
+        header.set_compression(Compression::None);
+        let mut batch = Batch::<RawRecords>::new();
+        batch.base_offset = file_batch.batch.base_offset;
+        batch.batch_len = (BATCH_HEADER_SIZE + file_batch.records.len()) as i32;
+        batch.header = header;
+        *batch.mut_records() = RawRecords(file_batch.records.into());
+        records.batches.push(batch);
+    }
+    Ok(records)
+}
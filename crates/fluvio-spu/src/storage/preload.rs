@@ -0,0 +1,45 @@
+//!
+//! # Segment Preloader
+//!
+//! After restart, first fetches of a partition would read its tail from disk.
+//! When enabled, tail of each leader replica loaded by this SPU is hinted into page cache
+//! in background, newest segments first, up to configured bytes per replica.
+//! Followers are not preloaded, they are only written to until promoted.
+//!
+use tracing::{debug, info, warn};
+
+use fluvio_future::task::spawn;
+use fluvio_storage::FileReplica;
+use fluvio_storage::preload::preload;
+
+use crate::replication::leader::LeaderReplicaState;
+
+pub(crate) struct SegmentPreloader;
+
+impl SegmentPreloader {
+    /// preload tail of leader replica, does nothing if `max_bytes` is 0
+    pub(crate) fn start(leader: &LeaderReplicaState<FileReplica>, max_bytes: u64) {
+        if max_bytes == 0 {
+            return;
+        }
+
+        let leader = leader.clone();
+        spawn(async move {
+            let targets = leader.read().await.preload_targets(max_bytes).await;
+            let mut bytes = 0;
+            for target in &targets {
+                match preload(target).await {
+                    Ok(len) => bytes += len,
+                    Err(err) => {
+                        warn!(replica = %leader.id(), path = ?target.path, %err, "segment preload failed");
+                    }
+                }
+            }
+            if bytes > 0 {
+                info!(replica = %leader.id(), bytes, "preloaded replica tail");
+            } else {
+                debug!(replica = %leader.id(), "nothing to preload");
+            }
+        });
+    }
+}
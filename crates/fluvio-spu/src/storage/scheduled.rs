@@ -0,0 +1,63 @@
+//!
+//! # Scheduled Batches
+//!
+//! Tracks stored batches with scheduled records which are not yet due.
+//! Batch becomes visible to consumers after its max timestamp, records are released
+//! in offset order: pending batch holds back itself and every record after it.
+//!
+//! Pending batches are kept in memory only. When replica is reloaded, its scheduled
+//! records are visible right away.
+//!
+use std::collections::BTreeMap;
+
+use async_lock::Mutex;
+
+use fluvio_protocol::record::Offset;
+use fluvio_types::Timestamp;
+
+#[derive(Debug, Default)]
+pub(super) struct ScheduledBatches {
+    /// base offset of batch to time it becomes visible
+    pending: Mutex<BTreeMap<Offset, Timestamp>>,
+}
+
+impl ScheduledBatches {
+    /// add batch starting at `base_offset` which is visible at `visible_at`
+    pub(super) async fn add(&self, base_offset: Offset, visible_at: Timestamp) {
+        self.pending.lock().await.insert(base_offset, visible_at);
+    }
+
+    /// offset of first batch not visible at `now`, None if all batches are visible.
+    /// Due batches at the front are dropped
+    pub(super) async fn visible_end(&self, now: Timestamp) -> Option<Offset> {
+        let mut pending = self.pending.lock().await;
+        while let Some(entry) = pending.first_entry() {
+            if *entry.get() > now {
+                return Some(*entry.key());
+            }
+            entry.remove();
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[fluvio_future::test]
+    async fn test_visible_end() {
+        let scheduled = ScheduledBatches::default();
+        assert_eq!(scheduled.visible_end(1000).await, None);
+
+        scheduled.add(10, 2000).await;
+        scheduled.add(20, 1500).await;
+        scheduled.add(30, 3000).await;
+
+        assert_eq!(scheduled.visible_end(1000).await, Some(10));
+        // later batch is due but still held back by earlier one
+        assert_eq!(scheduled.visible_end(1600).await, Some(10));
+        assert_eq!(scheduled.visible_end(2000).await, Some(30));
+        assert_eq!(scheduled.visible_end(3000).await, None);
+    }
+}
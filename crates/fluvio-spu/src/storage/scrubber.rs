@@ -0,0 +1,167 @@
+//!
+//! # Storage Scrubber
+//!
+//! Periodically re-validates checksums of closed segments of all replicas hosted by this SPU.
+//! Segments are scrubbed one at a time with a pause in between, so scrubbing doesn't compete
+//! with produce and fetch. Active segment is never scrubbed.
+//!
+//! Corrupt ranges found on a leader are reported to SC as part of the partition status.
+//! Corrupt follower is dropped and replicated again from its leader.
+//!
+//! Digests of scrubbed segments of leaders and followers are reported to SC, which compares
+//! them to detect followers silently diverging from leader. Encrypted replicas are not reported
+//! since each SPU encrypts records with its own nonces.
+//!
+use std::time::Duration;
+
+use tracing::{debug, error, info, warn};
+
+use fluvio_future::task::spawn;
+use fluvio_future::timer::sleep;
+use fluvio_controlplane::sc_api::update_lrs::ReplicaDigestsRequest;
+use fluvio_controlplane_metadata::partition::{CorruptRange, ReplicaDigests, ReplicaKey, SegmentDigest};
+use fluvio_storage::scrubber::{ScrubTarget, scrub_segment};
+
+use crate::core::DefaultSharedGlobalContext;
+
+/// pause between scrubbing of two segments
+const SEGMENT_PAUSE: Duration = Duration::from_millis(100);
+
+/// digests of at most this many most recent segments are reported
+const MAX_REPORTED_DIGESTS: usize = 16;
+
+pub(crate) struct StorageScrubber {
+    ctx: DefaultSharedGlobalContext,
+    interval: Duration,
+}
+
+impl StorageScrubber {
+    pub(crate) fn start(ctx: DefaultSharedGlobalContext) {
+        let interval_secs = ctx.config().log.scrub_interval_secs;
+        if interval_secs == 0 {
+            info!("storage scrubber is disabled");
+            return;
+        }
+
+        let scrubber = Self {
+            ctx,
+            interval: Duration::from_secs(interval_secs),
+        };
+        spawn(scrubber.dispatch_loop());
+    }
+
+    async fn dispatch_loop(self) {
+        info!(interval = ?self.interval, "starting storage scrubber");
+        loop {
+            sleep(self.interval).await;
+            self.scrub_leaders().await;
+            self.scrub_followers().await;
+        }
+    }
+
+    async fn scrub_leaders(&self) {
+        let leaders: Vec<_> = self
+            .ctx
+            .leaders_state()
+            .read()
+            .await
+            .values()
+            .cloned()
+            .collect();
+
+        for leader in leaders {
+            let targets = leader.read().await.scrub_targets().await;
+            let (corrupt, digests) = self.scrub_replica(leader.id(), targets).await;
+            self.report_digests(leader.id(), digests).await;
+            if !corrupt.is_empty() {
+                error!(replica = %leader.id(), ?corrupt, "leader replica has corrupt records");
+            }
+            leader.set_corrupt_ranges(corrupt).await;
+        }
+    }
+
+    async fn scrub_followers(&self) {
+        let followers: Vec<_> = self
+            .ctx
+            .followers_state()
+            .read()
+            .await
+            .iter()
+            .map(|(key, follower)| (key.clone(), follower.clone()))
+            .collect();
+
+        for (key, follower) in followers {
+            let targets = follower.read().await.scrub_targets().await;
+            drop(follower);
+            let (corrupt, digests) = self.scrub_replica(&key, targets).await;
+            self.report_digests(&key, digests).await;
+            if corrupt.is_empty() {
+                continue;
+            }
+
+            warn!(replica = %key, ?corrupt, "follower replica has corrupt records, replicating again");
+            let Some(replica) = self.ctx.replica_localstore().spec(&key) else {
+                debug!(replica = %key, "replica no longer exists");
+                continue;
+            };
+            match self.ctx.resync_follower_replica(replica).await {
+                Ok(()) => self.ctx.metrics().scrubber().add_repair(),
+                Err(err) => error!(replica = %key, %err, "unable to resync follower replica"),
+            }
+        }
+    }
+
+    async fn scrub_replica(
+        &self,
+        replica: &ReplicaKey,
+        targets: Vec<ScrubTarget>,
+    ) -> (Vec<CorruptRange>, Vec<SegmentDigest>) {
+        let metrics = self.ctx.metrics();
+        let mut corrupt = vec![];
+        let mut digests = vec![];
+        for target in targets {
+            match scrub_segment(&target).await {
+                Ok(report) => {
+                    metrics
+                        .scrubber()
+                        .add_segment(report.batches, report.corrupt.len() as u64);
+                    corrupt.extend(report.corrupt);
+                    digests.push(SegmentDigest {
+                        base_offset: target.base_offset,
+                        end_offset: target.end_offset,
+                        digest: report.digest,
+                    });
+                }
+                // segment may have been removed by cleaner meanwhile
+                Err(err) => debug!(%replica, path = ?target.path, %err, "segment not scrubbed"),
+            }
+            sleep(SEGMENT_PAUSE).await;
+        }
+        (corrupt, digests)
+    }
+
+    async fn report_digests(&self, replica: &ReplicaKey, mut digests: Vec<SegmentDigest>) {
+        if digests.is_empty() {
+            return;
+        }
+        let encrypted = self
+            .ctx
+            .replica_localstore()
+            .spec(replica)
+            .is_some_and(|spec| spec.encryption.is_some());
+        if encrypted {
+            return;
+        }
+
+        digests.sort_by_key(|digest| digest.base_offset);
+        let skip = digests.len().saturating_sub(MAX_REPORTED_DIGESTS);
+        digests.drain(..skip);
+        self.ctx
+            .status_update()
+            .send_digests(ReplicaDigestsRequest::new(
+                replica.clone(),
+                ReplicaDigests::new(self.ctx.local_spu_id(), digests),
+            ))
+            .await;
+    }
+}
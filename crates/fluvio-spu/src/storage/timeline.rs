@@ -0,0 +1,125 @@
+//!
+//! # Append Timeline
+//!
+//! Broker times of most recently appended batches, kept in memory only. Consumers asking for
+//! broker timestamps get times of the batch they are reading, older batches have no times.
+//!
+use std::collections::VecDeque;
+
+use async_lock::Mutex;
+
+use fluvio_protocol::record::Offset;
+use fluvio_types::Timestamp;
+
+/// number of batches to keep times for
+const MAX_TIMELINE_BATCHES: usize = 1024;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub(crate) struct AppendTimes {
+    pub(crate) append_time: Timestamp,
+    /// None until batch is committed
+    pub(crate) commit_time: Option<Timestamp>,
+}
+
+#[derive(Debug)]
+struct TimelineEntry {
+    base_offset: Offset,
+    /// offset after last record of batch
+    end_offset: Offset,
+    times: AppendTimes,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct AppendTimeline {
+    entries: Mutex<VecDeque<TimelineEntry>>,
+}
+
+impl AppendTimeline {
+    pub(crate) async fn append(
+        &self,
+        base_offset: Offset,
+        end_offset: Offset,
+        now: Timestamp,
+        committed: bool,
+    ) {
+        let mut entries = self.entries.lock().await;
+        if entries.len() == MAX_TIMELINE_BATCHES {
+            entries.pop_front();
+        }
+        entries.push_back(TimelineEntry {
+            base_offset,
+            end_offset,
+            times: AppendTimes {
+                append_time: now,
+                commit_time: committed.then_some(now),
+            },
+        });
+    }
+
+    /// mark batches before `hw` as committed, returns append times of newly committed batches
+    pub(crate) async fn commit(&self, hw: Offset, now: Timestamp) -> Vec<Timestamp> {
+        let mut entries = self.entries.lock().await;
+        let mut committed = vec![];
+        // batches are committed in order, so uncommitted ones are at the back
+        for entry in entries.iter_mut().rev() {
+            if entry.end_offset > hw {
+                continue;
+            }
+            if entry.times.commit_time.is_some() {
+                break;
+            }
+            entry.times.commit_time = Some(now);
+            committed.push(entry.times.append_time);
+        }
+        committed
+    }
+
+    /// times of batch containing `offset`
+    pub(crate) async fn get(&self, offset: Offset) -> Option<AppendTimes> {
+        let entries = self.entries.lock().await;
+        let index = entries.partition_point(|entry| entry.end_offset <= offset);
+        entries
+            .get(index)
+            .filter(|entry| entry.base_offset <= offset)
+            .map(|entry| entry.times)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[fluvio_future::test]
+    async fn test_timeline_commit_and_get() {
+        let timeline = AppendTimeline::default();
+        timeline.append(0, 2, 100, true).await;
+        timeline.append(2, 5, 110, false).await;
+        timeline.append(5, 6, 120, false).await;
+
+        assert!(timeline.get(6).await.is_none());
+        assert_eq!(
+            timeline.get(3).await,
+            Some(AppendTimes {
+                append_time: 110,
+                commit_time: None
+            })
+        );
+
+        assert_eq!(timeline.commit(5, 130).await, vec![110]);
+        assert!(timeline.commit(5, 140).await.is_empty());
+        assert_eq!(timeline.get(4).await.and_then(|t| t.commit_time), Some(130));
+        assert_eq!(timeline.get(0).await.and_then(|t| t.commit_time), Some(100));
+        assert_eq!(timeline.get(5).await.and_then(|t| t.commit_time), None);
+    }
+
+    #[fluvio_future::test]
+    async fn test_timeline_drops_oldest_batches() {
+        let timeline = AppendTimeline::default();
+        for offset in 0..(MAX_TIMELINE_BATCHES as Offset + 1) {
+            timeline.append(offset, offset + 1, offset, false).await;
+        }
+        assert!(timeline.get(0).await.is_none());
+        assert!(timeline.get(1).await.is_some());
+    }
+}
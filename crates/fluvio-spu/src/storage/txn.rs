@@ -0,0 +1,134 @@
+//!
+//! # Transaction Index
+//!
+//! Tracks open and aborted transactions of replica from transactional batches and
+//! control batches carrying transaction markers. Open transactions bound what read committed
+//! consumers can see; aborted ones are sent along with fetched records so consumers can
+//! skip their batches.
+//!
+use std::collections::HashMap;
+
+use fluvio_protocol::record::{BatchHeader, Offset, TxnMarker};
+use fluvio_spu_schema::fetch::AbortedTransaction;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+struct AbortedTxn {
+    producer_id: i64,
+    first_offset: Offset,
+    /// offset of abort marker
+    last_offset: Offset,
+}
+
+#[derive(Debug, Default)]
+pub(super) struct TxnIndex {
+    /// producer id to offset of first batch of its open transaction
+    ongoing: HashMap<i64, Offset>,
+    /// aborted transactions in order of their markers
+    aborted: Vec<AbortedTxn>,
+}
+
+impl TxnIndex {
+    /// track batch written at `base_offset`
+    pub(super) fn update(&mut self, header: &BatchHeader, base_offset: Offset) {
+        if !header.is_transactional() {
+            return;
+        }
+
+        match header.txn_marker() {
+            None => {
+                self.ongoing
+                    .entry(header.producer_id)
+                    .or_insert(base_offset);
+            }
+            Some(marker) => {
+                // marker without open transaction has been written before
+                let Some(first_offset) = self.ongoing.remove(&header.producer_id) else {
+                    return;
+                };
+                if marker == TxnMarker::Abort {
+                    self.aborted.push(AbortedTxn {
+                        producer_id: header.producer_id,
+                        first_offset,
+                        last_offset: base_offset,
+                    });
+                }
+            }
+        }
+    }
+
+    /// first offset of oldest open transaction, read committed consumers can't read past it
+    pub(super) fn first_unstable_offset(&self) -> Option<Offset> {
+        self.ongoing.values().min().copied()
+    }
+
+    /// true if producer has open transaction
+    pub(super) fn is_ongoing(&self, producer_id: i64) -> bool {
+        self.ongoing.contains_key(&producer_id)
+    }
+
+    /// aborted transactions with records between `start` and `end`
+    pub(super) fn aborted(&self, start: Offset, end: Offset) -> Vec<AbortedTransaction> {
+        self.aborted
+            .iter()
+            .filter(|txn| txn.first_offset < end && txn.last_offset >= start)
+            .map(|txn| AbortedTransaction {
+                producer_id: txn.producer_id,
+                first_offset: txn.first_offset,
+            })
+            .collect()
+    }
+
+    /// forget aborted transactions removed from log with old segments
+    pub(super) fn truncate_before(&mut self, log_start_offset: Offset) {
+        self.aborted
+            .retain(|txn| txn.last_offset >= log_start_offset);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn txn_header(producer_id: i64, marker: Option<TxnMarker>) -> BatchHeader {
+        let mut header = BatchHeader {
+            producer_id,
+            ..Default::default()
+        };
+        header.set_transactional();
+        if let Some(marker) = marker {
+            header.set_txn_marker(marker);
+        }
+        header
+    }
+
+    #[test]
+    fn test_txn_index() {
+        let mut index = TxnIndex::default();
+        index.update(&BatchHeader::default(), 0);
+        assert_eq!(index.first_unstable_offset(), None);
+
+        index.update(&txn_header(1, None), 10);
+        index.update(&txn_header(2, None), 12);
+        index.update(&txn_header(1, None), 14);
+        assert_eq!(index.first_unstable_offset(), Some(10));
+        assert!(index.is_ongoing(1));
+
+        index.update(&txn_header(1, Some(TxnMarker::Abort)), 16);
+        assert_eq!(index.first_unstable_offset(), Some(12));
+        index.update(&txn_header(2, Some(TxnMarker::Commit)), 17);
+        assert_eq!(index.first_unstable_offset(), None);
+
+        // repeated marker is ignored
+        index.update(&txn_header(1, Some(TxnMarker::Abort)), 18);
+
+        let aborted = index.aborted(0, 20);
+        assert_eq!(aborted.len(), 1);
+        assert_eq!(aborted[0].producer_id, 1);
+        assert_eq!(aborted[0].first_offset, 10);
+        assert!(index.aborted(17, 20).is_empty());
+        assert!(index.aborted(0, 10).is_empty());
+
+        index.truncate_before(17);
+        assert!(index.aborted(0, 20).is_empty());
+    }
+}
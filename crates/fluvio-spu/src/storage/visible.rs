@@ -0,0 +1,173 @@
+//!
+//! # Visible Offsets
+//!
+//! Offsets of replica which consumers are allowed to read up to. Both isolations are held
+//! back by scheduled batches which are not yet due. Read committed is also held back
+//! by the oldest open transaction, so consumers never read records whose transaction
+//! may still be aborted.
+//!
+use std::sync::Arc;
+
+use async_lock::Mutex;
+use chrono::Utc;
+
+use fluvio_protocol::record::{BatchHeader, Offset};
+use fluvio_spu_schema::Isolation;
+use fluvio_spu_schema::fetch::AbortedTransaction;
+use fluvio_types::Timestamp;
+use fluvio_types::event::offsets::{OffsetChangeListener, OffsetPublisher};
+
+use super::scheduled::ScheduledBatches;
+use super::txn::TxnIndex;
+
+#[derive(Debug)]
+pub(crate) struct VisibleOffsets {
+    scheduled: ScheduledBatches,
+    txns: Mutex<TxnIndex>,
+    hw: Arc<OffsetPublisher>,
+    leo: Arc<OffsetPublisher>,
+}
+
+impl VisibleOffsets {
+    pub(crate) fn new(hw: Offset, leo: Offset) -> Self {
+        Self {
+            scheduled: ScheduledBatches::default(),
+            txns: Mutex::new(TxnIndex::default()),
+            hw: OffsetPublisher::shared(hw),
+            leo: OffsetPublisher::shared(leo),
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn hw(&self) -> Offset {
+        self.hw.current_value()
+    }
+
+    #[cfg(test)]
+    pub(crate) fn leo(&self) -> Offset {
+        self.leo.current_value()
+    }
+
+    pub(crate) fn listener(&self, isolation: &Isolation) -> OffsetChangeListener {
+        match isolation {
+            Isolation::ReadCommitted => self.hw.change_listener(),
+            Isolation::ReadUncommitted => self.leo.change_listener(),
+        }
+    }
+
+    /// offset of first record not yet visible with isolation, None if nothing is held back
+    pub(crate) async fn end(&self, isolation: &Isolation) -> Option<Offset> {
+        let scheduled_end = self
+            .scheduled
+            .visible_end(Utc::now().timestamp_millis())
+            .await;
+        match isolation {
+            Isolation::ReadUncommitted => scheduled_end,
+            Isolation::ReadCommitted => {
+                let unstable = self.txns.lock().await.first_unstable_offset();
+                match (scheduled_end, unstable) {
+                    (Some(scheduled_end), Some(unstable)) => Some(scheduled_end.min(unstable)),
+                    (scheduled_end, unstable) => scheduled_end.or(unstable),
+                }
+            }
+        }
+    }
+
+    /// track batch written at `base_offset`.
+    /// Returns time it becomes visible if batch is scheduled for later
+    pub(crate) async fn add_batch(
+        &self,
+        header: &BatchHeader,
+        base_offset: Offset,
+    ) -> Option<Timestamp> {
+        self.txns.lock().await.update(header, base_offset);
+
+        let visible_at = header.max_time_stamp;
+        if header.has_scheduled_records() && visible_at > Utc::now().timestamp_millis() {
+            self.scheduled.add(base_offset, visible_at).await;
+            Some(visible_at)
+        } else {
+            None
+        }
+    }
+
+    /// true if producer has open transaction in this replica
+    pub(crate) async fn is_txn_ongoing(&self, producer_id: i64) -> bool {
+        self.txns.lock().await.is_ongoing(producer_id)
+    }
+
+    /// aborted transactions with records between `start` and `end`
+    pub(crate) async fn aborted_txns(&self, start: Offset, end: Offset) -> Vec<AbortedTransaction> {
+        self.txns.lock().await.aborted(start, end)
+    }
+
+    pub(crate) async fn truncate_before(&self, log_start_offset: Offset) {
+        self.txns.lock().await.truncate_before(log_start_offset);
+    }
+
+    /// publish replica offsets capped by what is not yet visible
+    pub(crate) async fn update(&self, hw: Offset, leo: Offset) {
+        let hw_end = self.end(&Isolation::ReadCommitted).await;
+        let leo_end = self.end(&Isolation::ReadUncommitted).await;
+        self.hw.update(hw_end.map_or(hw, |end| hw.min(end)));
+        self.leo.update(leo_end.map_or(leo, |end| leo.min(end)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fluvio_protocol::record::TxnMarker;
+
+    use super::*;
+
+    fn scheduled_header(visible_at: Timestamp) -> BatchHeader {
+        let mut header = BatchHeader {
+            max_time_stamp: visible_at,
+            ..Default::default()
+        };
+        header.set_scheduled_records();
+        header
+    }
+
+    #[fluvio_future::test]
+    async fn test_visible_offsets() {
+        let visible = VisibleOffsets::new(0, 0);
+        visible.update(5, 10).await;
+        assert_eq!((visible.hw(), visible.leo()), (5, 10));
+
+        let now = Utc::now().timestamp_millis();
+        assert!(visible
+            .add_batch(&scheduled_header(now + 60_000), 10)
+            .await
+            .is_some());
+        visible.update(12, 12).await;
+        assert_eq!((visible.hw(), visible.leo()), (10, 10));
+
+        // due batch is not tracked
+        assert!(visible
+            .add_batch(&scheduled_header(now - 1), 12)
+            .await
+            .is_none());
+        visible.update(15, 15).await;
+        assert_eq!((visible.hw(), visible.leo()), (10, 10));
+    }
+
+    #[fluvio_future::test]
+    async fn test_open_transaction_holds_back_read_committed() {
+        let visible = VisibleOffsets::new(0, 0);
+
+        let mut txn = BatchHeader {
+            producer_id: 1,
+            ..Default::default()
+        };
+        txn.set_transactional();
+        visible.add_batch(&txn, 5).await;
+        visible.update(8, 8).await;
+        assert_eq!((visible.hw(), visible.leo()), (5, 8));
+
+        txn.set_txn_marker(TxnMarker::Commit);
+        visible.add_batch(&txn, 8).await;
+        visible.update(9, 9).await;
+        assert_eq!((visible.hw(), visible.leo()), (9, 9));
+    }
+}
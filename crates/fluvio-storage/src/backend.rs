@@ -0,0 +1,468 @@
+//!
+//! # Storage backends
+//!
+//! Replica storage implementations are registered by name. Topic selects backend of its
+//! partitions with `backend` of its storage config, partitions of topics without it use file
+//! backend. `BackendReplica` resolves backend when replica is created and dispatches to it.
+//!
+use std::collections::HashMap;
+use std::fmt;
+use std::io::Cursor;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use anyhow::{anyhow, Result};
+
+use fluvio_controlplane::replica::Replica;
+use fluvio_controlplane_metadata::partition::StorageUsage;
+use fluvio_protocol::{Decoder, Encoder};
+use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::record::{Batch, BatchRecords, Offset, RawRecords, RecordSet};
+use fluvio_protocol::record::{ReplicaKey, Size64};
+use fluvio_spu_schema::Isolation;
+
+use crate::config::ReplicaConfig;
+use crate::{FileReplica, LogSync, ReplicaSlice, ReplicaStorage, ReplicaStorageConfig, StorageError};
+
+/// backend of topics which don't select one
+pub const FILE_BACKEND: &str = "file";
+
+#[cfg(all(feature = "memory", target_os = "linux"))]
+pub const MEMORY_BACKEND: &str = "memory";
+
+/// Object safe version of `ReplicaStorage`, records are written as raw batches
+#[async_trait]
+pub trait DynReplicaStorage: fmt::Debug + Send + Sync {
+    fn get_hw(&self) -> Offset;
+
+    fn get_leo(&self) -> Offset;
+
+    fn get_log_start_offset(&self) -> Offset;
+
+    async fn read_partition_slice(
+        &self,
+        offset: Offset,
+        max_len: u32,
+        isolation: Isolation,
+    ) -> Result<ReplicaSlice, ErrorCode>;
+
+    async fn read_partition_slice_before(
+        &self,
+        offset: Offset,
+        max_offset: Offset,
+        max_len: u32,
+    ) -> Result<ReplicaSlice, ErrorCode>;
+
+    fn get_partition_size(&self) -> Size64;
+
+    fn get_storage_usage(&self) -> StorageUsage;
+
+    fn log_sync(&self) -> Option<LogSync>;
+
+    async fn write_raw_recordset(
+        &mut self,
+        records: &mut RecordSet<RawRecords>,
+        update_highwatermark: bool,
+    ) -> Result<usize>;
+
+    async fn update_high_watermark(&mut self, offset: Offset) -> Result<bool, StorageError>;
+
+    async fn delete_records_before(&mut self, offset: Offset) -> Result<Offset, StorageError>;
+
+    async fn remove(&self) -> Result<(), StorageError>;
+
+    async fn read_state(&self, name: &str) -> Result<Option<Vec<u8>>, StorageError>;
+
+    async fn write_state(&self, name: &str, state: &[u8]) -> Result<(), StorageError>;
+}
+
+/// Creates replica storage of backend
+#[async_trait]
+pub trait StorageBackend: Send + Sync {
+    async fn create_or_load(
+        &self,
+        replica: &ReplicaKey,
+        replica_config: ReplicaConfig,
+    ) -> Result<Box<dyn DynReplicaStorage>>;
+}
+
+/// Backend of `ReplicaStorage` implementation configured with `ReplicaConfig`
+pub struct ReplicaBackend<S>(PhantomData<fn() -> S>);
+
+impl<S> Default for ReplicaBackend<S> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+#[async_trait]
+impl<S> StorageBackend for ReplicaBackend<S>
+where
+    S: ReplicaStorage<ReplicaConfig = ReplicaConfig> + fmt::Debug + Send + Sync + 'static,
+{
+    async fn create_or_load(
+        &self,
+        replica: &ReplicaKey,
+        replica_config: ReplicaConfig,
+    ) -> Result<Box<dyn DynReplicaStorage>> {
+        let storage = S::create_or_load(replica, replica_config).await?;
+        Ok(Box::new(StaticReplica(storage)))
+    }
+}
+
+#[derive(Debug)]
+struct StaticReplica<S>(S);
+
+#[async_trait]
+impl<S> DynReplicaStorage for StaticReplica<S>
+where
+    S: ReplicaStorage + fmt::Debug + Send + Sync,
+{
+    fn get_hw(&self) -> Offset {
+        self.0.get_hw()
+    }
+
+    fn get_leo(&self) -> Offset {
+        self.0.get_leo()
+    }
+
+    fn get_log_start_offset(&self) -> Offset {
+        self.0.get_log_start_offset()
+    }
+
+    async fn read_partition_slice(
+        &self,
+        offset: Offset,
+        max_len: u32,
+        isolation: Isolation,
+    ) -> Result<ReplicaSlice, ErrorCode> {
+        self.0
+            .read_partition_slice(offset, max_len, isolation)
+            .await
+    }
+
+    async fn read_partition_slice_before(
+        &self,
+        offset: Offset,
+        max_offset: Offset,
+        max_len: u32,
+    ) -> Result<ReplicaSlice, ErrorCode> {
+        self.0
+            .read_partition_slice_before(offset, max_offset, max_len)
+            .await
+    }
+
+    fn get_partition_size(&self) -> Size64 {
+        self.0.get_partition_size()
+    }
+
+    fn get_storage_usage(&self) -> StorageUsage {
+        self.0.get_storage_usage()
+    }
+
+    fn log_sync(&self) -> Option<LogSync> {
+        self.0.log_sync()
+    }
+
+    async fn write_raw_recordset(
+        &mut self,
+        records: &mut RecordSet<RawRecords>,
+        update_highwatermark: bool,
+    ) -> Result<usize> {
+        self.0.write_recordset(records, update_highwatermark).await
+    }
+
+    async fn update_high_watermark(&mut self, offset: Offset) -> Result<bool, StorageError> {
+        self.0.update_high_watermark(offset).await
+    }
+
+    async fn delete_records_before(&mut self, offset: Offset) -> Result<Offset, StorageError> {
+        self.0.delete_records_before(offset).await
+    }
+
+    async fn remove(&self) -> Result<(), StorageError> {
+        self.0.remove().await
+    }
+
+    async fn read_state(&self, name: &str) -> Result<Option<Vec<u8>>, StorageError> {
+        self.0.read_state(name).await
+    }
+
+    async fn write_state(&self, name: &str, state: &[u8]) -> Result<(), StorageError> {
+        self.0.write_state(name, state).await
+    }
+}
+
+/// Registered backends by name
+#[derive(Clone)]
+pub struct StorageBackends {
+    backends: HashMap<String, Arc<dyn StorageBackend>>,
+}
+
+impl Default for StorageBackends {
+    fn default() -> Self {
+        let mut backends = Self {
+            backends: HashMap::new(),
+        };
+        backends.register(FILE_BACKEND, ReplicaBackend::<FileReplica>::default());
+        #[cfg(all(feature = "memory", target_os = "linux"))]
+        backends.register(
+            MEMORY_BACKEND,
+            ReplicaBackend::<crate::memory::MemoryReplica>::default(),
+        );
+        backends
+    }
+}
+
+impl fmt::Debug for StorageBackends {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.names()).finish()
+    }
+}
+
+/// backends are equal if same names are registered
+impl PartialEq for StorageBackends {
+    fn eq(&self, other: &Self) -> bool {
+        self.names() == other.names()
+    }
+}
+
+impl Eq for StorageBackends {}
+
+impl StorageBackends {
+    /// register backend, replacing one with same name
+    pub fn register(&mut self, name: impl Into<String>, backend: impl StorageBackend + 'static) {
+        self.backends.insert(name.into(), Arc::new(backend));
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Arc<dyn StorageBackend>> {
+        self.backends.get(name)
+    }
+
+    /// sorted names of backends
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.backends.keys().map(|name| name.as_str()).collect();
+        names.sort_unstable();
+        names
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BackendReplicaConfig {
+    pub backend: String,
+    pub backends: StorageBackends,
+    pub replica: ReplicaConfig,
+}
+
+impl BackendReplicaConfig {
+    pub fn new(backends: StorageBackends, replica: ReplicaConfig) -> Self {
+        Self {
+            backend: FILE_BACKEND.to_owned(),
+            backends,
+            replica,
+        }
+    }
+}
+
+impl fmt::Display for BackendReplicaConfig {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} backend, {}", self.backend, self.replica)
+    }
+}
+
+impl ReplicaStorageConfig for BackendReplicaConfig {
+    fn update_from_replica(&mut self, replica: &Replica) {
+        self.replica.update_from_replica(replica);
+        if let Some(backend) = replica
+            .storage
+            .as_ref()
+            .and_then(|storage| storage.backend.as_ref())
+        {
+            self.backend.clone_from(backend);
+        }
+    }
+}
+
+/// Replica stored by backend selected at runtime
+#[derive(Debug)]
+pub struct BackendReplica {
+    backend: String,
+    inner: Box<dyn DynReplicaStorage>,
+}
+
+impl BackendReplica {
+    pub fn backend(&self) -> &str {
+        &self.backend
+    }
+}
+
+#[async_trait]
+impl ReplicaStorage for BackendReplica {
+    type ReplicaConfig = BackendReplicaConfig;
+
+    async fn create_or_load(
+        replica: &ReplicaKey,
+        replica_config: Self::ReplicaConfig,
+    ) -> Result<Self> {
+        let BackendReplicaConfig {
+            backend,
+            backends,
+            replica: config,
+        } = replica_config;
+        let Some(storage) = backends.get(&backend) else {
+            return Err(anyhow!(
+                "storage backend {backend} of replica {replica} is not registered, available: {:?}",
+                backends.names()
+            ));
+        };
+        let inner = storage.create_or_load(replica, config).await?;
+        Ok(Self { backend, inner })
+    }
+
+    fn get_hw(&self) -> Offset {
+        self.inner.get_hw()
+    }
+
+    fn get_leo(&self) -> Offset {
+        self.inner.get_leo()
+    }
+
+    fn get_log_start_offset(&self) -> Offset {
+        self.inner.get_log_start_offset()
+    }
+
+    async fn read_partition_slice(
+        &self,
+        offset: Offset,
+        max_len: u32,
+        isolation: Isolation,
+    ) -> Result<ReplicaSlice, ErrorCode> {
+        self.inner
+            .read_partition_slice(offset, max_len, isolation)
+            .await
+    }
+
+    async fn read_partition_slice_before(
+        &self,
+        offset: Offset,
+        max_offset: Offset,
+        max_len: u32,
+    ) -> Result<ReplicaSlice, ErrorCode> {
+        self.inner
+            .read_partition_slice_before(offset, max_offset, max_len)
+            .await
+    }
+
+    fn get_partition_size(&self) -> Size64 {
+        self.inner.get_partition_size()
+    }
+
+    fn get_storage_usage(&self) -> StorageUsage {
+        self.inner.get_storage_usage()
+    }
+
+    fn log_sync(&self) -> Option<LogSync> {
+        self.inner.log_sync()
+    }
+
+    /// batches are re-encoded as raw batches, base offsets assigned by backend are copied back
+    async fn write_recordset<R: BatchRecords>(
+        &mut self,
+        records: &mut RecordSet<R>,
+        update_highwatermark: bool,
+    ) -> Result<usize> {
+        let mut raw_records = RecordSet::<RawRecords>::default();
+        for batch in &records.batches {
+            let mut buf = Vec::with_capacity(batch.write_size(0));
+            batch.encode(&mut buf, 0)?;
+            raw_records
+                .batches
+                .push(Batch::<RawRecords>::decode_from(&mut Cursor::new(buf), 0)?);
+        }
+
+        let size = self
+            .inner
+            .write_raw_recordset(&mut raw_records, update_highwatermark)
+            .await?;
+        for (batch, raw_batch) in records.batches.iter_mut().zip(&raw_records.batches) {
+            batch.set_base_offset(raw_batch.get_base_offset());
+        }
+        Ok(size)
+    }
+
+    async fn update_high_watermark(&mut self, offset: Offset) -> Result<bool, StorageError> {
+        self.inner.update_high_watermark(offset).await
+    }
+
+    async fn delete_records_before(&mut self, offset: Offset) -> Result<Offset, StorageError> {
+        self.inner.delete_records_before(offset).await
+    }
+
+    async fn remove(&self) -> Result<(), StorageError> {
+        self.inner.remove().await
+    }
+
+    async fn read_state(&self, name: &str) -> Result<Option<Vec<u8>>, StorageError> {
+        self.inner.read_state(name).await
+    }
+
+    async fn write_state(&self, name: &str, state: &[u8]) -> Result<(), StorageError> {
+        self.inner.write_state(name, state).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::env::temp_dir;
+
+    use fluvio_controlplane_metadata::topic::TopicStorageConfig;
+    use fluvio_protocol::fixture::create_batch;
+    use flv_util::fixture::ensure_clean_dir;
+
+    use super::*;
+
+    fn replica_with_backend(backend: Option<&str>) -> Replica {
+        let mut replica = Replica::new(("test", 0), 5000, vec![5000]);
+        replica.storage = Some(TopicStorageConfig {
+            backend: backend.map(|backend| backend.to_owned()),
+            ..Default::default()
+        });
+        replica
+    }
+
+    #[fluvio_future::test]
+    async fn test_backend_selected_by_replica() {
+        let base_dir = temp_dir().join("test_backend_selected_by_replica");
+        ensure_clean_dir(&base_dir);
+
+        let mut config = BackendReplicaConfig::new(
+            StorageBackends::default(),
+            ReplicaConfig {
+                base_dir,
+                ..Default::default()
+            },
+        );
+        config.update_from_replica(&replica_with_backend(None));
+        assert_eq!(config.backend, FILE_BACKEND);
+
+        let mut replica = BackendReplica::create_or_load(&("test", 0).into(), config.clone())
+            .await
+            .expect("file replica");
+        assert_eq!(replica.backend(), FILE_BACKEND);
+
+        let mut records = RecordSet::default().add(create_batch()).add(create_batch());
+        replica
+            .write_recordset(&mut records, true)
+            .await
+            .expect("write");
+        assert_eq!(records.batches[1].get_base_offset(), 2);
+        assert_eq!(replica.get_hw(), 4);
+
+        config.update_from_replica(&replica_with_backend(Some("unknown")));
+        assert!(BackendReplica::create_or_load(&("test", 0).into(), config)
+            .await
+            .is_err());
+    }
+}
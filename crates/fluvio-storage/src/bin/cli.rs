@@ -9,7 +9,7 @@ use fluvio_future::task::run_block_on;
 use fluvio_storage::{
     LogIndex, OffsetPosition,
     batch_header::BatchHeaderStream,
-    segment::{MutableSegment},
+    segment::{MutableSegment, rebuild_index},
     config::{ReplicaConfig},
     FileReplica, ReplicaStorage,
 };
@@ -32,6 +32,10 @@ enum Main {
     #[clap(name = "validate")]
     ValidateSegment(SegmentValidateOpt),
 
+    /// regenerate index of segment from its log
+    #[clap(name = "rebuild-index")]
+    RebuildIndex(RebuildIndexOpt),
+
     /// show information about replica
     #[clap(name = "replica")]
     Replica(ReplicaOpt),
@@ -47,6 +51,7 @@ fn main() {
             Main::Log(opt) => dump_log(opt).await,
             Main::Index(opt) => dump_index(opt).await,
             Main::ValidateSegment(opt) => validate_segment(opt).await,
+            Main::RebuildIndex(opt) => rebuild_segment_index(opt).await,
             Main::Replica(opt) => replica_info(opt).await,
         }
     });
@@ -219,6 +224,42 @@ pub(crate) async fn validate_segment(opt: SegmentValidateOpt) -> Result<()> {
     Ok(())
 }
 
+#[derive(Debug, Parser)]
+pub(crate) struct RebuildIndexOpt {
+    /// replica directory containing segment
+    #[clap(value_parser)]
+    replica_dir: PathBuf,
+
+    #[clap(long, default_value = "0")]
+    base_offset: Offset,
+
+    /// bytes of records between index entries, SPU default if not set
+    #[clap(long)]
+    index_interval_bytes: Option<u32>,
+}
+
+pub(crate) async fn rebuild_segment_index(opt: RebuildIndexOpt) -> Result<()> {
+    let mut option = ReplicaConfig::builder()
+        .base_dir(opt.replica_dir.clone())
+        .build();
+    if let Some(index_interval_bytes) = opt.index_interval_bytes {
+        option.index_max_interval_bytes = index_interval_bytes;
+    }
+
+    println!(
+        "rebuilding index of segment: {} in {:#?}",
+        opt.base_offset, opt.replica_dir
+    );
+
+    let start = std::time::Instant::now();
+    let entries = rebuild_index(opt.base_offset, option.shared()).await?;
+    let duration = start.elapsed().as_secs_f32();
+
+    println!("completed, {entries} index entries, took: {duration} seconds");
+
+    Ok(())
+}
+
 #[derive(Debug, Parser)]
 pub(crate) struct ReplicaOpt {
     /// base data directory
@@ -1,21 +1,29 @@
+use std::collections::BTreeMap;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use std::ops::Div;
 use std::ops::Rem;
 
+use anyhow::Result;
+use async_lock::Mutex;
 use tracing::{debug, info, instrument};
 
 use fluvio_future::task::spawn;
 use fluvio_future::timer::sleep;
+use fluvio_protocol::record::{Offset, RawRecords};
+use fluvio_types::Timestamp;
 use fluvio_types::event::StickyEvent;
 
+use crate::batch::FileBatchStream;
 use crate::config::{SharedReplicaConfig, StorageConfig};
 use crate::replica::ReplicaSize;
+use crate::scrubber::ScrubTarget;
 use crate::segments::SharedSegments;
 
 /// Replica cleaner. This is a background task that periodically checks for expired segments and
 /// removes them. It also enforces max partition size by removing first segments if replica size is
 /// exceeded. In the future, this may be done by a central cleaner pool instead of per a replica.
+/// First segments are also removed before retention if all of their records have expired TTL.
 #[derive(Debug)]
 pub(crate) struct Cleaner {
     config: Arc<StorageConfig>,
@@ -23,6 +31,8 @@ pub(crate) struct Cleaner {
     segments: Arc<SharedSegments>,
     replica_size: Arc<ReplicaSize>,
     end_event: Arc<StickyEvent>,
+    /// expiry of records of closed segments by base offset, closed segments don't change
+    record_expiry: Mutex<BTreeMap<Offset, Option<Timestamp>>>,
 }
 
 impl Cleaner {
@@ -39,6 +49,7 @@ impl Cleaner {
             segments,
             replica_size,
             end_event,
+            record_expiry: Mutex::new(BTreeMap::new()),
         });
 
         let cleaner_ref = cleaner.clone();
@@ -74,6 +85,7 @@ impl Cleaner {
                 _ = sleep(sleep_period) => {
                     self.enforce_size().await;
                     self.enforce_ttl().await;
+                    self.enforce_record_ttl().await;
                 }
             }
         }
@@ -106,7 +118,7 @@ impl Cleaner {
             self.segments.remove_segments(&segments_to_remove).await;
 
             let read = self.segments.read().await;
-            self.replica_size.store_prev(read.occupied_memory());
+            self.replica_size.store_prev_segments(&read);
         }
     }
 
@@ -127,9 +139,85 @@ impl Cleaner {
         if !expired_segments.is_empty() {
             self.segments.remove_segments(&expired_segments).await;
             let read = self.segments.read().await;
-            self.replica_size.store_prev(read.occupied_memory());
+            self.replica_size.store_prev_segments(&read);
         }
     }
+
+    /// Remove first segments where every record has TTL and all of them have expired.
+    /// Segments are removed only from the start of the log, so remaining log stays contiguous
+    #[instrument(skip(self))]
+    async fn enforce_record_ttl(&self) {
+        let targets = self.segments.read().await.scrub_targets();
+        let now = now_millis();
+
+        let mut record_expiry = self.record_expiry.lock().await;
+        record_expiry.retain(|base_offset, _| {
+            targets
+                .iter()
+                .any(|target| target.base_offset == *base_offset)
+        });
+
+        let mut expired_segments = vec![];
+        for target in targets {
+            let expires_at = match record_expiry.get(&target.base_offset) {
+                Some(expires_at) => *expires_at,
+                None => match records_expire_at(&target).await {
+                    Ok(expires_at) => {
+                        record_expiry.insert(target.base_offset, expires_at);
+                        expires_at
+                    }
+                    Err(err) => {
+                        debug!(path = ?target.path, %err, "unable to read record expiry");
+                        None
+                    }
+                },
+            };
+            match expires_at {
+                Some(expires_at) if expires_at <= now => expired_segments.push(target.base_offset),
+                _ => break,
+            }
+        }
+        drop(record_expiry);
+
+        debug!(
+            expired = expired_segments.len(),
+            "segments with expired records"
+        );
+        if !expired_segments.is_empty() {
+            self.segments.remove_segments(&expired_segments).await;
+            let read = self.segments.read().await;
+            self.replica_size.store_prev_segments(&read);
+        }
+    }
+}
+
+/// time when all records of the segment are expired, None if any record has no TTL
+async fn records_expire_at(target: &ScrubTarget) -> Result<Option<Timestamp>> {
+    let mut stream: FileBatchStream<RawRecords> = FileBatchStream::open(&target.path).await?;
+    let mut segment_expires_at = None;
+    while let Some(batch_pos) = stream.try_next().await? {
+        let batch = batch_pos.get_batch();
+        // only batches with TTL flag need to be decoded, records of encrypted batches
+        // can't be read without key so they are left to retention
+        if !batch.header.has_record_ttl() || batch.header.is_encrypted() {
+            return Ok(None);
+        }
+        let timestamp_base = batch.get_base_timestamp();
+        for record in batch.memory_records()? {
+            let Some(expires_at) = record.expires_at(timestamp_base) else {
+                return Ok(None);
+            };
+            segment_expires_at = segment_expires_at.max(Some(expires_at));
+        }
+    }
+    Ok(segment_expires_at)
+}
+
+fn now_millis() -> Timestamp {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|since| since.as_millis() as Timestamp)
+        .unwrap_or_default()
 }
 
 #[cfg(test)]
@@ -145,7 +233,7 @@ mod tests {
     use fluvio_future::timer::sleep;
     use flv_util::fixture::ensure_new_dir;
     use fluvio_protocol::fixture::create_batch;
-    use fluvio_protocol::record::Offset;
+    use fluvio_protocol::record::{Batch, Offset, Record};
 
     use crate::config::SharedReplicaConfig;
     use crate::segment::MutableSegment;
@@ -156,7 +244,7 @@ mod tests {
 
     use crate::segments::{SegmentList, SharedSegments};
 
-    use crate::cleaner::Cleaner;
+    use crate::cleaner::{Cleaner, now_millis};
 
     #[fluvio_future::test]
     async fn test_enforce_size_delete_one() {
@@ -221,6 +309,56 @@ mod tests {
         assert_eq!(read.occupied_memory(), replica_size.get());
     }
 
+    #[fluvio_future::test]
+    async fn test_enforce_record_ttl() {
+        //given
+        let config = default_option();
+        let rep_dir = temp_dir().join("cleaner-enforce-record-ttl");
+        ensure_new_dir(&rep_dir).expect("new");
+        let option = ReplicaConfig {
+            base_dir: rep_dir,
+            ..config.clone()
+        }
+        .shared();
+        let slist = SharedSegments::from(SegmentList::new());
+        let expiring = ttl_batch(Some(Duration::from_millis(200)));
+        let permanent = ttl_batch(None);
+        for (start, end, mut batch) in [(100, 600, expiring), (600, 1200, permanent)] {
+            let mut segment = MutableSegment::create(start, option.clone())
+                .await
+                .expect("create");
+            segment.append_batch(&mut batch).await.expect("append");
+            segment.set_end_offset(end);
+            slist
+                .add_segment(segment.convert_to_segment().await.expect("convert"))
+                .await;
+        }
+        let replica_size = Arc::new(ReplicaSize::default());
+        let cleaner = test_cleaner(config, slist.clone(), replica_size.clone());
+
+        //when
+        cleaner.enforce_record_ttl().await;
+        assert_eq!(slist.read().await.find_first(10), vec![100, 600]);
+        sleep(Duration::from_millis(300)).await;
+        cleaner.enforce_record_ttl().await;
+
+        //then
+        let read = slist.read().await;
+        assert_eq!(read.find_first(10), vec![600]);
+        assert_eq!(read.occupied_memory(), replica_size.get());
+    }
+
+    fn ttl_batch(ttl: Option<Duration>) -> Batch {
+        let mut batch = Batch::default();
+        let mut record = Record::new("value");
+        if let Some(ttl) = ttl {
+            record.set_ttl(ttl);
+        }
+        batch.add_record(record);
+        batch.get_mut_header().first_timestamp = now_millis();
+        batch
+    }
+
     async fn shared_segments(
         path: &str,
         count: usize,
@@ -287,6 +425,7 @@ mod tests {
             segments,
             replica_size,
             end_event: StickyEvent::shared(),
+            record_expiry: Default::default(),
         }
     }
 }
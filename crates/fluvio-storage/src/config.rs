@@ -8,7 +8,7 @@ use derive_builder::Builder;
 use fluvio_controlplane::replica::Replica;
 use serde::Deserialize;
 
-use fluvio_controlplane_metadata::topic::CleanupPolicy;
+use fluvio_controlplane_metadata::topic::{CleanupPolicy, Durability};
 use fluvio_types::defaults::{
     SPU_LOG_INDEX_MAX_BYTES, SPU_LOG_BASE_DIR, STORAGE_FLUSH_WRITE_COUNT, STORAGE_FLUSH_IDLE_MSEC,
     STORAGE_MAX_BATCH_SIZE, STORAGE_RETENTION_SECONDS, SPU_PARTITION_MAX_BYTES,
@@ -53,6 +53,17 @@ pub struct ReplicaConfig {
     #[builder(default = "default_max_partition_size()")]
     #[serde(default = "default_max_partition_size")]
     pub max_partition_size: Size64,
+    #[builder(default)]
+    #[serde(skip)]
+    pub durability: Durability,
+    /// truncate torn tails of all segments on load and report truncations
+    #[builder(default)]
+    #[serde(default)]
+    pub recovery_audit: bool,
+    /// with batch durability, sync records of concurrent writes together instead of every batch
+    #[builder(default)]
+    #[serde(default)]
+    pub group_commit: bool,
 }
 
 impl fmt::Display for ReplicaConfig {
@@ -85,6 +96,27 @@ impl ReplicaStorageConfig for ReplicaConfig {
         {
             self.max_partition_size = max_partition_size;
         }
+        if let Some(durability) = replica
+            .storage
+            .as_ref()
+            .and_then(|storage| storage.durability)
+        {
+            self.durability = durability;
+        }
+        if let Some(index_interval_bytes) = replica
+            .storage
+            .as_ref()
+            .and_then(|storage| storage.index_interval_bytes)
+        {
+            self.index_max_interval_bytes = index_interval_bytes;
+        }
+        if let Some(index_max_bytes) = replica
+            .storage
+            .as_ref()
+            .and_then(|storage| storage.index_max_bytes)
+        {
+            self.index_max_bytes = index_max_bytes;
+        }
     }
 }
 
@@ -152,6 +184,9 @@ impl Default for ReplicaConfig {
             retention_seconds: default_retention_seconds(),
             max_partition_size: default_max_partition_size(),
             update_hw: true,
+            durability: Durability::default(),
+            recovery_audit: false,
+            group_commit: false,
         }
     }
 }
@@ -228,6 +263,9 @@ pub struct SharedReplicaConfig {
     pub update_hw: bool, // if true, enable hw update
     pub retention_seconds: SharedConfigU32Value,
     pub max_partition_size: SharedConfigU64Value,
+    pub durability: Durability,
+    pub recovery_audit: bool,
+    pub group_commit: bool,
 }
 
 impl From<ReplicaConfig> for SharedReplicaConfig {
@@ -243,6 +281,9 @@ impl From<ReplicaConfig> for SharedReplicaConfig {
             update_hw: config.update_hw,
             retention_seconds: SharedConfigU32Value::new(config.retention_seconds),
             max_partition_size: SharedConfigU64Value::new(config.max_partition_size),
+            durability: config.durability,
+            recovery_audit: config.recovery_audit,
+            group_commit: config.group_commit,
         }
     }
 }
@@ -270,4 +311,27 @@ mod tests {
 
         assert_eq!(ReplicaConfig::default(), config);
     }
+
+    #[test]
+    fn test_index_settings_from_replica() {
+        use fluvio_controlplane_metadata::topic::TopicStorageConfig;
+
+        let mut config = ReplicaConfig::default();
+        let mut replica = Replica::new(("test", 0), 5001, vec![5001]);
+        config.update_from_replica(&replica);
+        assert_eq!(
+            config.index_max_interval_bytes,
+            SPU_LOG_INDEX_MAX_INTERVAL_BYTES
+        );
+        assert_eq!(config.index_max_bytes, SPU_LOG_INDEX_MAX_BYTES);
+
+        replica.storage = Some(TopicStorageConfig {
+            index_interval_bytes: Some(512),
+            index_max_bytes: Some(2048),
+            ..Default::default()
+        });
+        config.update_from_replica(&replica);
+        assert_eq!(config.index_max_interval_bytes, 512);
+        assert_eq!(config.index_max_bytes, 2048);
+    }
 }
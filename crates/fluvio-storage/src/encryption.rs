@@ -0,0 +1,328 @@
+//!
+//! # Encryption at Rest
+//!
+//! Records of topics with encryption configured are stored encrypted with AES-256-GCM.
+//! Only records of a batch are encrypted, batch header stays in clear so offsets, timestamps,
+//! producer and transaction attributes can be used without the key.
+//! Encrypted records are stored as nonce followed by ciphertext and authentication tag,
+//! batch is flagged with encrypted attribute. Topic, partition and base offset of the batch are
+//! authenticated with the records, so encrypted records can't be moved to another partition
+//! or offset without failing decryption.
+//!
+//! Keys are looked up by key id configured on topic through [`KeyProvider`],
+//! which can be backed by external key management service.
+//! [`FileKeyProvider`] reads hex encoded keys from files of a directory.
+//!
+use std::fmt;
+use std::io::Error as IoError;
+use std::path::{Path, PathBuf};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::rand::{SecureRandom, SystemRandom};
+
+use fluvio_future::fs::read_to_string;
+use fluvio_protocol::record::{Batch, Offset, RawRecords, ReplicaKey};
+
+/// length of AES-256 key in bytes
+pub const KEY_LEN: usize = 32;
+
+#[derive(Debug, thiserror::Error)]
+pub enum EncryptionError {
+    #[error("Encryption key not found: {0}")]
+    KeyNotFound(String),
+    #[error("Invalid encryption key {key_id}: {reason}")]
+    InvalidKey { key_id: String, reason: String },
+    #[error("Unable to encrypt records")]
+    Encrypt,
+    #[error("Unable to decrypt records at offset: {0}")]
+    Decrypt(i64),
+    #[error(transparent)]
+    Io(#[from] IoError),
+}
+
+/// Raw AES-256 key
+#[derive(Clone, Eq, PartialEq)]
+pub struct EncryptionKey([u8; KEY_LEN]);
+
+impl EncryptionKey {
+    pub fn new(key: [u8; KEY_LEN]) -> Self {
+        Self(key)
+    }
+
+    /// parse key from 64 hex characters
+    pub fn from_hex(key_id: &str, hex: &str) -> Result<Self, EncryptionError> {
+        let invalid = |reason: &str| EncryptionError::InvalidKey {
+            key_id: key_id.to_owned(),
+            reason: reason.to_owned(),
+        };
+        let hex = hex.trim().as_bytes();
+        if hex.len() != KEY_LEN * 2 {
+            return Err(invalid("key must be 64 hex characters"));
+        }
+        let mut key = [0u8; KEY_LEN];
+        for (byte, pair) in key.iter_mut().zip(hex.chunks(2)) {
+            let pair = std::str::from_utf8(pair).map_err(|_| invalid("key is not hex"))?;
+            *byte = u8::from_str_radix(pair, 16).map_err(|_| invalid("key is not hex"))?;
+        }
+        Ok(Self(key))
+    }
+}
+
+// key material is never printed
+impl fmt::Debug for EncryptionKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "EncryptionKey(..)")
+    }
+}
+
+/// Source of topic encryption keys
+#[async_trait]
+pub trait KeyProvider: fmt::Debug + Send + Sync {
+    /// key for `key_id` configured on topic
+    async fn key(&self, key_id: &str) -> Result<EncryptionKey, EncryptionError>;
+}
+
+/// Reads key of each key id from file with the same name in a directory
+#[derive(Debug, Clone)]
+pub struct FileKeyProvider {
+    dir: PathBuf,
+}
+
+impl FileKeyProvider {
+    pub fn new(dir: impl AsRef<Path>) -> Self {
+        Self {
+            dir: dir.as_ref().to_owned(),
+        }
+    }
+}
+
+#[async_trait]
+impl KeyProvider for FileKeyProvider {
+    async fn key(&self, key_id: &str) -> Result<EncryptionKey, EncryptionError> {
+        // key id must not escape key directory
+        if key_id.is_empty() || key_id.contains(['/', '\\']) || key_id.starts_with('.') {
+            return Err(EncryptionError::InvalidKey {
+                key_id: key_id.to_owned(),
+                reason: "invalid key id".to_owned(),
+            });
+        }
+        let path = self.dir.join(key_id);
+        let hex = match read_to_string(&path).await {
+            Ok(hex) => hex,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                return Err(EncryptionError::KeyNotFound(key_id.to_owned()))
+            }
+            Err(err) => return Err(err.into()),
+        };
+        EncryptionKey::from_hex(key_id, &hex)
+    }
+}
+
+/// Encrypts and decrypts records of batches of a replica with a single key
+pub struct BatchCipher {
+    key: LessSafeKey,
+    rng: SystemRandom,
+    replica: ReplicaKey,
+}
+
+impl fmt::Debug for BatchCipher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "BatchCipher")
+    }
+}
+
+impl BatchCipher {
+    pub fn new(key: &EncryptionKey, replica: ReplicaKey) -> Self {
+        let key = UnboundKey::new(&AES_256_GCM, &key.0).expect("AES-256 key length");
+        Self {
+            key: LessSafeKey::new(key),
+            rng: SystemRandom::new(),
+            replica,
+        }
+    }
+
+    /// encrypt records of batch at its base offset, which must be the offset it's stored at.
+    /// Batches which are already encrypted and control batches are left as they are
+    pub fn encrypt(&self, batch: &mut Batch<RawRecords>) -> Result<(), EncryptionError> {
+        if batch.header.is_encrypted() || batch.header.is_control() {
+            return Ok(());
+        }
+
+        let sealed = self.seal(batch.base_offset, &batch.records().0)?;
+        replace_records(batch, sealed);
+        batch.header.set_encrypted(true);
+        Ok(())
+    }
+
+    /// decrypt records of batch, batches which are not encrypted are left as they are
+    pub fn decrypt(&self, batch: &mut Batch<RawRecords>) -> Result<(), EncryptionError> {
+        if !batch.header.is_encrypted() {
+            return Ok(());
+        }
+
+        let plain = self.open(batch.base_offset, &batch.records().0)?;
+        replace_records(batch, plain);
+        batch.header.set_encrypted(false);
+        Ok(())
+    }
+
+    /// encrypt records of batch at `base_offset`, output is nonce followed by ciphertext and tag
+    pub fn seal(&self, base_offset: Offset, records: &[u8]) -> Result<Vec<u8>, EncryptionError> {
+        let mut nonce = [0u8; NONCE_LEN];
+        self.rng
+            .fill(&mut nonce)
+            .map_err(|_| EncryptionError::Encrypt)?;
+        let mut sealed = records.to_vec();
+        self.key
+            .seal_in_place_append_tag(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::from(self.aad(base_offset)),
+                &mut sealed,
+            )
+            .map_err(|_| EncryptionError::Encrypt)?;
+
+        let mut output = Vec::with_capacity(NONCE_LEN + sealed.len());
+        output.extend_from_slice(&nonce);
+        output.extend_from_slice(&sealed);
+        Ok(output)
+    }
+
+    /// decrypt records sealed by [`seal`](Self::seal) of batch at `base_offset`
+    pub fn open(&self, base_offset: Offset, sealed: &[u8]) -> Result<Vec<u8>, EncryptionError> {
+        if sealed.len() < NONCE_LEN {
+            return Err(EncryptionError::Decrypt(base_offset));
+        }
+        let nonce = Nonce::try_assume_unique_for_key(&sealed[..NONCE_LEN])
+            .map_err(|_| EncryptionError::Decrypt(base_offset))?;
+        let mut plain = sealed[NONCE_LEN..].to_vec();
+        let plain_len = self
+            .key
+            .open_in_place(nonce, Aad::from(self.aad(base_offset)), &mut plain)
+            .map_err(|_| EncryptionError::Decrypt(base_offset))?
+            .len();
+        plain.truncate(plain_len);
+        Ok(plain)
+    }
+
+    /// topic followed by partition and base offset, which have fixed size
+    fn aad(&self, base_offset: Offset) -> Vec<u8> {
+        let topic = self.replica.topic.as_bytes();
+        let mut aad = Vec::with_capacity(topic.len() + 12);
+        aad.extend_from_slice(topic);
+        aad.extend_from_slice(&self.replica.partition.to_be_bytes());
+        aad.extend_from_slice(&base_offset.to_be_bytes());
+        aad
+    }
+}
+
+fn replace_records(batch: &mut Batch<RawRecords>, records: Vec<u8>) {
+    let old_len = batch.records().0.len() as i32;
+    batch.batch_len += records.len() as i32 - old_len;
+    *batch.mut_records() = RawRecords(Bytes::from(records));
+}
+
+#[cfg(test)]
+mod tests {
+    use fluvio_protocol::record::Record;
+    use flv_util::fixture::ensure_new_dir;
+
+    use super::*;
+
+    fn test_batch() -> Batch<RawRecords> {
+        let mut batch: Batch = Batch::default();
+        batch.add_record(Record::new("hello"));
+        batch.add_record(Record::new("world"));
+        batch.try_into().expect("raw batch")
+    }
+
+    #[test]
+    fn test_encrypt_decrypt_batch() {
+        let cipher = BatchCipher::new(
+            &EncryptionKey::new([7u8; KEY_LEN]),
+            ReplicaKey::new("orders", 0u32),
+        );
+        let mut batch = test_batch();
+        let plain = batch.records().0.clone();
+        let batch_len = batch.batch_len;
+
+        cipher.encrypt(&mut batch).expect("encrypt");
+        assert!(batch.header.is_encrypted());
+        assert_ne!(batch.records().0, plain);
+
+        // encrypting twice has no effect
+        let encrypted = batch.records().0.clone();
+        cipher.encrypt(&mut batch).expect("encrypt");
+        assert_eq!(batch.records().0, encrypted);
+
+        cipher.decrypt(&mut batch).expect("decrypt");
+        assert!(!batch.header.is_encrypted());
+        assert_eq!(batch.records().0, plain);
+        assert_eq!(batch.batch_len, batch_len);
+    }
+
+    #[test]
+    fn test_decrypt_with_wrong_key() {
+        let mut batch = test_batch();
+        let replica = ReplicaKey::new("orders", 0u32);
+        BatchCipher::new(&EncryptionKey::new([1u8; KEY_LEN]), replica.clone())
+            .encrypt(&mut batch)
+            .expect("encrypt");
+
+        let result =
+            BatchCipher::new(&EncryptionKey::new([2u8; KEY_LEN]), replica).decrypt(&mut batch);
+        assert!(matches!(result, Err(EncryptionError::Decrypt(_))));
+    }
+
+    #[test]
+    fn test_decrypt_moved_batch() {
+        let key = EncryptionKey::new([1u8; KEY_LEN]);
+        let mut batch = test_batch();
+        batch.set_base_offset(10);
+        BatchCipher::new(&key, ReplicaKey::new("orders", 0u32))
+            .encrypt(&mut batch)
+            .expect("encrypt");
+
+        // other partition
+        let mut moved = batch.clone();
+        let result = BatchCipher::new(&key, ReplicaKey::new("orders", 1u32)).decrypt(&mut moved);
+        assert!(matches!(result, Err(EncryptionError::Decrypt(10))));
+
+        // other offset
+        let mut moved = batch.clone();
+        moved.set_base_offset(20);
+        let result = BatchCipher::new(&key, ReplicaKey::new("orders", 0u32)).decrypt(&mut moved);
+        assert!(matches!(result, Err(EncryptionError::Decrypt(20))));
+
+        BatchCipher::new(&key, ReplicaKey::new("orders", 0u32))
+            .decrypt(&mut batch)
+            .expect("decrypt");
+    }
+
+    #[fluvio_future::test]
+    async fn test_file_key_provider() {
+        let dir = std::env::temp_dir().join("encryption-keys");
+        ensure_new_dir(&dir).expect("dir");
+        std::fs::write(dir.join("orders"), format!("{}\n", "0a".repeat(KEY_LEN))).expect("write");
+        std::fs::write(dir.join("short"), "0a0b").expect("write");
+
+        let provider = FileKeyProvider::new(&dir);
+        assert_eq!(
+            provider.key("orders").await.expect("key"),
+            EncryptionKey::new([10u8; KEY_LEN])
+        );
+        assert!(matches!(
+            provider.key("missing").await,
+            Err(EncryptionError::KeyNotFound(_))
+        ));
+        assert!(matches!(
+            provider.key("short").await,
+            Err(EncryptionError::InvalidKey { .. })
+        ));
+        assert!(matches!(
+            provider.key("../orders").await,
+            Err(EncryptionError::InvalidKey { .. })
+        ));
+    }
+}
@@ -112,6 +112,14 @@ impl LogIndex {
 
         debug!(?index_file_path, "opening index");
 
+        // memory mapping panics on missing file, caller rebuilds index from log
+        if !index_file_path.exists() {
+            return Err(IoError::new(
+                ErrorKind::NotFound,
+                format!("index file not found: {}", index_file_path.display()),
+            ));
+        }
+
         // make sure it is log file
         let (m_file, file) = MemoryMappedFile::open(&index_file_path, INDEX_ENTRY_SIZE).await?;
 
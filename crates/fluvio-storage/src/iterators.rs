@@ -1,4 +1,5 @@
 use std::collections::VecDeque;
+use std::sync::Arc;
 use std::os::fd::BorrowedFd;
 use std::os::unix::io::RawFd;
 use std::io::{Error as IoError, ErrorKind, Cursor};
@@ -11,6 +12,8 @@ use fluvio_protocol::{Decoder, Version};
 use fluvio_protocol::record::{Batch, Offset, BATCH_FILE_HEADER_SIZE, BATCH_HEADER_SIZE, Record};
 use fluvio_future::file_slice::AsyncFileSlice;
 
+use crate::encryption::BatchCipher;
+
 // only encode information necessary to decode batches efficiently
 pub struct FileBatch {
     pub batch: Batch,
@@ -29,6 +32,7 @@ pub struct FileBatchIterator {
     fd: RawFd,
     offset: Offset,
     end: i64,
+    cipher: Option<Arc<BatchCipher>>,
 }
 
 impl FileBatchIterator {
@@ -38,6 +42,7 @@ impl FileBatchIterator {
             fd,
             offset,
             end: offset + len,
+            cipher: None,
         }
     }
 
@@ -48,8 +53,16 @@ impl FileBatchIterator {
             fd: slice.as_raw_fd(),
             offset,
             end: offset + slice.len() as i64,
+            cipher: None,
         }
     }
+
+    /// decrypt records of encrypted batches with cipher.
+    /// Without cipher, records of encrypted batches are returned as they are stored
+    pub fn cipher(mut self, cipher: Option<Arc<BatchCipher>>) -> Self {
+        self.cipher = cipher;
+        self
+    }
 }
 
 impl Iterator for FileBatchIterator {
@@ -133,6 +146,23 @@ impl Iterator for FileBatchIterator {
             )));
         }
 
+        self.offset += bytes_read as i64;
+
+        if batch.header.is_encrypted() {
+            let Some(cipher) = &self.cipher else {
+                // header only readers don't need records
+                return Some(Ok(FileBatch {
+                    batch,
+                    records: raw_records,
+                }));
+            };
+            raw_records = match cipher.open(batch.base_offset, &raw_records) {
+                Ok(records) => records,
+                Err(err) => return Some(Err(IoError::new(ErrorKind::InvalidData, err))),
+            };
+            batch.header.set_encrypted(false);
+        }
+
         let compression = match batch.get_compression() {
             Ok(compression) => compression,
             Err(err) => {
@@ -154,8 +184,6 @@ impl Iterator for FileBatchIterator {
             }
         };
 
-        self.offset += bytes_read as i64;
-
         Some(Ok(FileBatch { batch, records }))
     }
 }
@@ -190,6 +218,16 @@ impl<T: Iterator<Item = Result<FileBatch, IoError>>> Iterator for FileRecordIter
                     Err(err) => return Some(Err(err)),
                 };
 
+                if next_batch.batch.header.is_encrypted() {
+                    return Some(Err(IoError::new(
+                        ErrorKind::InvalidData,
+                        format!(
+                            "batch at offset {} is encrypted",
+                            next_batch.batch.base_offset
+                        ),
+                    )));
+                }
+
                 let base_offset = next_batch.batch.base_offset;
                 let base_timestamp = next_batch.batch.header.first_timestamp;
 
@@ -311,6 +349,72 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_encrypted_file_batch_iterator() -> anyhow::Result<()> {
+        use fluvio_protocol::record::{RawRecords, ReplicaKey};
+        use crate::encryption::{EncryptionKey, KEY_LEN};
+
+        //given
+        let base_dir = temp_dir().join("test_encrypted_file_batch_iterator");
+        let topic = format!(
+            "test_encrypted_file_batch_iterator_{}",
+            SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)?
+                .as_millis()
+        );
+        let mut replica = run_block_on(FileReplica::create_or_load_with_storage(
+            topic.clone(),
+            Default::default(),
+            Default::default(),
+            ReplicaConfigBuilder::default().base_dir(base_dir).build(),
+            Arc::new(StorageConfigBuilder::default().build()?),
+        ))?;
+        let cipher = Arc::new(BatchCipher::new(
+            &EncryptionKey::new([5u8; KEY_LEN]),
+            ReplicaKey::new(topic, 0u32),
+        ));
+
+        let mut batch = Batch::default();
+        batch.add_record(Record::new("secret"));
+        let mut batch: Batch<RawRecords> = batch.try_into()?;
+        cipher.encrypt(&mut batch)?;
+        let mut records = RecordSet {
+            batches: vec![batch],
+        };
+        run_block_on(replica.write_recordset(&mut records, false))?;
+
+        let read_slice = || {
+            run_block_on(replica.read_partition_slice(
+                0,
+                u32::MAX,
+                fluvio_spu_schema::Isolation::ReadUncommitted,
+            ))
+            .map(|slice| slice.file_slice.expect("file slice"))
+        };
+
+        //when
+        let records: Vec<RecordItem> = FileRecordIterator::new(
+            FileBatchIterator::from_raw_slice(read_slice()?).cipher(Some(cipher)),
+            0,
+        )
+        .collect::<Result<Vec<RecordItem>, std::io::Error>>()?;
+
+        //then
+        assert_eq!(records.len(), 1);
+        assert_eq!(std::str::from_utf8(records[0].record.value())?, "secret");
+
+        // without cipher header is readable but records are not
+        let batch = FileBatchIterator::from_raw_slice(read_slice()?)
+            .next()
+            .expect("batch")?;
+        assert!(batch.batch.header.is_encrypted());
+        let res = FileRecordIterator::new(FileBatchIterator::from_raw_slice(read_slice()?), 0)
+            .collect::<Result<Vec<RecordItem>, std::io::Error>>();
+        assert_eq!(res.unwrap_err().kind(), std::io::ErrorKind::InvalidData);
+
+        Ok(())
+    }
+
     #[test]
     fn test_file_record_iterator_error_propagated() -> anyhow::Result<()> {
         //given
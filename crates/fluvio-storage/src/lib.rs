@@ -19,12 +19,21 @@ pub mod iterators;
 #[cfg(feature = "fixture")]
 pub mod fixture;
 mod cleaner;
+pub mod scrubber;
+pub mod preload;
+pub mod encryption;
+pub mod snapshot;
+pub mod recovery;
+pub mod backend;
+#[cfg(all(feature = "memory", target_os = "linux"))]
+pub mod memory;
 
 pub use crate::error::StorageError;
 pub use crate::records::FileRecordsSlice;
 pub use crate::index::LogIndex;
 pub use crate::index::OffsetPosition;
 pub use crate::replica::FileReplica;
+pub use crate::mut_records::LogSync;
 
 pub use inner::*;
 mod inner {
@@ -39,6 +48,9 @@ mod inner {
     use fluvio_protocol::record::RecordSet;
     use fluvio_future::file_slice::AsyncFileSlice;
     use fluvio_controlplane::replica::Replica;
+    use fluvio_controlplane_metadata::partition::StorageUsage;
+
+    use crate::LogSync;
 
     #[derive(Debug, Clone, Eq, PartialEq)]
     pub struct OffsetInfo {
@@ -154,8 +166,31 @@ mod inner {
             isolation: Isolation,
         ) -> Result<ReplicaSlice, ErrorCode>;
 
+        /// read partition slice with records before `max_offset` only
+        /// return hw and leo
+        async fn read_partition_slice_before(
+            &self,
+            offset: Offset,
+            max_offset: Offset,
+            max_len: u32,
+        ) -> Result<ReplicaSlice, ErrorCode>;
+
         fn get_partition_size(&self) -> Size64;
 
+        /// bytes used by segments and indexes, storage without indexes reports all as segments
+        fn get_storage_usage(&self) -> StorageUsage {
+            StorageUsage {
+                segment_bytes: self.get_partition_size(),
+                ..Default::default()
+            }
+        }
+
+        /// handle to sync written records outside of write,
+        /// None if records are synced by `write_recordset`
+        fn log_sync(&self) -> Option<LogSync> {
+            None
+        }
+
         /// write record set
         async fn write_recordset<R: BatchRecords>(
             &mut self,
@@ -165,8 +200,18 @@ mod inner {
 
         async fn update_high_watermark(&mut self, offset: Offset) -> Result<bool, StorageError>;
 
+        /// delete committed records before offset, returns new log start offset.
+        /// Records are logically deleted, reads before log start offset must fail
+        async fn delete_records_before(&mut self, offset: Offset) -> Result<Offset, StorageError>;
+
         /// permanently remove
         async fn remove(&self) -> Result<(), StorageError>;
+
+        /// read state saved with `write_state`, None if it was never saved
+        async fn read_state(&self, name: &str) -> Result<Option<Vec<u8>>, StorageError>;
+
+        /// save state derived from records next to them, storage without place for it drops it
+        async fn write_state(&self, name: &str, state: &[u8]) -> Result<(), StorageError>;
     }
 
     #[cfg(test)]
@@ -0,0 +1,330 @@
+//!
+//! # In-memory replica
+//!
+//! Keeps batches of replica in anonymous memory file, so records can still be sent to consumers
+//! as file slices. Records are lost when SPU restarts, meant for tests and scratch topics.
+//!
+use std::cmp::min;
+use std::ffi::CString;
+use std::fs::File;
+use std::io::Error as IoError;
+use std::io::ErrorKind;
+use std::os::unix::fs::FileExt;
+use std::os::unix::prelude::{AsRawFd, FromRawFd};
+
+use async_trait::async_trait;
+use anyhow::Result;
+use tracing::debug;
+
+use fluvio_future::file_slice::AsyncFileSlice;
+use fluvio_protocol::Encoder;
+use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::record::{BatchRecords, Offset, RecordSet, ReplicaKey, Size64};
+use fluvio_spu_schema::Isolation;
+
+use crate::config::ReplicaConfig;
+use crate::{OffsetInfo, ReplicaSlice, ReplicaStorage, StorageError};
+
+#[derive(Debug)]
+struct BatchPosition {
+    base_offset: Offset,
+    position: u64,
+    len: u64,
+}
+
+#[derive(Debug)]
+pub struct MemoryReplica {
+    file: File,
+    max_batch_size: usize,
+    batches: Vec<BatchPosition>,
+    /// end of written batches in file
+    end_position: u64,
+    log_start_offset: Offset,
+    hw: Offset,
+    leo: Offset,
+}
+
+impl MemoryReplica {
+    fn read_records(
+        &self,
+        start_offset: Offset,
+        max_offset: Option<Offset>,
+        max_len: u32,
+    ) -> Result<ReplicaSlice, ErrorCode> {
+        let mut slice = ReplicaSlice {
+            end: OffsetInfo {
+                hw: self.hw,
+                leo: self.leo,
+            },
+            start: self.log_start_offset,
+            ..Default::default()
+        };
+
+        if start_offset < slice.start {
+            return Err(ErrorCode::OffsetEvicted {
+                offset: start_offset,
+                next_available: slice.start,
+            });
+        }
+        if start_offset == self.leo {
+            return Ok(slice);
+        }
+        if start_offset > self.leo {
+            return Err(ErrorCode::Other(format!(
+                "start offset: {start_offset} is greater than leo: {}",
+                self.leo
+            )));
+        }
+
+        // batch containing start offset
+        let first = self
+            .batches
+            .partition_point(|batch| batch.base_offset <= start_offset)
+            .saturating_sub(1);
+        let end = match max_offset {
+            Some(max_offset) => self
+                .batches
+                .partition_point(|batch| batch.base_offset < max_offset),
+            None => self.batches.len(),
+        };
+        if end <= first {
+            return Ok(slice);
+        }
+
+        let position = self.batches[first].position;
+        let last = &self.batches[end - 1];
+        let len = last.position + last.len - position;
+        slice.file_slice = Some(AsyncFileSlice::new(
+            self.file.as_raw_fd(),
+            position,
+            min(len, max_len as u64),
+        ));
+        Ok(slice)
+    }
+
+    /// release memory of file before position
+    fn punch_hole(&self, end_position: u64) -> Result<(), StorageError> {
+        let res = unsafe {
+            libc::fallocate(
+                self.file.as_raw_fd(),
+                libc::FALLOC_FL_PUNCH_HOLE | libc::FALLOC_FL_KEEP_SIZE,
+                0,
+                end_position as libc::off_t,
+            )
+        };
+        if res < 0 {
+            return Err(IoError::last_os_error().into());
+        }
+        Ok(())
+    }
+}
+
+fn memory_file(replica: &ReplicaKey) -> Result<File, IoError> {
+    let name = CString::new(format!("replica-{replica}"))
+        .map_err(|err| IoError::new(ErrorKind::InvalidInput, err))?;
+    let fd = unsafe { libc::memfd_create(name.as_ptr(), libc::MFD_CLOEXEC) };
+    if fd < 0 {
+        return Err(IoError::last_os_error());
+    }
+    Ok(unsafe { File::from_raw_fd(fd) })
+}
+
+#[async_trait]
+impl ReplicaStorage for MemoryReplica {
+    type ReplicaConfig = ReplicaConfig;
+
+    /// memory replica always starts empty
+    async fn create_or_load(
+        replica: &ReplicaKey,
+        replica_config: Self::ReplicaConfig,
+    ) -> Result<Self> {
+        debug!(%replica, "creating memory replica");
+        Ok(Self {
+            file: memory_file(replica)?,
+            max_batch_size: replica_config.max_batch_size as usize,
+            batches: vec![],
+            end_position: 0,
+            log_start_offset: 0,
+            hw: 0,
+            leo: 0,
+        })
+    }
+
+    fn get_hw(&self) -> Offset {
+        self.hw
+    }
+
+    fn get_leo(&self) -> Offset {
+        self.leo
+    }
+
+    fn get_log_start_offset(&self) -> Offset {
+        self.log_start_offset
+    }
+
+    async fn read_partition_slice(
+        &self,
+        offset: Offset,
+        max_len: u32,
+        isolation: Isolation,
+    ) -> Result<ReplicaSlice, ErrorCode> {
+        match isolation {
+            Isolation::ReadCommitted => self.read_records(offset, Some(self.hw), max_len),
+            Isolation::ReadUncommitted => self.read_records(offset, None, max_len),
+        }
+    }
+
+    async fn read_partition_slice_before(
+        &self,
+        offset: Offset,
+        max_offset: Offset,
+        max_len: u32,
+    ) -> Result<ReplicaSlice, ErrorCode> {
+        self.read_records(offset, Some(max_offset), max_len)
+    }
+
+    fn get_partition_size(&self) -> Size64 {
+        self.batches.iter().map(|batch| batch.len).sum()
+    }
+
+    async fn write_recordset<R: BatchRecords>(
+        &mut self,
+        records: &mut RecordSet<R>,
+        update_highwatermark: bool,
+    ) -> Result<usize> {
+        for batch in &records.batches {
+            if batch.records_len() == 0 {
+                return Err(StorageError::EmptyBatch.into());
+            }
+            if batch.write_size(0) > self.max_batch_size {
+                return Err(StorageError::BatchTooBig(self.max_batch_size).into());
+            }
+        }
+
+        let mut total_size = 0;
+        for batch in &mut records.batches {
+            batch.set_base_offset(self.leo);
+            let mut buf = Vec::with_capacity(batch.write_size(0));
+            batch.encode(&mut buf, 0)?;
+            self.file.write_all_at(&buf, self.end_position)?;
+            self.batches.push(BatchPosition {
+                base_offset: self.leo,
+                position: self.end_position,
+                len: buf.len() as u64,
+            });
+            self.end_position += buf.len() as u64;
+            self.leo = batch.get_last_offset() + 1;
+            total_size += buf.len();
+        }
+
+        if update_highwatermark {
+            self.hw = self.leo;
+        }
+        Ok(total_size)
+    }
+
+    async fn update_high_watermark(&mut self, offset: Offset) -> Result<bool, StorageError> {
+        if self.hw == offset {
+            Ok(false)
+        } else {
+            self.hw = offset;
+            Ok(true)
+        }
+    }
+
+    /// batches ending before new log start are dropped and their memory released
+    async fn delete_records_before(&mut self, offset: Offset) -> Result<Offset, StorageError> {
+        let offset = min(offset, self.hw);
+        if offset <= self.log_start_offset {
+            return Ok(self.log_start_offset);
+        }
+        let containing = self
+            .batches
+            .partition_point(|batch| batch.base_offset <= offset)
+            .saturating_sub(1);
+        if containing > 0 {
+            let end_position = self.batches[containing].position;
+            self.batches.drain(..containing);
+            self.punch_hole(end_position)?;
+        }
+        self.log_start_offset = offset;
+        Ok(offset)
+    }
+
+    async fn remove(&self) -> Result<(), StorageError> {
+        Ok(())
+    }
+
+    /// state is not kept, records are gone after restart too
+    async fn read_state(&self, _name: &str) -> Result<Option<Vec<u8>>, StorageError> {
+        Ok(None)
+    }
+
+    async fn write_state(&self, _name: &str, _state: &[u8]) -> Result<(), StorageError> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use fluvio_protocol::fixture::create_batch;
+    use fluvio_protocol::record::RecordSet;
+    use fluvio_spu_schema::Isolation;
+
+    use crate::config::ReplicaConfig;
+    use crate::ReplicaStorage;
+
+    use super::MemoryReplica;
+
+    #[fluvio_future::test]
+    async fn test_memory_replica_write_read_delete() {
+        let mut replica =
+            MemoryReplica::create_or_load(&("test", 0).into(), ReplicaConfig::builder().build())
+                .await
+                .expect("create");
+
+        replica
+            .write_recordset(&mut RecordSet::default().add(create_batch()), true)
+            .await
+            .expect("write");
+        let first_batch_len = replica.get_partition_size();
+        replica
+            .write_recordset(&mut RecordSet::default().add(create_batch()), false)
+            .await
+            .expect("write");
+        assert_eq!(replica.get_hw(), 2);
+        assert_eq!(replica.get_leo(), 4);
+
+        let committed = replica
+            .read_partition_slice(0, 1000, Isolation::ReadCommitted)
+            .await
+            .expect("read");
+        let uncommitted = replica
+            .read_partition_slice(0, 1000, Isolation::ReadUncommitted)
+            .await
+            .expect("read");
+        assert_eq!(committed.file_slice.expect("slice").len(), first_batch_len);
+        assert_eq!(
+            uncommitted.file_slice.expect("slice").len(),
+            replica.get_partition_size()
+        );
+
+        replica.update_high_watermark(4).await.expect("hw");
+        assert_eq!(replica.delete_records_before(3).await.expect("delete"), 3);
+        assert_eq!(replica.get_log_start_offset(), 3);
+        assert!(replica
+            .read_partition_slice(0, 1000, Isolation::ReadCommitted)
+            .await
+            .is_err());
+        let slice = replica
+            .read_partition_slice(3, 1000, Isolation::ReadCommitted)
+            .await
+            .expect("read");
+        assert_eq!(slice.start, 3);
+        assert_eq!(
+            slice.file_slice.expect("slice").len(),
+            replica.get_partition_size()
+        );
+    }
+}
@@ -5,12 +5,13 @@ use std::os::unix::prelude::FromRawFd;
 use std::path::PathBuf;
 use std::path::Path;
 use std::fmt;
-use std::time::{Instant};
-use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::{Duration, Instant};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
 use std::sync::Arc;
 
+use blocking::unblock;
 use tracing::instrument;
-use tracing::{debug, trace};
+use tracing::{debug, trace, warn};
 use futures_lite::io::AsyncWriteExt;
 use async_channel::Sender;
 use anyhow::Result;
@@ -22,6 +23,9 @@ use fluvio_future::fs::BoundedFileSinkError;
 use fluvio_protocol::record::Batch;
 use fluvio_protocol::record::{Offset, Size, Size64};
 use fluvio_protocol::Encoder;
+use fluvio_controlplane_metadata::topic::Durability;
+use fluvio_future::task::spawn;
+use fluvio_future::timer::sleep;
 
 use crate::config::SharedReplicaConfig;
 use crate::mut_index::MutLogIndex;
@@ -48,8 +52,30 @@ pub struct MutFileRecords {
     _flush_policy: FlushPolicy,
     write_count: u64,
     flush_count: Arc<AtomicU32>,
+    durability: Durability,
+    sync_count: Arc<AtomicU32>,
+    sync_pending: Arc<AtomicBool>,
     path: PathBuf,
     _flush_time_tx: Option<Sender<Instant>>,
+    /// set when sync of batch durability is deferred to group commit
+    log_sync: Option<LogSync>,
+}
+
+/// Syncs records appended to log without holding replica,
+/// so single sync covers records of all writes done before it
+#[derive(Debug, Clone)]
+pub struct LogSync {
+    file: Arc<std::fs::File>,
+    sync_count: Arc<AtomicU32>,
+}
+
+impl LogSync {
+    pub async fn sync(&self) -> Result<(), IoError> {
+        let file = self.file.clone();
+        unblock(move || file.sync_data()).await?;
+        self.sync_count.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
 }
 
 impl fmt::Debug for MutFileRecords {
@@ -89,6 +115,19 @@ impl MutFileRecords {
         let metadata = file.metadata().await?;
         let len = metadata.len() as u32;
         debug!(len, "log created");
+        let sync_count = Arc::new(AtomicU32::new(0));
+        let log_sync = if option.group_commit && option.durability == Durability::Batch {
+            // own handle, so sync can run while log is being appended
+            let std_file = unsafe { std::fs::File::from_raw_fd(file.as_raw_fd()) };
+            let cloned = std_file.try_clone();
+            std::mem::forget(std_file);
+            Some(LogSync {
+                file: Arc::new(cloned?),
+                sync_count: sync_count.clone(),
+            })
+        } else {
+            None
+        };
         Ok(MutFileRecords {
             base_offset,
             file,
@@ -97,8 +136,12 @@ impl MutFileRecords {
             _flush_policy: get_flush_policy_from_config(&option),
             write_count: 0,
             flush_count: Arc::new(AtomicU32::new(0)),
+            durability: option.durability,
+            sync_count,
+            sync_pending: Arc::new(AtomicBool::new(false)),
             path: log_path.to_owned(),
             _flush_time_tx: None,
+            log_sync,
         })
     }
 
@@ -161,6 +204,7 @@ impl MutFileRecords {
                 write_count = self.write_count,
                 "Flushing Now"
             );
+            self.sync_for_durability().await?;
 
             /*
             match self.flush_policy.should_flush() {
@@ -199,6 +243,63 @@ impl MutFileRecords {
         self.flush_count.load(Ordering::Relaxed)
     }
 
+    /// sync written records to disk
+    pub async fn sync(&mut self) -> Result<(), IoError> {
+        self.file.sync_data().await?;
+        self.sync_count.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// number of syncs done, including delayed ones
+    pub fn sync_count(&self) -> u32 {
+        self.sync_count.load(Ordering::Relaxed)
+    }
+
+    /// handle to sync log, if syncs are deferred to group commit
+    pub fn log_sync(&self) -> Option<&LogSync> {
+        self.log_sync.as_ref()
+    }
+
+    async fn sync_for_durability(&mut self) -> Result<(), IoError> {
+        match self.durability {
+            Durability::Os => Ok(()),
+            // writer syncs after releasing replica
+            Durability::Batch if self.log_sync.is_some() => Ok(()),
+            Durability::Batch => self.sync().await,
+            Durability::IntervalMs(interval_ms) => {
+                // writes during pending sync are covered by it
+                if self.sync_pending.swap(true, Ordering::AcqRel) {
+                    return Ok(());
+                }
+                // sync own handle, so segment can be closed before sync is done
+                let std_file = unsafe { std::fs::File::from_raw_fd(self.file.as_raw_fd()) };
+                let file = std_file.try_clone();
+                std::mem::forget(std_file);
+                let file = match file {
+                    Ok(file) => file,
+                    Err(err) => {
+                        self.sync_pending.store(false, Ordering::Release);
+                        return Err(err);
+                    }
+                };
+                let pending = self.sync_pending.clone();
+                let sync_count = self.sync_count.clone();
+                let path = self.path.clone();
+                spawn(async move {
+                    sleep(Duration::from_millis(interval_ms as u64)).await;
+                    pending.store(false, Ordering::Release);
+                    match unblock(move || file.sync_data()).await {
+                        Ok(()) => {
+                            sync_count.fetch_add(1, Ordering::Relaxed);
+                        }
+                        Err(err) => warn!(?path, %err, "delayed sync failed"),
+                    }
+                });
+                Ok(())
+            }
+        }
+    }
+
     /*
     async fn delay_flush(&mut self, _delay_millis: u32) -> Result<(), IoError> {
         //let delay_tgt = delay_millis as u64;
@@ -398,6 +499,7 @@ mod tests {
     use crate::config::ReplicaConfig;
     use crate::records::FileRecords;
     use crate::fixture::BatchProducer;
+    use fluvio_controlplane_metadata::topic::Durability;
     use super::MutFileRecords;
 
     #[fluvio_future::test]
@@ -680,4 +782,101 @@ mod tests {
             .expect("check old sink");
         assert_eq!(old_msg_sink.get_base_offset(), OFFSET);
     }
+
+    #[fluvio_future::test]
+    async fn test_write_records_batch_durability() {
+        const OFFSET: Offset = 400;
+
+        let test_dir = temp_dir().join("write_records_batch_durability");
+        ensure_new_dir(&test_dir).expect("new");
+
+        let options = ReplicaConfig {
+            base_dir: test_dir,
+            segment_max_bytes: 1000,
+            durability: Durability::Batch,
+            ..Default::default()
+        }
+        .shared();
+        let mut msg_sink = MutFileRecords::create(OFFSET, options)
+            .await
+            .expect("create");
+        let mut builder = BatchProducer::builder()
+            .base_offset(OFFSET)
+            .build()
+            .expect("build");
+
+        for writes in 1..=3 {
+            msg_sink.write_batch(&builder.batch()).await.expect("write");
+            assert_eq!(msg_sink.sync_count(), writes);
+        }
+    }
+
+    #[fluvio_future::test]
+    async fn test_write_records_interval_durability() {
+        use std::time::Duration;
+        use fluvio_future::timer::sleep;
+
+        const OFFSET: Offset = 500;
+
+        let test_dir = temp_dir().join("write_records_interval_durability");
+        ensure_new_dir(&test_dir).expect("new");
+
+        let options = ReplicaConfig {
+            base_dir: test_dir,
+            segment_max_bytes: 1000,
+            durability: Durability::IntervalMs(50),
+            ..Default::default()
+        }
+        .shared();
+        let mut msg_sink = MutFileRecords::create(OFFSET, options)
+            .await
+            .expect("create");
+        let mut builder = BatchProducer::builder()
+            .base_offset(OFFSET)
+            .build()
+            .expect("build");
+
+        // writes within interval are synced together
+        for _ in 0..3 {
+            msg_sink.write_batch(&builder.batch()).await.expect("write");
+        }
+        assert_eq!(msg_sink.sync_count(), 0);
+
+        sleep(Duration::from_millis(200)).await;
+        assert_eq!(msg_sink.sync_count(), 1);
+    }
+
+    #[fluvio_future::test]
+    async fn test_write_records_group_commit() {
+        const OFFSET: Offset = 600;
+
+        let test_dir = temp_dir().join("write_records_group_commit");
+        ensure_new_dir(&test_dir).expect("new");
+
+        let options = ReplicaConfig {
+            base_dir: test_dir,
+            segment_max_bytes: 1000,
+            durability: Durability::Batch,
+            group_commit: true,
+            ..Default::default()
+        }
+        .shared();
+        let mut msg_sink = MutFileRecords::create(OFFSET, options)
+            .await
+            .expect("create");
+        let mut builder = BatchProducer::builder()
+            .base_offset(OFFSET)
+            .build()
+            .expect("build");
+
+        // sync is left to group commit
+        for _ in 0..3 {
+            msg_sink.write_batch(&builder.batch()).await.expect("write");
+        }
+        assert_eq!(msg_sink.sync_count(), 0);
+
+        let log_sync = msg_sink.log_sync().expect("deferred").clone();
+        log_sync.sync().await.expect("sync");
+        assert_eq!(msg_sink.sync_count(), 1);
+    }
 }
@@ -0,0 +1,159 @@
+//!
+//! # Segment Preloading
+//!
+//! Warms page cache with the tail of replica log, so first fetches after restart
+//! don't wait for disk reads.
+//!
+//! On Linux kernel is asked to read ahead with `posix_fadvise(POSIX_FADV_WILLNEED)`, which
+//! returns right away while pages are loaded in background. On other platforms the bytes
+//! are read and discarded. Index files are memory mapped, mapped pages are served from
+//! the same page cache, so hinting the file is enough for them too.
+//!
+use std::fs::File;
+use std::io::Error as IoError;
+use std::path::{Path, PathBuf};
+
+use blocking::unblock;
+use tracing::{debug, instrument};
+
+use crate::index::EXTENSION as INDEX_EXTENSION;
+
+/// Byte range of segment file to be loaded into page cache
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct PreloadTarget {
+    pub path: PathBuf,
+    pub offset: u64,
+    pub len: u64,
+}
+
+impl PreloadTarget {
+    /// targets for message log of segment and its index, only last `max_len` bytes of
+    /// message log are included. Returns bytes of message log included
+    pub(crate) fn segment(
+        log_path: &Path,
+        log_len: u64,
+        index_len: u64,
+        max_len: u64,
+        targets: &mut Vec<PreloadTarget>,
+    ) -> u64 {
+        let len = log_len.min(max_len);
+        if len == 0 {
+            return 0;
+        }
+        targets.push(PreloadTarget {
+            path: log_path.to_owned(),
+            offset: log_len - len,
+            len,
+        });
+        targets.push(PreloadTarget {
+            path: log_path.with_extension(INDEX_EXTENSION),
+            offset: 0,
+            len: index_len,
+        });
+        len
+    }
+}
+
+/// load range of file into page cache, returns bytes requested
+#[instrument]
+pub async fn preload(target: &PreloadTarget) -> Result<u64, IoError> {
+    let target = target.clone();
+    unblock(move || {
+        let file = File::open(&target.path)?;
+        let file_len = file.metadata()?.len();
+        let offset = target.offset.min(file_len);
+        let len = target.len.min(file_len - offset);
+        will_need(&file, offset, len)?;
+        debug!(path = ?target.path, offset, len, "preloaded");
+        Ok(len)
+    })
+    .await
+}
+
+#[cfg(target_os = "linux")]
+fn will_need(file: &File, offset: u64, len: u64) -> Result<(), IoError> {
+    use std::os::unix::io::AsRawFd;
+
+    let result = unsafe {
+        libc::posix_fadvise(
+            file.as_raw_fd(),
+            offset as libc::off_t,
+            len as libc::off_t,
+            libc::POSIX_FADV_WILLNEED,
+        )
+    };
+    if result != 0 {
+        return Err(IoError::from_raw_os_error(result));
+    }
+    Ok(())
+}
+
+#[cfg(not(target_os = "linux"))]
+fn will_need(mut file: &File, offset: u64, len: u64) -> Result<(), IoError> {
+    use std::io::{Read, Seek, SeekFrom};
+
+    file.seek(SeekFrom::Start(offset))?;
+    std::io::copy(&mut file.take(len), &mut std::io::sink())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use flv_util::fixture::ensure_new_dir;
+
+    use super::*;
+
+    #[test]
+    fn test_segment_targets() {
+        let mut targets = vec![];
+        let log_path = Path::new("/tmp/00000000000000000010.log");
+
+        assert_eq!(
+            PreloadTarget::segment(log_path, 1000, 80, 300, &mut targets),
+            300
+        );
+        assert_eq!(
+            targets,
+            vec![
+                PreloadTarget {
+                    path: log_path.to_owned(),
+                    offset: 700,
+                    len: 300,
+                },
+                PreloadTarget {
+                    path: PathBuf::from("/tmp/00000000000000000010.index"),
+                    offset: 0,
+                    len: 80,
+                },
+            ]
+        );
+
+        // nothing left in budget
+        assert_eq!(
+            PreloadTarget::segment(log_path, 1000, 80, 0, &mut targets),
+            0
+        );
+        assert_eq!(targets.len(), 2);
+    }
+
+    #[fluvio_future::test]
+    async fn test_preload_clamps_to_file() {
+        let dir = std::env::temp_dir().join("preload-test");
+        ensure_new_dir(&dir).expect("dir");
+        let path = dir.join("00000000000000000000.log");
+        File::create(&path)
+            .and_then(|mut file| file.write_all(&[0u8; 100]))
+            .expect("write");
+
+        let len = preload(&PreloadTarget {
+            path,
+            offset: 40,
+            len: 1000,
+        })
+        .await
+        .expect("preload");
+        assert_eq!(len, 60);
+    }
+}
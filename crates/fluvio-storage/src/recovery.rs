@@ -0,0 +1,146 @@
+//!
+//! # Crash Recovery
+//!
+//! Segments written before unclean shutdown may end with torn batch. Active segment is always
+//! truncated to its last valid batch. In recovery audit mode closed segments with torn tail are
+//! truncated too instead of failing replica load, and every truncation is collected in
+//! `RecoveryReport` which is logged once replica is loaded.
+//!
+use std::path::{Path, PathBuf};
+
+use tracing::{info, warn};
+use anyhow::Result;
+use serde::Serialize;
+
+use fluvio_protocol::record::Offset;
+
+/// Segment whose tail was truncated to last valid batch
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct SegmentRecovery {
+    pub base_offset: Offset,
+    /// end offset after truncation
+    pub end_offset: Offset,
+    /// log length before truncation
+    pub original_len: u64,
+    pub discarded_bytes: u64,
+    /// why tail couldn't be decoded
+    pub error: String,
+}
+
+/// Outcome of loading replica after possibly unclean shutdown
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct RecoveryReport {
+    pub replica_dir: PathBuf,
+    pub segments_scanned: u32,
+    pub truncated: Vec<SegmentRecovery>,
+    /// high watermark of checkpoint before recovery
+    pub checkpoint_hw: Offset,
+    /// log end offset after recovery
+    pub leo: Offset,
+}
+
+impl RecoveryReport {
+    pub(crate) fn new(replica_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            replica_dir: replica_dir.into(),
+            ..Default::default()
+        }
+    }
+
+    pub(crate) fn add(&mut self, recovery: Option<SegmentRecovery>) {
+        self.segments_scanned += 1;
+        if let Some(recovery) = recovery {
+            self.truncated.push(recovery);
+        }
+    }
+
+    /// true if nothing was truncated
+    pub fn is_clean(&self) -> bool {
+        self.truncated.is_empty() && self.truncated_offsets() == 0
+    }
+
+    pub fn discarded_bytes(&self) -> u64 {
+        self.truncated
+            .iter()
+            .map(|segment| segment.discarded_bytes)
+            .sum()
+    }
+
+    /// committed offsets missing from recovered log, offsets above checkpoint were never acknowledged
+    pub fn truncated_offsets(&self) -> Offset {
+        (self.checkpoint_hw - self.leo).max(0)
+    }
+
+    pub(crate) fn log(&self) {
+        if self.is_clean() {
+            info!(
+                replica = %self.replica_dir.display(),
+                segments = self.segments_scanned,
+                leo = self.leo,
+                "recovery audit found no torn writes"
+            );
+            return;
+        }
+        for segment in &self.truncated {
+            warn!(
+                replica = %self.replica_dir.display(),
+                base_offset = segment.base_offset,
+                end_offset = segment.end_offset,
+                original_len = segment.original_len,
+                discarded_bytes = segment.discarded_bytes,
+                error = %segment.error,
+                "segment truncated to last valid batch"
+            );
+        }
+        warn!(
+            replica = %self.replica_dir.display(),
+            segments = self.segments_scanned,
+            truncated_segments = self.truncated.len(),
+            discarded_bytes = self.discarded_bytes(),
+            checkpoint_hw = self.checkpoint_hw,
+            leo = self.leo,
+            truncated_offsets = self.truncated_offsets(),
+            "replica recovered from torn writes"
+        );
+    }
+}
+
+/// truncate log file of closed segment to `len`
+pub(crate) async fn truncate_log(path: &Path, len: u64) -> Result<()> {
+    let file = fluvio_future::fs::util::open_read_append(path).await?;
+    file.set_len(len).await?;
+    file.sync_all().await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recovery_report_totals() {
+        let mut report = RecoveryReport::new("/tmp/test-0");
+        report.add(None);
+        report.add(Some(SegmentRecovery {
+            base_offset: 10,
+            end_offset: 18,
+            original_len: 1000,
+            discarded_bytes: 40,
+            error: "torn".to_owned(),
+        }));
+        report.checkpoint_hw = 20;
+        report.leo = 18;
+
+        assert!(!report.is_clean());
+        assert_eq!(report.segments_scanned, 2);
+        assert_eq!(report.discarded_bytes(), 40);
+        assert_eq!(report.truncated_offsets(), 2);
+
+        let mut clean = RecoveryReport::new("/tmp/test-1");
+        clean.add(None);
+        clean.checkpoint_hw = 5;
+        clean.leo = 7;
+        assert!(clean.is_clean());
+        assert_eq!(clean.truncated_offsets(), 0);
+    }
+}
@@ -1,4 +1,5 @@
 use std::cmp::min;
+use std::io::ErrorKind;
 use std::mem;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering};
@@ -9,20 +10,25 @@ use anyhow::Result;
 
 use fluvio_future::file_slice::AsyncFileSlice;
 use fluvio_protocol::Encoder;
-use fluvio_future::fs::{create_dir_all, remove_dir_all};
+use futures_lite::io::{AsyncReadExt, AsyncWriteExt};
+use fluvio_future::fs::{create_dir_all, remove_dir_all, rename, File};
 use fluvio_protocol::link::ErrorCode;
 use fluvio_spu_schema::Isolation;
 use fluvio_protocol::record::{Offset, ReplicaKey, Size, Size64};
 use fluvio_protocol::record::{Batch, BatchRecords};
 use fluvio_protocol::record::RecordSet;
+use fluvio_controlplane_metadata::partition::StorageUsage;
 
 use crate::{OffsetInfo, checkpoint::CheckPoint};
-use crate::segments::SharedSegments;
-use crate::segment::MutableSegment;
+use crate::segments::{SegmentList, SharedSegments};
+use crate::segment::{MutableSegment, ReadSegment};
 use crate::config::{ReplicaConfig, SharedReplicaConfig, StorageConfig};
-use crate::ReplicaSlice;
+use crate::{LogSync, ReplicaSlice};
 use crate::{StorageError, ReplicaStorage};
 use crate::cleaner::Cleaner;
+use crate::scrubber::ScrubTarget;
+use crate::preload::PreloadTarget;
+use crate::recovery::RecoveryReport;
 
 /// Replica is public abstraction for commit log which are distributed.
 /// Internally it is stored as list of segments.  Each segment contains finite sets of record batches.
@@ -37,14 +43,21 @@ pub struct FileReplica {
     active_segment: MutableSegment,
     prev_segments: Arc<SharedSegments>,
     commit_checkpoint: CheckPoint<Offset>,
+    /// log start offset advanced by deleting records, segments may start before it
+    log_start_checkpoint: CheckPoint<Offset>,
     cleaner: Arc<Cleaner>,
     size: Arc<ReplicaSize>,
+    /// truncations done while loading, only collected in recovery audit mode
+    recovery: Option<RecoveryReport>,
 }
 
 #[derive(Debug, Default)]
 pub(crate) struct ReplicaSize {
     active_segment: AtomicU64,
     prev_segments: AtomicU64,
+    /// index part of segment sizes
+    active_index: AtomicU64,
+    prev_index: AtomicU64,
 }
 
 impl Unpin for FileReplica {}
@@ -85,11 +98,12 @@ impl ReplicaStorage for FileReplica {
     /// earliest offset
     fn get_log_start_offset(&self) -> Offset {
         let min_base_offset = self.prev_segments.min_offset();
-        if min_base_offset < 0 {
+        let segment_start = if min_base_offset < 0 {
             self.active_segment.get_base_offset()
         } else {
             min_base_offset
-        }
+        };
+        segment_start.max(*self.log_start_checkpoint.get_offset())
     }
 
     /// read partition slice
@@ -101,6 +115,13 @@ impl ReplicaStorage for FileReplica {
         max_len: u32,
         isolation: Isolation,
     ) -> Result<ReplicaSlice, ErrorCode> {
+        #[cfg(feature = "fault-injection")]
+        if inject_fault(fluvio_types::fault::STORAGE_READ, &self.option.base_dir)
+            .await
+            .map_err(|_| ErrorCode::StorageError)?
+        {
+            return Ok(ReplicaSlice::default());
+        }
         match isolation {
             Isolation::ReadCommitted => {
                 self.read_records(offset, Some(self.get_hw()), max_len)
@@ -110,6 +131,16 @@ impl ReplicaStorage for FileReplica {
         }
     }
 
+    #[instrument(skip(self, offset, max_offset, max_len))]
+    async fn read_partition_slice_before(
+        &self,
+        offset: Offset,
+        max_offset: Offset,
+        max_len: u32,
+    ) -> Result<ReplicaSlice, ErrorCode> {
+        self.read_records(offset, Some(max_offset), max_len).await
+    }
+
     /// return the size in bytes (includes index size and log size)
     #[instrument(skip(self))]
     fn get_partition_size(&self) -> Size64 {
@@ -123,6 +154,14 @@ impl ReplicaStorage for FileReplica {
         total_prev_segments_len + active_len
     }
 
+    fn get_storage_usage(&self) -> StorageUsage {
+        self.size.usage()
+    }
+
+    fn log_sync(&self) -> Option<LogSync> {
+        self.active_segment.log_sync().cloned()
+    }
+
     /// write records to this replica
     /// if update_highwatermark is set, set high watermark is end
     //  this is used when LRS = 1
@@ -151,6 +190,11 @@ impl ReplicaStorage for FileReplica {
             }
         }
 
+        #[cfg(feature = "fault-injection")]
+        if inject_fault(fluvio_types::fault::STORAGE_WRITE, &self.option.base_dir).await? {
+            return Ok(total_size);
+        }
+
         for batch in &mut records.batches {
             self.write_batch(batch).await?;
         }
@@ -183,6 +227,30 @@ impl ReplicaStorage for FileReplica {
         }
     }
 
+    /// delete records before `offset` by advancing log start offset.
+    /// Only committed records can be deleted, offset is capped at high watermark.
+    /// Segments which end before new log start are removed, records of segment containing it
+    /// are no longer readable.  Returns new log start offset
+    #[instrument(skip(self))]
+    async fn delete_records_before(&mut self, offset: Offset) -> Result<Offset, StorageError> {
+        let log_start = self.get_log_start_offset();
+        let offset = offset.min(self.get_hw());
+        if offset <= log_start {
+            debug!(offset, log_start, "records already deleted");
+            return Ok(log_start);
+        }
+        self.log_start_checkpoint.write(offset).await?;
+
+        let segments = self.prev_segments.read().await.find_ending_before(offset);
+        if !segments.is_empty() {
+            self.prev_segments.remove_segments(&segments).await;
+            let read = self.prev_segments.read().await;
+            self.size.store_prev_segments(&read);
+        }
+        info!(offset, removed_segments = segments.len(), "deleted records");
+        Ok(offset)
+    }
+
     #[instrument(skip(self))]
     async fn remove(&self) -> Result<(), StorageError> {
         remove_dir_all(&self.option.base_dir)
@@ -192,6 +260,28 @@ impl ReplicaStorage for FileReplica {
         self.cleaner.shutdown();
         Ok(())
     }
+
+    async fn read_state(&self, name: &str) -> Result<Option<Vec<u8>>, StorageError> {
+        let mut file = match File::open(self.option.base_dir.join(name)).await {
+            Ok(file) => file,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+        let mut state = vec![];
+        file.read_to_end(&mut state).await?;
+        Ok(Some(state))
+    }
+
+    /// state is written to temporary file and renamed, so it is either old or new one after crash
+    async fn write_state(&self, name: &str, state: &[u8]) -> Result<(), StorageError> {
+        let path = self.option.base_dir.join(name);
+        let tmp_path = path.with_extension("tmp");
+        let mut file = File::create(&tmp_path).await?;
+        file.write_all(state).await?;
+        file.sync_all().await?;
+        rename(&tmp_path, &path).await?;
+        Ok(())
+    }
 }
 
 impl FileReplica {
@@ -235,13 +325,16 @@ impl FileReplica {
 
         let shared_config: Arc<SharedReplicaConfig> = Arc::new(rep_option.into());
 
-        let (segments, last_offset_res) = SharedSegments::from_dir(shared_config.clone()).await?;
+        let mut recovery = RecoveryReport::new(&shared_config.base_dir);
+        let (segments, last_offset_res) =
+            SharedSegments::from_dir_with_recovery(shared_config.clone(), &mut recovery).await?;
 
         let active_segment = if let Some(last_offset) = last_offset_res {
             debug!(last_offset, "last segment found, validating offsets");
             let mut last_segment =
                 MutableSegment::open_for_write(last_offset, shared_config.clone()).await?;
-            last_segment.validate_and_repair().await?;
+            let (_, segment_recovery) = last_segment.validate_and_recover().await?;
+            recovery.add(segment_recovery);
             info!(
                 end_offset = last_segment.get_end_offset(),
                 "existing segment validated with last offset",
@@ -267,8 +360,20 @@ impl FileReplica {
             );
             commit_checkpoint.write(leo).await?;
         }
+        recovery.checkpoint_hw = hw;
+        recovery.leo = leo;
+        let recovery = shared_config.recovery_audit.then(|| {
+            recovery.log();
+            recovery
+        });
 
+        let log_start_checkpoint: CheckPoint<Offset> =
+            CheckPoint::create(shared_config.clone(), "log_start.chk", 0).await?;
+
+        // account for segments loaded from disk
         let size = Arc::new(ReplicaSize::default());
+        size.store_prev_segments(&*segments.read().await);
+        size.store_active_segment(&active_segment);
         let cleaner = Cleaner::start_new(
             storage_config,
             shared_config.clone(),
@@ -283,8 +388,10 @@ impl FileReplica {
             active_segment,
             prev_segments: segments,
             commit_checkpoint,
+            log_start_checkpoint,
             cleaner,
             size,
+            recovery,
         })
     }
 
@@ -301,6 +408,32 @@ impl FileReplica {
         }
     }
 
+    /// page cache preload targets for newest `max_bytes` of the log, newest segment first
+    pub async fn preload_targets(&self, max_bytes: u64) -> Vec<PreloadTarget> {
+        let mut targets = vec![];
+        let added = self.active_segment.preload_targets(max_bytes, &mut targets);
+        self.prev_segments
+            .read()
+            .await
+            .preload_targets(max_bytes - added, &mut targets);
+        targets
+    }
+
+    /// closed segments which can be scrubbed, active segment is never included
+    pub async fn scrub_targets(&self) -> Vec<ScrubTarget> {
+        self.prev_segments.read().await.scrub_targets()
+    }
+
+    /// truncations done while loading replica, None unless recovery audit is enabled
+    pub fn recovery_report(&self) -> Option<&RecoveryReport> {
+        self.recovery.as_ref()
+    }
+
+    /// flush and sync records of active segment to disk, regardless of flush policy
+    pub async fn sync(&mut self) -> Result<(), StorageError> {
+        self.active_segment.sync().await
+    }
+
     /// update high watermark to end
     #[instrument(skip(self))]
     pub async fn update_high_watermark_to_end(&mut self) -> Result<bool, StorageError> {
@@ -340,6 +473,14 @@ impl FileReplica {
             ..Default::default()
         };
 
+        // records before log start may still be in segment but are deleted
+        if start_offset < slice.start {
+            return Err(ErrorCode::OffsetEvicted {
+                offset: start_offset,
+                next_available: slice.start,
+            });
+        }
+
         let active_base_offset = self.active_segment.get_base_offset();
         let file_slice = if start_offset >= active_base_offset {
             debug!(start_offset, active_base_offset, "is in active segment");
@@ -404,12 +545,11 @@ impl FileReplica {
             let new_segment = MutableSegment::create(last_offset, self.option.clone()).await?;
             let old_mut_segment = mem::replace(&mut self.active_segment, new_segment);
             let old_segment = old_mut_segment.as_segment().await?;
-            self.size.add_prev(old_segment.occupied_memory());
+            self.size.add_prev_segment(&old_segment);
             self.prev_segments.add_segment(old_segment).await;
             self.active_segment.append_batch(item).await?;
         }
-        self.size
-            .store_active(self.active_segment.occupied_memory());
+        self.size.store_active_segment(&self.active_segment);
         Ok(())
     }
 }
@@ -423,22 +563,45 @@ impl ReplicaSize {
         self.prev_segments.store(prev_segments, Ordering::Release);
     }
 
-    fn store_active(&self, active: Size64) {
-        self.active_segment.store(active, Ordering::Release);
+    pub(crate) fn store_prev_segments(&self, segments: &SegmentList) {
+        self.store_prev(segments.occupied_memory());
+        self.prev_index
+            .store(segments.index_memory(), Ordering::Release);
+    }
+
+    fn store_active_segment(&self, active: &MutableSegment) {
+        self.active_segment
+            .store(active.occupied_memory(), Ordering::Release);
+        self.active_index
+            .store(active.index_memory(), Ordering::Release);
     }
 
     fn get_active(&self) -> Size64 {
         self.active_segment.load(Ordering::Acquire)
     }
 
-    fn add_prev(&self, prev_segment: Size64) {
+    fn add_prev_segment(&self, prev_segment: &ReadSegment) {
         self.prev_segments
-            .fetch_add(prev_segment, Ordering::Release);
+            .fetch_add(prev_segment.occupied_memory(), Ordering::Release);
+        self.prev_index
+            .fetch_add(prev_segment.index_memory(), Ordering::Release);
     }
 
     fn get_prev(&self) -> Size64 {
         self.prev_segments.load(Ordering::Acquire)
     }
+
+    /// split of total size into segment logs and indexes
+    fn usage(&self) -> StorageUsage {
+        let total = self.get();
+        let index_bytes =
+            self.active_index.load(Ordering::Acquire) + self.prev_index.load(Ordering::Acquire);
+        StorageUsage {
+            segment_bytes: total.saturating_sub(index_bytes),
+            index_bytes,
+            tiered_bytes: 0,
+        }
+    }
 }
 
 // generate replication folder name
@@ -446,6 +609,25 @@ fn replica_dir_name<S: AsRef<str>>(topic_name: S, partition_index: Size) -> Stri
     format!("{}-{}", topic_name.as_ref(), partition_index)
 }
 
+/// apply fault injected at storage point of replica in `replica_dir`,
+/// returns true if operation must be skipped
+#[cfg(feature = "fault-injection")]
+async fn inject_fault(point: &str, replica_dir: &std::path::Path) -> Result<bool, StorageError> {
+    use fluvio_types::fault::{check, FaultAction};
+
+    match check(point, &replica_dir.to_string_lossy()) {
+        Some(FaultAction::Delay(delay)) => {
+            fluvio_future::timer::sleep(delay).await;
+            Ok(false)
+        }
+        Some(FaultAction::Drop) => Ok(true),
+        Some(FaultAction::Error(kind)) => Err(StorageError::Io(std::io::Error::new(
+            kind,
+            format!("injected fault at {point}"),
+        ))),
+        None => Ok(false),
+    }
+}
 #[cfg(test)]
 #[cfg(feature = "fixture")]
 mod tests {
@@ -687,7 +869,8 @@ mod tests {
         assert_eq!(replica.get_log_start_offset(), START_OFFSET);
         let replica_dir = &option.base_dir.join("test-1");
         let dir_contents = fs::read_dir(replica_dir).expect("read_dir");
-        assert_eq!(dir_contents.count(), 5, "should be 5 files");
+        // two segments with their indexes, replication and log start checkpoints
+        assert_eq!(dir_contents.count(), 6, "should be 6 files");
 
         let seg2_file = replica_dir.join(TEST_SE2_NAME);
         let bytes = read_bytes_from_file(seg2_file).expect("file read");
@@ -707,6 +890,60 @@ mod tests {
         assert_eq!(seg1_metadata.len(), 8);
     }
 
+    #[fluvio_future::test]
+    async fn test_replica_recovery_audit() {
+        use std::io::Write;
+
+        let mut option = rollover_option("test_recovery_audit");
+        let mut replica = create_replica("test", START_OFFSET, option.clone()).await;
+        for _ in 0..2 {
+            let mut batch = create_batch();
+            replica.write_batch(&mut batch).await.expect("write");
+        }
+        replica.update_high_watermark_to_end().await.expect("hw");
+        drop(replica);
+
+        // simulate torn writes at tail of closed and active segment
+        let replica_dir = option.base_dir.join("test-0");
+        for (name, junk) in [(TEST_SEG_NAME, 3), (TEST_SE2_NAME, 5)] {
+            let mut file = fs::OpenOptions::new()
+                .append(true)
+                .open(replica_dir.join(name))
+                .expect("open log");
+            file.write_all(&vec![0x01; junk]).expect("write junk");
+        }
+
+        // torn closed segment fails load unless audited
+        assert!(FileReplica::create_or_load_with_storage(
+            "test",
+            0,
+            START_OFFSET,
+            option.clone(),
+            storage_config()
+        )
+        .await
+        .is_err());
+
+        option.recovery_audit = true;
+        let replica = create_replica("test", START_OFFSET, option).await;
+        let report = replica.recovery_report().expect("report");
+        assert_eq!(report.segments_scanned, 2);
+        assert_eq!(report.truncated.len(), 2);
+        assert_eq!(report.truncated[0].base_offset, START_OFFSET);
+        assert_eq!(report.truncated[0].end_offset, START_OFFSET + 2);
+        assert_eq!(report.discarded_bytes(), 8);
+        assert_eq!(report.leo, START_OFFSET + 4);
+        assert_eq!(report.truncated_offsets(), 0);
+        assert!(!report.is_clean());
+
+        assert_eq!(replica.get_leo(), START_OFFSET + 4);
+        let slice = replica
+            .read_partition_slice(START_OFFSET, 1000, Isolation::ReadUncommitted)
+            .await
+            .expect("read");
+        assert!(slice.file_slice.is_some());
+    }
+
     #[fluvio_future::test]
     async fn test_replica_commit() {
         let option = base_option("test_commit");
@@ -782,6 +1019,32 @@ mod tests {
         assert_eq!(size, 79);
     }
 
+    #[fluvio_future::test]
+    async fn test_replica_storage_usage() {
+        let mut option = base_option("test_storage_usage");
+        option.index_max_interval_bytes = 10;
+        let mut replica = create_replica("test", 0, option.clone()).await;
+
+        for _ in 0..3 {
+            let mut records = RecordSet::default().add(create_batch());
+            replica
+                .write_recordset(&mut records, true)
+                .await
+                .expect("write");
+        }
+
+        let usage = replica.get_storage_usage();
+        assert!(usage.index_bytes > 0);
+        assert_eq!(usage.segment_bytes, 79 * 3);
+        assert_eq!(usage.local_bytes(), replica.get_partition_size());
+        assert_eq!(usage.tiered_bytes, 0);
+        drop(replica);
+
+        // usage of existing segments is restored on load
+        let replica = create_replica("test", 0, option).await;
+        assert_eq!(replica.get_storage_usage(), usage);
+    }
+
     /// test fetch only committed records
     #[fluvio_future::test]
     async fn test_committed_fetch() {
@@ -889,6 +1152,90 @@ mod tests {
         ));
     }
 
+    #[fluvio_future::test]
+    async fn test_replica_delete_records() {
+        let mut option = base_option("test_delete_records");
+        // enough for 2 batch (2 records per batch)
+        option.segment_max_bytes = 160;
+
+        let producer = BatchProducer::builder()
+            .records(2u16)
+            .record_generator(Arc::new(|_, _| Record::new("1")))
+            .build()
+            .expect("batch");
+
+        let mut new_replica = create_replica("test", 0, option.clone()).await;
+        for _ in 0..4 {
+            new_replica
+                .write_batch(&mut producer.generate_batch())
+                .await
+                .expect("write");
+        }
+        new_replica
+            .update_high_watermark(6)
+            .await
+            .expect("update hw");
+        assert_eq!(new_replica.prev_segments.read().await.len(), 1);
+
+        assert_eq!(
+            new_replica.delete_records_before(5).await.expect("delete"),
+            5
+        );
+        assert_eq!(new_replica.get_log_start_offset(), 5);
+        assert_eq!(new_replica.prev_segments.read().await.len(), 0);
+        assert!(matches!(
+            new_replica.read_records(4, None, 1024).await,
+            Err(ErrorCode::OffsetEvicted {
+                offset: 4,
+                next_available: 5,
+            })
+        ));
+        assert!(new_replica.read_records(5, None, 1024).await.is_ok());
+
+        // log start never moves back and uncommitted records are kept
+        assert_eq!(
+            new_replica.delete_records_before(2).await.expect("delete"),
+            5
+        );
+        assert_eq!(
+            new_replica
+                .delete_records_before(100)
+                .await
+                .expect("delete"),
+            6
+        );
+        drop(new_replica);
+
+        let replica = create_replica("test", 0, option).await;
+        assert_eq!(replica.get_log_start_offset(), 6);
+        assert_eq!(replica.get_leo(), 8);
+    }
+
+    #[fluvio_future::test]
+    async fn test_replica_state() {
+        let option = base_option("test_replica_state");
+
+        let replica = create_replica("test", 0, option.clone()).await;
+        assert!(replica
+            .read_state("state.chk")
+            .await
+            .expect("read")
+            .is_none());
+
+        replica.write_state("state.chk", b"1").await.expect("write");
+        replica
+            .write_state("state.chk", b"22")
+            .await
+            .expect("write");
+        drop(replica);
+
+        let replica = create_replica("test", 0, option).await;
+        assert_eq!(
+            replica.read_state("state.chk").await.expect("read"),
+            Some(b"22".to_vec())
+        );
+    }
+
     /// create replicat with multiple segments
     #[fluvio_future::test]
     async fn test_replica_multiple_segment() {
@@ -1180,4 +1527,51 @@ mod tests {
             }
         ));
     }
+
+    #[cfg(feature = "fault-injection")]
+    #[fluvio_future::test]
+    async fn test_replica_write_fault() {
+        use std::io::ErrorKind;
+        use fluvio_types::fault::{self, FaultAction, STORAGE_WRITE};
+
+        let option = base_option("test_write_fault");
+        let scope = option.base_dir.to_string_lossy().to_string();
+        let mut replica = create_replica("test", 0, option).await;
+
+        // other replica is not affected
+        let other_option = base_option("test_write_fault_other");
+        let mut other_replica = create_replica("test", 0, other_option).await;
+        let guard = fault::inject_scoped(STORAGE_WRITE, &scope, FaultAction::Drop, None);
+        other_replica
+            .write_recordset(&mut RecordSet::default().add(create_batch()), true)
+            .await
+            .expect("write");
+        assert_eq!(other_replica.get_leo(), 2);
+        drop(guard);
+
+        let _fault = fault::inject_scoped(STORAGE_WRITE, &scope, FaultAction::Drop, Some(1));
+        replica
+            .write_recordset(&mut RecordSet::default().add(create_batch()), true)
+            .await
+            .expect("dropped write");
+        assert_eq!(replica.get_leo(), 0);
+
+        let _fault = fault::inject_scoped(
+            STORAGE_WRITE,
+            &scope,
+            FaultAction::Error(ErrorKind::Other),
+            Some(1),
+        );
+        assert!(replica
+            .write_recordset(&mut RecordSet::default().add(create_batch()), true)
+            .await
+            .is_err());
+        assert_eq!(replica.get_leo(), 0);
+
+        replica
+            .write_recordset(&mut RecordSet::default().add(create_batch()), true)
+            .await
+            .expect("write");
+        assert_eq!(replica.get_leo(), 2);
+    }
 }
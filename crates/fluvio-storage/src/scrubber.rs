@@ -0,0 +1,187 @@
+//!
+//! # Storage Scrubber
+//!
+//! Re-reads batches of closed segments and verifies their checksums.
+//! Segment is scanned batch by batch, so memory usage doesn't depend on segment size.
+//! Scrubbing only reports corruption; it is up to the caller to repair the replica.
+//! Report also contains digest of segment, so segments of different replicas can be compared.
+//!
+use std::path::PathBuf;
+
+use tracing::{debug, warn, instrument};
+use anyhow::Result;
+
+use fluvio_protocol::Encoder;
+use fluvio_protocol::record::{Batch, Offset, RawRecords};
+use fluvio_controlplane_metadata::partition::CorruptRange;
+
+use crate::batch::FileBatchStream;
+
+/// Closed segment to be scrubbed
+#[derive(Debug, Clone)]
+pub struct ScrubTarget {
+    pub base_offset: Offset,
+    pub end_offset: Offset,
+    pub path: PathBuf,
+}
+
+#[derive(Debug, Default)]
+pub struct ScrubReport {
+    pub batches: u64,
+    pub corrupt: Vec<CorruptRange>,
+    /// crc32c over offsets and actual checksums of all decoded batches
+    pub digest: u32,
+}
+
+impl ScrubReport {
+    pub fn is_clean(&self) -> bool {
+        self.corrupt.is_empty()
+    }
+
+    /// add range, adjacent ranges are merged
+    fn add(&mut self, range: CorruptRange) {
+        match self.corrupt.last_mut() {
+            Some(last) if last.end == range.start => last.end = range.end,
+            _ => self.corrupt.push(range),
+        }
+    }
+}
+
+/// verify checksum of every batch in the segment.
+/// If batch can't be decoded, rest of the segment is reported as corrupt
+#[instrument(skip(target), fields(path = ?target.path))]
+pub async fn scrub_segment(target: &ScrubTarget) -> Result<ScrubReport> {
+    let mut stream: FileBatchStream<RawRecords> = FileBatchStream::open(&target.path).await?;
+    let mut report = ScrubReport::default();
+    let mut next_offset = target.base_offset;
+
+    loop {
+        match stream.try_next().await {
+            Ok(Some(batch_pos)) => {
+                let batch = batch_pos.get_batch();
+                report.batches += 1;
+                let end = batch.get_last_offset() + 1;
+                let crc = batch_crc(batch)?;
+                report.digest =
+                    crc32c::crc32c_append(report.digest, &batch.base_offset.to_be_bytes());
+                report.digest = crc32c::crc32c_append(report.digest, &crc.to_be_bytes());
+                if crc != batch.header.crc {
+                    warn!(
+                        base_offset = batch.base_offset,
+                        pos = batch_pos.get_pos(),
+                        expected = batch.header.crc,
+                        actual = crc,
+                        "batch checksum mismatch"
+                    );
+                    report.add(CorruptRange {
+                        start: batch.base_offset,
+                        end,
+                    });
+                }
+                next_offset = end;
+            }
+            Ok(None) => break,
+            Err(err) => {
+                warn!(next_offset, %err, "unable to decode batch");
+                if next_offset < target.end_offset {
+                    report.add(CorruptRange {
+                        start: next_offset,
+                        end: target.end_offset,
+                    });
+                }
+                break;
+            }
+        }
+    }
+
+    debug!(batches = report.batches, corrupt = ?report.corrupt, "segment scrubbed");
+    Ok(report)
+}
+
+/// compute checksum same way as batch encoder does.
+/// raw records of stored batch already contains schema id if present
+fn batch_crc(batch: &Batch<RawRecords>) -> Result<u32> {
+    let header = &batch.header;
+    let mut out: Vec<u8> = Vec::new();
+    header.attributes.encode(&mut out, 0)?;
+    header.last_offset_delta.encode(&mut out, 0)?;
+    header.first_timestamp.encode(&mut out, 0)?;
+    header.max_time_stamp.encode(&mut out, 0)?;
+    header.producer_id.encode(&mut out, 0)?;
+    header.producer_epoch.encode(&mut out, 0)?;
+    header.first_sequence.encode(&mut out, 0)?;
+    let crc = crc32c::crc32c(&out);
+    Ok(crc32c::crc32c_append(crc, &batch.records().0))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::env::temp_dir;
+
+    use flv_util::fixture::ensure_new_dir;
+    use fluvio_protocol::fixture::create_batch;
+
+    use crate::config::ReplicaConfig;
+    use crate::records::FileRecords;
+    use crate::segment::MutableSegment;
+
+    use super::*;
+
+    #[fluvio_future::test]
+    async fn test_scrub_detects_corrupt_batch() {
+        let test_dir = temp_dir().join("scrub-corrupt-batch");
+        ensure_new_dir(&test_dir).expect("new");
+
+        let option = ReplicaConfig {
+            base_dir: test_dir,
+            segment_max_bytes: 1000,
+            index_max_bytes: 1000,
+            ..Default::default()
+        }
+        .shared();
+
+        let mut segment = MutableSegment::create(300, option).await.expect("create");
+        segment
+            .append_batch(&mut create_batch())
+            .await
+            .expect("write");
+        segment
+            .append_batch(&mut create_batch())
+            .await
+            .expect("write");
+        segment.flush().await.expect("flush");
+
+        let target = ScrubTarget {
+            base_offset: segment.get_base_offset(),
+            end_offset: segment.get_end_offset(),
+            path: segment.get_msg_log().get_path().to_owned(),
+        };
+
+        let report = scrub_segment(&target).await.expect("scrub");
+        assert_eq!(report.batches, 2);
+        assert!(report.is_clean());
+        let clean_digest = report.digest;
+        assert_eq!(
+            scrub_segment(&target).await.expect("scrub").digest,
+            clean_digest
+        );
+
+        // flip last byte of second batch
+        let mut bytes = std::fs::read(&target.path).expect("read");
+        let last = bytes.len() - 1;
+        bytes[last] ^= 0xFF;
+        std::fs::write(&target.path, bytes).expect("write");
+
+        let report = scrub_segment(&target).await.expect("scrub");
+        assert_eq!(report.batches, 2);
+        assert_eq!(
+            report.corrupt,
+            vec![CorruptRange {
+                start: 302,
+                end: 304
+            }]
+        );
+        assert_ne!(report.digest, clean_digest);
+    }
+}
@@ -1,11 +1,11 @@
 use std::fmt;
-use std::io::Error as IoError;
+use std::io::{Error as IoError, ErrorKind};
 use std::ops::Deref;
 use std::sync::Arc;
 use std::time::Duration;
 
-use tracing::{debug, trace, instrument, info, error};
-use anyhow::{Result};
+use tracing::{debug, trace, instrument, info, error, warn};
+use anyhow::{Result, anyhow};
 
 use fluvio_future::fs::remove_file;
 use fluvio_future::file_slice::AsyncFileSlice;
@@ -15,16 +15,19 @@ use fluvio_protocol::link::ErrorCode;
 
 use crate::batch_header::{BatchHeaderStream, FileEmptyRecords};
 use crate::mut_index::MutLogIndex;
-use crate::index::LogIndex;
+use crate::index::{LogIndex, INDEX_ENTRY_SIZE, EXTENSION as INDEX_EXTENSION};
 use crate::index::Index;
 use crate::records::FileRecords;
-use crate::mut_records::MutFileRecords;
-use crate::records::FileRecordsSlice;
+use crate::mut_records::{MutFileRecords, LogSync};
+use crate::records::{FileRecordsSlice, MESSAGE_LOG_EXTENSION};
+use crate::util::generate_file_name;
 use crate::config::{SharedReplicaConfig};
 use crate::StorageError;
 use crate::batch::{FileBatchStream};
 use crate::index::OffsetPosition;
 use crate::validator::LogValidationError;
+use crate::preload::PreloadTarget;
+use crate::recovery::{SegmentRecovery, truncate_log};
 
 pub type MutableSegment = Segment<MutLogIndex, MutFileRecords>;
 pub type ReadSegment = Segment<LogIndex, FileRecordsSlice>;
@@ -229,6 +232,27 @@ where
     pub(crate) fn occupied_memory(&self) -> Size64 {
         self.index.len() + self.msg_log.len()
     }
+
+    /// bytes used by index, included in `occupied_memory`
+    pub(crate) fn index_memory(&self) -> Size64 {
+        self.index.len()
+    }
+
+    /// add page cache preload targets for last `max_len` bytes of message log.
+    /// Returns bytes of message log added
+    pub(crate) fn preload_targets(
+        &self,
+        max_len: Size64,
+        targets: &mut Vec<PreloadTarget>,
+    ) -> Size64 {
+        PreloadTarget::segment(
+            self.msg_log.get_path(),
+            self.msg_log.len(),
+            self.index.len(),
+            max_len,
+            targets,
+        )
+    }
 }
 
 impl Segment<LogIndex, FileRecordsSlice> {
@@ -254,30 +278,76 @@ impl Segment<LogIndex, FileRecordsSlice> {
     }
 
     /// open read only segments if we don't know end offset
-    #[instrument(skip(option),fields(base_dir=?option.base_dir))]
     pub async fn open_unknown(
         base_offset: Offset,
         option: Arc<SharedReplicaConfig>,
     ) -> Result<Self> {
-        let msg_log = FileRecordsSlice::open(base_offset, option.clone()).await?;
-        let index = LogIndex::open_from_offset(base_offset, option.clone()).await?;
+        let (segment, _) = Self::open_unknown_with_recovery(base_offset, option).await?;
+        Ok(segment)
+    }
+
+    /// open read only segments if we don't know end offset.
+    /// In recovery audit mode, torn tail is truncated to last valid batch instead of failing
+    #[instrument(skip(option),fields(base_dir=?option.base_dir))]
+    pub(crate) async fn open_unknown_with_recovery(
+        base_offset: Offset,
+        option: Arc<SharedReplicaConfig>,
+    ) -> Result<(Self, Option<SegmentRecovery>)> {
+        let mut msg_log = FileRecordsSlice::open(base_offset, option.clone()).await?;
+        let mut index = match LogIndex::open_from_offset(base_offset, option.clone()).await {
+            Ok(index) => index,
+            Err(err) if err.kind() == ErrorKind::NotFound => {
+                warn!(base_offset, "index not found, rebuilding");
+                rebuild_index(base_offset, option.clone()).await?;
+                LogIndex::open_from_offset(base_offset, option.clone()).await?
+            }
+            Err(err) => return Err(err.into()),
+        };
         let base_offset = msg_log.get_base_offset();
         match msg_log.validate(&index).await {
             Ok(val) => {
+                let leo = val.leo();
+                let mut recovery = None;
                 // check if validation is successful
                 if let Some(err) = val.error {
                     error!(err = ?err, "segment validation failed");
-                    return Err(err.into());
+                    if !(option.recovery_audit
+                        && matches!(err, LogValidationError::BatchDecoding(_)))
+                    {
+                        return Err(err.into());
+                    }
+                    let original_len = msg_log.len();
+                    let valid_len = val.last_valid_file_pos as u64;
+                    let path = msg_log.get_path().to_owned();
+                    drop(msg_log);
+                    drop(index);
+                    truncate_log(&path, valid_len).await?;
+                    rebuild_index(base_offset, option.clone()).await?;
+                    msg_log = FileRecordsSlice::open(base_offset, option.clone()).await?;
+                    index = LogIndex::open_from_offset(base_offset, option.clone()).await?;
+                    recovery = Some(SegmentRecovery {
+                        base_offset,
+                        end_offset: leo,
+                        original_len,
+                        discarded_bytes: original_len.saturating_sub(valid_len),
+                        error: err.to_string(),
+                    });
+                } else if let Some(index_error) = val.index_error {
+                    warn!(%index_error, base_offset, "index is inconsistent with log, rebuilding");
+                    drop(index);
+                    rebuild_index(base_offset, option.clone()).await?;
+                    index = LogIndex::open_from_offset(base_offset, option.clone()).await?;
                 }
 
-                info!(end_offset = val.leo(), base_offset = val.base_offset, time_ms = %val.duration.as_millis(), "segment validated");
-                Ok(Segment {
+                info!(end_offset = leo, base_offset = val.base_offset, time_ms = %val.duration.as_millis(), "segment validated");
+                let segment = Segment {
                     msg_log,
                     index,
                     option,
                     base_offset,
-                    end_offset: val.leo(),
-                })
+                    end_offset: leo,
+                };
+                Ok((segment, recovery))
             }
             Err(err) => {
                 error!(?err, "segment validation encountered fail error");
@@ -351,23 +421,46 @@ impl Segment<MutLogIndex, MutFileRecords> {
 
     /// validate and repair if necessary
     pub async fn validate_and_repair(&mut self) -> Result<Offset> {
+        let (end_offset, _) = self.validate_and_recover().await?;
+        Ok(end_offset)
+    }
+
+    /// validate and repair if necessary, returns truncation of torn tail if there was one
+    pub(crate) async fn validate_and_recover(
+        &mut self,
+    ) -> Result<(Offset, Option<SegmentRecovery>)> {
         let validation = self.msg_log.validate(&self.index).await?;
+        let mut recovery = None;
         let leo = validation.leo();
+        // index entries may point past truncated log, missing index has no entries at all
+        let mut rebuild = validation.index_error.is_some()
+            || (self.index.len() == 0
+                && validation.last_valid_file_pos > self.option.index_max_interval_bytes.get());
         // check for error and see if it's recoverable
         if let Some(err) = validation.error {
             error!(err = ?err, "log validation failed");
             match err {
-                LogValidationError::BatchDecoding(_header_error) => {
+                LogValidationError::BatchDecoding(ref header_error) => {
                     info!(
                         len = validation.last_valid_file_pos,
                         "batch decoding error, trying to recover"
                     );
+                    let original_len = self.msg_log.get_pos() as u64;
+                    recovery = Some(SegmentRecovery {
+                        base_offset: self.base_offset,
+                        end_offset: leo,
+                        original_len,
+                        discarded_bytes: original_len
+                            .saturating_sub(validation.last_valid_file_pos as u64),
+                        error: header_error.to_string(),
+                    });
                     // for decoding batch error, we can readjust
                     self.msg_log.set_len(validation.last_valid_file_pos).await?;
                     info!(
                         len = validation.last_valid_file_pos,
                         "readjust segment length"
                     );
+                    rebuild = true;
                 }
                 _ => {
                     // for other error, we can't recover
@@ -375,8 +468,16 @@ impl Segment<MutLogIndex, MutFileRecords> {
                 }
             }
         }
+        if rebuild {
+            warn!(
+                base_offset = self.base_offset,
+                "rebuilding index of active segment"
+            );
+            rebuild_index(self.base_offset, self.option.clone()).await?;
+            self.index = MutLogIndex::open(self.base_offset, self.option.clone()).await?;
+        }
         self.end_offset = leo;
-        Ok(self.end_offset)
+        Ok((self.end_offset, recovery))
     }
 
     // shrink index
@@ -385,8 +486,17 @@ impl Segment<MutLogIndex, MutFileRecords> {
         self.index.shrink().await
     }
 
+    /// handle to sync log, if syncs are deferred to group commit
+    pub fn log_sync(&self) -> Option<&LogSync> {
+        self.msg_log.log_sync()
+    }
+
     // perform any action during roll over
     pub async fn roll_over(&mut self) -> Result<(), IoError> {
+        // deferred sync only covers active segment
+        if let Some(log_sync) = self.msg_log.log_sync() {
+            log_sync.sync().await?;
+        }
         self.index.shrink().await
     }
 
@@ -457,6 +567,65 @@ impl Segment<MutLogIndex, MutFileRecords> {
     pub async fn flush(&mut self) -> Result<(), StorageError> {
         self.msg_log.flush().await.map_err(|err| err.into())
     }
+
+    /// flush and sync log to disk
+    pub async fn sync(&mut self) -> Result<(), StorageError> {
+        self.msg_log.flush().await?;
+        self.msg_log.sync().await.map_err(|err| err.into())
+    }
+}
+
+/// Regenerate index of segment from its log, replacing existing index file.
+/// Entries are written with current index interval, so interval changes apply to rebuilt segments.
+/// Returns number of index entries
+#[instrument(skip(option), fields(base_dir = ?option.base_dir))]
+pub async fn rebuild_index(base_offset: Offset, option: Arc<SharedReplicaConfig>) -> Result<u32> {
+    let index_path = generate_file_name(&option.base_dir, base_offset, INDEX_EXTENSION);
+    let log_path = generate_file_name(&option.base_dir, base_offset, MESSAGE_LOG_EXTENSION);
+
+    if let Err(err) = remove_file(&index_path).await {
+        if err.kind() != ErrorKind::NotFound {
+            return Err(err.into());
+        }
+    }
+    let mut index = MutLogIndex::create(base_offset, option).await?;
+
+    let mut batches: u32 = 0;
+    let mut last_relative_offset = 0;
+    match BatchHeaderStream::open(&log_path).await {
+        Ok(mut stream) => {
+            while let Some(batch_pos) = stream.try_next().await? {
+                let file_pos = batch_pos.get_pos();
+                let batch_base_offset = batch_pos.get_batch().get_base_offset();
+                if batch_base_offset < base_offset {
+                    return Err(LogValidationError::InvalidBaseOffsetMinimum {
+                        invalid_batch_offset: batch_base_offset,
+                    }
+                    .into());
+                }
+                let relative_offset = (batch_base_offset - base_offset) as u32;
+                if batches > 0 && relative_offset <= last_relative_offset {
+                    return Err(anyhow!(
+                        "batch offset {batch_base_offset} is not increasing, can't rebuild index"
+                    ));
+                }
+                let batch_len = stream.get_pos() - file_pos;
+                index
+                    .write_index(relative_offset, file_pos, batch_len)
+                    .await?;
+                last_relative_offset = relative_offset;
+                batches += 1;
+            }
+        }
+        // empty log has no index entries
+        Err(err) if err.kind() == ErrorKind::UnexpectedEof => {}
+        Err(err) => return Err(err.into()),
+    }
+    index.shrink().await?;
+
+    let entries = (index.len() / INDEX_ENTRY_SIZE) as u32;
+    info!(base_offset, batches, entries, "index rebuilt");
+    Ok(entries)
 }
 
 #[cfg(test)]
@@ -476,10 +645,10 @@ mod tests {
     use fluvio_protocol::fixture::create_batch;
     use fluvio_protocol::fixture::read_bytes_from_file;
 
-    use super::MutableSegment;
+    use super::{MutableSegment, ReadSegment, rebuild_index};
 
     use crate::config::ReplicaConfig;
-    use crate::index::OffsetPosition;
+    use crate::index::{Index, OffsetPosition};
 
     // TODO: consolidate
 
@@ -680,4 +849,57 @@ mod tests {
             )
             .expect("failed to get records");
     }
+
+    #[fluvio_future::test]
+    async fn test_segment_rebuild_index() {
+        let test_dir = temp_dir().join("seg-rebuild-index");
+        ensure_new_dir(&test_dir).expect("new");
+        let index_path = test_dir.join("00000000000000000060.index");
+
+        let base_offset = 60;
+        let option = default_option(test_dir.clone(), 50).shared();
+
+        let mut seg_sink = MutableSegment::create(base_offset, option.clone())
+            .await
+            .expect("create");
+        for _ in 0..3 {
+            seg_sink
+                .append_batch(&mut create_batch())
+                .await
+                .expect("write");
+        }
+        assert_eq!(seg_sink.get_index()[0].to_be(), (2, 79));
+        drop(seg_sink.convert_to_segment().await.expect("convert"));
+
+        // missing index is rebuilt when segment is opened
+        std::fs::remove_file(&index_path).expect("remove index");
+        let segment = ReadSegment::open_unknown(base_offset, option.clone())
+            .await
+            .expect("open");
+        assert_eq!(segment.get_end_offset(), 66);
+        assert_eq!(Index::len(segment.get_index()), 8);
+        assert_eq!(segment.get_index()[0].to_be(), (2, 79));
+        drop(segment);
+
+        // index pointing to wrong position is rebuilt
+        std::fs::write(&index_path, [0, 0, 0, 2, 0, 0, 0, 100]).expect("corrupt index");
+        let segment = ReadSegment::open_unknown(base_offset, option.clone())
+            .await
+            .expect("open");
+        assert_eq!(segment.get_index()[0].to_be(), (2, 79));
+        let offset_pos = segment
+            .find_offset_position(62)
+            .await
+            .expect("pos")
+            .expect("offset exists");
+        assert_eq!(offset_pos.pos, 79);
+        drop(segment);
+
+        // rebuilding with smaller interval indexes every batch
+        let option = default_option(test_dir.clone(), 0).shared();
+        assert_eq!(
+            rebuild_index(base_offset, option).await.expect("rebuild"),
+            3
+        );
+    }
 }
@@ -16,6 +16,10 @@ use fluvio_future::file_slice::AsyncFileSlice;
 
 use crate::config::SharedReplicaConfig;
 use crate::segment::ReadSegment;
+use crate::records::FileRecords;
+use crate::scrubber::ScrubTarget;
+use crate::preload::PreloadTarget;
+use crate::recovery::RecoveryReport;
 use crate::util::log_path_get_offset;
 
 const MEM_ORDER: std::sync::atomic::Ordering = std::sync::atomic::Ordering::SeqCst;
@@ -37,8 +41,18 @@ impl SharedSegments {
         })
     }
 
+    #[cfg(test)]
     pub async fn from_dir(
         option: Arc<SharedReplicaConfig>,
+    ) -> Result<(Arc<SharedSegments>, Option<Offset>)> {
+        let mut report = RecoveryReport::new(&option.base_dir);
+        Self::from_dir_with_recovery(option, &mut report).await
+    }
+
+    /// load closed segments, truncations of their torn tails are added to `report`
+    pub(crate) async fn from_dir_with_recovery(
+        option: Arc<SharedReplicaConfig>,
+        report: &mut RecoveryReport,
     ) -> Result<(Arc<SharedSegments>, Option<Offset>)> {
         let dirs = option.base_dir.read_dir()?;
         debug!("reading segments at: {:#?}", dirs);
@@ -75,8 +89,9 @@ impl SharedSegments {
 
         for offset in offsets {
             // for now, set end offset same as base, this will be reset when validation occurs
-            match ReadSegment::open_unknown(offset, option.clone()).await {
-                Ok(segment) => {
+            match ReadSegment::open_unknown_with_recovery(offset, option.clone()).await {
+                Ok((segment, recovery)) => {
+                    report.add(recovery);
                     let min_offset = segments.add_segment(segment);
                     debug!(min_offset, "adding segment");
                 }
@@ -181,6 +196,10 @@ impl SegmentList {
             .sum()
     }
 
+    pub(crate) fn index_memory(&self) -> Size64 {
+        self.segments.values().map(ReadSegment::index_memory).sum()
+    }
+
     #[instrument(skip(self, segment))]
     fn add_segment(&mut self, segment: ReadSegment) -> Offset {
         debug!(
@@ -258,10 +277,40 @@ impl SegmentList {
             .collect()
     }
 
+    /// segments whose records all precede `offset`
+    pub(crate) fn find_ending_before(&self, offset: Offset) -> Vec<Offset> {
+        self.segments
+            .iter()
+            .take_while(|(_, segment)| segment.get_end_offset() <= offset)
+            .map(|(base_offset, _)| *base_offset)
+            .collect()
+    }
+
     #[instrument(skip(self))]
     pub(crate) fn find_first(&self, count: usize) -> Vec<Offset> {
         self.segments.keys().take(count).copied().collect()
     }
+
+    /// add preload targets of newest segments, until `max_len` bytes of message log
+    pub(crate) fn preload_targets(&self, mut max_len: Size64, targets: &mut Vec<PreloadTarget>) {
+        for segment in self.segments.values().rev() {
+            if max_len == 0 {
+                break;
+            }
+            max_len -= segment.preload_targets(max_len, targets);
+        }
+    }
+
+    pub(crate) fn scrub_targets(&self) -> Vec<ScrubTarget> {
+        self.segments
+            .values()
+            .map(|segment| ScrubTarget {
+                base_offset: segment.get_base_offset(),
+                end_offset: segment.get_end_offset(),
+                path: segment.get_msg_log().get_path().to_owned(),
+            })
+            .collect()
+    }
 }
 
 #[cfg(test)]
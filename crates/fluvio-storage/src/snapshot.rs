@@ -0,0 +1,321 @@
+//!
+//! # Partition Snapshots
+//!
+//! Snapshot archive holds batches of a partition log exactly as they are stored in segments,
+//! preceded by header identifying the source partition. Archive is written by the leader
+//! from file slices of its log, so batches are copied without decompressing them.
+//! Archive is written to temporary file and renamed once complete, a partial archive is never
+//! left under snapshot name.
+//!
+//! Restoring reads batches back in order so they can be written to another partition.
+//! Batches of encrypted partition are archived as ciphertext together with id of their key.
+//! Their records are bound to partition and offsets they were written at, so they can only be
+//! restored to the same partition, encrypted with the same key, at the same offsets.
+//!
+use std::io::{Cursor, Error as IoError, ErrorKind};
+use std::os::fd::BorrowedFd;
+use std::os::unix::io::AsRawFd;
+use std::path::{Path, PathBuf};
+
+use futures_lite::io::{AsyncReadExt, AsyncWriteExt, BufReader, BufWriter};
+use nix::sys::uio::pread;
+use tracing::debug;
+
+use fluvio_future::file_slice::AsyncFileSlice;
+use fluvio_future::fs::{create_dir_all, rename, File};
+use fluvio_protocol::record::{Batch, Offset, RawRecords, RecordSet, BATCH_PREAMBLE_SIZE};
+use fluvio_protocol::{Decoder, Encoder, Version};
+
+/// first bytes of every snapshot archive
+pub const SNAPSHOT_MAGIC: &[u8; 8] = b"FLVSNAP\0";
+
+/// archive format version, stored after magic
+pub const SNAPSHOT_VERSION: Version = 1;
+
+const TMP_EXTENSION: &str = "tmp";
+
+/// Identifies partition snapshot was taken from
+#[derive(Debug, Default, Clone, Eq, PartialEq, Encoder, Decoder)]
+pub struct SnapshotHeader {
+    pub topic: String,
+    pub partition: u32,
+    /// key batches are encrypted with, None if records are stored in clear
+    #[fluvio(min_version = 1)]
+    pub key_id: Option<String>,
+}
+
+/// Offsets and size of batches in snapshot
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct SnapshotInfo {
+    /// base offset of first batch
+    pub start_offset: Offset,
+    /// offset after last batch
+    pub end_offset: Offset,
+    pub batches: u32,
+    pub bytes: u64,
+}
+
+impl SnapshotInfo {
+    fn add(&mut self, batch: &Batch<RawRecords>, len: usize) {
+        if self.batches == 0 {
+            self.start_offset = batch.base_offset;
+        }
+        self.end_offset = batch.get_last_offset() + 1;
+        self.batches += 1;
+        self.bytes += len as u64;
+    }
+}
+
+/// Writes batches of partition log into snapshot archive
+pub struct SnapshotWriter {
+    path: PathBuf,
+    tmp_path: PathBuf,
+    file: BufWriter<File>,
+    info: SnapshotInfo,
+}
+
+impl SnapshotWriter {
+    pub async fn create(path: impl AsRef<Path>, header: &SnapshotHeader) -> Result<Self, IoError> {
+        let path = path.as_ref().to_owned();
+        let tmp_path = path.with_extension(TMP_EXTENSION);
+        if let Some(parent) = path.parent() {
+            create_dir_all(parent).await?;
+        }
+        let mut file = BufWriter::new(File::create(&tmp_path).await?);
+
+        // header is prefixed with its length so readers can load it at once
+        let mut buf = SNAPSHOT_MAGIC.to_vec();
+        SNAPSHOT_VERSION.encode(&mut buf, 0)?;
+        (header.write_size(SNAPSHOT_VERSION) as u32).encode(&mut buf, 0)?;
+        header.encode(&mut buf, SNAPSHOT_VERSION)?;
+        file.write_all(&buf).await?;
+
+        Ok(Self {
+            path,
+            tmp_path,
+            file,
+            info: SnapshotInfo::default(),
+        })
+    }
+
+    /// append batches of file slice which end before `end_offset`.
+    /// Returns offset to read next slice from, None once a batch reaching `end_offset` is found
+    /// or slice is empty
+    pub async fn append_slice(
+        &mut self,
+        slice: &AsyncFileSlice,
+        end_offset: Offset,
+    ) -> Result<Option<Offset>, IoError> {
+        let mut buf = vec![0u8; slice.len() as usize];
+        let bytes_read = pread(
+            unsafe { BorrowedFd::borrow_raw(slice.as_raw_fd()) },
+            &mut buf,
+            slice.position() as i64,
+        )
+        .map_err(|err| IoError::new(ErrorKind::Other, format!("pread error {err}")))?;
+        buf.truncate(bytes_read);
+
+        let mut next_offset = None;
+        let mut position = 0;
+        while position + BATCH_PREAMBLE_SIZE <= buf.len() {
+            let mut cursor = Cursor::new(&buf[position..]);
+            let mut batch: Batch<RawRecords> = Batch::default();
+            if batch.decode(&mut cursor, 0).is_err() {
+                // partial batch at end of slice, next slice starts from it
+                break;
+            }
+            if batch.get_last_offset() >= end_offset {
+                return Ok(None);
+            }
+            let len = cursor.position() as usize;
+            self.file.write_all(&buf[position..position + len]).await?;
+            self.info.add(&batch, len);
+            next_offset = Some(batch.get_last_offset() + 1);
+            position += len;
+        }
+        if next_offset.is_none() && !buf.is_empty() {
+            return Err(IoError::new(
+                ErrorKind::InvalidData,
+                "batch is larger than slice",
+            ));
+        }
+        Ok(next_offset)
+    }
+
+    /// complete archive, it's only visible under its name after this
+    pub async fn finish(mut self) -> Result<SnapshotInfo, IoError> {
+        self.file.flush().await?;
+        self.file.get_ref().sync_all().await?;
+        rename(&self.tmp_path, &self.path).await?;
+        debug!(path = ?self.path, info = ?self.info, "snapshot written");
+        Ok(self.info)
+    }
+}
+
+/// Reads batches of snapshot archive in order
+pub struct SnapshotReader {
+    file: BufReader<File>,
+    header: SnapshotHeader,
+}
+
+impl SnapshotReader {
+    pub async fn open(path: impl AsRef<Path>) -> Result<Self, IoError> {
+        let mut file = BufReader::new(File::open(path.as_ref()).await?);
+
+        let mut magic = [0u8; SNAPSHOT_MAGIC.len()];
+        file.read_exact(&mut magic).await?;
+        if &magic != SNAPSHOT_MAGIC {
+            return Err(IoError::new(
+                ErrorKind::InvalidData,
+                "not a partition snapshot",
+            ));
+        }
+        let mut prefix = [0u8; 6];
+        file.read_exact(&mut prefix).await?;
+        let mut cursor = Cursor::new(&prefix);
+        let version = Version::decode_from(&mut cursor, 0)?;
+        if version > SNAPSHOT_VERSION {
+            return Err(IoError::new(
+                ErrorKind::InvalidData,
+                format!("unsupported snapshot version: {version}"),
+            ));
+        }
+        let header_len = u32::decode_from(&mut cursor, 0)?;
+        let mut header_buf = vec![0u8; header_len as usize];
+        file.read_exact(&mut header_buf).await?;
+        let header = SnapshotHeader::decode_from(&mut Cursor::new(&header_buf), version)?;
+
+        Ok(Self { file, header })
+    }
+
+    pub fn header(&self) -> &SnapshotHeader {
+        &self.header
+    }
+
+    /// next batches up to about `max_bytes`, None when all batches are read
+    pub async fn next_batches(
+        &mut self,
+        max_bytes: usize,
+    ) -> Result<Option<RecordSet<RawRecords>>, IoError> {
+        let mut records = RecordSet::default();
+        let mut bytes = 0;
+        while bytes < max_bytes {
+            let Some(batch) = self.next_batch().await? else {
+                break;
+            };
+            bytes += batch.write_size(0);
+            records.batches.push(batch);
+        }
+        if records.batches.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(records))
+        }
+    }
+
+    async fn next_batch(&mut self) -> Result<Option<Batch<RawRecords>>, IoError> {
+        let mut buf = vec![0u8; BATCH_PREAMBLE_SIZE];
+        match self.file.read_exact(&mut buf).await {
+            Ok(()) => {}
+            Err(err) if err.kind() == ErrorKind::UnexpectedEof => return Ok(None),
+            Err(err) => return Err(err),
+        }
+        let batch_len = i32::decode_from(&mut Cursor::new(&buf[8..]), 0)?;
+        if batch_len < 0 {
+            return Err(IoError::new(
+                ErrorKind::InvalidData,
+                format!("invalid batch length: {batch_len}"),
+            ));
+        }
+        buf.resize(BATCH_PREAMBLE_SIZE + batch_len as usize, 0);
+        self.file
+            .read_exact(&mut buf[BATCH_PREAMBLE_SIZE..])
+            .await?;
+
+        let batch = Batch::<RawRecords>::decode_from(&mut Cursor::new(&buf), 0)?;
+        Ok(Some(batch))
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "fixture")]
+mod tests {
+    use flv_util::fixture::ensure_new_dir;
+    use fluvio_protocol::fixture::BatchProducer;
+
+    use crate::config::ReplicaConfig;
+    use crate::fixture::storage_config;
+    use crate::{FileReplica, ReplicaStorage};
+    use fluvio_spu_schema::Isolation;
+
+    use super::*;
+
+    #[fluvio_future::test]
+    async fn test_snapshot_roundtrip() {
+        let dir = std::env::temp_dir().join("snapshot-roundtrip");
+        ensure_new_dir(&dir).expect("dir");
+
+        let option = ReplicaConfig {
+            base_dir: dir.join("replica"),
+            ..Default::default()
+        };
+        let mut replica =
+            FileReplica::create_or_load_with_storage("test", 0, 0, option, storage_config())
+                .await
+                .expect("replica");
+        let producer = BatchProducer::builder()
+            .records(2u16)
+            .build()
+            .expect("producer");
+        for _ in 0..5 {
+            replica
+                .write_recordset(&mut producer.records(), true)
+                .await
+                .expect("write");
+        }
+
+        let header = SnapshotHeader {
+            topic: "test".to_owned(),
+            partition: 0,
+            key_id: None,
+        };
+        let path = dir.join("test-0.snapshot");
+        let mut writer = SnapshotWriter::create(&path, &header)
+            .await
+            .expect("writer");
+        let mut offset = 0;
+        loop {
+            let slice = replica
+                .read_partition_slice(offset, 1000, Isolation::ReadUncommitted)
+                .await
+                .expect("slice");
+            let Some(file_slice) = slice.file_slice else {
+                break;
+            };
+            match writer.append_slice(&file_slice, 6).await.expect("append") {
+                Some(next) => offset = next,
+                None => break,
+            }
+        }
+        let info = writer.finish().await.expect("finish");
+        assert_eq!(info.start_offset, 0);
+        assert_eq!(info.end_offset, 6);
+        assert_eq!(info.batches, 3);
+        assert!(!path.with_extension(TMP_EXTENSION).exists());
+
+        let mut reader = SnapshotReader::open(&path).await.expect("reader");
+        assert_eq!(reader.header(), &header);
+        let records = reader
+            .next_batches(usize::MAX)
+            .await
+            .expect("read")
+            .expect("batches");
+        assert_eq!(records.batches.len(), 3);
+        assert_eq!(records.batches[2].base_offset, 4);
+        assert!(reader
+            .next_batches(usize::MAX)
+            .await
+            .expect("read")
+            .is_none());
+    }
+}
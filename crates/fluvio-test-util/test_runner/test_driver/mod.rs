@@ -213,6 +213,11 @@ impl TestDriver {
         let storage = TopicStorageConfig {
             segment_size: Some(option.topic_segment_size),
             max_partition_size: Some(option.topic_max_partition_size),
+            durability: None,
+            backend: None,
+            min_in_sync_replicas: None,
+            index_interval_bytes: None,
+            index_max_bytes: None,
         };
         topic_spec.set_storage(storage);
 
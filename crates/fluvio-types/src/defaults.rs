@@ -37,12 +37,22 @@ pub const SPU_LOG_SIZE: &str = "10Gi";
 pub const SPU_LOG_INDEX_MAX_BYTES: u32 = 10485760;
 pub const SPU_LOG_INDEX_MAX_INTERVAL_BYTES: u32 = 4096;
 pub const SPU_LOG_SEGMENT_MAX_BYTES: u32 = 1073741824;
+pub const SPU_LOG_SCRUB_INTERVAL_SECS: u64 = 6 * 3600; // 0 disables scrubbing
+pub const SPU_LOG_PRELOAD_BYTES: u64 = 0; // 0 disables preloading
+pub const SPU_CONSUMER_OFFSET_RETENTION_SECS: u64 = 0; // 0 keeps offsets forever
+pub const SPU_CONSUMER_OFFSET_EXPIRY_INTERVAL_SECS: u64 = 3600;
+pub const SPU_FETCH_MAX_WAIT_MS: u64 = 500; // 0 disables long polling
+pub const SPU_MAX_QUEUED_REQUESTS: u64 = 1000;
+pub const SPU_ENCRYPTION_KEY_DIR: &str = "/etc/fluvio/.keys";
+pub const SPU_SNAPSHOT_DIR: &str = "/var/lib/fluvio/snapshots";
 pub const SPU_MONITORING_UNIX_SOCKET: &str = "/tmp/fluvio-spu.sock";
+pub const SPU_SHUTDOWN_TIMEOUT_SECS: u64 = 30;
 
 pub const SPU_PARTITION_MAX_BYTES: u64 = 107_374_182_400; //100Gb
 pub const SPU_PARTITION_MAX_BYTES_MIN: u64 = SPU_LOG_LOG_SEGMENT_MAX_BYTE_MIN as u64 * 2;
 
 pub const SPU_LOG_LOG_SEGMENT_MAX_BYTE_MIN: u32 = 1024; // crd
+pub const SPU_LOG_INDEX_MAX_BYTES_MIN: u32 = 1024; // crd
 
 pub const STORAGE_RETENTION_SECONDS: u32 = 7 * 24 * 3600;
 
@@ -54,6 +64,8 @@ pub const STORAGE_MAX_BATCH_SIZE: u32 = 33_554_432;
 pub const SPU_SMARTENGINE_STORE_MAX_BYTES: usize = 1_073_741_824; //1Gb
 
 pub const CONSUMER_STORAGE_TOPIC: &str = "consumer-offset";
+/// cluster lifecycle events, topic names can't start with underscore so it isn't `_events`
+pub const EVENTS_TOPIC: &str = "cluster-events";
 
 // CLI config
 pub const CLI_PROFILES_DIR: &str = "profiles";
@@ -75,6 +87,7 @@ pub const FLV_SPU_PRIVATE_PORT: &str = "FLV_SPU_PRIVATE_PORT";
 pub const FLV_SC_PRIVATE_HOST: &str = "FLV_SC_PRIVATE_HOST";
 pub const FLV_SC_PRIVATE_PORT: &str = "FLV_SC_PRIVATE_PORT";
 pub const FLV_SC_RETRY_TIMEOUT_MS: &str = "FLV_SC_RETRY_TIMEOUT_MS";
+pub const FLV_LOG_FORMAT: &str = "FLV_LOG_FORMAT";
 pub const FLV_REPLICA_IN_SYNC_REPLICA_MIN: &str = "FLV_REPLICA_IN_SYNC_REPLICA_MIN";
 pub const FLV_LOG_BASE_DIR: &str = "FLV_LOG_BASE_DIR";
 pub const FLV_LOG_SIZE: &str = "FLV_LOG_SIZE";
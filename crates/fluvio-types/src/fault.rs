@@ -0,0 +1,194 @@
+//! Fault injection for integration tests.
+//!
+//! Tests register faults for named points, instrumented code calls [`check`] at those
+//! points with scope of its instance and applies returned action. Fault is removed when
+//! guard returned by [`inject`] is dropped, so it doesn't outlive the test.
+//! Only compiled with `fault-injection` feature.
+//!
+//! Fault scoped with [`inject_scoped`] is only triggered by instances whose scope is it or
+//! a path under it, so tests running in parallel don't see each other's faults.
+//! Storage is scoped by replica directory and socket by peer address.
+//!
+//! ```ignore
+//! let _fault = fault::inject_scoped(fault::SOCKET_SEND, &leader_addr, FaultAction::Drop, Some(1));
+//! ```
+
+use std::io::ErrorKind;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use tracing::info;
+
+/// sending request or response on socket
+pub const SOCKET_SEND: &str = "socket.send";
+/// writing records to replica storage
+pub const STORAGE_WRITE: &str = "storage.write";
+/// reading records from replica storage
+pub const STORAGE_READ: &str = "storage.read";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FaultAction {
+    /// sleep before performing operation
+    Delay(Duration),
+    /// silently skip operation
+    Drop,
+    /// fail operation with error of kind
+    Error(ErrorKind),
+}
+
+#[derive(Debug)]
+struct Fault {
+    id: u64,
+    point: String,
+    /// instance scope or its parent path, any instance if none
+    scope: Option<String>,
+    action: FaultAction,
+    /// number of times fault is triggered, forever if none
+    remaining: Option<u32>,
+}
+
+impl Fault {
+    fn matches(&self, point: &str, scope: &str) -> bool {
+        self.point == point
+            && self
+                .scope
+                .as_ref()
+                .map_or(true, |prefix| Path::new(scope).starts_with(prefix))
+    }
+}
+
+static FAULTS: Mutex<Vec<Fault>> = Mutex::new(Vec::new());
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Removes fault when dropped
+#[must_use = "fault is removed when guard is dropped"]
+#[derive(Debug)]
+pub struct FaultGuard {
+    id: u64,
+}
+
+impl Drop for FaultGuard {
+    fn drop(&mut self) {
+        if let Ok(mut faults) = FAULTS.lock() {
+            faults.retain(|fault| fault.id != self.id);
+        }
+    }
+}
+
+/// register fault for point of every instance.
+/// Fault is triggered `times` times, forever if none, `Some(0)` disables it
+pub fn inject(point: &str, action: FaultAction, times: Option<u32>) -> FaultGuard {
+    register(point, None, action, times)
+}
+
+/// register fault for point of instances whose scope is `scope` or a path under it
+pub fn inject_scoped(
+    point: &str,
+    scope: &str,
+    action: FaultAction,
+    times: Option<u32>,
+) -> FaultGuard {
+    register(point, Some(scope), action, times)
+}
+
+fn register(
+    point: &str,
+    scope: Option<&str>,
+    action: FaultAction,
+    times: Option<u32>,
+) -> FaultGuard {
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    if times == Some(0) {
+        info!(point, scope, ?action, "fault disabled");
+        return FaultGuard { id };
+    }
+
+    info!(point, scope, ?action, ?times, "injecting fault");
+    if let Ok(mut faults) = FAULTS.lock() {
+        faults.push(Fault {
+            id,
+            point: point.to_owned(),
+            scope: scope.map(str::to_owned),
+            action,
+            remaining: times,
+        });
+    }
+    FaultGuard { id }
+}
+
+/// action to apply at point of instance with scope, if fault is registered.
+/// Most recently injected fault wins
+pub fn check(point: &str, scope: &str) -> Option<FaultAction> {
+    let mut faults = FAULTS.lock().ok()?;
+    let index = faults
+        .iter()
+        .rposition(|fault| fault.matches(point, scope))?;
+    let fault = &mut faults[index];
+    let action = fault.action.clone();
+    if let Some(remaining) = fault.remaining.as_mut() {
+        *remaining -= 1;
+        if *remaining == 0 {
+            faults.remove(index);
+        }
+    }
+    info!(point, scope, ?action, "fault triggered");
+    Some(action)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fault_times() {
+        let point = "test.times";
+        assert_eq!(check(point, ""), None);
+
+        let _fault = inject(point, FaultAction::Drop, Some(2));
+        assert_eq!(check(point, ""), Some(FaultAction::Drop));
+        assert_eq!(check(point, ""), Some(FaultAction::Drop));
+        assert_eq!(check(point, ""), None);
+
+        let _fault = inject(point, FaultAction::Drop, Some(0));
+        assert_eq!(check(point, ""), None);
+    }
+
+    #[test]
+    fn test_fault_guard() {
+        let point = "test.guard";
+        let fault = inject(point, FaultAction::Error(ErrorKind::Other), None);
+        for _ in 0..5 {
+            assert_eq!(
+                check(point, "replica"),
+                Some(FaultAction::Error(ErrorKind::Other))
+            );
+        }
+
+        // newer fault wins while both are registered
+        let delay = inject(point, FaultAction::Delay(Duration::from_millis(1)), None);
+        assert_eq!(
+            check(point, "replica"),
+            Some(FaultAction::Delay(Duration::from_millis(1)))
+        );
+        drop(fault);
+        assert_eq!(
+            check(point, "replica"),
+            Some(FaultAction::Delay(Duration::from_millis(1)))
+        );
+        drop(delay);
+        assert_eq!(check(point, "replica"), None);
+    }
+
+    #[test]
+    fn test_fault_scope() {
+        let point = "test.scope";
+        let _fault = inject_scoped(point, "/tmp/test1", FaultAction::Drop, None);
+
+        assert_eq!(check(point, "/tmp/test1/topic-0"), Some(FaultAction::Drop));
+        assert_eq!(check(point, "/tmp/test2/topic-0"), None);
+        assert_eq!(check(point, "/tmp/test10/topic-0"), None);
+        assert_eq!(check("test.other", "/tmp/test1/topic-0"), None);
+    }
+}
@@ -4,10 +4,14 @@ pub mod defaults;
 pub mod macros;
 pub mod partition;
 pub mod config_file;
+pub mod secret;
 
 #[cfg(feature = "events")]
 pub mod event;
 
+#[cfg(feature = "fault-injection")]
+pub mod fault;
+
 pub use partition::PartitionError;
 
 //
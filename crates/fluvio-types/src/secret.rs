@@ -0,0 +1,308 @@
+//! Secret providers
+//!
+//! Credentials such as TLS keys and auth tokens are referenced as `<provider>:<name>`
+//! instead of being stored as plaintext in configuration. For example:
+//!
+//! ```text
+//! env:FLUVIO_TOKEN              value of environment variable
+//! file:/etc/fluvio/token        content of file
+//! k8s:fluvio-auth/token         key `token` of Kubernetes secret `fluvio-auth` mounted as volume
+//! vault:secret/fluvio#token     field `token` of Vault KV secret, requires vault provider
+//! ```
+
+use std::collections::HashMap;
+use std::fmt::{self, Debug, Display};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use thiserror::Error;
+use tracing::debug;
+
+pub const ENV_PROVIDER: &str = "env";
+pub const FILE_PROVIDER: &str = "file";
+pub const K8_PROVIDER: &str = "k8s";
+pub const VAULT_PROVIDER: &str = "vault";
+
+/// directory where Kubernetes secrets are mounted, one sub directory per secret
+pub const K8_SECRET_MOUNT_ENV: &str = "FLV_SECRET_MOUNT";
+pub const K8_SECRET_MOUNT_DEFAULT: &str = "/var/run/secrets/fluvio";
+
+#[derive(Debug, Error)]
+pub enum SecretError {
+    #[error("invalid secret reference: {0}, expected <provider>:<name>")]
+    InvalidRef(String),
+    #[error("no secret provider registered for: {0}")]
+    UnknownProvider(String),
+    #[error("secret not found: {0}")]
+    NotFound(String),
+    #[error("failed to read secret {name}: {source}")]
+    Io {
+        name: String,
+        source: std::io::Error,
+    },
+    #[error("secret provider error: {0}")]
+    Provider(String),
+}
+
+impl From<SecretError> for std::io::Error {
+    fn from(err: SecretError) -> Self {
+        match err {
+            SecretError::Io { source, .. } => source,
+            SecretError::NotFound(_) => std::io::Error::new(std::io::ErrorKind::NotFound, err),
+            _ => std::io::Error::new(std::io::ErrorKind::InvalidInput, err),
+        }
+    }
+}
+
+/// Source of secret values
+pub trait SecretProvider: Debug + Send + Sync {
+    /// return value of secret, `name` is provider specific
+    fn get_secret(&self, name: &str) -> Result<String, SecretError>;
+}
+
+pub type SharedSecretProvider = Arc<dyn SecretProvider>;
+
+/// Reads secret from environment variable
+#[derive(Debug, Default)]
+pub struct EnvSecretProvider;
+
+impl SecretProvider for EnvSecretProvider {
+    fn get_secret(&self, name: &str) -> Result<String, SecretError> {
+        std::env::var(name).map_err(|_| SecretError::NotFound(format!("{ENV_PROVIDER}:{name}")))
+    }
+}
+
+/// Reads secret from file, relative names are resolved against `base_dir`
+#[derive(Debug, Default)]
+pub struct FileSecretProvider {
+    base_dir: Option<PathBuf>,
+}
+
+impl FileSecretProvider {
+    pub fn new(base_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            base_dir: Some(base_dir.into()),
+        }
+    }
+
+    fn read(&self, name: &str, path: PathBuf) -> Result<String, SecretError> {
+        let path = match &self.base_dir {
+            Some(base_dir) if path.is_relative() => base_dir.join(path),
+            _ => path,
+        };
+        debug!(?path, "reading secret");
+        std::fs::read_to_string(&path)
+            // editors and `echo` leave trailing new line
+            .map(|value| value.trim_end_matches(['\r', '\n']).to_owned())
+            .map_err(|source| match source.kind() {
+                std::io::ErrorKind::NotFound => SecretError::NotFound(name.to_owned()),
+                _ => SecretError::Io {
+                    name: name.to_owned(),
+                    source,
+                },
+            })
+    }
+}
+
+impl SecretProvider for FileSecretProvider {
+    fn get_secret(&self, name: &str) -> Result<String, SecretError> {
+        self.read(name, PathBuf::from(name))
+    }
+}
+
+/// Reads key of Kubernetes secret mounted as volume.
+/// Name is `<secret>/<key>`, file is `<mount_dir>/<secret>/<key>`
+#[derive(Debug)]
+pub struct K8SecretProvider {
+    files: FileSecretProvider,
+}
+
+impl Default for K8SecretProvider {
+    fn default() -> Self {
+        let mount_dir = std::env::var(K8_SECRET_MOUNT_ENV)
+            .unwrap_or_else(|_| K8_SECRET_MOUNT_DEFAULT.to_owned());
+        Self::new(mount_dir)
+    }
+}
+
+impl K8SecretProvider {
+    pub fn new(mount_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            files: FileSecretProvider::new(mount_dir),
+        }
+    }
+}
+
+impl SecretProvider for K8SecretProvider {
+    fn get_secret(&self, name: &str) -> Result<String, SecretError> {
+        let (secret, key) = name
+            .split_once('/')
+            .filter(|(secret, key)| !secret.is_empty() && !key.is_empty() && !key.contains('/'))
+            .ok_or_else(|| SecretError::InvalidRef(format!("{K8_PROVIDER}:{name}")))?;
+        self.files.read(name, PathBuf::from(secret).join(key))
+    }
+}
+
+/// Reference to secret: `<provider>:<name>`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SecretRef {
+    provider: String,
+    name: String,
+}
+
+impl SecretRef {
+    pub fn new(provider: impl Into<String>, name: impl Into<String>) -> Self {
+        Self {
+            provider: provider.into(),
+            name: name.into(),
+        }
+    }
+
+    pub fn provider(&self) -> &str {
+        &self.provider
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// resolve with built-in providers
+    pub fn resolve(&self) -> Result<String, SecretError> {
+        SecretResolver::default().resolve(self)
+    }
+}
+
+impl Display for SecretRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.provider, self.name)
+    }
+}
+
+impl FromStr for SecretRef {
+    type Err = SecretError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            Some((provider, name)) if !provider.is_empty() && !name.is_empty() => {
+                Ok(Self::new(provider, name))
+            }
+            _ => Err(SecretError::InvalidRef(s.to_owned())),
+        }
+    }
+}
+
+impl Serialize for SecretRef {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for SecretRef {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        value.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// Dispatches secret references to registered providers.
+/// `env`, `file` and `k8s` are registered by default.
+#[derive(Debug, Clone)]
+pub struct SecretResolver {
+    providers: HashMap<String, SharedSecretProvider>,
+}
+
+impl Default for SecretResolver {
+    fn default() -> Self {
+        Self::empty()
+            .with_provider(ENV_PROVIDER, Arc::new(EnvSecretProvider))
+            .with_provider(FILE_PROVIDER, Arc::new(FileSecretProvider::default()))
+            .with_provider(K8_PROVIDER, Arc::new(K8SecretProvider::default()))
+    }
+}
+
+impl SecretResolver {
+    pub fn empty() -> Self {
+        Self {
+            providers: HashMap::new(),
+        }
+    }
+
+    /// register provider, replaces existing provider with same name
+    pub fn with_provider(
+        mut self,
+        provider: impl Into<String>,
+        secrets: SharedSecretProvider,
+    ) -> Self {
+        self.providers.insert(provider.into(), secrets);
+        self
+    }
+
+    pub fn resolve(&self, secret: &SecretRef) -> Result<String, SecretError> {
+        let provider = self
+            .providers
+            .get(secret.provider())
+            .ok_or_else(|| SecretError::UnknownProvider(secret.provider().to_owned()))?;
+        debug!(%secret, "resolving secret");
+        provider.get_secret(secret.name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_secret_ref() {
+        let secret: SecretRef = "vault:secret/fluvio#token".parse().expect("parse");
+        assert_eq!(secret.provider(), "vault");
+        assert_eq!(secret.name(), "secret/fluvio#token");
+        assert_eq!(secret.to_string(), "vault:secret/fluvio#token");
+
+        let secret: SecretRef = "file:c:/fluvio/token".parse().expect("parse");
+        assert_eq!(secret.provider(), "file");
+        assert_eq!(secret.name(), "c:/fluvio/token");
+
+        assert!("token".parse::<SecretRef>().is_err());
+        assert!(":token".parse::<SecretRef>().is_err());
+        assert!("env:".parse::<SecretRef>().is_err());
+    }
+
+    #[test]
+    fn test_resolve_file_and_k8_secret() {
+        let dir = std::env::temp_dir().join("fluvio-secret-test");
+        fs::create_dir_all(dir.join("fluvio-auth")).expect("dir");
+        fs::write(dir.join("token"), "file-token\n").expect("write");
+        fs::write(dir.join("fluvio-auth").join("token"), "k8-token").expect("write");
+
+        let resolver = SecretResolver::empty()
+            .with_provider(FILE_PROVIDER, Arc::new(FileSecretProvider::new(&dir)))
+            .with_provider(K8_PROVIDER, Arc::new(K8SecretProvider::new(&dir)));
+
+        let file_token = resolver
+            .resolve(&"file:token".parse().expect("parse"))
+            .expect("file");
+        assert_eq!(file_token, "file-token");
+
+        let k8_token = resolver
+            .resolve(&"k8s:fluvio-auth/token".parse().expect("parse"))
+            .expect("k8s");
+        assert_eq!(k8_token, "k8-token");
+
+        assert!(matches!(
+            resolver.resolve(&"k8s:fluvio-auth/missing".parse().expect("parse")),
+            Err(SecretError::NotFound(_))
+        ));
+        assert!(matches!(
+            resolver.resolve(&"k8s:token".parse().expect("parse")),
+            Err(SecretError::InvalidRef(_))
+        ));
+        assert!(matches!(
+            resolver.resolve(&"env:PATH".parse().expect("parse")),
+            Err(SecretError::UnknownProvider(_))
+        ));
+    }
+}
@@ -0,0 +1,82 @@
+//! Garbage Collection Command
+//!
+//! The `gc` command removes versions from the FVM cache which are no longer
+//! needed: leftovers of interrupted installs, and least recently used versions
+//! when the cache exceeds its maximum size. The active version is never removed.
+
+use anyhow::Result;
+use bytesize::ByteSize;
+use clap::Parser;
+use colored::Colorize;
+
+use fluvio_hub_util::cache::{lru_evictions, remove_entries, CacheEntry};
+
+use crate::common::notify::Notify;
+use crate::common::settings::Settings;
+use crate::common::version_directory::VersionDirectory;
+use crate::common::workdir::fvm_versions_path;
+
+#[derive(Debug, Parser)]
+pub struct GcOpt {
+    /// Remove least recently used versions until the cache fits this size, e.g. "1GB"
+    #[arg(long, value_name = "SIZE")]
+    max_size: Option<ByteSize>,
+    /// Print versions which would be removed, without removing them
+    #[arg(long)]
+    dry_run: bool,
+}
+
+impl GcOpt {
+    pub async fn process(&self, notify: Notify) -> Result<()> {
+        let versions_path = fvm_versions_path()?;
+
+        if !versions_path.exists() {
+            notify.info("No versions installed, nothing to collect");
+            return Ok(());
+        }
+
+        let active = Settings::open()?.channel;
+        let mut active_size = 0;
+        let mut orphans = Vec::new();
+        let mut versions = Vec::new();
+
+        for entry in versions_path.read_dir()? {
+            let path = entry?.path();
+            let cached = CacheEntry::open(&path)?;
+
+            // Directories without a readable manifest are left by interrupted installs
+            match path.is_dir().then(|| VersionDirectory::open(path.clone())) {
+                Some(Ok(version_dir)) if Some(&version_dir.manifest.channel) == active.as_ref() => {
+                    active_size += cached.size;
+                }
+                Some(Ok(_)) => versions.push(cached),
+                _ => orphans.push(cached),
+            }
+        }
+
+        let mut evicted = orphans;
+        if let Some(max_size) = self.max_size {
+            let budget = max_size.as_u64().saturating_sub(active_size);
+            evicted.extend(lru_evictions(versions, budget));
+        }
+
+        let report = remove_entries(evicted, self.dry_run)?;
+        for path in &report.removed {
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            if self.dry_run {
+                notify.info(format!("Would remove {}", name.bold()));
+            } else {
+                notify.info(format!("Removed {}", name.bold()));
+            }
+        }
+
+        let reclaimed = ByteSize(report.reclaimed).to_string();
+        if self.dry_run {
+            notify.done(format!("{} would be reclaimed", reclaimed.bold()));
+        } else {
+            notify.done(format!("Reclaimed {}", reclaimed.bold()));
+        }
+
+        Ok(())
+    }
+}
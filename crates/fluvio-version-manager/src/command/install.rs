@@ -4,17 +4,18 @@
 //! FVM cache.
 
 use std::fs::create_dir_all;
+use std::sync::Arc;
 
 use anyhow::Result;
 use clap::Parser;
 use url::Url;
 
 use fluvio_hub_util::HUB_REMOTE;
-use fluvio_hub_util::fvm::{Client, Channel};
+use fluvio_hub_util::fvm::{Client, Channel, MetricsSummary};
 
 use crate::common::TARGET;
 use crate::common::notify::Notify;
-use crate::common::version_installer::VersionInstaller;
+use crate::common::version_installer::{VersionInstaller, print_download_summary};
 use crate::common::workdir::fvm_versions_path;
 
 /// The `install` command is responsible of installing the desired Package Set
@@ -37,11 +38,15 @@ impl InstallOpt {
             create_dir_all(&versions_path)?;
         }
 
-        let client = Client::new(self.registry.as_str())?;
+        let summary = Arc::new(MetricsSummary::default());
+        let client = Client::new(self.registry.as_str())?.with_metrics(summary.clone());
         let pkgset = client.fetch_package_set(&self.version, TARGET).await?;
 
-        VersionInstaller::new(self.version.to_owned(), pkgset, notify)
+        VersionInstaller::new(self.version.to_owned(), pkgset, notify.clone(), client)
             .install()
-            .await
+            .await?;
+        print_download_summary(&notify, &summary);
+
+        Ok(())
     }
 }
@@ -1,4 +1,5 @@
 pub mod current;
+pub mod gc;
 pub mod install;
 pub mod itself;
 pub mod list;
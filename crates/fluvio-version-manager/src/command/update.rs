@@ -1,17 +1,19 @@
 //! Updates version of the current channel to the most recent one
 
+use std::sync::Arc;
+
 use anyhow::{Result, Error};
 use clap::Args;
 use colored::Colorize;
 use url::Url;
 
 use fluvio_hub_util::HUB_REMOTE;
-use fluvio_hub_util::fvm::{Client, Channel, PackageSet};
+use fluvio_hub_util::fvm::{Client, Channel, MetricsSummary, PackageSet};
 
 use crate::common::TARGET;
 use crate::common::notify::Notify;
 use crate::common::settings::Settings;
-use crate::common::version_installer::VersionInstaller;
+use crate::common::version_installer::{VersionInstaller, print_download_summary};
 
 #[derive(Debug, Args)]
 pub struct UpdateOpt {
@@ -35,7 +37,9 @@ impl UpdateOpt {
             return Ok(());
         }
 
-        let latest_pkgset = self.fetch_latest_version(&channel).await?;
+        let summary = Arc::new(MetricsSummary::default());
+        let client = Client::new(self.registry.as_str())?.with_metrics(summary.clone());
+        let latest_pkgset = self.fetch_latest_version(&client, &channel).await?;
         let Some(version) = settings.version else {
             notify.info(
                 "No installed version detected, please install a version first using `fvm install`",
@@ -55,9 +59,12 @@ impl UpdateOpt {
                         version
                     ));
 
-                    return VersionInstaller::new(channel, latest_pkgset, notify)
+                    VersionInstaller::new(channel, latest_pkgset, notify.clone(), client)
                         .install()
-                        .await;
+                        .await?;
+                    print_download_summary(&notify, &summary);
+
+                    return Ok(());
                 }
 
                 notify.done("You are already up to date");
@@ -74,9 +81,12 @@ impl UpdateOpt {
                         version
                     ));
 
-                    return VersionInstaller::new(channel, latest_pkgset, notify)
+                    VersionInstaller::new(channel, latest_pkgset, notify.clone(), client)
                         .install()
-                        .await;
+                        .await?;
+                    print_download_summary(&notify, &summary);
+
+                    return Ok(());
                 }
 
                 notify.done("You are already up to date");
@@ -89,14 +99,13 @@ impl UpdateOpt {
         Ok(())
     }
 
-    async fn fetch_latest_version(&self, channel: &Channel) -> Result<PackageSet> {
+    async fn fetch_latest_version(&self, client: &Client, channel: &Channel) -> Result<PackageSet> {
         if channel.is_version_tag() {
             return Err(Error::msg(
                 "Cannot update a static version tag. You must use a channel.",
             ));
         }
 
-        let client = Client::new(self.registry.as_str())?;
         let pkgset = client.fetch_package_set(channel, TARGET).await?;
 
         Ok(pkgset)
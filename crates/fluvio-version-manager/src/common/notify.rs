@@ -1,5 +1,6 @@
 use colored::Colorize;
 
+#[derive(Clone)]
 pub struct Notify {
     /// Whether to suppress all output
     quiet: bool,
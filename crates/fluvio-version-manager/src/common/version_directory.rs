@@ -5,6 +5,7 @@ use std::path::PathBuf;
 
 use anyhow::Result;
 
+use fluvio_hub_util::cache::touch;
 use fluvio_hub_util::fvm::Channel;
 
 use crate::common::manifest::{PACKAGE_SET_MANIFEST_FILENAME, VersionManifest};
@@ -110,6 +111,9 @@ impl VersionDirectory {
 
         Settings::open()?.update_from_manifest(&self.manifest)?;
 
+        // Last use of versions decides which ones are collected first by `fvm gc`
+        touch(self.path.join(PACKAGE_SET_MANIFEST_FILENAME))?;
+
         Ok(())
     }
 
@@ -1,12 +1,14 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::fs::{create_dir, copy, rename};
+use std::time::Instant;
 
 use anyhow::{anyhow, Result};
+use bytesize::ByteSize;
 use tempfile::TempDir;
 
-use fluvio_hub_util::fvm::{PackageSet, Download, Channel};
+use fluvio_hub_util::fvm::{Artifact, Channel, Client, MetricsSummary, PackageSet};
 
-use super::manifest::{VersionedArtifact, VersionManifest};
+use super::manifest::{VersionedArtifact, VersionManifest, PACKAGE_SET_MANIFEST_FILENAME};
 use super::notify::Notify;
 use super::version_directory::VersionDirectory;
 use super::workdir::fvm_versions_path;
@@ -15,14 +17,16 @@ pub struct VersionInstaller {
     channel: Channel,
     package_set: PackageSet,
     notify: Notify,
+    client: Client,
 }
 
 impl VersionInstaller {
-    pub fn new(channel: Channel, package_set: PackageSet, notify: Notify) -> Self {
+    pub fn new(channel: Channel, package_set: PackageSet, notify: Notify, client: Client) -> Self {
         Self {
             channel,
             package_set,
             notify,
+            client,
         }
     }
 
@@ -31,8 +35,22 @@ impl VersionInstaller {
         // destination directory. By dropping `tmp_dir` the directory will be
         // deleted from the filesystem.
         let tmp_dir = TempDir::new()?;
+        let installed = self.installed_manifest();
 
         for (idx, artf) in self.package_set.artifacts.iter().enumerate() {
+            if let Some(manifest) = &installed {
+                if self.reuse_installed(manifest, artf, tmp_dir.path())? {
+                    self.notify.info(format!(
+                        "Reusing ({}/{}): {}@{}",
+                        idx + 1,
+                        self.package_set.artifacts.len(),
+                        artf.name,
+                        artf.version
+                    ));
+                    continue;
+                }
+            }
+
             self.notify.info(format!(
                 "Downloading ({}/{}): {}@{}",
                 idx + 1,
@@ -41,7 +59,7 @@ impl VersionInstaller {
                 artf.version
             ));
 
-            let artf_path = artf.download(tmp_dir.path().to_path_buf()).await?;
+            let artf_path = self.client.download(artf, tmp_dir.path()).await?;
             Self::set_executable_mode(artf_path)?;
         }
 
@@ -74,6 +92,45 @@ impl VersionInstaller {
         Ok(())
     }
 
+    /// Manifest of the version currently installed for this channel, if any
+    fn installed_manifest(&self) -> Option<VersionManifest> {
+        let path = fvm_versions_path()
+            .ok()?
+            .join(self.channel.to_string())
+            .join(PACKAGE_SET_MANIFEST_FILENAME);
+
+        VersionManifest::open(path).ok()
+    }
+
+    /// Copies the artifact from the installed version to `tmp_dir` when the
+    /// same version is already installed. Returns `false` if it must be downloaded.
+    fn reuse_installed(
+        &self,
+        manifest: &VersionManifest,
+        artf: &Artifact,
+        tmp_dir: &Path,
+    ) -> Result<bool> {
+        let version = artf.version.to_string();
+        let is_installed = manifest
+            .contents
+            .iter()
+            .flatten()
+            .any(|installed| installed.name == artf.name && installed.version == version);
+        let src = fvm_versions_path()?
+            .join(self.channel.to_string())
+            .join(&artf.name);
+
+        if !is_installed || !src.exists() {
+            return Ok(false);
+        }
+
+        let start = Instant::now();
+        let bytes = copy(&src, tmp_dir.join(&artf.name))?;
+
+        self.client.record_cache_hit(artf, bytes, start.elapsed());
+        Ok(true)
+    }
+
     /// Allocates artifacts in the FVM `versions` directory for future use.
     /// Returns the path to the allocated version directory.
     async fn store_artifacts(
@@ -132,6 +189,23 @@ impl VersionInstaller {
     }
 }
 
+/// Prints the artifacts retrieved by an install, their size and time taken
+pub fn print_download_summary(notify: &Notify, summary: &MetricsSummary) {
+    let downloads = summary.downloads();
+    if downloads.is_empty() {
+        return;
+    }
+
+    notify.info(format!(
+        "Retrieved {} artifacts in {:.1}s: {} downloaded, {} reused, {} retries",
+        downloads.len(),
+        summary.total_duration().as_secs_f64(),
+        ByteSize(summary.total_bytes()),
+        summary.cache_hits(),
+        summary.total_retries()
+    ));
+}
+
 #[cfg(test)]
 mod test {
     use std::fs::File;
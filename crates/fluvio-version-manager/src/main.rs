@@ -6,6 +6,7 @@ use clap::Parser;
 use command::uninstall::UninstallOpt;
 
 use self::command::current::CurrentOpt;
+use self::command::gc::GcOpt;
 use self::command::install::InstallOpt;
 use self::command::itself::SelfOpt;
 use self::command::list::ListOpt;
@@ -49,6 +50,9 @@ pub enum Command {
     /// Print the current active Fluvio Version
     #[command(name = "current")]
     Current(CurrentOpt),
+    /// Remove unused versions from the FVM cache
+    #[command(name = "gc")]
+    Gc(GcOpt),
     /// Manage FVM
     #[command(name = "self")]
     Itself(SelfOpt),
@@ -79,6 +83,7 @@ impl Cli {
 
         match command {
             Command::Current(cmd) => cmd.process(notify).await,
+            Command::Gc(cmd) => cmd.process(notify).await,
             Command::Itself(cmd) => cmd.process(notify).await,
             Command::Install(cmd) => cmd.process(notify).await,
             Command::List(cmd) => cmd.process(notify).await,
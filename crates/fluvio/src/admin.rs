@@ -16,6 +16,15 @@ use fluvio_sc_schema::objects::{
     CommonCreateRequest,
 };
 use fluvio_sc_schema::{AdminSpec, DeletableAdminSpec, CreatableAdminSpec, TryEncodableFrom};
+use fluvio_sc_schema::partition::ReassignPartitionRequest;
+use fluvio_sc_schema::topic::{AddPartitionsRequest, SetReadOnlyRequest};
+use fluvio_sc_schema::spu::{DrainSpuRequest, DrainSpuResponse};
+use fluvio_sc_schema::cli_release::{RecommendedCliRequest, RecommendedCliResponse};
+use fluvio_sc_schema::audit::{AuditEntry, AuditLogRequest};
+use fluvio_sc_schema::acl::{AclEntry, AclListRequest, AclListResponse, AclUpdateRequest};
+use fluvio_sc_schema::diagnostics::{ClusterDiagnosticsRequest, ClusterHealthReport};
+use fluvio_sc_schema::events::{ClusterEvent, ClusterEventKind, ClusterEventsRequest};
+use fluvio_types::{PartitionCount, PartitionId, SpuId};
 use fluvio_socket::{ClientConfig, VersionedSerialSocket, SerialFrame, MultiplexerSocket};
 
 use crate::FluvioConfig;
@@ -116,14 +125,16 @@ impl FluvioAdmin {
     #[instrument(skip(config))]
     pub async fn connect_with_config(config: &FluvioConfig) -> Result<Self> {
         let connector = DomainConnector::try_from(config.tls.clone())?;
-        let client_config =
+        let mut client_config =
             ClientConfig::new(&config.endpoint, connector, config.use_spu_local_address);
+        client_config.set_auth_token(config.resolve_auth_token()?);
         let inner_client = client_config.connect().await?;
         debug!(addr = %inner_client.config().addr(), "connected to cluster");
 
         let (socket, config, versions) = inner_client.split();
         if let Some(watch_version) = versions.lookup_version::<ObjectApiWatchRequest>() {
-            let socket = MultiplexerSocket::shared(socket);
+            let socket =
+                MultiplexerSocket::shared_with_observers(socket, config.observers().clone());
             let metadata = MetadataStores::start(socket.clone(), watch_version).await?;
             let versioned_socket = VersionedSerialSocket::new(socket, config, versions);
 
@@ -301,6 +312,212 @@ impl FluvioAdmin {
             .map(|out: ListResponse<S>| out.inner())
     }
 
+    /// Move partition replicas to the target SPUs. First SPU of the target becomes leader.
+    ///
+    /// This only starts the reassignment. New replicas catch up with the leader in the
+    /// background, then leadership is switched and old replicas are removed.
+    /// Progress can be followed in the partition list.
+    #[instrument(skip(self))]
+    pub async fn reassign_partition(
+        &self,
+        topic: impl Into<String> + Debug,
+        partition: PartitionId,
+        target: Vec<SpuId>,
+    ) -> Result<()> {
+        if self
+            .socket
+            .lookup_version::<ReassignPartitionRequest>()
+            .is_none()
+        {
+            return Err(anyhow!(
+                "partition reassignment is not supported by the cluster"
+            ));
+        }
+        let request = ReassignPartitionRequest::new((topic, partition), target);
+        self.socket.send_receive(request).await?.as_result()?;
+        Ok(())
+    }
+
+    /// Increase partition count of existing topic to `partitions` total.
+    ///
+    /// Replicas for new partitions are scheduled by the cluster in the background.
+    /// Keys may be routed to different partitions after the increase, see
+    /// [`Partitioner`](crate::producer::Partitioner).
+    #[instrument(skip(self))]
+    pub async fn add_partitions(
+        &self,
+        topic: impl Into<String> + Debug,
+        partitions: PartitionCount,
+    ) -> Result<()> {
+        if self
+            .socket
+            .lookup_version::<AddPartitionsRequest>()
+            .is_none()
+        {
+            return Err(anyhow!("adding partitions is not supported by the cluster"));
+        }
+        let request = AddPartitionsRequest::new(topic, partitions);
+        self.socket.send_receive(request).await?.as_result()?;
+        Ok(())
+    }
+
+    /// Mark topic, or only `partition` of it, read-only. Passing `false` makes it writable again.
+    ///
+    /// Produces to read-only partitions fail with [`ErrorCode::PartitionReadOnly`],
+    /// consumers can still read all records.
+    ///
+    /// [`ErrorCode::PartitionReadOnly`]: fluvio_protocol::link::ErrorCode::PartitionReadOnly
+    #[instrument(skip(self))]
+    pub async fn set_read_only(
+        &self,
+        topic: impl Into<String> + Debug,
+        partition: Option<PartitionId>,
+        read_only: bool,
+    ) -> Result<()> {
+        if self.socket.lookup_version::<SetReadOnlyRequest>().is_none() {
+            return Err(anyhow!("read-only mode is not supported by the cluster"));
+        }
+        let request = SetReadOnlyRequest::new(topic, partition, read_only);
+        self.socket.send_receive(request).await?.as_result()?;
+        Ok(())
+    }
+
+    /// Fluvio CLI release recommended by the cluster for clients on `target`,
+    /// so all operators of the cluster converge on the same release
+    #[instrument(skip(self))]
+    pub async fn recommended_cli(
+        &self,
+        target: impl Into<String> + Debug,
+    ) -> Result<RecommendedCliResponse> {
+        if self
+            .socket
+            .lookup_version::<RecommendedCliRequest>()
+            .is_none()
+        {
+            return Err(anyhow!("recommending cli is not supported by the cluster"));
+        }
+        let request = RecommendedCliRequest::new(target);
+        Ok(self.socket.send_receive(request).await?.as_result()?)
+    }
+
+    /// Most recent admin mutations recorded by the cluster, oldest first.
+    /// All entries kept by the cluster are returned if `limit` is 0
+    #[instrument(skip(self))]
+    pub async fn audit_log(&self, limit: u32) -> Result<Vec<AuditEntry>> {
+        if self.socket.lookup_version::<AuditLogRequest>().is_none() {
+            return Err(anyhow!("audit log is not supported by the cluster"));
+        }
+        let request = AuditLogRequest::new(limit);
+        let response = self.socket.send_receive(request).await?.as_result()?;
+        Ok(response.entries)
+    }
+
+    /// ACL entries of the cluster, and whether they are enforced
+    #[instrument(skip(self))]
+    pub async fn acl_list(&self) -> Result<AclListResponse> {
+        if self.socket.lookup_version::<AclListRequest>().is_none() {
+            return Err(anyhow!("ACLs are not supported by the cluster"));
+        }
+        let response = self
+            .socket
+            .send_receive(AclListRequest::default())
+            .await?
+            .as_result()?;
+        Ok(response)
+    }
+
+    /// Grant permissions of entries
+    #[instrument(skip(self))]
+    pub async fn acl_add(&self, entries: Vec<AclEntry>) -> Result<()> {
+        self.send_acl_update(AclUpdateRequest::add(entries)).await
+    }
+
+    /// Revoke permissions of entries
+    #[instrument(skip(self))]
+    pub async fn acl_remove(&self, entries: Vec<AclEntry>) -> Result<()> {
+        self.send_acl_update(AclUpdateRequest::remove(entries))
+            .await
+    }
+
+    async fn send_acl_update(&self, request: AclUpdateRequest) -> Result<()> {
+        if self.socket.lookup_version::<AclUpdateRequest>().is_none() {
+            return Err(anyhow!("ACLs are not supported by the cluster"));
+        }
+        self.socket.send_receive(request).await?.as_result()?;
+        Ok(())
+    }
+
+    /// Health of SPUs, partitions and mirrors aggregated by the cluster
+    #[instrument(skip(self))]
+    pub async fn cluster_health(&self) -> Result<ClusterHealthReport> {
+        if self
+            .socket
+            .lookup_version::<ClusterDiagnosticsRequest>()
+            .is_none()
+        {
+            return Err(anyhow!(
+                "cluster diagnostics are not supported by the cluster"
+            ));
+        }
+        let response = self
+            .socket
+            .send_receive(ClusterDiagnosticsRequest::default())
+            .await?
+            .as_result()?;
+        Ok(response.report)
+    }
+
+    /// Subscribe to cluster change events, all kinds if `kinds` is empty.
+    /// Each item is batch of events observed together by the cluster.
+    #[instrument(skip(self))]
+    pub async fn cluster_events(
+        &self,
+        kinds: Vec<ClusterEventKind>,
+    ) -> Result<impl Stream<Item = Result<Vec<ClusterEvent>, IoError>>> {
+        let version = self
+            .socket
+            .lookup_version::<ClusterEventsRequest>()
+            .ok_or(anyhow!("cluster events are not supported by the cluster"))?;
+        let mut req_msg = RequestMessage::new_request(ClusterEventsRequest::new(kinds));
+        req_msg.get_mut_header().set_api_version(version);
+        let stream = self.socket.new_socket().create_stream(req_msg, 10).await?;
+        Ok(stream.map(|response| match response {
+            Ok(response) => response
+                .as_result()
+                .map(|response| response.events)
+                .map_err(|err| IoError::new(ErrorKind::Other, err.to_string())),
+            Err(err) => Err(IoError::new(
+                ErrorKind::Other,
+                format!("socket error {err}"),
+            )),
+        }))
+    }
+
+    /// Start draining SPU before maintenance.
+    ///
+    /// Leadership of partitions led by SPU is moved to followers once they are caught up,
+    /// and no new replicas are assigned to SPU. Drain progresses in the background,
+    /// calling this again returns current progress. SPU can be shut down once
+    /// [`DrainSpuResponse::is_drained`] is true.
+    #[instrument(skip(self))]
+    pub async fn drain_spu(&self, spu_id: SpuId) -> Result<DrainSpuResponse> {
+        self.send_drain_spu(DrainSpuRequest::new(spu_id)).await
+    }
+
+    /// Stop draining SPU, it can be assigned replicas and leaders again
+    #[instrument(skip(self))]
+    pub async fn cancel_spu_drain(&self, spu_id: SpuId) -> Result<()> {
+        self.send_drain_spu(DrainSpuRequest::cancel(spu_id)).await?;
+        Ok(())
+    }
+
+    async fn send_drain_spu(&self, request: DrainSpuRequest) -> Result<DrainSpuResponse> {
+        if self.socket.lookup_version::<DrainSpuRequest>().is_none() {
+            return Err(anyhow!("draining spu is not supported by the cluster"));
+        }
+        Ok(self.socket.send_receive(request).await?.as_result()?)
+    }
+
     /// Watch stream of changes for metadata
     /// There is caching, this is just pass through
     #[instrument(skip(self))]
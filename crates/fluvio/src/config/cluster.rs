@@ -6,13 +6,18 @@
 use serde::{Serialize, Deserialize};
 use toml::Table as Metadata;
 
+use fluvio_types::secret::{SecretError, SecretRef};
+
 use crate::{config::TlsPolicy, FluvioError};
 
 use super::ConfigFile;
 
+/// environment variable with bearer token, overrides token of profile
+pub const FLUVIO_AUTH_TOKEN: &str = "FLUVIO_AUTH_TOKEN";
+
 /// Fluvio Cluster Target Configuration
 /// This is part of profile
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
 #[non_exhaustive]
 pub struct FluvioConfig {
     /// The address to connect to the Fluvio cluster
@@ -30,6 +35,15 @@ pub struct FluvioConfig {
     #[serde(default)]
     pub tls: TlsPolicy,
 
+    /// Bearer token to authenticate with, for clusters which require tokens.
+    /// Overridden by `FLUVIO_AUTH_TOKEN` environment variable
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auth_token: Option<String>,
+
+    /// Secret with bearer token, e.g. `k8s:fluvio-auth/token`, used instead of `auth_token`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auth_token_secret: Option<SecretRef>,
+
     /// Cluster custom metadata
     #[serde(default = "Metadata::new", skip_serializing_if = "Metadata::is_empty")]
     metadata: Metadata,
@@ -40,6 +54,23 @@ pub struct FluvioConfig {
     pub client_id: Option<String>,
 }
 
+impl std::fmt::Debug for FluvioConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FluvioConfig")
+            .field("endpoint", &self.endpoint)
+            .field("use_spu_local_address", &self.use_spu_local_address)
+            .field("tls", &self.tls)
+            .field(
+                "auth_token",
+                &self.auth_token.as_ref().map(|_| "<redacted>"),
+            )
+            .field("auth_token_secret", &self.auth_token_secret)
+            .field("metadata", &self.metadata)
+            .field("client_id", &self.client_id)
+            .finish()
+    }
+}
+
 impl FluvioConfig {
     /// get current cluster config from default profile
     pub fn load() -> Result<Self, FluvioError> {
@@ -54,6 +85,8 @@ impl FluvioConfig {
             endpoint: addr.into(),
             use_spu_local_address: false,
             tls: TlsPolicy::Disabled,
+            auth_token: None,
+            auth_token_secret: None,
             metadata: Metadata::new(),
             client_id: None,
         }
@@ -65,6 +98,32 @@ impl FluvioConfig {
         self
     }
 
+    /// Add bearer token to authenticate with
+    pub fn with_auth_token(mut self, token: impl Into<String>) -> Self {
+        self.auth_token = Some(token.into());
+        self
+    }
+
+    /// Read bearer token from secret instead of configuration
+    pub fn with_auth_token_secret(mut self, secret: SecretRef) -> Self {
+        self.auth_token_secret = Some(secret);
+        self
+    }
+
+    /// token to authenticate with, from environment, secret or configuration
+    pub fn resolve_auth_token(&self) -> Result<Option<String>, SecretError> {
+        if let Some(token) = std::env::var(FLUVIO_AUTH_TOKEN)
+            .ok()
+            .filter(|token| !token.is_empty())
+        {
+            return Ok(Some(token));
+        }
+        if let Some(secret) = &self.auth_token_secret {
+            return secret.resolve().map(Some);
+        }
+        Ok(self.auth_token.clone())
+    }
+
     pub fn query_metadata_by_name<'de, T>(&self, name: &str) -> Option<T>
     where
         T: Deserialize<'de>,
@@ -101,11 +160,10 @@ impl TryFrom<FluvioConfig> for fluvio_socket::ClientConfig {
     type Error = std::io::Error;
     fn try_from(config: FluvioConfig) -> Result<Self, Self::Error> {
         let connector = fluvio_future::net::DomainConnector::try_from(config.tls.clone())?;
-        Ok(Self::new(
-            &config.endpoint,
-            connector,
-            config.use_spu_local_address,
-        ))
+        let mut client_config =
+            Self::new(&config.endpoint, connector, config.use_spu_local_address);
+        client_config.set_auth_token(config.resolve_auth_token()?);
+        Ok(client_config)
     }
 }
 
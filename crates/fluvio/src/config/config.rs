@@ -200,6 +200,20 @@ pub struct Config {
     pub profile: HashMap<String, Profile>,
     pub cluster: HashMap<String, FluvioConfig>,
     client_id: Option<String>,
+    /// network settings of installer, shared by all profiles
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub installer: Option<InstallerConfig>,
+}
+
+/// Network settings used by the CLI to download packages and plugins
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InstallerConfig {
+    /// proxy url, e.g. "http://proxy.example.com:8080"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<String>,
+    /// PEM files of CA certificates to trust in addition to the system ones
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ca_certs: Vec<PathBuf>,
 }
 
 impl Config {
@@ -505,6 +519,43 @@ pub mod test {
         assert_eq!(cluster.endpoint, "127.0.0.1:9003");
     }
 
+    #[test]
+    fn test_installer_config() {
+        let config: Config = toml::from_str(
+            r#"
+version = "2.0"
+
+[profile]
+
+[cluster]
+
+[installer]
+proxy = "http://proxy.example.com:8080"
+ca_certs = ["/etc/ssl/corp-ca.pem"]
+"#,
+        )
+        .expect("parse");
+        assert_eq!(
+            config.installer,
+            Some(InstallerConfig {
+                proxy: Some("http://proxy.example.com:8080".to_owned()),
+                ca_certs: vec![PathBuf::from("/etc/ssl/corp-ca.pem")],
+            })
+        );
+
+        let config: Config = toml::from_str(
+            r#"
+version = "2.0"
+
+[profile]
+
+[cluster]
+"#,
+        )
+        .expect("parse");
+        assert!(config.installer.is_none());
+    }
+
     #[test]
     fn test_rename_profile() {
         let mut conf_file = ConfigFile::load(Some("test-data/profiles/config.toml".to_owned()))
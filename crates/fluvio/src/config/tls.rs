@@ -7,6 +7,7 @@ use std::fmt::{Debug, self};
 use tracing::info;
 use serde::{Deserialize, Serialize};
 use fluvio_future::net::{DomainConnector, DefaultDomainConnector};
+use fluvio_types::secret::SecretRef;
 
 /// Describes whether or not to use TLS and how
 #[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
@@ -49,6 +50,12 @@ impl From<TlsPaths> for TlsPolicy {
     }
 }
 
+impl From<TlsSecrets> for TlsPolicy {
+    fn from(secrets: TlsSecrets) -> Self {
+        Self::Verified(secrets.into())
+    }
+}
+
 /// Describes the TLS configuration either inline or via file paths
 #[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 #[serde(tag = "tls_source", content = "certs")]
@@ -59,6 +66,9 @@ pub enum TlsConfig {
     /// TLS client config with paths to keys and certs
     #[serde(rename = "files", alias = "file")]
     Files(TlsPaths),
+    /// TLS client config with keys and certs from secret provider
+    #[serde(rename = "secrets")]
+    Secrets(TlsSecrets),
 }
 
 impl TlsConfig {
@@ -67,6 +77,7 @@ impl TlsConfig {
         match self {
             TlsConfig::Files(TlsPaths { domain, .. }) => domain,
             TlsConfig::Inline(TlsCerts { domain, .. }) => domain,
+            TlsConfig::Secrets(TlsSecrets { domain, .. }) => domain,
         }
     }
 }
@@ -83,6 +94,12 @@ impl From<TlsPaths> for TlsConfig {
     }
 }
 
+impl From<TlsSecrets> for TlsConfig {
+    fn from(secrets: TlsSecrets) -> Self {
+        Self::Secrets(secrets)
+    }
+}
+
 /// TLS config with inline keys and certs
 ///
 /// Keys and certs stored in the `TlsCerts` type should be PEM PKCS1
@@ -152,6 +169,19 @@ impl TryFrom<TlsPaths> for TlsCerts {
     }
 }
 
+impl TryFrom<TlsSecrets> for TlsCerts {
+    type Error = IoError;
+
+    fn try_from(secrets: TlsSecrets) -> Result<Self, Self::Error> {
+        Ok(Self {
+            domain: secrets.domain,
+            key: secrets.key.resolve()?,
+            cert: secrets.cert.resolve()?,
+            ca_cert: secrets.ca_cert.resolve()?,
+        })
+    }
+}
+
 /// TLS config with paths to keys and certs
 #[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub struct TlsPaths {
@@ -165,6 +195,20 @@ pub struct TlsPaths {
     pub ca_cert: PathBuf,
 }
 
+/// TLS config with references to keys and certs, e.g. `k8s:fluvio-tls/tls.key`.
+/// Secrets are resolved when connecting, so they are never stored in the profile.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TlsSecrets {
+    /// Domain name
+    pub domain: String,
+    /// Secret with client or server private key
+    pub key: SecretRef,
+    /// Secret with client or server certificate
+    pub cert: SecretRef,
+    /// Secret with Certificate Authority certificate
+    pub ca_cert: SecretRef,
+}
+
 cfg_if::cfg_if! {
     if #[cfg(target_arch = "wasm32")] {
 
@@ -257,6 +301,14 @@ cfg_if::cfg_if! {
                             tls.domain,
                         )))
                     }
+                    TlsPolicy::Verified(TlsConfig::Secrets(secrets)) => {
+                        info!(
+                            domain = &*secrets.domain,
+                            "Using verified TLS with certificates from secrets"
+                        );
+                        let certs = TlsCerts::try_from(secrets)?;
+                        TlsPolicy::from(certs).try_into()
+                    }
                 }
             }
         }
@@ -318,6 +370,14 @@ cfg_if::cfg_if! {
                             tls.domain
                         )))
                     }
+                    TlsPolicy::Verified(TlsConfig::Secrets(secrets)) => {
+                        info!(
+                            domain = &*secrets.domain,
+                            "Using verified TLS with certificates from secrets"
+                        );
+                        let certs = TlsCerts::try_from(secrets)?;
+                        TlsPolicy::from(certs).try_into()
+                    }
                 }
             }
         }
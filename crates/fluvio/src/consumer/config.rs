@@ -4,6 +4,7 @@ use anyhow::Result;
 use derive_builder::Builder;
 
 use fluvio_spu_schema::{server::smartmodule::SmartModuleInvocation, Isolation};
+use fluvio_spu_schema::server::consumer_group::GroupMembership;
 use fluvio_types::PartitionId;
 
 use crate::{FluvioError, Offset};
@@ -24,6 +25,22 @@ pub struct ConsumerConfig {
     pub isolation: Isolation,
     #[builder(default)]
     pub smartmodule: Vec<SmartModuleInvocation>,
+    /// Allows reading from in-sync follower replicas that are not staler than this bound
+    #[builder(default, setter(strip_option))]
+    pub max_staleness: Option<Duration>,
+    /// Rack of the consumer, follower replicas in the same rack are preferred
+    #[builder(default, setter(strip_option, into))]
+    pub rack: Option<String>,
+    /// Records failing in SmartModule are written to this topic instead of ending the stream
+    #[builder(default, setter(strip_option, into))]
+    pub dead_letter_topic: Option<String>,
+    /// Ask SPU for broker append, commit and fetch times of received batches
+    #[builder(default)]
+    pub broker_timestamps: bool,
+    /// Consumer group member consuming assigned partition, SPU rejects stream once
+    /// the generation is stale
+    #[builder(default, setter(strip_option))]
+    pub consumer_group: Option<GroupMembership>,
 }
 
 impl ConsumerConfig {
@@ -74,6 +91,22 @@ pub struct ConsumerConfigExt {
     pub isolation: Isolation,
     #[builder(default)]
     pub smartmodule: Vec<SmartModuleInvocation>,
+    /// Allows reading from in-sync follower replicas that are not staler than this bound
+    #[builder(default, setter(strip_option))]
+    pub max_staleness: Option<Duration>,
+    /// Rack of the consumer, follower replicas in the same rack are preferred
+    #[builder(default, setter(strip_option, into))]
+    pub rack: Option<String>,
+    /// Records failing in SmartModule are written to this topic instead of ending the stream
+    #[builder(default, setter(strip_option, into))]
+    pub dead_letter_topic: Option<String>,
+    /// Ask SPU for broker append, commit and fetch times of received batches
+    #[builder(default)]
+    pub broker_timestamps: bool,
+    /// Consumer group member consuming assigned partition, SPU rejects stream once
+    /// the generation is stale
+    #[builder(default, setter(strip_option))]
+    pub consumer_group: Option<GroupMembership>,
 }
 
 impl ConsumerConfigExt {
@@ -101,6 +134,11 @@ impl ConsumerConfigExt {
             smartmodule,
             offset_strategy,
             offset_flush,
+            max_staleness,
+            rack,
+            dead_letter_topic,
+            broker_timestamps,
+            consumer_group,
         } = self;
 
         let config = ConsumerConfig {
@@ -108,6 +146,11 @@ impl ConsumerConfigExt {
             max_bytes,
             isolation,
             smartmodule,
+            max_staleness,
+            rack,
+            dead_letter_topic,
+            broker_timestamps,
+            consumer_group,
         };
 
         (
@@ -140,6 +183,11 @@ impl From<ConsumerConfigExt> for ConsumerConfig {
             max_bytes,
             isolation,
             smartmodule,
+            max_staleness,
+            rack,
+            dead_letter_topic,
+            broker_timestamps,
+            consumer_group,
         } = value;
 
         Self {
@@ -147,6 +195,11 @@ impl From<ConsumerConfigExt> for ConsumerConfig {
             max_bytes,
             isolation,
             smartmodule,
+            max_staleness,
+            rack,
+            dead_letter_topic,
+            broker_timestamps,
+            consumer_group,
         }
     }
 }
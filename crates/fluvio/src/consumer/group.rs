@@ -0,0 +1,138 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use tracing::{debug, info};
+
+use fluvio_protocol::link::ErrorCode;
+use fluvio_socket::VersionedSerialSocket;
+use fluvio_spu_schema::server::consumer_group::{
+    GroupAssignment, GroupMembership, HeartbeatRequest, JoinGroupRequest, LeaveGroupRequest,
+};
+use fluvio_types::{PartitionCount, PartitionId};
+
+/// Session timeout used when member doesn't specify one
+pub const DEFAULT_SESSION_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Membership in a consumer group.
+///
+/// Partitions of the group topic are split across the members of the group.
+/// Member must call [`heartbeat`](Self::heartbeat) more often than the session timeout,
+/// otherwise it is removed from the group and its partitions are given to other members.
+///
+/// When partitions change, member must stop consuming partitions it lost before next heartbeat,
+/// which tells the group they are revoked, so they can be handed to other members.
+/// Consumers created with [`membership`](Self::membership) are rejected by SPU once the
+/// assignment they were created for is stale.
+pub struct ConsumerGroupMember {
+    socket: VersionedSerialSocket,
+    group_id: String,
+    member_id: String,
+    topic: String,
+    assignment: GroupAssignment,
+}
+
+impl ConsumerGroupMember {
+    pub(crate) async fn join(
+        socket: VersionedSerialSocket,
+        group_id: String,
+        topic: String,
+        partitions: PartitionCount,
+        session_timeout: Duration,
+    ) -> Result<Self> {
+        let request = JoinGroupRequest::new(
+            group_id.clone(),
+            topic.clone(),
+            partitions,
+            session_timeout.as_millis() as u32,
+        );
+        let response = socket.send_receive(request).await?;
+        if response.error_code != ErrorCode::None {
+            anyhow::bail!("join consumer group failed with: {}", response.error_code);
+        }
+        info!(
+            group_id,
+            member_id = response.member_id,
+            generation = response.assignment.generation,
+            "joined consumer group"
+        );
+        Ok(Self {
+            socket,
+            group_id,
+            member_id: response.member_id,
+            topic,
+            assignment: response.assignment,
+        })
+    }
+
+    pub fn group_id(&self) -> &str {
+        &self.group_id
+    }
+
+    pub fn member_id(&self) -> &str {
+        &self.member_id
+    }
+
+    pub fn topic(&self) -> &str {
+        &self.topic
+    }
+
+    /// Partitions currently assigned to this member
+    pub fn partitions(&self) -> &[PartitionId] {
+        &self.assignment.partitions
+    }
+
+    /// Membership in current generation, to be set in consumer config of assigned partitions
+    pub fn membership(&self) -> GroupMembership {
+        GroupMembership {
+            group_id: self.group_id.clone(),
+            member_id: self.member_id.clone(),
+            generation: self.assignment.generation,
+        }
+    }
+
+    /// Keeps membership alive. Returns true if the group has been rebalanced
+    /// since the previous call and [`partitions`](Self::partitions) have changed.
+    /// Calling it acknowledges that partitions no longer assigned are not consumed anymore.
+    pub async fn heartbeat(&mut self) -> Result<bool> {
+        let response = self
+            .socket
+            .send_receive(HeartbeatRequest {
+                group_id: self.group_id.clone(),
+                member_id: self.member_id.clone(),
+                generation: self.assignment.generation,
+            })
+            .await?;
+        if response.error_code != ErrorCode::None {
+            anyhow::bail!(
+                "consumer group heartbeat failed with: {}",
+                response.error_code
+            );
+        }
+        if response.assignment.generation == self.assignment.generation {
+            return Ok(false);
+        }
+        debug!(
+            generation = response.assignment.generation,
+            partitions = ?response.assignment.partitions,
+            "consumer group rebalanced"
+        );
+        let changed = response.assignment.partitions != self.assignment.partitions;
+        self.assignment = response.assignment;
+        Ok(changed)
+    }
+
+    /// Leaves the group so its partitions are assigned to other members right away
+    pub async fn leave(self) -> Result<()> {
+        let response = self
+            .socket
+            .send_receive(LeaveGroupRequest {
+                group_id: self.group_id,
+                member_id: self.member_id,
+            })
+            .await?;
+        if response.error_code != ErrorCode::None {
+            anyhow::bail!("leave consumer group failed with: {}", response.error_code);
+        }
+        Ok(())
+    }
+}
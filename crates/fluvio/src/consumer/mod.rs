@@ -3,6 +3,7 @@
 mod config;
 mod stream;
 mod offset;
+mod group;
 
 use std::sync::Arc;
 
@@ -12,6 +13,7 @@ use fluvio_spu_schema::server::consumer_offset::UpdateConsumerOffsetRequest;
 use tracing::{debug, error, trace, instrument, info, warn};
 use futures_util::stream::{Stream, select_all};
 use once_cell::sync::Lazy;
+use chrono::Utc;
 use futures_util::future::{Either, err, join_all};
 use futures_util::stream::{StreamExt, once, iter};
 use futures_util::FutureExt;
@@ -20,11 +22,12 @@ use fluvio_types::PartitionId;
 use fluvio_types::defaults::{FLUVIO_CLIENT_MAX_FETCH_BYTES, FLUVIO_MAX_SIZE_TOPIC_NAME};
 use fluvio_spu_schema::server::stream_fetch::{
     DefaultStreamFetchRequest, DefaultStreamFetchResponse, CHAIN_SMARTMODULE_API,
-    OFFSET_MANAGEMENT_API,
 };
 use fluvio_protocol::record::ReplicaKey;
+use fluvio_protocol::link::capabilities::Capability;
 use fluvio_protocol::link::ErrorCode;
 use fluvio_protocol::record::Batch;
+use fluvio_spu_schema::fetch::AbortedTxnFilter;
 
 use crate::FluvioError;
 use crate::metrics::ClientMetrics;
@@ -35,8 +38,10 @@ pub use config::{ConsumerConfig, ConsumerConfigBuilder};
 pub use config::{ConsumerConfigExt, ConsumerConfigExtBuilder, OffsetManagementStrategy};
 pub use stream::{ConsumerStream, MultiplePartitionConsumerStream, SinglePartitionConsumerStream};
 pub use offset::ConsumerOffset;
+pub use group::{ConsumerGroupMember, DEFAULT_SESSION_TIMEOUT};
 
 pub use fluvio_protocol::record::ConsumerRecord as Record;
+pub use fluvio_protocol::link::smartmodule::DeadLetter;
 pub use fluvio_spu_schema::server::smartmodule::SmartModuleInvocation;
 pub use fluvio_spu_schema::server::smartmodule::SmartModuleInvocationWasm;
 pub use fluvio_spu_schema::server::smartmodule::SmartModuleKind;
@@ -74,7 +79,7 @@ impl<P> Clone for PartitionConsumer<P> {
 
 impl<P> PartitionConsumer<P>
 where
-    P: SpuDirectory,
+    P: SpuDirectory + Sync,
 {
     pub fn new(
         topic: String,
@@ -212,11 +217,13 @@ where
         let flattened = stream.flat_map(move |result: Result<Batch, _>| match result {
             Err(e) => Either::Right(once(err(e))),
             Ok(batch) => {
+                // expired records are skipped at fetch time, storage reclaims them later
+                let now = Utc::now().timestamp_millis();
                 let records =
                     batch
                         .into_consumer_records_iter(partition)
                         .filter_map(move |record| {
-                            if record.offset >= start_offset {
+                            if record.offset >= start_offset && !record.is_expired(now) {
                                 Some(Ok(record))
                             } else {
                                 None
@@ -288,7 +295,7 @@ where
         let metrics = self.metrics.clone();
         let flattened =
             stream.flat_map(move |batch_result: Result<DefaultStreamFetchResponse, _>| {
-                let response = match batch_result {
+                let mut response = match batch_result {
                     Ok(response) => response,
                     Err(e) => return Either::Right(once(err(e))),
                 };
@@ -298,30 +305,39 @@ where
                 // This way the consumer always gets to read all records that were properly
                 // processed before hitting an error, so that the error does not obscure those records.
 
+                if let Some(timestamps) = response.partition.broker_timestamps {
+                    metrics.consumer_latency().add_latency(
+                        (Utc::now().timestamp_millis() - timestamps.append_time).max(0) as u64,
+                    );
+                }
                 let inner_metrics = metrics.clone();
-                let batches =
-                    response
-                        .partition
-                        .records
-                        .batches
-                        .into_iter()
-                        .map(move |raw_batch| {
-                            inner_metrics
-                                .consumer()
-                                .add_records(raw_batch.records_len() as u64);
-                            inner_metrics
-                                .consumer()
-                                .add_bytes(raw_batch.batch_len() as u64);
-
-                            let batch: Result<Batch, _> = raw_batch.try_into();
-                            match batch {
-                                Ok(batch) => Ok(batch),
-                                Err(err) => {
-                                    tracing::error!("{err:?}");
-                                    Err(ErrorCode::Other(err.to_string()))
-                                }
+                let mut txn_filter =
+                    AbortedTxnFilter::new(response.partition.aborted.take().unwrap_or_default());
+                let batches = response
+                    .partition
+                    .records
+                    .batches
+                    .into_iter()
+                    .filter(move |raw_batch| {
+                        txn_filter.keep(raw_batch.base_offset, raw_batch.get_header())
+                    })
+                    .map(move |raw_batch| {
+                        inner_metrics
+                            .consumer()
+                            .add_records(raw_batch.records_len() as u64);
+                        inner_metrics
+                            .consumer()
+                            .add_bytes(raw_batch.batch_len() as u64);
+
+                        let batch: Result<Batch, _> = raw_batch.try_into();
+                        match batch {
+                            Ok(batch) => Ok(batch),
+                            Err(err) => {
+                                tracing::error!("{err:?}");
+                                Err(ErrorCode::Other(err.to_string()))
                             }
-                        });
+                        }
+                    });
                 let error = {
                     let code = response.partition.error_code;
                     match code {
@@ -366,6 +382,8 @@ where
         debug!(start_absolute_offset, end_absolute_offset, record_count);
 
         let with_consumer_id = consumer_id.is_some();
+        let max_staleness_ms = config.max_staleness.map(|d| d.as_millis() as u32);
+        let leader_epoch = self.pool.leader_epoch(&replica).await;
         let stream_request = DefaultStreamFetchRequest::builder()
             .topic(self.topic.to_owned())
             .partition(self.partition)
@@ -374,24 +392,55 @@ where
             .max_bytes(config.max_bytes)
             .smartmodules(config.smartmodule)
             .consumer_id(consumer_id)
+            .max_staleness_ms(max_staleness_ms)
+            .dead_letter_topic(config.dead_letter_topic.clone())
+            .broker_timestamps(config.broker_timestamps)
+            .leader_epoch(leader_epoch)
+            .consumer_group(config.consumer_group.clone())
             .build()?;
 
-        let stream_fetch_version = serial_socket
-            .versions()
+        let versions = serial_socket.versions();
+        let stream_fetch_version = versions
             .lookup_version::<DefaultStreamFetchRequest>()
             .unwrap_or(CHAIN_SMARTMODULE_API - 1);
-        debug!(%stream_fetch_version, "stream_fetch_version");
-        if stream_fetch_version < CHAIN_SMARTMODULE_API {
+        debug!(%stream_fetch_version, capabilities = ?versions.capabilities(), "stream_fetch_version");
+        if !versions.supports(Capability::SmartModuleChain) {
             warn!("SPU does not support SmartModule chaining. SmartModules will not be applied to the stream");
         }
-        if with_consumer_id && stream_fetch_version < OFFSET_MANAGEMENT_API {
+        if with_consumer_id && !versions.supports(Capability::OffsetManagement) {
             warn!("SPU does not support Offset Management API");
         }
 
-        let mut stream = self
-            .pool
-            .create_stream_with_version(&replica, stream_request, stream_fetch_version)
-            .await?;
+        let read_from_follower = versions.supports(Capability::ReadFromFollower);
+        if max_staleness_ms.is_some() && !read_from_follower {
+            warn!("SPU does not support reading from followers");
+        }
+
+        if config.dead_letter_topic.is_some() && !versions.supports(Capability::DeadLetterTopic) {
+            warn!("SPU does not support dead-letter topic");
+        }
+
+        if config.broker_timestamps && !versions.supports(Capability::BrokerTimestamps) {
+            warn!("SPU does not support broker timestamps");
+        }
+
+        let mut stream = match config.rack {
+            Some(ref rack) if max_staleness_ms.is_some() && read_from_follower => {
+                self.pool
+                    .create_stream_with_version_from_rack(
+                        &replica,
+                        stream_request,
+                        stream_fetch_version,
+                        rack,
+                    )
+                    .await?
+            }
+            _ => {
+                self.pool
+                    .create_stream_with_version(&replica, stream_request, stream_fetch_version)
+                    .await?
+            }
+        };
 
         let (server_sender, server_recv) =
             async_channel::bounded::<StreamToServer>(STREAM_TO_SERVER_CHANNEL_SIZE);
@@ -527,11 +576,13 @@ where
         let flattened = stream.flat_map(move |result: Result<Batch, _>| match result {
             Err(e) => Either::Right(once(err(e))),
             Ok(batch) => {
+                // expired records are skipped at fetch time, storage reclaims them later
+                let now = Utc::now().timestamp_millis();
                 let records =
                     batch
                         .into_consumer_records_iter(partition)
                         .filter_map(move |record| {
-                            if record.offset >= start_offset {
+                            if record.offset >= start_offset && !record.is_expired(now) {
                                 Some(Ok(record))
                             } else {
                                 None
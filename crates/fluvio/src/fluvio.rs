@@ -1,14 +1,24 @@
 use std::convert::TryFrom;
 use std::sync::Arc;
+use std::time::Duration;
 
 use tracing::{debug, info};
 use tokio::sync::OnceCell;
 use anyhow::{anyhow, Result};
 
+use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::record::ReplicaKey;
 use fluvio_sc_schema::objects::ObjectApiWatchRequest;
+use fluvio_spu_schema::server::delete_records::DeleteRecordsRequest;
+use fluvio_spu_schema::server::snapshot::{
+    SnapshotPartitionRequest, SnapshotPartitionResponse, RestorePartitionRequest,
+    RestorePartitionResponse,
+};
 use fluvio_types::PartitionId;
+use fluvio_types::defaults::CONSUMER_STORAGE_TOPIC;
 use fluvio_socket::{
     ClientConfig, Versions, VersionedSerialSocket, SharedMultiplexerSocket, MultiplexerSocket,
+    SharedSocketObserver,
 };
 use fluvio_future::net::DomainConnector;
 use semver::Version;
@@ -22,10 +32,11 @@ use crate::FluvioConfig;
 use crate::consumer::{MultiplePartitionConsumer, PartitionSelectionStrategy};
 use crate::consumer::{
     ConsumerStream, MultiplePartitionConsumerStream, Record, ConsumerConfigExt, ConsumerOffset,
+    ConsumerGroupMember,
 };
 use crate::metrics::ClientMetrics;
-use crate::producer::TopicProducerConfig;
-use crate::spu::SpuPool;
+use crate::producer::{TopicProducerConfig, TransactionProducer};
+use crate::spu::{SpuDirectory, SpuPool};
 use crate::sync::MetadataStores;
 
 /// An interface for interacting with Fluvio streaming
@@ -87,11 +98,35 @@ impl Fluvio {
         connector: DomainConnector,
         config: &FluvioConfig,
     ) -> Result<Self> {
+        Self::connect_with_observers(connector, config, vec![]).await
+    }
+
+    /// Creates a new Fluvio client which reports transport events
+    /// (bytes, request latency, reconnects) of all its connections to `observer`
+    pub async fn connect_with_observer(
+        config: &FluvioConfig,
+        observer: SharedSocketObserver,
+    ) -> Result<Self> {
+        let connector = DomainConnector::try_from(config.tls.clone())?;
+        Self::connect_with_observers(connector, config, vec![observer]).await
+    }
+
+    async fn connect_with_observers(
+        connector: DomainConnector,
+        config: &FluvioConfig,
+        observers: Vec<SharedSocketObserver>,
+    ) -> Result<Self> {
+        let metric = Arc::new(ClientMetrics::new());
         let mut client_config =
             ClientConfig::new(&config.endpoint, connector, config.use_spu_local_address);
         if let Some(client_id) = &config.client_id {
             client_config.set_client_id(client_id.to_owned());
         }
+        client_config.set_auth_token(config.resolve_auth_token()?);
+        client_config.add_observer(metric.clone());
+        for observer in observers {
+            client_config.add_observer(observer);
+        }
         let inner_client = client_config.connect().await?;
         debug!("connected to cluster");
 
@@ -102,7 +137,8 @@ impl Fluvio {
             debug!(platform = %versions.platform_version(),"checking platform version");
             check_platform_compatible(versions.platform_version())?;
 
-            let socket = MultiplexerSocket::shared(socket);
+            let socket =
+                MultiplexerSocket::shared_with_observers(socket, config.observers().clone());
             let metadata = MetadataStores::start(socket.clone(), watch_version).await?;
 
             let spu_pool = OnceCell::new();
@@ -113,7 +149,7 @@ impl Fluvio {
                 spu_pool,
                 metadata,
                 watch_version,
-                metric: Arc::new(ClientMetrics::new()),
+                metric,
             })
         } else {
             Err(anyhow!("WatchApi version not found"))
@@ -397,6 +433,127 @@ impl Fluvio {
         Ok(())
     }
 
+    /// Writes committed records of the partition before `end_offset` to snapshot archive
+    /// `name` in snapshot directory of the partition leader.
+    /// High watermark is used if end offset is not given.
+    pub async fn snapshot_partition(
+        &self,
+        replica_id: impl Into<ReplicaKey>,
+        name: impl Into<String>,
+        end_offset: Option<i64>,
+    ) -> Result<SnapshotPartitionResponse> {
+        let replica_id = replica_id.into();
+        let spu_pool = self.spu_pool().await?;
+        let socket = spu_pool.create_serial_socket(&replica_id).await?;
+        let response = socket
+            .send_receive(SnapshotPartitionRequest {
+                replica: replica_id,
+                name: name.into(),
+                end_offset,
+            })
+            .await?;
+        if response.error_code != ErrorCode::None {
+            anyhow::bail!("snapshot partition failed with: {}", response.error_code);
+        }
+        Ok(response)
+    }
+
+    /// Restores snapshot archive `name` into the partition, which must be empty.
+    /// Archive must be in snapshot directory of the partition leader.
+    /// Archive of encrypted partition is restored as it is, only to the same partition
+    /// encrypted with the same key and starting at the same offset.
+    pub async fn restore_partition(
+        &self,
+        replica_id: impl Into<ReplicaKey>,
+        name: impl Into<String>,
+    ) -> Result<RestorePartitionResponse> {
+        let replica_id = replica_id.into();
+        let spu_pool = self.spu_pool().await?;
+        let socket = spu_pool.create_serial_socket(&replica_id).await?;
+        let response = socket
+            .send_receive(RestorePartitionRequest {
+                replica: replica_id,
+                name: name.into(),
+            })
+            .await?;
+        if response.error_code != ErrorCode::None {
+            anyhow::bail!("restore partition failed with: {}", response.error_code);
+        }
+        Ok(response)
+    }
+
+    /// Deletes records of the partition before `offset`, ahead of retention.
+    /// Only committed records are deleted, returns new log start offset of the partition.
+    pub async fn delete_records(
+        &self,
+        replica_id: impl Into<ReplicaKey>,
+        offset: i64,
+    ) -> Result<i64> {
+        let replica_id = replica_id.into();
+        let spu_pool = self.spu_pool().await?;
+        let socket = spu_pool.create_serial_socket(&replica_id).await?;
+        let response = socket
+            .send_receive(DeleteRecordsRequest {
+                replica: replica_id,
+                offset,
+            })
+            .await?;
+        if response.error_code != ErrorCode::None {
+            anyhow::bail!("delete records failed with: {}", response.error_code);
+        }
+        Ok(response.log_start_offset)
+    }
+
+    /// Joins a consumer group consuming the given topic.
+    ///
+    /// Members of the same group get disjoint sets of the topic partitions,
+    /// which are rebalanced when members join, leave or stop sending heartbeats.
+    pub async fn join_consumer_group(
+        &self,
+        group_id: impl Into<String>,
+        topic: impl Into<String>,
+        session_timeout: Duration,
+    ) -> Result<ConsumerGroupMember> {
+        let topic = topic.into();
+        let spu_pool = self.spu_pool().await?;
+        let topic_spec = spu_pool
+            .metadata
+            .topics()
+            .lookup_by_key(&topic)
+            .await?
+            .ok_or_else(|| FluvioError::TopicNotFound(topic.clone()))?
+            .spec;
+        let consumers_replica_id =
+            ReplicaKey::new(CONSUMER_STORAGE_TOPIC, <PartitionId as Default>::default());
+        let socket = spu_pool.create_serial_socket(&consumers_replica_id).await?;
+        ConsumerGroupMember::join(
+            socket,
+            group_id.into(),
+            topic,
+            topic_spec.partitions(),
+            session_timeout,
+        )
+        .await
+    }
+
+    /// Creates a producer writing to several partitions in transactions.
+    ///
+    /// Open transaction of previous producer with the same transactional id is aborted
+    /// and that producer can't commit anymore.
+    pub async fn transaction_producer(
+        &self,
+        transactional_id: impl Into<String>,
+        timeout: Duration,
+    ) -> Result<TransactionProducer> {
+        let spu_pool = self.spu_pool().await?;
+        let coordinator_replica_id =
+            ReplicaKey::new(CONSUMER_STORAGE_TOPIC, <PartitionId as Default>::default());
+        let socket = spu_pool
+            .create_serial_socket(&coordinator_replica_id)
+            .await?;
+        TransactionProducer::init(spu_pool, socket, transactional_id.into(), timeout).await
+    }
+
     /// Provides an interface for managing a Fluvio cluster
     ///
     /// # Example
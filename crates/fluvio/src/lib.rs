@@ -22,7 +22,7 @@ pub use config::FluvioConfig;
 pub use producer::{
     TopicProducerConfigBuilder, TopicProducerConfig, TopicProducer, RecordKey, ProduceOutput,
     FutureRecordMetadata, RecordMetadata, DeliverySemantic, RetryPolicy, RetryStrategy,
-    Partitioner, PartitionerConfig, ProducerError,
+    Partitioner, PartitionerConfig, ProducerError, TransactionProducer, DEFAULT_TRANSACTION_TIMEOUT,
 };
 #[cfg(feature = "smartengine")]
 pub use producer::{SmartModuleChainBuilder, SmartModuleConfig, SmartModuleInitialData};
@@ -200,6 +200,18 @@ pub mod metadata {
     pub mod store {
         pub use fluvio_sc_schema::store::*;
     }
+
+    pub mod audit {
+        pub use fluvio_sc_schema::audit::*;
+    }
+
+    pub mod acl {
+        pub use fluvio_sc_schema::acl::*;
+    }
+
+    pub mod diagnostics {
+        pub use fluvio_sc_schema::diagnostics::*;
+    }
 }
 
 pub mod dataplane {
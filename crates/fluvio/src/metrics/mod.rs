@@ -1,10 +1,18 @@
 use serde::{Serialize, Deserialize};
 
+use fluvio_socket::SocketObserver;
+
 #[derive(Default, Debug, Deserialize, Serialize)]
 pub struct ClientMetrics {
     consumer: RecordCounter,
+    /// from broker append to consumer receiving batch, for streams asking for broker timestamps
+    #[serde(default)]
+    consumer_latency: LatencyCounter,
     producer_connector: RecordCounter,
     producer_client: RecordCounter,
+    /// bytes, requests and reconnects of connections to SC and SPUs
+    #[serde(default)]
+    transport: TransportCounter,
     #[cfg(feature = "smartengine")]
     smartmodule: fluvio_smartengine::metrics::SmartModuleChainMetrics,
 }
@@ -19,6 +27,11 @@ impl ClientMetrics {
         &self.consumer
     }
 
+    #[inline]
+    pub fn consumer_latency(&self) -> &LatencyCounter {
+        &self.consumer_latency
+    }
+
     /// producer counter from connector
     #[inline]
     pub fn producer_connector(&self) -> &RecordCounter {
@@ -31,6 +44,11 @@ impl ClientMetrics {
         &self.producer_client
     }
 
+    #[inline]
+    pub fn transport(&self) -> &TransportCounter {
+        &self.transport
+    }
+
     #[cfg(feature = "smartengine")]
     pub(crate) fn chain_metrics(&self) -> &fluvio_smartengine::metrics::SmartModuleChainMetrics {
         &self.smartmodule
@@ -55,8 +73,27 @@ cfg_if::cfg_if! {
             }
         }
 
+        #[derive(Default, Debug, Deserialize, Serialize)]
+        pub struct LatencyCounter {
+
+        }
+
+        impl LatencyCounter {
+            #[inline]
+            pub(crate) fn add_latency(&self, _latency_ms: u64) {
+            }
+        }
+
+        #[derive(Default, Debug, Deserialize, Serialize)]
+        pub struct TransportCounter {
+
+        }
+
+        impl SocketObserver for ClientMetrics {}
+
     } else {
         use std::sync::atomic::{AtomicU64, Ordering};
+        use std::time::Duration;
 
         #[derive(Default, Debug, Serialize, Deserialize)]
         pub struct RecordCounter {
@@ -76,5 +113,51 @@ cfg_if::cfg_if! {
             }
         }
 
+        #[derive(Default, Debug, Serialize, Deserialize)]
+        pub struct LatencyCounter {
+            pub count: AtomicU64,
+            pub total_ms: AtomicU64,
+            pub max_ms: AtomicU64,
+        }
+
+        impl LatencyCounter {
+            #[inline]
+            pub(crate) fn add_latency(&self, latency_ms: u64) {
+                self.count.fetch_add(1, Ordering::SeqCst);
+                self.total_ms.fetch_add(latency_ms, Ordering::SeqCst);
+                self.max_ms.fetch_max(latency_ms, Ordering::SeqCst);
+            }
+        }
+
+        #[derive(Default, Debug, Serialize, Deserialize)]
+        pub struct TransportCounter {
+            pub bytes_sent: AtomicU64,
+            pub bytes_received: AtomicU64,
+            pub request_errors: AtomicU64,
+            pub request_latency: LatencyCounter,
+            pub reconnects: AtomicU64,
+        }
+
+        impl SocketObserver for ClientMetrics {
+            fn bytes_sent(&self, bytes: usize) {
+                self.transport.bytes_sent.fetch_add(bytes as u64, Ordering::SeqCst);
+            }
+
+            fn bytes_received(&self, bytes: usize) {
+                self.transport.bytes_received.fetch_add(bytes as u64, Ordering::SeqCst);
+            }
+
+            fn request_completed(&self, _api_key: u16, latency: Duration, success: bool) {
+                if !success {
+                    self.transport.request_errors.fetch_add(1, Ordering::SeqCst);
+                }
+                self.transport.request_latency.add_latency(latency.as_millis() as u64);
+            }
+
+            fn reconnected(&self, _addr: &str) {
+                self.transport.reconnects.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
     }
 }
@@ -41,7 +41,12 @@ impl MemoryBatch {
             .get_mut_header()
             .set_offset_delta(current_offset as Offset);
 
-        let timestamp_delta = self.elapsed();
+        let mut timestamp_delta = self.elapsed();
+        if record.get_header().is_scheduled() {
+            // scheduled record carries absolute deliver time until now
+            let deliver_at = record.get_header().get_timestamp_delta();
+            timestamp_delta = timestamp_delta.max(deliver_at - self.create_time);
+        }
         record.get_mut_header().set_timestamp_delta(timestamp_delta);
 
         let record_size = record.write_size(0);
@@ -114,14 +119,24 @@ impl From<MemoryBatch> for Batch<MemoryRecords> {
         let first_timestamp = p_batch.create_time;
 
         let max_time_stamp = records
-            .last()
+            .iter()
             .map(|r| first_timestamp + r.timestamp_delta())
+            .max()
             .unwrap_or(0);
 
         header.set_first_timestamp(first_timestamp);
         header.set_max_time_stamp(max_time_stamp);
 
         header.set_compression(compression);
+        if records.iter().any(|record| record.ttl().is_some()) {
+            header.set_record_ttl();
+        }
+        if records
+            .iter()
+            .any(|record| record.get_header().is_scheduled())
+        {
+            header.set_scheduled_records();
+        }
 
         *batch.mut_records() = records;
 
@@ -233,4 +248,28 @@ mod test {
             (BATCH_HEADER_SIZE + memory_batch_size_uncompressed) as i32
         );
     }
+
+    #[test]
+    fn test_scheduled_memory_batch() {
+        let mut mb = MemoryBatch::new(1024, Compression::None);
+        let deliver_at = mb.create_time + 60_000;
+
+        assert!(mb.push_record(Record::new("now")).is_some());
+        assert!(mb
+            .push_record(Record::new("later").with_deliver_at(deliver_at))
+            .is_some());
+        // deliver time in the past is same as now
+        assert!(mb
+            .push_record(Record::new("past").with_deliver_at(0))
+            .is_some());
+
+        let batch: Batch<MemoryRecords> = mb.into();
+        assert!(batch.header.has_scheduled_records());
+        assert_eq!(batch.header.max_time_stamp, deliver_at);
+        assert_eq!(
+            batch.records()[1].deliver_at(batch.header.first_timestamp),
+            Some(deliver_at)
+        );
+        assert!(batch.records()[2].timestamp_delta() < 60_000);
+    }
 }
@@ -1,4 +1,5 @@
 use std::sync::Arc;
+use std::time::Duration;
 
 use tracing::instrument;
 use async_lock::RwLock;
@@ -8,7 +9,7 @@ use fluvio_protocol::record::ReplicaKey;
 use fluvio_protocol::record::Record;
 use fluvio_compression::Compression;
 use fluvio_sc_schema::topic::CompressionAlgorithm;
-use fluvio_types::PartitionId;
+use fluvio_types::{PartitionCount, PartitionId, Timestamp};
 use fluvio_types::event::StickyEvent;
 
 mod accumulator;
@@ -19,6 +20,7 @@ mod record;
 mod partitioning;
 mod partition_producer;
 mod memory_batch;
+mod transaction;
 
 pub mod event;
 
@@ -42,6 +44,7 @@ use self::event::EventHandler;
 pub use self::output::ProduceOutput;
 use self::partition_producer::PartitionProducer;
 pub use self::record::{FutureRecordMetadata, RecordMetadata};
+pub use self::transaction::{TransactionProducer, DEFAULT_TRANSACTION_TIMEOUT};
 
 /// Pool of producers for a given topic. There is a producer per partition
 struct ProducerPool {
@@ -169,6 +172,7 @@ struct InnerTopicProducer {
     spu_pool: Arc<SpuPool>,
     record_accumulator: RecordAccumulator,
     producer_pool: Arc<ProducerPool>,
+    partition_count: PartitionCount,
 }
 
 impl InnerTopicProducer {
@@ -181,13 +185,15 @@ impl InnerTopicProducer {
     async fn push_record(self: Arc<Self>, record: Record) -> Result<PushRecord> {
         let topics = self.spu_pool.metadata.topics();
 
-        let topic_spec = topics
+        topics
             .lookup_by_key(&self.topic)
             .await?
-            .ok_or_else(|| FluvioError::TopicNotFound(self.topic.to_string()))?
-            .spec;
-        let partition_count = topic_spec.partitions();
-        let partition_config = PartitionerConfig { partition_count };
+            .ok_or_else(|| FluvioError::TopicNotFound(self.topic.to_string()))?;
+        // partitions added to the topic after this producer was created are not used by it,
+        // so keys are routed to the same partitions for the whole life of the producer
+        let partition_config = PartitionerConfig {
+            partition_count: self.partition_count,
+        };
 
         let key = record.key.as_ref().map(|k| k.as_ref());
         let value = record.value.as_ref();
@@ -382,6 +388,7 @@ impl TopicProducer {
                 spu_pool,
                 producer_pool,
                 record_accumulator,
+                partition_count,
             }),
             #[cfg(feature = "smartengine")]
             sm_chain: Default::default(),
@@ -435,7 +442,48 @@ impl TopicProducer {
         let record_key = key.into();
         let record_value = value.into();
         let record = Record::from((record_key, record_value));
+        self.send_record(record).await
+    }
+
+    /// Sends a key/value record which expires after `ttl`.
+    ///
+    /// TTL is counted from the record timestamp. Expired records are skipped by consumers
+    /// and removed from storage once all records of a segment have expired.
+    #[instrument(
+        skip(self, key, value),
+        fields(topic = %self.inner.topic),
+    )]
+    pub async fn send_with_ttl(
+        &self,
+        key: impl Into<RecordKey>,
+        value: impl Into<RecordData>,
+        ttl: Duration,
+    ) -> Result<ProduceOutput> {
+        let record = Record::from((key, value)).with_ttl(ttl);
+        self.send_record(record).await
+    }
+
+    /// Sends a key/value record which is hidden from consumers until `deliver_at`
+    /// (milliseconds since epoch).
+    ///
+    /// Record is stored right away, but SPU holds back it and all records after it
+    /// in the partition until deliver time has passed. Records are released in order,
+    /// so schedule only on partitions dedicated to delayed delivery, e.g. retry topics.
+    #[instrument(
+        skip(self, key, value),
+        fields(topic = %self.inner.topic),
+    )]
+    pub async fn send_scheduled(
+        &self,
+        key: impl Into<RecordKey>,
+        value: impl Into<RecordData>,
+        deliver_at: Timestamp,
+    ) -> Result<ProduceOutput> {
+        let record = Record::from((key, value)).with_deliver_at(deliver_at);
+        self.send_record(record).await
+    }
 
+    async fn send_record(&self, record: Record) -> Result<ProduceOutput> {
         cfg_if::cfg_if! {
             if #[cfg(feature = "smartengine")] {
                 let mut entries = vec![record];
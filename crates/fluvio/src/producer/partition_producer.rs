@@ -159,7 +159,8 @@ impl PartitionProducer {
         *error_handle = Some(ProducerError::Internal(error.to_string()));
     }
 
-    async fn current_leader(&self) -> Result<SpuId> {
+    /// leader of partition and its epoch
+    async fn current_leader(&self) -> Result<(SpuId, i32)> {
         let partition_spec = self
             .spu_pool
             .metadata
@@ -173,13 +174,13 @@ impl PartitionProducer {
                 )
             })?
             .spec;
-        Ok(partition_spec.leader)
+        Ok((partition_spec.leader, partition_spec.leader_epoch))
     }
 
     /// Flush all the batches that are full or have reached the linger time.
     /// If force is set to true, flush all batches regardless of linger time.
     pub(crate) async fn flush(&self, force: bool) -> Result<()> {
-        let leader = self.current_leader().await?;
+        let (leader, leader_epoch) = self.current_leader().await?;
 
         let spu_socket = self
             .spu_pool
@@ -218,6 +219,7 @@ impl PartitionProducer {
         for p_batch in batches_ready {
             let mut partition_request = DefaultPartitionRequest {
                 partition_index: self.replica.partition,
+                leader_epoch,
                 ..Default::default()
             };
             let notify = p_batch.notify.clone();
@@ -12,6 +12,11 @@ use fluvio_types::{PartitionId, PartitionCount};
 /// partitions. This includes deciding what partition to assign to records
 /// with no keys (represented by `None` values in the keys slice).
 ///
+/// Partition count is read once when the producer is created. If partitions are
+/// added to the topic later, existing producers keep mapping keys over the old
+/// count and never write to new partitions; producers created after the increase
+/// map keys over the new count, so a key may land on a different partition.
+///
 /// See [`SiphashRoundRobinPartitioner`] for a reference implementation.
 pub trait Partitioner {
     fn partition(
@@ -0,0 +1,204 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use tracing::{debug, info, instrument};
+
+use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::record::{Batch, RawRecords, Record, RecordData, RecordKey, ReplicaKey};
+use fluvio_socket::VersionedSerialSocket;
+use fluvio_spu_schema::produce::{DefaultPartitionRequest, DefaultProduceRequest, DefaultTopicRequest};
+use fluvio_spu_schema::server::transaction::{
+    AddTxnPartitionsRequest, EndTransactionRequest, InitTransactionRequest,
+};
+use fluvio_spu_schema::Isolation;
+use fluvio_types::PartitionId;
+
+use crate::spu::{SpuDirectory, SpuPool};
+
+/// Transaction timeout used when producer doesn't specify one
+pub const DEFAULT_TRANSACTION_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Producer writing records to several partitions atomically.
+///
+/// Records sent within a transaction are buffered until [`commit`](Self::commit), which
+/// writes them and makes them visible to read committed consumers all at once.
+/// A transaction which is not committed within its timeout is aborted by the coordinator.
+///
+/// Only one producer may use a transactional id at a time: creating a new producer
+/// with the same id aborts open transaction of the previous one and fences it off.
+pub struct TransactionProducer {
+    spu_pool: Arc<SpuPool>,
+    coordinator: VersionedSerialSocket,
+    transactional_id: String,
+    producer_id: i64,
+    producer_epoch: i16,
+    /// next sequence of each partition written by this producer
+    sequences: HashMap<ReplicaKey, i32>,
+    /// records of open transaction
+    pending: BTreeMap<ReplicaKey, Vec<Record>>,
+}
+
+impl TransactionProducer {
+    pub(crate) async fn init(
+        spu_pool: Arc<SpuPool>,
+        coordinator: VersionedSerialSocket,
+        transactional_id: String,
+        timeout: Duration,
+    ) -> Result<Self> {
+        let response = coordinator
+            .send_receive(InitTransactionRequest {
+                transactional_id: transactional_id.clone(),
+                timeout_ms: timeout.as_millis() as u32,
+            })
+            .await?;
+        if response.error_code != ErrorCode::None {
+            anyhow::bail!("init transaction failed with: {}", response.error_code);
+        }
+        info!(
+            transactional_id,
+            producer_id = response.producer_id,
+            epoch = response.producer_epoch,
+            "transactional producer initialized"
+        );
+        Ok(Self {
+            spu_pool,
+            coordinator,
+            transactional_id,
+            producer_id: response.producer_id,
+            producer_epoch: response.producer_epoch,
+            sequences: HashMap::new(),
+            pending: BTreeMap::new(),
+        })
+    }
+
+    pub fn transactional_id(&self) -> &str {
+        &self.transactional_id
+    }
+
+    /// Adds record to open transaction
+    pub fn send(
+        &mut self,
+        topic: impl Into<String>,
+        partition: PartitionId,
+        key: impl Into<RecordKey>,
+        value: impl Into<RecordData>,
+    ) {
+        self.pending
+            .entry(ReplicaKey::new(topic, partition))
+            .or_default()
+            .push(Record::new_key_value(key, value));
+    }
+
+    /// Writes records of open transaction and commits it.
+    /// If any partition can't be written, transaction is aborted and error is returned
+    #[instrument(skip(self), fields(transactional_id = self.transactional_id))]
+    pub async fn commit(&mut self) -> Result<()> {
+        let pending = std::mem::take(&mut self.pending);
+        if pending.is_empty() {
+            return Ok(());
+        }
+
+        self.add_partitions(pending.keys().cloned().collect())
+            .await?;
+        for (replica, records) in pending {
+            if let Err(err) = self.write(replica, records).await {
+                self.end(false).await?;
+                return Err(err);
+            }
+        }
+        self.end(true).await
+    }
+
+    /// Discards records of open transaction.
+    /// Nothing is written before commit, so coordinator doesn't need to know
+    pub fn abort(&mut self) {
+        self.pending.clear();
+    }
+
+    async fn add_partitions(&self, partitions: Vec<ReplicaKey>) -> Result<()> {
+        let response = self
+            .coordinator
+            .send_receive(AddTxnPartitionsRequest {
+                transactional_id: self.transactional_id.clone(),
+                producer_id: self.producer_id,
+                producer_epoch: self.producer_epoch,
+                partitions,
+            })
+            .await?;
+        if response.error_code != ErrorCode::None {
+            anyhow::bail!(
+                "adding partitions to transaction failed with: {}",
+                response.error_code
+            );
+        }
+        Ok(())
+    }
+
+    async fn write(&mut self, replica: ReplicaKey, mut records: Vec<Record>) -> Result<()> {
+        let sequence = self.sequences.entry(replica.clone()).or_default();
+        let records_len = records.len() as i32;
+
+        let mut batch: Batch = Batch::default();
+        batch.add_records(&mut records);
+        let header = batch.get_mut_header();
+        header.producer_id = self.producer_id;
+        header.producer_epoch = self.producer_epoch;
+        header.first_sequence = *sequence;
+        header.set_transactional();
+        let raw_batch: Batch<RawRecords> = batch.try_into()?;
+
+        let mut partition_request = DefaultPartitionRequest {
+            partition_index: replica.partition,
+            leader_epoch: self.spu_pool.leader_epoch(&replica).await.unwrap_or(-1),
+            ..Default::default()
+        };
+        partition_request.records.batches.push(raw_batch);
+        let mut topic_request = DefaultTopicRequest {
+            name: replica.topic.clone(),
+            ..Default::default()
+        };
+        topic_request.partitions.push(partition_request);
+        let mut request = DefaultProduceRequest {
+            isolation: Isolation::ReadCommitted,
+            ..Default::default()
+        };
+        request.topics.push(topic_request);
+
+        let socket = self.spu_pool.create_serial_socket(&replica).await?;
+        let response = socket.send_receive(request).await?;
+        for partition in response
+            .responses
+            .iter()
+            .flat_map(|topic| topic.partitions.iter())
+        {
+            if partition.error_code != ErrorCode::None {
+                anyhow::bail!(
+                    "transactional write to {replica} failed with: {}",
+                    partition.error_code
+                );
+            }
+        }
+
+        *sequence += records_len;
+        debug!(%replica, records_len, "transactional records written");
+        Ok(())
+    }
+
+    async fn end(&self, commit: bool) -> Result<()> {
+        let response = self
+            .coordinator
+            .send_receive(EndTransactionRequest {
+                transactional_id: self.transactional_id.clone(),
+                producer_id: self.producer_id,
+                producer_epoch: self.producer_epoch,
+                commit,
+            })
+            .await?;
+        if response.error_code != ErrorCode::None {
+            anyhow::bail!("ending transaction failed with: {}", response.error_code);
+        }
+        Ok(())
+    }
+}
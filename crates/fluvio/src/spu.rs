@@ -8,9 +8,10 @@ use async_trait::async_trait;
 
 use fluvio_protocol::record::ReplicaKey;
 use fluvio_protocol::api::Request;
+use fluvio_spu_schema::server::stream_fetch::{DefaultStreamFetchRequest, STREAM_FETCH_CAPABILITIES};
 use fluvio_types::SpuId;
 use fluvio_socket::{
-    AsyncResponse, ClientConfig, MultiplexerSocket, SocketError, StreamSocket,
+    AsyncResponse, ClientConfig, MultiplexerSocket, SocketError, SocketObserver, StreamSocket,
     VersionedSerialSocket,
 };
 use crate::FluvioError;
@@ -39,6 +40,26 @@ pub trait SpuDirectory {
     ) -> Result<AsyncResponse<R>, FluvioError>
     where
         R: Sync + Send;
+
+    /// create stream to replica hosted in the given rack, leader is used if there is none
+    async fn create_stream_with_version_from_rack<R: Request>(
+        &self,
+        replica: &ReplicaKey,
+        request: R,
+        version: i16,
+        _rack: &str,
+    ) -> Result<AsyncResponse<R>, FluvioError>
+    where
+        R: Sync + Send,
+    {
+        self.create_stream_with_version(replica, request, version)
+            .await
+    }
+
+    /// leader epoch of replica known to client, requests carrying it are fenced by stale leaders
+    async fn leader_epoch(&self, _replica: &ReplicaKey) -> Option<i32> {
+        None
+    }
 }
 
 /// connection pool to spu
@@ -69,9 +90,13 @@ impl SpuPool {
         })
     }
 
-    /// create new spu socket
+    /// create new spu socket, `reconnect` is set when it replaces stale socket
     #[instrument(skip(self))]
-    async fn connect_to_leader(&self, leader: SpuId) -> Result<StreamSocket, FluvioError> {
+    async fn connect_to_leader(
+        &self,
+        leader: SpuId,
+        reconnect: bool,
+    ) -> Result<StreamSocket, FluvioError> {
         let spu = self.metadata.spus().look_up_by_id(leader).await?;
 
         let mut client_config = self.config.with_prefix_sni_domain(spu.key());
@@ -88,12 +113,13 @@ impl SpuPool {
         debug!(leader = spu.spec.id,addr = %spu_addr,"try connecting to spu");
         client_config.set_addr(spu_addr);
         let versioned_socket = client_config.connect().await?;
-        let (socket, config, versions) = versioned_socket.split();
-        Ok(StreamSocket::new(
-            config,
-            MultiplexerSocket::shared(socket),
-            versions,
-        ))
+        let (socket, config, mut versions) = versioned_socket.split();
+        versions.infer_capabilities::<DefaultStreamFetchRequest>(STREAM_FETCH_CAPABILITIES);
+        if reconnect {
+            config.observers().reconnected(config.addr());
+        }
+        let socket = MultiplexerSocket::shared_with_observers(socket, config.observers().clone());
+        Ok(StreamSocket::new(config, socket, versions))
     }
 
     #[instrument(skip(self))]
@@ -103,16 +129,18 @@ impl SpuPool {
     ) -> Result<VersionedSerialSocket, FluvioError> {
         // check if already have existing connection to same SPU
         let mut client_lock = self.spu_clients.lock().await;
+        let mut reconnect = false;
 
         if let Some(spu_socket) = client_lock.get_mut(&leader_id) {
             if !spu_socket.is_stale() {
                 return Ok(spu_socket.create_serial_socket().await);
             } else {
                 client_lock.remove(&leader_id);
+                reconnect = true;
             }
         }
 
-        let mut spu_socket = self.connect_to_leader(leader_id).await?;
+        let mut spu_socket = self.connect_to_leader(leader_id, reconnect).await?;
         let serial_socket = spu_socket.create_serial_socket().await;
         client_lock.insert(leader_id, spu_socket);
 
@@ -184,22 +212,80 @@ impl SpuDirectory for SpuPool {
         };
 
         let leader_id = partition.spec.leader;
+        self.create_stream_to_spu(leader_id, request, version).await
+    }
+
+    #[instrument(skip(self, replica, request, version))]
+    async fn create_stream_with_version_from_rack<R: Request>(
+        &self,
+        replica: &ReplicaKey,
+        request: R,
+        version: i16,
+        rack: &str,
+    ) -> Result<AsyncResponse<R>, FluvioError>
+    where
+        R: Sync + Send,
+    {
+        let partition_search = self.metadata.partitions().lookup_by_key(replica).await?;
 
-        // check if already have existing leader or create new connection to leader
+        let partition = if let Some(partition) = partition_search {
+            partition
+        } else {
+            return Err(FluvioError::PartitionNotFound(
+                replica.topic.to_owned(),
+                replica.partition,
+            ));
+        };
+
+        let mut spu_id = partition.spec.leader;
+        for replica_id in &partition.spec.replicas {
+            let spu = self.metadata.spus().look_up_by_id(*replica_id).await?;
+            if spu.spec.rack.as_deref() == Some(rack) {
+                spu_id = *replica_id;
+                break;
+            }
+        }
+        debug!(spu_id, rack, "selected replica");
+
+        self.create_stream_to_spu(spu_id, request, version).await
+    }
+
+    async fn leader_epoch(&self, replica: &ReplicaKey) -> Option<i32> {
+        self.metadata
+            .partitions()
+            .lookup_by_key(replica)
+            .await
+            .ok()
+            .flatten()
+            .map(|partition| partition.spec.leader_epoch)
+    }
+}
+
+impl SpuPool {
+    /// create stream to the spu, reusing existing connection if there is one
+    async fn create_stream_to_spu<R>(
+        &self,
+        spu_id: SpuId,
+        request: R,
+        version: i16,
+    ) -> Result<AsyncResponse<R>, FluvioError>
+    where
+        R: Request + Sync + Send,
+    {
         let mut client_lock = self.spu_clients.lock().await;
 
-        if let Some(spu_socket) = client_lock.get_mut(&leader_id) {
+        if let Some(spu_socket) = client_lock.get_mut(&spu_id) {
             return spu_socket
                 .create_stream_with_version(request, version)
                 .await
                 .map_err(|err| err.into());
         }
 
-        let mut spu_socket = self.connect_to_leader(leader_id).await?;
+        let mut spu_socket = self.connect_to_leader(spu_id, false).await?;
         let stream = spu_socket
             .create_stream_with_version(request, version)
             .await?;
-        client_lock.insert(leader_id, spu_socket);
+        client_lock.insert(spu_id, spu_socket);
 
         Ok(stream)
     }
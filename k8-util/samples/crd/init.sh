@@ -7,3 +7,4 @@ kubectl apply -f ${DATA_DIR}/crd_cluster.yaml
 kubectl apply -f ${DATA_DIR}/crd_partition.yaml
 kubectl apply -f ${DATA_DIR}/crd_topic.yaml
 kubectl apply -f ${DATA_DIR}/crd_mirror.yaml
+kubectl apply -f ${DATA_DIR}/crd_acl.yaml